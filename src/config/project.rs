@@ -0,0 +1,319 @@
+//! Project-level configuration file
+//!
+//! Teams can commit a `version-lsp.json`, `version-lsp.toml` or
+//! `.version-lsp.toml` to the project so every editor uses the same registry
+//! settings. The file uses the same schema as the `version-lsp` section of
+//! the LSP client configuration. Only the keys the file sets override the
+//! client configuration.
+//!
+//! The file is committed with the repository, so it can never supply or
+//! redirect credentials: auth tokens and npm scope registries only come from
+//! the client, and the client's crates token is dropped when the project
+//! points the crates registry somewhere else.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::config::{
+    CacheConfig, CratesRegistryConfig, DockerRegistryConfig, LspConfig, NpmRegistryConfig,
+    RegistriesConfig, RegistryConfig,
+};
+
+/// Config file names, in lookup order within a directory
+pub const PROJECT_CONFIG_FILE_NAMES: [&str; 3] =
+    ["version-lsp.json", "version-lsp.toml", ".version-lsp.toml"];
+
+/// Settings a project config file can override
+///
+/// Every key is optional so that keys missing from the file keep the client's
+/// value instead of falling back to the defaults. Editor-facing settings
+/// (diagnostic severity, content detection, inlay hints, ...) are not part of
+/// it and always come from the client.
+#[derive(Clone, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProjectConfig {
+    pub cache: ProjectCacheConfig,
+    pub registries: ProjectRegistriesConfig,
+    pub ignore_prerelease: Option<bool>,
+    pub show_yanked: Option<bool>,
+    pub workspace_scan_depth: Option<usize>,
+}
+
+impl ProjectConfig {
+    /// Override the keys set in the project file
+    pub fn apply(&self, config: &mut LspConfig) {
+        self.cache.apply(&mut config.cache);
+        self.registries.apply(&mut config.registries);
+        set(&mut config.ignore_prerelease, &self.ignore_prerelease);
+        set(&mut config.show_yanked, &self.show_yanked);
+        set(&mut config.workspace_scan_depth, &self.workspace_scan_depth);
+    }
+}
+
+/// Project overrides of [`CacheConfig`]
+#[derive(Clone, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProjectCacheConfig {
+    pub refresh_interval: Option<i64>,
+    pub not_found_ttl: Option<i64>,
+}
+
+impl ProjectCacheConfig {
+    fn apply(&self, config: &mut CacheConfig) {
+        set(&mut config.refresh_interval, &self.refresh_interval);
+        set(&mut config.not_found_ttl, &self.not_found_ttl);
+    }
+}
+
+/// Project overrides of [`RegistriesConfig`]
+#[derive(Clone, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ProjectRegistriesConfig {
+    pub npm: ProjectNpmRegistryConfig,
+    pub crates: ProjectCratesRegistryConfig,
+    #[serde(rename = "goProxy")]
+    pub go_proxy: ProjectRegistryConfig,
+    pub github: ProjectRegistryConfig,
+    #[serde(rename = "pnpmCatalog")]
+    pub pnpm_catalog: ProjectRegistryConfig,
+    pub jsr: ProjectRegistryConfig,
+    pub pypi: ProjectRegistryConfig,
+    pub docker: ProjectDockerRegistryConfig,
+    pub conda: ProjectRegistryConfig,
+    pub node: ProjectRegistryConfig,
+    #[serde(rename = "goToolchain")]
+    pub go_toolchain: ProjectRegistryConfig,
+    #[serde(rename = "rustToolchain")]
+    pub rust_toolchain: ProjectRegistryConfig,
+    pub rubygems: ProjectRegistryConfig,
+    #[serde(rename = "pub")]
+    pub pub_dev: ProjectRegistryConfig,
+}
+
+impl ProjectRegistriesConfig {
+    fn apply(&self, config: &mut RegistriesConfig) {
+        self.npm.apply(&mut config.npm);
+        self.crates.apply(&mut config.crates);
+        self.go_proxy.apply(&mut config.go_proxy);
+        self.github.apply(&mut config.github);
+        self.pnpm_catalog.apply(&mut config.pnpm_catalog);
+        self.jsr.apply(&mut config.jsr);
+        self.pypi.apply(&mut config.pypi);
+        self.docker.apply(&mut config.docker);
+        self.conda.apply(&mut config.conda);
+        self.node.apply(&mut config.node);
+        self.go_toolchain.apply(&mut config.go_toolchain);
+        self.rust_toolchain.apply(&mut config.rust_toolchain);
+        self.rubygems.apply(&mut config.rubygems);
+        self.pub_dev.apply(&mut config.pub_dev);
+    }
+}
+
+/// Project overrides of [`RegistryConfig`]
+#[derive(Clone, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ProjectRegistryConfig {
+    pub enabled: Option<bool>,
+    pub url: Option<String>,
+}
+
+impl ProjectRegistryConfig {
+    fn apply(&self, config: &mut RegistryConfig) {
+        set(&mut config.enabled, &self.enabled);
+        set_some(&mut config.url, &self.url);
+    }
+}
+
+/// Project overrides of [`CratesRegistryConfig`]
+///
+/// There is no `authToken`: the token comes from the client only.
+#[derive(Clone, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProjectCratesRegistryConfig {
+    pub enabled: Option<bool>,
+    pub url: Option<String>,
+    pub fallback_urls: Option<Vec<String>>,
+}
+
+impl ProjectCratesRegistryConfig {
+    fn apply(&self, config: &mut CratesRegistryConfig) {
+        set(&mut config.enabled, &self.enabled);
+        // The client's token belongs to the client's registry; never send it
+        // to a host picked by the repository
+        if self.url.is_some() && self.url != config.url {
+            config.auth_token = None;
+        }
+        set_some(&mut config.url, &self.url);
+        set(&mut config.fallback_urls, &self.fallback_urls);
+    }
+}
+
+/// Project overrides of [`NpmRegistryConfig`]
+///
+/// There are no `scopeRegistries`: they carry auth tokens, so they come from
+/// the client only.
+#[derive(Clone, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProjectNpmRegistryConfig {
+    pub enabled: Option<bool>,
+    pub url: Option<String>,
+}
+
+impl ProjectNpmRegistryConfig {
+    fn apply(&self, config: &mut NpmRegistryConfig) {
+        set(&mut config.enabled, &self.enabled);
+        set_some(&mut config.url, &self.url);
+    }
+}
+
+/// Project overrides of [`DockerRegistryConfig`]
+#[derive(Clone, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProjectDockerRegistryConfig {
+    pub enabled: Option<bool>,
+    pub docker_hub_registry_url: Option<String>,
+    pub docker_hub_auth_url: Option<String>,
+    pub ghcr_registry_url: Option<String>,
+    pub ghcr_auth_url: Option<String>,
+}
+
+impl ProjectDockerRegistryConfig {
+    fn apply(&self, config: &mut DockerRegistryConfig) {
+        set(&mut config.enabled, &self.enabled);
+        set_some(
+            &mut config.docker_hub_registry_url,
+            &self.docker_hub_registry_url,
+        );
+        set_some(&mut config.docker_hub_auth_url, &self.docker_hub_auth_url);
+        set_some(&mut config.ghcr_registry_url, &self.ghcr_registry_url);
+        set_some(&mut config.ghcr_auth_url, &self.ghcr_auth_url);
+    }
+}
+
+/// Override `target` when the project file sets the key
+fn set<T: Clone>(target: &mut T, value: &Option<T>) {
+    if let Some(value) = value {
+        *target = value.clone();
+    }
+}
+
+/// Override an optional `target` when the project file sets the key
+fn set_some<T: Clone>(target: &mut Option<T>, value: &Option<T>) {
+    if value.is_some() {
+        *target = value.clone();
+    }
+}
+
+/// Find and load the project config, walking up from `workspace_root`
+///
+/// Returns None when no config file is found or the nearest one is invalid.
+pub fn load_project_config(workspace_root: &Path) -> Option<ProjectConfig> {
+    let path = find_project_config(workspace_root)?;
+    let content = std::fs::read_to_string(&path)
+        .inspect_err(|e| warn!("Failed to read project config {:?}: {}", path, e))
+        .ok()?;
+
+    let config = if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str::<ProjectConfig>(&content).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str::<ProjectConfig>(&content).map_err(|e| e.to_string())
+    };
+
+    config
+        .inspect(|_| info!("Loaded project config from {:?}", path))
+        .inspect_err(|e| warn!("Failed to parse project config {:?}: {}", path, e))
+        .ok()
+}
+
+/// Find the nearest config file in `start` or one of its ancestors
+fn find_project_config(start: &Path) -> Option<PathBuf> {
+    start.ancestors().find_map(|dir| {
+        PROJECT_CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn load_project_config_reads_json_file() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("version-lsp.json"),
+            r#"{ "registries": { "npm": { "url": "https://npm.example.com" } } }"#,
+        )
+        .unwrap();
+
+        let config = load_project_config(temp_dir.path()).unwrap();
+
+        assert_eq!(
+            config.registries.npm.url.as_deref(),
+            Some("https://npm.example.com")
+        );
+        assert_eq!(config.registries.npm.enabled, None);
+    }
+
+    #[test]
+    fn load_project_config_reads_toml_file_in_parent_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_root = temp_dir.path().join("packages").join("app");
+        std::fs::create_dir_all(&workspace_root).unwrap();
+        std::fs::write(
+            temp_dir.path().join(".version-lsp.toml"),
+            r#"
+ignorePrerelease = false
+
+[registries.crates]
+enabled = false
+"#,
+        )
+        .unwrap();
+
+        let config = load_project_config(&workspace_root).unwrap();
+
+        assert_eq!(config.ignore_prerelease, Some(false));
+        assert_eq!(config.registries.crates.enabled, Some(false));
+        assert_eq!(config.registries.npm.enabled, None);
+    }
+
+    #[test]
+    fn load_project_config_prefers_nearest_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_root = temp_dir.path().join("app");
+        std::fs::create_dir_all(&workspace_root).unwrap();
+        std::fs::write(
+            temp_dir.path().join("version-lsp.json"),
+            r#"{ "registries": { "npm": { "enabled": false } } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            workspace_root.join("version-lsp.toml"),
+            "[registries.jsr]\nenabled = false\n",
+        )
+        .unwrap();
+
+        let config = load_project_config(&workspace_root).unwrap();
+
+        assert_eq!(config.registries.npm.enabled, None);
+        assert_eq!(config.registries.jsr.enabled, Some(false));
+    }
+
+    #[test]
+    fn load_project_config_returns_none_for_invalid_file() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("version-lsp.json"),
+            r#"{ "registries": { "npm": { "enabled": "yes" } } }"#,
+        )
+        .unwrap();
+
+        assert!(load_project_config(temp_dir.path()).is_none());
+    }
+}
@@ -1,35 +1,148 @@
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer};
 use tracing::{debug, error, info, warn};
 
-use crate::config::{LspConfig, data_dir, db_path};
+use crate::config::project::{ProjectConfig, load_project_config};
+use crate::config::{FETCH_TIMEOUT_MS, LspConfig, data_dir, db_path};
+use crate::lsp::catalog::{
+    catalog_entry_location, catalog_name_of, catalog_references, find_pnpm_workspace,
+};
 use crate::lsp::code_action::{
-    PackageIndex, generate_constraint_code_actions, generate_pypi_constraint_code_actions,
-    generate_upgrade_code_actions, generate_upgrade_code_actions_with_sha,
+    PackageIndex, generate_bulk_update_code_action, generate_constraint_code_actions,
+    generate_pin_code_action, generate_pypi_constraint_code_actions, generate_update_all_action,
+    generate_upgrade_code_actions, generate_upgrade_code_actions_with_sha, version_range,
+};
+use crate::lsp::completion::{CompletionContext, generate_completions};
+use crate::lsp::diagnostics::{
+    PeerDependencyConsistencyChecker, generate_diagnostics, generate_go_sum_diagnostics,
+    generate_own_version_diagnostic,
+};
+use crate::lsp::folding_range::dependency_folding_ranges;
+use crate::lsp::hover::package_hover;
+use crate::lsp::inlay_hints::generate_inlay_hints;
+use crate::lsp::refresh::{
+    fetch_missing_packages, fetch_stale_packages, force_fetch_packages, refresh_packages,
 };
-use crate::lsp::diagnostics::generate_diagnostics;
-use crate::lsp::refresh::{fetch_missing_packages, refresh_packages};
 use crate::lsp::resolver::{PackageResolver, create_resolvers};
-use crate::parser::types::{PackageInfo, RegistryType, detect_parser_type};
-use crate::version::cache::Cache;
-use crate::version::checker::VersionStorer;
+use crate::lsp::symbol::package_symbols;
+use crate::lsp::workspace::{find_manifests, pnpm_workspace_manifests};
+use crate::parser::go_sum::GoSumParser;
+use crate::parser::package_json::PackageJsonParser;
+use crate::parser::traits::{ParseOutput, Parser};
+use crate::parser::types::{
+    DocumentMetadata, ExtraInfo, PackageInfo, RegistryType, detect_parser_type,
+    detect_parser_type_from_content, is_binary_lock_file, is_lock_file,
+};
+use crate::version::cache::{Cache, PackageId};
+use crate::version::checker::{VersionStorer, compare_version};
+use crate::version::circuit_breaker::CircuitBreaker;
+use crate::version::matcher::VersionMatcher;
+use crate::version::registries::github::TagShaFetcher;
 use crate::version::registry::Registry;
 
+/// `workspace/executeCommand` command returning cached package counts per registry
+pub const CACHE_STATS_COMMAND: &str = "version-lsp/cacheStats";
+
+/// `workspace/executeCommand` command that refetches the packages of an open
+/// document, then republishes its diagnostics
+pub const DIAGNOSE_DOCUMENT_COMMAND: &str = "version-lsp/diagnoseDocument";
+
+/// Matcher and optional SHA fetcher used to build code actions for a package
+type CodeActionResolver = (Arc<dyn VersionMatcher>, Option<Arc<dyn TagShaFetcher>>);
+
+/// Parser of a document plus the matcher and registry of every enabled
+/// registry, snapshotted so no lock is held across awaits
+type DocumentResolvers = (
+    Arc<dyn Parser>,
+    HashMap<RegistryType, Arc<dyn VersionMatcher>>,
+    HashMap<RegistryType, Arc<dyn Registry>>,
+);
+
+/// A parsed document and everything needed to compute its diagnostics,
+/// snapshotted so background tasks can republish them without `&self`
+struct DocumentDiagnostics {
+    parser: Arc<dyn Parser>,
+    matchers: HashMap<RegistryType, Arc<dyn VersionMatcher>>,
+    registries: HashMap<RegistryType, Arc<dyn Registry>>,
+    content: String,
+    /// The package.json's own name and version, when `checkOwnVersion` is on
+    own_package: Option<PackageInfo>,
+    /// Packages to fetch (dependencies and the own package), grouped by
+    /// registry because each registry is fetched separately
+    packages_by_registry: HashMap<RegistryType, Vec<PackageInfo>>,
+    /// go.sum and peer dependency diagnostics, which don't depend on
+    /// registry data
+    local_diagnostics: Vec<Diagnostic>,
+    outdated_severity: DiagnosticSeverity,
+    show_yanked: bool,
+}
+
+impl DocumentDiagnostics {
+    /// Diagnostics of the document from the versions cached in `storer`
+    fn generate<S: VersionStorer>(&self, storer: &S) -> Vec<Diagnostic> {
+        let mut diagnostics = generate_diagnostics(
+            &*self.parser,
+            &self.matchers,
+            storer,
+            &self.content,
+            self.outdated_severity,
+            self.show_yanked,
+        );
+        diagnostics.extend(
+            self.own_package
+                .as_ref()
+                .and_then(|package| generate_own_version_diagnostic(package, storer)),
+        );
+        diagnostics.extend(self.local_diagnostics.iter().cloned());
+        diagnostics
+    }
+}
+
+/// Arguments of [`DIAGNOSE_DOCUMENT_COMMAND`]
+#[derive(Debug, serde::Deserialize)]
+struct DiagnoseDocumentArguments {
+    uri: Url,
+}
+
 /// Cached parsed packages for a document
 struct DocumentCache {
+    registry_type: Option<RegistryType>,
     packages: Vec<PackageInfo>,
+    /// Document-level information shown on hover (e.g. an ESM package.json)
+    metadata: DocumentMetadata,
+    /// Latest text of the document, for re-checking on request
+    content: String,
 }
 
 pub struct Backend<S: VersionStorer> {
     client: Client,
     storer: Option<Arc<S>>,
     config: Arc<RwLock<LspConfig>>,
+    /// Config loaded from a `version-lsp.json`/`.toml` file in the workspace
+    project_config: Arc<RwLock<Option<ProjectConfig>>>,
     resolvers: Arc<RwLock<HashMap<RegistryType, PackageResolver>>>,
     documents: Arc<RwLock<HashMap<Url, DocumentCache>>>,
+    /// LSP version of each open document, from the latest didOpen/didChange.
+    /// Background fetch tasks drop their diagnostics once the document has
+    /// moved on to a newer version.
+    document_versions: Arc<Mutex<HashMap<Url, i32>>>,
+    /// Root directory of the workspace, from the initialize request
+    workspace_root: Arc<RwLock<Option<PathBuf>>>,
+    /// Skips fetches to registries that keep failing. Replaced when its
+    /// threshold or cooldown changes, which closes every circuit.
+    circuit_breaker: Arc<RwLock<CircuitBreaker>>,
+    /// Limits background fetch tasks running at once to `maxConcurrentFetches`,
+    /// so opening many documents doesn't flood the registries. Replaced when
+    /// the limit changes; tasks holding a permit of the old one still finish.
+    fetch_semaphore: Arc<RwLock<Arc<Semaphore>>>,
 }
 
 impl Backend<Cache> {
@@ -37,12 +150,19 @@ impl Backend<Cache> {
         let config = LspConfig::default();
         let storer = Self::initialize_storer(&config);
         let resolvers = create_resolvers(&config);
+        let fetch_semaphore = Self::new_fetch_semaphore(&config);
+        let circuit_breaker = Self::new_circuit_breaker(&config);
         Self {
             client,
             storer,
             config: Arc::new(RwLock::new(config)),
+            project_config: Arc::new(RwLock::new(None)),
             resolvers: Arc::new(RwLock::new(resolvers)),
             documents: Arc::new(RwLock::new(HashMap::new())),
+            document_versions: Arc::new(Mutex::new(HashMap::new())),
+            workspace_root: Arc::new(RwLock::new(None)),
+            circuit_breaker: Arc::new(RwLock::new(circuit_breaker)),
+            fetch_semaphore: Arc::new(RwLock::new(fetch_semaphore)),
         }
     }
 
@@ -63,7 +183,9 @@ impl Backend<Cache> {
         ) {
             Ok(cache) => {
                 info!("Cache initialized at {:?}", db_path);
-                Some(Arc::new(cache))
+                Some(Arc::new(
+                    cache.with_not_found_ttl(config.cache.not_found_ttl),
+                ))
             }
             Err(e) => {
                 error!("Failed to initialize cache: {}", e);
@@ -80,25 +202,78 @@ impl<S: VersionStorer> Backend<S> {
         storer: Arc<S>,
         resolvers: HashMap<RegistryType, PackageResolver>,
     ) -> Self {
+        let config = LspConfig::default();
+        let fetch_semaphore = Self::new_fetch_semaphore(&config);
+        let circuit_breaker = Self::new_circuit_breaker(&config);
         Self {
             client,
             storer: Some(storer),
-            config: Arc::new(RwLock::new(LspConfig::default())),
+            config: Arc::new(RwLock::new(config)),
+            project_config: Arc::new(RwLock::new(None)),
             resolvers: Arc::new(RwLock::new(resolvers)),
             documents: Arc::new(RwLock::new(HashMap::new())),
+            document_versions: Arc::new(Mutex::new(HashMap::new())),
+            workspace_root: Arc::new(RwLock::new(None)),
+            circuit_breaker: Arc::new(RwLock::new(circuit_breaker)),
+            fetch_semaphore: Arc::new(RwLock::new(fetch_semaphore)),
         }
     }
 
+    fn new_fetch_semaphore(config: &LspConfig) -> Arc<Semaphore> {
+        Arc::new(Semaphore::new(config.max_concurrent_fetches.max(1)))
+    }
+
+    fn new_circuit_breaker(config: &LspConfig) -> CircuitBreaker {
+        CircuitBreaker::new(
+            config.circuit_breaker_threshold,
+            Duration::from_millis(config.circuit_breaker_cooldown_ms),
+        )
+    }
+
+    /// Circuit breaker that background fetches report to
+    fn circuit_breaker(&self) -> CircuitBreaker {
+        self.circuit_breaker
+            .read()
+            .expect("circuit breaker lock poisoned")
+            .clone()
+    }
+
+    /// Semaphore that background fetch tasks acquire a permit from
+    fn fetch_semaphore(&self) -> Arc<Semaphore> {
+        self.fetch_semaphore
+            .read()
+            .expect("fetch semaphore lock poisoned")
+            .clone()
+    }
+
+    /// Record the LSP version of an opened or changed document
+    fn set_document_version(&self, uri: &Url, version: i32) {
+        self.document_versions
+            .lock()
+            .expect("document versions lock poisoned")
+            .insert(uri.clone(), version);
+    }
+
+    /// Latest LSP version of an open document
+    fn document_version(&self, uri: &Url) -> Option<i32> {
+        self.document_versions
+            .lock()
+            .expect("document versions lock poisoned")
+            .get(uri)
+            .copied()
+    }
+
     /// Parse document and cache packages
     fn cache_document(&self, uri: &Url, content: &str) {
         let uri_str = uri.as_str();
+        let registry_type = self.detect_registry_type(uri_str, content);
         let resolvers = self.resolvers.read().expect("resolvers lock poisoned");
-        let packages = detect_parser_type(uri_str)
+        let ParseOutput { packages, metadata } = registry_type
             .and_then(|registry_type| resolvers.get(&registry_type))
             .map(|resolver| {
                 resolver
-                    .parser()
-                    .parse(content)
+                    .parser_for(uri_str)
+                    .parse_document(content)
                     .inspect_err(|e| warn!("Failed to parse {}: {}", uri_str, e))
                     .unwrap_or_default()
             })
@@ -106,7 +281,27 @@ impl<S: VersionStorer> Backend<S> {
         drop(resolvers);
 
         let mut docs = self.documents.write().expect("documents lock poisoned");
-        docs.insert(uri.clone(), DocumentCache { packages });
+        docs.insert(
+            uri.clone(),
+            DocumentCache {
+                registry_type,
+                packages,
+                metadata,
+                content: content.to_string(),
+            },
+        );
+    }
+
+    /// Detect the registry type of a document, falling back to a content
+    /// heuristic when `detectByContent` is enabled and the path is not recognized
+    fn detect_registry_type(&self, uri: &str, content: &str) -> Option<RegistryType> {
+        detect_parser_type(uri).or_else(|| {
+            let config = self.config.read().expect("config lock poisoned");
+            config
+                .detect_by_content
+                .then(|| detect_parser_type_from_content(uri, content))
+                .flatten()
+        })
     }
 
     /// Check if a registry is enabled in the configuration
@@ -121,14 +316,166 @@ impl<S: VersionStorer> Backend<S> {
             RegistryType::Jsr => config.registries.jsr.enabled,
             RegistryType::PyPI => config.registries.pypi.enabled,
             RegistryType::Docker => config.registries.docker.enabled,
+            RegistryType::Conda => config.registries.conda.enabled,
+            RegistryType::NodeRelease => config.registries.node.enabled,
+            RegistryType::GoToolchain => config.registries.go_toolchain.enabled,
+            RegistryType::RustToolchain => config.registries.rust_toolchain.enabled,
+            RegistryType::RubyGems => config.registries.rubygems.enabled,
+            RegistryType::Pub => config.registries.pub_dev.enabled,
+        }
+    }
+
+    /// Whether versions the registry yanked count as existing (`showYanked`)
+    fn show_yanked(&self) -> bool {
+        self.config
+            .read()
+            .expect("config lock poisoned")
+            .show_yanked
+    }
+
+    /// Whether upgrade actions offer pre-releases (`includePreRelease`)
+    fn include_pre_release(&self) -> bool {
+        self.config
+            .read()
+            .expect("config lock poisoned")
+            .include_pre_release
+    }
+
+    /// Severity for "update available" diagnostics on the document at `uri`
+    ///
+    /// rust-toolchain.toml pins are usually kept on purpose, so a newer
+    /// toolchain is only reported as information
+    fn outdated_severity(&self, uri: &str) -> DiagnosticSeverity {
+        if is_lock_file(uri) {
+            let config = self.config.read().expect("config lock poisoned");
+            config.lock_file_severity.into()
+        } else if uri.ends_with("/rust-toolchain.toml") {
+            DiagnosticSeverity::INFORMATION
+        } else {
+            DiagnosticSeverity::WARNING
+        }
+    }
+
+    /// Diagnostics for package.json devDependencies outside the package's
+    /// own peerDependencies range, when `checkPeerDependencies` is enabled
+    fn peer_dependency_diagnostics(&self, uri: &Url, content: &str) -> Vec<Diagnostic> {
+        let enabled = self
+            .config
+            .read()
+            .expect("config lock poisoned")
+            .check_peer_dependencies;
+        if !enabled || !uri.path().ends_with("/package.json") {
+            return Vec::new();
         }
+        let parser = PackageJsonParser::new();
+        PeerDependencyConsistencyChecker.check(
+            &parser.parse_dependency_field(content, "devDependencies"),
+            &parser.parse_dependency_field(content, "peerDependencies"),
+        )
+    }
+
+    /// The package.json package itself, whose own `version` is checked
+    /// against npm when `checkOwnVersion` is enabled
+    ///
+    /// Private packages are never published, so they are skipped.
+    fn own_package(&self, uri: &Url, content: &str) -> Option<PackageInfo> {
+        let enabled = self
+            .config
+            .read()
+            .expect("config lock poisoned")
+            .check_own_version;
+        if !enabled
+            || !uri.path().ends_with("/package.json")
+            || !self.is_registry_enabled(RegistryType::Npm)
+        {
+            return None;
+        }
+        PackageJsonParser::new()
+            .parse_self_package(content)
+            .filter(|package| {
+                !matches!(
+                    package.extra_info,
+                    Some(ExtraInfo::PackageJsonMeta { private: true, .. })
+                )
+            })
+    }
+
+    /// Replace the active configuration, hand the not-found TTL to the storer
+    /// and rebuild resolvers so URL overrides take effect on subsequent fetches
+    fn apply_config(
+        storer: Option<&Arc<S>>,
+        config: &RwLock<LspConfig>,
+        resolvers: &RwLock<HashMap<RegistryType, PackageResolver>>,
+        fetch_semaphore: &RwLock<Arc<Semaphore>>,
+        circuit_breaker: &RwLock<CircuitBreaker>,
+        new_config: LspConfig,
+    ) {
+        let new_resolvers = create_resolvers(&new_config);
+        if let Some(storer) = storer {
+            storer.set_not_found_ttl(new_config.cache.not_found_ttl);
+        }
+
+        let mut cfg = config.write().expect("config lock poisoned");
+        if cfg.max_concurrent_fetches != new_config.max_concurrent_fetches {
+            *fetch_semaphore
+                .write()
+                .expect("fetch semaphore lock poisoned") = Self::new_fetch_semaphore(&new_config);
+        }
+        if cfg.circuit_breaker_threshold != new_config.circuit_breaker_threshold
+            || cfg.circuit_breaker_cooldown_ms != new_config.circuit_breaker_cooldown_ms
+        {
+            *circuit_breaker
+                .write()
+                .expect("circuit breaker lock poisoned") = Self::new_circuit_breaker(&new_config);
+        }
+        *cfg = new_config;
+        drop(cfg);
+
+        let mut res = resolvers.write().expect("resolvers lock poisoned");
+        *res = new_resolvers;
+        debug!("Resolvers rebuilt with new configuration");
+    }
+
+    /// Load the project config file for the workspace and apply it on top of
+    /// the current configuration
+    fn load_project_config(&self, workspace_root: &Path) {
+        let Some(project_config) = load_project_config(workspace_root) else {
+            return;
+        };
+
+        let current = self.config.read().expect("config lock poisoned").clone();
+        let new_config = current.with_project_config(&project_config);
+        info!(
+            "Configuration updated from project config: {:?}",
+            new_config
+        );
+
+        *self
+            .project_config
+            .write()
+            .expect("project config lock poisoned") = Some(project_config);
+        Self::apply_config(
+            self.storer.as_ref(),
+            &self.config,
+            &self.resolvers,
+            &self.fetch_semaphore,
+            &self.circuit_breaker,
+            new_config,
+        );
     }
 
     /// Spawn background task to fetch configuration from client
-    fn spawn_fetch_configuration(&self) {
+    ///
+    /// The returned handle completes once the configuration was applied (or
+    /// the request failed).
+    fn spawn_fetch_configuration(&self) -> JoinHandle<()> {
         let client = self.client.clone();
+        let storer = self.storer.clone();
         let config = self.config.clone();
+        let project_config = self.project_config.clone();
         let resolvers = self.resolvers.clone();
+        let fetch_semaphore = self.fetch_semaphore.clone();
+        let circuit_breaker = self.circuit_breaker.clone();
 
         tokio::spawn(async move {
             let items = vec![ConfigurationItem {
@@ -153,19 +500,25 @@ impl<S: VersionStorer> Backend<S> {
                                 }
                             }
                         };
+                        // The project config file wins for registry settings
+                        let new_config = match project_config
+                            .read()
+                            .expect("project config lock poisoned")
+                            .as_ref()
+                        {
+                            Some(project) => new_config.with_project_config(project),
+                            None => new_config,
+                        };
                         info!("Configuration updated: {:?}", new_config);
 
-                        // Rebuild resolvers from the new config so URL
-                        // overrides take effect on subsequent fetches.
-                        let new_resolvers = create_resolvers(&new_config);
-
-                        let mut cfg = config.write().expect("config lock poisoned");
-                        *cfg = new_config;
-                        drop(cfg);
-
-                        let mut res = resolvers.write().expect("resolvers lock poisoned");
-                        *res = new_resolvers;
-                        debug!("Resolvers rebuilt with new configuration");
+                        Self::apply_config(
+                            storer.as_ref(),
+                            &config,
+                            &resolvers,
+                            &fetch_semaphore,
+                            &circuit_breaker,
+                            new_config,
+                        );
                     }
                 }
                 Err(e) => {
@@ -173,7 +526,38 @@ impl<S: VersionStorer> Backend<S> {
                     debug!("workspace/configuration request failed: {}", e);
                 }
             }
-        });
+        })
+    }
+
+    /// Re-check every open document, e.g. after the configuration changed
+    ///
+    /// Documents whose registry is no longer enabled get their diagnostics
+    /// cleared, since [`Self::check_and_publish_diagnostics`] skips them.
+    async fn republish_open_documents(&self) {
+        let documents: Vec<(Url, String)> = self
+            .documents
+            .read()
+            .expect("documents lock poisoned")
+            .iter()
+            .map(|(uri, doc)| (uri.clone(), doc.content.clone()))
+            .collect();
+
+        for (uri, content) in documents {
+            let Some(version) = self.document_version(&uri) else {
+                continue;
+            };
+            // Re-parse, as `detectByContent` may have changed the registry type
+            self.cache_document(&uri, &content);
+
+            if self.document_resolvers(uri.as_str(), &content).is_none() {
+                self.client
+                    .publish_diagnostics(uri, Vec::new(), Some(version))
+                    .await;
+                continue;
+            }
+            self.check_and_publish_diagnostics(uri, content, version)
+                .await;
+        }
     }
 
     pub fn server_capabilities() -> ServerCapabilities {
@@ -182,10 +566,30 @@ impl<S: VersionStorer> Backend<S> {
                 TextDocumentSyncOptions {
                     open_close: Some(true),
                     change: Some(TextDocumentSyncKind::FULL),
+                    save: Some(TextDocumentSyncSaveOptions::Supported(true)),
                     ..Default::default()
                 },
             )),
             code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+            hover_provider: Some(HoverProviderCapability::Simple(true)),
+            completion_provider: Some(CompletionOptions::default()),
+            inlay_hint_provider: Some(OneOf::Left(true)),
+            folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+            workspace_symbol_provider: Some(OneOf::Left(true)),
+            linked_editing_range_provider: Some(LinkedEditingRangeServerCapabilities::Simple(true)),
+            definition_provider: Some(OneOf::Left(true)),
+            references_provider: Some(OneOf::Left(true)),
+            rename_provider: Some(OneOf::Right(RenameOptions {
+                prepare_provider: Some(true),
+                work_done_progress_options: Default::default(),
+            })),
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: vec![
+                    CACHE_STATS_COMMAND.to_string(),
+                    DIAGNOSE_DOCUMENT_COMMAND.to_string(),
+                ],
+                ..Default::default()
+            }),
             ..Default::default()
         }
     }
@@ -205,6 +609,7 @@ impl<S: VersionStorer> Backend<S> {
             .iter()
             .map(|(k, v)| (*k, v.registry().clone()))
             .collect();
+        let breaker = self.circuit_breaker();
 
         tokio::spawn(async move {
             let Some(packages) = storer
@@ -234,19 +639,22 @@ impl<S: VersionStorer> Backend<S> {
             // Refresh packages for each registry type
             for (registry_type, packages) in packages_by_registry {
                 if let Some(registry) = registries.get(&registry_type) {
-                    refresh_packages(&*storer, &**registry, packages).await;
+                    refresh_packages(&storer, &**registry, &breaker, packages, false).await;
                 }
             }
         });
     }
 
-    async fn check_and_publish_diagnostics(&self, uri: Url, content: String) {
-        let uri_str = uri.as_str();
-        debug!("Checking diagnostics for URI: {}", uri_str);
-
-        let Some(registry_type) = detect_parser_type(uri_str) else {
+    /// Snapshot the resolvers needed to check the document at `uri_str`
+    ///
+    /// Returns None when the document type is not recognized or its registry
+    /// is disabled. A document may contain packages of several registries
+    /// (e.g. pip requirements in environment.yml), so the matcher and registry
+    /// of every enabled registry are included.
+    fn document_resolvers(&self, uri_str: &str, content: &str) -> Option<DocumentResolvers> {
+        let Some(registry_type) = self.detect_registry_type(uri_str, content) else {
             debug!("No parser type detected for URI: {}", uri_str);
-            return;
+            return None;
         };
         debug!("Detected registry type: {:?}", registry_type);
 
@@ -256,22 +664,84 @@ impl<S: VersionStorer> Backend<S> {
                 "Registry {:?} is disabled, skipping diagnostics",
                 registry_type
             );
-            return;
+            return None;
         }
 
-        // Snapshot parser/matcher/registry from the resolver under a brief
-        // read lock so we don't hold the lock across awaits or `tokio::spawn`.
-        let (parser, matcher, registry) = {
-            let resolvers = self.resolvers.read().expect("resolvers lock poisoned");
-            let Some(resolver) = resolvers.get(&registry_type) else {
-                debug!("No resolver found for registry type: {:?}", registry_type);
-                return;
-            };
-            (
-                resolver.parser().clone(),
-                resolver.matcher().clone(),
-                resolver.registry().clone(),
-            )
+        // Snapshot under a brief read lock, so we don't hold the lock across
+        // awaits or `tokio::spawn`
+        let resolvers = self.resolvers.read().expect("resolvers lock poisoned");
+        let Some(resolver) = resolvers.get(&registry_type) else {
+            debug!("No resolver found for registry type: {:?}", registry_type);
+            return None;
+        };
+        let enabled: Vec<_> = resolvers
+            .iter()
+            .filter(|(registry_type, _)| self.is_registry_enabled(**registry_type))
+            .collect();
+        let matchers: HashMap<RegistryType, Arc<dyn VersionMatcher>> = enabled
+            .iter()
+            .map(|(registry_type, resolver)| (**registry_type, resolver.matcher().clone()))
+            .collect();
+        let registries: HashMap<RegistryType, Arc<dyn Registry>> = enabled
+            .iter()
+            .map(|(registry_type, resolver)| (**registry_type, resolver.registry().clone()))
+            .collect();
+        Some((resolver.parser_for(uri_str).clone(), matchers, registries))
+    }
+
+    /// Parse the document at `uri` and snapshot what is needed to compute
+    /// its diagnostics
+    ///
+    /// Returns None when the document type is not recognized or its registry
+    /// is disabled.
+    fn document_diagnostics(&self, uri: &Url, content: String) -> Option<DocumentDiagnostics> {
+        let uri_str = uri.as_str();
+        let (parser, matchers, registries) = self.document_resolvers(uri_str, &content)?;
+
+        let packages = parser
+            .parse(&content)
+            .inspect_err(|e| warn!("Failed to parse {}: {}", uri_str, e))
+            .unwrap_or_default();
+        debug!("Parsed {} packages: {:?}", packages.len(), packages);
+        let own_package = self.own_package(uri, &content);
+        let packages_by_registry = group_packages_by_registry(
+            &packages
+                .iter()
+                .chain(&own_package)
+                .cloned()
+                .collect::<Vec<_>>(),
+            &registries,
+        );
+
+        let mut local_diagnostics = read_sibling_go_sum(uri)
+            .map(|go_sum| generate_go_sum_diagnostics(&packages, &go_sum))
+            .unwrap_or_default();
+        local_diagnostics.extend(self.peer_dependency_diagnostics(uri, &content));
+
+        Some(DocumentDiagnostics {
+            parser,
+            matchers,
+            registries,
+            own_package,
+            packages_by_registry,
+            local_diagnostics,
+            outdated_severity: self.outdated_severity(uri_str),
+            show_yanked: self.show_yanked(),
+            content,
+        })
+    }
+
+    /// Publish diagnostics for `version` of a document, then fetch missing
+    /// and stale packages in the background and republish
+    ///
+    /// The republish is dropped when the document changed while fetching, so
+    /// diagnostics of an old version never replace those of a newer one.
+    async fn check_and_publish_diagnostics(&self, uri: Url, content: String, version: i32) {
+        let uri_str = uri.as_str();
+        debug!("Checking diagnostics for URI: {}", uri_str);
+
+        let Some(document) = self.document_diagnostics(&uri, content) else {
+            return;
         };
 
         let Some(storer) = &self.storer else {
@@ -284,14 +754,7 @@ impl<S: VersionStorer> Backend<S> {
             return;
         };
 
-        // Parse document to get packages (needed for on-demand fetch)
-        let packages = parser
-            .parse(&content)
-            .inspect_err(|e| warn!("Failed to parse {}: {}", uri_str, e))
-            .unwrap_or_default();
-        debug!("Parsed {} packages: {:?}", packages.len(), packages);
-
-        let diagnostics = generate_diagnostics(&*parser, &*matcher, &**storer, &content);
+        let diagnostics = document.generate(storer);
 
         self.client
             .log_message(
@@ -305,166 +768,685 @@ impl<S: VersionStorer> Backend<S> {
             .await;
 
         self.client
-            .publish_diagnostics(uri.clone(), diagnostics, None)
+            .publish_diagnostics(uri.clone(), diagnostics, Some(version))
             .await;
 
-        // Spawn background task to fetch missing packages
-        if !packages.is_empty() {
+        // Spawn background task to fetch missing packages and refresh stale
+        // ones. Diagnostics above were already published from stale data.
+        if !document.packages_by_registry.is_empty() {
             debug!(
-                "Spawning background task to fetch {} packages",
-                packages.len()
+                "Spawning background task to fetch packages from {} registries",
+                document.packages_by_registry.len()
             );
             let storer = storer.clone();
             let client = self.client.clone();
+            let breaker = self.circuit_breaker();
+            let fetch_semaphore = self.fetch_semaphore();
+            let document_versions = self.document_versions.clone();
+            let inlay_hints = self
+                .config
+                .read()
+                .expect("config lock poisoned")
+                .inlay_hints;
 
             tokio::spawn(async move {
+                let Ok(_permit) = fetch_semaphore.acquire().await else {
+                    return;
+                };
                 debug!("Background task started for fetching packages");
-                let fetched = fetch_missing_packages(&*storer, &*registry, &packages).await;
-                debug!("fetch_missing_packages returned {} packages", fetched.len());
+                let mut fetched = Vec::new();
+                for (registry_type, packages) in &document.packages_by_registry {
+                    let registry = &document.registries[registry_type];
+                    let missing =
+                        fetch_missing_packages(&storer, &**registry, &breaker, packages).await;
+                    debug!("fetch_missing_packages returned {} packages", missing.len());
+                    let refreshed =
+                        fetch_stale_packages(&storer, &**registry, &breaker, packages).await;
+                    debug!("fetch_stale_packages returned {} packages", refreshed.len());
+                    fetched.extend(missing);
+                    fetched.extend(refreshed);
+                }
+
+                let current_version = document_versions
+                    .lock()
+                    .expect("document versions lock poisoned")
+                    .get(&uri)
+                    .copied();
+                if current_version != Some(version) {
+                    debug!(
+                        "Dropping diagnostics for version {} of {}, document is now at {:?}",
+                        version, uri, current_version
+                    );
+                    return;
+                }
 
                 if !fetched.is_empty() {
                     client
                         .log_message(
                             MessageType::LOG,
                             format!(
-                                "Fetched {} missing packages, republishing diagnostics",
+                                "Fetched {} missing or stale packages, republishing diagnostics",
                                 fetched.len()
                             ),
                         )
                         .await;
 
-                    let diagnostics = generate_diagnostics(&*parser, &*matcher, &*storer, &content);
-
-                    client.publish_diagnostics(uri, diagnostics, None).await;
+                    client
+                        .publish_diagnostics(uri, document.generate(&storer), Some(version))
+                        .await;
+                    if inlay_hints {
+                        // Clients re-request hints for the now cached versions
+                        if let Err(e) = client.inlay_hint_refresh().await {
+                            debug!("Failed to refresh inlay hints: {}", e);
+                        }
+                    }
                 }
             });
         }
     }
-}
 
-#[tower_lsp::async_trait]
-impl<S: VersionStorer> LanguageServer for Backend<S> {
-    async fn initialize(&self, _params: InitializeParams) -> Result<InitializeResult> {
-        self.client
-            .log_message(MessageType::INFO, "LSP server initializing")
-            .await;
-        Ok(InitializeResult {
-            capabilities: Self::server_capabilities(),
-            server_info: Some(ServerInfo {
-                name: "version-lsp".to_string(),
-                version: Some(env!("CARGO_PKG_VERSION").to_string()),
-            }),
-        })
-    }
+    /// Refetch every package of a saved document in the background, even if
+    /// its cached versions are fresh, then republish its diagnostics
+    ///
+    /// Unlike the fetch on open/change, this does not wait for a permit of
+    /// the fetch semaphore: the user is looking at the document right now.
+    fn spawn_forced_refresh(&self, uri: Url, content: String) {
+        let Some(version) = self.document_version(&uri) else {
+            return;
+        };
+        let Some(storer) = self.storer.clone() else {
+            return;
+        };
+        let Some(document) = self.document_diagnostics(&uri, content) else {
+            return;
+        };
+        if document.packages_by_registry.is_empty() {
+            return;
+        }
 
-    async fn initialized(&self, _params: InitializedParams) {
-        self.client
-            .log_message(MessageType::INFO, "LSP server initialized")
-            .await;
+        let client = self.client.clone();
+        let breaker = self.circuit_breaker();
+        let document_versions = self.document_versions.clone();
 
-        // Request configuration from client via workspace/configuration (non-blocking)
-        self.spawn_fetch_configuration();
+        tokio::spawn(async move {
+            for (registry_type, packages) in &document.packages_by_registry {
+                let package_ids = packages
+                    .iter()
+                    .map(|package| PackageId {
+                        registry_type: *registry_type,
+                        package_name: package.name.clone(),
+                    })
+                    .collect();
+                refresh_packages(
+                    &storer,
+                    &*document.registries[registry_type],
+                    &breaker,
+                    package_ids,
+                    true,
+                )
+                .await;
+            }
 
-        self.spawn_background_refresh();
-    }
+            let current_version = document_versions
+                .lock()
+                .expect("document versions lock poisoned")
+                .get(&uri)
+                .copied();
+            if current_version != Some(version) {
+                debug!(
+                    "Dropping refreshed diagnostics for version {} of {}, document is now at {:?}",
+                    version, uri, current_version
+                );
+                return;
+            }
 
-    async fn shutdown(&self) -> Result<()> {
-        self.client
-            .log_message(MessageType::INFO, "LSP server shutting down")
-            .await;
-        Ok(())
+            client
+                .publish_diagnostics(uri, document.generate(&storer), Some(version))
+                .await;
+        });
     }
 
-    async fn did_open(&self, params: DidOpenTextDocumentParams) {
-        self.client
-            .log_message(
-                MessageType::LOG,
-                format!("Document opened: {}", params.text_document.uri),
-            )
-            .await;
-
-        // Parse and cache packages
-        self.cache_document(&params.text_document.uri, &params.text_document.text);
+    /// Cached package counts per registry, for [`CACHE_STATS_COMMAND`]
+    fn cache_stats(&self) -> Result<Option<serde_json::Value>> {
+        let Some(storer) = &self.storer else {
+            return Ok(None);
+        };
+        let counts = storer.count_packages().map_err(|e| {
+            error!("Failed to count cached packages: {}", e);
+            tower_lsp::jsonrpc::Error::internal_error()
+        })?;
 
-        self.check_and_publish_diagnostics(params.text_document.uri, params.text_document.text)
-            .await;
+        Ok(Some(serde_json::json!(counts)))
     }
 
-    async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        // With FULL sync mode, the last content change contains the full document text
-        let Some(content) = params.content_changes.into_iter().last().map(|c| c.text) else {
-            return;
+    /// Refetch the packages of an open document, even if their cached
+    /// versions are fresh, and republish its diagnostics
+    ///
+    /// Fetching waits at most `FETCH_TIMEOUT_MS`; packages not fetched by
+    /// then, or whose fetch failed, keep their cached versions. Returns
+    /// `{ fetched, diagnostics }`.
+    async fn diagnose_document(&self, uri: Url) -> Result<Option<serde_json::Value>> {
+        let content = self
+            .documents
+            .read()
+            .expect("documents lock poisoned")
+            .get(&uri)
+            .map(|doc| doc.content.clone());
+        let Some(content) = content else {
+            return Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                "Document is not open: {uri}"
+            )));
+        };
+        let Some(storer) = &self.storer else {
+            return Ok(None);
+        };
+        let Some(document) = self.document_diagnostics(&uri, content) else {
+            return Ok(Some(serde_json::json!({ "fetched": 0, "diagnostics": 0 })));
         };
 
-        self.client
-            .log_message(
-                MessageType::LOG,
-                format!("Document changed: {}", params.text_document.uri),
-            )
-            .await;
-
-        // Re-parse and cache packages
-        self.cache_document(&params.text_document.uri, &content);
+        let mut fetched = 0;
+        let fetch_all = async {
+            let fetch_semaphore = self.fetch_semaphore();
+            let Ok(_permit) = fetch_semaphore.acquire().await else {
+                return;
+            };
+            let breaker = self.circuit_breaker();
+            for (registry_type, packages) in &document.packages_by_registry {
+                let registry = &document.registries[registry_type];
+                fetched += force_fetch_packages(storer, &**registry, &breaker, packages)
+                    .await
+                    .len();
+            }
+        };
+        if tokio::time::timeout(Duration::from_millis(FETCH_TIMEOUT_MS as u64), fetch_all)
+            .await
+            .is_err()
+        {
+            warn!("Timed out refetching packages of {}", uri);
+        }
 
-        self.check_and_publish_diagnostics(params.text_document.uri, content)
-            .await;
-    }
+        let diagnostics = document.generate(storer);
+        let diagnostic_count = diagnostics.len();
+        let version = self.document_version(&uri);
 
-    async fn did_close(&self, params: DidCloseTextDocumentParams) {
         self.client
-            .log_message(
-                MessageType::LOG,
-                format!("Document closed: {}", params.text_document.uri),
-            )
+            .publish_diagnostics(uri, diagnostics, version)
             .await;
 
-        // Remove document from cache
-        {
-            let mut docs = self.documents.write().expect("documents lock poisoned");
-            docs.remove(&params.text_document.uri);
-        }
+        Ok(Some(serde_json::json!({
+            "fetched": fetched,
+            "diagnostics": diagnostic_count,
+        })))
     }
 
-    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
-        let uri = &params.text_document.uri;
-        let uri_str = uri.as_str();
-        debug!("Code action requested for URI: {}", uri_str);
+    /// Spawn background task to cache the dependencies of every manifest in
+    /// the workspace (up to `workspaceScanDepth` levels deep) and of every pnpm
+    /// workspace member, so diagnostics are ready when a manifest is first
+    /// opened. Runs once, on startup.
+    fn spawn_workspace_prefetch(&self) {
+        let Some(root) = self
+            .workspace_root
+            .read()
+            .expect("workspace root lock poisoned")
+            .clone()
+        else {
+            return;
+        };
+        let Some(storer) = self.storer.clone() else {
+            return;
+        };
 
-        let Some(registry_type) = detect_parser_type(uri_str) else {
-            debug!("No parser type detected for URI: {}", uri_str);
-            return Ok(None);
+        let scan_depth = self
+            .config
+            .read()
+            .expect("config lock poisoned")
+            .workspace_scan_depth;
+
+        let (enabled_resolvers, registries) = {
+            let resolvers = self.resolvers.read().expect("resolvers lock poisoned");
+            let enabled: Vec<_> = resolvers
+                .iter()
+                .filter(|(registry_type, _)| self.is_registry_enabled(**registry_type))
+                .collect();
+            let enabled_resolvers: HashMap<RegistryType, PackageResolver> = enabled
+                .iter()
+                .map(|(registry_type, resolver)| (**registry_type, (*resolver).clone()))
+                .collect();
+            let registries: HashMap<RegistryType, Arc<dyn Registry>> = enabled
+                .iter()
+                .map(|(registry_type, resolver)| (**registry_type, resolver.registry().clone()))
+                .collect();
+            (enabled_resolvers, registries)
         };
+        let breaker = self.circuit_breaker();
+        let fetch_semaphore = self.fetch_semaphore();
 
-        if !self.is_registry_enabled(registry_type) {
-            debug!(
-                "Registry {:?} is disabled, skipping code actions",
+        tokio::spawn(async move {
+            let packages = tokio::task::spawn_blocking(move || {
+                let mut manifests = find_manifests(&root, scan_depth);
+                manifests.extend(pnpm_workspace_manifests(&root));
+                manifests.sort();
+                manifests.dedup();
+                debug!("Found {} manifests in workspace", manifests.len());
+
+                manifests
+                    .into_iter()
+                    .filter_map(|path| {
+                        let path_str = path.to_string_lossy();
+                        let parser = detect_parser_type(&path_str)
+                            .and_then(|registry_type| enabled_resolvers.get(&registry_type))?
+                            .parser_for(&path_str);
+                        let content = std::fs::read_to_string(&path)
+                            .inspect_err(|e| warn!("Failed to read {:?}: {}", path, e))
+                            .ok()?;
+                        Some(parser.parse(&content).unwrap_or_default())
+                    })
+                    .flatten()
+                    .collect::<Vec<_>>()
+            })
+            .await
+            .unwrap_or_default();
+
+            if packages.is_empty() {
+                return;
+            }
+            info!(
+                "Prefetching {} dependencies of workspace manifests",
+                packages.len()
+            );
+
+            let mut packages_by_registry: HashMap<RegistryType, Vec<PackageInfo>> = HashMap::new();
+            for package in packages.into_iter().filter(|p| !p.is_git_dependency()) {
+                packages_by_registry
+                    .entry(package.registry_type)
+                    .or_default()
+                    .push(package);
+            }
+
+            let Ok(_permit) = fetch_semaphore.acquire().await else {
+                return;
+            };
+            for (registry_type, packages) in &packages_by_registry {
+                let Some(registry) = registries.get(registry_type) else {
+                    continue;
+                };
+                let fetched =
+                    fetch_missing_packages(&storer, &**registry, &breaker, packages).await;
+                debug!(
+                    "Prefetched {} {:?} packages for workspace manifests",
+                    fetched.len(),
+                    registry_type
+                );
+            }
+        });
+    }
+
+    /// Matcher and SHA fetcher for a package's registry, or None for git
+    /// dependencies and when the registry is disabled or has no resolver
+    ///
+    /// Packages are resolved by their own registry, which may differ from
+    /// the document's (e.g. pip requirements in environment.yml).
+    fn package_resolver_parts(&self, package: &PackageInfo) -> Option<CodeActionResolver> {
+        if package.is_git_dependency() {
+            debug!(
+                "{} is a git dependency, skipping code actions",
+                package.name
+            );
+            return None;
+        }
+
+        let registry_type = package.registry_type;
+        if !self.is_registry_enabled(registry_type) {
+            debug!(
+                "Registry {:?} is disabled, skipping code actions",
                 registry_type
             );
-            return Ok(None);
+            return None;
         }
 
-        let Some(storer) = &self.storer else {
-            debug!("Storer not available");
-            return Ok(None);
+        let resolvers = self.resolvers.read().expect("resolvers lock poisoned");
+        let Some(resolver) = resolvers.get(&registry_type) else {
+            debug!("No resolver for registry type {:?}", registry_type);
+            return None;
         };
+        Some((resolver.matcher().clone(), resolver.sha_fetcher().cloned()))
+    }
 
-        // Get cached packages
-        let packages = {
+    /// Upgrade actions for a single package
+    async fn upgrade_code_actions(
+        storer: &Arc<S>,
+        package: &PackageInfo,
+        uri: &Url,
+        matcher: &dyn VersionMatcher,
+        sha_fetcher: Option<Arc<dyn TagShaFetcher>>,
+        include_pre_release: bool,
+    ) -> Vec<CodeAction> {
+        // For GitHub Actions with commit hash, use async function to fetch SHA
+        if package.registry_type == RegistryType::GitHubActions && package.commit_hash.is_some() {
+            let Some(sha_fetcher) = sha_fetcher else {
+                debug!(
+                    "No SHA fetcher for registry type {:?}",
+                    package.registry_type
+                );
+                return Vec::new();
+            };
+            generate_upgrade_code_actions_with_sha(
+                storer,
+                package,
+                uri,
+                &*sha_fetcher,
+                matcher,
+                include_pre_release,
+            )
+            .await
+        } else {
+            generate_upgrade_code_actions(storer, package, uri, matcher, include_pre_release)
+        }
+    }
+
+    /// Upgrade actions for every package in a selection, each titled with
+    /// its package name, plus an "Update all in selection" action, or an
+    /// "Update all outdated dependencies" action when the selection covers
+    /// the whole document
+    async fn selection_code_actions(
+        &self,
+        storer: &Arc<S>,
+        packages: &[&PackageInfo],
+        uri: &Url,
+        whole_document: bool,
+    ) -> Vec<CodeAction> {
+        let resolved: Vec<(&PackageInfo, CodeActionResolver)> = packages
+            .iter()
+            .filter_map(|&package| Some((package, self.package_resolver_parts(package)?)))
+            .collect();
+
+        let include_pre_release = self.include_pre_release();
+        let mut actions = Vec::new();
+        for (package, (matcher, sha_fetcher)) in &resolved {
+            let package_actions = Self::upgrade_code_actions(
+                storer,
+                package,
+                uri,
+                &**matcher,
+                sha_fetcher.clone(),
+                include_pre_release,
+            )
+            .await;
+            actions.extend(package_actions.into_iter().map(|mut action| {
+                action.title = format!("{}: {}", package.name, action.title);
+                action
+            }));
+        }
+
+        let targets: Vec<(&PackageInfo, &dyn VersionMatcher)> = resolved
+            .iter()
+            .map(|(package, (matcher, _))| (*package, &**matcher))
+            .collect();
+        if whole_document {
+            actions.extend(generate_bulk_update_code_action(
+                storer,
+                &targets,
+                uri,
+                include_pre_release,
+            ));
+        } else {
+            actions.extend(generate_update_all_action(
+                storer,
+                &targets,
+                uri,
+                include_pre_release,
+            ));
+        }
+
+        actions
+    }
+
+    /// The "Update all outdated dependencies" source action for `packages`
+    fn bulk_update_code_action(
+        &self,
+        storer: &Arc<S>,
+        packages: &[PackageInfo],
+        uri: &Url,
+    ) -> Option<CodeAction> {
+        let resolved: Vec<(&PackageInfo, CodeActionResolver)> = packages
+            .iter()
+            .filter_map(|package| Some((package, self.package_resolver_parts(package)?)))
+            .collect();
+        let targets: Vec<(&PackageInfo, &dyn VersionMatcher)> = resolved
+            .iter()
+            .map(|(package, (matcher, _))| (*package, &**matcher))
+            .collect();
+        generate_bulk_update_code_action(storer, &targets, uri, self.include_pre_release())
+    }
+}
+
+#[tower_lsp::async_trait]
+impl<S: VersionStorer> LanguageServer for Backend<S> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        self.client
+            .log_message(MessageType::INFO, "LSP server initializing")
+            .await;
+
+        if let Some(workspace_root) = workspace_root(&params) {
+            self.load_project_config(&workspace_root);
+            *self
+                .workspace_root
+                .write()
+                .expect("workspace root lock poisoned") = Some(workspace_root);
+        }
+
+        Ok(InitializeResult {
+            capabilities: Self::server_capabilities(),
+            server_info: Some(ServerInfo {
+                name: "version-lsp".to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+        })
+    }
+
+    async fn initialized(&self, _params: InitializedParams) {
+        self.client
+            .log_message(MessageType::INFO, "LSP server initialized")
+            .await;
+
+        // Request configuration from client via workspace/configuration (non-blocking)
+        self.spawn_fetch_configuration();
+
+        self.spawn_background_refresh();
+
+        self.spawn_workspace_prefetch();
+    }
+
+    async fn did_change_configuration(&self, _params: DidChangeConfigurationParams) {
+        // The pushed settings are ignored: the configuration is pulled again
+        // via workspace/configuration, so the project config file is applied
+        // on top of it the same way as on startup
+        debug!("Configuration changed, fetching configuration");
+        if let Err(e) = self.spawn_fetch_configuration().await {
+            warn!("Configuration fetch task failed: {}", e);
+            return;
+        }
+
+        self.republish_open_documents().await;
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        self.client
+            .log_message(MessageType::INFO, "LSP server shutting down")
+            .await;
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        self.client
+            .log_message(
+                MessageType::LOG,
+                format!("Document opened: {}", params.text_document.uri),
+            )
+            .await;
+
+        if is_binary_lock_file(params.text_document.uri.as_str()) {
+            self.client
+                .show_message(
+                    MessageType::WARNING,
+                    "bun.lockb is a binary lock file and cannot be read; \
+                     use the text bun.lock format (`bun install --save-text-lockfile`)",
+                )
+                .await;
+            return;
+        }
+
+        // Parse and cache packages
+        self.cache_document(&params.text_document.uri, &params.text_document.text);
+        self.set_document_version(&params.text_document.uri, params.text_document.version);
+
+        self.check_and_publish_diagnostics(
+            params.text_document.uri,
+            params.text_document.text,
+            params.text_document.version,
+        )
+        .await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // With FULL sync mode, the last content change contains the full document text
+        let Some(content) = params.content_changes.into_iter().last().map(|c| c.text) else {
+            return;
+        };
+
+        self.client
+            .log_message(
+                MessageType::LOG,
+                format!("Document changed: {}", params.text_document.uri),
+            )
+            .await;
+
+        // Re-parse and cache packages
+        self.cache_document(&params.text_document.uri, &content);
+        self.set_document_version(&params.text_document.uri, params.text_document.version);
+
+        self.check_and_publish_diagnostics(
+            params.text_document.uri,
+            content,
+            params.text_document.version,
+        )
+        .await;
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.client
+            .log_message(MessageType::LOG, format!("Document saved: {}", uri))
+            .await;
+
+        // The cached text is that of the last didOpen/didChange, which already
+        // matches the saved document
+        let Some(content) = self
+            .documents
+            .read()
+            .expect("documents lock poisoned")
+            .get(&uri)
+            .map(|cache| cache.content.clone())
+        else {
+            return;
+        };
+        let Some(version) = self.document_version(&uri) else {
+            return;
+        };
+
+        self.check_and_publish_diagnostics(uri.clone(), content.clone(), version)
+            .await;
+        self.spawn_forced_refresh(uri, content);
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        self.client
+            .log_message(
+                MessageType::LOG,
+                format!("Document closed: {}", params.text_document.uri),
+            )
+            .await;
+
+        // Remove document from cache
+        {
+            let mut docs = self.documents.write().expect("documents lock poisoned");
+            docs.remove(&params.text_document.uri);
+        }
+        self.document_versions
+            .lock()
+            .expect("document versions lock poisoned")
+            .remove(&params.text_document.uri);
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = &params.text_document.uri;
+        let uri_str = uri.as_str();
+        debug!("Code action requested for URI: {}", uri_str);
+
+        // Get cached registry type and packages
+        let (registry_type, packages, line_count) = {
             let docs = self.documents.read().expect("documents lock poisoned");
             let Some(cache) = docs.get(uri) else {
                 debug!("Document not found in cache: {}", uri_str);
                 return Ok(None);
             };
-            cache.packages.clone()
+            (
+                cache.registry_type,
+                cache.packages.clone(),
+                cache.content.lines().count(),
+            )
+        };
+
+        if registry_type.is_none() {
+            debug!("No parser type detected for URI: {}", uri_str);
+            return Ok(None);
+        }
+
+        // Lock files are regenerated by the package manager, not edited by hand
+        if is_lock_file(uri_str) {
+            debug!("Skipping code actions for lock file: {}", uri_str);
+            return Ok(None);
+        }
+
+        let Some(storer) = &self.storer else {
+            debug!("Storer not available");
+            return Ok(None);
         };
 
         if packages.is_empty() {
             return Ok(None);
         }
 
+        // A request for source actions only gets the update of every
+        // outdated package of the document
+        let source_only = params
+            .context
+            .only
+            .as_ref()
+            .is_some_and(|only| only.contains(&CodeActionKind::SOURCE));
+        if source_only {
+            let actions = self
+                .bulk_update_code_action(storer, &packages, uri)
+                .into_iter()
+                .collect();
+            return Ok(to_code_action_response(actions));
+        }
+
         let index = PackageIndex::new(&packages);
-        let position = params.range.start;
 
+        // A multi-line selection offers upgrades for every package in it
+        if params.range.start.line != params.range.end.line {
+            let selected = index.packages_in_range(params.range);
+            debug!("Found {} packages in selection", selected.len());
+            let whole_document = params.range.start == Position::new(0, 0)
+                && params.range.end.line as usize + 1 >= line_count;
+            let actions = self
+                .selection_code_actions(storer, &selected, uri, whole_document)
+                .await;
+            return Ok(to_code_action_response(actions));
+        }
+
+        let position = params.range.start;
         let Some(package) = index.find_at_position(position) else {
             debug!("No package found at position {:?}", position);
             return Ok(None);
@@ -475,34 +1457,19 @@ impl<S: VersionStorer> LanguageServer for Backend<S> {
             package.name, package.version
         );
 
-        let (matcher, sha_fetcher) = {
-            let resolvers = self.resolvers.read().expect("resolvers lock poisoned");
-            let Some(resolver) = resolvers.get(&registry_type) else {
-                debug!("No resolver for registry type {:?}", registry_type);
-                return Ok(None);
-            };
-            (resolver.matcher().clone(), resolver.sha_fetcher().cloned())
-        };
-
-        // For GitHub Actions with commit hash, use async function to fetch SHA
-        let mut actions = if package.registry_type == RegistryType::GitHubActions
-            && package.commit_hash.is_some()
-        {
-            let Some(sha_fetcher) = sha_fetcher else {
-                debug!("No SHA fetcher for registry type {:?}", registry_type);
-                return Ok(None);
-            };
-            generate_upgrade_code_actions_with_sha(
-                &**storer,
-                package,
-                uri,
-                &*sha_fetcher,
-                &*matcher,
-            )
-            .await
-        } else {
-            generate_upgrade_code_actions(&**storer, package, uri, &*matcher)
+        let Some((matcher, sha_fetcher)) = self.package_resolver_parts(package) else {
+            return Ok(None);
         };
+        let mut actions = Self::upgrade_code_actions(
+            storer,
+            package,
+            uri,
+            &*matcher,
+            sha_fetcher,
+            self.include_pre_release(),
+        )
+        .await;
+        actions.extend(generate_pin_code_action(storer, package, uri, &*matcher));
 
         // Append constraint actions based on registry type
         match package.registry_type {
@@ -518,15 +1485,481 @@ impl<S: VersionStorer> LanguageServer for Backend<S> {
             _ => {}
         }
 
-        if actions.is_empty() {
+        Ok(to_code_action_response(actions))
+    }
+
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let (package, document) = {
+            let docs = self.documents.read().expect("documents lock poisoned");
+            let Some(cache) = docs.get(uri) else {
+                debug!("Document not found in cache: {}", uri);
+                return Ok(None);
+            };
+            let package = PackageIndex::new(&cache.packages)
+                .find_at_position(position)
+                .cloned();
+            (package, cache.metadata.clone())
+        };
+        let Some(package) = package else {
+            return Ok(None);
+        };
+        let matcher = self
+            .resolvers
+            .read()
+            .expect("resolvers lock poisoned")
+            .get(&package.registry_type)
+            .map(|resolver| resolver.matcher().clone());
+        let storer = self.storer.clone();
+        let show_yanked = self.show_yanked();
+
+        // The cache is read with blocking SQLite calls
+        let hover = tokio::task::spawn_blocking(move || {
+            let comparison = storer
+                .as_ref()
+                .zip(matcher)
+                .filter(|_| !package.is_git_dependency())
+                .and_then(|(storer, matcher)| {
+                    compare_version(
+                        &**storer,
+                        &*matcher,
+                        &package.name,
+                        &package.version,
+                        show_yanked,
+                    )
+                    .inspect_err(|e| warn!("Failed to compare version of {}: {}", package.name, e))
+                    .ok()
+                });
+            let metadata = storer.as_ref().and_then(|storer| {
+                storer
+                    .get_metadata(package.registry_type, &package.name)
+                    .inspect_err(|e| warn!("Failed to get metadata of {}: {}", package.name, e))
+                    .ok()
+                    .flatten()
+            });
+            let last_fetch_time = storer.as_ref().and_then(|storer| {
+                storer
+                    .get_last_fetch_time(package.registry_type, &package.name)
+                    .inspect_err(|e| {
+                        warn!("Failed to get last fetch time of {}: {}", package.name, e)
+                    })
+                    .ok()
+                    .flatten()
+            });
+
+            package_hover(
+                &package,
+                &document,
+                comparison.as_ref(),
+                metadata.as_ref(),
+                last_fetch_time,
+                chrono::Utc::now(),
+            )
+        })
+        .await
+        .inspect_err(|e| warn!("Failed to generate hover: {}", e))
+        .unwrap_or_default();
+
+        Ok(hover)
+    }
+
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        let uri = &params.text_document.uri;
+
+        if !self
+            .config
+            .read()
+            .expect("config lock poisoned")
+            .inlay_hints
+        {
             return Ok(None);
         }
+        let Some(storer) = self.storer.clone() else {
+            return Ok(None);
+        };
 
-        Ok(Some(
-            actions
+        let (content, packages) = {
+            let docs = self.documents.read().expect("documents lock poisoned");
+            let Some(cache) = docs.get(uri) else {
+                debug!("Document not found in cache: {}", uri);
+                return Ok(None);
+            };
+            let packages: Vec<PackageInfo> = PackageIndex::new(&cache.packages)
+                .packages_in_range(params.range)
                 .into_iter()
-                .map(CodeActionOrCommand::CodeAction)
-                .collect(),
-        ))
+                .cloned()
+                .collect();
+            (cache.content.clone(), packages)
+        };
+        let Some((_, matchers, _)) = self.document_resolvers(uri.as_str(), &content) else {
+            return Ok(None);
+        };
+        let show_yanked = self.show_yanked();
+
+        // The cache is read with blocking SQLite calls
+        let hints = tokio::task::spawn_blocking(move || {
+            let packages: Vec<&PackageInfo> = packages.iter().collect();
+            generate_inlay_hints(&packages, &content, &matchers, &*storer, show_yanked)
+        })
+        .await
+        .inspect_err(|e| warn!("Failed to generate inlay hints: {}", e))
+        .unwrap_or_default();
+
+        Ok(Some(hints))
+    }
+
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        let Some(storer) = self.storer.clone() else {
+            return Ok(None);
+        };
+        let package = {
+            let docs = self.documents.read().expect("documents lock poisoned");
+            let Some(cache) = docs.get(uri) else {
+                debug!("Document not found in cache: {}", uri);
+                return Ok(None);
+            };
+            PackageIndex::new(&cache.packages)
+                .find_at_position(position)
+                .cloned()
+        };
+        let Some(package) = package.filter(|package| !package.is_git_dependency()) else {
+            return Ok(None);
+        };
+        let context = CompletionContext {
+            ignore_prerelease: self
+                .config
+                .read()
+                .expect("config lock poisoned")
+                .ignore_prerelease,
+        };
+
+        // The cache is read with blocking SQLite calls
+        let items =
+            tokio::task::spawn_blocking(move || generate_completions(&package, &*storer, context))
+                .await
+                .inspect_err(|e| warn!("Failed to generate completions: {}", e))
+                .unwrap_or_default();
+
+        if items.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        let docs = self.documents.read().expect("documents lock poisoned");
+        let mut symbols: Vec<SymbolInformation> = docs
+            .iter()
+            .flat_map(|(uri, cache)| package_symbols(uri, &cache.packages, &params.query))
+            .collect();
+        // Documents are unordered; list symbols by file, then position
+        symbols.sort_by(|a, b| {
+            (a.location.uri.as_str(), a.location.range.start)
+                .cmp(&(b.location.uri.as_str(), b.location.range.start))
+        });
+
+        Ok(Some(symbols))
+    }
+
+    async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        let uri = &params.text_document.uri;
+
+        let docs = self.documents.read().expect("documents lock poisoned");
+        let Some(cache) = docs.get(uri) else {
+            debug!("Document not found in cache: {}", uri);
+            return Ok(None);
+        };
+
+        Ok(Some(dependency_folding_ranges(&cache.packages)))
+    }
+
+    async fn linked_editing_range(
+        &self,
+        params: LinkedEditingRangeParams,
+    ) -> Result<Option<LinkedEditingRanges>> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let packages = {
+            let docs = self.documents.read().expect("documents lock poisoned");
+            let Some(cache) = docs.get(uri) else {
+                debug!("Document not found in cache: {}", uri);
+                return Ok(None);
+            };
+            cache.packages.clone()
+        };
+
+        let index = PackageIndex::new(&packages);
+        let Some(package) = index.find_at_position(position) else {
+            return Ok(None);
+        };
+
+        // Link the same dependency declared in several sections
+        // (e.g. dependencies and devDependencies) with the same version
+        let ranges: Vec<Range> = index
+            .packages_named(&package.name)
+            .into_iter()
+            .filter(|other| {
+                other.registry_type == package.registry_type && other.version == package.version
+            })
+            .map(version_range)
+            .collect();
+
+        if ranges.len() < 2 {
+            return Ok(None);
+        }
+
+        debug!("Linked {} version ranges of {}", ranges.len(), package.name);
+        Ok(Some(LinkedEditingRanges {
+            ranges,
+            word_pattern: None,
+        }))
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let packages = {
+            let docs = self.documents.read().expect("documents lock poisoned");
+            let Some(cache) = docs.get(uri) else {
+                debug!("Document not found in cache: {}", uri);
+                return Ok(None);
+            };
+            cache.packages.clone()
+        };
+        let index = PackageIndex::new(&packages);
+        let Some(package) = index.find_at_position(position) else {
+            return Ok(None);
+        };
+
+        // Only pnpm `catalog:` references point somewhere else
+        let Some(ExtraInfo::PnpmCatalogRef { catalog_name }) = &package.extra_info else {
+            return Ok(None);
+        };
+        let Some(workspace_file) = uri
+            .to_file_path()
+            .ok()
+            .and_then(|path| find_pnpm_workspace(&path))
+        else {
+            debug!("No pnpm-workspace.yaml found for {}", uri);
+            return Ok(None);
+        };
+
+        Ok(
+            catalog_entry_location(&workspace_file, catalog_name, &package.name)
+                .map(GotoDefinitionResponse::Scalar),
+        )
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        let (packages, content) = {
+            let docs = self.documents.read().expect("documents lock poisoned");
+            let Some(cache) = docs.get(uri) else {
+                debug!("Document not found in cache: {}", uri);
+                return Ok(None);
+            };
+            // Only catalog entries of pnpm-workspace.yaml are referenced
+            if cache.registry_type != Some(RegistryType::PnpmCatalog) {
+                return Ok(None);
+            }
+            (cache.packages.clone(), cache.content.clone())
+        };
+        let index = PackageIndex::new(&packages);
+        let Some(entry) = index.find_at_position(position) else {
+            return Ok(None);
+        };
+        let Some((catalog_name, declared_name)) = catalog_name_of(&content, entry) else {
+            return Ok(None);
+        };
+        let Some(root) = uri
+            .to_file_path()
+            .ok()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+        else {
+            return Ok(None);
+        };
+
+        let mut locations = Vec::new();
+        if params.context.include_declaration {
+            locations.push(Location {
+                uri: uri.clone(),
+                range: version_range(entry),
+            });
+        }
+        locations.extend(catalog_references(&root, &catalog_name, &declared_name));
+
+        debug!(
+            "Found {} references to {} in catalog {}",
+            locations.len(),
+            declared_name,
+            catalog_name
+        );
+        Ok((!locations.is_empty()).then_some(locations))
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let uri = &params.text_document.uri;
+        let packages = {
+            let docs = self.documents.read().expect("documents lock poisoned");
+            let Some(cache) = docs.get(uri) else {
+                debug!("Document not found in cache: {}", uri);
+                return Ok(None);
+            };
+            cache.packages.clone()
+        };
+        let index = PackageIndex::new(&packages);
+
+        Ok(index
+            .find_at_position(params.position)
+            .filter(|package| package.has_replaceable_version())
+            .map(|package| PrepareRenameResponse::Range(version_range(package))))
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        let packages = {
+            let docs = self.documents.read().expect("documents lock poisoned");
+            let Some(cache) = docs.get(&uri) else {
+                debug!("Document not found in cache: {}", &uri);
+                return Ok(None);
+            };
+            cache.packages.clone()
+        };
+        let index = PackageIndex::new(&packages);
+        let Some(package) = index
+            .find_at_position(position)
+            .filter(|package| package.has_replaceable_version())
+        else {
+            return Ok(None);
+        };
+
+        // Apply the new version to every declaration of the dependency
+        let edits: Vec<TextEdit> = index
+            .packages_named(&package.name)
+            .into_iter()
+            .filter(|other| {
+                other.registry_type == package.registry_type && other.has_replaceable_version()
+            })
+            .map(|other| TextEdit {
+                range: version_range(other),
+                new_text: params.new_name.clone(),
+            })
+            .collect();
+
+        debug!(
+            "Renaming {} version ranges of {} to {}",
+            edits.len(),
+            package.name,
+            params.new_name
+        );
+        Ok(Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri, edits)])),
+            ..Default::default()
+        }))
+    }
+
+    async fn execute_command(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<serde_json::Value>> {
+        match params.command.as_str() {
+            CACHE_STATS_COMMAND => self.cache_stats(),
+            DIAGNOSE_DOCUMENT_COMMAND => {
+                let Some(DiagnoseDocumentArguments { uri }) = params
+                    .arguments
+                    .into_iter()
+                    .next()
+                    .and_then(|argument| serde_json::from_value(argument).ok())
+                else {
+                    return Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                        "{DIAGNOSE_DOCUMENT_COMMAND} expects {{ \"uri\": string }}"
+                    )));
+                };
+                self.diagnose_document(uri).await
+            }
+            _ => Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                "Unknown command: {}",
+                params.command
+            ))),
+        }
+    }
+}
+
+/// Group packages by registry, keeping only registries in `registries`,
+/// because each registry is fetched separately
+fn group_packages_by_registry(
+    packages: &[PackageInfo],
+    registries: &HashMap<RegistryType, Arc<dyn Registry>>,
+) -> HashMap<RegistryType, Vec<PackageInfo>> {
+    let mut packages_by_registry: HashMap<RegistryType, Vec<PackageInfo>> = HashMap::new();
+    for package in packages {
+        if registries.contains_key(&package.registry_type) && !package.is_git_dependency() {
+            packages_by_registry
+                .entry(package.registry_type)
+                .or_default()
+                .push(package.clone());
+        }
+    }
+    packages_by_registry
+}
+
+/// Wrap code actions in a response, or None when there are none
+fn to_code_action_response(actions: Vec<CodeAction>) -> Option<CodeActionResponse> {
+    if actions.is_empty() {
+        return None;
+    }
+
+    Some(
+        actions
+            .into_iter()
+            .map(CodeActionOrCommand::CodeAction)
+            .collect(),
+    )
+}
+
+/// Workspace root directory from the first workspace folder, falling back to
+/// the deprecated `rootUri`
+fn workspace_root(params: &InitializeParams) -> Option<PathBuf> {
+    #[allow(deprecated)]
+    let root_uri = params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri)
+        .or(params.root_uri.as_ref())?;
+
+    root_uri.to_file_path().ok()
+}
+
+/// Module versions recorded in the go.sum next to a go.mod document
+///
+/// Returns None for other documents, or when there is no readable go.sum
+/// (e.g. a module without dependencies downloaded yet).
+fn read_sibling_go_sum(uri: &Url) -> Option<HashSet<(String, String)>> {
+    if !uri.path().ends_with("/go.mod") {
+        return None;
     }
+    let go_sum_path = uri.to_file_path().ok()?.with_file_name("go.sum");
+    let content = std::fs::read_to_string(go_sum_path).ok()?;
+    Some(GoSumParser::new().parse(&content))
 }
@@ -0,0 +1,232 @@
+//! Navigation between pnpm `catalog:` references and catalog entries
+//!
+//! A `catalog:` version in package.json takes its version from an entry of
+//! the pnpm-workspace.yaml catalogs. Definition jumps from the reference to
+//! the entry; references lists the package.json files using an entry.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use tower_lsp::lsp_types::{Location, Url};
+use tracing::debug;
+
+use crate::lsp::code_action::version_range;
+use crate::lsp::workspace::pnpm_workspace_manifests;
+use crate::parser::package_json::PackageJsonParser;
+use crate::parser::pnpm_workspace::PnpmWorkspaceParser;
+use crate::parser::traits::Parser;
+use crate::parser::types::{ExtraInfo, PackageInfo};
+
+/// File holding the pnpm catalogs
+pub const PNPM_WORKSPACE_FILE: &str = "pnpm-workspace.yaml";
+
+/// Find the pnpm-workspace.yaml of the workspace containing `document`,
+/// searching the document's directory and then each parent directory
+pub fn find_pnpm_workspace(document: &Path) -> Option<PathBuf> {
+    document
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join(PNPM_WORKSPACE_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Location of the entry for `package_name` in catalog `catalog_name` of the
+/// pnpm-workspace.yaml at `workspace_file`
+pub fn catalog_entry_location(
+    workspace_file: &Path,
+    catalog_name: &str,
+    package_name: &str,
+) -> Option<Location> {
+    let content = std::fs::read_to_string(workspace_file)
+        .inspect_err(|e| debug!("Failed to read {:?}: {}", workspace_file, e))
+        .ok()?;
+    let catalogs = PnpmWorkspaceParser.catalogs(&content);
+    let entry = catalogs
+        .get(catalog_name)?
+        .iter()
+        .find(|entry| entry.name == package_name)?;
+
+    Some(Location {
+        uri: Url::from_file_path(workspace_file).ok()?,
+        range: version_range(entry),
+    })
+}
+
+/// Name of the catalog holding `entry`, one of the packages of the
+/// pnpm-workspace.yaml `content`, and the package name the entry is declared
+/// under
+///
+/// The declared name differs from `entry.name` for `npm:` alias entries, and
+/// is the name `catalog:` references use.
+pub fn catalog_name_of(content: &str, entry: &PackageInfo) -> Option<(String, String)> {
+    PnpmWorkspaceParser
+        .catalogs(content)
+        .into_iter()
+        .find_map(|(catalog_name, entries)| {
+            entries
+                .into_iter()
+                .find(|other| other.start_offset == entry.start_offset)
+                .map(|declared| (catalog_name, declared.name))
+        })
+}
+
+/// Locations of the `catalog:` references to `package_name` from catalog
+/// `catalog_name` in the package.json files of the pnpm workspace at `root`
+///
+/// The root package.json and the package.json of every workspace member are
+/// searched, as saved on disk.
+pub fn catalog_references(root: &Path, catalog_name: &str, package_name: &str) -> Vec<Location> {
+    let mut manifests: BTreeSet<PathBuf> = pnpm_workspace_manifests(root).into_iter().collect();
+    let root_manifest = root.join("package.json");
+    if root_manifest.is_file() {
+        manifests.insert(root_manifest);
+    }
+
+    let parser = PackageJsonParser::new();
+    manifests
+        .into_iter()
+        .flat_map(|path| {
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                return Vec::new();
+            };
+            let Ok(uri) = Url::from_file_path(&path) else {
+                return Vec::new();
+            };
+            parser
+                .parse(&content)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|package| {
+                    package.name == package_name
+                        && matches!(
+                            &package.extra_info,
+                            Some(ExtraInfo::PnpmCatalogRef { catalog_name: name })
+                                if name == catalog_name
+                        )
+                })
+                .map(|package| Location {
+                    uri: uri.clone(),
+                    range: version_range(&package),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tower_lsp::lsp_types::Position;
+
+    const WORKSPACE: &str = r#"packages:
+  - 'apps/*'
+catalog:
+  react: ^18.2.0
+catalogs:
+  ag-grid:
+    ag-grid-community: ^31.0.0
+"#;
+
+    fn write(root: &Path, path: &str, content: &str) -> PathBuf {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn find_pnpm_workspace_searches_parent_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let workspace_file = write(root, PNPM_WORKSPACE_FILE, WORKSPACE);
+        let manifest = write(root, "apps/web/package.json", "{}");
+
+        assert_eq!(find_pnpm_workspace(&manifest), Some(workspace_file));
+    }
+
+    #[test]
+    fn find_pnpm_workspace_returns_none_outside_pnpm_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = write(temp_dir.path(), "package.json", "{}");
+
+        assert_eq!(find_pnpm_workspace(&manifest), None);
+    }
+
+    #[rstest::rstest]
+    #[case("ag-grid", "ag-grid-community", Some((6, 23)))]
+    #[case("default", "react", Some((3, 9)))]
+    #[case("default", "ag-grid-community", None)]
+    #[case("missing", "react", None)]
+    fn catalog_entry_location_points_at_entry_version(
+        #[case] catalog_name: &str,
+        #[case] package_name: &str,
+        #[case] expected: Option<(u32, u32)>,
+    ) {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace_file = write(temp_dir.path(), PNPM_WORKSPACE_FILE, WORKSPACE);
+
+        let location = catalog_entry_location(&workspace_file, catalog_name, package_name);
+
+        assert_eq!(
+            location.map(|location| (location.range.start.line, location.range.start.character)),
+            expected
+        );
+    }
+
+    #[test]
+    fn catalog_name_of_finds_catalog_of_entry() {
+        let entries = PnpmWorkspaceParser.parse(WORKSPACE).unwrap();
+        let ag_grid = entries
+            .iter()
+            .find(|entry| entry.name == "ag-grid-community")
+            .unwrap();
+
+        assert_eq!(
+            catalog_name_of(WORKSPACE, ag_grid),
+            Some(("ag-grid".to_string(), "ag-grid-community".to_string()))
+        );
+    }
+
+    #[test]
+    fn catalog_references_finds_references_in_workspace_manifests() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(root, PNPM_WORKSPACE_FILE, WORKSPACE);
+        let web = write(
+            root,
+            "apps/web/package.json",
+            r#"{
+  "dependencies": {
+    "ag-grid-community": "catalog:ag-grid",
+    "react": "catalog:"
+  }
+}"#,
+        );
+        write(
+            root,
+            "apps/admin/package.json",
+            r#"{ "dependencies": { "ag-grid-community": "^30.0.0" } }"#,
+        );
+        let root_manifest = write(
+            root,
+            "package.json",
+            r#"{ "devDependencies": { "react": "catalog:default" } }"#,
+        );
+
+        let ag_grid = catalog_references(root, "ag-grid", "ag-grid-community");
+        let react = catalog_references(root, "default", "react");
+
+        assert_eq!(ag_grid.len(), 1);
+        assert_eq!(ag_grid[0].uri, Url::from_file_path(&web).unwrap());
+        assert_eq!(ag_grid[0].range.start, Position::new(2, 26));
+        let mut react_uris: Vec<Url> = react.into_iter().map(|location| location.uri).collect();
+        react_uris.sort();
+        let mut expected = vec![
+            Url::from_file_path(&root_manifest).unwrap(),
+            Url::from_file_path(&web).unwrap(),
+        ];
+        expected.sort();
+        assert_eq!(react_uris, expected);
+    }
+}
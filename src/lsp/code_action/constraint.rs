@@ -1,7 +1,10 @@
-//! Constraint code actions — switching version constraint operators
+//! Constraint code actions — switching version constraint operators and
+//! pinning ranges to an exact version
 
 use crate::parser::types::PackageInfo;
-use tower_lsp::lsp_types::{CodeAction, Url};
+use crate::version::checker::VersionStorer;
+use crate::version::matcher::VersionMatcher;
+use tower_lsp::lsp_types::{CodeAction, CodeActionKind, Url};
 
 use super::{create_bump_action, extract_version_prefix, strip_version_prefix};
 
@@ -20,11 +23,53 @@ fn parse_pypi_version(version: &str) -> Option<(&str, &str)> {
     }
 }
 
+/// Generate a code action pinning a version range to the highest cached
+/// version it accepts (`^4.17.0` → `4.17.21`, `~1.35` → `1.35.3`)
+///
+/// The replacement comes from [`VersionMatcher::exact_spec`]; tag-style
+/// versions keep their prefix (`v4` → `v4.2.1`). Returns None when the
+/// specification already names a single version, no cached version
+/// satisfies it, or its version token can't be replaced as a whole
+/// (commit-pinned actions, git dependencies, pnpm `catalog:` references).
+pub fn generate_pin_code_action<S: VersionStorer>(
+    storer: &S,
+    package: &PackageInfo,
+    uri: &Url,
+    matcher: &dyn VersionMatcher,
+) -> Option<CodeAction> {
+    let current = &package.version;
+    if !package.has_replaceable_version() {
+        return None;
+    }
+
+    // Yanked versions are never suggested
+    let versions = storer
+        .get_versions_ordered_by_semver(package.registry_type, &package.name, false)
+        .ok()?;
+    let version = matcher.version_in_range(current, &versions)?;
+    let pinned = matcher.exact_spec(&version);
+    if *current == version || *current == pinned {
+        return None;
+    }
+
+    Some(CodeAction {
+        kind: Some(CodeActionKind::REFACTOR),
+        ..create_bump_action(
+            &format!("Pin to exact version: {pinned}"),
+            &pinned,
+            package,
+            uri,
+        )
+    })
+}
+
 /// Generate constraint code actions for semver registries (npm, crates, jsr, pnpm catalogs)
 ///
-/// Changes only the version prefix, not the version itself.
-/// - `^X.Y.Z` → offers exact pin and `~`
-/// - `~X.Y.Z` → offers exact pin and `^`
+/// Changes only the version prefix, not the version itself. Pinning is left
+/// to [`generate_pin_code_action`], which pins the version the range
+/// resolves to.
+/// - `^X.Y.Z` → offers `~`
+/// - `~X.Y.Z` → offers `^`
 /// - `X.Y.Z` (bare) → offers `^` and `~`
 pub fn generate_constraint_code_actions(package: &PackageInfo, uri: &Url) -> Vec<CodeAction> {
     let current = &package.version;
@@ -39,24 +84,18 @@ pub fn generate_constraint_code_actions(package: &PackageInfo, uri: &Url) -> Vec
     }
 
     match prefix {
-        "^" => vec![
-            create_bump_action(&format!("Pin to exact version: {bare}"), bare, package, uri),
-            create_bump_action(
-                &format!("Patch updates only (~): ~{bare}"),
-                &format!("~{bare}"),
-                package,
-                uri,
-            ),
-        ],
-        "~" => vec![
-            create_bump_action(&format!("Pin to exact version: {bare}"), bare, package, uri),
-            create_bump_action(
-                &format!("Compatible updates (^): ^{bare}"),
-                &format!("^{bare}"),
-                package,
-                uri,
-            ),
-        ],
+        "^" => vec![create_bump_action(
+            &format!("Patch updates only (~): ~{bare}"),
+            &format!("~{bare}"),
+            package,
+            uri,
+        )],
+        "~" => vec![create_bump_action(
+            &format!("Compatible updates (^): ^{bare}"),
+            &format!("^{bare}"),
+            package,
+            uri,
+        )],
         "" => vec![
             create_bump_action(
                 &format!("Compatible updates (^): ^{bare}"),
@@ -78,8 +117,9 @@ pub fn generate_constraint_code_actions(package: &PackageInfo, uri: &Url) -> Vec
 /// Generate PyPI constraint code actions
 ///
 /// Changes only the operator, not the version. For simple PyPI version specs
-/// (no compound ranges), offers to switch between == (pin), >= (minimum),
-/// and ~= (compatible release) operators.
+/// (no compound ranges), offers to switch between >= (minimum) and ~=
+/// (compatible release) operators, and from == (pin) to either. Pinning is
+/// left to [`generate_pin_code_action`].
 pub fn generate_pypi_constraint_code_actions(package: &PackageInfo, uri: &Url) -> Vec<CodeAction> {
     let current = &package.version;
 
@@ -92,9 +132,9 @@ pub fn generate_pypi_constraint_code_actions(package: &PackageInfo, uri: &Url) -
     };
 
     let alternatives: &[(&str, &str)] = match op {
-        ">=" => &[("==", "Pin to exact version"), ("~=", "Compatible release")],
+        ">=" => &[("~=", "Compatible release")],
         "==" => &[(">=", "Minimum version"), ("~=", "Compatible release")],
-        "~=" => &[("==", "Pin to exact version"), (">=", "Minimum version")],
+        "~=" => &[(">=", "Minimum version")],
         _ => return vec![],
     };
 
@@ -115,7 +155,12 @@ pub fn generate_pypi_constraint_code_actions(package: &PackageInfo, uri: &Url) -
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::types::RegistryType;
+    use crate::parser::types::{ExtraInfo, RegistryType};
+    use crate::version::checker::MockVersionStorer;
+    use crate::version::matchers::{
+        CratesVersionMatcher, GitHubActionsMatcher, NpmVersionMatcher, PnpmCatalogMatcher,
+        PypiVersionMatcher,
+    };
     use rstest::rstest;
 
     fn make_package(name: &str, version: &str, line: u32, column: u32, len: usize) -> PackageInfo {
@@ -124,10 +169,13 @@ mod tests {
             version: version.to_string(),
             commit_hash: None,
             registry_type: RegistryType::Npm,
+            registry_url: None,
             start_offset: 0,
             end_offset: len,
             line: line as usize,
             column: column as usize,
+            section_name: None,
+            section_line: None,
             extra_info: None,
         }
     }
@@ -138,44 +186,124 @@ mod tests {
             version: version.to_string(),
             commit_hash: None,
             registry_type: RegistryType::PyPI,
+            registry_url: None,
             start_offset: 0,
             end_offset: version.len(),
             line: line as usize,
             column: column as usize,
+            section_name: None,
+            section_line: None,
             extra_info: None,
         }
     }
 
+    fn storer(versions: &[&str]) -> MockVersionStorer {
+        let versions: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+        let mut storer = MockVersionStorer::new();
+        storer
+            .expect_get_versions_ordered_by_semver()
+            .returning(move |_, _, _| Ok(versions.clone()));
+        storer
+    }
+
+    // ── Pin action tests ──
+
+    #[rstest]
+    #[case(RegistryType::Npm, "^4.17.0", &["4.16.0", "4.17.0", "4.17.21", "5.0.0"], "4.17.21")]
+    #[case(RegistryType::Npm, "~1.35", &["1.35.0", "1.35.3", "1.36.0"], "1.35.3")]
+    #[case(RegistryType::CratesIo, "1.0", &["1.0.0", "1.2.5", "2.0.0"], "=1.2.5")]
+    #[case(RegistryType::PyPI, ">=2.28, <3", &["2.28.0", "2.31.0", "3.0.0"], "==2.31.0")]
+    #[case(RegistryType::GitHubActions, "v4", &["v3.9.0", "v4.1.0", "v4.2.1"], "v4.2.1")]
+    fn pin_replaces_range_with_highest_satisfying_version(
+        #[case] registry_type: RegistryType,
+        #[case] version: &str,
+        #[case] versions: &[&str],
+        #[case] expected: &str,
+    ) {
+        let package = PackageInfo {
+            registry_type,
+            ..make_package("pkg", version, 3, 15, version.len())
+        };
+        let uri = Url::parse("file:///test/manifest").unwrap();
+        let matcher: Box<dyn VersionMatcher> = match registry_type {
+            RegistryType::CratesIo => Box::new(CratesVersionMatcher),
+            RegistryType::PyPI => Box::new(PypiVersionMatcher),
+            RegistryType::GitHubActions => Box::new(GitHubActionsMatcher),
+            _ => Box::new(NpmVersionMatcher),
+        };
+
+        let action =
+            generate_pin_code_action(&storer(versions), &package, &uri, &*matcher).unwrap();
+
+        assert_eq!(action.title, format!("Pin to exact version: {expected}"));
+        assert_eq!(action.kind, Some(CodeActionKind::REFACTOR));
+        let changes = action.edit.unwrap().changes.unwrap();
+        assert_eq!(changes[&uri][0].new_text, expected);
+    }
+
+    #[rstest]
+    #[case("4.17.21")]
+    #[case("^6.0.0")]
+    #[case("")]
+    fn pin_is_not_offered_for_exact_or_unsatisfied_spec(#[case] version: &str) {
+        let package = make_package("lodash", version, 3, 15, version.len());
+        let uri = Url::parse("file:///test/package.json").unwrap();
+
+        let action = generate_pin_code_action(
+            &storer(&["4.17.20", "4.17.21"]),
+            &package,
+            &uri,
+            &NpmVersionMatcher,
+        );
+
+        assert_eq!(action, None);
+    }
+
+    #[test]
+    fn pin_is_not_offered_for_catalog_reference() {
+        let package = PackageInfo {
+            registry_type: RegistryType::PnpmCatalog,
+            extra_info: Some(ExtraInfo::PnpmCatalogRef {
+                catalog_name: "default".to_string(),
+            }),
+            ..make_package("react", "catalog:", 3, 15, 8)
+        };
+        let uri = Url::parse("file:///test/package.json").unwrap();
+
+        let action =
+            generate_pin_code_action(&storer(&["18.3.1"]), &package, &uri, &PnpmCatalogMatcher);
+
+        assert_eq!(action, None);
+    }
+
     // ── Semver constraint action tests ──
 
     #[test]
-    fn constraint_from_caret_offers_pin_and_tilde() {
+    fn constraint_from_caret_offers_tilde() {
         let package = make_package("lodash", "^4.17.19", 3, 15, 8);
         let uri = Url::parse("file:///test/package.json").unwrap();
 
         let actions = generate_constraint_code_actions(&package, &uri);
 
-        assert_eq!(actions.len(), 2);
-        assert_eq!(actions[0].title, "Pin to exact version: 4.17.19");
-        assert_eq!(actions[1].title, "Patch updates only (~): ~4.17.19");
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Patch updates only (~): ~4.17.19");
 
-        // Verify text edit removes prefix for pin
+        // Verify text edit replaces the prefix
         let edit = actions[0].edit.as_ref().unwrap();
         let changes = edit.changes.as_ref().unwrap();
         let edits = changes.get(&uri).unwrap();
-        assert_eq!(edits[0].new_text, "4.17.19");
+        assert_eq!(edits[0].new_text, "~4.17.19");
     }
 
     #[test]
-    fn constraint_from_tilde_offers_pin_and_caret() {
+    fn constraint_from_tilde_offers_caret() {
         let package = make_package("lodash", "~4.17.19", 3, 15, 8);
         let uri = Url::parse("file:///test/package.json").unwrap();
 
         let actions = generate_constraint_code_actions(&package, &uri);
 
-        assert_eq!(actions.len(), 2);
-        assert_eq!(actions[0].title, "Pin to exact version: 4.17.19");
-        assert_eq!(actions[1].title, "Compatible updates (^): ^4.17.19");
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Compatible updates (^): ^4.17.19");
     }
 
     #[test]
@@ -239,15 +367,14 @@ mod tests {
     // ── PyPI constraint action tests ──
 
     #[test]
-    fn pypi_constraint_from_gte_offers_pin_and_compatible() {
+    fn pypi_constraint_from_gte_offers_compatible() {
         let package = make_pypi_package("requests", ">=2.28.0", 3, 15);
         let uri = Url::parse("file:///test/pyproject.toml").unwrap();
 
         let actions = generate_pypi_constraint_code_actions(&package, &uri);
 
-        assert_eq!(actions.len(), 2);
-        assert_eq!(actions[0].title, "Pin to exact version (==): ==2.28.0");
-        assert_eq!(actions[1].title, "Compatible release (~=): ~=2.28.0");
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Compatible release (~=): ~=2.28.0");
     }
 
     #[test]
@@ -263,15 +390,14 @@ mod tests {
     }
 
     #[test]
-    fn pypi_constraint_from_compatible_offers_pin_and_minimum() {
+    fn pypi_constraint_from_compatible_offers_minimum() {
         let package = make_pypi_package("numpy", "~=1.21.0", 3, 15);
         let uri = Url::parse("file:///test/pyproject.toml").unwrap();
 
         let actions = generate_pypi_constraint_code_actions(&package, &uri);
 
-        assert_eq!(actions.len(), 2);
-        assert_eq!(actions[0].title, "Pin to exact version (==): ==1.21.0");
-        assert_eq!(actions[1].title, "Minimum version (>=): >=1.21.0");
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Minimum version (>=): >=1.21.0");
     }
 
     #[test]
@@ -1,12 +1,20 @@
-//! Code action generation for version bumping and constraint switching
+//! Code action generation for version bumping, constraint switching and
+//! pinning
 
 mod constraint;
 mod upgrade;
 
-pub use constraint::{generate_constraint_code_actions, generate_pypi_constraint_code_actions};
-pub use upgrade::{generate_upgrade_code_actions, generate_upgrade_code_actions_with_sha};
+pub use constraint::{
+    generate_constraint_code_actions, generate_pin_code_action,
+    generate_pypi_constraint_code_actions,
+};
+pub use upgrade::{
+    generate_bulk_update_code_action, generate_update_all_action, generate_upgrade_code_actions,
+    generate_upgrade_code_actions_with_sha,
+};
 
 use crate::parser::types::PackageInfo;
+use crate::version::semver::extract_version_prefix;
 use std::collections::HashMap;
 use tower_lsp::lsp_types::{
     CodeAction, CodeActionKind, Position, Range, TextEdit, Url, WorkspaceEdit,
@@ -44,34 +52,40 @@ impl<'a> PackageIndex<'a> {
             }
         })
     }
-}
 
-/// Extract version prefix (^, ~, ~=, ==, !=, >=, <=, >, <, =, v) from a version string
-fn extract_version_prefix(version: &str) -> &str {
-    if version.starts_with("~=") {
-        "~="
-    } else if version.starts_with(">=") {
-        ">="
-    } else if version.starts_with("<=") {
-        "<="
-    } else if version.starts_with("==") {
-        "=="
-    } else if version.starts_with("!=") {
-        "!="
-    } else if version.starts_with('>') {
-        ">"
-    } else if version.starts_with('<') {
-        "<"
-    } else if version.starts_with('=') {
-        "="
-    } else if version.starts_with('^') {
-        "^"
-    } else if version.starts_with('~') {
-        "~"
-    } else if version.starts_with('v') {
-        "v"
-    } else {
-        ""
+    /// Find all packages whose version overlaps the given range
+    ///
+    /// Packages are returned in document order.
+    pub fn packages_in_range(&self, range: Range) -> Vec<&'a PackageInfo> {
+        (range.start.line..=range.end.line)
+            .filter_map(|line| self.by_line.get(&line))
+            .flat_map(|packages_on_line| {
+                let mut packages_on_line = packages_on_line.clone();
+                packages_on_line.sort_by_key(|pkg| pkg.column);
+                packages_on_line
+            })
+            .filter(|pkg| {
+                let line = pkg.line as u32;
+                let start_col = pkg.column as u32;
+                let end_col = start_col + pkg.version.len() as u32;
+
+                (line > range.start.line || end_col > range.start.character)
+                    && (line < range.end.line || start_col < range.end.character)
+            })
+            .collect()
+    }
+
+    /// Find all packages with the given name, in document order
+    pub fn packages_named(&self, name: &str) -> Vec<&'a PackageInfo> {
+        let mut packages: Vec<&'a PackageInfo> = self
+            .by_line
+            .values()
+            .flatten()
+            .copied()
+            .filter(|pkg| pkg.name == name)
+            .collect();
+        packages.sort_by_key(|pkg| (pkg.line, pkg.column));
+        packages
     }
 }
 
@@ -81,6 +95,28 @@ fn strip_version_prefix(version: &str) -> &str {
     &version[prefix.len()..]
 }
 
+/// Range of a package's version token in the document
+pub fn version_range(package: &PackageInfo) -> Range {
+    Range {
+        start: Position {
+            line: package.line as u32,
+            character: package.column as u32,
+        },
+        end: Position {
+            line: package.line as u32,
+            character: (package.column + package.version.len()) as u32,
+        },
+    }
+}
+
+/// Create a text edit that replaces a package's version text
+fn create_bump_edit(new_version: &str, package: &PackageInfo) -> TextEdit {
+    TextEdit {
+        range: version_range(package),
+        new_text: new_version.to_string(),
+    }
+}
+
 /// Create a code action that replaces a package's version text
 fn create_bump_action(
     title: &str,
@@ -88,22 +124,8 @@ fn create_bump_action(
     package: &PackageInfo,
     uri: &Url,
 ) -> CodeAction {
-    let start = Position {
-        line: package.line as u32,
-        character: package.column as u32,
-    };
-    let end = Position {
-        line: package.line as u32,
-        character: package.column as u32 + package.version.len() as u32,
-    };
-
-    let text_edit = TextEdit {
-        range: Range { start, end },
-        new_text: new_version.to_string(),
-    };
-
     let mut changes = HashMap::new();
-    changes.insert(uri.clone(), vec![text_edit]);
+    changes.insert(uri.clone(), vec![create_bump_edit(new_version, package)]);
 
     CodeAction {
         title: title.to_string(),
@@ -128,10 +150,13 @@ mod tests {
             version: version.to_string(),
             commit_hash: None,
             registry_type: RegistryType::Npm,
+            registry_url: None,
             start_offset: 0,
             end_offset: len,
             line: line as usize,
             column: column as usize,
+            section_name: None,
+            section_line: None,
             extra_info: None,
         }
     }
@@ -174,4 +199,79 @@ mod tests {
         let result = index.find_at_position(position);
         assert_eq!(result.map(|p| p.name.as_str()), expected_name);
     }
+
+    #[rstest]
+    #[case(
+        // whole lines 3-5
+        Range {
+            start: Position { line: 3, character: 0 },
+            end: Position { line: 5, character: 40 },
+        },
+        vec!["lodash", "express", "react"]
+    )]
+    #[case(
+        // starts after the version on line 3
+        Range {
+            start: Position { line: 3, character: 30 },
+            end: Position { line: 5, character: 40 },
+        },
+        vec!["express", "react"]
+    )]
+    #[case(
+        // ends before the version on line 5
+        Range {
+            start: Position { line: 3, character: 0 },
+            end: Position { line: 5, character: 10 },
+        },
+        vec!["lodash", "express"]
+    )]
+    #[case(
+        // partially overlaps the version on line 5
+        Range {
+            start: Position { line: 4, character: 0 },
+            end: Position { line: 5, character: 11 },
+        },
+        vec!["express", "react"]
+    )]
+    #[case(
+        Range {
+            start: Position { line: 6, character: 0 },
+            end: Position { line: 8, character: 0 },
+        },
+        vec![]
+    )]
+    fn test_package_index_packages_in_range(
+        #[case] range: Range,
+        #[case] expected_names: Vec<&str>,
+    ) {
+        let packages = vec![
+            make_package("react", "18.2.0", 5, 10, 6),
+            make_package("lodash", "4.17.21", 3, 15, 7),
+            make_package("express", "4.18.2", 4, 16, 6),
+        ];
+        let index = PackageIndex::new(&packages);
+
+        let result = index.packages_in_range(range);
+
+        let names: Vec<&str> = result.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, expected_names);
+    }
+
+    #[test]
+    fn test_package_index_packages_named() {
+        let packages = vec![
+            make_package("lodash", "4.17.21", 9, 15, 7),
+            make_package("react", "18.2.0", 5, 10, 6),
+            make_package("lodash", "4.17.21", 3, 15, 7),
+        ];
+        let index = PackageIndex::new(&packages);
+
+        let lines: Vec<usize> = index
+            .packages_named("lodash")
+            .iter()
+            .map(|p| p.line)
+            .collect();
+        assert_eq!(lines, vec![3, 9]);
+        assert!(index.packages_named("express").is_empty());
+    }
 }
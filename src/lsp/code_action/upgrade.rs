@@ -9,19 +9,21 @@ use tower_lsp::lsp_types::{
     CodeAction, CodeActionKind, Position, Range, TextEdit, Url, WorkspaceEdit,
 };
 
-use super::{create_bump_action, extract_version_prefix};
+use super::{create_bump_action, create_bump_edit};
 
 /// Compute deduplicated bump targets from smallest to largest jump.
 ///
-/// Returns `(bare_version, label)` pairs with duplicates removed. Bump target
+/// Returns `(version, label)` pairs with duplicates removed. Bump target
 /// calculation is delegated to the registry-specific matcher so suffix-aware
 /// formats (e.g. Docker tags) can override the default semver behavior.
+/// Pre-releases are offered only with `include_pre_release` (`includePreRelease`).
 fn compute_bump_targets<'a>(
     current: &str,
     versions: &[String],
     matcher: &dyn VersionMatcher,
+    include_pre_release: bool,
 ) -> Vec<(String, &'a str)> {
-    let targets = matcher.calculate_bump_targets(current, versions);
+    let targets = matcher.calculate_bump_targets(current, versions, include_pre_release);
 
     // Only include "next" targets when they differ from "latest" — otherwise
     // the "next" label would win the dedup race and hide the "latest" label.
@@ -57,15 +59,20 @@ fn compute_bump_targets<'a>(
 /// Generate upgrade code actions
 ///
 /// Creates up to 5 code actions (patch, next minor, minor, next major, major)
-/// based on available versions. Preserves the current version prefix.
+/// based on available versions. The replacement text comes from
+/// [`VersionMatcher::suggest_upgrade`], which preserves the current version prefix.
 /// Returns an empty Vec if no newer versions are available or if versions are not in cache.
 pub fn generate_upgrade_code_actions<S: VersionStorer>(
     storer: &S,
     package: &PackageInfo,
     uri: &Url,
     matcher: &dyn VersionMatcher,
+    include_pre_release: bool,
 ) -> Vec<CodeAction> {
-    let Ok(versions) = storer.get_versions(package.registry_type, &package.name) else {
+    // Yanked versions are never suggested
+    let Ok(versions) =
+        storer.get_versions_ordered_by_semver(package.registry_type, &package.name, false)
+    else {
         return vec![];
     };
 
@@ -74,12 +81,11 @@ pub fn generate_upgrade_code_actions<S: VersionStorer>(
     }
 
     let current = &package.version;
-    let prefix = extract_version_prefix(current);
 
-    compute_bump_targets(current, &versions, matcher)
+    compute_bump_targets(current, &versions, matcher, include_pre_release)
         .into_iter()
         .map(|(v, label)| {
-            let new_version = format!("{prefix}{v}");
+            let new_version = matcher.suggest_upgrade(current, &v);
             create_bump_action(
                 &format!("Upgrade to {label}: {new_version}"),
                 &new_version,
@@ -90,6 +96,104 @@ pub fn generate_upgrade_code_actions<S: VersionStorer>(
         .collect()
 }
 
+/// Edits upgrading every given package that has a newer version to its
+/// largest bump target
+///
+/// Packages pinned to a commit hash are skipped, since their SHA would have
+/// to be fetched for the new version.
+fn largest_bump_edits<S: VersionStorer>(
+    storer: &S,
+    packages: &[(&PackageInfo, &dyn VersionMatcher)],
+    include_pre_release: bool,
+) -> Vec<TextEdit> {
+    packages
+        .iter()
+        .filter(|(package, _)| package.commit_hash.is_none())
+        .filter_map(|(package, matcher)| {
+            let versions = storer
+                .get_versions_ordered_by_semver(package.registry_type, &package.name, false)
+                .ok()?;
+            let (latest, _) =
+                compute_bump_targets(&package.version, &versions, *matcher, include_pre_release)
+                    .into_iter()
+                    .last()?;
+            Some(create_bump_edit(
+                &matcher.suggest_upgrade(&package.version, &latest),
+                package,
+            ))
+        })
+        .collect()
+}
+
+/// Code action applying `edits` to the document at `uri`
+fn bulk_edit_action(
+    title: String,
+    kind: CodeActionKind,
+    edits: Vec<TextEdit>,
+    uri: &Url,
+) -> CodeAction {
+    let mut changes = HashMap::new();
+    changes.insert(uri.clone(), edits);
+
+    CodeAction {
+        title,
+        kind: Some(kind),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Generate a single code action that upgrades every given package to its
+/// largest bump target, for "update all in selection"
+///
+/// Returns None unless at least two packages can be upgraded; a single
+/// upgrade is already offered on its own.
+pub fn generate_update_all_action<S: VersionStorer>(
+    storer: &S,
+    packages: &[(&PackageInfo, &dyn VersionMatcher)],
+    uri: &Url,
+    include_pre_release: bool,
+) -> Option<CodeAction> {
+    let edits = largest_bump_edits(storer, packages, include_pre_release);
+    if edits.len() < 2 {
+        return None;
+    }
+
+    Some(bulk_edit_action(
+        format!("Update all in selection ({} packages)", edits.len()),
+        CodeActionKind::QUICKFIX,
+        edits,
+        uri,
+    ))
+}
+
+/// Generate a source action that upgrades every outdated package of a
+/// document to its largest bump target
+///
+/// Offered for the whole document (a `source` action request, or a
+/// selection covering the document). Returns None when nothing is outdated.
+pub fn generate_bulk_update_code_action<S: VersionStorer>(
+    storer: &S,
+    packages: &[(&PackageInfo, &dyn VersionMatcher)],
+    uri: &Url,
+    include_pre_release: bool,
+) -> Option<CodeAction> {
+    let edits = largest_bump_edits(storer, packages, include_pre_release);
+    if edits.is_empty() {
+        return None;
+    }
+
+    Some(bulk_edit_action(
+        "Update all outdated dependencies".to_string(),
+        CodeActionKind::SOURCE,
+        edits,
+        uri,
+    ))
+}
+
 /// Generate upgrade code actions with SHA fetching for GitHub Actions
 ///
 /// When the package has a commit hash (GitHub Actions), this function will fetch
@@ -101,8 +205,12 @@ pub async fn generate_upgrade_code_actions_with_sha<S: VersionStorer, F: TagShaF
     uri: &Url,
     sha_fetcher: &F,
     matcher: &dyn VersionMatcher,
+    include_pre_release: bool,
 ) -> Vec<CodeAction> {
-    let Ok(versions) = storer.get_versions(package.registry_type, &package.name) else {
+    // Yanked versions are never suggested
+    let Ok(versions) =
+        storer.get_versions_ordered_by_semver(package.registry_type, &package.name, false)
+    else {
         return vec![];
     };
 
@@ -120,12 +228,11 @@ pub async fn generate_upgrade_code_actions_with_sha<S: VersionStorer, F: TagShaF
     }
 
     let current = &package.version;
-    let prefix = extract_version_prefix(current);
 
     let mut actions = Vec::new();
 
-    for (v, label) in compute_bump_targets(current, &versions, matcher) {
-        let new_version = format!("{prefix}{v}");
+    for (v, label) in compute_bump_targets(current, &versions, matcher, include_pre_release) {
+        let new_version = matcher.suggest_upgrade(current, &v);
 
         // If package has a commit hash, we need to fetch the SHA for the new version
         if package.commit_hash.is_some() {
@@ -212,7 +319,7 @@ fn create_hash_bump_action(
             let end_col = package.column + (comment_end_offset - hash_start_offset);
             (end_col as u32, format!("{new_sha} # {new_version}"))
         }
-        None => {
+        _ => {
             // Pattern 1: Hash only
             // Replace just the hash (40 characters)
             let hash_len = package.commit_hash.as_ref().map(|h| h.len()).unwrap_or(40);
@@ -250,7 +357,7 @@ mod tests {
     use crate::parser::types::RegistryType;
     use crate::version::cache::PackageId;
     use crate::version::error::{CacheError, RegistryError};
-    use crate::version::matchers::{GitHubActionsMatcher, NpmVersionMatcher};
+    use crate::version::matchers::{CratesVersionMatcher, GitHubActionsMatcher, NpmVersionMatcher};
     use rstest::rstest;
 
     fn make_package(name: &str, version: &str, line: u32, column: u32, len: usize) -> PackageInfo {
@@ -259,10 +366,13 @@ mod tests {
             version: version.to_string(),
             commit_hash: None,
             registry_type: RegistryType::Npm,
+            registry_url: None,
             start_offset: 0,
             end_offset: len,
             line: line as usize,
             column: column as usize,
+            section_name: None,
+            section_line: None,
             extra_info: None,
         }
     }
@@ -293,6 +403,7 @@ mod tests {
             &self,
             _registry_type: RegistryType,
             _package_name: &str,
+            _include_yanked: bool,
         ) -> Result<Vec<String>, CacheError> {
             Ok(self.versions.clone())
         }
@@ -302,6 +413,7 @@ mod tests {
             _registry_type: RegistryType,
             _package_name: &str,
             version: &str,
+            _include_yanked: bool,
         ) -> Result<bool, CacheError> {
             Ok(self.versions.contains(&version.to_string()))
         }
@@ -311,6 +423,15 @@ mod tests {
             _registry_type: RegistryType,
             _package_name: &str,
             _versions: Vec<String>,
+            _yanked: &[String],
+        ) -> Result<(), CacheError> {
+            Ok(())
+        }
+
+        fn touch_package(
+            &self,
+            _registry_type: RegistryType,
+            _package_name: &str,
         ) -> Result<(), CacheError> {
             Ok(())
         }
@@ -376,7 +497,8 @@ mod tests {
         let package = make_package("lodash", "4.17.19", 3, 15, 7);
         let uri = Url::parse("file:///test/package.json").unwrap();
 
-        let actions = generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher);
+        let actions =
+            generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher, false);
 
         assert_eq!(actions.len(), 3);
         assert_eq!(actions[0].title, "Upgrade to latest patch: 4.17.21");
@@ -384,13 +506,101 @@ mod tests {
         assert_eq!(actions[2].title, "Upgrade to latest major: 5.0.0");
     }
 
+    #[test]
+    fn update_all_action_upgrades_each_package_to_largest_target() {
+        let storer = MockStorer::new(vec!["4.17.19", "4.17.21", "4.18.0", "5.0.0"]);
+        let lodash = make_package("lodash", "^4.17.19", 3, 15, 9);
+        let underscore = make_package("underscore", "4.18.0", 4, 19, 6);
+        let latest = make_package("latest", "5.0.0", 5, 15, 5);
+        let uri = Url::parse("file:///test/package.json").unwrap();
+
+        let action = generate_update_all_action(
+            &storer,
+            &[
+                (&lodash, &NpmVersionMatcher),
+                (&underscore, &NpmVersionMatcher),
+                (&latest, &NpmVersionMatcher),
+            ],
+            &uri,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(action.title, "Update all in selection (2 packages)");
+        let changes = action.edit.unwrap().changes.unwrap();
+        let edits: Vec<(u32, &str)> = changes[&uri]
+            .iter()
+            .map(|edit| (edit.range.start.line, edit.new_text.as_str()))
+            .collect();
+        assert_eq!(edits, vec![(3, "^5.0.0"), (4, "5.0.0")]);
+    }
+
+    #[test]
+    fn update_all_action_returns_none_for_single_upgrade() {
+        let storer = MockStorer::new(vec!["4.17.19", "4.17.21"]);
+        let lodash = make_package("lodash", "4.17.19", 3, 15, 7);
+        let latest = make_package("latest", "4.17.21", 4, 15, 7);
+        let uri = Url::parse("file:///test/package.json").unwrap();
+
+        let action = generate_update_all_action(
+            &storer,
+            &[(&lodash, &NpmVersionMatcher), (&latest, &NpmVersionMatcher)],
+            &uri,
+            false,
+        );
+
+        assert!(action.is_none());
+    }
+
+    #[test]
+    fn bulk_update_action_upgrades_every_outdated_package() {
+        let storer = MockStorer::new(vec!["4.17.19", "4.17.21", "5.0.0"]);
+        let lodash = make_package("lodash", "4.17.19", 3, 15, 7);
+        let latest = make_package("latest", "5.0.0", 4, 15, 5);
+        let uri = Url::parse("file:///test/package.json").unwrap();
+
+        let action = generate_bulk_update_code_action(
+            &storer,
+            &[(&lodash, &NpmVersionMatcher), (&latest, &NpmVersionMatcher)],
+            &uri,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(action.title, "Update all outdated dependencies");
+        assert_eq!(action.kind, Some(CodeActionKind::SOURCE));
+        let changes = action.edit.unwrap().changes.unwrap();
+        let edits: Vec<(u32, &str)> = changes[&uri]
+            .iter()
+            .map(|edit| (edit.range.start.line, edit.new_text.as_str()))
+            .collect();
+        assert_eq!(edits, vec![(3, "5.0.0")]);
+    }
+
+    #[test]
+    fn bulk_update_action_returns_none_when_nothing_is_outdated() {
+        let storer = MockStorer::new(vec!["4.17.19", "5.0.0"]);
+        let latest = make_package("latest", "5.0.0", 4, 15, 5);
+        let uri = Url::parse("file:///test/package.json").unwrap();
+
+        let action = generate_bulk_update_code_action(
+            &storer,
+            &[(&latest, &NpmVersionMatcher)],
+            &uri,
+            false,
+        );
+
+        assert!(action.is_none());
+    }
+
     #[test]
     fn upgrade_returns_empty_when_no_versions_in_cache() {
         let storer = MockStorer::new(vec![]);
         let package = make_package("lodash", "4.17.19", 3, 15, 7);
         let uri = Url::parse("file:///test/package.json").unwrap();
 
-        let actions = generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher);
+        let actions =
+            generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher, false);
 
         assert!(actions.is_empty());
     }
@@ -401,7 +611,8 @@ mod tests {
         let package = make_package("lodash", "5.0.0", 3, 15, 5);
         let uri = Url::parse("file:///test/package.json").unwrap();
 
-        let actions = generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher);
+        let actions =
+            generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher, false);
 
         assert!(actions.is_empty());
     }
@@ -412,7 +623,8 @@ mod tests {
         let package = make_package("lodash", "4.17.19", 3, 15, 7);
         let uri = Url::parse("file:///test/package.json").unwrap();
 
-        let actions = generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher);
+        let actions =
+            generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher, false);
 
         assert_eq!(actions.len(), 1);
         let edit = actions[0].edit.as_ref().unwrap();
@@ -441,7 +653,8 @@ mod tests {
         let package = make_package("lodash", "^4.17.19", 3, 15, 8);
         let uri = Url::parse("file:///test/package.json").unwrap();
 
-        let actions = generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher);
+        let actions =
+            generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher, false);
 
         assert_eq!(actions.len(), 3);
         assert_eq!(actions[0].title, "Upgrade to latest patch: ^4.17.21");
@@ -461,7 +674,8 @@ mod tests {
         let package = make_package("lodash", "~4.17.19", 3, 15, 8);
         let uri = Url::parse("file:///test/package.json").unwrap();
 
-        let actions = generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher);
+        let actions =
+            generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher, false);
 
         assert_eq!(actions.len(), 1);
         assert_eq!(actions[0].title, "Upgrade to latest patch: ~4.17.21");
@@ -473,19 +687,41 @@ mod tests {
         let package = make_package("lodash", ">=4.17.19", 3, 15, 9);
         let uri = Url::parse("file:///test/package.json").unwrap();
 
-        let actions = generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher);
+        let actions =
+            generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher, false);
 
         assert_eq!(actions.len(), 1);
         assert_eq!(actions[0].title, "Upgrade to latest major: >=5.0.0");
     }
 
+    #[test]
+    fn upgrade_replaces_whole_multi_constraint_spec() {
+        let storer = MockStorer::new(vec!["1.0.0", "2.1.0"]);
+        let package = PackageInfo {
+            registry_type: RegistryType::CratesIo,
+            ..make_package("serde", ">=1.0, <2.0", 3, 11, 11)
+        };
+        let uri = Url::parse("file:///test/Cargo.toml").unwrap();
+
+        let actions =
+            generate_upgrade_code_actions(&storer, &package, &uri, &CratesVersionMatcher, false);
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Upgrade to latest major: >=2.1.0, <3.0");
+        let edits = &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(edits[0].new_text, ">=2.1.0, <3.0");
+        assert_eq!(edits[0].range.start.character, 11);
+        assert_eq!(edits[0].range.end.character, 22);
+    }
+
     #[test]
     fn upgrade_preserves_v_prefix_for_go() {
         let storer = MockStorer::new(vec!["0.14.0", "0.15.0", "1.0.0"]);
         let package = make_package("golang.org/x/text", "v0.14.0", 3, 15, 7);
         let uri = Url::parse("file:///test/go.mod").unwrap();
 
-        let actions = generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher);
+        let actions =
+            generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher, false);
 
         assert_eq!(actions.len(), 2);
         assert_eq!(actions[0].title, "Upgrade to latest minor: v0.15.0");
@@ -498,7 +734,8 @@ mod tests {
         let package = make_package("lodash", "^2.0.0", 3, 15, 6);
         let uri = Url::parse("file:///test/package.json").unwrap();
 
-        let actions = generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher);
+        let actions =
+            generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher, false);
 
         assert_eq!(actions.len(), 2);
         assert_eq!(actions[0].title, "Upgrade to next major: ^3.5.0");
@@ -511,13 +748,40 @@ mod tests {
         let package = make_package("lodash", "^4.17.0", 3, 15, 7);
         let uri = Url::parse("file:///test/package.json").unwrap();
 
-        let actions = generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher);
+        let actions =
+            generate_upgrade_code_actions(&storer, &package, &uri, &NpmVersionMatcher, false);
 
         assert_eq!(actions.len(), 2);
         assert_eq!(actions[0].title, "Upgrade to next minor: ^4.18.5");
         assert_eq!(actions[1].title, "Upgrade to latest minor: ^4.20.0");
     }
 
+    #[rstest]
+    #[case(false, vec!["Upgrade to latest major: ^5.0.0"])]
+    #[case(
+        true,
+        vec!["Upgrade to next major: ^5.0.0", "Upgrade to latest major: ^6.0.0-rc.1"]
+    )]
+    fn upgrade_offers_pre_release_only_when_included(
+        #[case] include_pre_release: bool,
+        #[case] expected: Vec<&str>,
+    ) {
+        let storer = MockStorer::new(vec!["4.0.0", "5.0.0", "6.0.0-rc.1"]);
+        let package = make_package("lodash", "^4.0.0", 3, 15, 6);
+        let uri = Url::parse("file:///test/package.json").unwrap();
+
+        let actions = generate_upgrade_code_actions(
+            &storer,
+            &package,
+            &uri,
+            &NpmVersionMatcher,
+            include_pre_release,
+        );
+
+        let titles: Vec<&str> = actions.iter().map(|a| a.title.as_str()).collect();
+        assert_eq!(titles, expected);
+    }
+
     // ── Upgrade with SHA tests ──
 
     /// Mock TagShaFetcher for testing
@@ -574,10 +838,13 @@ mod tests {
             version: version.to_string(),
             commit_hash: Some(commit_hash.to_string()),
             registry_type: RegistryType::GitHubActions,
+            registry_url: None,
             start_offset: column as usize,
             end_offset: column as usize + commit_hash.len(),
             line: line as usize,
             column: column as usize,
+            section_name: None,
+            section_line: None,
             extra_info: None,
         }
     }
@@ -596,10 +863,13 @@ mod tests {
             version: version.to_string(),
             commit_hash: Some(commit_hash.to_string()),
             registry_type: RegistryType::GitHubActions,
+            registry_url: None,
             start_offset: column as usize,
             end_offset: column as usize + commit_hash.len(),
             line: line as usize,
             column: column as usize,
+            section_name: None,
+            section_line: None,
             extra_info: Some(ExtraInfo::GitHubActions {
                 comment_text: version.to_string(),
                 comment_start_offset: comment_start,
@@ -628,6 +898,7 @@ mod tests {
             &uri,
             &sha_fetcher,
             &GitHubActionsMatcher,
+            false,
         )
         .await;
 
@@ -670,6 +941,7 @@ mod tests {
             &uri,
             &sha_fetcher,
             &GitHubActionsMatcher,
+            false,
         )
         .await;
 
@@ -709,6 +981,7 @@ mod tests {
             &uri,
             &sha_fetcher,
             &GitHubActionsMatcher,
+            false,
         )
         .await;
 
@@ -728,6 +1001,7 @@ mod tests {
             &uri,
             &sha_fetcher,
             &GitHubActionsMatcher,
+            false,
         )
         .await;
 
@@ -743,16 +1017,28 @@ mod tests {
         assert_eq!(edits[0].range.end.character, 37);
     }
 
-    #[rstest]
-    #[case("^4.17.19", "^")]
-    #[case("~4.17.19", "~")]
-    #[case("4.17.19", "")]
-    #[case(">=2.0.0", ">=")]
-    #[case("==2.0.0", "==")]
-    #[case("~=1.4.2", "~=")]
-    #[case("!=2.0.0", "!=")]
-    #[case("v1.0.0", "v")]
-    fn test_extract_version_prefix(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(extract_version_prefix(input), expected);
+    #[tokio::test]
+    async fn upgrade_with_sha_pattern3_preserves_v_prefix_for_patch_bump() {
+        let storer = MockStorer::new(vec!["v4.1.6", "v4.1.7"]);
+        let sha_fetcher = MockTagShaFetcher::new(vec![]);
+        let package = make_package("actions/checkout", "v4.1.6", 4, 31, 6);
+        let uri = Url::parse("file:///test/.github/workflows/ci.yml").unwrap();
+
+        let actions = generate_upgrade_code_actions_with_sha(
+            &storer,
+            &package,
+            &uri,
+            &sha_fetcher,
+            &GitHubActionsMatcher,
+            false,
+        )
+        .await;
+
+        let new_texts: Vec<&str> = actions
+            .iter()
+            .flat_map(|action| action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri].iter())
+            .map(|edit| edit.new_text.as_str())
+            .collect();
+        assert_eq!(new_texts, vec!["v4.1.7"]);
     }
 }
@@ -0,0 +1,228 @@
+//! Completion of dependency versions from the cached versions
+
+use std::cmp::Ordering;
+
+use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind, CompletionTextEdit, TextEdit};
+use tracing::warn;
+
+use crate::lsp::code_action::version_range;
+use crate::parser::types::PackageInfo;
+use crate::version::checker::VersionStorer;
+use crate::version::semver::{extract_version_prefix, is_prerelease, parse_version};
+
+/// Options for version completions
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompletionContext {
+    /// Leave out pre-release versions, unless the current version is one
+    pub ignore_prerelease: bool,
+}
+
+/// Completions for the version of `package`, newest first
+///
+/// Each completion keeps the operator of the current version (`^4.0.0`
+/// completes to `^4.1.0`) and is labeled with how recent it is, e.g.
+/// `4.17.21 (latest)` or `4.17.20 (1 release behind)`. Compound ranges
+/// (`>=2.28,<3.0`, `^1.0 || ^2.0`) are not completed, as replacing them with
+/// one version would drop their other bounds.
+pub fn generate_completions(
+    package: &PackageInfo,
+    storer: &dyn VersionStorer,
+    context: CompletionContext,
+) -> Vec<CompletionItem> {
+    let prefix = extract_version_prefix(&package.version);
+    let current = package.version[prefix.len()..].trim_start();
+    if current.contains([',', ' ', '|']) {
+        return Vec::new();
+    }
+
+    // Yanked versions are never suggested
+    let Ok(versions) = storer
+        .get_versions_ordered_by_semver(package.registry_type, &package.name, false)
+        .inspect_err(|e| warn!("Failed to get versions of {}: {}", package.name, e))
+    else {
+        return Vec::new();
+    };
+    let latest = storer
+        .get_latest_version(package.registry_type, &package.name)
+        .inspect_err(|e| warn!("Failed to get latest version of {}: {}", package.name, e))
+        .ok()
+        .flatten();
+
+    let skip_prerelease = context.ignore_prerelease && !is_prerelease(current);
+    let newest_first: Vec<&String> = versions
+        .iter()
+        .rev()
+        .filter(|version| parse_version(version).is_some())
+        .filter(|version| !(skip_prerelease && is_prerelease(version)))
+        .collect();
+    let latest_index = latest
+        .as_ref()
+        .and_then(|latest| newest_first.iter().position(|version| *version == latest));
+
+    newest_first
+        .iter()
+        .enumerate()
+        .map(|(index, version)| {
+            let recency = latest_index.map(|latest_index| match index.cmp(&latest_index) {
+                Ordering::Equal => "latest".to_string(),
+                Ordering::Less => "pre-release".to_string(),
+                Ordering::Greater => {
+                    let behind = index - latest_index;
+                    let plural = if behind == 1 { "" } else { "s" };
+                    format!("{} release{} behind", behind, plural)
+                }
+            });
+            completion_item(package, prefix, version, index, recency.as_deref())
+        })
+        .collect()
+}
+
+/// Completion replacing the version of `package` with `prefix` + `version`
+fn completion_item(
+    package: &PackageInfo,
+    prefix: &str,
+    version: &str,
+    index: usize,
+    recency: Option<&str>,
+) -> CompletionItem {
+    let new_text = format!("{}{}", prefix, version);
+    CompletionItem {
+        label: match recency {
+            Some(recency) => format!("{} ({})", version, recency),
+            None => version.to_string(),
+        },
+        kind: Some(CompletionItemKind::VALUE),
+        preselect: Some(recency == Some("latest")),
+        // Keep the newest-first order instead of the client's label order
+        sort_text: Some(format!("{:05}", index)),
+        filter_text: Some(new_text.clone()),
+        text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+            range: version_range(package),
+            new_text,
+        })),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::types::RegistryType;
+    use crate::version::checker::MockVersionStorer;
+    use rstest::rstest;
+    use tower_lsp::lsp_types::{Position, Range};
+
+    fn package(version: &str) -> PackageInfo {
+        PackageInfo {
+            name: "lodash".to_string(),
+            version: version.to_string(),
+            commit_hash: None,
+            registry_type: RegistryType::Npm,
+            registry_url: None,
+            start_offset: 0,
+            end_offset: version.len(),
+            line: 2,
+            column: 15,
+            section_name: None,
+            section_line: None,
+            extra_info: None,
+        }
+    }
+
+    fn storer(versions: &[&str], latest: &str) -> MockVersionStorer {
+        let versions: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+        let latest = latest.to_string();
+        let mut storer = MockVersionStorer::new();
+        storer
+            .expect_get_versions_ordered_by_semver()
+            .returning(move |_, _, _| Ok(versions.clone()));
+        storer
+            .expect_get_latest_version()
+            .returning(move |_, _| Ok(Some(latest.clone())));
+        storer
+    }
+
+    fn completions(version: &str, context: CompletionContext) -> Vec<(String, String)> {
+        let storer = storer(&["4.16.0", "4.17.20", "4.17.21", "5.0.0-beta.1"], "4.17.21");
+
+        generate_completions(&package(version), &storer, context)
+            .into_iter()
+            .map(|item| {
+                let Some(CompletionTextEdit::Edit(edit)) = item.text_edit else {
+                    panic!("Expected a text edit");
+                };
+                (item.label, edit.new_text)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn generate_completions_lists_versions_newest_first_with_recency() {
+        assert_eq!(
+            completions("^4.0.0", CompletionContext::default()),
+            vec![
+                (
+                    "5.0.0-beta.1 (pre-release)".to_string(),
+                    "^5.0.0-beta.1".to_string()
+                ),
+                ("4.17.21 (latest)".to_string(), "^4.17.21".to_string()),
+                (
+                    "4.17.20 (1 release behind)".to_string(),
+                    "^4.17.20".to_string()
+                ),
+                (
+                    "4.16.0 (2 releases behind)".to_string(),
+                    "^4.16.0".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn generate_completions_replaces_version_range_and_preselects_latest() {
+        let storer = storer(&["4.17.20", "4.17.21"], "4.17.21");
+
+        let items =
+            generate_completions(&package("~4.17.0"), &storer, CompletionContext::default());
+
+        assert_eq!(items[0].preselect, Some(true));
+        assert_eq!(items[1].preselect, Some(false));
+        assert_eq!(
+            items[0].text_edit,
+            Some(CompletionTextEdit::Edit(TextEdit {
+                range: Range::new(Position::new(2, 15), Position::new(2, 22)),
+                new_text: "~4.17.21".to_string(),
+            }))
+        );
+    }
+
+    #[rstest]
+    #[case("4.0.0", false)]
+    #[case("5.0.0-alpha.1", true)]
+    fn generate_completions_leaves_out_prereleases_when_ignored(
+        #[case] version: &str,
+        #[case] expected_prerelease: bool,
+    ) {
+        let context = CompletionContext {
+            ignore_prerelease: true,
+        };
+
+        let labels: Vec<String> = completions(version, context)
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect();
+
+        assert_eq!(
+            labels.contains(&"5.0.0-beta.1 (pre-release)".to_string()),
+            expected_prerelease
+        );
+        assert!(labels.contains(&"4.17.21 (latest)".to_string()));
+    }
+
+    #[rstest]
+    #[case(">=4.0.0,<5.0.0")]
+    #[case("^3.0.0 || ^4.0.0")]
+    fn generate_completions_skips_compound_ranges(#[case] version: &str) {
+        assert!(completions(version, CompletionContext::default()).is_empty());
+    }
+}
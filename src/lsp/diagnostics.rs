@@ -1,63 +1,388 @@
 //! Diagnostics generation for version checking results
 
-use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde_json::json;
+use tower_lsp::lsp_types::{
+    Diagnostic, DiagnosticSeverity, DiagnosticTag, NumberOrString, Position, Range,
+};
 use tracing::warn;
 
+use crate::config::SeverityLevel;
+use crate::parser::go_mod::commit_date;
 use crate::parser::traits::Parser;
-use crate::parser::types::PackageInfo;
+use crate::parser::types::{ExtraInfo, PackageInfo, RegistryType};
 use crate::version::checker::{
-    VersionCompareResult, VersionStatus, VersionStorer, compare_version,
+    VersionCompareResult, VersionStatus, VersionStorer, compare_version_with_versions,
 };
 use crate::version::matcher::VersionMatcher;
+use crate::version::matchers::npm::npm_spec_satisfies_range;
+use crate::version::semver::parse_version;
+use crate::version::types::Advisory;
 
 const PACKAGE_NAME: &str = env!("CARGO_PKG_NAME");
 
+/// Stable diagnostic codes, so editors can filter or suppress classes of diagnostics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum DiagnosticCode {
+    /// A newer version is available
+    Outdated = 1001,
+    /// The version does not exist in the registry
+    NotFound = 1002,
+    /// The version string cannot be interpreted
+    InvalidFormat = 1003,
+    /// The Go module version has no go.sum entry
+    MissingGoSum = 1005,
+    /// The registry marks the package as deprecated
+    Deprecated = 1006,
+    /// The latest version has a known security advisory
+    Advisory = 1007,
+    /// A devDependencies version is outside the peerDependencies range
+    PeerDependencyMismatch = 1008,
+    /// The package's own package.json version is not published
+    UnpublishedOwnVersion = 1009,
+}
+
+impl From<DiagnosticCode> for NumberOrString {
+    fn from(code: DiagnosticCode) -> Self {
+        NumberOrString::Number(code as i32)
+    }
+}
+
+impl From<SeverityLevel> for DiagnosticSeverity {
+    fn from(level: SeverityLevel) -> Self {
+        match level {
+            SeverityLevel::Error => DiagnosticSeverity::ERROR,
+            SeverityLevel::Warning => DiagnosticSeverity::WARNING,
+            SeverityLevel::Information => DiagnosticSeverity::INFORMATION,
+            SeverityLevel::Hint => DiagnosticSeverity::HINT,
+        }
+    }
+}
+
 /// Generate diagnostics for a document by parsing and checking versions
+///
+/// Each package is checked with the matcher of its own registry, so one
+/// document can mix registries (e.g. pip requirements in environment.yml).
+/// Packages whose registry has no matcher in `matchers` are skipped.
+///
+/// `outdated_severity` is used for "update available" diagnostics, so lock
+/// files can report drift less loudly than manifests.
+///
+/// `show_yanked` counts versions the registry yanked as existing, so a
+/// version pinned to one is not reported as not found.
+///
+/// A deprecated package gets a separate deprecation diagnostic, in addition
+/// to any version diagnostic. A package at the latest version gets one
+/// diagnostic per security advisory affecting that version.
 pub fn generate_diagnostics<S: VersionStorer>(
     parser: &dyn Parser,
-    matcher: &dyn VersionMatcher,
+    matchers: &HashMap<RegistryType, Arc<dyn VersionMatcher>>,
     storer: &S,
     content: &str,
+    outdated_severity: DiagnosticSeverity,
+    show_yanked: bool,
 ) -> Vec<Diagnostic> {
     let packages = parser
         .parse(content)
         .inspect_err(|e| warn!("Failed to parse document: {}", e))
         .unwrap_or_default();
 
+    let packages: Vec<(&PackageInfo, &Arc<dyn VersionMatcher>)> = packages
+        .iter()
+        .filter(|package| !package.is_git_dependency())
+        .filter_map(|package| Some((package, matchers.get(&package.registry_type)?)))
+        .collect();
+    if packages.is_empty() {
+        return Vec::new();
+    }
+
+    // Look up every package in one go instead of several storer calls per
+    // package
+    let lookups: Vec<(RegistryType, &str)> = packages
+        .iter()
+        .map(|(package, _)| (package.registry_type, package.name.as_str()))
+        .collect();
+    let Ok(versions) = storer
+        .batch_get_versions(&lookups, show_yanked)
+        .inspect_err(|e| warn!("Failed to get versions: {}", e))
+    else {
+        return Vec::new();
+    };
+    let Ok(summaries) = storer
+        .batch_get_package_summaries(&lookups)
+        .inspect_err(|e| warn!("Failed to get package summaries: {}", e))
+    else {
+        return Vec::new();
+    };
+
+    packages
+        .into_iter()
+        .flat_map(|(package, matcher)| {
+            let key = (package.registry_type, package.name.clone());
+            // Packages without a latest version are not cached yet
+            let Some(summary) = summaries.get(&key) else {
+                return Vec::new();
+            };
+            let package_versions = versions.get(&key).map(Vec::as_slice).unwrap_or_default();
+            let result = compare_version_with_versions(
+                &**matcher,
+                &package.version,
+                package_versions,
+                &summary.latest_version,
+                summary.dist_tags.get(&package.version).map(String::as_str),
+            );
+            if result.status == VersionStatus::NotInCache {
+                return Vec::new();
+            }
+
+            // Advisories are checked against the latest version, so they
+            // only apply to packages already at it
+            let advisories = if result.status == VersionStatus::Latest {
+                summary.advisories.as_slice()
+            } else {
+                &[]
+            };
+
+            create_diagnostic(package, &**matcher, &result, outdated_severity)
+                .into_iter()
+                .chain(
+                    summary
+                        .deprecated
+                        .as_deref()
+                        .map(|message| create_deprecated_diagnostic(package, message)),
+                )
+                .chain(
+                    advisories
+                        .iter()
+                        .map(|advisory| create_advisory_diagnostic(package, advisory)),
+                )
+                .collect()
+        })
+        .collect()
+}
+
+/// Create a diagnostic tagged as deprecated, carrying the registry's message
+fn create_deprecated_diagnostic(package: &PackageInfo, message: &str) -> Diagnostic {
+    Diagnostic {
+        tags: Some(vec![DiagnosticTag::DEPRECATED]),
+        ..build_diagnostic(
+            package,
+            DiagnosticCode::Deprecated,
+            DiagnosticSeverity::WARNING,
+            format!("{} is deprecated: {}", package.name, message),
+        )
+    }
+}
+
+/// Create a diagnostic tagged as deprecated for a security advisory
+fn create_advisory_diagnostic(package: &PackageInfo, advisory: &Advisory) -> Diagnostic {
+    Diagnostic {
+        tags: Some(vec![DiagnosticTag::DEPRECATED]),
+        ..build_diagnostic(
+            package,
+            DiagnosticCode::Advisory,
+            DiagnosticSeverity::WARNING,
+            format!(
+                "{} has security advisory {} ({}): {}",
+                package.name, advisory.id, advisory.severity, advisory.summary
+            ),
+        )
+    }
+}
+
+/// Generate diagnostics for go.mod requirements missing from go.sum
+///
+/// `go_sum` holds the `(module, version)` pairs of the go.sum next to the
+/// go.mod. A missing entry means the version was never downloaded and
+/// verified, so `go build` will fail until `go mod tidy` is run.
+pub fn generate_go_sum_diagnostics(
+    packages: &[PackageInfo],
+    go_sum: &HashSet<(String, String)>,
+) -> Vec<Diagnostic> {
     packages
         .iter()
-        .filter_map(|package| {
-            let result = compare_version(storer, matcher, &package.name, &package.version).ok()?;
-            create_diagnostic(package, &result)
+        .filter(|package| package.registry_type == RegistryType::GoProxy)
+        .filter(|package| !go_sum.contains(&(package.name.clone(), package.version.clone())))
+        .map(|package| {
+            build_diagnostic(
+                package,
+                DiagnosticCode::MissingGoSum,
+                DiagnosticSeverity::INFORMATION,
+                format!(
+                    "Missing go.sum entry for {} {}",
+                    package.name, package.version
+                ),
+            )
         })
         .collect()
 }
 
+/// Checks that package.json devDependencies satisfy the package's own
+/// peerDependencies
+///
+/// A library usually develops against one version of a peer
+/// (`devDependencies["react"] = "18.3.0"`) while declaring the range it
+/// supports (`peerDependencies["react"] = ">=17.0.0"`). When the two drift
+/// apart, the tests run against a version users are told is unsupported.
+pub struct PeerDependencyConsistencyChecker;
+
+impl PeerDependencyConsistencyChecker {
+    /// Report each devDependency whose lowest allowed version is outside
+    /// the peerDependencies range of the same package
+    ///
+    /// Only plain npm versions are compared; `catalog:`, `workspace:`, tags
+    /// and other specs that are not semver ranges are skipped.
+    pub fn check(
+        &self,
+        dev_dependencies: &[PackageInfo],
+        peer_dependencies: &[PackageInfo],
+    ) -> Vec<Diagnostic> {
+        dev_dependencies
+            .iter()
+            .filter(|dev| dev.registry_type == RegistryType::Npm)
+            .filter_map(|dev| {
+                let peer = peer_dependencies.iter().find(|peer| {
+                    peer.registry_type == RegistryType::Npm && peer.name == dev.name
+                })?;
+                let satisfied = npm_spec_satisfies_range(&dev.version, &peer.version)?;
+                (!satisfied).then(|| {
+                    build_diagnostic(
+                        dev,
+                        DiagnosticCode::PeerDependencyMismatch,
+                        DiagnosticSeverity::INFORMATION,
+                        format!(
+                            "{} {} does not satisfy peerDependencies range {}",
+                            dev.name, dev.version, peer.version
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Check the package's own package.json `version` against npm
+///
+/// `package` is the synthetic entry of
+/// [`PackageJsonParser::parse_self_package`](crate::parser::package_json::PackageJsonParser::parse_self_package).
+/// A published version gets no diagnostic. An unpublished one is either the
+/// next release, or behind the latest published version (e.g. a release
+/// was published from another branch). Packages never fetched from npm,
+/// including packages that were never published at all, are skipped.
+pub fn generate_own_version_diagnostic<S: VersionStorer>(
+    package: &PackageInfo,
+    storer: &S,
+) -> Option<Diagnostic> {
+    // A yanked version was still published
+    let versions = storer
+        .get_versions(RegistryType::Npm, &package.name, true)
+        .inspect_err(|e| warn!("Failed to get versions of {}: {}", package.name, e))
+        .ok()?;
+    if versions.is_empty() || versions.contains(&package.version) {
+        return None;
+    }
+
+    let latest = storer
+        .get_latest_version(RegistryType::Npm, &package.name)
+        .ok()
+        .flatten();
+    let behind_latest = latest
+        .as_deref()
+        .and_then(parse_version)
+        .zip(parse_version(&package.version))
+        .is_some_and(|(latest, current)| current < latest);
+    let message = match latest {
+        Some(latest) if behind_latest => format!(
+            "Latest published version is {}, current package.json version is {}",
+            latest, package.version
+        ),
+        _ => format!("Version {} not yet published to npm", package.version),
+    };
+
+    Some(build_diagnostic(
+        package,
+        DiagnosticCode::UnpublishedOwnVersion,
+        DiagnosticSeverity::INFORMATION,
+        message,
+    ))
+}
+
 /// Create a diagnostic from package info and version check result
 /// Returns None if no diagnostic should be shown (e.g., NotInCache)
-fn create_diagnostic(package: &PackageInfo, result: &VersionCompareResult) -> Option<Diagnostic> {
-    let (severity, message) = match result.status {
+fn create_diagnostic(
+    package: &PackageInfo,
+    matcher: &dyn VersionMatcher,
+    result: &VersionCompareResult,
+    outdated_severity: DiagnosticSeverity,
+) -> Option<Diagnostic> {
+    let (code, severity, message) = match result.status {
         // No diagnostic for: not cached, latest version, or newer than latest
         // Newer: version exists but is newer than dist-tags.latest (valid scenario)
         VersionStatus::NotInCache | VersionStatus::Latest | VersionStatus::Newer => return None,
         VersionStatus::Outdated => (
-            DiagnosticSeverity::WARNING,
-            format!(
-                "Update available: {} -> {}",
-                result.current_version,
-                result.latest_version.as_deref().unwrap_or("unknown")
-            ),
+            DiagnosticCode::Outdated,
+            outdated_severity,
+            outdated_message(package, matcher, result),
         ),
         VersionStatus::NotFound => (
+            DiagnosticCode::NotFound,
             DiagnosticSeverity::ERROR,
             format!("Version {} not found in registry", result.current_version),
         ),
         VersionStatus::Invalid => (
+            DiagnosticCode::InvalidFormat,
             DiagnosticSeverity::ERROR,
             format!("Invalid version format: {}", result.current_version),
         ),
     };
 
+    Some(build_diagnostic(package, code, severity, message))
+}
+
+/// Message of an "update available" diagnostic
+///
+/// A Go pseudo-version names a commit rather than a release, so its message
+/// gives the commit date instead of the version. A package locked by
+/// conda-lock is updated by re-locking, which the message suggests.
+fn outdated_message(
+    package: &PackageInfo,
+    matcher: &dyn VersionMatcher,
+    result: &VersionCompareResult,
+) -> String {
+    let latest_version = result.latest_version.as_deref().unwrap_or("unknown");
+    match &package.extra_info {
+        Some(ExtraInfo::GoPseudoVersion {
+            commit_timestamp, ..
+        }) => format!(
+            "Pinned to unreleased commit from {}; latest release is {}",
+            commit_date(commit_timestamp),
+            latest_version
+        ),
+        Some(ExtraInfo::CondaLock) => format!(
+            "Update available on {}: {} -> {}; run `conda-lock update` to update the lock file",
+            package.registry_type.as_display_name(),
+            matcher.normalize_spec(&result.current_version),
+            latest_version
+        ),
+        _ => format!(
+            "Update available on {}: {} -> {}",
+            package.registry_type.as_display_name(),
+            matcher.normalize_spec(&result.current_version),
+            latest_version
+        ),
+    }
+}
+
+/// Build a diagnostic covering the package's version
+fn build_diagnostic(
+    package: &PackageInfo,
+    code: DiagnosticCode,
+    severity: DiagnosticSeverity,
+    message: String,
+) -> Diagnostic {
     let range = Range {
         start: Position {
             line: package.line as u32,
@@ -69,34 +394,85 @@ fn create_diagnostic(package: &PackageInfo, result: &VersionCompareResult) -> Op
         },
     };
 
-    Some(Diagnostic {
+    Diagnostic {
         range,
         severity: Some(severity),
-        message,
+        code: Some(code.into()),
         source: Some(PACKAGE_NAME.to_string()),
+        message,
+        // Structured copy of the code for editor extensions
+        data: Some(json!({ "code": code as i32 })),
         ..Default::default()
-    })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::parser::traits::MockParser;
-    use crate::parser::types::RegistryType;
     use crate::version::checker::MockVersionStorer;
-    use crate::version::matchers::GitHubActionsMatcher;
+    use crate::version::matchers::{CratesVersionMatcher, GitHubActionsMatcher, GoVersionMatcher};
+    use crate::version::memory_storer::MemoryVersionStorer;
+    use crate::version::types::PackageSummary;
     use rstest::rstest;
 
+    fn github_actions_matchers() -> HashMap<RegistryType, Arc<dyn VersionMatcher>> {
+        HashMap::from([(
+            RegistryType::GitHubActions,
+            Arc::new(GitHubActionsMatcher) as Arc<dyn VersionMatcher>,
+        )])
+    }
+
+    /// Answer batch version lookups with `versions` for every package
+    fn expect_batch_versions(storer: &mut MockVersionStorer, versions: Vec<String>) {
+        storer
+            .expect_batch_get_versions()
+            .returning(move |packages, _| {
+                Ok(packages
+                    .iter()
+                    .map(|(registry_type, name)| {
+                        let key = (*registry_type, name.to_string());
+                        (key, versions.clone())
+                    })
+                    .collect())
+            });
+    }
+
+    /// Answer batch summary lookups with `summary` for every package
+    fn expect_batch_summaries(storer: &mut MockVersionStorer, summary: PackageSummary) {
+        storer
+            .expect_batch_get_package_summaries()
+            .returning(move |packages| {
+                Ok(packages
+                    .iter()
+                    .map(|(registry_type, name)| {
+                        let key = (*registry_type, name.to_string());
+                        (key, summary.clone())
+                    })
+                    .collect())
+            });
+    }
+
+    fn latest_summary(latest: &str) -> PackageSummary {
+        PackageSummary {
+            latest_version: latest.to_string(),
+            ..Default::default()
+        }
+    }
+
     fn make_package_info(name: &str, version: &str, line: usize, column: usize) -> PackageInfo {
         PackageInfo {
             name: name.to_string(),
             version: version.to_string(),
             commit_hash: None,
             registry_type: RegistryType::GitHubActions,
+            registry_url: None,
             start_offset: column,
             end_offset: column + version.len(),
             line,
             column,
+            section_name: None,
+            section_line: None,
             extra_info: None,
         }
     }
@@ -106,25 +482,29 @@ mod tests {
         "3.0.0",
         true,
         DiagnosticSeverity::WARNING,
-        "Update available: 3.0.0 -> 4.0.0"
+        "Update available on GitHub Actions: 3.0.0 -> 4.0.0",
+        DiagnosticCode::Outdated
     )]
     #[case(
         "9.9.9",
         false,
         DiagnosticSeverity::ERROR,
-        "Version 9.9.9 not found in registry"
+        "Version 9.9.9 not found in registry",
+        DiagnosticCode::NotFound
     )]
     #[case(
         "invalid",
         true,
         DiagnosticSeverity::ERROR,
-        "Invalid version format: invalid"
+        "Invalid version format: invalid",
+        DiagnosticCode::InvalidFormat
     )]
     fn generate_diagnostics_returns_expected_diagnostic(
         #[case] current_version: &str,
         #[case] version_exists: bool,
         #[case] expected_severity: DiagnosticSeverity,
         #[case] expected_message: &str,
+        #[case] expected_code: DiagnosticCode,
     ) {
         let version = current_version.to_string();
         let mut parser = MockParser::new();
@@ -137,29 +517,66 @@ mod tests {
             )])
         });
 
-        let exists = version_exists;
-        let version_for_closure = current_version.to_string();
         let mut storer = MockVersionStorer::new();
-        storer
-            .expect_get_latest_version()
-            .returning(|_, _| Ok(Some("4.0.0".to_string())));
-        storer.expect_get_dist_tag().returning(|_, _, _| Ok(None)); // GitHub Actions don't have dist-tags
-        storer.expect_get_versions().returning(move |_, _| {
-            if exists {
-                // Return versions that include the current version for existence check
-                Ok(vec![version_for_closure.clone(), "4.0.0".to_string()])
-            } else {
-                // Return versions without the current version
-                Ok(vec!["4.0.0".to_string()])
-            }
-        });
-        let matcher = GitHubActionsMatcher;
+        expect_batch_summaries(&mut storer, latest_summary("4.0.0"));
+        // Without the current version, the existence check fails
+        let versions = if version_exists {
+            vec![current_version.to_string(), "4.0.0".to_string()]
+        } else {
+            vec!["4.0.0".to_string()]
+        };
+        expect_batch_versions(&mut storer, versions);
+        let matchers = github_actions_matchers();
 
-        let diagnostics = generate_diagnostics(&parser, &matcher, &storer, "content");
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::WARNING,
+            false,
+        );
 
         assert_eq!(diagnostics.len(), 1);
         assert_eq!(diagnostics[0].severity, Some(expected_severity));
         assert_eq!(diagnostics[0].message, expected_message);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::Number(expected_code as i32))
+        );
+        assert_eq!(diagnostics[0].source.as_deref(), Some("version-lsp"));
+        assert_eq!(
+            diagnostics[0].data,
+            Some(json!({ "code": expected_code as i32 }))
+        );
+    }
+
+    #[test]
+    fn generate_diagnostics_uses_given_severity_for_outdated_package() {
+        let mut parser = MockParser::new();
+        parser
+            .expect_parse()
+            .returning(|_| Ok(vec![make_package_info("actions/checkout", "3.0.0", 5, 14)]));
+
+        let mut storer = MockVersionStorer::new();
+        expect_batch_summaries(&mut storer, latest_summary("4.0.0"));
+        expect_batch_versions(&mut storer, vec!["3.0.0".to_string(), "4.0.0".to_string()]);
+        let matchers = github_actions_matchers();
+
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::INFORMATION,
+            false,
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].severity,
+            Some(DiagnosticSeverity::INFORMATION)
+        );
     }
 
     #[test]
@@ -170,16 +587,18 @@ mod tests {
             .returning(|_| Ok(vec![make_package_info("actions/checkout", "4.0.0", 5, 14)]));
 
         let mut storer = MockVersionStorer::new();
-        storer
-            .expect_get_latest_version()
-            .returning(|_, _| Ok(Some("4.0.0".to_string())));
-        storer.expect_get_dist_tag().returning(|_, _, _| Ok(None));
-        storer
-            .expect_get_versions()
-            .returning(|_, _| Ok(vec!["4.0.0".to_string()]));
-        let matcher = GitHubActionsMatcher;
+        expect_batch_summaries(&mut storer, latest_summary("4.0.0"));
+        expect_batch_versions(&mut storer, vec!["4.0.0".to_string()]);
+        let matchers = github_actions_matchers();
 
-        let diagnostics = generate_diagnostics(&parser, &matcher, &storer, "content");
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::WARNING,
+            false,
+        );
 
         assert!(diagnostics.is_empty());
     }
@@ -193,15 +612,181 @@ mod tests {
 
         let mut storer = MockVersionStorer::new();
         storer
-            .expect_get_latest_version()
-            .returning(|_, _| Ok(None));
-        let matcher = GitHubActionsMatcher;
+            .expect_batch_get_package_summaries()
+            .returning(|_| Ok(HashMap::new()));
+        expect_batch_versions(&mut storer, Vec::new());
+        let matchers = github_actions_matchers();
 
-        let diagnostics = generate_diagnostics(&parser, &matcher, &storer, "content");
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::WARNING,
+            false,
+        );
 
         assert!(diagnostics.is_empty());
     }
 
+    #[test]
+    fn generate_diagnostics_skips_git_dependencies() {
+        let mut parser = MockParser::new();
+        parser.expect_parse().returning(|_| {
+            Ok(vec![PackageInfo {
+                section_name: None,
+                section_line: None,
+                extra_info: Some(ExtraInfo::CargoGitDep {
+                    url: "https://github.com/actions/checkout".to_string(),
+                    rev_or_tag: None,
+                }),
+                ..make_package_info("actions/checkout", "HEAD", 5, 14)
+            }])
+        });
+
+        // The storer must not be consulted: MockVersionStorer panics on any call
+        let storer = MockVersionStorer::new();
+        let matchers = github_actions_matchers();
+
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::WARNING,
+            false,
+        );
+
+        assert!(diagnostics.is_empty());
+    }
+
+    #[rstest]
+    #[case("1", "Update available on crates.io: ^1.0.0 -> 2.0.0")]
+    #[case("~1", "Update available on crates.io: ~1.0.0 -> 2.0.0")]
+    #[case("1.2.0", "Update available on crates.io: 1.2.0 -> 2.0.0")]
+    fn generate_diagnostics_shows_normalized_spec_in_outdated_message(
+        #[case] current_version: &str,
+        #[case] expected_message: &str,
+    ) {
+        let version = current_version.to_string();
+        let mut parser = MockParser::new();
+        parser.expect_parse().returning(move |_| {
+            Ok(vec![PackageInfo {
+                registry_type: RegistryType::CratesIo,
+                ..make_package_info("serde", &version, 5, 10)
+            }])
+        });
+        let storer = MemoryVersionStorer::new(vec![(
+            RegistryType::CratesIo,
+            "serde",
+            vec!["1.0.0", "1.2.0", "2.0.0"],
+        )]);
+        let matchers: HashMap<RegistryType, Arc<dyn VersionMatcher>> = HashMap::from([(
+            RegistryType::CratesIo,
+            Arc::new(CratesVersionMatcher) as Arc<dyn VersionMatcher>,
+        )]);
+
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::WARNING,
+            false,
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, expected_message);
+    }
+
+    #[test]
+    fn generate_diagnostics_shows_commit_date_for_outdated_pseudo_version() {
+        let mut parser = MockParser::new();
+        parser.expect_parse().returning(|_| {
+            Ok(vec![PackageInfo {
+                registry_type: RegistryType::GoProxy,
+                section_name: None,
+                section_line: None,
+                extra_info: Some(ExtraInfo::GoPseudoVersion {
+                    commit_timestamp: "20210101000000".to_string(),
+                    commit_hash: "abcdef123456".to_string(),
+                }),
+                ..make_package_info(
+                    "github.com/some/repo",
+                    "v0.0.0-20210101000000-abcdef123456",
+                    3,
+                    29,
+                )
+            }])
+        });
+        let storer = MemoryVersionStorer::new(vec![(
+            RegistryType::GoProxy,
+            "github.com/some/repo",
+            vec!["v1.0.0", "v1.5.0"],
+        )]);
+        let matchers: HashMap<RegistryType, Arc<dyn VersionMatcher>> = HashMap::from([(
+            RegistryType::GoProxy,
+            Arc::new(GoVersionMatcher) as Arc<dyn VersionMatcher>,
+        )]);
+
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::WARNING,
+            false,
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Pinned to unreleased commit from 2021-01-01; latest release is v1.5.0"
+        );
+        assert_eq!(diagnostics[0].code, Some(DiagnosticCode::Outdated.into()));
+    }
+
+    #[rstest]
+    #[case(false, DiagnosticCode::NotFound)]
+    #[case(true, DiagnosticCode::Outdated)]
+    fn generate_diagnostics_counts_yanked_version_as_existing_only_if_shown(
+        #[case] show_yanked: bool,
+        #[case] expected_code: DiagnosticCode,
+    ) {
+        let mut parser = MockParser::new();
+        parser.expect_parse().returning(|_| {
+            Ok(vec![PackageInfo {
+                registry_type: RegistryType::CratesIo,
+                ..make_package_info("serde", "=1.1.0", 3, 9)
+            }])
+        });
+        let storer = MemoryVersionStorer::new(vec![]);
+        storer
+            .replace_versions(
+                RegistryType::CratesIo,
+                "serde",
+                vec!["1.0.0".to_string(), "1.2.0".to_string()],
+                &["1.1.0".to_string()],
+            )
+            .unwrap();
+        let matchers: HashMap<RegistryType, Arc<dyn VersionMatcher>> = HashMap::from([(
+            RegistryType::CratesIo,
+            Arc::new(CratesVersionMatcher) as Arc<dyn VersionMatcher>,
+        )]);
+
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::WARNING,
+            show_yanked,
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some(expected_code.into()));
+    }
+
     #[test]
     fn generate_diagnostics_skips_version_newer_than_latest() {
         // When a version exists but is newer than the "latest" dist-tag
@@ -213,21 +798,125 @@ mod tests {
             .returning(|_| Ok(vec![make_package_info("actions/checkout", "5.0.0", 5, 14)]));
 
         let mut storer = MockVersionStorer::new();
-        storer
-            .expect_get_latest_version()
-            .returning(|_, _| Ok(Some("4.0.0".to_string())));
-        storer.expect_get_dist_tag().returning(|_, _, _| Ok(None));
-        storer
-            .expect_get_versions()
-            .returning(|_, _| Ok(vec!["5.0.0".to_string(), "4.0.0".to_string()]));
-        let matcher = GitHubActionsMatcher;
+        expect_batch_summaries(&mut storer, latest_summary("4.0.0"));
+        expect_batch_versions(&mut storer, vec!["5.0.0".to_string(), "4.0.0".to_string()]);
+        let matchers = github_actions_matchers();
 
-        let diagnostics = generate_diagnostics(&parser, &matcher, &storer, "content");
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::WARNING,
+            false,
+        );
 
         // Version 5.0.0 exists and is newer than latest (4.0.0) - no diagnostic
         assert!(diagnostics.is_empty());
     }
 
+    #[rstest]
+    #[case("3.0.0", vec![DiagnosticCode::Outdated, DiagnosticCode::Deprecated])]
+    #[case("4.0.0", vec![DiagnosticCode::Deprecated])]
+    fn generate_diagnostics_reports_deprecated_package_in_addition_to_outdated(
+        #[case] current_version: &str,
+        #[case] expected_codes: Vec<DiagnosticCode>,
+    ) {
+        let version = current_version.to_string();
+        let mut parser = MockParser::new();
+        parser
+            .expect_parse()
+            .returning(move |_| Ok(vec![make_package_info("actions/checkout", &version, 5, 14)]));
+
+        let mut storer = MockVersionStorer::new();
+        expect_batch_summaries(
+            &mut storer,
+            PackageSummary {
+                deprecated: Some("Use actions/checkout-v2 instead".to_string()),
+                ..latest_summary("4.0.0")
+            },
+        );
+        expect_batch_versions(&mut storer, vec!["3.0.0".to_string(), "4.0.0".to_string()]);
+        let matchers = github_actions_matchers();
+
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::WARNING,
+            false,
+        );
+
+        let codes: Vec<Option<NumberOrString>> =
+            diagnostics.iter().map(|d| d.code.clone()).collect();
+        let expected: Vec<Option<NumberOrString>> =
+            expected_codes.into_iter().map(|c| Some(c.into())).collect();
+        assert_eq!(codes, expected);
+
+        let deprecated = diagnostics.last().unwrap();
+        assert_eq!(deprecated.severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(deprecated.tags, Some(vec![DiagnosticTag::DEPRECATED]));
+        assert_eq!(
+            deprecated.message,
+            "actions/checkout is deprecated: Use actions/checkout-v2 instead"
+        );
+    }
+
+    #[rstest]
+    #[case("3.0.0", vec![DiagnosticCode::Outdated])]
+    #[case("4.0.0", vec![DiagnosticCode::Advisory])]
+    fn generate_diagnostics_reports_advisories_of_latest_version(
+        #[case] current_version: &str,
+        #[case] expected_codes: Vec<DiagnosticCode>,
+    ) {
+        let version = current_version.to_string();
+        let mut parser = MockParser::new();
+        parser
+            .expect_parse()
+            .returning(move |_| Ok(vec![make_package_info("actions/checkout", &version, 5, 14)]));
+
+        let mut storer = MockVersionStorer::new();
+        expect_batch_summaries(
+            &mut storer,
+            PackageSummary {
+                advisories: vec![Advisory {
+                    id: "GHSA-xxxx-yyyy-zzzz".to_string(),
+                    severity: "HIGH".to_string(),
+                    summary: "Token leak".to_string(),
+                }],
+                ..latest_summary("4.0.0")
+            },
+        );
+        expect_batch_versions(&mut storer, vec!["3.0.0".to_string(), "4.0.0".to_string()]);
+        let matchers = github_actions_matchers();
+
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::WARNING,
+            false,
+        );
+
+        let codes: Vec<Option<NumberOrString>> =
+            diagnostics.iter().map(|d| d.code.clone()).collect();
+        let expected: Vec<Option<NumberOrString>> =
+            expected_codes.into_iter().map(|c| Some(c.into())).collect();
+        assert_eq!(codes, expected);
+
+        if current_version == "4.0.0" {
+            let advisory = &diagnostics[0];
+            assert_eq!(advisory.severity, Some(DiagnosticSeverity::WARNING));
+            assert_eq!(advisory.tags, Some(vec![DiagnosticTag::DEPRECATED]));
+            assert_eq!(
+                advisory.message,
+                "actions/checkout has security advisory GHSA-xxxx-yyyy-zzzz (HIGH): Token leak"
+            );
+        }
+    }
+
     #[test]
     fn generate_diagnostics_calculates_correct_range() {
         let mut parser = MockParser::new();
@@ -237,25 +926,30 @@ mod tests {
                 version: "3.0.0".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::GitHubActions,
+                registry_url: None,
                 start_offset: 10,
                 end_offset: 20,
                 line: 5,
                 column: 10,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }])
         });
 
         let mut storer = MockVersionStorer::new();
-        storer
-            .expect_get_latest_version()
-            .returning(|_, _| Ok(Some("4.0.0".to_string())));
-        storer.expect_get_dist_tag().returning(|_, _, _| Ok(None));
-        storer
-            .expect_get_versions()
-            .returning(|_, _| Ok(vec!["3.0.0".to_string(), "4.0.0".to_string()]));
-        let matcher = GitHubActionsMatcher;
+        expect_batch_summaries(&mut storer, latest_summary("4.0.0"));
+        expect_batch_versions(&mut storer, vec!["3.0.0".to_string(), "4.0.0".to_string()]);
+        let matchers = github_actions_matchers();
 
-        let diagnostics = generate_diagnostics(&parser, &matcher, &storer, "content");
+        let diagnostics = generate_diagnostics(
+            &parser,
+            &matchers,
+            &storer,
+            "content",
+            DiagnosticSeverity::WARNING,
+            false,
+        );
 
         assert_eq!(diagnostics.len(), 1);
         assert_eq!(
@@ -272,4 +966,155 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn generate_go_sum_diagnostics_reports_modules_missing_from_go_sum() {
+        let go_module = |name: &str, version: &str, line: usize| PackageInfo {
+            name: name.to_string(),
+            version: version.to_string(),
+            commit_hash: None,
+            registry_type: RegistryType::GoProxy,
+            registry_url: None,
+            start_offset: 0,
+            end_offset: version.len(),
+            line,
+            column: 4,
+            section_name: None,
+            section_line: None,
+            extra_info: None,
+        };
+        let packages = vec![
+            go_module("golang.org/x/text", "v0.14.0", 3),
+            go_module("golang.org/x/net", "v0.20.0", 4),
+            go_module("golang.org/x/sync", "v0.6.0", 5),
+        ];
+        let go_sum = HashSet::from([
+            ("golang.org/x/text".to_string(), "v0.14.0".to_string()),
+            ("golang.org/x/net".to_string(), "v0.19.0".to_string()),
+        ]);
+
+        let diagnostics = generate_go_sum_diagnostics(&packages, &go_sum);
+
+        let reported: Vec<(u32, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.range.start.line, d.message.as_str()))
+            .collect();
+        assert_eq!(
+            reported,
+            vec![
+                (4, "Missing go.sum entry for golang.org/x/net v0.20.0"),
+                (5, "Missing go.sum entry for golang.org/x/sync v0.6.0"),
+            ]
+        );
+        assert!(diagnostics.iter().all(|d| {
+            d.severity == Some(DiagnosticSeverity::INFORMATION)
+                && d.code == Some(DiagnosticCode::MissingGoSum.into())
+        }));
+    }
+
+    fn npm_package(name: &str, version: &str, line: usize) -> PackageInfo {
+        PackageInfo {
+            registry_type: RegistryType::Npm,
+            ..make_package_info(name, version, line, 4)
+        }
+    }
+
+    #[rstest]
+    #[case("18.3.0", ">=17.0.0", None)]
+    #[case("^18.2.0", "^17.0.0 || ^18.0.0", None)]
+    #[case(
+        "16.14.0",
+        ">=17.0.0",
+        Some("react 16.14.0 does not satisfy peerDependencies range >=17.0.0")
+    )]
+    #[case(
+        "^19.0.0",
+        "^18.0.0",
+        Some("react ^19.0.0 does not satisfy peerDependencies range ^18.0.0")
+    )]
+    #[case("latest", "^18.0.0", None)]
+    fn peer_dependency_consistency_checker_reports_dev_version_outside_peer_range(
+        #[case] dev_version: &str,
+        #[case] peer_range: &str,
+        #[case] expected_message: Option<&str>,
+    ) {
+        let dev = vec![
+            npm_package("react", dev_version, 5),
+            npm_package("typescript", "5.4.0", 6),
+        ];
+        let peer = vec![npm_package("react", peer_range, 9)];
+
+        let diagnostics = PeerDependencyConsistencyChecker.check(&dev, &peer);
+
+        let reported: Vec<(u32, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.range.start.line, d.message.as_str()))
+            .collect();
+        assert_eq!(
+            reported,
+            expected_message
+                .map(|m| (5, m))
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+        assert!(diagnostics.iter().all(|d| {
+            d.severity == Some(DiagnosticSeverity::INFORMATION)
+                && d.code == Some(DiagnosticCode::PeerDependencyMismatch.into())
+        }));
+    }
+
+    #[test]
+    fn peer_dependency_consistency_checker_skips_catalog_references() {
+        let dev = vec![PackageInfo {
+            registry_type: RegistryType::PnpmCatalog,
+            ..make_package_info("react", "catalog:", 5, 4)
+        }];
+        let peer = vec![npm_package("react", "^18.0.0", 9)];
+
+        assert!(
+            PeerDependencyConsistencyChecker
+                .check(&dev, &peer)
+                .is_empty()
+        );
+    }
+
+    #[rstest]
+    #[case("1.2.2", None)]
+    #[case("1.2.3", Some("Version 1.2.3 not yet published to npm"))]
+    #[case("2.0.0-beta.1", Some("Version 2.0.0-beta.1 not yet published to npm"))]
+    #[case(
+        "1.1.5",
+        Some("Latest published version is 1.2.2, current package.json version is 1.1.5")
+    )]
+    fn generate_own_version_diagnostic_reports_unpublished_version(
+        #[case] own_version: &str,
+        #[case] expected_message: Option<&str>,
+    ) {
+        let storer = MemoryVersionStorer::new(vec![(
+            RegistryType::Npm,
+            "my-lib",
+            vec!["1.1.0", "1.2.0", "1.2.2"],
+        )]);
+        let package = npm_package("my-lib", own_version, 3);
+
+        let diagnostic = generate_own_version_diagnostic(&package, &storer);
+
+        assert_eq!(
+            diagnostic.as_ref().map(|d| d.message.as_str()),
+            expected_message
+        );
+        assert!(diagnostic.iter().all(|d| {
+            d.severity == Some(DiagnosticSeverity::INFORMATION)
+                && d.code == Some(DiagnosticCode::UnpublishedOwnVersion.into())
+        }));
+    }
+
+    #[test]
+    fn generate_own_version_diagnostic_skips_package_not_in_cache() {
+        let storer = MemoryVersionStorer::new(vec![]);
+
+        assert!(
+            generate_own_version_diagnostic(&npm_package("my-lib", "1.0.0", 3), &storer).is_none()
+        );
+    }
 }
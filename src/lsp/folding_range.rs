@@ -0,0 +1,114 @@
+//! Folding ranges for dependency sections
+
+use std::collections::BTreeMap;
+
+use tower_lsp::lsp_types::{FoldingRange, FoldingRangeKind};
+
+use crate::parser::types::PackageInfo;
+
+/// Folding ranges covering each dependency section of a document
+///
+/// A section runs from the header line recorded by the parser
+/// (`[dev-dependencies]`, `require (`) to its last package. Packages without
+/// a `section_line` are not folded.
+pub fn dependency_folding_ranges(packages: &[PackageInfo]) -> Vec<FoldingRange> {
+    // Header line -> last package line
+    let mut sections: BTreeMap<usize, usize> = BTreeMap::new();
+    for package in packages {
+        let Some(header) = package.section_line else {
+            continue;
+        };
+        let end = sections.entry(header).or_insert(package.line);
+        *end = (*end).max(package.line);
+    }
+
+    sections
+        .into_iter()
+        .map(|(start, end)| FoldingRange {
+            start_line: start as u32,
+            end_line: end as u32,
+            kind: Some(FoldingRangeKind::Region),
+            ..Default::default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::CargoTomlParser;
+    use crate::parser::GoModParser;
+    use crate::parser::PubspecYamlParser;
+    use crate::parser::traits::Parser;
+
+    fn folded_lines(ranges: Vec<FoldingRange>) -> Vec<(u32, u32)> {
+        ranges
+            .into_iter()
+            .map(|range| (range.start_line, range.end_line))
+            .collect()
+    }
+
+    #[test]
+    fn dependency_folding_ranges_folds_cargo_dependency_tables() {
+        let content = r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+# Serialization
+serde = "1.0"
+tokio = { version = "1.40", features = ["full"] }
+
+[dev-dependencies]
+rstest = "0.23"
+"#;
+        let packages = CargoTomlParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            folded_lines(dependency_folding_ranges(&packages)),
+            vec![(4, 7), (9, 10)]
+        );
+    }
+
+    #[test]
+    fn dependency_folding_ranges_folds_each_go_require_block() {
+        let content = r#"module example.com/myapp
+
+go 1.21
+
+require github.com/single/dep v1.0.0
+
+require (
+	golang.org/x/text v0.14.0
+	golang.org/x/net v0.20.0
+)
+
+require (
+	golang.org/x/sys v0.15.0 // indirect
+)
+"#;
+        let packages = GoModParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            folded_lines(dependency_folding_ranges(&packages)),
+            vec![(6, 8), (11, 12)]
+        );
+    }
+
+    #[test]
+    fn dependency_folding_ranges_folds_pubspec_dependency_sections() {
+        let content = r#"name: app
+dependencies:
+  http: ^1.2.0
+  provider: 6.1.2
+dev_dependencies:
+  lints: ^3.0.0
+"#;
+        let packages = PubspecYamlParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            folded_lines(dependency_folding_ranges(&packages)),
+            vec![(1, 3), (4, 5)]
+        );
+    }
+}
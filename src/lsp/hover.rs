@@ -0,0 +1,389 @@
+//! Hover content for dependency versions
+
+use chrono::{DateTime, Utc};
+use tower_lsp::lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind};
+
+use crate::lsp::code_action::version_range;
+use crate::parser::go_mod::commit_date;
+use crate::parser::types::{DocumentMetadata, ExtraInfo, PackageInfo, registry_type_to_url};
+use crate::version::checker::{VersionCompareResult, VersionStatus};
+use crate::version::types::PackageMetadata;
+
+/// Length of the commit hash shown for a Go pseudo-version
+const SHORT_COMMIT_HASH_LEN: usize = 8;
+
+/// Hover for the version of `package`
+///
+/// `comparison` is the result of comparing the version with the cached
+/// versions, shown as `**lodash** ^4.17.20 → latest: 4.17.21` with the
+/// version status, a link to the registry page and, when `metadata` knows
+/// the package's repository or homepage, a `[Changelog](url)` link.
+/// `last_fetch_time` is when the package's versions were last fetched
+/// (milliseconds since UNIX epoch), shown relative to `now` so stale
+/// diagnostics can be told apart from current ones. `document` is the
+/// document-level information of the document holding `package`; an ES
+/// module package.json gets a note that CommonJS-only packages may not work.
+///
+/// Returns None for versions with nothing to show beyond the version itself,
+/// such as packages whose versions are not cached yet.
+pub fn package_hover(
+    package: &PackageInfo,
+    document: &DocumentMetadata,
+    comparison: Option<&VersionCompareResult>,
+    metadata: Option<&PackageMetadata>,
+    last_fetch_time: Option<i64>,
+    now: DateTime<Utc>,
+) -> Option<Hover> {
+    let status = comparison.and_then(|comparison| version_status(package, comparison, metadata));
+    let commit = match &package.extra_info {
+        Some(ExtraInfo::GoPseudoVersion {
+            commit_timestamp,
+            commit_hash,
+        }) => Some(format!(
+            "Pinned to commit `{}` from {}",
+            &commit_hash[..SHORT_COMMIT_HASH_LEN.min(commit_hash.len())],
+            commit_date(commit_timestamp)
+        )),
+        _ => None,
+    };
+    let last_updated = last_fetch_time
+        .and_then(|timestamp_ms| format_fetch_time(timestamp_ms, now))
+        .map(|time| format!("Last updated: {}", time));
+    let esm = document
+        .is_esm
+        .then(|| "ESM project - some CommonJS-only packages may not work".to_string());
+
+    let sections: Vec<String> = status
+        .into_iter()
+        .chain(commit)
+        .chain(last_updated)
+        .chain(esm)
+        .collect();
+    if sections.is_empty() {
+        return None;
+    }
+    let value = sections.join("\n\n");
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }),
+        range: Some(version_range(package)),
+    })
+}
+
+/// Current and latest version, status, registry and changelog links of a
+/// package, or None when its versions are not cached
+fn version_status(
+    package: &PackageInfo,
+    comparison: &VersionCompareResult,
+    metadata: Option<&PackageMetadata>,
+) -> Option<String> {
+    let latest = comparison.latest_version.as_deref()?;
+    let status = match comparison.status {
+        VersionStatus::Latest => "Up to date",
+        VersionStatus::Outdated => "Update available",
+        VersionStatus::Newer => "Newer than the latest version",
+        VersionStatus::Invalid => "Invalid version format",
+        VersionStatus::NotFound => "Version not found in the registry",
+        VersionStatus::NotInCache => return None,
+    };
+    let url = package
+        .registry_url
+        .clone()
+        .unwrap_or_else(|| registry_type_to_url(package.registry_type, &package.name));
+
+    let changelog = metadata
+        .and_then(PackageMetadata::changelog_link)
+        .map(|link| format!(" · {}", link))
+        .unwrap_or_default();
+
+    Some(format!(
+        "**{}** {} → latest: {}\n\n{} · [View on {}]({}){}",
+        package.name,
+        package.version,
+        latest,
+        status,
+        package.registry_type.as_display_name(),
+        url,
+        changelog
+    ))
+}
+
+/// Format a millisecond UNIX timestamp with its age relative to `now`,
+/// e.g. `2024-01-15 10:30 UTC (2 hours ago)`
+pub fn format_fetch_time(timestamp_ms: i64, now: DateTime<Utc>) -> Option<String> {
+    let time = DateTime::from_timestamp_millis(timestamp_ms)?;
+    Some(format!(
+        "{} ({})",
+        time.format("%Y-%m-%d %H:%M UTC"),
+        format_age(now - time)
+    ))
+}
+
+/// Human-readable age in the largest whole unit, e.g. `2 hours ago`
+fn format_age(age: chrono::TimeDelta) -> String {
+    let (count, unit) = if age.num_days() > 0 {
+        (age.num_days(), "day")
+    } else if age.num_hours() > 0 {
+        (age.num_hours(), "hour")
+    } else if age.num_minutes() > 0 {
+        (age.num_minutes(), "minute")
+    } else {
+        return "just now".to_string();
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{} {}{} ago", count, unit, plural)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::types::RegistryType;
+    use rstest::rstest;
+    use tower_lsp::lsp_types::{Position, Range};
+
+    fn now() -> DateTime<Utc> {
+        "2024-01-15T12:30:00Z".parse().unwrap()
+    }
+
+    fn millis(time: &str) -> i64 {
+        time.parse::<DateTime<Utc>>().unwrap().timestamp_millis()
+    }
+
+    fn go_package(version: &str, extra_info: Option<ExtraInfo>) -> PackageInfo {
+        PackageInfo {
+            name: "github.com/some/repo".to_string(),
+            version: version.to_string(),
+            commit_hash: None,
+            registry_type: RegistryType::GoProxy,
+            registry_url: None,
+            start_offset: 0,
+            end_offset: version.len(),
+            line: 2,
+            column: 29,
+            section_name: None,
+            section_line: None,
+            extra_info,
+        }
+    }
+
+    #[test]
+    fn package_hover_shows_commit_of_pseudo_version() {
+        let package = go_package(
+            "v0.0.0-20210101000000-abcdef123456",
+            Some(ExtraInfo::GoPseudoVersion {
+                commit_timestamp: "20210101000000".to_string(),
+                commit_hash: "abcdef123456".to_string(),
+            }),
+        );
+
+        let hover = package_hover(
+            &package,
+            &DocumentMetadata::default(),
+            None,
+            None,
+            None,
+            now(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            hover.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: "Pinned to commit `abcdef12` from 2021-01-01".to_string(),
+            })
+        );
+        assert_eq!(
+            hover.range,
+            Some(Range::new(Position::new(2, 29), Position::new(2, 63)))
+        );
+    }
+
+    #[test]
+    fn package_hover_returns_none_for_release_never_fetched() {
+        assert_eq!(
+            package_hover(
+                &go_package("v1.5.0", None),
+                &DocumentMetadata::default(),
+                None,
+                None,
+                None,
+                now()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn package_hover_shows_last_fetch_time() {
+        let package = go_package(
+            "v0.0.0-20210101000000-abcdef123456",
+            Some(ExtraInfo::GoPseudoVersion {
+                commit_timestamp: "20210101000000".to_string(),
+                commit_hash: "abcdef123456".to_string(),
+            }),
+        );
+
+        let hover = package_hover(
+            &package,
+            &DocumentMetadata::default(),
+            None,
+            None,
+            Some(millis("2024-01-15T10:30:00Z")),
+            now(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            hover.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: "Pinned to commit `abcdef12` from 2021-01-01\n\nLast updated: 2024-01-15 10:30 UTC (2 hours ago)".to_string(),
+            })
+        );
+    }
+
+    fn lodash_package() -> PackageInfo {
+        PackageInfo {
+            name: "lodash".to_string(),
+            version: "^4.17.20".to_string(),
+            commit_hash: None,
+            registry_type: RegistryType::Npm,
+            registry_url: Some("https://www.npmjs.com/package/lodash".to_string()),
+            start_offset: 0,
+            end_offset: 8,
+            line: 2,
+            column: 15,
+            section_name: None,
+            section_line: None,
+            extra_info: None,
+        }
+    }
+
+    fn comparison(latest_version: Option<&str>, status: VersionStatus) -> VersionCompareResult {
+        VersionCompareResult {
+            current_version: "^4.17.20".to_string(),
+            latest_version: latest_version.map(str::to_string),
+            status,
+        }
+    }
+
+    #[rstest]
+    #[case(VersionStatus::Latest, "Up to date")]
+    #[case(VersionStatus::Outdated, "Update available")]
+    #[case(VersionStatus::NotFound, "Version not found in the registry")]
+    fn package_hover_shows_latest_version_and_status(
+        #[case] status: VersionStatus,
+        #[case] expected_status: &str,
+    ) {
+        let comparison = comparison(Some("4.17.21"), status);
+
+        let hover = package_hover(
+            &lodash_package(),
+            &DocumentMetadata::default(),
+            Some(&comparison),
+            None,
+            None,
+            now(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            hover.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!(
+                    "**lodash** ^4.17.20 → latest: 4.17.21\n\n{} · [View on npm](https://www.npmjs.com/package/lodash)",
+                    expected_status
+                ),
+            })
+        );
+        assert_eq!(
+            hover.range,
+            Some(Range::new(Position::new(2, 15), Position::new(2, 23)))
+        );
+    }
+
+    #[test]
+    fn package_hover_links_changelog_from_metadata() {
+        let comparison = comparison(Some("4.17.21"), VersionStatus::Latest);
+        let metadata = PackageMetadata {
+            homepage: Some("https://lodash.com/".to_string()),
+            repository: Some("https://github.com/lodash/lodash".to_string()),
+        };
+
+        let hover = package_hover(
+            &lodash_package(),
+            &DocumentMetadata::default(),
+            Some(&comparison),
+            Some(&metadata),
+            None,
+            now(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            hover.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: "**lodash** ^4.17.20 → latest: 4.17.21\n\nUp to date · [View on npm](https://www.npmjs.com/package/lodash) · [Changelog](https://github.com/lodash/lodash/releases)".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn package_hover_notes_esm_project() {
+        let comparison = comparison(Some("4.17.21"), VersionStatus::Latest);
+        let document = DocumentMetadata { is_esm: true };
+
+        let hover = package_hover(
+            &lodash_package(),
+            &document,
+            Some(&comparison),
+            None,
+            None,
+            now(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            hover.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: "**lodash** ^4.17.20 → latest: 4.17.21\n\nUp to date · [View on npm](https://www.npmjs.com/package/lodash)\n\nESM project - some CommonJS-only packages may not work".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn package_hover_returns_none_for_package_not_in_cache() {
+        let comparison = comparison(None, VersionStatus::NotInCache);
+
+        assert_eq!(
+            package_hover(
+                &lodash_package(),
+                &DocumentMetadata::default(),
+                Some(&comparison),
+                None,
+                None,
+                now()
+            ),
+            None
+        );
+    }
+
+    #[rstest]
+    #[case("2024-01-15T12:29:30Z", "2024-01-15 12:29 UTC (just now)")]
+    #[case("2024-01-15T12:29:00Z", "2024-01-15 12:29 UTC (1 minute ago)")]
+    #[case("2024-01-15T11:00:00Z", "2024-01-15 11:00 UTC (1 hour ago)")]
+    #[case("2024-01-12T08:00:00Z", "2024-01-12 08:00 UTC (3 days ago)")]
+    fn format_fetch_time_shows_age(#[case] fetched_at: &str, #[case] expected: &str) {
+        assert_eq!(
+            format_fetch_time(millis(fetched_at), now()).as_deref(),
+            Some(expected)
+        );
+    }
+}
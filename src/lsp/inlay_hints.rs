@@ -0,0 +1,182 @@
+//! Inlay hints showing the latest version next to each dependency version
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tower_lsp::lsp_types::{InlayHint, InlayHintLabel, Position};
+use tracing::warn;
+
+use crate::lsp::code_action::version_range;
+use crate::parser::types::{PackageInfo, RegistryType};
+use crate::version::checker::{VersionStatus, VersionStorer, compare_version_with_versions};
+use crate::version::matcher::VersionMatcher;
+
+/// Inlay hints for the versions of `packages` in a document with `content`
+///
+/// An outdated version gets `→ 4.17.21` and a version at the latest gets
+/// `✓ up-to-date`, placed after the closing quote of quoted versions.
+/// Packages without cached versions, or with a version that is invalid or
+/// not found (already reported by diagnostics), get no hint. `show_yanked`
+/// counts versions the registry yanked as existing.
+pub fn generate_inlay_hints<S: VersionStorer>(
+    packages: &[&PackageInfo],
+    content: &str,
+    matchers: &HashMap<RegistryType, Arc<dyn VersionMatcher>>,
+    storer: &S,
+    show_yanked: bool,
+) -> Vec<InlayHint> {
+    let packages: Vec<(&PackageInfo, &Arc<dyn VersionMatcher>)> = packages
+        .iter()
+        .filter(|package| !package.is_git_dependency())
+        .filter_map(|package| Some((*package, matchers.get(&package.registry_type)?)))
+        .collect();
+    if packages.is_empty() {
+        return Vec::new();
+    }
+
+    let lookups: Vec<(RegistryType, &str)> = packages
+        .iter()
+        .map(|(package, _)| (package.registry_type, package.name.as_str()))
+        .collect();
+    let Ok(versions) = storer
+        .batch_get_versions(&lookups, show_yanked)
+        .inspect_err(|e| warn!("Failed to get versions: {}", e))
+    else {
+        return Vec::new();
+    };
+    let Ok(summaries) = storer
+        .batch_get_package_summaries(&lookups)
+        .inspect_err(|e| warn!("Failed to get package summaries: {}", e))
+    else {
+        return Vec::new();
+    };
+
+    packages
+        .into_iter()
+        .filter_map(|(package, matcher)| {
+            let key = (package.registry_type, package.name.clone());
+            let package_versions = versions.get(&key)?;
+            let summary = summaries.get(&key)?;
+            let result = compare_version_with_versions(
+                &**matcher,
+                &package.version,
+                package_versions,
+                &summary.latest_version,
+                summary.dist_tags.get(&package.version).map(String::as_str),
+            );
+            let label = match result.status {
+                VersionStatus::Latest => "✓ up-to-date".to_string(),
+                VersionStatus::Outdated => format!("→ {}", result.latest_version?),
+                _ => return None,
+            };
+
+            Some(InlayHint {
+                position: hint_position(package, content),
+                label: InlayHintLabel::String(label),
+                kind: None,
+                text_edits: None,
+                tooltip: None,
+                padding_left: Some(true),
+                padding_right: None,
+                data: None,
+            })
+        })
+        .collect()
+}
+
+/// Position right after the version, or after its closing quote
+fn hint_position(package: &PackageInfo, content: &str) -> Position {
+    let end = version_range(package).end;
+    let quoted = content
+        .get(package.end_offset..)
+        .is_some_and(|rest| rest.starts_with(['"', '\'']));
+    Position::new(end.line, end.character + u32::from(quoted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::PackageJsonParser;
+    use crate::parser::traits::Parser;
+    use crate::version::checker::MockVersionStorer;
+    use crate::version::matchers::NpmVersionMatcher;
+    use crate::version::types::PackageSummary;
+
+    fn hints(content: &str) -> Vec<(Position, String)> {
+        let packages = PackageJsonParser::new().parse(content).unwrap();
+        let matchers: HashMap<RegistryType, Arc<dyn VersionMatcher>> =
+            HashMap::from([(RegistryType::Npm, Arc::new(NpmVersionMatcher) as _)]);
+
+        let mut storer = MockVersionStorer::new();
+        storer.expect_batch_get_versions().returning(|_, _| {
+            Ok(HashMap::from([(
+                (RegistryType::Npm, "lodash".to_string()),
+                vec!["4.17.20".to_string(), "4.17.21".to_string()],
+            )]))
+        });
+        storer.expect_batch_get_package_summaries().returning(|_| {
+            Ok(HashMap::from([(
+                (RegistryType::Npm, "lodash".to_string()),
+                PackageSummary {
+                    latest_version: "4.17.21".to_string(),
+                    ..Default::default()
+                },
+            )]))
+        });
+
+        generate_inlay_hints(
+            &packages.iter().collect::<Vec<_>>(),
+            content,
+            &matchers,
+            &storer,
+            false,
+        )
+        .into_iter()
+        .map(|hint| {
+            let InlayHintLabel::String(label) = hint.label else {
+                panic!("Expected a string label");
+            };
+            (hint.position, label)
+        })
+        .collect()
+    }
+
+    #[test]
+    fn generate_inlay_hints_shows_latest_version_after_closing_quote() {
+        let content = r#"{
+  "dependencies": {
+    "lodash": "4.17.20"
+  }
+}"#;
+
+        assert_eq!(
+            hints(content),
+            vec![(Position::new(2, 23), "→ 4.17.21".to_string())]
+        );
+    }
+
+    #[test]
+    fn generate_inlay_hints_marks_satisfied_range_up_to_date() {
+        let content = r#"{
+  "dependencies": {
+    "lodash": "^4.17.0"
+  }
+}"#;
+
+        assert_eq!(
+            hints(content),
+            vec![(Position::new(2, 23), "✓ up-to-date".to_string())]
+        );
+    }
+
+    #[test]
+    fn generate_inlay_hints_omits_packages_without_cached_versions() {
+        let content = r#"{
+  "dependencies": {
+    "react": "18.2.0"
+  }
+}"#;
+
+        assert!(hints(content).is_empty());
+    }
+}
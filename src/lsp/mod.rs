@@ -6,14 +6,28 @@
 //! # Modules
 //!
 //! - [`backend`]: Main LSP backend implementing `LanguageServer` trait
+//! - [`catalog`]: Navigation between pnpm `catalog:` references and entries
+//! - [`completion`]: Version completions from the cached versions
 //! - [`diagnostics`]: Generates version-related diagnostics (warnings, errors)
+//! - [`folding_range`]: Folding ranges for dependency sections
+//! - [`hover`]: Hover content for dependency versions
+//! - [`inlay_hints`]: Inlay hints with the latest version of each dependency
 //! - [`refresh`]: Background refresh logic for package version cache
 //! - [`resolver`]: Groups parser, matcher, and registry per registry type
 //! - [`server`]: LSP server initialization and lifecycle
+//! - [`symbol`]: Workspace symbols for dependencies
+//! - [`workspace`]: Workspace member discovery for startup prefetch
 
 pub mod backend;
+pub mod catalog;
 pub mod code_action;
+pub mod completion;
 pub mod diagnostics;
+pub mod folding_range;
+pub mod hover;
+pub mod inlay_hints;
 pub mod refresh;
 pub mod resolver;
 pub mod server;
+pub mod symbol;
+pub mod workspace;
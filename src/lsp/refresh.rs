@@ -1,5 +1,6 @@
 //! Background refresh logic for package version cache
 
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
 use futures::future::join_all;
@@ -10,27 +11,33 @@ use crate::config::FETCH_STAGGER_DELAY_MS;
 use crate::parser::types::{PackageInfo, RegistryType};
 use crate::version::cache::PackageId;
 use crate::version::checker::VersionStorer;
+use crate::version::circuit_breaker::{CircuitBreaker, RequestPermit};
 use crate::version::error::RegistryError;
 use crate::version::registry::Registry;
+use crate::version::types::{Advisory, FetchResult};
 
 /// Fetch and cache a single package's versions
 ///
 /// Handles:
 /// - Acquiring fetch lock (prevents duplicate fetches)
-/// - Fetching versions from registry
+/// - Skipping the fetch while the registry's circuit is open
+/// - Fetching versions from registry, conditional on the cached ETag/Last-Modified
 /// - Saving versions and dist tags to cache
 /// - Releasing fetch lock
 ///
 /// Returns true if the package was successfully fetched and cached.
+/// A 304 Not Modified response keeps the cached versions and returns false.
 async fn fetch_and_cache_package<S: VersionStorer>(
     storer: &S,
     registry: &dyn Registry,
+    breaker: &CircuitBreaker,
     registry_type: RegistryType,
     package_name: &str,
 ) -> bool {
     let registry_type_str = registry_type.as_str();
 
-    // Try to acquire fetch lock (returns false if another process is fetching)
+    // Try to acquire fetch lock (returns false if another process is fetching
+    // or the package was recently marked as not found)
     let can_fetch = storer
         .try_start_fetch(registry_type, package_name)
         .inspect_err(|e| {
@@ -43,17 +50,112 @@ async fn fetch_and_cache_package<S: VersionStorer>(
 
     if !can_fetch {
         info!(
-            "Skipping {}/{}: already being fetched by another process",
+            "Skipping {}/{}: already being fetched or recently not found",
             registry_type_str, package_name
         );
         return false;
     }
 
-    let success = match registry.fetch_all_versions(package_name).await {
-        Ok(pkg_versions) => {
+    let success = if let Some(permit) = breaker.allow_request(registry_type) {
+        fetch_with_lock_held(storer, registry, permit, registry_type, package_name).await
+    } else {
+        info!(
+            "Skipping {}/{}: circuit open after repeated registry failures",
+            registry_type_str, package_name
+        );
+        false
+    };
+
+    // Release fetch lock (always call regardless of success/failure)
+    let _ = storer
+        .finish_fetch(registry_type, package_name)
+        .inspect_err(|e| {
+            error!(
+                "Failed to finish fetch for {}/{}: {}",
+                registry_type_str, package_name, e
+            )
+        });
+
+    success
+}
+
+/// Fetch a package and save it to cache, reporting the outcome to the circuit breaker
+///
+/// Not-found responses count as successes since the registry answered.
+/// If this future is dropped before the registry answers, the permit
+/// records a failure.
+async fn fetch_with_lock_held<S: VersionStorer>(
+    storer: &S,
+    registry: &dyn Registry,
+    permit: RequestPermit,
+    registry_type: RegistryType,
+    package_name: &str,
+) -> bool {
+    let registry_type_str = registry_type.as_str();
+
+    let cache_metadata = storer
+        .get_cache_metadata(registry_type, package_name)
+        .inspect_err(|e| {
+            error!(
+                "Failed to get cache metadata for {}/{}: {}",
+                registry_type_str, package_name, e
+            )
+        })
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+    let result = registry
+        .fetch_versions_if_modified(package_name, &cache_metadata)
+        .await;
+    match &result {
+        Err(RegistryError::NotFound(_)) | Ok(_) => permit.record_success(),
+        Err(_) => permit.record_failure(),
+    }
+
+    match result {
+        Ok(FetchResult::NotModified) => {
+            info!(
+                "Not modified: {}/{}. Keeping cached versions.",
+                registry_type_str, package_name
+            );
+            // The cached versions were just revalidated, so they are fresh again
+            let _ = storer
+                .touch_package(registry_type, package_name)
+                .inspect_err(|e| {
+                    error!(
+                        "Failed to mark {}/{} as fresh: {}",
+                        registry_type_str, package_name, e
+                    );
+                });
+
+            // Advisories can be published without a new release
+            let versions = storer
+                .get_versions_ordered_by_semver(registry_type, package_name, false)
+                .inspect_err(|e| {
+                    error!(
+                        "Failed to get cached versions for {}/{}: {}",
+                        registry_type_str, package_name, e
+                    );
+                })
+                .unwrap_or_default();
+            if let Some(advisories) = registry.fetch_advisories(package_name, &versions).await {
+                save_advisories(storer, registry_type, package_name, &advisories);
+            }
+            false
+        }
+        Ok(FetchResult::Modified(pkg_versions)) => {
             let version_count = pkg_versions.versions.len();
-            let save_result =
-                storer.replace_versions(registry_type, package_name, pkg_versions.versions);
+            let advisories = registry
+                .fetch_advisories(package_name, &pkg_versions.versions)
+                .await;
+            let metadata = pkg_versions.metadata();
+            let save_result = storer.replace_versions(
+                registry_type,
+                package_name,
+                pkg_versions.versions,
+                &pkg_versions.yanked,
+            );
 
             if save_result
                 .inspect_err(|e| {
@@ -81,6 +183,56 @@ async fn fetch_and_cache_package<S: VersionStorer>(
                         });
                 }
 
+                // Save the deprecation message (clears it once lifted)
+                let _ = storer
+                    .save_deprecated(registry_type, package_name, pkg_versions.deprecated.clone())
+                    .inspect_err(|e| {
+                        error!(
+                            "Failed to save deprecation for {}/{}: {}",
+                            registry_type_str, package_name, e
+                        );
+                    });
+
+                // A failed advisory lookup keeps the cached advisories
+                if let Some(advisories) = advisories {
+                    save_advisories(storer, registry_type, package_name, &advisories);
+                }
+
+                // Save validators for the next conditional fetch (clears stale ones)
+                let _ = storer
+                    .save_cache_metadata(registry_type, package_name, &pkg_versions.cache_metadata)
+                    .inspect_err(|e| {
+                        error!(
+                            "Failed to save cache metadata for {}/{}: {}",
+                            registry_type_str, package_name, e
+                        );
+                    });
+
+                // Save homepage/repository links if available. Registries
+                // leaving them out of the fetched document (npm's abbreviated
+                // metadata) are asked once; their answer is saved even when
+                // empty so it is not asked again
+                let metadata = if !metadata.is_empty() {
+                    Some(metadata)
+                } else if storer
+                    .get_metadata(registry_type, package_name)
+                    .is_ok_and(|cached| cached.is_none())
+                {
+                    registry.fetch_metadata(package_name).await
+                } else {
+                    None
+                };
+                if let Some(metadata) = metadata {
+                    let _ = storer
+                        .save_metadata(registry_type, package_name, &metadata)
+                        .inspect_err(|e| {
+                            error!(
+                                "Failed to save metadata for {}/{}: {}",
+                                registry_type_str, package_name, e
+                            );
+                        });
+                }
+
                 true
             } else {
                 false
@@ -108,19 +260,26 @@ async fn fetch_and_cache_package<S: VersionStorer>(
             );
             false
         }
-    };
+    }
+}
 
-    // Release fetch lock (always call regardless of success/failure)
+/// Save a package's security advisories (an empty list clears them once fixed)
+fn save_advisories<S: VersionStorer>(
+    storer: &S,
+    registry_type: RegistryType,
+    package_name: &str,
+    advisories: &[Advisory],
+) {
     let _ = storer
-        .finish_fetch(registry_type, package_name)
+        .save_advisories(registry_type, package_name, advisories)
         .inspect_err(|e| {
             error!(
-                "Failed to finish fetch for {}/{}: {}",
-                registry_type_str, package_name, e
-            )
+                "Failed to save advisories for {}/{}: {}",
+                registry_type.as_str(),
+                package_name,
+                e
+            );
         });
-
-    success
 }
 
 /// Refresh versions for packages that need updating
@@ -129,11 +288,23 @@ async fn fetch_and_cache_package<S: VersionStorer>(
 /// Uses try_start_fetch/finish_fetch to prevent duplicate fetches across processes.
 /// Errors are logged but do not stop processing of other packages.
 /// Fetches are executed in parallel with staggered start times to avoid rate limiting.
+///
+/// Without `force`, packages that are no longer stale (e.g. another process
+/// sharing the cache refreshed them since they were listed) are skipped.
+/// `force` fetches every package, for refreshes the user asked for.
 pub async fn refresh_packages<S: VersionStorer>(
     storer: &S,
     registry: &dyn Registry,
+    breaker: &CircuitBreaker,
     packages: Vec<PackageId>,
+    force: bool,
 ) {
+    let packages = if force {
+        packages
+    } else {
+        skip_fresh_packages(storer, packages)
+    };
+
     let futures = packages.into_iter().enumerate().map(|(i, package)| {
         let delay = Duration::from_millis(FETCH_STAGGER_DELAY_MS * i as u64);
         async move {
@@ -141,6 +312,7 @@ pub async fn refresh_packages<S: VersionStorer>(
             fetch_and_cache_package(
                 storer,
                 registry,
+                breaker,
                 package.registry_type,
                 &package.package_name,
             )
@@ -151,6 +323,46 @@ pub async fn refresh_packages<S: VersionStorer>(
     join_all(futures).await;
 }
 
+/// Drop the packages that are cached and within the refresh interval
+///
+/// Packages not in the cache are kept. On a cache error every package is
+/// kept, so a refresh is never lost.
+fn skip_fresh_packages<S: VersionStorer>(storer: &S, packages: Vec<PackageId>) -> Vec<PackageId> {
+    let mut names_by_registry: HashMap<RegistryType, Vec<String>> = HashMap::new();
+    for package in &packages {
+        names_by_registry
+            .entry(package.registry_type)
+            .or_default()
+            .push(package.package_name.clone());
+    }
+
+    let mut fresh: HashSet<(RegistryType, String)> = HashSet::new();
+    for (registry_type, names) in names_by_registry {
+        let (Ok(not_in_cache), Ok(stale)) = (
+            storer.filter_packages_not_in_cache(registry_type, &names),
+            storer.filter_stale_packages(registry_type, &names),
+        ) else {
+            error!(
+                "Failed to check staleness of {} packages, refreshing all",
+                registry_type.as_str()
+            );
+            continue;
+        };
+        fresh.extend(
+            names
+                .into_iter()
+                .filter(|name| !not_in_cache.contains(name) && !stale.contains(name))
+                .map(|name| (registry_type, name)),
+        );
+    }
+    debug!("Skipping refresh of fresh packages: {:?}", fresh);
+
+    packages
+        .into_iter()
+        .filter(|package| !fresh.contains(&(package.registry_type, package.package_name.clone())))
+        .collect()
+}
+
 /// Fetch packages that are not in the cache (on-demand fetch)
 ///
 /// Identifies packages not in cache, fetches from registry, and updates cache.
@@ -160,6 +372,7 @@ pub async fn refresh_packages<S: VersionStorer>(
 pub async fn fetch_missing_packages<S: VersionStorer>(
     storer: &S,
     registry: &dyn Registry,
+    breaker: &CircuitBreaker,
     packages: &[PackageInfo],
 ) -> Vec<String> {
     if packages.is_empty() {
@@ -180,36 +393,107 @@ pub async fn fetch_missing_packages<S: VersionStorer>(
         .unwrap_or_default();
     debug!("Packages not in cache: {:?}", not_in_cache);
 
-    // Create a HashSet for efficient lookup
-    let not_in_cache_set: std::collections::HashSet<_> = not_in_cache.into_iter().collect();
+    if not_in_cache.is_empty() {
+        debug!("All packages are already in cache");
+        return Vec::new();
+    }
 
-    // Filter original packages to those not in cache
-    let packages_to_fetch: Vec<_> = packages
-        .iter()
-        .filter(|p| not_in_cache_set.contains(&p.name))
-        .collect();
+    fetch_packages(
+        storer,
+        registry,
+        breaker,
+        registry_type,
+        dedup(not_in_cache),
+    )
+    .await
+}
 
-    if packages_to_fetch.is_empty() {
-        debug!("All packages are already in cache");
+/// Refresh packages whose cached versions are stale (stale-while-revalidate)
+///
+/// Stale versions are still used to publish diagnostics immediately; this
+/// fetches fresh versions so the caller can republish once they arrive.
+/// Returns the list of packages that were successfully refreshed.
+pub async fn fetch_stale_packages<S: VersionStorer>(
+    storer: &S,
+    registry: &dyn Registry,
+    breaker: &CircuitBreaker,
+    packages: &[PackageInfo],
+) -> Vec<String> {
+    let Some(first) = packages.first() else {
+        return Vec::new();
+    };
+    let registry_type = first.registry_type;
+
+    let package_names: Vec<_> = packages.iter().map(|p| p.name.clone()).collect();
+    let stale = storer
+        .filter_stale_packages(registry_type, &package_names)
+        .inspect_err(|e| error!("Failed to filter stale packages: {}", e))
+        .unwrap_or_default();
+    debug!("Stale packages: {:?}", stale);
+
+    if stale.is_empty() {
         return Vec::new();
     }
 
-    let futures = packages_to_fetch
+    fetch_packages(storer, registry, breaker, registry_type, dedup(stale)).await
+}
+
+/// Fetch every given package, even if its cached versions are fresh
+///
+/// The cached versions are only replaced by a successful fetch, so a failing
+/// registry keeps serving them. Returns the list of packages that were
+/// successfully fetched.
+pub async fn force_fetch_packages<S: VersionStorer>(
+    storer: &S,
+    registry: &dyn Registry,
+    breaker: &CircuitBreaker,
+    packages: &[PackageInfo],
+) -> Vec<String> {
+    let Some(first) = packages.first() else {
+        return Vec::new();
+    };
+    let registry_type = first.registry_type;
+
+    let package_names = packages.iter().map(|p| p.name.clone()).collect();
+    fetch_packages(
+        storer,
+        registry,
+        breaker,
+        registry_type,
+        dedup(package_names),
+    )
+    .await
+}
+
+/// Fetch the given packages in parallel with staggered start times.
+/// Returns the names of the packages that were successfully fetched and cached.
+async fn fetch_packages<S: VersionStorer>(
+    storer: &S,
+    registry: &dyn Registry,
+    breaker: &CircuitBreaker,
+    registry_type: RegistryType,
+    package_names: Vec<String>,
+) -> Vec<String> {
+    let futures = package_names
         .into_iter()
         .enumerate()
-        .map(|(i, package)| {
+        .map(|(i, package_name)| {
             let delay = Duration::from_millis(FETCH_STAGGER_DELAY_MS * i as u64);
-            let package_name = package.name.clone();
             async move {
                 sleep(delay).await;
                 info!(
-                    "Fetching missing package {}/{} from registry",
-                    package.registry_type.as_str(),
-                    package.name
+                    "Fetching package {}/{} from registry",
+                    registry_type.as_str(),
+                    package_name
                 );
-                let success =
-                    fetch_and_cache_package(storer, registry, package.registry_type, &package.name)
-                        .await;
+                let success = fetch_and_cache_package(
+                    storer,
+                    registry,
+                    breaker,
+                    registry_type,
+                    &package_name,
+                )
+                .await;
                 if success { Some(package_name) } else { None }
             }
         });
@@ -217,13 +501,23 @@ pub async fn fetch_missing_packages<S: VersionStorer>(
     join_all(futures).await.into_iter().flatten().collect()
 }
 
+/// Remove duplicate package names, keeping the first occurrence
+fn dedup(package_names: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    package_names
+        .into_iter()
+        .filter(|name| seen.insert(name.clone()))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::parser::types::RegistryType;
     use crate::version::cache::Cache;
+    use crate::version::registries::npm::NpmRegistry;
     use crate::version::registry::MockRegistry;
-    use crate::version::types::PackageVersions;
+    use crate::version::types::{CacheMetadata, PackageMetadata, PackageVersions};
     use std::sync::Arc;
     use tempfile::TempDir;
 
@@ -234,16 +528,27 @@ mod tests {
         (temp_dir, Arc::new(cache))
     }
 
+    /// A registry that does not check advisories or look up links separately
+    fn mock_registry() -> MockRegistry {
+        let mut registry = MockRegistry::new();
+        registry.expect_fetch_advisories().returning(|_, _| None);
+        registry.expect_fetch_metadata().returning(|_| None);
+        registry
+    }
+
     fn make_package_info(name: &str, version: &str) -> PackageInfo {
         PackageInfo {
             name: name.to_string(),
             version: version.to_string(),
             commit_hash: None,
             registry_type: RegistryType::GitHubActions,
+            registry_url: None,
             start_offset: 0,
             end_offset: version.len(),
             line: 0,
             column: 0,
+            section_name: None,
+            section_line: None,
             extra_info: None,
         }
     }
@@ -252,19 +557,19 @@ mod tests {
     async fn refresh_packages_fetches_versions_from_registry_and_saves_to_cache() {
         let (_temp_dir, cache) = create_test_cache();
 
-        let mut registry = MockRegistry::new();
+        let mut registry = mock_registry();
         registry
             .expect_registry_type()
             .returning(|| RegistryType::GitHubActions);
         registry
-            .expect_fetch_all_versions()
-            .withf(|name| name == "actions/checkout")
+            .expect_fetch_versions_if_modified()
+            .withf(|name, _| name == "actions/checkout")
             .times(1)
-            .returning(|_| {
-                Ok(PackageVersions::new(vec![
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(PackageVersions::new(vec![
                     "v4.0.0".to_string(),
                     "v3.0.0".to_string(),
-                ]))
+                ])))
             });
 
         let packages = vec![PackageId {
@@ -272,31 +577,218 @@ mod tests {
             package_name: "actions/checkout".to_string(),
         }];
 
-        refresh_packages(&*cache, &registry, packages).await;
+        refresh_packages(
+            &*cache,
+            &registry,
+            &CircuitBreaker::default(),
+            packages,
+            false,
+        )
+        .await;
 
         // Verify versions were saved to cache
         let mut versions = cache
-            .get_versions(RegistryType::GitHubActions, "actions/checkout")
+            .get_versions(RegistryType::GitHubActions, "actions/checkout", false)
             .unwrap();
         versions.sort();
         assert_eq!(versions, vec!["v3.0.0", "v4.0.0"]);
     }
 
+    #[tokio::test]
+    async fn refresh_packages_looks_up_npm_links_missing_from_abbreviated_metadata_once() {
+        let (_temp_dir, cache) = create_test_cache();
+        let mut server = mockito::Server::new_async().await;
+        let abbreviated_mock = server
+            .mock("GET", "/lodash")
+            .match_header(
+                "accept",
+                "application/vnd.npm.install-v1+json; q=1.0, application/json; q=0.8",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/vnd.npm.install-v1+json")
+            .with_body(
+                r#"{
+                    "name": "lodash",
+                    "dist-tags": { "latest": "4.17.21" },
+                    "versions": { "4.17.21": { "name": "lodash", "version": "4.17.21" } }
+                }"#,
+            )
+            .expect(2)
+            .create_async()
+            .await;
+        let full_mock = server
+            .mock("GET", "/lodash")
+            .match_header("accept", "application/json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "name": "lodash",
+                    "versions": { "4.17.21": {} },
+                    "homepage": "https://lodash.com/",
+                    "repository": {
+                        "type": "git",
+                        "url": "git+https://github.com/lodash/lodash.git"
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let registry = NpmRegistry::new(&server.url());
+        let packages = vec![PackageId {
+            registry_type: RegistryType::Npm,
+            package_name: "lodash".to_string(),
+        }];
+
+        // The second refresh finds the links cached and skips the full document
+        for _ in 0..2 {
+            refresh_packages(
+                &*cache,
+                &registry,
+                &CircuitBreaker::default(),
+                packages.clone(),
+                true,
+            )
+            .await;
+        }
+
+        abbreviated_mock.assert_async().await;
+        full_mock.assert_async().await;
+        let metadata = cache.get_metadata(RegistryType::Npm, "lodash").unwrap();
+        assert_eq!(
+            metadata,
+            Some(PackageMetadata {
+                homepage: Some("https://lodash.com/".to_string()),
+                repository: Some("https://github.com/lodash/lodash".to_string()),
+            })
+        );
+        assert_eq!(
+            metadata.unwrap().changelog_url().as_deref(),
+            Some("https://github.com/lodash/lodash/releases")
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_packages_saves_dist_tags_so_latest_tag_wins_over_highest_version() {
+        let (_temp_dir, cache) = create_test_cache();
+
+        let mut registry = mock_registry();
+        registry
+            .expect_registry_type()
+            .returning(|| RegistryType::Npm);
+        registry
+            .expect_fetch_versions_if_modified()
+            .withf(|name, _| name == "pkg")
+            .times(1)
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(PackageVersions::with_dist_tags(
+                    vec![
+                        "1.5.0".to_string(),
+                        "2.0.0-beta.1".to_string(),
+                        "2.0.0".to_string(),
+                    ],
+                    HashMap::from([
+                        ("latest".to_string(), "1.5.0".to_string()),
+                        ("next".to_string(), "2.0.0".to_string()),
+                    ]),
+                )))
+            });
+
+        let packages = vec![PackageId {
+            registry_type: RegistryType::Npm,
+            package_name: "pkg".to_string(),
+        }];
+
+        refresh_packages(
+            &*cache,
+            &registry,
+            &CircuitBreaker::default(),
+            packages,
+            false,
+        )
+        .await;
+
+        assert_eq!(
+            cache
+                .get_dist_tag(RegistryType::Npm, "pkg", "next")
+                .unwrap(),
+            Some("2.0.0".to_string())
+        );
+        assert_eq!(
+            cache.get_latest_version(RegistryType::Npm, "pkg").unwrap(),
+            Some("1.5.0".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_packages_marks_versions_yanked_after_they_were_cached() {
+        let (_temp_dir, cache) = create_test_cache();
+        cache
+            .replace_versions(
+                RegistryType::CratesIo,
+                "serde",
+                vec!["1.0.0".to_string(), "1.0.1".to_string()],
+                &[],
+            )
+            .unwrap();
+
+        let mut registry = mock_registry();
+        registry
+            .expect_registry_type()
+            .returning(|| RegistryType::CratesIo);
+        registry
+            .expect_fetch_versions_if_modified()
+            .times(1)
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(
+                    PackageVersions::new(vec!["1.0.0".to_string(), "1.0.2".to_string()])
+                        .with_yanked(vec!["1.0.1".to_string()]),
+                ))
+            });
+
+        let packages = vec![PackageId {
+            registry_type: RegistryType::CratesIo,
+            package_name: "serde".to_string(),
+        }];
+
+        refresh_packages(
+            &*cache,
+            &registry,
+            &CircuitBreaker::default(),
+            packages,
+            true,
+        )
+        .await;
+
+        assert_eq!(
+            cache
+                .get_versions(RegistryType::CratesIo, "serde", false)
+                .unwrap(),
+            vec!["1.0.0", "1.0.2"]
+        );
+        assert!(
+            !cache
+                .version_exists(RegistryType::CratesIo, "serde", "1.0.1", false)
+                .unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn refresh_packages_continues_on_registry_error() {
         let (_temp_dir, cache) = create_test_cache();
 
-        let mut registry = MockRegistry::new();
+        let mut registry = mock_registry();
         registry
             .expect_registry_type()
             .returning(|| RegistryType::GitHubActions);
 
         // First package fails
         registry
-            .expect_fetch_all_versions()
-            .withf(|name| name == "failing/repo")
+            .expect_fetch_versions_if_modified()
+            .withf(|name, _| name == "failing/repo")
             .times(1)
-            .returning(|_| {
+            .returning(|_, _| {
                 Err(crate::version::error::RegistryError::NotFound(
                     "failing/repo".to_string(),
                 ))
@@ -304,10 +796,14 @@ mod tests {
 
         // Second package succeeds
         registry
-            .expect_fetch_all_versions()
-            .withf(|name| name == "actions/checkout")
+            .expect_fetch_versions_if_modified()
+            .withf(|name, _| name == "actions/checkout")
             .times(1)
-            .returning(|_| Ok(PackageVersions::new(vec!["v4.0.0".to_string()])));
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(PackageVersions::new(vec![
+                    "v4.0.0".to_string(),
+                ])))
+            });
 
         let packages = vec![
             PackageId {
@@ -320,70 +816,248 @@ mod tests {
             },
         ];
 
-        refresh_packages(&*cache, &registry, packages).await;
+        refresh_packages(
+            &*cache,
+            &registry,
+            &CircuitBreaker::default(),
+            packages,
+            false,
+        )
+        .await;
 
         // First package should not be in cache
         let failing_versions = cache
-            .get_versions(RegistryType::GitHubActions, "failing/repo")
+            .get_versions(RegistryType::GitHubActions, "failing/repo", false)
             .unwrap();
         assert!(failing_versions.is_empty());
 
         // Second package should be saved
         let checkout_versions = cache
-            .get_versions(RegistryType::GitHubActions, "actions/checkout")
+            .get_versions(RegistryType::GitHubActions, "actions/checkout", false)
             .unwrap();
         assert_eq!(checkout_versions, vec!["v4.0.0"]);
     }
 
+    #[tokio::test]
+    async fn refresh_packages_skips_fetch_while_circuit_is_open() {
+        let (_temp_dir, cache) = create_test_cache();
+        let breaker = CircuitBreaker::new(1, std::time::Duration::from_secs(60));
+
+        let mut registry = mock_registry();
+        registry
+            .expect_registry_type()
+            .returning(|| RegistryType::GitHubActions);
+        // Only the first fetch reaches the registry; its failure opens the circuit
+        registry
+            .expect_fetch_versions_if_modified()
+            .times(1)
+            .returning(|_, _| {
+                Err(crate::version::error::RegistryError::InvalidResponse(
+                    "HTTP 503".to_string(),
+                ))
+            });
+
+        let package = PackageId {
+            registry_type: RegistryType::GitHubActions,
+            package_name: "actions/checkout".to_string(),
+        };
+
+        refresh_packages(&*cache, &registry, &breaker, vec![package.clone()], false).await;
+        refresh_packages(&*cache, &registry, &breaker, vec![package], false).await;
+
+        // The fetch lock is released even though the fetch was skipped
+        assert!(
+            cache
+                .try_start_fetch(RegistryType::GitHubActions, "actions/checkout")
+                .unwrap()
+        );
+    }
+
+    /// A registry whose fetches never complete
+    struct StalledRegistry;
+
+    #[async_trait::async_trait]
+    impl Registry for StalledRegistry {
+        fn registry_type(&self) -> RegistryType {
+            RegistryType::GitHubActions
+        }
+
+        async fn fetch_all_versions(
+            &self,
+            _package_name: &str,
+        ) -> Result<PackageVersions, RegistryError> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelled_probe_reopens_circuit() {
+        use crate::version::circuit_breaker::CircuitState;
+
+        let (_temp_dir, cache) = create_test_cache();
+        let breaker = CircuitBreaker::new(1, std::time::Duration::ZERO);
+
+        // One failure opens the circuit; with no cooldown the next fetch is the probe
+        drop(breaker.allow_request(RegistryType::GitHubActions));
+
+        let package = PackageId {
+            registry_type: RegistryType::GitHubActions,
+            package_name: "actions/checkout".to_string(),
+        };
+
+        // The probe is cancelled mid-fetch, like a fetch hitting FETCH_TIMEOUT_MS
+        let result = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            refresh_packages(&*cache, &StalledRegistry, &breaker, vec![package], false),
+        )
+        .await;
+        assert!(result.is_err());
+
+        assert!(matches!(
+            breaker.state(RegistryType::GitHubActions),
+            CircuitState::Open { .. }
+        ));
+        assert!(breaker.allow_request(RegistryType::GitHubActions).is_some());
+    }
+
     #[tokio::test]
     async fn refresh_packages_handles_empty_package_list() {
         let (_temp_dir, cache) = create_test_cache();
 
-        let mut registry = MockRegistry::new();
+        let mut registry = mock_registry();
         registry
             .expect_registry_type()
             .returning(|| RegistryType::GitHubActions);
         // fetch_all_versions should never be called
-        registry.expect_fetch_all_versions().times(0);
+        registry.expect_fetch_versions_if_modified().times(0);
 
         let packages = vec![];
 
-        refresh_packages(&*cache, &registry, packages).await;
+        refresh_packages(
+            &*cache,
+            &registry,
+            &CircuitBreaker::default(),
+            packages,
+            false,
+        )
+        .await;
         // No panic, no error
     }
 
+    #[rstest::rstest]
+    #[case(false, 0)]
+    #[case(true, 1)]
+    #[tokio::test]
+    async fn refresh_packages_skips_fresh_packages_unless_forced(
+        #[case] force: bool,
+        #[case] expected_fetches: usize,
+    ) {
+        let (_temp_dir, cache) = create_test_cache();
+        cache
+            .replace_versions(
+                RegistryType::GitHubActions,
+                "actions/checkout",
+                vec!["v4.0.0".to_string()],
+                &[],
+            )
+            .unwrap();
+
+        let mut registry = mock_registry();
+        registry
+            .expect_registry_type()
+            .returning(|| RegistryType::GitHubActions);
+        registry
+            .expect_fetch_versions_if_modified()
+            .times(expected_fetches)
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(PackageVersions::new(vec![
+                    "v4.0.0".to_string(),
+                    "v5.0.0".to_string(),
+                ])))
+            });
+
+        let packages = vec![PackageId {
+            registry_type: RegistryType::GitHubActions,
+            package_name: "actions/checkout".to_string(),
+        }];
+
+        refresh_packages(
+            &*cache,
+            &registry,
+            &CircuitBreaker::default(),
+            packages,
+            force,
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn fetch_missing_packages_fetches_packages_not_in_cache() {
         let (_temp_dir, cache) = create_test_cache();
 
-        let mut registry = MockRegistry::new();
+        let mut registry = mock_registry();
         registry
             .expect_registry_type()
             .returning(|| RegistryType::GitHubActions);
         registry
-            .expect_fetch_all_versions()
-            .withf(|name| name == "actions/checkout")
+            .expect_fetch_versions_if_modified()
+            .withf(|name, _| name == "actions/checkout")
             .times(1)
-            .returning(|_| {
-                Ok(PackageVersions::new(vec![
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(PackageVersions::new(vec![
                     "v4.0.0".to_string(),
                     "v3.0.0".to_string(),
-                ]))
+                ])))
             });
 
         let packages = vec![make_package_info("actions/checkout", "v3.0.0")];
 
-        let fetched = fetch_missing_packages(&*cache, &registry, &packages).await;
+        let fetched =
+            fetch_missing_packages(&*cache, &registry, &CircuitBreaker::default(), &packages).await;
 
         assert_eq!(fetched, vec!["actions/checkout"]);
 
         // Verify versions were saved to cache
         let versions = cache
-            .get_versions(RegistryType::GitHubActions, "actions/checkout")
+            .get_versions(RegistryType::GitHubActions, "actions/checkout", false)
             .unwrap();
         assert!(!versions.is_empty());
     }
 
+    #[tokio::test]
+    async fn fetch_missing_packages_saves_package_metadata() {
+        let (_temp_dir, cache) = create_test_cache();
+
+        let mut registry = mock_registry();
+        registry
+            .expect_registry_type()
+            .returning(|| RegistryType::GitHubActions);
+        registry
+            .expect_fetch_versions_if_modified()
+            .times(1)
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(
+                    PackageVersions::new(vec!["v4.0.0".to_string()]).with_metadata(
+                        None,
+                        Some("https://github.com/actions/checkout".to_string()),
+                    ),
+                ))
+            });
+
+        let packages = vec![make_package_info("actions/checkout", "v3.0.0")];
+
+        fetch_missing_packages(&*cache, &registry, &CircuitBreaker::default(), &packages).await;
+
+        let metadata = cache
+            .get_metadata(RegistryType::GitHubActions, "actions/checkout")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            metadata.changelog_url(),
+            Some("https://github.com/actions/checkout/releases".to_string())
+        );
+    }
+
     #[tokio::test]
     async fn fetch_missing_packages_skips_packages_already_in_cache() {
         let (_temp_dir, cache) = create_test_cache();
@@ -394,19 +1068,21 @@ mod tests {
                 RegistryType::GitHubActions,
                 "actions/checkout",
                 vec!["v4.0.0".to_string()],
+                &[],
             )
             .unwrap();
 
-        let mut registry = MockRegistry::new();
+        let mut registry = mock_registry();
         registry
             .expect_registry_type()
             .returning(|| RegistryType::GitHubActions);
         // fetch_all_versions should NOT be called for cached package
-        registry.expect_fetch_all_versions().times(0);
+        registry.expect_fetch_versions_if_modified().times(0);
 
         let packages = vec![make_package_info("actions/checkout", "v3.0.0")];
 
-        let fetched = fetch_missing_packages(&*cache, &registry, &packages).await;
+        let fetched =
+            fetch_missing_packages(&*cache, &registry, &CircuitBreaker::default(), &packages).await;
 
         // No packages should be fetched
         assert!(fetched.is_empty());
@@ -422,33 +1098,368 @@ mod tests {
                 RegistryType::GitHubActions,
                 "actions/checkout",
                 vec!["v4.0.0".to_string()],
+                &[],
             )
             .unwrap();
 
-        let mut registry = MockRegistry::new();
+        let mut registry = mock_registry();
         registry
             .expect_registry_type()
             .returning(|| RegistryType::GitHubActions);
         // Only the uncached package should be fetched
         registry
-            .expect_fetch_all_versions()
-            .withf(|name| name == "actions/setup-node")
+            .expect_fetch_versions_if_modified()
+            .withf(|name, _| name == "actions/setup-node")
             .times(1)
-            .returning(|_| Ok(PackageVersions::new(vec!["v4.0.0".to_string()])));
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(PackageVersions::new(vec![
+                    "v4.0.0".to_string(),
+                ])))
+            });
 
         let packages = vec![
             make_package_info("actions/checkout", "v3.0.0"),
             make_package_info("actions/setup-node", "v3.0.0"),
         ];
 
-        let fetched = fetch_missing_packages(&*cache, &registry, &packages).await;
+        let fetched =
+            fetch_missing_packages(&*cache, &registry, &CircuitBreaker::default(), &packages).await;
 
         assert_eq!(fetched, vec!["actions/setup-node"]);
 
         // Verify only setup-node was fetched
         let setup_node_versions = cache
-            .get_versions(RegistryType::GitHubActions, "actions/setup-node")
+            .get_versions(RegistryType::GitHubActions, "actions/setup-node", false)
             .unwrap();
         assert!(!setup_node_versions.is_empty());
     }
+
+    #[tokio::test]
+    async fn fetch_stale_packages_refreshes_packages_older_than_refresh_interval() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        // refresh_interval = 100ms
+        let cache = Cache::new(&db_path, 100, false).unwrap();
+        cache
+            .replace_versions(
+                RegistryType::GitHubActions,
+                "actions/checkout",
+                vec!["v4.0.0".to_string()],
+                &[],
+            )
+            .unwrap();
+
+        // Wait for the package to become stale
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        let mut registry = mock_registry();
+        registry
+            .expect_fetch_versions_if_modified()
+            .withf(|name, _| name == "actions/checkout")
+            .times(1)
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(PackageVersions::new(vec![
+                    "v5.0.0".to_string(),
+                    "v4.0.0".to_string(),
+                ])))
+            });
+
+        let packages = vec![make_package_info("actions/checkout", "v4.0.0")];
+
+        let fetched =
+            fetch_stale_packages(&cache, &registry, &CircuitBreaker::default(), &packages).await;
+
+        assert_eq!(fetched, vec!["actions/checkout"]);
+        assert_eq!(
+            cache
+                .get_latest_version(RegistryType::GitHubActions, "actions/checkout")
+                .unwrap(),
+            Some("v5.0.0".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_stale_packages_sends_cached_validators_and_keeps_versions_on_not_modified() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        // refresh_interval = 100ms
+        let cache = Cache::new(&db_path, 100, false).unwrap();
+        cache
+            .replace_versions(
+                RegistryType::GitHubActions,
+                "actions/checkout",
+                vec!["v4.0.0".to_string()],
+                &[],
+            )
+            .unwrap();
+        cache
+            .save_cache_metadata(
+                RegistryType::GitHubActions,
+                "actions/checkout",
+                &CacheMetadata {
+                    etag: Some("\"abc\"".to_string()),
+                    last_modified: None,
+                },
+            )
+            .unwrap();
+
+        // Wait for the package to become stale
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        let mut registry = mock_registry();
+        registry
+            .expect_fetch_versions_if_modified()
+            .withf(|name, cache_metadata| {
+                name == "actions/checkout" && cache_metadata.etag.as_deref() == Some("\"abc\"")
+            })
+            .times(1)
+            .returning(|_, _| Ok(FetchResult::NotModified));
+
+        let packages = vec![make_package_info("actions/checkout", "v4.0.0")];
+
+        let fetched =
+            fetch_stale_packages(&cache, &registry, &CircuitBreaker::default(), &packages).await;
+
+        assert!(fetched.is_empty());
+        assert_eq!(
+            cache
+                .get_versions(RegistryType::GitHubActions, "actions/checkout")
+                .unwrap(),
+            vec!["v4.0.0"]
+        );
+        // Revalidated versions are fresh until the refresh interval elapses again
+        assert!(
+            cache
+                .filter_stale_packages(
+                    RegistryType::GitHubActions,
+                    &["actions/checkout".to_string()]
+                )
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_missing_packages_saves_response_validators() {
+        let (_temp_dir, cache) = create_test_cache();
+
+        let mut registry = mock_registry();
+        registry
+            .expect_fetch_versions_if_modified()
+            .withf(|_, cache_metadata| cache_metadata.is_empty())
+            .times(1)
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(
+                    PackageVersions::new(vec!["v4.0.0".to_string()]).with_cache_metadata(
+                        CacheMetadata {
+                            etag: Some("\"abc\"".to_string()),
+                            last_modified: None,
+                        },
+                    ),
+                ))
+            });
+
+        let packages = vec![make_package_info("actions/checkout", "v3.0.0")];
+
+        fetch_missing_packages(&*cache, &registry, &CircuitBreaker::default(), &packages).await;
+
+        assert_eq!(
+            cache
+                .get_cache_metadata(RegistryType::GitHubActions, "actions/checkout")
+                .unwrap()
+                .and_then(|m| m.etag),
+            Some("\"abc\"".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_stale_packages_skips_fresh_packages() {
+        let (_temp_dir, cache) = create_test_cache();
+        cache
+            .replace_versions(
+                RegistryType::GitHubActions,
+                "actions/checkout",
+                vec!["v4.0.0".to_string()],
+                &[],
+            )
+            .unwrap();
+
+        let mut registry = mock_registry();
+        registry.expect_fetch_versions_if_modified().times(0);
+
+        let packages = vec![make_package_info("actions/checkout", "v4.0.0")];
+
+        let fetched =
+            fetch_stale_packages(&*cache, &registry, &CircuitBreaker::default(), &packages).await;
+
+        assert!(fetched.is_empty());
+    }
+
+    #[tokio::test]
+    async fn force_fetch_packages_refetches_fresh_packages_and_keeps_cache_on_failure() {
+        let (_temp_dir, cache) = create_test_cache();
+        for name in ["actions/checkout", "actions/setup-node"] {
+            cache
+                .replace_versions(
+                    RegistryType::GitHubActions,
+                    name,
+                    vec!["v3.0.0".to_string()],
+                    &[],
+                )
+                .unwrap();
+        }
+
+        let mut registry = mock_registry();
+        registry
+            .expect_fetch_versions_if_modified()
+            .withf(|name, _| name == "actions/checkout")
+            .times(1)
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(PackageVersions::new(vec![
+                    "v3.0.0".to_string(),
+                    "v4.0.0".to_string(),
+                ])))
+            });
+        registry
+            .expect_fetch_versions_if_modified()
+            .withf(|name, _| name == "actions/setup-node")
+            .times(1)
+            .returning(|_, _| {
+                Err(RegistryError::InvalidResponse(
+                    "Unexpected status: 503".to_string(),
+                ))
+            });
+
+        let packages = vec![
+            make_package_info("actions/checkout", "v3.0.0"),
+            make_package_info("actions/setup-node", "v3.0.0"),
+        ];
+
+        let fetched =
+            force_fetch_packages(&*cache, &registry, &CircuitBreaker::default(), &packages).await;
+
+        assert_eq!(fetched, vec!["actions/checkout"]);
+        assert_eq!(
+            cache
+                .get_versions(RegistryType::GitHubActions, "actions/checkout", false)
+                .unwrap(),
+            vec!["v3.0.0", "v4.0.0"]
+        );
+        assert_eq!(
+            cache
+                .get_versions(RegistryType::GitHubActions, "actions/setup-node", false)
+                .unwrap(),
+            vec!["v3.0.0"]
+        );
+    }
+
+    fn advisory(id: &str) -> Advisory {
+        Advisory {
+            id: id.to_string(),
+            severity: "HIGH".to_string(),
+            summary: "Potential segfault".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_stale_packages_checks_advisories_when_not_modified() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        // refresh_interval = 100ms
+        let cache = Cache::new(&db_path, 100, false).unwrap();
+        cache
+            .replace_versions(
+                RegistryType::CratesIo,
+                "time",
+                vec!["0.1.44".to_string(), "0.1.45".to_string()],
+                &[],
+            )
+            .unwrap();
+
+        // Wait for the package to become stale
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        let mut registry = MockRegistry::new();
+        registry
+            .expect_fetch_versions_if_modified()
+            .times(1)
+            .returning(|_, _| Ok(FetchResult::NotModified));
+        registry
+            .expect_fetch_advisories()
+            .withf(|name, versions| name == "time" && versions == ["0.1.44", "0.1.45"])
+            .times(1)
+            .returning(|_, _| Some(vec![advisory("RUSTSEC-2020-0071")]));
+
+        let mut package = make_package_info("time", "0.1.45");
+        package.registry_type = RegistryType::CratesIo;
+
+        fetch_stale_packages(&cache, &registry, &CircuitBreaker::default(), &[package]).await;
+
+        assert_eq!(
+            cache
+                .get_advisories(RegistryType::CratesIo, "time")
+                .unwrap(),
+            vec![advisory("RUSTSEC-2020-0071")]
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_packages_keeps_cached_advisories_when_lookup_fails() {
+        let (_temp_dir, cache) = create_test_cache();
+        cache
+            .replace_versions(
+                RegistryType::CratesIo,
+                "time",
+                vec!["0.1.45".to_string()],
+                &[],
+            )
+            .unwrap();
+        cache
+            .save_advisories(
+                RegistryType::CratesIo,
+                "time",
+                &[advisory("RUSTSEC-2020-0071")],
+            )
+            .unwrap();
+
+        let mut registry = MockRegistry::new();
+        registry
+            .expect_fetch_versions_if_modified()
+            .returning(|_, _| {
+                Ok(FetchResult::Modified(PackageVersions::new(vec![
+                    "0.1.45".to_string(),
+                    "0.1.46".to_string(),
+                ])))
+            });
+        registry.expect_fetch_advisories().returning(|_, _| None);
+        registry.expect_fetch_metadata().returning(|_| None);
+
+        let packages = vec![PackageId {
+            registry_type: RegistryType::CratesIo,
+            package_name: "time".to_string(),
+        }];
+
+        refresh_packages(
+            &*cache,
+            &registry,
+            &CircuitBreaker::default(),
+            packages,
+            true,
+        )
+        .await;
+
+        assert_eq!(
+            cache
+                .get_versions(RegistryType::CratesIo, "time", false)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            cache
+                .get_advisories(RegistryType::CratesIo, "time")
+                .unwrap(),
+            vec![advisory("RUSTSEC-2020-0071")]
+        );
+    }
 }
@@ -6,31 +6,57 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::config::{LspConfig, RegistryConfig};
+use crate::config::{CratesRegistryConfig, LspConfig, NpmRegistryConfig, RegistryConfig};
+use crate::parser::bun_lock::BunLockParser;
+use crate::parser::cargo_lock::CargoLockParser;
 use crate::parser::cargo_toml::CargoTomlParser;
 use crate::parser::compose::ComposeParser;
+use crate::parser::conda_env::CondaEnvParser;
+use crate::parser::conda_lock::CondaLockParser;
 use crate::parser::deno_json::DenoJsonParser;
+use crate::parser::dockerfile::DockerfileParser;
+use crate::parser::gemfile::GemfileParser;
 use crate::parser::github_actions::GitHubActionsParser;
 use crate::parser::go_mod::GoModParser;
+use crate::parser::go_work::GoWorkParser;
+use crate::parser::nvmrc::NvmrcParser;
 use crate::parser::package_json::PackageJsonParser;
+use crate::parser::package_lock::PackageLockParser;
 use crate::parser::pnpm_workspace::PnpmWorkspaceParser;
+use crate::parser::poetry_lock::PoetryLockParser;
+use crate::parser::pubspec_yaml::PubspecYamlParser;
 use crate::parser::pyproject_toml::PyprojectTomlParser;
+use crate::parser::requirements_txt::RequirementsTxtParser;
+use crate::parser::rust_toolchain::RustToolchainParser;
 use crate::parser::traits::Parser;
-use crate::parser::types::RegistryType;
+use crate::parser::types::{RegistryType, is_dockerfile, is_go_work_file, is_requirements_file};
 use crate::version::matcher::VersionMatcher;
 use crate::version::matchers::{
-    CratesVersionMatcher, DockerVersionMatcher, GitHubActionsMatcher, GoVersionMatcher,
-    JsrVersionMatcher, NpmVersionMatcher, PnpmCatalogMatcher, PypiVersionMatcher,
+    CondaVersionMatcher, CratesVersionMatcher, DockerVersionMatcher, GitHubActionsMatcher,
+    GoToolchainMatcher, GoVersionMatcher, JsrVersionMatcher, NodeVersionMatcher, NpmVersionMatcher,
+    PnpmCatalogMatcher, PubVersionMatcher, PypiVersionMatcher, RubyGemsVersionMatcher,
+    RustToolchainMatcher,
 };
+use crate::version::osv::OsvAdvisoryChecker;
+use crate::version::registries::conda::CondaRegistry;
 use crate::version::registries::crates_io::CratesIoRegistry;
 use crate::version::registries::docker::DockerRegistry;
 use crate::version::registries::github::{GitHubRegistry, TagShaFetcher};
 use crate::version::registries::go_proxy::GoProxyRegistry;
+use crate::version::registries::go_toolchain::GoToolchainRegistry;
 use crate::version::registries::jsr::JsrRegistry;
-use crate::version::registries::npm::NpmRegistry;
+use crate::version::registries::multi::MultiRegistry;
+use crate::version::registries::node::NodeReleaseRegistry;
+use crate::version::registries::npm::{NpmRegistry, ScopeRegistry};
+use crate::version::registries::pub_dev::PubRegistry;
 use crate::version::registries::pypi::PypiRegistry;
+use crate::version::registries::rubygems::RubyGemsRegistry;
+use crate::version::registries::rust_toolchain::RustToolchainRegistry;
 use crate::version::registry::Registry;
 
+/// Predicate selecting the documents a parser handles by URI
+pub type UriMatcher = fn(&str) -> bool;
+
 /// Groups all components needed to resolve and validate package versions for a specific registry.
 ///
 /// Each registry type (Npm, CratesIo, GoProxy, GitHubActions) has one PackageResolver instance
@@ -38,11 +64,17 @@ use crate::version::registry::Registry;
 /// - Parsing files to extract package information
 /// - Matching version specifications against available versions
 /// - Fetching package versions from the remote registry
+#[derive(Clone)]
 pub struct PackageResolver {
     parser: Arc<dyn Parser>,
     matcher: Arc<dyn VersionMatcher>,
     registry: Arc<dyn Registry>,
     sha_fetcher: Option<Arc<dyn TagShaFetcher>>,
+    /// Parsers for lock files, keyed by lock file name (e.g., "poetry.lock")
+    lock_parsers: Vec<(&'static str, Arc<dyn Parser>)>,
+    /// Parsers for files whose names vary (e.g., "requirements-dev.txt"),
+    /// keyed by a predicate on the URI
+    file_parsers: Vec<(UriMatcher, Arc<dyn Parser>)>,
 }
 
 impl PackageResolver {
@@ -57,6 +89,8 @@ impl PackageResolver {
             matcher,
             registry,
             sha_fetcher: None,
+            lock_parsers: Vec::new(),
+            file_parsers: Vec::new(),
         }
     }
 
@@ -68,11 +102,44 @@ impl PackageResolver {
         self
     }
 
+    /// Attach a parser for a lock file of this registry type. Lock files
+    /// share the matcher and registry with the manifest but need their own
+    /// parser since the file format is different.
+    pub fn with_lock_parser(mut self, file_name: &'static str, parser: Arc<dyn Parser>) -> Self {
+        self.lock_parsers.push((file_name, parser));
+        self
+    }
+
+    /// Attach a parser for other files of this registry type whose names
+    /// are not fixed, such as `requirements-dev.txt`, selected when
+    /// `matches` accepts the document URI
+    pub fn with_file_parser(mut self, matches: UriMatcher, parser: Arc<dyn Parser>) -> Self {
+        self.file_parsers.push((matches, parser));
+        self
+    }
+
     /// Get the parser for this registry type
     pub fn parser(&self) -> &Arc<dyn Parser> {
         &self.parser
     }
 
+    /// Get the parser for the document at `uri`, preferring a lock file
+    /// parser whose file name matches, then a file parser accepting the URI,
+    /// over the manifest parser
+    pub fn parser_for(&self, uri: &str) -> &Arc<dyn Parser> {
+        self.lock_parsers
+            .iter()
+            .find(|(file_name, _)| uri.ends_with(&format!("/{}", file_name)))
+            .map(|(_, parser)| parser)
+            .or_else(|| {
+                self.file_parsers
+                    .iter()
+                    .find(|(matches, _)| matches(uri))
+                    .map(|(_, parser)| parser)
+            })
+            .unwrap_or(&self.parser)
+    }
+
     /// Get the version matcher for this registry type
     pub fn matcher(&self) -> &Arc<dyn VersionMatcher> {
         &self.matcher
@@ -100,7 +167,7 @@ pub fn create_resolvers(config: &LspConfig) -> HashMap<RegistryType, PackageReso
     // separate config keys so a user could override them independently, but
     // sharing the instance when both URLs match avoids duplicate HTTP clients.
     // We accept the rare case where they differ by building two clients.
-    let npm_registry = npm_registry_from(&registries.npm);
+    let npm_registry = npm_registry_from(registries.npm.url.as_deref(), &registries.npm);
 
     // One GitHubRegistry instance serves both the version fetch (Registry) and
     // the commit-hash → SHA fetch (TagShaFetcher) so the configured URL
@@ -123,7 +190,9 @@ pub fn create_resolvers(config: &LspConfig) -> HashMap<RegistryType, PackageReso
             Arc::new(PackageJsonParser::new()),
             Arc::new(NpmVersionMatcher),
             Arc::new(npm_registry.clone()),
-        ),
+        )
+        .with_lock_parser("package-lock.json", Arc::new(PackageLockParser::new()))
+        .with_lock_parser("bun.lock", Arc::new(BunLockParser::new())),
     );
 
     resolvers.insert(
@@ -131,8 +200,9 @@ pub fn create_resolvers(config: &LspConfig) -> HashMap<RegistryType, PackageReso
         PackageResolver::new(
             Arc::new(CargoTomlParser::new()),
             Arc::new(CratesVersionMatcher),
-            Arc::new(crates_registry_from(&registries.crates)),
-        ),
+            crates_registry_from(&registries.crates, config.advisories.enabled),
+        )
+        .with_lock_parser("Cargo.lock", Arc::new(CargoLockParser::new())),
     );
 
     resolvers.insert(
@@ -141,7 +211,8 @@ pub fn create_resolvers(config: &LspConfig) -> HashMap<RegistryType, PackageReso
             Arc::new(GoModParser::new()),
             Arc::new(GoVersionMatcher),
             Arc::new(go_proxy_registry_from(&registries.go_proxy)),
-        ),
+        )
+        .with_file_parser(is_go_work_file, Arc::new(GoWorkParser::new())),
     );
 
     // pnpm catalog reuses the npm registry. If the user overrides the
@@ -149,7 +220,7 @@ pub fn create_resolvers(config: &LspConfig) -> HashMap<RegistryType, PackageReso
     let pnpm_registry = if registries.pnpm_catalog.url == registries.npm.url {
         npm_registry
     } else {
-        npm_registry_from(&registries.pnpm_catalog)
+        npm_registry_from(registries.pnpm_catalog.url.as_deref(), &registries.npm)
     };
 
     resolvers.insert(
@@ -176,7 +247,9 @@ pub fn create_resolvers(config: &LspConfig) -> HashMap<RegistryType, PackageReso
             Arc::new(PyprojectTomlParser::new()),
             Arc::new(PypiVersionMatcher),
             Arc::new(pypi_registry_from(&registries.pypi)),
-        ),
+        )
+        .with_lock_parser("poetry.lock", Arc::new(PoetryLockParser::new()))
+        .with_file_parser(is_requirements_file, Arc::new(RequirementsTxtParser::new())),
     );
 
     resolvers.insert(
@@ -190,6 +263,64 @@ pub fn create_resolvers(config: &LspConfig) -> HashMap<RegistryType, PackageReso
                 registries.docker.ghcr_registry_url.as_deref(),
                 registries.docker.ghcr_auth_url.as_deref(),
             )),
+        )
+        .with_file_parser(is_dockerfile, Arc::new(DockerfileParser::new())),
+    );
+
+    resolvers.insert(
+        RegistryType::Conda,
+        PackageResolver::new(
+            Arc::new(CondaEnvParser::new()),
+            Arc::new(CondaVersionMatcher),
+            Arc::new(conda_registry_from(&registries.conda)),
+        )
+        .with_lock_parser("conda-lock.yml", Arc::new(CondaLockParser::new())),
+    );
+
+    resolvers.insert(
+        RegistryType::NodeRelease,
+        PackageResolver::new(
+            Arc::new(NvmrcParser::new()),
+            Arc::new(NodeVersionMatcher),
+            Arc::new(node_registry_from(&registries.node)),
+        ),
+    );
+
+    // The go directive is parsed from go.mod and go.work by the Go Proxy
+    // resolver; this resolver only provides its matcher and registry
+    resolvers.insert(
+        RegistryType::GoToolchain,
+        PackageResolver::new(
+            Arc::new(GoModParser::new()),
+            Arc::new(GoToolchainMatcher),
+            Arc::new(go_toolchain_registry_from(&registries.go_toolchain)),
+        ),
+    );
+
+    resolvers.insert(
+        RegistryType::RustToolchain,
+        PackageResolver::new(
+            Arc::new(RustToolchainParser::new()),
+            Arc::new(RustToolchainMatcher),
+            Arc::new(rust_toolchain_registry_from(&registries.rust_toolchain)),
+        ),
+    );
+
+    resolvers.insert(
+        RegistryType::RubyGems,
+        PackageResolver::new(
+            Arc::new(GemfileParser::new()),
+            Arc::new(RubyGemsVersionMatcher),
+            Arc::new(rubygems_registry_from(&registries.rubygems)),
+        ),
+    );
+
+    resolvers.insert(
+        RegistryType::Pub,
+        PackageResolver::new(
+            Arc::new(PubspecYamlParser::new()),
+            Arc::new(PubVersionMatcher),
+            Arc::new(pub_registry_from(&registries.pub_dev)),
         ),
     );
 
@@ -209,22 +340,107 @@ fn pypi_registry_from(cfg: &RegistryConfig) -> PypiRegistry {
         .unwrap_or_default()
 }
 
-fn npm_registry_from(cfg: &RegistryConfig) -> NpmRegistry {
-    cfg.url.as_deref().map(NpmRegistry::new).unwrap_or_default()
+fn conda_registry_from(cfg: &RegistryConfig) -> CondaRegistry {
+    cfg.url
+        .as_deref()
+        .map(|u| CondaRegistry::new(u.to_string()))
+        .unwrap_or_default()
 }
 
-fn crates_registry_from(cfg: &RegistryConfig) -> CratesIoRegistry {
+fn node_registry_from(cfg: &RegistryConfig) -> NodeReleaseRegistry {
     cfg.url
         .as_deref()
-        .map(CratesIoRegistry::new)
+        .map(|u| NodeReleaseRegistry::new(u.to_string()))
+        .unwrap_or_default()
+}
+
+fn go_toolchain_registry_from(cfg: &RegistryConfig) -> GoToolchainRegistry {
+    cfg.url
+        .as_deref()
+        .map(|u| GoToolchainRegistry::new(u.to_string()))
+        .unwrap_or_default()
+}
+
+fn rust_toolchain_registry_from(cfg: &RegistryConfig) -> RustToolchainRegistry {
+    cfg.url
+        .as_deref()
+        .map(|u| RustToolchainRegistry::new(u.to_string()))
+        .unwrap_or_default()
+}
+
+fn rubygems_registry_from(cfg: &RegistryConfig) -> RubyGemsRegistry {
+    cfg.url
+        .as_deref()
+        .map(|u| RubyGemsRegistry::new(u.to_string()))
+        .unwrap_or_default()
+}
+
+fn pub_registry_from(cfg: &RegistryConfig) -> PubRegistry {
+    cfg.url
+        .as_deref()
+        .map(|u| PubRegistry::new(u.to_string()))
+        .unwrap_or_default()
+}
+
+/// Build an NpmRegistry for `url`, routing scoped packages to the scope
+/// registries of the npm config
+fn npm_registry_from(url: Option<&str>, npm: &NpmRegistryConfig) -> NpmRegistry {
+    let scope_registries = npm
+        .scope_registries
+        .iter()
+        .map(|(scope, cfg)| {
+            let registry = ScopeRegistry {
+                base_url: cfg.url.clone(),
+                auth_token: cfg.auth_token.clone(),
+            };
+            (scope.clone(), registry)
+        })
+        .collect();
+
+    url.map(NpmRegistry::new)
+        .unwrap_or_default()
+        .with_scope_registries(scope_registries)
+}
+
+/// Build the crates registry. With `fallbackUrls` configured, crates not
+/// found at the primary registry are looked up in each fallback in order.
+/// With `check_advisories`, the registry looks up the OSV advisories of the
+/// latest version on every refresh.
+fn crates_registry_from(cfg: &CratesRegistryConfig, check_advisories: bool) -> Arc<dyn Registry> {
+    let advisory_checker = check_advisories.then(|| Arc::new(OsvAdvisoryChecker::default()));
+    let primary = cfg
+        .url
+        .as_deref()
+        .map(CratesIoRegistry::with_default_api)
         .unwrap_or_default()
+        .with_auth_token(cfg.auth_token.clone())
+        .with_advisory_checker(advisory_checker.clone());
+    if cfg.fallback_urls.is_empty() {
+        return Arc::new(primary);
+    }
+
+    let fallbacks = cfg
+        .fallback_urls
+        .iter()
+        .map(|url| {
+            Arc::new(
+                CratesIoRegistry::with_default_api(url)
+                    .with_advisory_checker(advisory_checker.clone()),
+            ) as Arc<dyn Registry>
+        })
+        .collect();
+    Arc::new(MultiRegistry::new(Arc::new(primary), fallbacks))
 }
 
+/// Build a `GoProxyRegistry`. LSP config takes precedence over the first
+/// proxy listed in `GOPROXY`; private modules come from `GOPRIVATE` and
+/// `GONOSUMCHECK`.
 fn go_proxy_registry_from(cfg: &RegistryConfig) -> GoProxyRegistry {
     cfg.url
         .as_deref()
         .map(GoProxyRegistry::new)
         .unwrap_or_default()
+        .with_private_patterns_from_env()
 }
 
 fn jsr_registry_from(cfg: &RegistryConfig) -> JsrRegistry {
@@ -246,7 +462,8 @@ fn github_registry_from(cfg: &RegistryConfig) -> GitHubRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{DockerRegistryConfig, RegistriesConfig};
+    use crate::config::{DockerRegistryConfig, RegistriesConfig, ScopeRegistryConfig};
+    use crate::version::error::RegistryError;
 
     #[test]
     fn create_resolvers_with_default_config_includes_all_registry_types() {
@@ -261,6 +478,12 @@ mod tests {
             RegistryType::Jsr,
             RegistryType::PyPI,
             RegistryType::Docker,
+            RegistryType::Conda,
+            RegistryType::NodeRelease,
+            RegistryType::GoToolchain,
+            RegistryType::RustToolchain,
+            RegistryType::RubyGems,
+            RegistryType::Pub,
         ] {
             assert!(
                 resolvers.contains_key(&registry_type),
@@ -270,6 +493,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parser_for_selects_lock_parser_by_file_name() {
+        let resolvers = create_resolvers(&LspConfig::default());
+        let resolver = resolvers
+            .get(&RegistryType::PyPI)
+            .expect("PyPI resolver missing");
+        let content = r#"[[package]]
+name = "six"
+version = "1.16.0"
+"#;
+
+        let lock_packages = resolver
+            .parser_for("file:///project/poetry.lock")
+            .parse(content)
+            .unwrap();
+        let manifest_packages = resolver
+            .parser_for("file:///project/pyproject.toml")
+            .parse(content)
+            .unwrap();
+
+        assert_eq!(lock_packages.len(), 1);
+        assert!(manifest_packages.is_empty());
+    }
+
+    #[test]
+    fn parser_for_selects_file_parser_by_predicate() {
+        let resolvers = create_resolvers(&LspConfig::default());
+        let resolver = resolvers
+            .get(&RegistryType::PyPI)
+            .expect("PyPI resolver missing");
+        let content = "requests>=2.28\n";
+
+        let requirements_packages = resolver
+            .parser_for("file:///project/requirements-dev.txt")
+            .parse(content)
+            .unwrap();
+        let manifest_packages = resolver
+            .parser_for("file:///project/pyproject.toml")
+            .parse(content)
+            .unwrap();
+
+        assert_eq!(requirements_packages.len(), 1);
+        assert!(manifest_packages.is_empty());
+    }
+
     #[tokio::test]
     async fn create_resolvers_routes_pypi_fetches_to_overridden_url() {
         let mut server = mockito::Server::new_async().await;
@@ -296,11 +564,46 @@ mod tests {
         assert_eq!(result.versions, vec!["1.0.0"]);
     }
 
+    #[tokio::test]
+    async fn create_resolvers_looks_up_crates_in_fallback_registries_when_not_found() {
+        // Path matching is left to the registry tests, as it depends on the
+        // `sparse-index` feature
+        let mut primary = mockito::Server::new_async().await;
+        let primary_mock = primary
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(404)
+            .create_async()
+            .await;
+        let mut fallback = mockito::Server::new_async().await;
+        let fallback_mock = fallback
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let mut config = LspConfig::default();
+        config.registries.crates.url = Some(primary.url());
+        config.registries.crates.fallback_urls = vec![fallback.url()];
+
+        let resolvers = create_resolvers(&config);
+        let registry = resolvers
+            .get(&RegistryType::CratesIo)
+            .expect("CratesIo resolver missing")
+            .registry();
+
+        let result = registry.fetch_all_versions("serde").await;
+
+        primary_mock.assert_async().await;
+        fallback_mock.assert_async().await;
+        assert!(matches!(result, Err(RegistryError::NotFound(_))));
+    }
+
     #[tokio::test]
     async fn create_resolvers_routes_github_sha_fetches_to_overridden_url() {
         let mut server = mockito::Server::new_async().await;
         let mock = server
             .mock("GET", "/repos/actions/checkout/tags")
+            .match_query(mockito::Matcher::UrlEncoded("per_page".to_string(), "100".to_string()))
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(r#"[{"name":"v4.1.7","commit":{"sha":"newsha4170000000000000000000000000000000"}}]"#)
@@ -326,6 +629,35 @@ mod tests {
         assert_eq!(sha, "newsha4170000000000000000000000000000000");
     }
 
+    #[tokio::test]
+    async fn create_resolvers_routes_github_fetches_to_enterprise_api_url() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v3/repos/actions/checkout/releases")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"[{"tag_name":"v4.1.7","published_at":"2024-06-01T00:00:00Z"}]"#)
+            .create_async()
+            .await;
+
+        let mut config = LspConfig::default();
+        config.registries.github.url = Some(format!("{}/api/v3/", server.url()));
+
+        let resolvers = create_resolvers(&config);
+        let registry = resolvers
+            .get(&RegistryType::GitHubActions)
+            .expect("GitHubActions resolver missing")
+            .registry();
+        let result = registry
+            .fetch_all_versions("actions/checkout")
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(result.versions, vec!["v4.1.7"]);
+    }
+
     #[tokio::test]
     async fn create_resolvers_routes_npm_fetches_to_overridden_url() {
         let mut server = mockito::Server::new_async().await;
@@ -374,9 +706,9 @@ mod tests {
 
         let config = LspConfig {
             registries: RegistriesConfig {
-                npm: RegistryConfig {
-                    enabled: true,
+                npm: NpmRegistryConfig {
                     url: Some(npm_server.url()),
+                    ..NpmRegistryConfig::default()
                 },
                 pnpm_catalog: RegistryConfig {
                     enabled: true,
@@ -404,6 +736,42 @@ mod tests {
         assert_eq!(pnpm_result.versions, vec!["5.0.0"]);
     }
 
+    #[tokio::test]
+    async fn create_resolvers_routes_scoped_npm_and_pnpm_packages_to_scope_registry() {
+        let mut scope_server = mockito::Server::new_async().await;
+        let mock = scope_server
+            .mock("GET", "/@mycompany%2Fcore")
+            .match_header("authorization", "Bearer secret-token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"versions":{"1.0.0":{}},"dist-tags":{"latest":"1.0.0"},"time":{}}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let mut config = LspConfig::default();
+        config.registries.pnpm_catalog.url = Some("https://pnpm.example.com".to_string());
+        config.registries.npm.scope_registries = HashMap::from([(
+            "@mycompany".to_string(),
+            ScopeRegistryConfig {
+                url: scope_server.url(),
+                auth_token: Some("secret-token".to_string()),
+            },
+        )]);
+
+        let resolvers = create_resolvers(&config);
+
+        for registry_type in [RegistryType::Npm, RegistryType::PnpmCatalog] {
+            let registry = resolvers.get(&registry_type).unwrap().registry();
+            let result = registry
+                .fetch_all_versions("@mycompany/core")
+                .await
+                .unwrap();
+            assert_eq!(result.versions, vec!["1.0.0"]);
+        }
+        mock.assert_async().await;
+    }
+
     #[test]
     fn docker_with_overrides_applies_partial_overrides_from_config() {
         // We can't easily HTTP-test Docker here (it makes auth + tag calls in
@@ -1,20 +1,56 @@
 use crate::log::init;
+use tokio::net::TcpListener;
 use tower_lsp::{LspService, Server};
 use tracing::info;
 
 use crate::lsp::backend::Backend;
 
-pub async fn run_server() -> anyhow::Result<()> {
+/// Transport the LSP messages are exchanged over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// stdin/stdout, for editors that spawn the server
+    Stdio,
+    /// TCP on `127.0.0.1:port`, for attaching an editor to a server started
+    /// separately (e.g. under a debugger)
+    Tcp { port: u16 },
+}
+
+pub async fn run_server(transport: Transport) -> anyhow::Result<()> {
     init()?;
 
     info!("Starting version-lsp server");
 
-    let stdin = tokio::io::stdin();
-    let stdout = tokio::io::stdout();
+    match transport {
+        Transport::Stdio => {
+            let stdin = tokio::io::stdin();
+            let stdout = tokio::io::stdout();
 
-    let (service, socket) = LspService::new(Backend::new);
-    Server::new(stdin, stdout, socket).serve(service).await;
+            let (service, socket) = LspService::new(Backend::new);
+            Server::new(stdin, stdout, socket).serve(service).await;
+        }
+        Transport::Tcp { port } => serve_tcp(port).await?,
+    }
 
     info!("version-lsp server stopped");
     Ok(())
 }
+
+/// Serve clients connecting to `127.0.0.1:port`, one at a time
+///
+/// Each connection gets a fresh backend, so an editor can disconnect and
+/// reconnect without the server being restarted.
+async fn serve_tcp(port: u16) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    info!("Listening on {}", listener.local_addr()?);
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        info!("Client connected from {}", peer);
+
+        let (read, write) = tokio::io::split(stream);
+        let (service, socket) = LspService::new(Backend::new);
+        Server::new(read, write, socket).serve(service).await;
+
+        info!("Client {} disconnected", peer);
+    }
+}
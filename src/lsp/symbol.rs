@@ -0,0 +1,81 @@
+//! Workspace symbols for dependencies, to find packages across documents
+
+use tower_lsp::lsp_types::{Location, SymbolInformation, SymbolKind, Url};
+
+use crate::lsp::code_action::version_range;
+use crate::parser::types::PackageInfo;
+
+/// Symbols for the packages of a document whose name contains `query`
+///
+/// Matching is case-insensitive, and an empty query matches every package.
+/// Each symbol is named `<name> <version>` and located at the version.
+pub fn package_symbols(uri: &Url, packages: &[PackageInfo], query: &str) -> Vec<SymbolInformation> {
+    let query = query.to_lowercase();
+    packages
+        .iter()
+        .filter(|package| package.name.to_lowercase().contains(&query))
+        .map(|package| {
+            #[allow(deprecated)]
+            SymbolInformation {
+                name: format!("{} {}", package.name, package.version),
+                kind: SymbolKind::PACKAGE,
+                tags: None,
+                deprecated: None,
+                location: Location::new(uri.clone(), version_range(package)),
+                container_name: None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::PackageJsonParser;
+    use crate::parser::traits::Parser;
+    use tower_lsp::lsp_types::{Position, Range};
+
+    fn symbols(query: &str) -> Vec<(String, Range)> {
+        let content = r#"{
+  "dependencies": {
+    "lodash": "^4.17.21",
+    "lodash.merge": "4.6.2",
+    "react": "18.2.0"
+  }
+}"#;
+        let packages = PackageJsonParser::new().parse(content).unwrap();
+        let uri = Url::parse("file:///project/package.json").unwrap();
+
+        package_symbols(&uri, &packages, query)
+            .into_iter()
+            .map(|symbol| {
+                assert_eq!(symbol.kind, SymbolKind::PACKAGE);
+                assert_eq!(symbol.location.uri, uri);
+                (symbol.name, symbol.location.range)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn package_symbols_matches_name_case_insensitively() {
+        assert_eq!(
+            symbols("LoDash"),
+            vec![
+                (
+                    "lodash ^4.17.21".to_string(),
+                    Range::new(Position::new(2, 15), Position::new(2, 23))
+                ),
+                (
+                    "lodash.merge 4.6.2".to_string(),
+                    Range::new(Position::new(3, 21), Position::new(3, 26))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn package_symbols_with_empty_query_returns_all_packages() {
+        assert_eq!(symbols("").len(), 3);
+        assert!(symbols("vue").is_empty());
+    }
+}
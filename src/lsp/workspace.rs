@@ -0,0 +1,209 @@
+//! Workspace manifest discovery
+//!
+//! Manifests in the workspace, and the members of a pnpm monorepo, are
+//! pre-parsed on startup so their dependencies are already cached when a
+//! manifest is first opened.
+
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+
+use glob::Pattern;
+use tracing::{debug, warn};
+
+use crate::parser::pnpm_workspace::PnpmWorkspaceParser;
+use crate::parser::types::{detect_parser_type, is_lock_file};
+
+/// Directories never searched for manifests: dependency and build outputs
+const IGNORED_DIRS: [&str; 3] = ["node_modules", "target", "vendor"];
+
+/// Find the manifests of every supported file type under `root`
+///
+/// Searches `max_depth` directory levels below the root. Lock files are
+/// skipped, as are hidden directories other than `.github` and dependency
+/// or build output directories. Returns paths sorted.
+pub fn find_manifests(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut manifests = BTreeSet::new();
+    collect_manifests(root, max_depth, &mut manifests);
+    manifests.into_iter().collect()
+}
+
+fn collect_manifests(dir: &Path, remaining_depth: usize, manifests: &mut BTreeSet<PathBuf>) {
+    let Ok(entries) =
+        std::fs::read_dir(dir).inspect_err(|e| debug!("Failed to read {:?}: {}", dir, e))
+    else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let hidden = name.starts_with('.') && name != ".github";
+            if remaining_depth > 0 && !hidden && !IGNORED_DIRS.contains(&name.as_ref()) {
+                collect_manifests(&path, remaining_depth - 1, manifests);
+            }
+        } else if file_type.is_file() {
+            let path_str = path.to_string_lossy();
+            if detect_parser_type(&path_str).is_some() && !is_lock_file(&path_str) {
+                manifests.insert(path);
+            }
+        }
+    }
+}
+
+/// Find the package.json of every member of the pnpm workspace at `root`
+///
+/// Members are listed by the `packages:` globs of `pnpm-workspace.yaml`.
+/// Returns an empty list when the root has no pnpm-workspace.yaml.
+pub fn pnpm_workspace_manifests(root: &Path) -> Vec<PathBuf> {
+    let Ok(content) = std::fs::read_to_string(root.join("pnpm-workspace.yaml")) else {
+        return Vec::new();
+    };
+
+    let globs = PnpmWorkspaceParser.workspace_globs(&content);
+    debug!("pnpm workspace globs: {:?}", globs);
+    discover_manifests(root, &globs)
+}
+
+/// Resolve member globs to package.json paths, sorted and deduplicated
+///
+/// Globs prefixed with `!` exclude members. `node_modules` is never searched.
+fn discover_manifests(root: &Path, globs: &[String]) -> Vec<PathBuf> {
+    let (excludes, includes): (Vec<&String>, Vec<&String>) =
+        globs.iter().partition(|glob| glob.starts_with('!'));
+    let excludes: Vec<Pattern> = excludes
+        .iter()
+        .filter_map(|glob| Pattern::new(normalize_glob(&glob[1..])).ok())
+        .collect();
+
+    let Some(root_str) = root.to_str() else {
+        warn!("Workspace root is not valid UTF-8: {:?}", root);
+        return Vec::new();
+    };
+    let escaped_root = Pattern::escape(root_str);
+
+    let mut manifests = BTreeSet::new();
+    for glob in includes {
+        let pattern = format!("{}/{}/package.json", escaped_root, normalize_glob(glob));
+        let Ok(paths) =
+            glob::glob(&pattern).inspect_err(|e| warn!("Invalid workspace glob '{}': {}", glob, e))
+        else {
+            continue;
+        };
+
+        for path in paths.flatten() {
+            let Ok(relative) = path.strip_prefix(root) else {
+                continue;
+            };
+            let member_dir = relative.parent().unwrap_or(Path::new(""));
+            let in_node_modules = member_dir
+                .components()
+                .any(|c| c == Component::Normal("node_modules".as_ref()));
+            if in_node_modules || excludes.iter().any(|p| p.matches_path(member_dir)) {
+                continue;
+            }
+            manifests.insert(path);
+        }
+    }
+
+    manifests.into_iter().collect()
+}
+
+/// Strip a leading `./` and trailing `/` so globs can be joined to paths
+fn normalize_glob(glob: &str) -> &str {
+    glob.trim_start_matches("./").trim_end_matches('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_manifest(root: &Path, dir: &str) {
+        let dir = root.join(dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("package.json"), "{}").unwrap();
+    }
+
+    #[test]
+    fn find_manifests_finds_supported_files_up_to_max_depth() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let write = |path: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        };
+        write("package.json");
+        write("package-lock.json");
+        write("README.md");
+        write(".github/workflows/ci.yml");
+        write("crates/core/Cargo.toml");
+        write("a/b/c/go.mod");
+        write("a/b/c/d/go.mod");
+        write("node_modules/lodash/package.json");
+        write("target/debug/Cargo.toml");
+        write(".cache/package.json");
+
+        let manifests = find_manifests(root, 3);
+
+        let relative: Vec<PathBuf> = manifests
+            .iter()
+            .map(|path| path.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            relative,
+            vec![
+                PathBuf::from(".github/workflows/ci.yml"),
+                PathBuf::from("a/b/c/go.mod"),
+                PathBuf::from("crates/core/Cargo.toml"),
+                PathBuf::from("package.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn pnpm_workspace_manifests_resolves_member_globs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("pnpm-workspace.yaml"),
+            "packages:\n  - shared\n  - 'web/**'\n  - '!web/fixtures/**'\n",
+        )
+        .unwrap();
+        write_manifest(root, "shared");
+        write_manifest(root, "web/app");
+        write_manifest(root, "web/libs/ui");
+        write_manifest(root, "web/fixtures/sample");
+        write_manifest(root, "web/app/node_modules/lodash");
+        write_manifest(root, "other");
+
+        let manifests = pnpm_workspace_manifests(root);
+
+        let relative: Vec<PathBuf> = manifests
+            .iter()
+            .map(|path| path.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            relative,
+            vec![
+                PathBuf::from("shared/package.json"),
+                PathBuf::from("web/app/package.json"),
+                PathBuf::from("web/libs/ui/package.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn pnpm_workspace_manifests_returns_empty_without_workspace_file() {
+        let temp_dir = TempDir::new().unwrap();
+        write_manifest(temp_dir.path(), "shared");
+
+        assert!(pnpm_workspace_manifests(temp_dir.path()).is_empty());
+    }
+}
@@ -1,5 +1,11 @@
+use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
 
+use version_lsp::config::{DEFAULT_REFRESH_INTERVAL_MS, db_path};
+use version_lsp::lsp::hover::format_fetch_time;
+use version_lsp::lsp::server::{Transport, run_server};
+use version_lsp::version::cache::Cache;
+
 #[derive(Parser)]
 #[command(name = "version-lsp")]
 #[command(version, about = "Language Server for package version management")]
@@ -10,17 +16,111 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Command {
-    // Future subcommands will be added here
-    // e.g., Cache { #[command(subcommand)] action: CacheAction }
+    /// Serve LSP over TCP instead of stdio, e.g. to attach an editor to a
+    /// server running under a debugger
+    Serve {
+        /// Port to listen on at 127.0.0.1
+        #[arg(long)]
+        port: u16,
+    },
+    /// Inspect the version cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Show package counts, version count, database size and update times
+    Stats,
+    /// List cached packages with the time their versions were last fetched
+    List,
 }
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        None => tokio::runtime::Builder::new_multi_thread()
-            .enable_all()
-            .build()?
-            .block_on(version_lsp::lsp::server::run_server()),
+        None => serve(Transport::Stdio),
+        Some(Command::Serve { port }) => serve(Transport::Tcp { port }),
+        Some(Command::Cache {
+            action: CacheAction::Stats,
+        }) => print_cache_stats(),
+        Some(Command::Cache {
+            action: CacheAction::List,
+        }) => print_cache_list(),
+    }
+}
+
+fn serve(transport: Transport) -> anyhow::Result<()> {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?
+        .block_on(run_server(transport))
+}
+
+fn print_cache_stats() -> anyhow::Result<()> {
+    let db_path = db_path();
+    if !db_path.exists() {
+        println!("No cache database at {}", db_path.display());
+        return Ok(());
     }
+
+    let cache = Cache::new(&db_path, DEFAULT_REFRESH_INTERVAL_MS, false)?;
+    let stats = cache.stats()?;
+    let db_size = std::fs::metadata(&db_path)?.len();
+
+    println!("Database: {} ({} bytes)", db_path.display(), db_size);
+
+    let mut packages: Vec<_> = stats.packages.iter().collect();
+    packages.sort();
+    println!(
+        "Packages: {}",
+        packages.iter().map(|(_, n)| *n).sum::<usize>()
+    );
+    for (registry_type, count) in packages {
+        println!("  {registry_type}: {count}");
+    }
+
+    println!("Versions: {}", stats.total_versions);
+    println!(
+        "Oldest update: {}",
+        format_timestamp(stats.oldest_updated_at)
+    );
+    println!(
+        "Newest update: {}",
+        format_timestamp(stats.newest_updated_at)
+    );
+
+    Ok(())
+}
+
+fn print_cache_list() -> anyhow::Result<()> {
+    let db_path = db_path();
+    if !db_path.exists() {
+        println!("No cache database at {}", db_path.display());
+        return Ok(());
+    }
+
+    let cache = Cache::new(&db_path, DEFAULT_REFRESH_INTERVAL_MS, false)?;
+    let now = Utc::now();
+    for package in cache.list_packages()? {
+        println!(
+            "{} {}: {}",
+            package.registry_type,
+            package.package_name,
+            format_fetch_time(package.updated_at, now).unwrap_or_else(|| "-".to_string())
+        );
+    }
+
+    Ok(())
+}
+
+/// Format a millisecond UNIX timestamp as RFC 3339, or "-" when absent
+fn format_timestamp(timestamp_ms: Option<i64>) -> String {
+    timestamp_ms
+        .and_then(DateTime::from_timestamp_millis)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| "-".to_string())
 }
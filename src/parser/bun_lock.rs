@@ -0,0 +1,298 @@
+//! bun.lock parser for locked npm dependencies
+//!
+//! `bun.lock` is Bun's text lock file (JSON with trailing commas). Resolved
+//! packages live in a `packages` object keyed by name, with the resolution as
+//! the first element of an array:
+//!
+//! ```json
+//! "packages": {
+//!   "lodash": ["lodash@4.17.20", "", {}, "sha512-..."],
+//!   "express/debug": ["debug@2.6.9", "", {}, "sha512-..."],
+//! }
+//! ```
+//!
+//! Only direct dependencies, i.e. names listed in a workspace's
+//! `dependencies`/`devDependencies`/`optionalDependencies`/`peerDependencies`,
+//! are extracted; nested (transitive) keys like `express/debug` are skipped.
+//! The binary `bun.lockb` format cannot be read.
+
+use std::collections::HashSet;
+
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
+use tracing::warn;
+
+const DEPENDENCY_SECTIONS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "optionalDependencies",
+    "peerDependencies",
+];
+
+/// Parser for bun.lock files
+pub struct BunLockParser;
+
+impl BunLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for BunLockParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for BunLockParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        let mut parser = tree_sitter::Parser::new();
+        let language = tree_sitter_json::LANGUAGE;
+        parser.set_language(&language.into()).map_err(|e| {
+            warn!("Failed to set JSON language for tree-sitter: {}", e);
+            ParseError::TreeSitter(e.to_string())
+        })?;
+
+        let tree = parser.parse(content, None).ok_or_else(|| {
+            warn!("Failed to parse JSON content");
+            ParseError::ParseFailed("Failed to parse JSON".to_string())
+        })?;
+
+        let root = tree.root_node();
+        let mut results = Vec::new();
+
+        if let Some(document) = root.child(0)
+            && document.kind() == "object"
+            && let Some(packages) = find_object_value(document, content, "packages")
+        {
+            let direct = find_object_value(document, content, "workspaces")
+                .map(|workspaces| direct_dependency_names(workspaces, content))
+                .unwrap_or_default();
+            self.extract_packages(packages, content, &direct, &mut results);
+        }
+
+        Ok(results)
+    }
+}
+
+impl BunLockParser {
+    /// Extract direct dependencies from the `packages` object
+    fn extract_packages(
+        &self,
+        packages_node: tree_sitter::Node,
+        content: &str,
+        direct: &HashSet<String>,
+        results: &mut Vec<PackageInfo>,
+    ) {
+        let mut cursor = packages_node.walk();
+
+        for pair in packages_node.children(&mut cursor) {
+            if pair.kind() != "pair" {
+                continue;
+            }
+
+            let (Some(key_node), Some(value_node)) = (
+                pair.child_by_field_name("key"),
+                pair.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+
+            if !direct.contains(&string_value(key_node, content)) {
+                continue;
+            }
+
+            let Some(resolution_node) = first_array_string(value_node) else {
+                continue;
+            };
+
+            // "name@version"; the name may be scoped (`@types/node@20.0.0`)
+            // and differ from the key for aliases
+            let resolution = string_value(resolution_node, content);
+            let Some(at) = resolution.rfind('@').filter(|&at| at > 0) else {
+                continue;
+            };
+            let (name, version) = (&resolution[..at], &resolution[at + 1..]);
+
+            // Skip git, file and workspace resolutions (`github:...`, `workspace:...`)
+            if version.is_empty() || version.contains(':') {
+                continue;
+            }
+
+            let start_offset = resolution_node.start_byte() + 1 + at + 1;
+            let start_point = resolution_node.start_position();
+
+            results.push(PackageInfo {
+                name: name.to_string(),
+                version: version.to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::Npm,
+                registry_url: Some(registry_type_to_url(RegistryType::Npm, name)),
+                start_offset,
+                end_offset: start_offset + version.len(),
+                line: start_point.row,
+                column: start_point.column + 1 + at + 1,
+                section_name: None,
+                section_line: None,
+                extra_info: None,
+            });
+        }
+    }
+}
+
+/// Collect dependency names declared by any workspace
+fn direct_dependency_names(workspaces: tree_sitter::Node, content: &str) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut cursor = workspaces.walk();
+
+    for workspace in workspaces
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "pair")
+        .filter_map(|pair| pair.child_by_field_name("value"))
+        .filter(|value| value.kind() == "object")
+    {
+        for section in DEPENDENCY_SECTIONS {
+            let Some(dependencies) = find_object_value(workspace, content, section) else {
+                continue;
+            };
+            let mut dep_cursor = dependencies.walk();
+            names.extend(
+                dependencies
+                    .children(&mut dep_cursor)
+                    .filter(|child| child.kind() == "pair")
+                    .filter_map(|pair| pair.child_by_field_name("key"))
+                    .map(|key| string_value(key, content)),
+            );
+        }
+    }
+
+    names
+}
+
+/// Get the first element of a JSON array, if it is a string
+fn first_array_string(node: tree_sitter::Node) -> Option<tree_sitter::Node> {
+    if node.kind() != "array" {
+        return None;
+    }
+    node.named_child(0).filter(|child| child.kind() == "string")
+}
+
+/// Find the value node of `key` in a JSON object, if it is an object
+fn find_object_value<'a>(
+    object_node: tree_sitter::Node<'a>,
+    content: &str,
+    key: &str,
+) -> Option<tree_sitter::Node<'a>> {
+    let mut cursor = object_node.walk();
+    object_node
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "pair")
+        .find(|pair| {
+            pair.child_by_field_name("key")
+                .is_some_and(|key_node| string_value(key_node, content) == key)
+        })
+        .and_then(|pair| pair.child_by_field_name("value"))
+        .filter(|node| node.kind() == "object")
+}
+
+/// Get the string value from a string node (removes quotes)
+fn string_value(node: tree_sitter::Node, content: &str) -> String {
+    content[node.byte_range()]
+        .trim()
+        .trim_start_matches('"')
+        .trim_end_matches('"')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_extracts_direct_dependencies() {
+        let parser = BunLockParser::new();
+        let content = r#"{
+  "lockfileVersion": 1,
+  "workspaces": {
+    "": {
+      "name": "my-app",
+      "dependencies": {
+        "lodash": "^4.17.0",
+      },
+    },
+  },
+  "packages": {
+    "lodash": ["lodash@4.17.20", "", {}, "sha512-abc"],
+  }
+}"#;
+        let result = parser.parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![PackageInfo {
+                name: "lodash".to_string(),
+                version: "4.17.20".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::Npm,
+                registry_url: Some("https://www.npmjs.com/package/lodash".to_string()),
+                start_offset: 191,
+                end_offset: 198,
+                line: 11,
+                column: 23,
+                section_name: None,
+                section_line: None,
+                extra_info: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_skips_transitive_and_non_registry_packages() {
+        let parser = BunLockParser::new();
+        let content = r#"{
+  "lockfileVersion": 1,
+  "workspaces": {
+    "": {
+      "dependencies": {
+        "@types/node": "^20.0.0",
+        "express": "^4.0.0",
+        "my-lib": "github:owner/my-lib",
+      },
+      "devDependencies": {
+        "typescript": "^5.0.0",
+      },
+    },
+  },
+  "packages": {
+    "@types/node": ["@types/node@20.11.0", "", {}, "sha512-a"],
+    "debug": ["debug@4.3.4", "", {}, "sha512-b"],
+    "express": ["express@4.18.2", "", {}, "sha512-c"],
+    "express/debug": ["debug@2.6.9", "", {}, "sha512-d"],
+    "my-lib": ["my-lib@github:owner/my-lib#abc123", {}, "abc123"],
+    "typescript": ["typescript@5.3.3", "", {}, "sha512-e"],
+  }
+}"#;
+        let result = parser.parse(content).unwrap();
+
+        let packages: Vec<(&str, &str)> = result
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        assert_eq!(
+            packages,
+            vec![
+                ("@types/node", "20.11.0"),
+                ("express", "4.18.2"),
+                ("typescript", "5.3.3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_returns_empty_without_packages() {
+        let parser = BunLockParser::new();
+        let result = parser.parse(r#"{ "lockfileVersion": 1 }"#).unwrap();
+
+        assert!(result.is_empty());
+    }
+}
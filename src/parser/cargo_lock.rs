@@ -0,0 +1,249 @@
+//! Cargo.lock parser for locked crate versions
+//!
+//! Extracts every `[[package]]` entry whose `source` is a registry
+//! (`registry+...` or `sparse+...`). Workspace members and path dependencies
+//! have no `source`, and git dependencies have a `git+...` source; both are
+//! skipped since they cannot be checked against crates.io.
+
+use tracing::warn;
+
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
+
+/// Source prefixes of packages downloaded from a registry
+const REGISTRY_SOURCE_PREFIXES: [&str; 2] = ["registry+", "sparse+"];
+
+/// Parser for Cargo.lock files
+pub struct CargoLockParser;
+
+impl CargoLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CargoLockParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for CargoLockParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        let mut parser = tree_sitter::Parser::new();
+        let language = tree_sitter_toml_ng::LANGUAGE;
+        parser.set_language(&language.into()).map_err(|e| {
+            warn!("Failed to set TOML language for tree-sitter: {}", e);
+            ParseError::TreeSitter(e.to_string())
+        })?;
+
+        let tree = parser.parse(content, None).ok_or_else(|| {
+            warn!("Failed to parse TOML content");
+            ParseError::ParseFailed("Failed to parse TOML".to_string())
+        })?;
+
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let results = root
+            .children(&mut cursor)
+            .filter(|child| child.kind() == "table_array_element")
+            .filter(|child| table_name(*child, content).as_deref() == Some("package"))
+            .filter_map(|child| self.extract_package(child, content))
+            .collect();
+
+        Ok(results)
+    }
+}
+
+impl CargoLockParser {
+    /// Extract name and version from a `[[package]]` entry from a registry
+    ///
+    /// The version is reported as the exact requirement `=x.y.z`: a bare
+    /// Cargo version is a caret requirement, which any newer compatible
+    /// release would satisfy, hiding the drift the lock file is checked for.
+    fn extract_package(&self, table_node: tree_sitter::Node, content: &str) -> Option<PackageInfo> {
+        let mut name: Option<String> = None;
+        let mut version: Option<tree_sitter::Node> = None;
+        let mut from_registry = false;
+
+        let mut cursor = table_node.walk();
+        for pair in table_node.children(&mut cursor) {
+            if pair.kind() != "pair" {
+                continue;
+            }
+            let (Some(key), Some(value)) = (pair.child(0), pair.child(2)) else {
+                continue;
+            };
+            match (&content[key.byte_range()], value.kind()) {
+                ("name", "string") => name = Some(unquote(&content[value.byte_range()])),
+                ("version", "string") => version = Some(value),
+                ("source", "string") => {
+                    let source = unquote(&content[value.byte_range()]);
+                    from_registry = REGISTRY_SOURCE_PREFIXES
+                        .iter()
+                        .any(|prefix| source.starts_with(prefix));
+                }
+                _ => {}
+            }
+        }
+
+        if !from_registry {
+            return None;
+        }
+        let (name, version_node) = (name?, version?);
+        let start_point = version_node.start_position();
+
+        let registry_url = Some(registry_type_to_url(RegistryType::CratesIo, &name));
+        Some(PackageInfo {
+            name,
+            version: format!("={}", unquote(&content[version_node.byte_range()])),
+            commit_hash: None,
+            registry_type: RegistryType::CratesIo,
+            registry_url,
+            start_offset: version_node.start_byte() + 1,
+            end_offset: version_node.end_byte() - 1,
+            line: start_point.row,
+            column: start_point.column + 1,
+            section_name: None,
+            section_line: None,
+            extra_info: None,
+        })
+    }
+}
+
+/// Get the header name of a table array element (e.g., "package")
+fn table_name(table_node: tree_sitter::Node, content: &str) -> Option<String> {
+    let mut cursor = table_node.walk();
+    table_node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "bare_key" || child.kind() == "dotted_key")
+        .map(|child| content[child.byte_range()].to_string())
+}
+
+/// Strip the surrounding quotes from a TOML string literal
+fn unquote(text: &str) -> String {
+    text.trim()
+        .trim_start_matches(['"', '\''])
+        .trim_end_matches(['"', '\''])
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_extracts_locked_registry_packages() {
+        let parser = CargoLockParser::new();
+        let content = r#"# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 4
+
+[[package]]
+name = "itoa"
+version = "1.0.11"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "49f1f14873335454500d59611f1cf4a4b0f786f9ac11f4312a78e4cf2566695b"
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+source = "sparse+https://index.crates.io/"
+checksum = "ddc6f9cc94d67c0e21aaf7eda3a010fd3af78ebf6e096aa6e2e13c79749cce4f"
+dependencies = [
+ "itoa",
+]
+"#;
+        let result = parser.parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                PackageInfo {
+                    name: "itoa".to_string(),
+                    version: "=1.0.11".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::CratesIo,
+                    registry_url: Some("https://crates.io/crates/itoa".to_string()),
+                    start_offset: 141,
+                    end_offset: 147,
+                    line: 6,
+                    column: 11,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+                PackageInfo {
+                    name: "serde".to_string(),
+                    version: "=1.0.200".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::CratesIo,
+                    registry_url: Some("https://crates.io/crates/serde".to_string()),
+                    start_offset: 331,
+                    end_offset: 338,
+                    line: 12,
+                    column: 11,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_skips_workspace_members_and_git_packages() {
+        let parser = CargoLockParser::new();
+        let content = r#"version = 4
+
+[[package]]
+name = "my-app"
+version = "0.1.0"
+dependencies = [
+ "my-lib",
+ "serde",
+]
+
+[[package]]
+name = "my-lib"
+version = "0.1.0"
+source = "git+https://github.com/example/my-lib#4a2f1c0"
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+        let result = parser.parse(content).unwrap();
+
+        let names: Vec<&str> = result.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["serde"]);
+    }
+
+    #[test]
+    fn parse_keeps_every_locked_version_of_a_crate() {
+        let parser = CargoLockParser::new();
+        let content = r#"[[package]]
+name = "syn"
+version = "1.0.109"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "syn"
+version = "2.0.60"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+        let result = parser.parse(content).unwrap();
+
+        let versions: Vec<&str> = result.iter().map(|p| p.version.as_str()).collect();
+        assert_eq!(versions, vec!["=1.0.109", "=2.0.60"]);
+    }
+
+    #[test]
+    fn parse_returns_empty_for_no_packages() {
+        let parser = CargoLockParser::new();
+        let result = parser.parse("version = 4\n").unwrap();
+
+        assert!(result.is_empty());
+    }
+}
@@ -1,15 +1,29 @@
 //! Cargo.toml parser
 
 use crate::parser::traits::{ParseError, Parser};
-use crate::parser::types::{PackageInfo, RegistryType};
+use crate::parser::types::{ExtraInfo, PackageInfo, RegistryType, registry_type_to_url};
+use regex::Regex;
 use tracing::warn;
 
+/// A version string and its location: (version, start offset, end offset, line, column)
+type VersionSpan = (String, usize, usize, usize, usize);
+
 /// Parser for Cargo.toml files
-pub struct CargoTomlParser;
+pub struct CargoTomlParser {
+    /// Regex for platform-specific dependency tables:
+    /// `target.'cfg(unix)'.dependencies`, `target.x86_64-pc-windows-msvc.dev-dependencies`
+    target_table_re: Regex,
+}
 
 impl CargoTomlParser {
     pub fn new() -> Self {
-        Self
+        Self {
+            // Match: target.<quoted cfg or triple, or bare triple>.<dependency table>
+            target_table_re: Regex::new(
+                r#"^target\.(?:'[^']+'|"(?:[^"\\]|\\.)+"|[A-Za-z0-9_-]+)\.(?:dev-|build-)?dependencies$"#,
+            )
+            .unwrap(),
+        }
     }
 }
 
@@ -51,6 +65,24 @@ impl CargoTomlParser {
         "workspace.dependencies",
     ];
 
+    /// Tables that never declare dependencies, even when their entries look
+    /// like dependency specs (`[patch.crates-io]`, `[replace]`)
+    const SKIP_TABLE_PREFIXES: [&'static str; 3] = ["profile", "patch", "replace"];
+
+    /// Whether `name` is one of the skipped tables or a sub-table of one
+    fn is_skipped_table(name: &str) -> bool {
+        Self::SKIP_TABLE_PREFIXES.iter().any(|prefix| {
+            name.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    }
+
+    /// Whether `name` is a dependency table, including platform-specific
+    /// ones under `[target.<cfg or triple>]`
+    fn is_dependency_table(&self, name: &str) -> bool {
+        Self::DEPENDENCY_TABLES.contains(&name) || self.target_table_re.is_match(name)
+    }
+
     /// Extract dependencies from all dependency tables
     fn extract_dependencies(
         &self,
@@ -98,7 +130,7 @@ impl CargoTomlParser {
             return;
         };
 
-        if !Self::DEPENDENCY_TABLES.contains(&name.as_str()) {
+        if Self::is_skipped_table(&name) || !self.is_dependency_table(&name) {
             return;
         }
 
@@ -106,7 +138,13 @@ impl CargoTomlParser {
         let mut cursor = table_node.walk();
         for child in table_node.children(&mut cursor) {
             if child.kind() == "pair" {
-                self.extract_package_from_pair(child, content, results);
+                self.extract_package_from_pair(
+                    child,
+                    content,
+                    &name,
+                    table_node.start_position().row,
+                    results,
+                );
             }
         }
     }
@@ -116,11 +154,14 @@ impl CargoTomlParser {
         &self,
         pair_node: tree_sitter::Node,
         content: &str,
+        table_name: &str,
+        table_line: usize,
         results: &mut Vec<PackageInfo>,
     ) {
         let mut cursor = pair_node.walk();
         let mut package_name: Option<String> = None;
-        let mut version_info: Option<(String, usize, usize, usize, usize)> = None;
+        let mut version_info: Option<VersionSpan> = None;
+        let mut extra_info: Option<ExtraInfo> = None;
         let mut is_dotted_key = false;
         let mut dotted_key_suffix: Option<String> = None;
 
@@ -143,43 +184,21 @@ impl CargoTomlParser {
                     if is_dotted_key {
                         // For dotted keys, only extract if suffix is "version"
                         if dotted_key_suffix.as_deref() == Some("version") {
-                            let text = &content[child.byte_range()];
-                            let version = text
-                                .trim()
-                                .trim_start_matches('"')
-                                .trim_end_matches('"')
-                                .to_string();
-                            let start_point = child.start_position();
-                            version_info = Some((
-                                version,
-                                child.start_byte() + 1,
-                                child.end_byte() - 1,
-                                start_point.row,
-                                start_point.column + 1,
-                            ));
+                            version_info = Some(Self::string_span(child, content));
                         }
                         // Skip if suffix is path, workspace, registry, or other
                     } else {
                         // Non-dotted simple version: serde = "1.0"
-                        let text = &content[child.byte_range()];
-                        let version = text
-                            .trim()
-                            .trim_start_matches('"')
-                            .trim_end_matches('"')
-                            .to_string();
-                        let start_point = child.start_position();
-                        version_info = Some((
-                            version,
-                            child.start_byte() + 1,
-                            child.end_byte() - 1,
-                            start_point.row,
-                            start_point.column + 1,
-                        ));
+                        version_info = Some(Self::string_span(child, content));
                     }
                 }
                 "inline_table" => {
                     // Inline table: serde = { version = "1.0", features = ["derive"] }
-                    version_info = self.extract_version_from_inline_table(child, content);
+                    (version_info, extra_info) =
+                        match self.extract_version_from_inline_table(child, content) {
+                            Some((span, extra_info)) => (Some(span), extra_info),
+                            None => (None, None),
+                        };
                 }
                 _ => {}
             }
@@ -188,71 +207,115 @@ impl CargoTomlParser {
         if let (Some(name), Some((version, start_offset, end_offset, line, column))) =
             (package_name, version_info)
         {
+            let registry_url = Some(registry_type_to_url(RegistryType::CratesIo, &name));
             results.push(PackageInfo {
                 name,
                 version,
                 commit_hash: None,
                 registry_type: RegistryType::CratesIo,
+                registry_url,
                 start_offset,
                 end_offset,
                 line,
                 column,
-                extra_info: None,
+                section_name: Some(table_name.to_string()),
+                section_line: Some(table_line),
+                extra_info,
             });
         }
     }
 
+    /// Version and location of a TOML string node, without the quotes
+    fn string_span(node: tree_sitter::Node, content: &str) -> VersionSpan {
+        let text = &content[node.byte_range()];
+        let version = text
+            .trim()
+            .trim_start_matches('"')
+            .trim_end_matches('"')
+            .to_string();
+        let start_point = node.start_position();
+        (
+            version,
+            node.start_byte() + 1,
+            node.end_byte() - 1,
+            start_point.row,
+            start_point.column + 1,
+        )
+    }
+
     /// Keys that indicate dependencies that should be skipped
+    /// (`optional`, `default-features` and `features` still resolve from crates.io).
     const SKIP_KEYS: [&'static str; 3] = ["path", "workspace", "registry"];
 
+    /// Keys pinning the revision of a git dependency, in order of precedence
+    const GIT_REF_KEYS: [&'static str; 3] = ["rev", "tag", "branch"];
+
     /// Extract version from an inline table: { version = "1.0", ... }
-    /// Returns None if the dependency should be skipped (path, workspace, or registry)
+    ///
+    /// Git dependencies resolve from the repository, even with a `version`
+    /// key, so they are returned with their pinned `rev`/`tag`/`branch` (or
+    /// `HEAD`) as version and [`ExtraInfo::CargoGitDep`]. Returns None if the
+    /// dependency should be skipped (path, workspace or registry).
     fn extract_version_from_inline_table(
         &self,
         table_node: tree_sitter::Node,
         content: &str,
-    ) -> Option<(String, usize, usize, usize, usize)> {
-        // First pass: check for skip keys
+    ) -> Option<(VersionSpan, Option<ExtraInfo>)> {
+        let strings = Self::inline_table_strings(table_node, content);
+        let find = |key: &str| {
+            strings
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, node)| *node)
+        };
+
+        if let Some(git_node) = find("git") {
+            let ref_node = Self::GIT_REF_KEYS.iter().find_map(|key| find(key));
+            let (url, ..) = Self::string_span(git_node, content);
+            // Point at the pinned revision, or at the URL when tracking HEAD
+            let (rev_or_tag, start_offset, end_offset, line, column) =
+                Self::string_span(ref_node.unwrap_or(git_node), content);
+            let rev_or_tag = ref_node.map(|_| rev_or_tag);
+            let version = rev_or_tag.clone().unwrap_or_else(|| "HEAD".to_string());
+            return Some((
+                (version, start_offset, end_offset, line, column),
+                Some(ExtraInfo::CargoGitDep { url, rev_or_tag }),
+            ));
+        }
+
         if self.should_skip_inline_table(table_node, content) {
             return None;
         }
 
-        // Second pass: extract version
+        find("version").map(|node| (Self::string_span(node, content), None))
+    }
+
+    /// String values of an inline table, keyed by their bare key
+    fn inline_table_strings<'a>(
+        table_node: tree_sitter::Node<'a>,
+        content: &'a str,
+    ) -> Vec<(&'a str, tree_sitter::Node<'a>)> {
+        let mut strings = Vec::new();
         let mut cursor = table_node.walk();
         for child in table_node.children(&mut cursor) {
-            if child.kind() == "pair" {
-                let mut pair_cursor = child.walk();
-                let mut is_version_key = false;
-
-                for pair_child in child.children(&mut pair_cursor) {
-                    match pair_child.kind() {
-                        "bare_key" => {
-                            let key = &content[pair_child.byte_range()];
-                            is_version_key = key == "version";
-                        }
-                        "string" if is_version_key => {
-                            let text = &content[pair_child.byte_range()];
-                            let version = text
-                                .trim()
-                                .trim_start_matches('"')
-                                .trim_end_matches('"')
-                                .to_string();
-                            let start_point = pair_child.start_position();
-                            return Some((
-                                version,
-                                pair_child.start_byte() + 1,
-                                pair_child.end_byte() - 1,
-                                start_point.row,
-                                start_point.column + 1,
-                            ));
+            if child.kind() != "pair" {
+                continue;
+            }
+            let mut pair_cursor = child.walk();
+            let mut key = None;
+            for pair_child in child.children(&mut pair_cursor) {
+                match pair_child.kind() {
+                    "bare_key" => key = Some(&content[pair_child.byte_range()]),
+                    "string" => {
+                        if let Some(key) = key {
+                            strings.push((key, pair_child));
                         }
-                        _ => {}
                     }
+                    _ => {}
                 }
             }
         }
-
-        None
+        strings
     }
 
     /// Check if an inline table contains keys that should cause the dependency to be skipped
@@ -281,6 +344,7 @@ impl CargoTomlParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     #[test]
     fn parse_extracts_dependencies() {
@@ -301,10 +365,13 @@ serde = "1.0.0"
                 version: "1.0.0".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::CratesIo,
+                registry_url: Some("https://crates.io/crates/serde".to_string()),
                 start_offset: 69,
                 end_offset: 74,
                 line: 5,
                 column: 9,
+                section_name: Some("dependencies".to_string()),
+                section_line: Some(4),
                 extra_info: None,
             }
         );
@@ -328,15 +395,44 @@ mockall = "0.14"
                 version: "0.14".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::CratesIo,
+                registry_url: Some("https://crates.io/crates/mockall".to_string()),
                 start_offset: 57,
                 end_offset: 61,
                 line: 4,
                 column: 11,
+                section_name: Some("dev-dependencies".to_string()),
+                section_line: Some(3),
                 extra_info: None,
             }
         );
     }
 
+    #[test]
+    fn parse_extracts_optional_dependency_with_features() {
+        let parser = CargoTomlParser::new();
+        let content = r#"[dependencies]
+serde = { version = "1.0", optional = true, default-features = false, features = ["derive"] }
+"#;
+        let result = parser.parse(content).unwrap();
+        assert_eq!(
+            result,
+            vec![PackageInfo {
+                name: "serde".to_string(),
+                version: "1.0".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::CratesIo,
+                registry_url: Some("https://crates.io/crates/serde".to_string()),
+                start_offset: 36,
+                end_offset: 39,
+                line: 1,
+                column: 21,
+                section_name: Some("dependencies".to_string()),
+                section_line: Some(0),
+                extra_info: None,
+            }]
+        );
+    }
+
     #[test]
     fn parse_extracts_build_dependencies() {
         let parser = CargoTomlParser::new();
@@ -355,10 +451,13 @@ cc = "1.0"
                 version: "1.0".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::CratesIo,
+                registry_url: Some("https://crates.io/crates/cc".to_string()),
                 start_offset: 54,
                 end_offset: 57,
                 line: 4,
                 column: 6,
+                section_name: Some("build-dependencies".to_string()),
+                section_line: Some(3),
                 extra_info: None,
             }
         );
@@ -473,6 +572,65 @@ tokio = "1.0"
         assert_eq!(result[1].name, "tokio");
     }
 
+    #[test]
+    fn parse_extracts_git_dependencies_with_git_info() {
+        let parser = CargoTomlParser::new();
+        let content = r#"[dependencies]
+serde = "1.0"
+forked = { git = "https://github.com/owner/forked", tag = "v1.0" }
+pinned = { git = "https://github.com/owner/pinned", rev = "abc123", version = "1.0" }
+head = { git = "https://github.com/owner/head" }
+"#;
+        let result = parser.parse(content).unwrap();
+
+        let git_deps: Vec<(&str, &str, Option<&ExtraInfo>)> = result
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str(), p.extra_info.as_ref()))
+            .collect();
+        assert_eq!(
+            git_deps,
+            vec![
+                ("serde", "1.0", None),
+                (
+                    "forked",
+                    "v1.0",
+                    Some(&ExtraInfo::CargoGitDep {
+                        url: "https://github.com/owner/forked".to_string(),
+                        rev_or_tag: Some("v1.0".to_string()),
+                    })
+                ),
+                (
+                    "pinned",
+                    "abc123",
+                    Some(&ExtraInfo::CargoGitDep {
+                        url: "https://github.com/owner/pinned".to_string(),
+                        rev_or_tag: Some("abc123".to_string()),
+                    })
+                ),
+                (
+                    "head",
+                    "HEAD",
+                    Some(&ExtraInfo::CargoGitDep {
+                        url: "https://github.com/owner/head".to_string(),
+                        rev_or_tag: None,
+                    })
+                ),
+            ]
+        );
+        assert!(result[1].is_git_dependency());
+        assert!(!result[0].is_git_dependency());
+
+        // The span points at the pinned revision, or at the URL for HEAD
+        assert_eq!(
+            &content[result[2].start_offset..result[2].end_offset],
+            "abc123"
+        );
+        assert_eq!(
+            &content[result[3].start_offset..result[3].end_offset],
+            "https://github.com/owner/head"
+        );
+    }
+
     #[test]
     fn parse_skips_mixed_special_dependencies() {
         let parser = CargoTomlParser::new();
@@ -547,4 +705,89 @@ serde = { version = "1.0", features = ["derive"] }
         assert_eq!(result[1].name, "serde");
         assert_eq!(result[1].version, "1.0");
     }
+
+    #[rstest]
+    #[case::profile(
+        r#"[profile.release]
+opt-level = 3
+
+[profile.dev.package.serde]
+opt-level = "2"
+"#
+    )]
+    #[case::patch(
+        r#"[patch.crates-io]
+serde = { version = "1.0.200", git = "https://github.com/serde-rs/serde" }
+tokio = "1.40"
+"#
+    )]
+    #[case::replace(
+        r#"[replace]
+"foo:0.1.0" = { version = "0.1.1" }
+"#
+    )]
+    fn parse_skips_non_dependency_tables(#[case] skipped_table: &str) {
+        let parser = CargoTomlParser::new();
+        let content = format!("[dependencies]\nanyhow = \"1.0\"\n\n{skipped_table}");
+
+        let result = parser.parse(&content).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "anyhow");
+    }
+
+    #[test]
+    fn parse_extracts_target_specific_dependencies() {
+        let parser = CargoTomlParser::new();
+        let content = r#"[target.'cfg(unix)'.dependencies]
+nix = "0.29"
+
+[target.'x86_64-pc-windows-msvc'.dev-dependencies]
+windows-sys = { version = "0.59" }
+
+[target.x86_64-unknown-linux-gnu.build-dependencies]
+cc = "1.1"
+
+[target."cfg(target_os = \"macos\")".dependencies]
+core-foundation = "0.10"
+"#;
+
+        let result = parser.parse(content).unwrap();
+
+        let names: Vec<_> = result.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["nix", "windows-sys", "cc", "core-foundation"]);
+        assert_eq!(result[0].version, "0.29");
+        assert_eq!(result[0].line, 1);
+        assert_eq!(result[0].column, 7);
+        assert_eq!(
+            result[1].section_name.as_deref(),
+            Some("target.'x86_64-pc-windows-msvc'.dev-dependencies")
+        );
+    }
+
+    #[rstest]
+    #[case("dependencies", true)]
+    #[case("workspace.dependencies", true)]
+    #[case("target.'cfg(unix)'.dependencies", true)]
+    #[case("target.'cfg(windows)'.dev-dependencies", true)]
+    #[case("target.'aarch64-apple-darwin'.build-dependencies", true)]
+    #[case("target.x86_64-unknown-linux-gnu.dependencies", true)]
+    #[case("target.'cfg(unix)'.features", false)]
+    #[case("target.dependencies", false)]
+    #[case("package.metadata.dependencies", false)]
+    fn is_dependency_table_matches_target_tables(#[case] name: &str, #[case] expected: bool) {
+        assert_eq!(CargoTomlParser::new().is_dependency_table(name), expected);
+    }
+
+    #[rstest]
+    #[case("profile", true)]
+    #[case("profile.release", true)]
+    #[case("patch.crates-io", true)]
+    #[case("replace", true)]
+    #[case("dependencies", false)]
+    #[case("replacements", false)]
+    #[case("workspace.dependencies", false)]
+    fn is_skipped_table_matches_whole_segments(#[case] name: &str, #[case] expected: bool) {
+        assert_eq!(CargoTomlParser::is_skipped_table(name), expected);
+    }
 }
@@ -4,7 +4,7 @@
 //! Supports Docker Hub (official and user images) and ghcr.io images.
 
 use crate::parser::traits::{ParseError, Parser};
-use crate::parser::types::{PackageInfo, RegistryType};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
 use tracing::warn;
 
 /// Parser for compose.yaml / docker-compose.yaml files
@@ -111,14 +111,34 @@ fn parse_image_value(node: tree_sitter::Node, content: &str) -> Option<PackageIn
     let trimmed = raw_text.trim();
 
     // Remove quotes if present
-    let image_ref = if (trimmed.starts_with('\'') && trimmed.ends_with('\''))
+    let (image_ref, quote_offset) = if (trimmed.starts_with('\'') && trimmed.ends_with('\''))
         || (trimmed.starts_with('"') && trimmed.ends_with('"'))
     {
-        &trimmed[1..trimmed.len() - 1]
+        (&trimmed[1..trimmed.len() - 1], 1)
     } else {
-        trimmed
+        (trimmed, 0)
     };
 
+    // Find where the actual text starts within the node (skip leading whitespace)
+    let leading_whitespace = raw_text.len() - raw_text.trim_start().len();
+
+    image_tag_package(
+        image_ref,
+        node.start_byte() + leading_whitespace + quote_offset,
+        content,
+    )
+}
+
+/// Build the package of the tag of an image reference like "nginx:1.25" or
+/// "ghcr.io/owner/repo:v1.0.0", which starts at `ref_offset` in `content`
+///
+/// References without a checkable tag are skipped: no tag, `latest`,
+/// digests, variable expansions and images of unsupported registries.
+pub(crate) fn image_tag_package(
+    image_ref: &str,
+    ref_offset: usize,
+    content: &str,
+) -> Option<PackageInfo> {
     // Skip variable expansions
     if image_ref.contains("${") || image_ref.contains("$") {
         return None;
@@ -137,8 +157,9 @@ fn parse_image_value(node: tree_sitter::Node, content: &str) -> Option<PackageIn
         return None;
     }
 
-    // Skip empty tag
-    if tag.is_empty() {
+    // Skip empty tag, and the port of a registry without a tag
+    // (`localhost:5000/app`)
+    if tag.is_empty() || tag.contains('/') {
         return None;
     }
 
@@ -146,34 +167,25 @@ fn parse_image_value(node: tree_sitter::Node, content: &str) -> Option<PackageIn
     let name = resolve_image_name(image_name)?;
 
     // Calculate offset for the tag part (after the colon)
-    let colon_pos = image_ref.rfind(':')?;
-    let value_start = node.start_byte();
-
-    // Account for quotes
-    let quote_offset = if raw_text.trim().starts_with('"') || raw_text.trim().starts_with('\'') {
-        1
-    } else {
-        0
-    };
-
-    // Find where the actual text starts within the node (skip leading whitespace)
-    let leading_whitespace = raw_text.len() - raw_text.trim_start().len();
-
-    let tag_start_offset = value_start + leading_whitespace + quote_offset + colon_pos + 1;
+    let tag_start_offset = ref_offset + image_name.len() + 1;
     let tag_end_offset = tag_start_offset + tag.len();
 
     // Calculate line/column from tag start offset
     let (line, column) = offset_to_line_col(content, tag_start_offset);
 
+    let registry_url = Some(registry_type_to_url(RegistryType::Docker, &name));
     Some(PackageInfo {
         name,
         version: tag.to_string(),
         commit_hash: None,
         registry_type: RegistryType::Docker,
+        registry_url,
         start_offset: tag_start_offset,
         end_offset: tag_end_offset,
         line,
         column,
+        section_name: None,
+        section_line: None,
         extra_info: None,
     })
 }
@@ -253,10 +265,13 @@ mod tests {
                 version: "1.25".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::Docker,
+                registry_url: Some("https://hub.docker.com/_/nginx".to_string()),
                 start_offset: 34,
                 end_offset: 38,
                 line: 2,
                 column: 17,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
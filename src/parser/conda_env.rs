@@ -0,0 +1,368 @@
+//! Conda environment.yml parser
+//!
+//! Extracts entries from the top-level `dependencies:` list:
+//! - `- numpy=1.21.0`, `- scipy>=1.7`, `- python 3.11` - conda packages (`RegistryType::Conda`)
+//! - `- pip: [flask>=2.0]` - pip requirements (`RegistryType::PyPI`)
+//!
+//! Entries without a version, and conda packages pinned to an explicit
+//! channel (`conda-forge::numpy=1.21`), are skipped. A trailing build string
+//! (`numpy=1.21.0=py39h5d0ccc0_0`) is not part of the reported version.
+
+use std::str::FromStr;
+
+use pep508_rs::{Requirement, VerbatimUrl, VersionOrUrl};
+use tracing::warn;
+
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
+
+/// Parser for Conda environment.yml files
+pub struct CondaEnvParser;
+
+impl CondaEnvParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CondaEnvParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for CondaEnvParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        let mut parser = tree_sitter::Parser::new();
+        let language = tree_sitter_yaml::LANGUAGE;
+        parser.set_language(&language.into()).map_err(|e| {
+            warn!("Failed to set YAML language for tree-sitter: {}", e);
+            ParseError::TreeSitter(e.to_string())
+        })?;
+
+        let tree = parser.parse(content, None).ok_or_else(|| {
+            warn!("Failed to parse YAML content");
+            ParseError::ParseFailed("Failed to parse YAML".to_string())
+        })?;
+
+        let root = tree.root_node();
+        let mut results = Vec::new();
+
+        if let Some(dependencies) = find_top_level_value(root, content, "dependencies") {
+            self.extract_dependencies(dependencies, content, &mut results);
+        }
+
+        Ok(results)
+    }
+}
+
+impl CondaEnvParser {
+    /// Extract conda packages and nested pip requirements from the `dependencies` list
+    fn extract_dependencies(
+        &self,
+        dependencies: tree_sitter::Node,
+        content: &str,
+        results: &mut Vec<PackageInfo>,
+    ) {
+        for item in sequence_items(dependencies) {
+            match item.kind() {
+                "flow_node" => {
+                    if let Some(scalar) = Scalar::from_node(item, content) {
+                        results.extend(parse_conda_spec(&scalar));
+                    }
+                }
+                "block_node" => {
+                    let Some(pip) = find_mapping_value(item, content, "pip") else {
+                        continue;
+                    };
+                    results.extend(
+                        sequence_items(pip)
+                            .into_iter()
+                            .filter_map(|node| Scalar::from_node(node, content))
+                            .filter_map(|scalar| parse_pip_requirement(&scalar)),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A YAML scalar with its unquoted text and source position
+pub(crate) struct Scalar<'a> {
+    pub(crate) text: &'a str,
+    /// Byte offset of `text` in the document
+    start_offset: usize,
+    line: usize,
+    /// Column of `text` in the line
+    column: usize,
+}
+
+impl<'a> Scalar<'a> {
+    pub(crate) fn from_node(flow_node: tree_sitter::Node, content: &'a str) -> Option<Self> {
+        let scalar = flow_node.named_child(0)?;
+        let quote_len = match scalar.kind() {
+            "plain_scalar" => 0,
+            "double_quote_scalar" | "single_quote_scalar" => 1,
+            _ => return None,
+        };
+        let range = scalar.byte_range();
+        let start_offset = range.start + quote_len;
+        let start_point = scalar.start_position();
+
+        Some(Self {
+            text: &content[start_offset..range.end - quote_len],
+            start_offset,
+            line: start_point.row,
+            column: start_point.column + quote_len,
+        })
+    }
+
+    /// Build a PackageInfo whose version is `text[start..end]`
+    pub(crate) fn package_info(
+        &self,
+        name: String,
+        start: usize,
+        end: usize,
+        registry_type: RegistryType,
+    ) -> PackageInfo {
+        let registry_url = Some(registry_type_to_url(registry_type, &name));
+        PackageInfo {
+            name,
+            version: self.text[start..end].to_string(),
+            commit_hash: None,
+            registry_type,
+            registry_url,
+            start_offset: self.start_offset + start,
+            end_offset: self.start_offset + end,
+            line: self.line,
+            column: self.column + start,
+            section_name: None,
+            section_line: None,
+            extra_info: None,
+        }
+    }
+}
+
+/// Parse a conda match spec (`numpy=1.21.0`, `scipy>=1.7`, `python 3.11`)
+fn parse_conda_spec(scalar: &Scalar) -> Option<PackageInfo> {
+    let text = scalar.text.trim_end();
+
+    // Channel-pinned packages are not served by the configured channel
+    if text.contains("::") {
+        return None;
+    }
+
+    let name_end = text.find(['=', '<', '>', '!', '~', ' '])?;
+    let name = &text[..name_end];
+    let start = name_end + text[name_end..].find(|c: char| c != ' ')?;
+    let spec = &text[start..];
+
+    // `name=version=build` and `name version build` carry a build string
+    // after the version; comparison specs end at the first space.
+    let operator_len = if spec.starts_with("==") {
+        2
+    } else if spec.starts_with('=') {
+        1
+    } else {
+        0
+    };
+    let version_len = spec[operator_len..]
+        .find(|c: char| c == ' ' || (operator_len > 0 && c == '='))
+        .map_or(spec.len(), |pos| operator_len + pos);
+    if name.is_empty() || version_len == operator_len {
+        return None;
+    }
+
+    Some(scalar.package_info(
+        name.to_string(),
+        start,
+        start + version_len,
+        RegistryType::Conda,
+    ))
+}
+
+/// Parse a pip requirement (`flask>=2.0`) listed under `- pip:`
+fn parse_pip_requirement(scalar: &Scalar) -> Option<PackageInfo> {
+    let req = Requirement::<VerbatimUrl>::from_str(scalar.text)
+        .inspect_err(|e| warn!("Failed to parse pip requirement '{}': {}", scalar.text, e))
+        .ok()?;
+
+    // Skip URL requirements and requirements without a version
+    let Some(VersionOrUrl::VersionSpecifier(_)) = req.version_or_url else {
+        return None;
+    };
+
+    let text = scalar.text;
+    let start = text.find(['<', '>', '=', '!', '~'])?;
+    let end = start + text[start..].find(';').unwrap_or(text.len() - start);
+    let end = start + text[start..end].trim_end().len();
+
+    Some(scalar.package_info(req.name.to_string(), start, end, RegistryType::PyPI))
+}
+
+/// Find the value of a key in the document's top-level mapping
+pub(crate) fn find_top_level_value<'a>(
+    root: tree_sitter::Node<'a>,
+    content: &str,
+    key: &str,
+) -> Option<tree_sitter::Node<'a>> {
+    let document = root.named_child(0)?;
+    let block_node = document.named_child(0)?;
+    find_mapping_value(block_node, content, key)
+}
+
+/// Find the value of a key in a block mapping wrapped by `block_node`
+pub(crate) fn find_mapping_value<'a>(
+    block_node: tree_sitter::Node<'a>,
+    content: &str,
+    key: &str,
+) -> Option<tree_sitter::Node<'a>> {
+    let mapping = block_node.named_child(0)?;
+    if mapping.kind() != "block_mapping" {
+        return None;
+    }
+
+    let mut cursor = mapping.walk();
+    mapping
+        .named_children(&mut cursor)
+        .filter(|pair| pair.kind() == "block_mapping_pair")
+        .find(|pair| {
+            pair.child_by_field_name("key")
+                .is_some_and(|k| content[k.byte_range()].trim() == key)
+        })
+        .and_then(|pair| pair.child_by_field_name("value"))
+}
+
+/// Get the item nodes of a block (`- a`) or flow (`[a, b]`) sequence
+pub(crate) fn sequence_items(node: tree_sitter::Node) -> Vec<tree_sitter::Node> {
+    let Some(sequence) = node.named_child(0) else {
+        return Vec::new();
+    };
+
+    let mut cursor = sequence.walk();
+    match sequence.kind() {
+        "block_sequence" => sequence
+            .named_children(&mut cursor)
+            .filter(|item| item.kind() == "block_sequence_item")
+            .filter_map(|item| item.named_child(0))
+            .collect(),
+        "flow_sequence" => sequence
+            .named_children(&mut cursor)
+            .filter(|item| item.kind() == "flow_node")
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_extracts_conda_and_pip_dependencies() {
+        let parser = CondaEnvParser::new();
+        let content = r#"name: myenv
+channels:
+  - conda-forge
+dependencies:
+  - numpy=1.21.0
+  - pip:
+    - flask>=2.0
+"#;
+        let result = parser.parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                PackageInfo {
+                    name: "numpy".to_string(),
+                    version: "=1.21.0".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::Conda,
+                    registry_url: Some("https://anaconda.org/conda-forge/numpy".to_string()),
+                    start_offset: 61,
+                    end_offset: 68,
+                    line: 4,
+                    column: 9,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+                PackageInfo {
+                    name: "flask".to_string(),
+                    version: ">=2.0".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::PyPI,
+                    registry_url: Some("https://pypi.org/project/flask/".to_string()),
+                    start_offset: 89,
+                    end_offset: 94,
+                    line: 6,
+                    column: 11,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("numpy=1.21.0", Some(("numpy", "=1.21.0")))]
+    #[case("numpy==1.21.0", Some(("numpy", "==1.21.0")))]
+    #[case("scipy>=1.7", Some(("scipy", ">=1.7")))]
+    #[case("scipy>=1.7,<2", Some(("scipy", ">=1.7,<2")))]
+    #[case("python 3.11", Some(("python", "3.11")))]
+    #[case("numpy=1.21.0=py39h5d0ccc0_0", Some(("numpy", "=1.21.0")))]
+    #[case("numpy 1.21.0 py39h5d0ccc0_0", Some(("numpy", "1.21.0")))]
+    #[case("\"pandas>=2.0\"", Some(("pandas", ">=2.0")))]
+    #[case("numpy", None)]
+    #[case("conda-forge::numpy=1.21", None)]
+    fn parse_handles_conda_specs(#[case] entry: &str, #[case] expected: Option<(&str, &str)>) {
+        let parser = CondaEnvParser::new();
+        let content = format!("dependencies:\n  - {entry}\n");
+        let result = parser.parse(&content).unwrap();
+
+        let packages: Vec<(&str, &str)> = result
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        assert_eq!(packages, expected.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn parse_extracts_pip_flow_sequence_and_skips_unversioned() {
+        let parser = CondaEnvParser::new();
+        let content = r#"dependencies:
+  - pip: [requests==2.31.0, rich, "httpx>=0.27; python_version >= '3.8'"]
+"#;
+        let result = parser.parse(content).unwrap();
+
+        let packages: Vec<(&str, &str, RegistryType)> = result
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str(), p.registry_type))
+            .collect();
+        assert_eq!(
+            packages,
+            vec![
+                ("requests", "==2.31.0", RegistryType::PyPI),
+                ("httpx", ">=0.27", RegistryType::PyPI),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_ignores_non_dependency_sections() {
+        let parser = CondaEnvParser::new();
+        let content = r#"name: myenv
+channels:
+  - conda-forge=1.0
+variables:
+  dependencies: numpy=1.0
+"#;
+        let result = parser.parse(content).unwrap();
+
+        assert!(result.is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+//! conda-lock.yml parser for locked Conda environments
+//!
+//! Extracts every entry of the top-level `package:` list with its locked
+//! `version`. conda-lock repeats a package once per platform, so only the
+//! first entry of each package is reported. The `manager` key selects the
+//! registry: `conda` packages are checked against Conda, `pip` packages
+//! against PyPI. Entries of other managers are skipped.
+
+use std::collections::HashSet;
+
+use tracing::warn;
+
+use crate::parser::conda_env::{Scalar, find_mapping_value, find_top_level_value, sequence_items};
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::{ExtraInfo, PackageInfo, RegistryType};
+
+/// Parser for conda-lock.yml files
+pub struct CondaLockParser;
+
+impl CondaLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CondaLockParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for CondaLockParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        let mut parser = tree_sitter::Parser::new();
+        let language = tree_sitter_yaml::LANGUAGE;
+        parser.set_language(&language.into()).map_err(|e| {
+            warn!("Failed to set YAML language for tree-sitter: {}", e);
+            ParseError::TreeSitter(e.to_string())
+        })?;
+
+        let tree = parser.parse(content, None).ok_or_else(|| {
+            warn!("Failed to parse YAML content");
+            ParseError::ParseFailed("Failed to parse YAML".to_string())
+        })?;
+
+        let root = tree.root_node();
+        let Some(packages) = find_top_level_value(root, content, "package") else {
+            return Ok(Vec::new());
+        };
+
+        let mut seen = HashSet::new();
+        Ok(sequence_items(packages)
+            .into_iter()
+            .filter_map(|item| self.extract_package(item, content))
+            .filter(|package| seen.insert((package.registry_type, package.name.clone())))
+            .collect())
+    }
+}
+
+impl CondaLockParser {
+    /// Extract one `- name: ... version: ... manager: ...` entry
+    fn extract_package(&self, item: tree_sitter::Node, content: &str) -> Option<PackageInfo> {
+        let field = |key| {
+            find_mapping_value(item, content, key).and_then(|node| Scalar::from_node(node, content))
+        };
+
+        let registry_type = match field("manager")?.text {
+            "conda" => RegistryType::Conda,
+            "pip" => RegistryType::PyPI,
+            _ => return None,
+        };
+        let name = field("name")?.text.to_string();
+        let version = field("version")?;
+        if name.is_empty() || version.text.is_empty() {
+            return None;
+        }
+
+        let mut package = version.package_info(name, 0, version.text.len(), registry_type);
+        package.extra_info = Some(ExtraInfo::CondaLock);
+        Some(package)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_extracts_conda_and_pip_packages_once() {
+        let parser = CondaLockParser::new();
+        let content = r#"version: 1
+metadata:
+  platforms:
+  - linux-64
+  - osx-arm64
+package:
+- name: numpy
+  version: 1.26.4
+  manager: conda
+  platform: linux-64
+  url: https://conda.anaconda.org/conda-forge/linux-64/numpy-1.26.4-py312heda63a1_0.conda
+- name: numpy
+  version: 1.26.3
+  manager: conda
+  platform: osx-arm64
+  url: https://conda.anaconda.org/conda-forge/osx-arm64/numpy-1.26.3-py312h8442bc7_0.conda
+- name: requests
+  version: '2.31.0'
+  manager: pip
+  platform: linux-64
+  url: https://files.pythonhosted.org/packages/requests-2.31.0-py3-none-any.whl
+"#;
+
+        let result = parser.parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                PackageInfo {
+                    name: "numpy".to_string(),
+                    version: "1.26.4".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::Conda,
+                    registry_url: Some("https://anaconda.org/conda-forge/numpy".to_string()),
+                    start_offset: 95,
+                    end_offset: 101,
+                    line: 7,
+                    column: 11,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: Some(ExtraInfo::CondaLock),
+                },
+                PackageInfo {
+                    name: "requests".to_string(),
+                    version: "2.31.0".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::PyPI,
+                    registry_url: Some("https://pypi.org/project/requests/".to_string()),
+                    start_offset: 421,
+                    end_offset: 427,
+                    line: 17,
+                    column: 12,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: Some(ExtraInfo::CondaLock),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_skips_packages_of_other_managers() {
+        let parser = CondaLockParser::new();
+        let content = r#"package:
+- name: numpy
+  version: 1.26.4
+  manager: conda
+- name: local-tool
+  version: 0.1.0
+  manager: cargo
+- name: flask
+  manager: pip
+"#;
+
+        let result = parser.parse(content).unwrap();
+
+        let names: Vec<_> = result.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["numpy"]);
+    }
+
+    #[test]
+    fn parse_returns_empty_without_package_list() {
+        let parser = CondaLockParser::new();
+        let content = "version: 1\nmetadata:\n  platforms:\n  - linux-64\n";
+
+        assert!(parser.parse(content).unwrap().is_empty());
+    }
+}
@@ -1,7 +1,13 @@
 //! deno.json parser
+//!
+//! Extracts `jsr:` specifiers from `imports`. `npm:` specifiers are only
+//! extracted when the config opts into npm resolution through `node_modules`
+//! (`"nodeModulesDir"`) or `"unstable": ["npm"]`; they are then checked
+//! against the npm registry with npm's version matching. The `lock` field
+//! does not affect which versions are available, so it is ignored.
 
 use crate::parser::traits::{ParseError, Parser};
-use crate::parser::types::{PackageInfo, RegistryType};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
 use tracing::warn;
 
 /// Parser for deno.json files
@@ -40,20 +46,21 @@ impl Parser for DenoJsonParser {
         if let Some(document) = root.child(0)
             && document.kind() == "object"
         {
-            self.extract_imports(document, content, &mut results);
+            let npm_resolution = self.uses_npm_resolution(document, content);
+            self.extract_imports(document, content, npm_resolution, &mut results);
         }
 
         Ok(results)
     }
 }
 
-/// Parsed JSR specifier (`jsr:@scope/pkg@version`).
+/// Parsed registry specifier (`jsr:@scope/pkg@version`, `npm:pkg@version`).
 ///
 /// `version_offset_in_value` is `Some(idx)` when an explicit version is
 /// present and points at the version token's start inside the raw value,
 /// so code-action edits can target only the version range. For specifiers
 /// without a version, it is `None` and `version` is the sentinel `"latest"`.
-struct JsrSpecifier {
+struct Specifier {
     package_name: String,
     version: String,
     version_offset_in_value: Option<usize>,
@@ -61,7 +68,7 @@ struct JsrSpecifier {
 
 impl DenoJsonParser {
     /// Parse JSR specifier format: `jsr:@scope/package@version`.
-    fn parse_jsr_specifier(value: &str) -> Option<JsrSpecifier> {
+    fn parse_jsr_specifier(value: &str) -> Option<Specifier> {
         let rest = value.strip_prefix("jsr:")?;
         let prefix_len = "jsr:".len();
 
@@ -72,13 +79,13 @@ impl DenoJsonParser {
             let package_name = &rest[..slash_pos + 1 + at_pos];
             let version = &after_slash[at_pos + 1..];
             let version_offset_in_value = prefix_len + slash_pos + 1 + at_pos + 1;
-            Some(JsrSpecifier {
+            Some(Specifier {
                 package_name: package_name.to_string(),
                 version: version.to_string(),
                 version_offset_in_value: Some(version_offset_in_value),
             })
         } else {
-            Some(JsrSpecifier {
+            Some(Specifier {
                 package_name: rest.to_string(),
                 version: "latest".to_string(),
                 version_offset_in_value: None,
@@ -86,11 +93,83 @@ impl DenoJsonParser {
         }
     }
 
+    /// Parse npm specifier format: `npm:package@version`, optionally scoped
+    /// and followed by a subpath (`npm:preact@10.19.0/hooks`).
+    fn parse_npm_specifier(value: &str) -> Option<Specifier> {
+        let rest = value.strip_prefix("npm:")?;
+        let prefix_len = "npm:".len();
+
+        // Skip the leading '@' of scoped names when looking for the version
+        if let Some(at_pos) = rest.get(1..)?.find('@').map(|pos| pos + 1) {
+            let package_name = &rest[..at_pos];
+            let version = rest[at_pos + 1..].split('/').next().unwrap_or_default();
+            if package_name.is_empty() || version.is_empty() {
+                return None;
+            }
+            Some(Specifier {
+                package_name: package_name.to_string(),
+                version: version.to_string(),
+                version_offset_in_value: Some(prefix_len + at_pos + 1),
+            })
+        } else {
+            let segments = if rest.starts_with('@') { 2 } else { 1 };
+            let package_name = rest
+                .splitn(segments + 1, '/')
+                .take(segments)
+                .collect::<Vec<_>>();
+            if package_name.iter().any(|segment| segment.is_empty()) {
+                return None;
+            }
+            Some(Specifier {
+                package_name: package_name.join("/"),
+                version: "latest".to_string(),
+                version_offset_in_value: None,
+            })
+        }
+    }
+
+    /// Whether `npm:` specifiers resolve through npm: `nodeModulesDir` is
+    /// enabled (`true`, `"auto"` or `"manual"`) or `unstable` contains `"npm"`
+    fn uses_npm_resolution(&self, object_node: tree_sitter::Node, content: &str) -> bool {
+        let mut cursor = object_node.walk();
+
+        object_node
+            .children(&mut cursor)
+            .filter(|child| child.kind() == "pair")
+            .any(|pair| {
+                let (Some(key_node), Some(value_node)) = (
+                    pair.child_by_field_name("key"),
+                    pair.child_by_field_name("value"),
+                ) else {
+                    return false;
+                };
+
+                match self.get_string_value(key_node, content).as_str() {
+                    "nodeModulesDir" => match value_node.kind() {
+                        "true" => true,
+                        "string" => matches!(
+                            self.get_string_value(value_node, content).as_str(),
+                            "auto" | "manual"
+                        ),
+                        _ => false,
+                    },
+                    "unstable" if value_node.kind() == "array" => {
+                        let mut array_cursor = value_node.walk();
+                        value_node.named_children(&mut array_cursor).any(|item| {
+                            item.kind() == "string" && self.get_string_value(item, content) == "npm"
+                        })
+                    }
+                    _ => false,
+                }
+            })
+    }
+
     /// Extract imports from the root object
     fn extract_imports(
         &self,
         object_node: tree_sitter::Node,
         content: &str,
+        npm_resolution: bool,
         results: &mut Vec<PackageInfo>,
     ) {
         let mut cursor = object_node.walk();
@@ -115,7 +194,7 @@ impl DenoJsonParser {
             };
 
             if value_node.kind() == "object" {
-                self.extract_packages_from_imports(value_node, content, results);
+                self.extract_packages_from_imports(value_node, content, npm_resolution, results);
             }
         }
     }
@@ -125,6 +204,7 @@ impl DenoJsonParser {
         &self,
         object_node: tree_sitter::Node,
         content: &str,
+        npm_resolution: bool,
         results: &mut Vec<PackageInfo>,
     ) {
         let mut cursor = object_node.walk();
@@ -144,8 +224,16 @@ impl DenoJsonParser {
 
             let raw_value = self.get_string_value(value_node, content);
 
-            // Only process jsr: prefixed entries
-            let Some(specifier) = Self::parse_jsr_specifier(&raw_value) else {
+            // Only process jsr: prefixed entries, plus npm: when npm resolution is enabled
+            let Some((registry_type, specifier)) = Self::parse_jsr_specifier(&raw_value)
+                .map(|specifier| (RegistryType::Jsr, specifier))
+                .or_else(|| {
+                    npm_resolution
+                        .then(|| Self::parse_npm_specifier(&raw_value))
+                        .flatten()
+                        .map(|specifier| (RegistryType::Npm, specifier))
+                })
+            else {
                 continue;
             };
 
@@ -168,15 +256,19 @@ impl DenoJsonParser {
                     (value_start + 1, value_end - 1, start_point.column + 1)
                 };
 
+            let registry_url = Some(registry_type_to_url(registry_type, &specifier.package_name));
             results.push(PackageInfo {
                 name: specifier.package_name,
                 version: specifier.version,
                 commit_hash: None,
-                registry_type: RegistryType::Jsr,
+                registry_type,
+                registry_url,
                 start_offset: version_start_offset,
                 end_offset: version_end_offset,
                 line: start_point.row,
                 column: version_column,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             });
         }
@@ -195,6 +287,7 @@ impl DenoJsonParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     #[test]
     fn parse_extracts_jsr_package() {
@@ -213,10 +306,13 @@ mod tests {
                 version: "^1.0.1".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::Jsr,
+                registry_url: Some("https://jsr.io/@luca/flag".to_string()),
                 start_offset: 51,
                 end_offset: 57,
                 line: 2,
                 column: 34,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
@@ -240,10 +336,13 @@ mod tests {
                     version: "^1.0.1".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::Jsr,
+                    registry_url: Some("https://jsr.io/@luca/flag".to_string()),
                     start_offset: 51,
                     end_offset: 57,
                     line: 2,
                     column: 34,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -251,10 +350,13 @@ mod tests {
                     version: "1.0.0".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::Jsr,
+                    registry_url: Some("https://jsr.io/@std/path".to_string()),
                     start_offset: 92,
                     end_offset: 97,
                     line: 3,
                     column: 32,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
             ]
@@ -278,10 +380,13 @@ mod tests {
                 version: "^1.0.1".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::Jsr,
+                registry_url: Some("https://jsr.io/@luca/flag".to_string()),
                 start_offset: 51,
                 end_offset: 57,
                 line: 2,
                 column: 34,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }]
         );
@@ -303,15 +408,125 @@ mod tests {
                 version: "latest".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::Jsr,
+                registry_url: Some("https://jsr.io/@std/path".to_string()),
                 start_offset: 35,
                 end_offset: 48,
                 line: 2,
                 column: 18,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }]
         );
     }
 
+    #[test]
+    fn parse_extracts_npm_packages_when_node_modules_dir_is_enabled() {
+        let parser = DenoJsonParser::new();
+        let content = r#"{
+  "nodeModulesDir": "auto",
+  "imports": {
+    "@std/path": "jsr:@std/path@1.0.0",
+    "@types/node": "npm:@types/node@^20.0.0",
+    "preact/hooks": "npm:preact@10.19.0/hooks",
+    "chalk": "npm:chalk"
+  }
+}"#;
+        let result = parser.parse(content).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                PackageInfo {
+                    name: "@std/path".to_string(),
+                    version: "1.0.0".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::Jsr,
+                    registry_url: Some("https://jsr.io/@std/path".to_string()),
+                    start_offset: 77,
+                    end_offset: 82,
+                    line: 3,
+                    column: 32,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+                PackageInfo {
+                    name: "@types/node".to_string(),
+                    version: "^20.0.0".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::Npm,
+                    registry_url: Some("https://www.npmjs.com/package/@types/node".to_string()),
+                    start_offset: 121,
+                    end_offset: 128,
+                    line: 4,
+                    column: 36,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+                PackageInfo {
+                    name: "preact".to_string(),
+                    version: "10.19.0".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::Npm,
+                    registry_url: Some("https://www.npmjs.com/package/preact".to_string()),
+                    start_offset: 163,
+                    end_offset: 170,
+                    line: 5,
+                    column: 32,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+                PackageInfo {
+                    name: "chalk".to_string(),
+                    version: "latest".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::Npm,
+                    registry_url: Some("https://www.npmjs.com/package/chalk".to_string()),
+                    start_offset: 193,
+                    end_offset: 202,
+                    line: 6,
+                    column: 14,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(r#""nodeModulesDir": true"#, 1)]
+    #[case(r#""nodeModulesDir": "manual""#, 1)]
+    #[case(r#""unstable": ["kv", "npm"]"#, 1)]
+    #[case(r#""nodeModulesDir": false"#, 0)]
+    #[case(r#""nodeModulesDir": "none""#, 0)]
+    #[case(r#""lock": false"#, 0)]
+    fn parse_extracts_npm_packages_only_with_npm_resolution(
+        #[case] config: &str,
+        #[case] expected_npm_packages: usize,
+    ) {
+        let parser = DenoJsonParser::new();
+        let content = format!(
+            r#"{{
+  {config},
+  "imports": {{
+    "chalk": "npm:chalk@5.3.0"
+  }}
+}}"#
+        );
+        let result = parser.parse(&content).unwrap();
+
+        assert_eq!(
+            result
+                .iter()
+                .filter(|p| p.registry_type == RegistryType::Npm)
+                .count(),
+            expected_npm_packages
+        );
+    }
+
     #[test]
     fn parse_returns_empty_for_no_imports() {
         let parser = DenoJsonParser::new();
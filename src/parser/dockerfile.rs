@@ -0,0 +1,112 @@
+//! Dockerfile parser
+//!
+//! Extracts the image tags of `FROM` instructions, including every stage of
+//! multi-stage builds (`FROM golang:1.22 AS builder`) and images with a
+//! `--platform` flag. Images are resolved like in compose files: Docker Hub
+//! and ghcr.io images with a tag are reported, while `latest`, digests,
+//! `${ARG}` expansions, `scratch` and references to earlier stages, which
+//! have no tag, are skipped.
+
+use crate::parser::compose::image_tag_package;
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::PackageInfo;
+
+/// Parser for Dockerfiles
+#[derive(Default)]
+pub struct DockerfileParser;
+
+impl DockerfileParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Parser for DockerfileParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        let mut results = Vec::new();
+        let mut line_offset = 0;
+
+        for line in content.split_inclusive('\n') {
+            if let Some((image_ref, column)) = from_image(line) {
+                results.extend(image_tag_package(image_ref, line_offset + column, content));
+            }
+            line_offset += line.len();
+        }
+
+        Ok(results)
+    }
+}
+
+/// Image reference of a `FROM` instruction line, with its column
+fn from_image(line: &str) -> Option<(&str, usize)> {
+    let mut tokens = line.split_whitespace();
+    if !tokens.next()?.eq_ignore_ascii_case("FROM") {
+        return None;
+    }
+
+    // Flags such as `--platform=$BUILDPLATFORM` precede the image
+    let image = tokens.find(|token| !token.starts_with("--"))?;
+    let column = image.as_ptr() as usize - line.as_ptr() as usize;
+    Some((image, column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::types::RegistryType;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_extracts_every_stage_image() {
+        let content = "# syntax=docker/dockerfile:1\nFROM golang:1.22-alpine AS builder\nRUN go build\n\nfrom --platform=linux/amd64 ghcr.io/owner/base:v1.2.0\nCOPY --from=builder /app /app\n";
+
+        let result = DockerfileParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                PackageInfo {
+                    name: "library/golang".to_string(),
+                    version: "1.22-alpine".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::Docker,
+                    registry_url: Some("https://hub.docker.com/_/golang".to_string()),
+                    start_offset: 41,
+                    end_offset: 52,
+                    line: 1,
+                    column: 12,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+                PackageInfo {
+                    name: "ghcr.io/owner/base".to_string(),
+                    version: "v1.2.0".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::Docker,
+                    registry_url: Some("https://ghcr.io/owner/base".to_string()),
+                    start_offset: 125,
+                    end_offset: 131,
+                    line: 4,
+                    column: 47,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("FROM scratch")]
+    #[case("FROM builder")]
+    #[case("FROM node:latest")]
+    #[case("FROM python:3.12@sha256:abcdef")]
+    #[case("FROM python:${PYTHON_VERSION}")]
+    #[case("FROM mcr.microsoft.com/dotnet/sdk:8.0")]
+    #[case("# FROM python:3.12")]
+    #[case("RUN echo FROM python:3.12")]
+    fn parse_skips_images_without_checkable_tag(#[case] content: &str) {
+        assert!(DockerfileParser::new().parse(content).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! Gemfile parser for Ruby projects
+//!
+//! Each `gem` call with version requirements is reported, in any of its
+//! forms: `gem 'rails', '~> 7.1'`, `gem "devise", ">= 4.9"` or
+//! `gem("puma", "6.4.2")`. Multiple requirements
+//! (`gem 'pg', '>= 0.18', '< 2.0'`) are reported as one comma-separated
+//! version (`>= 0.18, < 2.0`) whose range spans all of them.
+//!
+//! Gems without a version, or whose source is a `git:`, `github:` or `path:`
+//! option, are skipped, as are options following the requirements
+//! (`require: false`, `platforms: :ruby`).
+
+use regex::Regex;
+
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
+
+/// Parser for Gemfile files
+pub struct GemfileParser {
+    /// Regex for the gem name: `gem 'rails'`, `gem("rails"`
+    gem_re: Regex,
+    /// Regex for a following requirement argument: `, '~> 7.1'`
+    requirement_re: Regex,
+}
+
+impl GemfileParser {
+    pub fn new() -> Self {
+        Self {
+            // Match: gem 'name' / gem "name" / gem('name'
+            gem_re: Regex::new(r#"^\s*gem\s*\(?\s*(?:'([^']+)'|"([^"]+)")"#).unwrap(),
+            // Match: , 'requirement' / , "requirement"
+            requirement_re: Regex::new(r#"^\s*,\s*(?:'([^']*)'|"([^"]*)")"#).unwrap(),
+        }
+    }
+}
+
+impl Default for GemfileParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for GemfileParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        // Byte offset of the start of each line. `str::lines` also strips the
+        // `\r` of CRLF line endings, so offsets can't be derived from line lengths.
+        let line_offsets: Vec<usize> = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(pos, _)| pos + 1))
+            .collect();
+
+        Ok(content
+            .lines()
+            .enumerate()
+            .filter_map(|(line_num, line)| {
+                self.parse_gem(line)
+                    .map(|gem| gem.into_package_info(line_num, line_offsets[line_num]))
+            })
+            .collect())
+    }
+}
+
+/// A `gem` call with its requirements
+struct GemRequirements<'a> {
+    name: &'a str,
+    requirements: Vec<&'a str>,
+    /// Column of the start of the first requirement
+    start: usize,
+    /// Column of the end of the last requirement
+    end: usize,
+}
+
+impl GemRequirements<'_> {
+    fn into_package_info(self, line: usize, line_offset: usize) -> PackageInfo {
+        PackageInfo {
+            name: self.name.to_string(),
+            version: self.requirements.join(", "),
+            commit_hash: None,
+            registry_type: RegistryType::RubyGems,
+            registry_url: Some(registry_type_to_url(RegistryType::RubyGems, self.name)),
+            start_offset: line_offset + self.start,
+            end_offset: line_offset + self.end,
+            line,
+            column: self.start,
+            section_name: None,
+            section_line: None,
+            extra_info: None,
+        }
+    }
+}
+
+impl GemfileParser {
+    /// Parse the `gem` call of a line, if it has version requirements
+    fn parse_gem<'a>(&self, line: &'a str) -> Option<GemRequirements<'a>> {
+        let caps = self.gem_re.captures(line)?;
+        let name = caps.get(1).or_else(|| caps.get(2))?.as_str();
+
+        let mut requirements = Vec::new();
+        let mut span: Option<(usize, usize)> = None;
+        let mut pos = caps.get(0)?.end();
+        while let Some(caps) = self.requirement_re.captures(&line[pos..]) {
+            let requirement = caps.get(1).or_else(|| caps.get(2))?;
+            let (start, end) = (pos + requirement.start(), pos + requirement.end());
+            span = Some((span.map_or(start, |(start, _)| start), end));
+            requirements.push(requirement.as_str().trim());
+            pos += caps.get(0)?.end();
+        }
+
+        let (start, end) = span?;
+        if requirements
+            .iter()
+            .any(|requirement| requirement.is_empty())
+        {
+            return None;
+        }
+        Some(GemRequirements {
+            name,
+            requirements,
+            start,
+            end,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_extracts_gem_with_position() {
+        let content = "source 'https://rubygems.org'\n\ngem 'rails', '~> 7.1'\n";
+
+        let result = GemfileParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![PackageInfo {
+                name: "rails".to_string(),
+                version: "~> 7.1".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::RubyGems,
+                registry_url: Some("https://rubygems.org/gems/rails".to_string()),
+                start_offset: 45,
+                end_offset: 51,
+                line: 2,
+                column: 14,
+                section_name: None,
+                section_line: None,
+                extra_info: None,
+            }]
+        );
+    }
+
+    #[rstest]
+    #[case(r#"gem "devise", ">= 4.9""#, ("devise", ">= 4.9"))]
+    #[case("gem('puma', '6.4.2')", ("puma", "6.4.2"))]
+    #[case("  gem 'pg', '>= 0.18', '< 2.0'", ("pg", ">= 0.18, < 2.0"))]
+    #[case("gem 'bootsnap', '>= 1.4.4', require: false", ("bootsnap", ">= 1.4.4"))]
+    #[case("gem 'tzinfo-data', '~>2.0' # Windows", ("tzinfo-data", "~>2.0"))]
+    fn parse_extracts_requirements(#[case] content: &str, #[case] expected: (&str, &str)) {
+        let result = GemfileParser::new().parse(content).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            (result[0].name.as_str(), result[0].version.as_str()),
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_spans_all_requirements() {
+        let content = "gem 'pg', '>= 0.18', '< 2.0'\n";
+
+        let package = &GemfileParser::new().parse(content).unwrap()[0];
+
+        assert_eq!(
+            &content[package.start_offset..package.end_offset],
+            ">= 0.18', '< 2.0"
+        );
+    }
+
+    #[rstest]
+    #[case("gem 'rails'")]
+    #[case("gem 'rails', github: 'rails/rails'")]
+    #[case("gem 'my_gem', path: '../my_gem'")]
+    #[case("gem 'rails', ''")]
+    #[case("# gem 'rails', '~> 7.1'")]
+    #[case("gemspec")]
+    #[case("ruby '3.3.0'")]
+    #[case("group :development, :test do")]
+    fn parse_skips_lines_without_requirements(#[case] content: &str) {
+        assert!(GemfileParser::new().parse(content).unwrap().is_empty());
+    }
+}
@@ -1,7 +1,11 @@
 //! GitHub Actions workflow file parser
+//!
+//! Only `uses:` references inside `steps` and `strategy.matrix.include`
+//! entries are extracted. Docker images (`container.image`,
+//! `services.*.image` and `docker://` steps) are skipped.
 
 use crate::parser::traits::{ParseError, Parser};
-use crate::parser::types::{ExtraInfo, PackageInfo, RegistryType};
+use crate::parser::types::{ExtraInfo, PackageInfo, RegistryType, registry_type_to_url};
 use tracing::warn;
 
 /// Parser for GitHub Actions workflow files (.github/workflows/*.yml)
@@ -80,6 +84,21 @@ impl GitHubActionsParser {
             return;
         }
 
+        // Matrix include entries may list action refs to test against:
+        // strategy: { matrix: { include: [{ uses: owner/repo@v1 }] } }
+        if node.kind() == "block_mapping_pair"
+            && let Some(key_node) = node.child_by_field_name("key")
+            && self.get_node_text(key_node, content) == "strategy"
+            && let Some(value_node) = node.child_by_field_name("value")
+        {
+            if let Some(matrix) = self.find_pair_value(value_node, content, "matrix")
+                && let Some(include) = self.find_pair_value(matrix, content, "include")
+            {
+                self.find_uses_in_steps(include, content, results);
+            }
+            return;
+        }
+
         // Recurse into children to find "steps" blocks
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
@@ -94,8 +113,8 @@ impl GitHubActionsParser {
         content: &str,
         results: &mut Vec<PackageInfo>,
     ) {
-        // Check if this is a block_mapping_pair with key "uses"
-        if node.kind() == "block_mapping_pair"
+        // Check if this is a block_mapping_pair or flow_pair with key "uses"
+        if matches!(node.kind(), "block_mapping_pair" | "flow_pair")
             && let Some(key_node) = node.child_by_field_name("key")
             && self.get_node_text(key_node, content) == "uses"
             && let Some(value_node) = node.child_by_field_name("value")
@@ -113,6 +132,25 @@ impl GitHubActionsParser {
         }
     }
 
+    /// Find the value of the first `key:` pair (block or flow style) under a node
+    fn find_pair_value<'a>(
+        &self,
+        node: tree_sitter::Node<'a>,
+        content: &str,
+        key: &str,
+    ) -> Option<tree_sitter::Node<'a>> {
+        if matches!(node.kind(), "block_mapping_pair" | "flow_pair")
+            && let Some(key_node) = node.child_by_field_name("key")
+            && self.get_node_text(key_node, content) == key
+        {
+            return node.child_by_field_name("value");
+        }
+
+        let mut cursor = node.walk();
+        node.children(&mut cursor)
+            .find_map(|child| self.find_pair_value(child, content, key))
+    }
+
     /// Get text content of a node, removing quotes if present
     fn get_node_text(&self, node: tree_sitter::Node, content: &str) -> String {
         let text = &content[node.byte_range()];
@@ -137,18 +175,29 @@ impl GitHubActionsParser {
     ///
     /// # Returns
     /// * `Some(PackageInfo)` - Parsed package info with name="owner/repo" and version
-    /// * `None` - If the value doesn't match expected format
+    /// * `None` - If the value doesn't match expected format, or refers to a
+    ///   Docker image (`docker://alpine:3.8`), which is not a GitHub release
     fn parse_uses_value(
         &self,
         value: &str,
         node: tree_sitter::Node,
         content: &str,
     ) -> Option<PackageInfo> {
+        // Docker container actions are image references, not owner/repo@ref
+        if value.starts_with("docker://") {
+            return None;
+        }
+
         // Parse: owner/repo@version or owner/repo/subdir@version
         let at_pos = value.find('@')?;
         let (repo_part, version) = value.split_at(at_pos);
         let version = &version[1..]; // Skip '@'
 
+        // Templated refs (`@${{ matrix.version }}`) can't be resolved statically
+        if version.contains("${{") {
+            return None;
+        }
+
         // Parse owner/repo (ignore subdirectories like actions/aws/ec2)
         let parts: Vec<&str> = repo_part.split('/').collect();
         if parts.len() < 2 {
@@ -220,15 +269,19 @@ impl GitHubActionsParser {
             (version.to_string(), None, None)
         };
 
+        let registry_url = Some(registry_type_to_url(RegistryType::GitHubActions, &name));
         Some(PackageInfo {
             name,
             version: final_version,
             commit_hash,
             registry_type: RegistryType::GitHubActions,
+            registry_url,
             start_offset: start_offset + version_start_in_value,
             end_offset,
             line: start_point.row,
             column: version_column,
+            section_name: None,
+            section_line: None,
             extra_info,
         })
     }
@@ -258,10 +311,13 @@ jobs:
                 version: "v4".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::GitHubActions,
+                registry_url: Some("https://github.com/actions/checkout".to_string()),
                 start_offset: 102,
                 end_offset: 104,
                 line: 6,
                 column: 31,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
@@ -289,10 +345,13 @@ jobs:
                     version: "v4".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::GitHubActions,
+                    registry_url: Some("https://github.com/actions/checkout".to_string()),
                     start_offset: 102,
                     end_offset: 104,
                     line: 6,
                     column: 31,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -300,10 +359,13 @@ jobs:
                     version: "v4".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::GitHubActions,
+                    registry_url: Some("https://github.com/actions/setup-node".to_string()),
                     start_offset: 138,
                     end_offset: 140,
                     line: 7,
                     column: 33,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -311,10 +373,13 @@ jobs:
                     version: "v3".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::GitHubActions,
+                    registry_url: Some("https://github.com/actions/cache".to_string()),
                     start_offset: 169,
                     end_offset: 171,
                     line: 8,
                     column: 28,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
             ]
@@ -341,10 +406,13 @@ jobs:
                 version: "8e5e7e5ab8b370d6c329ec480221332ada57f0ab".to_string(),
                 commit_hash: Some("8e5e7e5ab8b370d6c329ec480221332ada57f0ab".to_string()),
                 registry_type: RegistryType::GitHubActions,
+                registry_url: Some("https://github.com/actions/checkout".to_string()),
                 start_offset: 102,
                 end_offset: 142,
                 line: 6,
                 column: 31,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
@@ -370,10 +438,13 @@ jobs:
                 version: "v1".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::GitHubActions,
+                registry_url: Some("https://github.com/actions/aws".to_string()),
                 start_offset: 101,
                 end_offset: 103,
                 line: 6,
                 column: 30,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
@@ -399,10 +470,13 @@ jobs:
                 version: "v4".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::GitHubActions,
+                registry_url: Some("https://github.com/actions/checkout".to_string()),
                 start_offset: 102,
                 end_offset: 106,
                 line: 6,
                 column: 31,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
@@ -438,10 +512,13 @@ on: push
                     version: "v3.5.0".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::GitHubActions,
+                    registry_url: Some("https://github.com/actions/checkout".to_string()),
                     start_offset: 83,
                     end_offset: 89,
                     line: 4,
                     column: 31,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -449,10 +526,13 @@ on: push
                     version: "v4.1.0".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::GitHubActions,
+                    registry_url: Some("https://github.com/actions/setup-go".to_string()),
                     start_offset: 121,
                     end_offset: 127,
                     line: 5,
                     column: 31,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -460,10 +540,13 @@ on: push
                     version: "main".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::GitHubActions,
+                    registry_url: Some("https://github.com/actions/cache".to_string()),
                     start_offset: 156,
                     end_offset: 160,
                     line: 6,
                     column: 28,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
             ]
@@ -489,10 +572,13 @@ on: push
                     version: "v3".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::GitHubActions,
+                    registry_url: Some("https://github.com/actions/checkout".to_string()),
                     start_offset: 83,
                     end_offset: 85,
                     line: 4,
                     column: 31,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -500,10 +586,13 @@ on: push
                     version: "v4".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::GitHubActions,
+                    registry_url: Some("https://github.com/actions/setup-node".to_string()),
                     start_offset: 136,
                     end_offset: 138,
                     line: 5,
                     column: 33,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
             ]
@@ -533,15 +622,42 @@ jobs:
                 version: "v4".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::GitHubActions,
+                registry_url: Some("https://github.com/actions/setup-node".to_string()),
                 start_offset: 193,
                 end_offset: 195,
                 line: 9,
                 column: 33,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
     }
 
+    #[test]
+    fn parse_ignores_docker_image_references() {
+        let parser = GitHubActionsParser::new();
+        let content = r#"name: Test Workflow
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    container:
+      image: node:20-alpine
+    services:
+      db:
+        image: postgres:15
+    steps:
+      - uses: docker://alpine@sha256:1e014f84205d569a5cc3be4e108ca614055f7e21d11928946113ab3f36054801
+      - uses: docker://ghcr.io/owner/image:1.0.0
+      - uses: actions/checkout@v4
+"#;
+        let result = parser.parse(content).unwrap();
+
+        let names: Vec<&str> = result.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["actions/checkout"]);
+    }
+
     #[test]
     fn parse_returns_empty_for_buffer_without_actions() {
         let parser = GitHubActionsParser::new();
@@ -579,10 +695,13 @@ jobs:
                     version: "v2.62.25".to_string(),
                     commit_hash: Some("e30c5b8cfc4910a9f163907c8149ac1e54f1ab11".to_string()),
                     registry_type: RegistryType::GitHubActions,
+                    registry_url: Some("https://github.com/taiki-e/install-action".to_string()),
                     start_offset: 89,
                     end_offset: 129,
                     line: 4,
                     column: 37,
+                    section_name: None,
+                    section_line: None,
                     extra_info: Some(ExtraInfo::GitHubActions {
                         comment_text: "v2.62.25".to_string(),
                         comment_start_offset: 130,
@@ -594,10 +713,13 @@ jobs:
                     version: "v4.1.6".to_string(),
                     commit_hash: Some("a5ac7e51b41094c92402da3b24376905380afc29".to_string()),
                     registry_type: RegistryType::GitHubActions,
+                    registry_url: Some("https://github.com/actions/checkout".to_string()),
                     start_offset: 172,
                     end_offset: 212,
                     line: 5,
                     column: 31,
+                    section_name: None,
+                    section_line: None,
                     extra_info: Some(ExtraInfo::GitHubActions {
                         comment_text: "v4.1.6".to_string(),
                         comment_start_offset: 213,
@@ -626,10 +748,13 @@ jobs:
                 version: "8e5e7e5ab8b370d6c329ec480221332ada57f0ab".to_string(),
                 commit_hash: Some("8e5e7e5ab8b370d6c329ec480221332ada57f0ab".to_string()),
                 registry_type: RegistryType::GitHubActions,
+                registry_url: Some("https://github.com/actions/checkout".to_string()),
                 start_offset: 83,
                 end_offset: 123,
                 line: 4,
                 column: 31,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
@@ -663,10 +788,13 @@ jobs:
                 version: "v4.1.6".to_string(),
                 commit_hash: Some("8e5e7e5ab8b370d6c329ec480221332ada57f0ab".to_string()),
                 registry_type: RegistryType::GitHubActions,
+                registry_url: Some("https://github.com/actions/checkout".to_string()),
                 start_offset: 83,
                 end_offset: 123,
                 line: 4,
                 column: 31,
+                section_name: None,
+                section_line: None,
                 extra_info: Some(ExtraInfo::GitHubActions {
                     comment_text: "v4.1.6".to_string(),
                     comment_start_offset: 124,
@@ -694,12 +822,47 @@ jobs:
                 version: "v4".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::GitHubActions,
+                registry_url: Some("https://github.com/actions/checkout".to_string()),
                 start_offset: 83,
                 end_offset: 85,
                 line: 4,
                 column: 31,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
     }
+
+    #[test]
+    fn parse_extracts_uses_from_matrix_include() {
+        let parser = GitHubActionsParser::new();
+        let content = r#"jobs:
+  test:
+    strategy:
+      matrix:
+        os: [ubuntu-latest]
+        include:
+          - uses: actions/setup-node@v3
+          - { uses: actions/setup-node@v4 }
+    runs-on: ${{ matrix.os }}
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@${{ matrix.version }}
+"#;
+        let result = parser.parse(content).unwrap();
+
+        let packages: Vec<(&str, &str, usize, usize)> = result
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str(), p.line, p.column))
+            .collect();
+        assert_eq!(
+            packages,
+            vec![
+                ("actions/setup-node", "v3", 6, 37),
+                ("actions/setup-node", "v4", 7, 39),
+                ("actions/checkout", "v4", 10, 31),
+            ]
+        );
+    }
 }
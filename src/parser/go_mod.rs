@@ -2,8 +2,11 @@
 //!
 //! Parses go.mod files to extract module dependencies.
 //! Supports both single-line require directives and require blocks.
+//! The `go` directive is extracted as the [`GO_TOOLCHAIN_PACKAGE_NAME`]
+//! package, checked against the Go releases.
 //!
 //! Format examples:
+//! - Go version: `go 1.21`
 //! - Single: `require golang.org/x/text v0.14.0`
 //! - Block:
 //!   ```text
@@ -16,16 +19,79 @@
 use regex::Regex;
 
 use crate::parser::traits::{ParseError, Parser};
-use crate::parser::types::{PackageInfo, RegistryType};
+use crate::parser::types::{ExtraInfo, PackageInfo, RegistryType, registry_type_to_url};
+
+/// Package name of the Go toolchain required by the `go` directive
+pub const GO_TOOLCHAIN_PACKAGE_NAME: &str = "go";
+
+/// Commit of a pseudo-version, as [`ExtraInfo::GoPseudoVersion`]
+///
+/// Pseudo-versions end in a `YYYYMMDDHHMMSS` UTC commit timestamp and a
+/// 12-character commit hash, after the base version and `-`
+/// (`v0.0.0-20210101000000-abcdef123456`), `-0.` (`v1.2.4-0.2021...`) or
+/// `-pre.0.` (`v1.2.4-pre.0.2021...`).
+pub fn pseudo_version_info(version: &str) -> Option<ExtraInfo> {
+    let version = version.strip_suffix("+incompatible").unwrap_or(version);
+    let (rest, commit_hash) = version.rsplit_once('-')?;
+    let (_, commit_timestamp) = rest.rsplit_once(['-', '.'])?;
+
+    let is_timestamp =
+        commit_timestamp.len() == 14 && commit_timestamp.chars().all(|c| c.is_ascii_digit());
+    let is_hash = commit_hash.len() == 12 && commit_hash.chars().all(|c| c.is_ascii_hexdigit());
+    (is_timestamp && is_hash).then(|| ExtraInfo::GoPseudoVersion {
+        commit_timestamp: commit_timestamp.to_string(),
+        commit_hash: commit_hash.to_string(),
+    })
+}
+
+/// `YYYY-MM-DD` date of a pseudo-version commit timestamp
+pub fn commit_date(commit_timestamp: &str) -> String {
+    format!(
+        "{}-{}-{}",
+        &commit_timestamp[0..4],
+        &commit_timestamp[4..6],
+        &commit_timestamp[6..8]
+    )
+}
+
+/// Block of a go.mod file the parser is in
+///
+/// Any block start switches the state, so when an edited file leaves a
+/// block unclosed, the entries of the following block are never mistaken
+/// for requirements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockState {
+    Normal,
+    InRequireBlock,
+    InRetractBlock,
+    InExcludeBlock,
+    InReplaceBlock,
+    /// Other directive blocks, e.g. `tool (` or `godebug (`
+    InOtherBlock,
+}
+
+impl BlockState {
+    fn for_directive(directive: &str) -> Self {
+        match directive {
+            "require" => Self::InRequireBlock,
+            "retract" => Self::InRetractBlock,
+            "exclude" => Self::InExcludeBlock,
+            "replace" => Self::InReplaceBlock,
+            _ => Self::InOtherBlock,
+        }
+    }
+}
 
 /// Parser for go.mod files
 pub struct GoModParser {
     /// Regex for single-line require: `require module/path v1.2.3`
     single_require_re: Regex,
-    /// Regex for require block start: `require (`
+    /// Regex for directive block start: `require (`, `retract (`, ...
     block_start_re: Regex,
     /// Regex for require spec inside block: `module/path v1.2.3`
     require_spec_re: Regex,
+    /// Regex for the minimum Go version: `go 1.21`
+    go_directive_re: Regex,
 }
 
 impl GoModParser {
@@ -33,10 +99,12 @@ impl GoModParser {
         Self {
             // Match: require module/path v1.2.3 [// comment]
             single_require_re: Regex::new(r"^require\s+(\S+)\s+(v[^\s]+)(?:\s*//.*)?$").unwrap(),
-            // Match: require (
-            block_start_re: Regex::new(r"^require\s*\(\s*$").unwrap(),
+            // Match: require ( / retract ( / ...
+            block_start_re: Regex::new(r"^(\w+)\s*\(\s*$").unwrap(),
             // Match: module/path v1.2.3 [// comment]
             require_spec_re: Regex::new(r"^\s*(\S+)\s+(v[^\s]+)(?:\s*//.*)?$").unwrap(),
+            // Match: go 1.21 / go 1.21.0 [// comment]
+            go_directive_re: Regex::new(r"^go\s+(\d+\.\d+(?:\.\d+)?)(?:\s*//.*)?$").unwrap(),
         }
     }
 }
@@ -50,7 +118,14 @@ impl Default for GoModParser {
 impl Parser for GoModParser {
     fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
         let mut results = Vec::new();
-        let mut in_require_block = false;
+        let mut state = BlockState::Normal;
+        let mut block_start_line = 0;
+
+        // Byte offset of the start of each line. `str::lines` also strips the
+        // `\r` of CRLF line endings, so offsets can't be derived from line lengths.
+        let line_offsets: Vec<usize> = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(pos, _)| pos + 1))
+            .collect();
 
         for (line_num, line) in content.lines().enumerate() {
             let trimmed = line.trim();
@@ -61,66 +136,86 @@ impl Parser for GoModParser {
             }
 
             // Check for block end
-            if in_require_block && trimmed == ")" {
-                in_require_block = false;
+            if state != BlockState::Normal && trimmed == ")" {
+                state = BlockState::Normal;
                 continue;
             }
 
             // Check for block start
-            if self.block_start_re.is_match(trimmed) {
-                in_require_block = true;
+            if let Some(caps) = self.block_start_re.captures(trimmed) {
+                state = BlockState::for_directive(&caps[1]);
+                block_start_line = line_num;
                 continue;
             }
 
             // Parse require spec
-            if in_require_block {
+            if state == BlockState::InRequireBlock {
                 if let Some(caps) = self.require_spec_re.captures(line) {
                     let module_path = caps.get(1).unwrap().as_str();
                     let version_match = caps.get(2).unwrap();
                     let version = version_match.as_str();
 
-                    // Calculate byte offset for version
-                    let line_start = content
-                        .lines()
-                        .take(line_num)
-                        .map(|l| l.len() + 1)
-                        .sum::<usize>();
-                    let version_start = line_start + version_match.start();
-                    let version_end = line_start + version_match.end();
-
-                    // Calculate column (byte offset within line)
+                    // The capture position is the version token itself, so
+                    // it can't match a look-alike inside the module path
                     let column = version_match.start();
+                    let version_start = line_offsets[line_num] + column;
+                    let version_end = version_start + version.len();
 
                     results.push(PackageInfo {
                         name: module_path.to_string(),
                         version: version.to_string(),
                         commit_hash: None,
                         registry_type: RegistryType::GoProxy,
+                        registry_url: Some(registry_type_to_url(
+                            RegistryType::GoProxy,
+                            module_path,
+                        )),
                         start_offset: version_start,
                         end_offset: version_end,
                         line: line_num,
                         column,
-                        extra_info: None,
+                        section_name: Some("require".to_string()),
+                        section_line: Some(block_start_line),
+                        extra_info: pseudo_version_info(version),
                     });
                 }
+            } else if state != BlockState::Normal {
+                // Entries of retract, exclude, replace and other blocks
+                continue;
+            } else if let Some(caps) = self.go_directive_re.captures(trimmed) {
+                let version_match = caps.get(1).unwrap();
+                let version = version_match.as_str();
+
+                let indent = line.len() - line.trim_start().len();
+                let column = indent + version_match.start();
+                let version_start = line_offsets[line_num] + column;
+
+                results.push(PackageInfo {
+                    name: GO_TOOLCHAIN_PACKAGE_NAME.to_string(),
+                    version: version.to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::GoToolchain,
+                    registry_url: Some(registry_type_to_url(
+                        RegistryType::GoToolchain,
+                        GO_TOOLCHAIN_PACKAGE_NAME,
+                    )),
+                    start_offset: version_start,
+                    end_offset: version_start + version.len(),
+                    line: line_num,
+                    column,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                });
             } else if let Some(caps) = self.single_require_re.captures(trimmed) {
                 let module_path = caps.get(1).unwrap().as_str();
                 let version_match = caps.get(2).unwrap();
                 let version = version_match.as_str();
 
-                // Calculate byte offset for version
-                let line_start = content
-                    .lines()
-                    .take(line_num)
-                    .map(|l| l.len() + 1)
-                    .sum::<usize>();
-                // Find actual position in the original line (not trimmed)
-                let require_pos = line.find("require").unwrap_or(0);
-                let version_pos_in_line = line[require_pos..]
-                    .find(version)
-                    .map(|p| require_pos + p)
-                    .unwrap_or(0);
-                let version_start = line_start + version_pos_in_line;
+                // Captures are relative to the trimmed line
+                let indent = line.len() - line.trim_start().len();
+                let column = indent + version_match.start();
+                let version_start = line_offsets[line_num] + column;
                 let version_end = version_start + version.len();
 
                 results.push(PackageInfo {
@@ -128,11 +223,14 @@ impl Parser for GoModParser {
                     version: version.to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::GoProxy,
+                    registry_url: Some(registry_type_to_url(RegistryType::GoProxy, module_path)),
                     start_offset: version_start,
                     end_offset: version_end,
                     line: line_num,
-                    column: version_pos_in_line,
-                    extra_info: None,
+                    column,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: pseudo_version_info(version),
                 });
             }
         }
@@ -144,6 +242,7 @@ impl Parser for GoModParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     #[test]
     fn parse_extracts_single_require() {
@@ -155,10 +254,10 @@ go 1.21
 require golang.org/x/text v0.14.0
 "#;
         let result = parser.parse(content).unwrap();
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].name, "golang.org/x/text");
-        assert_eq!(result[0].version, "v0.14.0");
-        assert_eq!(result[0].registry_type, RegistryType::GoProxy);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[1].name, "golang.org/x/text");
+        assert_eq!(result[1].version, "v0.14.0");
+        assert_eq!(result[1].registry_type, RegistryType::GoProxy);
     }
 
     #[test]
@@ -174,11 +273,14 @@ require (
 )
 "#;
         let result = parser.parse(content).unwrap();
-        assert_eq!(result.len(), 2);
-        assert_eq!(result[0].name, "golang.org/x/text");
-        assert_eq!(result[0].version, "v0.14.0");
-        assert_eq!(result[1].name, "golang.org/x/net");
-        assert_eq!(result[1].version, "v0.20.0");
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[1].name, "golang.org/x/text");
+        assert_eq!(result[1].version, "v0.14.0");
+        assert_eq!(result[2].name, "golang.org/x/net");
+        assert_eq!(result[2].version, "v0.20.0");
+        assert_eq!(result[0].section_name, None);
+        assert_eq!(result[1].section_name.as_deref(), Some("require"));
+        assert_eq!(result[1].section_line, Some(4));
     }
 
     #[test]
@@ -231,17 +333,97 @@ require github.com/some/repo v0.0.0-20210101000000-abcdef123456
         let result = parser.parse(content).unwrap();
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].version, "v0.0.0-20210101000000-abcdef123456");
+        assert_eq!(
+            result[0].extra_info,
+            Some(ExtraInfo::GoPseudoVersion {
+                commit_timestamp: "20210101000000".to_string(),
+                commit_hash: "abcdef123456".to_string(),
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "v0.0.0-20210101000000-abcdef123456",
+        Some(("20210101000000", "abcdef123456"))
+    )]
+    #[case(
+        "v1.1.3-0.20240916144458-20a13a1f6b7c",
+        Some(("20240916144458", "20a13a1f6b7c"))
+    )]
+    #[case(
+        "v2.0.1-rc.0.20230102030405-0123456789ab+incompatible",
+        Some(("20230102030405", "0123456789ab"))
+    )]
+    #[case("v1.2.3", None)]
+    #[case("v1.2.3-rc.1", None)]
+    #[case("v0.0.0-20210101000000-abc123", None)]
+    fn pseudo_version_info_extracts_commit(
+        #[case] version: &str,
+        #[case] expected: Option<(&str, &str)>,
+    ) {
+        let expected = expected.map(
+            |(commit_timestamp, commit_hash)| ExtraInfo::GoPseudoVersion {
+                commit_timestamp: commit_timestamp.to_string(),
+                commit_hash: commit_hash.to_string(),
+            },
+        );
+        assert_eq!(pseudo_version_info(version), expected);
+    }
+
+    #[test]
+    fn commit_date_formats_timestamp_as_date() {
+        assert_eq!(commit_date("20210101000000"), "2021-01-01");
+        assert_eq!(commit_date("20240916144458"), "2024-09-16");
     }
 
     #[test]
-    fn parse_returns_empty_for_no_requires() {
+    fn parse_returns_only_go_directive_for_no_requires() {
         let parser = GoModParser::new();
         let content = r#"module example.com/myapp
 
 go 1.21
 "#;
         let result = parser.parse(content).unwrap();
-        assert!(result.is_empty());
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].registry_type, RegistryType::GoToolchain);
+    }
+
+    #[test]
+    fn parse_extracts_go_directive_as_go_toolchain() {
+        let parser = GoModParser::new();
+        let content = "module example.com/myapp\n\ngo 1.21.5 // minimum\n\ntoolchain go1.22.0\n";
+        let result = parser.parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![PackageInfo {
+                name: "go".to_string(),
+                version: "1.21.5".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::GoToolchain,
+                registry_url: Some("https://go.dev/doc/devel/release".to_string()),
+                start_offset: 29,
+                end_offset: 35,
+                line: 2,
+                column: 3,
+                section_name: None,
+                section_line: None,
+                extra_info: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_ignores_go_lines_inside_blocks() {
+        let parser = GoModParser::new();
+        let content = r#"module example.com/myapp
+
+godebug (
+	go 1.21
+)
+"#;
+        assert!(parser.parse(content).unwrap().is_empty());
     }
 
     #[test]
@@ -278,8 +460,8 @@ replace (
 )
 "#;
         let result = parser.parse(content).unwrap();
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].name, "golang.org/x/text");
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[1].name, "golang.org/x/text");
     }
 
     #[test]
@@ -339,8 +521,86 @@ exclude golang.org/x/crypto v1.0.0
 retract v0.0.1
 "#;
         let result = parser.parse(content).unwrap();
-        assert_eq!(result.len(), 2);
-        assert_eq!(result[0].name, "golang.org/x/text");
-        assert_eq!(result[1].name, "golang.org/x/net");
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].name, "go");
+        assert_eq!(result[1].name, "golang.org/x/text");
+        assert_eq!(result[2].name, "golang.org/x/net");
+    }
+
+    #[test]
+    fn parse_stops_require_block_at_next_block_start_when_unclosed() {
+        let parser = GoModParser::new();
+        // The require block is missing its `)` while the file is being edited
+        let content = r#"module example.com/myapp
+
+require (
+	golang.org/x/text v0.14.0 // => replaced below
+
+exclude (
+	golang.org/x/crypto v0.1.0
+)
+
+retract (
+	example.com/fake v1.0.1 // published by mistake
+)
+
+require golang.org/x/net v0.20.0
+"#;
+        let result = parser.parse(content).unwrap();
+        let names: Vec<&str> = result.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["golang.org/x/text", "golang.org/x/net"]);
+    }
+
+    #[test]
+    fn parse_skips_entries_of_unclosed_replace_block() {
+        let parser = GoModParser::new();
+        let content = r#"module example.com/myapp
+
+replace (
+	golang.org/x/text v0.13.0 => golang.org/x/text v0.14.0
+	golang.org/x/net v0.19.0
+require golang.org/x/sync v0.5.0
+"#;
+        assert!(parser.parse(content).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_locates_version_when_it_also_appears_in_module_path() {
+        let parser = GoModParser::new();
+        let content = "module example.com/myapp\n\n  require example.com/v1.2.0/mod v1.2.0\n";
+        let result = parser.parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![PackageInfo {
+                name: "example.com/v1.2.0/mod".to_string(),
+                version: "v1.2.0".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::GoProxy,
+                registry_url: Some("https://pkg.go.dev/example.com/v1.2.0/mod".to_string()),
+                start_offset: 59,
+                end_offset: 65,
+                line: 2,
+                column: 33,
+                section_name: None,
+                section_line: None,
+                extra_info: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_calculates_offsets_with_crlf_line_endings() {
+        let parser = GoModParser::new();
+        let content =
+            "module example.com/myapp\r\n\r\nrequire (\r\n\tgolang.org/x/text v0.14.0\r\n)\r\n";
+        let result = parser.parse(content).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            &content[result[0].start_offset..result[0].end_offset],
+            "v0.14.0"
+        );
+        assert_eq!((result[0].line, result[0].column), (3, 19));
     }
 }
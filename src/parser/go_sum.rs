@@ -0,0 +1,77 @@
+//! go.sum parser
+//!
+//! go.sum records the checksums of module versions verified against the
+//! checksum database. Each line is `module version hash`, where the version
+//! may carry a `/go.mod` suffix when only the module's go.mod was hashed:
+//!
+//! ```text
+//! golang.org/x/text v0.14.0 h1:ScX5w1eTa3QqT8oi6+ziP7dTV1S2+ALU0bI+0zXKWiQ=
+//! golang.org/x/text v0.14.0/go.mod h1:18ZOQIKpY8NJVqYksKHtTdi31H5itFRjB5/qKTNYzSU=
+//! ```
+//!
+//! Unlike the manifest parsers, this does not implement [`Parser`]: go.sum is
+//! only used to cross-reference the requirements of the go.mod next to it.
+//!
+//! [`Parser`]: crate::parser::traits::Parser
+
+use std::collections::HashSet;
+
+/// Parser for go.sum files
+pub struct GoSumParser;
+
+impl GoSumParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse go.sum content into the set of `(module, version)` pairs it covers
+    ///
+    /// Malformed lines are skipped.
+    pub fn parse(&self, content: &str) -> HashSet<(String, String)> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let (Some(module), Some(version), Some(_hash), None) =
+                    (fields.next(), fields.next(), fields.next(), fields.next())
+                else {
+                    return None;
+                };
+                let version = version.strip_suffix("/go.mod").unwrap_or(version);
+                Some((module.to_string(), version.to_string()))
+            })
+            .collect()
+    }
+}
+
+impl Default for GoSumParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_collects_module_versions() {
+        let parser = GoSumParser::new();
+        let content = "\
+golang.org/x/text v0.14.0 h1:ScX5w1eTa3QqT8oi6+ziP7dTV1S2+ALU0bI+0zXKWiQ=
+golang.org/x/text v0.14.0/go.mod h1:18ZOQIKpY8NJVqYksKHtTdi31H5itFRjB5/qKTNYzSU=
+golang.org/x/net v0.20.0/go.mod h1:z8BVo6PvndSri0LbOE3hAn0apkU+1YvI6E70E9jsnlY=
+
+not a valid line
+";
+        let result = parser.parse(content);
+
+        assert_eq!(
+            result,
+            HashSet::from([
+                ("golang.org/x/text".to_string(), "v0.14.0".to_string()),
+                ("golang.org/x/net".to_string(), "v0.20.0".to_string()),
+            ])
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! go.work parser for Go workspaces
+//!
+//! go.work files share the directive grammar of go.mod, so parsing is
+//! delegated to [`GoModParser`]: `require` directives and blocks are checked
+//! against the Go proxy and the `go` directive against the Go releases.
+//! `use` directives point to local module directories and `replace`
+//! directives override the versions of workspace modules, so both are
+//! skipped.
+//!
+//! Format example:
+//! ```text
+//! go 1.22
+//!
+//! use (
+//!     ./api
+//!     ./web
+//! )
+//!
+//! require golang.org/x/text v0.14.0
+//!
+//! replace golang.org/x/net v0.19.0 => ../net
+//! ```
+
+use crate::parser::go_mod::GoModParser;
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::PackageInfo;
+
+/// Parser for go.work files
+#[derive(Default)]
+pub struct GoWorkParser {
+    go_mod: GoModParser,
+}
+
+impl GoWorkParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Parser for GoWorkParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        self.go_mod.parse(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::types::RegistryType;
+
+    #[test]
+    fn parse_extracts_requires_and_skips_use_and_replace() {
+        let content = r#"go 1.22
+
+use (
+	./api
+	./web
+)
+use ./tools
+
+require (
+	golang.org/x/text v0.14.0
+)
+require golang.org/x/sync v0.5.0
+
+replace golang.org/x/net v0.19.0 => ../net
+
+replace (
+	golang.org/x/crypto v0.16.0 => golang.org/x/crypto v0.17.0
+)
+"#;
+
+        let result: Vec<(String, String, RegistryType)> = GoWorkParser::new()
+            .parse(content)
+            .unwrap()
+            .into_iter()
+            .map(|info| (info.name, info.version, info.registry_type))
+            .collect();
+
+        assert_eq!(
+            result,
+            vec![
+                (
+                    "go".to_string(),
+                    "1.22".to_string(),
+                    RegistryType::GoToolchain
+                ),
+                (
+                    "golang.org/x/text".to_string(),
+                    "v0.14.0".to_string(),
+                    RegistryType::GoProxy
+                ),
+                (
+                    "golang.org/x/sync".to_string(),
+                    "v0.5.0".to_string(),
+                    RegistryType::GoProxy
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_locates_require_version() {
+        let content = "go 1.22\n\nuse ./api\n\nrequire (\n\tgolang.org/x/text v0.14.0\n)\n";
+
+        let result = GoWorkParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            result[1],
+            PackageInfo {
+                name: "golang.org/x/text".to_string(),
+                version: "v0.14.0".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::GoProxy,
+                registry_url: Some("https://pkg.go.dev/golang.org/x/text".to_string()),
+                start_offset: 49,
+                end_offset: 56,
+                line: 5,
+                column: 19,
+                section_name: Some("require".to_string()),
+                section_line: Some(4),
+                extra_info: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_returns_only_go_directive_for_use_only_workspace() {
+        let content = "go 1.22\n\nuse ./api\n";
+
+        let result = GoWorkParser::new().parse(content).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].registry_type, RegistryType::GoToolchain);
+    }
+}
@@ -3,30 +3,72 @@
 //! - types.rs: Common types (PackageInfo, RegistryType)
 //! - github_actions.rs: GitHub Actions workflow parser
 //! - package_json.rs: package.json parser
+//! - package_lock.rs: package-lock.json parser
+//! - bun_lock.rs: bun.lock parser
 //! - cargo_toml.rs: Cargo.toml parser
+//! - cargo_lock.rs: Cargo.lock parser
 //! - go_mod.rs: go.mod parser
+//! - go_sum.rs: go.sum checksum entries
+//! - go_work.rs: go.work parser
 //! - pnpm_workspace.rs: pnpm-workspace.yaml catalog parser
 //! - deno_json.rs: deno.json parser
 //! - pyproject_toml.rs: pyproject.toml parser
+//! - poetry_lock.rs: poetry.lock parser
+//! - requirements_txt.rs: pip requirements.txt parser
+//! - conda_env.rs: Conda environment.yml parser
+//! - conda_lock.rs: conda-lock.yml parser
+//! - nvmrc.rs: .nvmrc / .node-version parser
+//! - rust_toolchain.rs: rust-toolchain.toml parser
+//! - gemfile.rs: Gemfile parser
+//! - dockerfile.rs: Dockerfile FROM parser
+//! - pubspec_yaml.rs: Dart pubspec.yaml parser
 
+pub mod bun_lock;
+pub mod cargo_lock;
 pub mod cargo_toml;
 pub mod compose;
+pub mod conda_env;
+pub mod conda_lock;
 pub mod deno_json;
+pub mod dockerfile;
+pub mod gemfile;
 pub mod github_actions;
 pub mod go_mod;
+pub mod go_sum;
+pub mod go_work;
+pub mod nvmrc;
 pub mod package_json;
+pub mod package_lock;
 pub mod pnpm_workspace;
+pub mod poetry_lock;
+pub mod pubspec_yaml;
 pub mod pyproject_toml;
+pub mod requirements_txt;
+pub mod rust_toolchain;
 pub mod traits;
 pub mod types;
 
+pub use bun_lock::BunLockParser;
+pub use cargo_lock::CargoLockParser;
 pub use cargo_toml::CargoTomlParser;
 pub use compose::ComposeParser;
+pub use conda_env::CondaEnvParser;
+pub use conda_lock::CondaLockParser;
 pub use deno_json::DenoJsonParser;
+pub use dockerfile::DockerfileParser;
+pub use gemfile::GemfileParser;
 pub use github_actions::GitHubActionsParser;
 pub use go_mod::GoModParser;
+pub use go_sum::GoSumParser;
+pub use go_work::GoWorkParser;
+pub use nvmrc::NvmrcParser;
 pub use package_json::PackageJsonParser;
+pub use package_lock::PackageLockParser;
 pub use pnpm_workspace::PnpmWorkspaceParser;
+pub use poetry_lock::PoetryLockParser;
+pub use pubspec_yaml::PubspecYamlParser;
 pub use pyproject_toml::PyprojectTomlParser;
+pub use requirements_txt::RequirementsTxtParser;
+pub use rust_toolchain::RustToolchainParser;
 pub use traits::{ParseError, Parser};
 pub use types::{PackageInfo, RegistryType};
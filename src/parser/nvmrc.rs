@@ -0,0 +1,117 @@
+//! .nvmrc / .node-version parser
+//!
+//! Both files hold a single Node.js version (`18.17.0`, `v20`, `20.11`).
+//! Aliases such as `lts/hydrogen`, `lts/*` or `node` are skipped for now:
+//! they always resolve to a current release, so there is nothing to check.
+
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
+
+/// Package name reported for the pinned Node.js version
+pub const NODE_PACKAGE_NAME: &str = "node";
+
+/// Parser for .nvmrc and .node-version files
+pub struct NvmrcParser;
+
+impl NvmrcParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for NvmrcParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for NvmrcParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        let version = content.trim();
+        if !is_version_like(version) {
+            return Ok(Vec::new());
+        }
+
+        let start_offset = content.len() - content.trim_start().len();
+        let before = &content[..start_offset];
+        let line = before.matches('\n').count();
+        let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1);
+
+        Ok(vec![PackageInfo {
+            name: NODE_PACKAGE_NAME.to_string(),
+            version: version.to_string(),
+            commit_hash: None,
+            registry_type: RegistryType::NodeRelease,
+            registry_url: Some(registry_type_to_url(
+                RegistryType::NodeRelease,
+                NODE_PACKAGE_NAME,
+            )),
+            start_offset,
+            end_offset: start_offset + version.len(),
+            line,
+            column,
+            section_name: None,
+            section_line: None,
+            extra_info: None,
+        }])
+    }
+}
+
+/// Whether the content is a (possibly partial) version like `v18`, `18.17` or `18.17.0`
+fn is_version_like(version: &str) -> bool {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let parts: Vec<&str> = version.split('.').collect();
+    parts.len() <= 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_extracts_node_version() {
+        let content = "\n  v18.17.0\n";
+
+        let result = NvmrcParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![PackageInfo {
+                name: "node".to_string(),
+                version: "v18.17.0".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::NodeRelease,
+                registry_url: Some("https://nodejs.org/en/about/previous-releases".to_string()),
+                start_offset: 3,
+                end_offset: 11,
+                line: 1,
+                column: 2,
+                section_name: None,
+                section_line: None,
+                extra_info: None,
+            }]
+        );
+    }
+
+    #[rstest]
+    #[case("18.17.0\n", Some("18.17.0"))]
+    #[case("20", Some("20"))]
+    #[case("v20.11", Some("v20.11"))]
+    #[case("lts/hydrogen\n", None)]
+    #[case("lts/*", None)]
+    #[case("node", None)]
+    #[case("18.17.0-rc.1", None)]
+    #[case("", None)]
+    fn parse_returns_expected_version(#[case] content: &str, #[case] expected: Option<&str>) {
+        let result = NvmrcParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            result.first().map(|package| package.version.as_str()),
+            expected
+        );
+    }
+}
@@ -1,7 +1,10 @@
 //! package.json parser
 
-use crate::parser::traits::{ParseError, Parser};
-use crate::parser::types::{PackageInfo, RegistryType};
+use crate::parser::pnpm_workspace::catalog_ref_name;
+use crate::parser::traits::{ParseError, ParseOutput, Parser};
+use crate::parser::types::{
+    DocumentMetadata, ExtraInfo, PackageInfo, RegistryType, registry_type_to_url,
+};
 use tracing::warn;
 
 /// Parser for package.json files
@@ -21,33 +24,146 @@ impl Default for PackageJsonParser {
 
 impl Parser for PackageJsonParser {
     fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
-        let mut parser = tree_sitter::Parser::new();
-        let language = tree_sitter_json::LANGUAGE;
-        parser.set_language(&language.into()).map_err(|e| {
-            warn!("Failed to set JSON language for tree-sitter: {}", e);
-            ParseError::TreeSitter(e.to_string())
-        })?;
-
-        let tree = parser.parse(content, None).ok_or_else(|| {
-            warn!("Failed to parse JSON content");
-            ParseError::ParseFailed("Failed to parse JSON".to_string())
-        })?;
+        self.parse_document(content).map(|output| output.packages)
+    }
 
+    fn parse_document(&self, content: &str) -> Result<ParseOutput, ParseError> {
+        let tree = Self::parse_tree(content)?;
         let root = tree.root_node();
-        let mut results = Vec::new();
+        let mut output = ParseOutput::default();
 
         // Find the root object
         if let Some(document) = root.child(0)
             && document.kind() == "object"
         {
-            self.extract_dependencies(document, content, &mut results);
+            self.extract_dependencies(document, content, &mut output.packages);
+            output.metadata = DocumentMetadata {
+                is_esm: self
+                    .root_string_field(document, content, "type")
+                    .is_some_and(|module_type| module_type == "module"),
+            };
         }
 
-        Ok(results)
+        Ok(output)
     }
 }
 
 impl PackageJsonParser {
+    /// String value of a field of the root object
+    fn root_string_field(
+        &self,
+        document: tree_sitter::Node,
+        content: &str,
+        field: &str,
+    ) -> Option<String> {
+        let mut cursor = document.walk();
+        document.children(&mut cursor).find_map(|child| {
+            let key_node = child.child_by_field_name("key")?;
+            let value_node = child.child_by_field_name("value")?;
+            (value_node.kind() == "string" && self.get_string_value(key_node, content) == field)
+                .then(|| self.get_string_value(value_node, content))
+        })
+    }
+
+    /// Extract the package itself as a synthetic entry
+    ///
+    /// The entry carries the package's own `version` and its
+    /// [`ExtraInfo::PackageJsonMeta`]. It is not returned by [`Parser::parse`],
+    /// so it is never checked as a dependency. Returns None unless the root
+    /// object has both a string `name` and `version`.
+    pub fn parse_self_package(&self, content: &str) -> Option<PackageInfo> {
+        let tree = Self::parse_tree(content).ok()?;
+        let document = tree.root_node().child(0)?;
+        if document.kind() != "object" {
+            return None;
+        }
+
+        let mut name = None;
+        let mut version_node = None;
+        let mut private = false;
+        let mut cursor = document.walk();
+        for child in document.children(&mut cursor) {
+            let (Some(key_node), Some(value_node)) = (
+                child.child_by_field_name("key"),
+                child.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+
+            match self.get_string_value(key_node, content).as_str() {
+                "name" if value_node.kind() == "string" => {
+                    name = Some(self.get_string_value(value_node, content));
+                }
+                "version" if value_node.kind() == "string" => version_node = Some(value_node),
+                "private" => private = value_node.kind() == "true",
+                _ => {}
+            }
+        }
+
+        let (name, version_node) = (name?, version_node?);
+        let own_version = self.get_string_value(version_node, content);
+        let start_point = version_node.start_position();
+
+        Some(PackageInfo {
+            name: name.clone(),
+            version: own_version.clone(),
+            commit_hash: None,
+            registry_type: RegistryType::Npm,
+            registry_url: Some(registry_type_to_url(RegistryType::Npm, &name)),
+            start_offset: version_node.start_byte() + 1,
+            end_offset: version_node.end_byte() - 1,
+            line: start_point.row,
+            column: start_point.column + 1,
+            section_name: None,
+            section_line: None,
+            extra_info: Some(ExtraInfo::PackageJsonMeta {
+                private,
+                name,
+                own_version,
+            }),
+        })
+    }
+
+    /// Extract the packages of a single dependency field (e.g.
+    /// `peerDependencies`), which [`Parser::parse`] merges with the others
+    pub fn parse_dependency_field(&self, content: &str, field: &str) -> Vec<PackageInfo> {
+        let mut results = Vec::new();
+        let Ok(tree) = Self::parse_tree(content) else {
+            return results;
+        };
+        let Some(document) = tree.root_node().child(0) else {
+            return results;
+        };
+
+        let mut cursor = document.walk();
+        for child in document.children(&mut cursor) {
+            let (Some(key_node), Some(value_node)) = (
+                child.child_by_field_name("key"),
+                child.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+            if value_node.kind() == "object" && self.get_string_value(key_node, content) == field {
+                self.extract_packages_from_object(value_node, content, &mut results);
+            }
+        }
+        results
+    }
+
+    fn parse_tree(content: &str) -> Result<tree_sitter::Tree, ParseError> {
+        let mut parser = tree_sitter::Parser::new();
+        let language = tree_sitter_json::LANGUAGE;
+        parser.set_language(&language.into()).map_err(|e| {
+            warn!("Failed to set JSON language for tree-sitter: {}", e);
+            ParseError::TreeSitter(e.to_string())
+        })?;
+
+        parser.parse(content, None).ok_or_else(|| {
+            warn!("Failed to parse JSON content");
+            ParseError::ParseFailed("Failed to parse JSON".to_string())
+        })
+    }
+
     /// Dependency field names to extract
     const DEPENDENCY_FIELDS: [&'static str; 4] = [
         "dependencies",
@@ -58,7 +174,7 @@ impl PackageJsonParser {
 
     /// Parse npm alias format: npm:package@version or npm:@scope/package@version
     /// Returns (actual_package_name, version)
-    fn parse_npm_alias(value: &str) -> Option<(String, String)> {
+    pub(crate) fn parse_npm_alias(value: &str) -> Option<(String, String)> {
         let rest = value.strip_prefix("npm:")?;
 
         // Handle scoped packages: @scope/package@version
@@ -153,11 +269,9 @@ impl PackageJsonParser {
             let key_name = self.get_string_value(key_node, content);
             let raw_version = self.get_string_value(value_node, content);
 
-            // Skip pnpm catalog references (e.g., "catalog:ag-grid" or "catalog:")
-            // These are resolved from pnpm-workspace.yaml, not version-checked here
-            if raw_version.starts_with("catalog:") {
-                continue;
-            }
+            // pnpm catalog references (e.g., "catalog:ag-grid" or "catalog:") take
+            // their version from pnpm-workspace.yaml; they are checked there
+            let catalog_name = catalog_ref_name(&raw_version).map(String::from);
 
             // Check for npm alias format: npm:package@version
             let (package_name, version) =
@@ -166,6 +280,13 @@ impl PackageJsonParser {
                 } else {
                     (key_name, raw_version)
                 };
+            let (registry_type, extra_info) = match catalog_name {
+                Some(catalog_name) => (
+                    RegistryType::PnpmCatalog,
+                    Some(ExtraInfo::PnpmCatalogRef { catalog_name }),
+                ),
+                None => (RegistryType::Npm, None),
+            };
 
             let start_point = value_node.start_position();
             let start_offset = value_node.start_byte();
@@ -176,16 +297,20 @@ impl PackageJsonParser {
             let version_end_offset = end_offset - 1;
             let version_column = start_point.column + 1;
 
+            let registry_url = Some(registry_type_to_url(registry_type, &package_name));
             results.push(PackageInfo {
                 name: package_name,
                 version,
                 commit_hash: None,
-                registry_type: RegistryType::Npm,
+                registry_type,
+                registry_url,
                 start_offset: version_start_offset,
                 end_offset: version_end_offset,
                 line: start_point.row,
                 column: version_column,
-                extra_info: None,
+                section_name: None,
+                section_line: None,
+                extra_info,
             });
         }
     }
@@ -204,6 +329,128 @@ impl PackageJsonParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_self_package_extracts_own_name_version_and_private_flag() {
+        let parser = PackageJsonParser::new();
+        let content = r#"{
+  "name": "@acme/ui",
+  "version": "1.2.3",
+  "private": true,
+  "dependencies": {
+    "lodash": "4.17.21"
+  }
+}"#;
+
+        assert_eq!(
+            parser.parse_self_package(content),
+            Some(PackageInfo {
+                name: "@acme/ui".to_string(),
+                version: "1.2.3".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::Npm,
+                registry_url: Some("https://www.npmjs.com/package/@acme/ui".to_string()),
+                start_offset: 38,
+                end_offset: 43,
+                line: 2,
+                column: 14,
+                section_name: None,
+                section_line: None,
+                extra_info: Some(ExtraInfo::PackageJsonMeta {
+                    private: true,
+                    name: "@acme/ui".to_string(),
+                    own_version: "1.2.3".to_string(),
+                }),
+            })
+        );
+        // The synthetic entry is never reported as a dependency
+        assert_eq!(parser.parse(content).unwrap().len(), 1);
+    }
+
+    #[rstest]
+    #[case(r#"{ "type": "module", "dependencies": {} }"#, true)]
+    #[case(r#"{ "type": "commonjs" }"#, false)]
+    #[case(r#"{ "name": "my-app" }"#, false)]
+    #[case(r#"{ "dependencies": { "type": "module" } }"#, false)]
+    fn parse_document_detects_esm_module_type(#[case] content: &str, #[case] expected: bool) {
+        let parser = PackageJsonParser::new();
+
+        let output = parser.parse_document(content).unwrap();
+
+        assert_eq!(output.metadata.is_esm, expected);
+    }
+
+    #[test]
+    fn parse_document_returns_dependencies_alongside_metadata() {
+        let parser = PackageJsonParser::new();
+        let content = r#"{
+  "type": "module",
+  "dependencies": {
+    "lodash": "4.17.21"
+  }
+}"#;
+
+        let output = parser.parse_document(content).unwrap();
+
+        assert_eq!(output.packages, parser.parse(content).unwrap());
+        assert_eq!(output.packages.len(), 1);
+        assert!(output.metadata.is_esm);
+    }
+
+    #[rstest]
+    #[case(r#"{ "name": "my-app", "version": "1.0.0" }"#, Some(false))]
+    #[case(
+        r#"{ "name": "my-app", "version": "1.0.0", "private": false }"#,
+        Some(false)
+    )]
+    #[case(r#"{ "name": "my-app" }"#, None)]
+    #[case(r#"{ "version": "1.0.0", "private": true }"#, None)]
+    fn parse_self_package_private_flag(#[case] content: &str, #[case] expected: Option<bool>) {
+        let parser = PackageJsonParser::new();
+
+        let private =
+            parser
+                .parse_self_package(content)
+                .and_then(|package| match package.extra_info {
+                    Some(ExtraInfo::PackageJsonMeta { private, .. }) => Some(private),
+                    _ => None,
+                });
+
+        assert_eq!(private, expected);
+    }
+
+    #[test]
+    fn parse_dependency_field_extracts_only_given_field() {
+        let parser = PackageJsonParser::new();
+        let content = r#"{
+  "dependencies": { "lodash": "4.17.21" },
+  "devDependencies": { "react": "18.3.0", "typescript": "^5.4.0" },
+  "peerDependencies": { "react": ">=17.0.0" }
+}"#;
+
+        let dev: Vec<(String, String)> = parser
+            .parse_dependency_field(content, "devDependencies")
+            .into_iter()
+            .map(|package| (package.name, package.version))
+            .collect();
+        let peer = parser.parse_dependency_field(content, "peerDependencies");
+
+        assert_eq!(
+            dev,
+            vec![
+                ("react".to_string(), "18.3.0".to_string()),
+                ("typescript".to_string(), "^5.4.0".to_string()),
+            ]
+        );
+        assert_eq!(peer.len(), 1);
+        assert_eq!((peer[0].line, peer[0].column), (3, 34));
+        assert!(
+            parser
+                .parse_dependency_field(content, "optionalDependencies")
+                .is_empty()
+        );
+    }
 
     #[test]
     fn parse_extracts_dependencies() {
@@ -223,10 +470,13 @@ mod tests {
                 version: "4.17.21".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::Npm,
+                registry_url: Some("https://www.npmjs.com/package/lodash".to_string()),
                 start_offset: 57,
                 end_offset: 64,
                 line: 3,
                 column: 15,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
@@ -250,10 +500,13 @@ mod tests {
                 version: "5.0.0".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::Npm,
+                registry_url: Some("https://www.npmjs.com/package/typescript".to_string()),
                 start_offset: 64,
                 end_offset: 69,
                 line: 3,
                 column: 19,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
@@ -277,10 +530,13 @@ mod tests {
                 version: ">=16.8.0".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::Npm,
+                registry_url: Some("https://www.npmjs.com/package/react".to_string()),
                 start_offset: 60,
                 end_offset: 68,
                 line: 3,
                 column: 14,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
@@ -310,10 +566,13 @@ mod tests {
                     version: "4.17.21".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::Npm,
+                    registry_url: Some("https://www.npmjs.com/package/lodash".to_string()),
                     start_offset: 57,
                     end_offset: 64,
                     line: 3,
                     column: 15,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -321,10 +580,13 @@ mod tests {
                     version: "5.0.0".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::Npm,
+                    registry_url: Some("https://www.npmjs.com/package/typescript".to_string()),
                     start_offset: 113,
                     end_offset: 118,
                     line: 6,
                     column: 19,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -332,10 +594,13 @@ mod tests {
                     version: "18.0.0".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::Npm,
+                    registry_url: Some("https://www.npmjs.com/package/react".to_string()),
                     start_offset: 163,
                     end_offset: 169,
                     line: 9,
                     column: 14,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
             ]
@@ -362,10 +627,13 @@ mod tests {
                     version: "^4.17.21".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::Npm,
+                    registry_url: Some("https://www.npmjs.com/package/lodash".to_string()),
                     start_offset: 57,
                     end_offset: 65,
                     line: 3,
                     column: 15,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -373,10 +641,13 @@ mod tests {
                     version: "~4.18.0".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::Npm,
+                    registry_url: Some("https://www.npmjs.com/package/express".to_string()),
                     start_offset: 84,
                     end_offset: 91,
                     line: 4,
                     column: 16,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -384,10 +655,13 @@ mod tests {
                     version: ">=9.0.0".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::Npm,
+                    registry_url: Some("https://www.npmjs.com/package/uuid".to_string()),
                     start_offset: 107,
                     end_offset: 114,
                     line: 5,
                     column: 13,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
             ]
@@ -413,10 +687,13 @@ mod tests {
                     version: "20.0.0".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::Npm,
+                    registry_url: Some("https://www.npmjs.com/package/@types/node".to_string()),
                     start_offset: 62,
                     end_offset: 68,
                     line: 3,
                     column: 20,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -424,10 +701,13 @@ mod tests {
                     version: "7.22.0".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::Npm,
+                    registry_url: Some("https://www.npmjs.com/package/@babel/core".to_string()),
                     start_offset: 91,
                     end_offset: 97,
                     line: 4,
                     column: 20,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
             ]
@@ -462,10 +742,13 @@ mod tests {
                 version: "4.17.21".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::Npm,
+                registry_url: Some("https://www.npmjs.com/package/lodash".to_string()),
                 start_offset: 37,
                 end_offset: 44,
                 line: 2,
                 column: 15,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
@@ -534,10 +817,13 @@ mod tests {
                 version: "4.17.21".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::Npm,
+                registry_url: Some("https://www.npmjs.com/package/lodash".to_string()),
                 start_offset: 54,
                 end_offset: 61,
                 line: 3,
                 column: 15,
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }
         );
@@ -576,33 +862,36 @@ mod tests {
         assert_eq!(result[0].version, "4.17.21");
     }
 
-    #[test]
-    fn parse_skips_pnpm_catalog_references() {
+    #[rstest]
+    #[case("catalog:ag-grid", "ag-grid")]
+    #[case("catalog:", "default")]
+    fn parse_extracts_pnpm_catalog_references(
+        #[case] version: &str,
+        #[case] expected_catalog: &str,
+    ) {
         let parser = PackageJsonParser::new();
-        let content = r#"{
-  "dependencies": {
+        let content = format!(
+            r#"{{
+  "dependencies": {{
     "lodash": "4.17.21",
-    "ag-grid-community": "catalog:ag-grid",
-    "react": "^18.0.0"
-  }
-}"#;
-        let result = parser.parse(content).unwrap();
-        // Should only have lodash and react, not ag-grid-community with catalog reference
-        assert_eq!(result.len(), 2);
-        assert_eq!(result[0].name, "lodash");
-        assert_eq!(result[1].name, "react");
-    }
+    "ag-grid-community": "{}"
+  }}
+}}"#,
+            version
+        );
+        let result = parser.parse(&content).unwrap();
 
-    #[test]
-    fn parse_skips_pnpm_default_catalog_reference() {
-        let parser = PackageJsonParser::new();
-        let content = r#"{
-  "dependencies": {
-    "lodash": "catalog:"
-  }
-}"#;
-        let result = parser.parse(content).unwrap();
-        // Should be empty - default catalog reference is skipped
-        assert!(result.is_empty());
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].registry_type, RegistryType::Npm);
+        assert_eq!(result[0].extra_info, None);
+        assert_eq!(result[1].name, "ag-grid-community");
+        assert_eq!(result[1].version, version);
+        assert_eq!(result[1].registry_type, RegistryType::PnpmCatalog);
+        assert_eq!(
+            result[1].extra_info,
+            Some(ExtraInfo::PnpmCatalogRef {
+                catalog_name: expected_catalog.to_string()
+            })
+        );
     }
 }
@@ -0,0 +1,267 @@
+//! package-lock.json parser for locked npm dependencies
+//!
+//! Supports lockfile v2 and v3, which both store resolved packages in a flat
+//! `packages` object keyed by install path (e.g., `node_modules/lodash`).
+//! Only top-level `node_modules/<name>` entries are extracted; nested paths
+//! (`node_modules/a/node_modules/b`), the root project (`""`) and workspace
+//! links are skipped.
+
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::{ExtraInfo, PackageInfo, RegistryType, registry_type_to_url};
+use tracing::warn;
+
+const NODE_MODULES_PREFIX: &str = "node_modules/";
+
+/// Parser for package-lock.json files
+pub struct PackageLockParser;
+
+impl PackageLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PackageLockParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for PackageLockParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        let mut parser = tree_sitter::Parser::new();
+        let language = tree_sitter_json::LANGUAGE;
+        parser.set_language(&language.into()).map_err(|e| {
+            warn!("Failed to set JSON language for tree-sitter: {}", e);
+            ParseError::TreeSitter(e.to_string())
+        })?;
+
+        let tree = parser.parse(content, None).ok_or_else(|| {
+            warn!("Failed to parse JSON content");
+            ParseError::ParseFailed("Failed to parse JSON".to_string())
+        })?;
+
+        let root = tree.root_node();
+        let mut results = Vec::new();
+
+        if let Some(document) = root.child(0)
+            && document.kind() == "object"
+            && let Some(packages) = find_object_value(document, content, "packages")
+        {
+            self.extract_packages(packages, content, &mut results);
+        }
+
+        Ok(results)
+    }
+}
+
+impl PackageLockParser {
+    /// Extract top-level packages from the `packages` object
+    fn extract_packages(
+        &self,
+        packages_node: tree_sitter::Node,
+        content: &str,
+        results: &mut Vec<PackageInfo>,
+    ) {
+        let mut cursor = packages_node.walk();
+
+        for pair in packages_node.children(&mut cursor) {
+            if pair.kind() != "pair" {
+                continue;
+            }
+
+            let (Some(key_node), Some(value_node)) = (
+                pair.child_by_field_name("key"),
+                pair.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+
+            let path = string_value(key_node, content);
+            let Some(name) = top_level_package_name(&path) else {
+                continue;
+            };
+
+            if value_node.kind() != "object" {
+                continue;
+            }
+
+            // Workspace packages are symlinked and have no registry version
+            if find_value(value_node, content, "link")
+                .is_some_and(|node| &content[node.byte_range()] == "true")
+            {
+                continue;
+            }
+
+            let Some(version_node) =
+                find_value(value_node, content, "version").filter(|node| node.kind() == "string")
+            else {
+                continue;
+            };
+
+            let extra_info = find_value(value_node, content, "resolved")
+                .filter(|node| node.kind() == "string")
+                .map(|node| ExtraInfo::NpmLock {
+                    resolved: string_value(node, content),
+                });
+
+            let start_point = version_node.start_position();
+
+            results.push(PackageInfo {
+                name: name.to_string(),
+                version: string_value(version_node, content),
+                commit_hash: None,
+                registry_type: RegistryType::Npm,
+                registry_url: Some(registry_type_to_url(RegistryType::Npm, name)),
+                start_offset: version_node.start_byte() + 1,
+                end_offset: version_node.end_byte() - 1,
+                line: start_point.row,
+                column: start_point.column + 1,
+                section_name: None,
+                section_line: None,
+                extra_info,
+            });
+        }
+    }
+}
+
+/// Get the package name from a top-level install path
+/// (`node_modules/lodash` -> `lodash`, `node_modules/@scope/pkg` -> `@scope/pkg`).
+/// Returns `None` for nested (transitive) paths and non-node_modules paths.
+fn top_level_package_name(path: &str) -> Option<&str> {
+    let name = path.strip_prefix(NODE_MODULES_PREFIX)?;
+    if name.is_empty() || name.contains("/node_modules/") {
+        return None;
+    }
+    Some(name)
+}
+
+/// Find the value node of `key` in a JSON object
+fn find_value<'a>(
+    object_node: tree_sitter::Node<'a>,
+    content: &str,
+    key: &str,
+) -> Option<tree_sitter::Node<'a>> {
+    let mut cursor = object_node.walk();
+    object_node
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "pair")
+        .find(|pair| {
+            pair.child_by_field_name("key")
+                .is_some_and(|key_node| string_value(key_node, content) == key)
+        })
+        .and_then(|pair| pair.child_by_field_name("value"))
+}
+
+/// Find the value node of `key` in a JSON object, if it is an object
+fn find_object_value<'a>(
+    object_node: tree_sitter::Node<'a>,
+    content: &str,
+    key: &str,
+) -> Option<tree_sitter::Node<'a>> {
+    find_value(object_node, content, key).filter(|node| node.kind() == "object")
+}
+
+/// Get the string value from a string node (removes quotes)
+fn string_value(node: tree_sitter::Node, content: &str) -> String {
+    content[node.byte_range()]
+        .trim()
+        .trim_start_matches('"')
+        .trim_end_matches('"')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_extracts_top_level_packages_from_v3_lockfile() {
+        let parser = PackageLockParser::new();
+        let content = r#"{
+  "name": "my-app",
+  "lockfileVersion": 3,
+  "packages": {
+    "": {
+      "name": "my-app",
+      "dependencies": { "lodash": "^4.17.0" }
+    },
+    "node_modules/lodash": {
+      "version": "4.17.20",
+      "resolved": "https://registry.npmjs.org/lodash/-/lodash-4.17.20.tgz"
+    }
+  }
+}"#;
+        let result = parser.parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![PackageInfo {
+                name: "lodash".to_string(),
+                version: "4.17.20".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::Npm,
+                registry_url: Some("https://www.npmjs.com/package/lodash".to_string()),
+                start_offset: 196,
+                end_offset: 203,
+                line: 9,
+                column: 18,
+                section_name: None,
+                section_line: None,
+                extra_info: Some(ExtraInfo::NpmLock {
+                    resolved: "https://registry.npmjs.org/lodash/-/lodash-4.17.20.tgz".to_string(),
+                }),
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_uses_packages_section_of_v2_lockfile() {
+        let parser = PackageLockParser::new();
+        let content = r#"{
+  "lockfileVersion": 2,
+  "packages": {
+    "node_modules/react": { "version": "18.2.0" }
+  },
+  "dependencies": {
+    "react": { "version": "18.2.0" }
+  }
+}"#;
+        let result = parser.parse(content).unwrap();
+
+        let names: Vec<(&str, &str)> = result
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        assert_eq!(names, vec![("react", "18.2.0")]);
+    }
+
+    #[test]
+    fn parse_skips_nested_and_linked_packages() {
+        let parser = PackageLockParser::new();
+        let content = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "node_modules/@types/node": { "version": "20.11.0" },
+    "node_modules/a/node_modules/b": { "version": "1.0.0" },
+    "node_modules/my-workspace": { "resolved": "packages/my-workspace", "link": true },
+    "packages/my-workspace": { "version": "0.1.0" }
+  }
+}"#;
+        let result = parser.parse(content).unwrap();
+
+        let names: Vec<&str> = result.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["@types/node"]);
+    }
+
+    #[rstest]
+    #[case("node_modules/lodash", Some("lodash"))]
+    #[case("node_modules/@scope/pkg", Some("@scope/pkg"))]
+    #[case("node_modules/a/node_modules/b", None)]
+    #[case("", None)]
+    #[case("packages/app", None)]
+    fn top_level_package_name_returns_expected(#[case] path: &str, #[case] expected: Option<&str>) {
+        assert_eq!(top_level_package_name(path), expected);
+    }
+}
@@ -1,9 +1,30 @@
 //! pnpm-workspace.yaml catalog parser
+//!
+//! Also exposes the `packages:` globs that locate workspace members.
 
+use std::collections::HashMap;
+
+use crate::parser::package_json::PackageJsonParser;
 use crate::parser::traits::{ParseError, Parser};
-use crate::parser::types::{PackageInfo, RegistryType};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
 use tracing::warn;
 
+/// Name the default catalog (`catalog:`) is indexed under
+pub const DEFAULT_CATALOG_NAME: &str = "default";
+
+/// Catalog name referenced by a `catalog:` version in package.json
+///
+/// `catalog:` refers to the default catalog and `catalog:foo` to the named
+/// catalog `foo`. Returns None for any other version.
+pub fn catalog_ref_name(version: &str) -> Option<&str> {
+    let name = version.strip_prefix("catalog:")?.trim();
+    Some(if name.is_empty() {
+        DEFAULT_CATALOG_NAME
+    } else {
+        name
+    })
+}
+
 /// Parser for pnpm-workspace.yaml catalog files
 pub struct PnpmWorkspaceParser;
 
@@ -27,21 +48,118 @@ impl Parser for PnpmWorkspaceParser {
         // Find catalog or catalogs sections
         self.find_catalog_entries(root, content, &mut results);
 
-        Ok(results)
+        Ok(results
+            .into_iter()
+            .map(|(_, info)| resolve_npm_alias(info))
+            .collect())
     }
 }
 
+/// Check an `npm:` alias entry (`vite: npm:@scope/pkg@latest`) against the
+/// aliased package, as package.json does
+///
+/// Only [`Parser::parse`] resolves aliases: the catalogs keep the declared
+/// name, which is what `catalog:` references in package.json use.
+fn resolve_npm_alias(mut info: PackageInfo) -> PackageInfo {
+    if let Some((name, version)) = PackageJsonParser::parse_npm_alias(&info.version) {
+        info.registry_url = Some(registry_type_to_url(info.registry_type, &name));
+        info.name = name;
+        info.version = version;
+    }
+    info
+}
+
 impl PnpmWorkspaceParser {
-    /// Find catalog entries in the YAML structure
+    /// Extract the workspace member globs from the top-level `packages:` list
+    ///
+    /// Exclusion globs keep their leading `!` (e.g. `!**/test/**`).
+    pub fn workspace_globs(&self, content: &str) -> Vec<String> {
+        let mut parser = tree_sitter::Parser::new();
+        let language = tree_sitter_yaml::LANGUAGE;
+        if let Err(e) = parser.set_language(&language.into()) {
+            warn!("Failed to set YAML language for tree-sitter: {}", e);
+            return Vec::new();
+        }
+        let Some(tree) = parser.parse(content, None) else {
+            warn!("Failed to parse YAML content");
+            return Vec::new();
+        };
+
+        // stream > document > block_node > block_mapping
+        let Some(mapping) = tree
+            .root_node()
+            .named_child(0)
+            .and_then(|document| document.named_child(0))
+            .and_then(|block_node| block_node.named_child(0))
+            .filter(|node| node.kind() == "block_mapping")
+        else {
+            return Vec::new();
+        };
+
+        let mut cursor = mapping.walk();
+        let Some(sequence) = mapping
+            .named_children(&mut cursor)
+            .filter(|pair| pair.kind() == "block_mapping_pair")
+            .find(|pair| {
+                pair.child_by_field_name("key")
+                    .is_some_and(|key| self.get_node_text(key, content) == "packages")
+            })
+            .and_then(|pair| pair.child_by_field_name("value"))
+            .and_then(|value| value.named_child(0))
+        else {
+            return Vec::new();
+        };
+
+        let mut cursor = sequence.walk();
+        sequence
+            .named_children(&mut cursor)
+            .filter_map(|item| match item.kind() {
+                "block_sequence_item" => item.named_child(0),
+                "flow_node" => Some(item),
+                _ => None,
+            })
+            .map(|node| self.get_node_text(node, content))
+            .filter(|glob| !glob.is_empty())
+            .collect()
+    }
+
+    /// Index catalog entries by catalog name
+    ///
+    /// Entries of the default catalog (`catalog:`) are indexed under
+    /// [`DEFAULT_CATALOG_NAME`], as are those of `catalogs: default:`, which
+    /// pnpm treats as the same catalog.
+    pub fn catalogs(&self, content: &str) -> HashMap<String, Vec<PackageInfo>> {
+        let mut parser = tree_sitter::Parser::new();
+        let language = tree_sitter_yaml::LANGUAGE;
+        if let Err(e) = parser.set_language(&language.into()) {
+            warn!("Failed to set YAML language for tree-sitter: {}", e);
+            return HashMap::new();
+        }
+        let Some(tree) = parser.parse(content, None) else {
+            warn!("Failed to parse YAML content");
+            return HashMap::new();
+        };
+
+        let mut entries = Vec::new();
+        self.find_catalog_entries(tree.root_node(), content, &mut entries);
+
+        let mut catalogs: HashMap<String, Vec<PackageInfo>> = HashMap::new();
+        for (catalog_name, info) in entries {
+            catalogs.entry(catalog_name).or_default().push(info);
+        }
+        catalogs
+    }
+
+    /// Find catalog entries in the YAML structure, with their catalog names
     ///
     /// Supports two formats:
-    /// 1. Single catalog: `catalog:` with direct package entries
+    /// 1. Default catalog: `catalog:` with direct package entries
     /// 2. Named catalogs: `catalogs:` with nested catalog groups
     fn find_catalog_entries(
         &self,
         node: tree_sitter::Node,
         content: &str,
-        results: &mut Vec<PackageInfo>,
+        results: &mut Vec<(String, PackageInfo)>,
     ) {
         if node.kind() == "block_mapping_pair"
             && let Some(key_node) = node.child_by_field_name("key")
@@ -49,9 +167,14 @@ impl PnpmWorkspaceParser {
             let key = self.get_node_text(key_node, content);
 
             if key == "catalog" {
-                // Single catalog format
+                // Default catalog format
                 if let Some(value_node) = node.child_by_field_name("value") {
-                    self.extract_packages_from_mapping(value_node, content, results);
+                    self.extract_packages_from_mapping(
+                        value_node,
+                        content,
+                        DEFAULT_CATALOG_NAME,
+                        results,
+                    );
                 }
                 return;
             } else if key == "catalogs" {
@@ -70,21 +193,22 @@ impl PnpmWorkspaceParser {
         }
     }
 
-    /// Extract packages from a block_mapping (for single catalog)
+    /// Extract packages of one catalog from a block_mapping
     fn extract_packages_from_mapping(
         &self,
         node: tree_sitter::Node,
         content: &str,
-        results: &mut Vec<PackageInfo>,
+        catalog_name: &str,
+        results: &mut Vec<(String, PackageInfo)>,
     ) {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
             if child.kind() == "block_mapping" {
-                self.extract_packages_from_mapping(child, content, results);
+                self.extract_packages_from_mapping(child, content, catalog_name, results);
             } else if child.kind() == "block_mapping_pair"
                 && let Some(info) = self.parse_package_entry(child, content)
             {
-                results.push(info);
+                results.push((catalog_name.to_string(), info));
             }
         }
     }
@@ -94,7 +218,7 @@ impl PnpmWorkspaceParser {
         &self,
         node: tree_sitter::Node,
         content: &str,
-        results: &mut Vec<PackageInfo>,
+        results: &mut Vec<(String, PackageInfo)>,
     ) {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
@@ -104,9 +228,16 @@ impl PnpmWorkspaceParser {
                 for catalog_pair in child.children(&mut inner_cursor) {
                     // The value of each catalog pair contains the packages
                     if catalog_pair.kind() == "block_mapping_pair"
+                        && let Some(key_node) = catalog_pair.child_by_field_name("key")
                         && let Some(value_node) = catalog_pair.child_by_field_name("value")
                     {
-                        self.extract_packages_from_mapping(value_node, content, results);
+                        let catalog_name = self.get_node_text(key_node, content);
+                        self.extract_packages_from_mapping(
+                            value_node,
+                            content,
+                            &catalog_name,
+                            results,
+                        );
                     }
                 }
             }
@@ -147,15 +278,19 @@ impl PnpmWorkspaceParser {
             (start_offset, end_offset, start_point.column)
         };
 
+        let registry_url = Some(registry_type_to_url(RegistryType::PnpmCatalog, &name));
         Some(PackageInfo {
             name,
             version: version.to_string(),
             commit_hash: None,
             registry_type: RegistryType::PnpmCatalog,
+            registry_url,
             start_offset: adjusted_start,
             end_offset: adjusted_end,
             line: start_point.row,
             column: adjusted_column,
+            section_name: None,
+            section_line: None,
             extra_info: None,
         })
     }
@@ -175,6 +310,19 @@ impl PnpmWorkspaceParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "packages:\n  - shared\n  - 'web/**'\n  - \"!**/test/**\"\ncatalog:\n  react: ^18.2.0\n",
+        vec!["shared", "web/**", "!**/test/**"]
+    )]
+    #[case("packages: [apps/*, \"packages/*\"]\n", vec!["apps/*", "packages/*"])]
+    #[case("catalog:\n  packages: ^1.0.0\n", vec![])]
+    #[case("", vec![])]
+    fn workspace_globs_returns_expected(#[case] content: &str, #[case] expected: Vec<&str>) {
+        assert_eq!(PnpmWorkspaceParser.workspace_globs(content), expected);
+    }
 
     #[test]
     fn parse_extracts_single_catalog_entries() {
@@ -192,10 +340,13 @@ mod tests {
                     version: "^18.2.0".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::PnpmCatalog,
+                    registry_url: Some("https://www.npmjs.com/package/react".to_string()),
                     start_offset: 18,
                     end_offset: 25,
                     line: 1,
                     column: 9,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -203,10 +354,13 @@ mod tests {
                     version: "^4.17.21".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::PnpmCatalog,
+                    registry_url: Some("https://www.npmjs.com/package/lodash".to_string()),
                     start_offset: 36,
                     end_offset: 44,
                     line: 2,
                     column: 10,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
             ]
@@ -232,10 +386,13 @@ mod tests {
                     version: "^17.0.2".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::PnpmCatalog,
+                    registry_url: Some("https://www.npmjs.com/package/react".to_string()),
                     start_offset: 32,
                     end_offset: 39,
                     line: 2,
                     column: 11,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -243,10 +400,13 @@ mod tests {
                     version: "^17.0.2".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::PnpmCatalog,
+                    registry_url: Some("https://www.npmjs.com/package/react-dom".to_string()),
                     start_offset: 55,
                     end_offset: 62,
                     line: 3,
                     column: 15,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
                 PackageInfo {
@@ -254,10 +414,13 @@ mod tests {
                     version: "^18.2.0".to_string(),
                     commit_hash: None,
                     registry_type: RegistryType::PnpmCatalog,
+                    registry_url: Some("https://www.npmjs.com/package/react".to_string()),
                     start_offset: 85,
                     end_offset: 92,
                     line: 5,
                     column: 11,
+                    section_name: None,
+                    section_line: None,
                     extra_info: None,
                 },
             ]
@@ -280,10 +443,13 @@ mod tests {
                 version: "5.0.0".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::PnpmCatalog,
+                registry_url: Some("https://www.npmjs.com/package/typescript".to_string()),
                 start_offset: 24, // After opening quote
                 end_offset: 29,   // Before closing quote
                 line: 1,
                 column: 15, // After opening quote
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }]
         );
@@ -304,15 +470,97 @@ mod tests {
                 version: "5.0.0".to_string(),
                 commit_hash: None,
                 registry_type: RegistryType::PnpmCatalog,
+                registry_url: Some("https://www.npmjs.com/package/typescript".to_string()),
                 start_offset: 24, // After opening quote
                 end_offset: 29,   // Before closing quote
                 line: 1,
                 column: 15, // After opening quote
+                section_name: None,
+                section_line: None,
                 extra_info: None,
             }]
         );
     }
 
+    #[test]
+    fn parse_resolves_npm_alias_entries() {
+        let content = r#"catalog:
+  vite: npm:@voidzero-dev/vite-plus-core@latest
+  lodash: npm:lodash-es@^4.17.21
+"#;
+        let result = PnpmWorkspaceParser.parse(content).unwrap();
+
+        assert_eq!(
+            result[0],
+            PackageInfo {
+                name: "@voidzero-dev/vite-plus-core".to_string(),
+                version: "latest".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::PnpmCatalog,
+                registry_url: Some(
+                    "https://www.npmjs.com/package/@voidzero-dev/vite-plus-core".to_string()
+                ),
+                start_offset: 17,
+                end_offset: 56,
+                line: 1,
+                column: 8,
+                section_name: None,
+                section_line: None,
+                extra_info: None,
+            }
+        );
+        assert_eq!(
+            (result[1].name.as_str(), result[1].version.as_str()),
+            ("lodash-es", "^4.17.21")
+        );
+        // `catalog:` references use the declared name
+        assert_eq!(
+            PnpmWorkspaceParser.catalogs(content)[DEFAULT_CATALOG_NAME][0].name,
+            "vite"
+        );
+    }
+
+    #[test]
+    fn catalogs_indexes_default_catalog_and_named_catalogs() {
+        let content = r#"catalog:
+  react: ^18.2.0
+catalogs:
+  react17:
+    react: ^17.0.2
+    react-dom: ^17.0.2
+"#;
+        let catalogs = PnpmWorkspaceParser.catalogs(content);
+
+        let names = |catalog: &str| -> Vec<(String, String)> {
+            catalogs[catalog]
+                .iter()
+                .map(|info| (info.name.clone(), info.version.clone()))
+                .collect()
+        };
+        assert_eq!(catalogs.len(), 2);
+        assert_eq!(
+            names(DEFAULT_CATALOG_NAME),
+            vec![("react".to_string(), "^18.2.0".to_string())]
+        );
+        assert_eq!(
+            names("react17"),
+            vec![
+                ("react".to_string(), "^17.0.2".to_string()),
+                ("react-dom".to_string(), "^17.0.2".to_string()),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("catalog:", Some("default"))]
+    #[case("catalog:react17", Some("react17"))]
+    #[case("catalog: react17", Some("react17"))]
+    #[case("^18.2.0", None)]
+    #[case("npm:catalog@1.0.0", None)]
+    fn catalog_ref_name_returns_expected(#[case] version: &str, #[case] expected: Option<&str>) {
+        assert_eq!(catalog_ref_name(version), expected);
+    }
+
     #[test]
     fn parse_returns_empty_for_no_catalog() {
         let parser = PnpmWorkspaceParser;
@@ -0,0 +1,264 @@
+//! poetry.lock parser for locked Python dependencies
+//!
+//! Extracts every `[[package]]` entry with its locked `version`.
+//! Packages that declare a `source` (either as a `[package.source]` table or
+//! an inline `source = { ... }` key) come from a git repository, a local path,
+//! or a private index and are skipped, since they cannot be checked against PyPI.
+
+use tracing::warn;
+
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
+
+/// Parser for poetry.lock files
+pub struct PoetryLockParser;
+
+impl PoetryLockParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PoetryLockParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for PoetryLockParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        let mut parser = tree_sitter::Parser::new();
+        let language = tree_sitter_toml_ng::LANGUAGE;
+        parser.set_language(&language.into()).map_err(|e| {
+            warn!("Failed to set TOML language for tree-sitter: {}", e);
+            ParseError::TreeSitter(e.to_string())
+        })?;
+
+        let tree = parser.parse(content, None).ok_or_else(|| {
+            warn!("Failed to parse TOML content");
+            ParseError::ParseFailed("Failed to parse TOML".to_string())
+        })?;
+
+        let root = tree.root_node();
+        let mut results = Vec::new();
+
+        self.extract_packages(root, content, &mut results);
+
+        Ok(results)
+    }
+}
+
+impl PoetryLockParser {
+    /// Walk top-level nodes, emitting one package per `[[package]]` entry.
+    ///
+    /// A `[package.source]` table belongs to the `[[package]]` entry that
+    /// precedes it, so the pending entry is only emitted once the next
+    /// `[[package]]` (or end of file) is reached without seeing a source.
+    fn extract_packages(
+        &self,
+        root: tree_sitter::Node,
+        content: &str,
+        results: &mut Vec<PackageInfo>,
+    ) {
+        let mut pending: Option<PackageInfo> = None;
+        let mut cursor = root.walk();
+
+        for child in root.children(&mut cursor) {
+            match child.kind() {
+                "table_array_element" => {
+                    results.extend(pending.take());
+                    if table_name(child, content).as_deref() == Some("package") {
+                        pending = self.extract_package(child, content);
+                    }
+                }
+                "table" if table_name(child, content).as_deref() == Some("package.source") => {
+                    pending = None;
+                }
+                _ => {}
+            }
+        }
+
+        results.extend(pending);
+    }
+
+    /// Extract name and version from a `[[package]]` entry
+    fn extract_package(&self, table_node: tree_sitter::Node, content: &str) -> Option<PackageInfo> {
+        let mut name: Option<String> = None;
+        let mut version: Option<tree_sitter::Node> = None;
+
+        let mut cursor = table_node.walk();
+        for pair in table_node.children(&mut cursor) {
+            if pair.kind() != "pair" {
+                continue;
+            }
+            let (Some(key), Some(value)) = (pair.child(0), pair.child(2)) else {
+                continue;
+            };
+            match (&content[key.byte_range()], value.kind()) {
+                ("name", "string") => name = Some(unquote(&content[value.byte_range()])),
+                ("version", "string") => version = Some(value),
+                // Non-PyPI package (git, path, url or private index)
+                ("source", _) => return None,
+                _ => {}
+            }
+        }
+
+        let (name, version_node) = (name?, version?);
+        let start_point = version_node.start_position();
+
+        let registry_url = Some(registry_type_to_url(RegistryType::PyPI, &name));
+        Some(PackageInfo {
+            name,
+            version: unquote(&content[version_node.byte_range()]),
+            commit_hash: None,
+            registry_type: RegistryType::PyPI,
+            registry_url,
+            start_offset: version_node.start_byte() + 1,
+            end_offset: version_node.end_byte() - 1,
+            line: start_point.row,
+            column: start_point.column + 1,
+            section_name: None,
+            section_line: None,
+            extra_info: None,
+        })
+    }
+}
+
+/// Get the header name of a table or table array element (e.g., "package.source")
+fn table_name(table_node: tree_sitter::Node, content: &str) -> Option<String> {
+    let mut cursor = table_node.walk();
+    table_node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "bare_key" || child.kind() == "dotted_key")
+        .map(|child| content[child.byte_range()].to_string())
+}
+
+/// Strip the surrounding quotes from a TOML string literal
+fn unquote(text: &str) -> String {
+    text.trim()
+        .trim_start_matches(['"', '\''])
+        .trim_end_matches(['"', '\''])
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_extracts_locked_packages() {
+        let parser = PoetryLockParser::new();
+        let content = r#"# This file is automatically @generated by Poetry and should not be changed by hand.
+
+[[package]]
+name = "certifi"
+version = "2024.2.2"
+description = "Python package for providing Mozilla's CA Bundle."
+optional = false
+python-versions = ">=3.6"
+files = [
+    {file = "certifi-2024.2.2-py3-none-any.whl", hash = "sha256:dc383c07b76109f368f6106eee2b593b04a011ea4d55f652c6ca24a754d1cdd1"},
+]
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+description = "Python HTTP for Humans."
+optional = false
+python-versions = ">=3.7"
+
+[package.dependencies]
+certifi = ">=2017.4.17"
+
+[metadata]
+lock-version = "2.0"
+"#;
+        let result = parser.parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                PackageInfo {
+                    name: "certifi".to_string(),
+                    version: "2024.2.2".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::PyPI,
+                    registry_url: Some("https://pypi.org/project/certifi/".to_string()),
+                    start_offset: 126,
+                    end_offset: 134,
+                    line: 4,
+                    column: 11,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+                PackageInfo {
+                    name: "requests".to_string(),
+                    version: "2.31.0".to_string(),
+                    commit_hash: None,
+                    registry_type: RegistryType::PyPI,
+                    registry_url: Some("https://pypi.org/project/requests/".to_string()),
+                    start_offset: 431,
+                    end_offset: 437,
+                    line: 14,
+                    column: 11,
+                    section_name: None,
+                    section_line: None,
+                    extra_info: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_skips_packages_with_source_table() {
+        let parser = PoetryLockParser::new();
+        let content = r#"[[package]]
+name = "mylib"
+version = "0.1.0"
+
+[package.source]
+type = "git"
+url = "https://github.com/example/mylib.git"
+reference = "main"
+
+[[package]]
+name = "six"
+version = "1.16.0"
+"#;
+        let result = parser.parse(content).unwrap();
+
+        let names: Vec<&str> = result.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["six"]);
+    }
+
+    #[test]
+    fn parse_skips_packages_with_inline_source() {
+        let parser = PoetryLockParser::new();
+        let content = r#"[[package]]
+name = "internal"
+version = "1.0.0"
+source = { type = "legacy", url = "https://pypi.internal.example.com/simple" }
+
+[[package]]
+name = "six"
+version = "1.16.0"
+"#;
+        let result = parser.parse(content).unwrap();
+
+        let names: Vec<&str> = result.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["six"]);
+    }
+
+    #[test]
+    fn parse_returns_empty_for_no_packages() {
+        let parser = PoetryLockParser::new();
+        let content = r#"[metadata]
+lock-version = "2.0"
+python-versions = "^3.11"
+"#;
+        let result = parser.parse(content).unwrap();
+
+        assert!(result.is_empty());
+    }
+}
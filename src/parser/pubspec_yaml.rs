@@ -0,0 +1,207 @@
+//! pubspec.yaml parser for Dart and Flutter projects
+//!
+//! Extracts the version constraints of the top-level `dependencies:` and
+//! `dev_dependencies:` mappings: `any`, `1.2.3`, `^1.2.3` or
+//! `">=1.0.0 <2.0.0"`.
+//!
+//! Dependencies given as a mapping (`flutter: {sdk: flutter}`,
+//! `path: ../pkg`, `git: ...`, `hosted: ...`) are not fetched from pub.dev
+//! and are skipped, as are dependencies without a constraint.
+
+use tracing::warn;
+
+use crate::parser::conda_env::{Scalar, find_top_level_value};
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::{PackageInfo, RegistryType};
+
+/// Dependency sections of pubspec.yaml
+const DEPENDENCY_SECTIONS: &[&str] = &["dependencies", "dev_dependencies"];
+
+/// Parser for pubspec.yaml files
+pub struct PubspecYamlParser;
+
+impl PubspecYamlParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PubspecYamlParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for PubspecYamlParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        let mut parser = tree_sitter::Parser::new();
+        let language = tree_sitter_yaml::LANGUAGE;
+        parser.set_language(&language.into()).map_err(|e| {
+            warn!("Failed to set YAML language for tree-sitter: {}", e);
+            ParseError::TreeSitter(e.to_string())
+        })?;
+
+        let tree = parser.parse(content, None).ok_or_else(|| {
+            warn!("Failed to parse YAML content");
+            ParseError::ParseFailed("Failed to parse YAML".to_string())
+        })?;
+
+        let root = tree.root_node();
+        let mut results = Vec::new();
+
+        for section in DEPENDENCY_SECTIONS {
+            if let Some(dependencies) = find_top_level_value(root, content, section) {
+                self.extract_dependencies(dependencies, content, section, &mut results);
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+impl PubspecYamlParser {
+    /// Extract the `name: constraint` pairs of a dependency section
+    fn extract_dependencies(
+        &self,
+        dependencies: tree_sitter::Node,
+        content: &str,
+        section: &str,
+        results: &mut Vec<PackageInfo>,
+    ) {
+        let Some(mapping) = dependencies
+            .named_child(0)
+            .filter(|node| node.kind() == "block_mapping")
+        else {
+            return;
+        };
+
+        // The value's parent is the `dependencies:` pair, which starts at the key
+        let section_line = dependencies
+            .parent()
+            .map_or(dependencies.start_position().row, |pair| {
+                pair.start_position().row
+            });
+
+        let mut cursor = mapping.walk();
+        for pair in mapping
+            .named_children(&mut cursor)
+            .filter(|pair| pair.kind() == "block_mapping_pair")
+        {
+            let (Some(key), Some(value)) = (
+                pair.child_by_field_name("key"),
+                pair.child_by_field_name("value"),
+            ) else {
+                continue;
+            };
+            // Mappings (sdk, path, git, hosted) are block or flow mappings,
+            // which have no scalar
+            let Some(scalar) = Some(value)
+                .filter(|value| value.kind() == "flow_node")
+                .and_then(|value| Scalar::from_node(value, content))
+                .filter(|scalar| !scalar.text.trim().is_empty())
+            else {
+                continue;
+            };
+
+            let name = content[key.byte_range()].trim().to_string();
+            let mut info = scalar.package_info(name, 0, scalar.text.len(), RegistryType::Pub);
+            info.section_name = Some(section.to_string());
+            info.section_line = Some(section_line);
+            results.push(info);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_extracts_dependencies_with_position() {
+        let content = "name: app\ndependencies:\n  http: ^1.2.0\n";
+
+        let result = PubspecYamlParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![PackageInfo {
+                name: "http".to_string(),
+                version: "^1.2.0".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::Pub,
+                registry_url: Some("https://pub.dev/packages/http".to_string()),
+                start_offset: 32,
+                end_offset: 38,
+                line: 2,
+                column: 8,
+                section_name: Some("dependencies".to_string()),
+                section_line: Some(1),
+                extra_info: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_extracts_both_sections_and_skips_mappings() {
+        let content = r#"name: app
+environment:
+  sdk: ">=3.0.0 <4.0.0"
+dependencies:
+  flutter:
+    sdk: flutter
+  provider: 6.1.2
+  intl: ">=0.18.0 <0.20.0"
+  local_pkg:
+    path: ../local_pkg
+  inline: {git: https://github.com/example/inline.git}
+  collection: any
+  empty:
+dev_dependencies:
+  flutter_test:
+    sdk: flutter
+  lints: '^3.0.0'
+"#;
+
+        let result: Vec<(String, String, Option<String>)> = PubspecYamlParser::new()
+            .parse(content)
+            .unwrap()
+            .into_iter()
+            .map(|info| (info.name, info.version, info.section_name))
+            .collect();
+
+        assert_eq!(
+            result,
+            vec![
+                (
+                    "provider".to_string(),
+                    "6.1.2".to_string(),
+                    Some("dependencies".to_string())
+                ),
+                (
+                    "intl".to_string(),
+                    ">=0.18.0 <0.20.0".to_string(),
+                    Some("dependencies".to_string())
+                ),
+                (
+                    "collection".to_string(),
+                    "any".to_string(),
+                    Some("dependencies".to_string())
+                ),
+                (
+                    "lints".to_string(),
+                    "^3.0.0".to_string(),
+                    Some("dev_dependencies".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("name: app\n")]
+    #[case("dependencies:\n")]
+    #[case("dependency_overrides:\n  http: 1.0.0\n")]
+    fn parse_returns_nothing_without_dependencies(#[case] content: &str) {
+        assert!(PubspecYamlParser::new().parse(content).unwrap().is_empty());
+    }
+}
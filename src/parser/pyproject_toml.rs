@@ -5,9 +5,12 @@
 //! - `[build-system].requires` - Build system requirements
 //! - `[project.optional-dependencies]` - Optional dependencies
 //! - `[dependency-groups]` - PEP 735 dependency groups
+//! - `[tool.poetry.dependencies]`, `[tool.poetry.dev-dependencies]` and
+//!   `[tool.poetry.group.<name>.dependencies]` - Poetry dependency tables
 //!
 //! URL dependencies (e.g., `pkg @ git+https://...`) are skipped
-//! as they don't exist on PyPI.
+//! as they don't exist on PyPI. Poetry git/path/url dependencies (inline
+//! tables without a `version` key) are skipped likewise.
 
 use std::str::FromStr;
 
@@ -15,7 +18,7 @@ use pep508_rs::{Requirement, VerbatimUrl, VersionOrUrl};
 use tracing::warn;
 
 use crate::parser::traits::{ParseError, Parser};
-use crate::parser::types::{PackageInfo, RegistryType};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
 
 /// Parser for pyproject.toml files
 pub struct PyprojectTomlParser;
@@ -117,10 +120,120 @@ impl PyprojectTomlParser {
                 // [project.optional-dependencies] / [dependency-groups] (PEP 735) - all keys have arrays
                 self.extract_all_arrays(table_node, content, results);
             }
+            "tool.poetry.dependencies" | "tool.poetry.dev-dependencies" => {
+                self.extract_poetry_dependencies(table_node, content, results);
+            }
+            name if Self::is_poetry_group_table(name) => {
+                // [tool.poetry.group.<name>.dependencies] (Poetry 1.2+)
+                self.extract_poetry_dependencies(table_node, content, results);
+            }
             _ => {}
         }
     }
 
+    /// Check for a `tool.poetry.group.<name>.dependencies` table name
+    fn is_poetry_group_table(name: &str) -> bool {
+        name.strip_prefix("tool.poetry.group.")
+            .and_then(|rest| rest.strip_suffix(".dependencies"))
+            .is_some_and(|group| !group.is_empty() && !group.contains('.'))
+    }
+
+    /// Extract `name = "spec"` / `name = { version = "spec", ... }` pairs of
+    /// a Poetry dependency table
+    fn extract_poetry_dependencies(
+        &self,
+        table_node: tree_sitter::Node,
+        content: &str,
+        results: &mut Vec<PackageInfo>,
+    ) {
+        let mut cursor = table_node.walk();
+
+        for pair in table_node.children(&mut cursor) {
+            if pair.kind() != "pair" {
+                continue;
+            }
+
+            let Some(key_node) = pair.child(0) else {
+                continue;
+            };
+            let package_name = match key_node.kind() {
+                "bare_key" => content[key_node.byte_range()].to_string(),
+                "quoted_key" => content[key_node.byte_range()]
+                    .trim_matches(|c| c == '"' || c == '\'')
+                    .to_string(),
+                _ => continue,
+            };
+
+            // `python` is the interpreter constraint, not a package
+            if package_name == "python" {
+                continue;
+            }
+
+            // Multiple-constraint arrays are not supported
+            let mut pair_cursor = pair.walk();
+            let version_node =
+                pair.children(&mut pair_cursor)
+                    .find_map(|child| match child.kind() {
+                        "string" => Some(child),
+                        "inline_table" => self.extract_version_from_inline_table(child, content),
+                        _ => None,
+                    });
+            let Some(version_node) = version_node else {
+                continue;
+            };
+
+            let version = content[version_node.byte_range()]
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string();
+            let start_point = version_node.start_position();
+            let registry_url = Some(registry_type_to_url(RegistryType::PyPI, &package_name));
+
+            results.push(PackageInfo {
+                name: package_name,
+                version,
+                commit_hash: None,
+                registry_type: RegistryType::PyPI,
+                registry_url,
+                start_offset: version_node.start_byte() + 1,
+                end_offset: version_node.end_byte() - 1,
+                line: start_point.row,
+                column: start_point.column + 1,
+                section_name: None,
+                section_line: None,
+                extra_info: None,
+            });
+        }
+    }
+
+    /// Find the `version` string of an inline table: { version = "^1.0", extras = [...] }
+    /// Returns None for git/path/url dependencies, which have no version
+    fn extract_version_from_inline_table<'a>(
+        &self,
+        table_node: tree_sitter::Node<'a>,
+        content: &str,
+    ) -> Option<tree_sitter::Node<'a>> {
+        let mut cursor = table_node.walk();
+
+        for child in table_node.children(&mut cursor) {
+            if child.kind() == "pair" {
+                let mut pair_cursor = child.walk();
+                let mut is_version_key = false;
+
+                for pair_child in child.children(&mut pair_cursor) {
+                    match pair_child.kind() {
+                        "bare_key" => {
+                            is_version_key = &content[pair_child.byte_range()] == "version";
+                        }
+                        "string" if is_version_key => return Some(pair_child),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
     /// Extract dependencies from a specific key's array value
     fn extract_key_array(
         &self,
@@ -285,15 +398,19 @@ impl PyprojectTomlParser {
         // Calculate column offset for version spec
         let version_column_offset = start_offset - string_start;
 
+        let registry_url = Some(registry_type_to_url(RegistryType::PyPI, &package_name));
         Some(PackageInfo {
             name: package_name,
             version: version_spec,
             commit_hash: None,
             registry_type: RegistryType::PyPI,
+            registry_url,
             start_offset,
             end_offset,
             line: start_point.row,
             column: start_point.column + version_column_offset,
+            section_name: None,
+            section_line: None,
             extra_info: None,
         })
     }
@@ -490,6 +607,69 @@ dev = [
         assert_eq!(result[0].version, ">=0.4.0");
     }
 
+    #[test]
+    fn parse_extracts_poetry_dependencies() {
+        let parser = PyprojectTomlParser::new();
+        let content = r#"[tool.poetry.dependencies]
+python = "^3.9"
+requests = { version = "^2.28", extras = ["socks"] }
+"#;
+        let result = parser.parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![PackageInfo {
+                name: "requests".to_string(),
+                version: "^2.28".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::PyPI,
+                registry_url: Some("https://pypi.org/project/requests/".to_string()),
+                start_offset: 67,
+                end_offset: 72,
+                line: 2,
+                column: 24,
+                section_name: None,
+                section_line: None,
+                extra_info: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_extracts_poetry_dev_and_group_dependencies() {
+        let parser = PyprojectTomlParser::new();
+        let content = r#"[tool.poetry.dependencies]
+flask = "~2.0"
+django = ">=3.2,<4.0"
+my-lib = { git = "https://github.com/user/my-lib.git" }
+local = { path = "../local" }
+
+[tool.poetry.dev-dependencies]
+pytest = "^7.0"
+
+[tool.poetry.group.docs.dependencies]
+sphinx = { version = "^5.0" }
+
+[tool.poetry.group.docs]
+optional = true
+"#;
+        let result = parser.parse(content).unwrap();
+
+        let packages: Vec<(&str, &str)> = result
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        assert_eq!(
+            packages,
+            vec![
+                ("flask", "~2.0"),
+                ("django", ">=3.2,<4.0"),
+                ("pytest", "^7.0"),
+                ("sphinx", "^5.0"),
+            ]
+        );
+    }
+
     #[test]
     fn parse_extracts_named_optional_dependencies_subsection() {
         let parser = PyprojectTomlParser::new();
@@ -0,0 +1,262 @@
+//! requirements.txt parser for pip requirements (PEP 508/PEP 440)
+//!
+//! Each line holds one requirement (`requests>=2.28,<3.0`, `Flask[async]>=2.0`).
+//! Environment markers (`; sys_platform == 'win32'`), comments and
+//! per-requirement options (`--hash=...`) are not part of the version.
+//! Lines ending with `\` continue on the next line.
+//!
+//! Option lines (`-r other.txt`, `-e .`, `--index-url ...`), URL and path
+//! requirements (`git+https://...`, `./wheels/pkg.whl`, `pkg @ https://...`)
+//! and requirements without a version are skipped.
+
+use std::str::FromStr;
+
+use pep508_rs::{Requirement, VerbatimUrl, VersionOrUrl};
+use tracing::warn;
+
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
+
+/// Parser for requirements.txt files
+pub struct RequirementsTxtParser;
+
+impl RequirementsTxtParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for RequirementsTxtParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for RequirementsTxtParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        Ok(logical_lines(content)
+            .iter()
+            .filter_map(|line| parse_requirement(content, line))
+            .collect())
+    }
+}
+
+/// A line with its `\` continuations joined
+struct LogicalLine {
+    text: String,
+    /// Offset in `text` and in the file content where each joined line starts
+    segments: Vec<(usize, usize)>,
+}
+
+impl LogicalLine {
+    /// Offset in the file content of the character at `pos` in `text`
+    fn content_offset(&self, pos: usize) -> usize {
+        let (text_start, content_start) = self
+            .segments
+            .iter()
+            .rev()
+            .find(|(text_start, _)| *text_start <= pos)
+            .copied()
+            .unwrap_or_default();
+        content_start + pos - text_start
+    }
+}
+
+/// Split the content into lines, joining lines that end with `\`
+fn logical_lines(content: &str) -> Vec<LogicalLine> {
+    let mut lines = Vec::new();
+    let mut current = LogicalLine {
+        text: String::new(),
+        segments: Vec::new(),
+    };
+
+    let mut offset = 0;
+    for physical in content.split_inclusive('\n') {
+        let line = physical.trim_end_matches(['\n', '\r']);
+        current.segments.push((current.text.len(), offset));
+        offset += physical.len();
+
+        match line.strip_suffix('\\') {
+            Some(continued) => current.text.push_str(continued),
+            None => {
+                current.text.push_str(line);
+                lines.push(std::mem::replace(
+                    &mut current,
+                    LogicalLine {
+                        text: String::new(),
+                        segments: Vec::new(),
+                    },
+                ));
+            }
+        }
+    }
+    if !current.segments.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Parse the requirement of a line, if it has a version to check
+fn parse_requirement(content: &str, line: &LogicalLine) -> Option<PackageInfo> {
+    let text = strip_comment(&line.text);
+    // Per-requirement options such as `--hash=sha256:...` follow the requirement
+    let text = text.find(" --").map_or(text, |pos| &text[..pos]);
+    let indent = text.len() - text.trim_start().len();
+    let requirement = text.trim();
+
+    if requirement.is_empty()
+        || requirement.starts_with('-')
+        || requirement.starts_with(['.', '/'])
+        || requirement.contains("://")
+    {
+        return None;
+    }
+
+    let req = Requirement::<VerbatimUrl>::from_str(requirement)
+        .inspect_err(|e| warn!("Failed to parse requirement '{}': {}", requirement, e))
+        .ok()?;
+
+    // Skip URL requirements (`pkg @ file:...`) and requirements without a version
+    let Some(VersionOrUrl::VersionSpecifier(_)) = req.version_or_url else {
+        return None;
+    };
+
+    // The version ends before the markers, and before the closing
+    // parenthesis of the `pkg (>=1.0)` form
+    let start = requirement.find(['<', '>', '=', '!', '~'])?;
+    let end = start
+        + requirement[start..]
+            .find(';')
+            .unwrap_or(requirement.len() - start);
+    let version = requirement[start..end]
+        .trim_end()
+        .trim_end_matches(')')
+        .trim_end();
+    if version.is_empty() {
+        return None;
+    }
+
+    let start_offset = line.content_offset(indent + start);
+    let end_offset = line.content_offset(indent + start + version.len() - 1) + 1;
+    let before = &content[..start_offset];
+    let line_number = before.matches('\n').count();
+    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1);
+
+    let name = req.name.to_string();
+    Some(PackageInfo {
+        registry_url: Some(registry_type_to_url(RegistryType::PyPI, &name)),
+        name,
+        version: version.to_string(),
+        commit_hash: None,
+        registry_type: RegistryType::PyPI,
+        start_offset,
+        end_offset,
+        line: line_number,
+        column,
+        section_name: None,
+        section_line: None,
+        extra_info: None,
+    })
+}
+
+/// Remove a `#` comment, which starts the line or follows whitespace
+fn strip_comment(line: &str) -> &str {
+    let comment = line
+        .char_indices()
+        .find(|(i, c)| *c == '#' && (*i == 0 || line[..*i].ends_with(char::is_whitespace)))
+        .map(|(i, _)| i);
+    comment.map_or(line, |i| &line[..i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn parse(content: &str) -> Vec<(String, String)> {
+        RequirementsTxtParser::new()
+            .parse(content)
+            .unwrap()
+            .into_iter()
+            .map(|package| {
+                // The range covers exactly the version
+                assert_eq!(
+                    content[package.start_offset..package.end_offset].replace("\\\n", ""),
+                    package.version
+                );
+                (package.name, package.version)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_extracts_requirement_with_position() {
+        let content = "# Web\nflask==2.0.0\n";
+
+        let result = RequirementsTxtParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![PackageInfo {
+                name: "flask".to_string(),
+                version: "==2.0.0".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::PyPI,
+                registry_url: Some("https://pypi.org/project/flask/".to_string()),
+                start_offset: 11,
+                end_offset: 18,
+                line: 1,
+                column: 5,
+                section_name: None,
+                section_line: None,
+                extra_info: None,
+            }]
+        );
+    }
+
+    #[rstest]
+    #[case("requests>=2.28,<3.0", ("requests", ">=2.28,<3.0"))]
+    #[case("Flask[async]>=2.0", ("flask", ">=2.0"))]
+    #[case("pywin32>=300; sys_platform=='win32'", ("pywin32", ">=300"))]
+    #[case("numpy ~= 1.26  # pinned for torch", ("numpy", "~= 1.26"))]
+    #[case("django (>=4.2)", ("django", ">=4.2"))]
+    #[case(
+        "urllib3==2.2.1 --hash=sha256:450b20ec296a467077128bff42b73080516e71b56ff59a60a02bef2232c4fa9d",
+        ("urllib3", "==2.2.1")
+    )]
+    fn parse_extracts_version_spec(#[case] content: &str, #[case] expected: (&str, &str)) {
+        assert_eq!(
+            parse(content),
+            vec![(expected.0.to_string(), expected.1.to_string())]
+        );
+    }
+
+    #[rstest]
+    #[case("-r other.txt")]
+    #[case("-e .")]
+    #[case("--index-url https://pypi.example.com/simple")]
+    #[case("git+https://github.com/psf/requests.git@v2.31.0#egg=requests")]
+    #[case("./wheels/pkg-1.0.0-py3-none-any.whl")]
+    #[case("requests @ https://example.com/requests-2.31.0.tar.gz")]
+    #[case("requests")]
+    #[case("# requests==2.31.0")]
+    #[case("")]
+    fn parse_skips_lines_without_version(#[case] content: &str) {
+        assert!(parse(content).is_empty());
+    }
+
+    #[test]
+    fn parse_joins_continued_lines() {
+        let content = "requests>=2.28,\\\n    <3.0\nflask \\\n  ==2.0.0\n";
+
+        assert_eq!(
+            parse(content),
+            vec![
+                ("requests".to_string(), ">=2.28,    <3.0".to_string()),
+                ("flask".to_string(), "==2.0.0".to_string()),
+            ]
+        );
+        let flask = &RequirementsTxtParser::new().parse(content).unwrap()[1];
+        assert_eq!((flask.line, flask.column), (3, 2));
+    }
+}
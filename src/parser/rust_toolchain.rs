@@ -0,0 +1,179 @@
+//! rust-toolchain.toml parser
+//!
+//! Only the `channel` of the `[toolchain]` table is checked. It is either a
+//! release (`1.75.0`, `1.75`) or a dated nightly (`nightly-2024-01-15`).
+//! Undated channels (`stable`, `beta`, `nightly`) always track the newest
+//! toolchain and are skipped, as are `components`, `targets` and `profile`,
+//! which name parts of the toolchain rather than versions.
+
+use tracing::warn;
+
+use crate::parser::traits::{ParseError, Parser};
+use crate::parser::types::{PackageInfo, RegistryType, registry_type_to_url};
+
+/// Package name reported for the pinned Rust toolchain
+pub const RUST_TOOLCHAIN_PACKAGE_NAME: &str = "rust";
+
+/// Parser for rust-toolchain.toml files
+pub struct RustToolchainParser;
+
+impl RustToolchainParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for RustToolchainParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for RustToolchainParser {
+    fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError> {
+        let mut parser = tree_sitter::Parser::new();
+        let language = tree_sitter_toml_ng::LANGUAGE;
+        parser.set_language(&language.into()).map_err(|e| {
+            warn!("Failed to set TOML language for tree-sitter: {}", e);
+            ParseError::TreeSitter(e.to_string())
+        })?;
+
+        let tree = parser.parse(content, None).ok_or_else(|| {
+            warn!("Failed to parse TOML content");
+            ParseError::ParseFailed("Failed to parse TOML".to_string())
+        })?;
+
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        Ok(root
+            .children(&mut cursor)
+            .filter(|child| {
+                child.kind() == "table" && table_name(*child, content) == Some("toolchain")
+            })
+            .find_map(|table| self.extract_channel(table, content))
+            .into_iter()
+            .collect())
+    }
+}
+
+impl RustToolchainParser {
+    /// Extract the `channel = "..."` pair of the `[toolchain]` table
+    fn extract_channel(&self, table: tree_sitter::Node, content: &str) -> Option<PackageInfo> {
+        let mut cursor = table.walk();
+        let value = table.children(&mut cursor).find_map(|pair| {
+            if pair.kind() != "pair" {
+                return None;
+            }
+            let key = pair.child(0)?;
+            if content[key.byte_range()].trim_matches('"') != "channel" {
+                return None;
+            }
+            let mut pair_cursor = pair.walk();
+            pair.children(&mut pair_cursor)
+                .find(|child| child.kind() == "string")
+        })?;
+
+        let channel = content[value.byte_range()].trim_matches(|c| c == '"' || c == '\'');
+        if !is_checkable_channel(channel) {
+            return None;
+        }
+
+        let start_point = value.start_position();
+        Some(PackageInfo {
+            name: RUST_TOOLCHAIN_PACKAGE_NAME.to_string(),
+            version: channel.to_string(),
+            commit_hash: None,
+            registry_type: RegistryType::RustToolchain,
+            registry_url: Some(registry_type_to_url(
+                RegistryType::RustToolchain,
+                RUST_TOOLCHAIN_PACKAGE_NAME,
+            )),
+            start_offset: value.start_byte() + 1,
+            end_offset: value.end_byte() - 1,
+            line: start_point.row,
+            column: start_point.column + 1,
+            section_name: None,
+            section_line: None,
+            extra_info: None,
+        })
+    }
+}
+
+/// Name of a `[table]` node
+fn table_name<'a>(table: tree_sitter::Node, content: &'a str) -> Option<&'a str> {
+    let mut cursor = table.walk();
+    table
+        .children(&mut cursor)
+        .find(|child| child.kind() == "bare_key" || child.kind() == "dotted_key")
+        .map(|key| &content[key.byte_range()])
+}
+
+/// Whether the channel pins a release (`1.75.0`, `1.75`) or a nightly date
+fn is_checkable_channel(channel: &str) -> bool {
+    if let Some(date) = channel.strip_prefix("nightly-") {
+        return chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok();
+    }
+    let parts: Vec<&str> = channel.split('.').collect();
+    (2..=3).contains(&parts.len())
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn parse_extracts_channel_only() {
+        let content = r#"[toolchain]
+channel = "nightly-2024-01-15"
+components = ["rust-src", "clippy"]
+targets = ["wasm32-unknown-unknown"]
+profile = "minimal"
+"#;
+
+        let result = RustToolchainParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            result,
+            vec![PackageInfo {
+                name: "rust".to_string(),
+                version: "nightly-2024-01-15".to_string(),
+                commit_hash: None,
+                registry_type: RegistryType::RustToolchain,
+                registry_url: Some("https://releases.rs".to_string()),
+                start_offset: 23,
+                end_offset: 41,
+                line: 1,
+                column: 11,
+                section_name: None,
+                section_line: None,
+                extra_info: None,
+            }]
+        );
+    }
+
+    #[rstest]
+    #[case("[toolchain]\nchannel = \"1.75.0\"\n", Some("1.75.0"))]
+    #[case("[toolchain]\nchannel = '1.75'\n", Some("1.75"))]
+    #[case(
+        "[toolchain]\nchannel = \"nightly-2024-01-15\"\n",
+        Some("nightly-2024-01-15")
+    )]
+    #[case("[toolchain]\nchannel = \"stable\"\n", None)]
+    #[case("[toolchain]\nchannel = \"nightly\"\n", None)]
+    #[case("[toolchain]\nchannel = \"nightly-2024-13-45\"\n", None)]
+    #[case("[toolchain]\ncomponents = [\"rustfmt\"]\n", None)]
+    #[case("[other]\nchannel = \"1.75.0\"\n", None)]
+    #[case("channel = \"1.75.0\"\n", None)]
+    fn parse_returns_expected_channel(#[case] content: &str, #[case] expected: Option<&str>) {
+        let result = RustToolchainParser::new().parse(content).unwrap();
+
+        assert_eq!(
+            result.first().map(|package| package.version.as_str()),
+            expected
+        );
+    }
+}
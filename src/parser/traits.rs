@@ -3,13 +3,31 @@
 #[cfg(test)]
 use mockall::automock;
 
-use crate::parser::types::PackageInfo;
+use crate::parser::types::{DocumentMetadata, PackageInfo};
+
+/// Packages of a document together with its document-level metadata
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOutput {
+    pub packages: Vec<PackageInfo>,
+    pub metadata: DocumentMetadata,
+}
 
 /// Trait for parsing package files
 #[cfg_attr(test, automock)]
 pub trait Parser: Send + Sync {
     /// Parse the content and extract package information
     fn parse(&self, content: &str) -> Result<Vec<PackageInfo>, ParseError>;
+
+    /// Parse the content into packages and document-level metadata
+    ///
+    /// Formats without document-level metadata return the packages of
+    /// [`Parser::parse`] with default metadata.
+    fn parse_document(&self, content: &str) -> Result<ParseOutput, ParseError> {
+        Ok(ParseOutput {
+            packages: self.parse(content)?,
+            metadata: DocumentMetadata::default(),
+        })
+    }
 }
 
 /// Error type for parsing operations
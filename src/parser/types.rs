@@ -1,9 +1,13 @@
 //! Common types for parsers
 
 /// Type of package registry
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// Serializes to the same string as [`RegistryType::as_str`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RegistryType {
     /// GitHub Actions (actions/checkout@v3)
+    #[serde(rename = "github_actions")]
     GitHubActions,
     /// npm registry (package.json)
     Npm,
@@ -15,10 +19,24 @@ pub enum RegistryType {
     PnpmCatalog,
     /// JSR (deno.json, deno.jsonc)
     Jsr,
-    /// PyPI (pyproject.toml)
+    /// PyPI (pyproject.toml, requirements.txt)
+    #[serde(rename = "pypi")]
     PyPI,
     /// Docker (compose.yaml)
     Docker,
+    /// Conda (environment.yml)
+    Conda,
+    /// Node.js releases (.nvmrc, .node-version)
+    NodeRelease,
+    /// Go toolchain releases (the go.mod `go` directive)
+    GoToolchain,
+    /// Rust toolchain releases (rust-toolchain.toml)
+    RustToolchain,
+    /// RubyGems (Gemfile)
+    #[serde(rename = "rubygems")]
+    RubyGems,
+    /// pub.dev (pubspec.yaml)
+    Pub,
 }
 
 impl RegistryType {
@@ -33,6 +51,32 @@ impl RegistryType {
             RegistryType::Jsr => "jsr",
             RegistryType::PyPI => "pypi",
             RegistryType::Docker => "docker",
+            RegistryType::Conda => "conda",
+            RegistryType::NodeRelease => "node_release",
+            RegistryType::GoToolchain => "go_toolchain",
+            RegistryType::RustToolchain => "rust_toolchain",
+            RegistryType::RubyGems => "rubygems",
+            RegistryType::Pub => "pub",
+        }
+    }
+
+    /// Returns the human-readable name for user-facing messages
+    pub fn as_display_name(&self) -> &'static str {
+        match self {
+            RegistryType::GitHubActions => "GitHub Actions",
+            RegistryType::Npm => "npm",
+            RegistryType::CratesIo => "crates.io",
+            RegistryType::GoProxy => "Go Proxy",
+            RegistryType::PnpmCatalog => "pnpm Catalog",
+            RegistryType::Jsr => "JSR",
+            RegistryType::PyPI => "PyPI",
+            RegistryType::Docker => "Docker",
+            RegistryType::Conda => "Conda",
+            RegistryType::NodeRelease => "Node.js",
+            RegistryType::GoToolchain => "Go",
+            RegistryType::RustToolchain => "Rust",
+            RegistryType::RubyGems => "RubyGems",
+            RegistryType::Pub => "pub.dev",
         }
     }
 }
@@ -50,34 +94,184 @@ impl std::str::FromStr for RegistryType {
             "jsr" => Ok(RegistryType::Jsr),
             "pypi" => Ok(RegistryType::PyPI),
             "docker" => Ok(RegistryType::Docker),
+            "conda" => Ok(RegistryType::Conda),
+            "node_release" => Ok(RegistryType::NodeRelease),
+            "go_toolchain" => Ok(RegistryType::GoToolchain),
+            "rust_toolchain" => Ok(RegistryType::RustToolchain),
+            "rubygems" => Ok(RegistryType::RubyGems),
+            "pub" => Ok(RegistryType::Pub),
             _ => Err(()),
         }
     }
 }
 
+impl TryFrom<&str> for RegistryType {
+    type Error = ();
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for RegistryType {
+    type Error = ();
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 /// Detect the appropriate parser type based on URI
 pub fn detect_parser_type(uri: &str) -> Option<RegistryType> {
     if is_github_actions_workflow(uri) {
         Some(RegistryType::GitHubActions)
-    } else if uri.ends_with("/package.json") {
+    } else if uri.ends_with("/package.json")
+        || uri.ends_with("/package-lock.json")
+        || uri.ends_with("/bun.lock")
+    {
         Some(RegistryType::Npm)
-    } else if uri.ends_with("/Cargo.toml") {
+    } else if uri.ends_with("/Cargo.toml") || uri.ends_with("/Cargo.lock") {
         Some(RegistryType::CratesIo)
-    } else if uri.ends_with("/go.mod") {
+    } else if uri.ends_with("/go.mod") || is_go_work_file(uri) {
         Some(RegistryType::GoProxy)
     } else if uri.ends_with("/pnpm-workspace.yaml") {
         Some(RegistryType::PnpmCatalog)
     } else if uri.ends_with("/deno.json") || uri.ends_with("/deno.jsonc") {
         Some(RegistryType::Jsr)
-    } else if uri.ends_with("/pyproject.toml") {
+    } else if uri.ends_with("/pyproject.toml")
+        || uri.ends_with("/poetry.lock")
+        || is_requirements_file(uri)
+    {
         Some(RegistryType::PyPI)
-    } else if is_compose_file(uri) {
+    } else if is_compose_file(uri) || is_dockerfile(uri) {
         Some(RegistryType::Docker)
+    } else if uri.ends_with("/environment.yml")
+        || uri.ends_with("/environment.yaml")
+        || uri.ends_with("/conda-lock.yml")
+    {
+        Some(RegistryType::Conda)
+    } else if uri.ends_with("/.nvmrc") || uri.ends_with("/.node-version") {
+        Some(RegistryType::NodeRelease)
+    } else if uri.ends_with("/rust-toolchain.toml") {
+        Some(RegistryType::RustToolchain)
+    } else if uri.ends_with("/Gemfile") {
+        Some(RegistryType::RubyGems)
+    } else if uri.ends_with("/pubspec.yaml") {
+        Some(RegistryType::Pub)
+    } else {
+        None
+    }
+}
+
+/// Build the registry page URL for a package, for hover and go-to-definition
+pub fn registry_type_to_url(registry_type: RegistryType, name: &str) -> String {
+    match registry_type {
+        RegistryType::GitHubActions => {
+            // Actions in subdirectories (owner/repo/path) live in the owner/repo repository
+            let repository: Vec<&str> = name.splitn(3, '/').take(2).collect();
+            format!("https://github.com/{}", repository.join("/"))
+        }
+        RegistryType::Npm | RegistryType::PnpmCatalog => {
+            format!("https://www.npmjs.com/package/{}", name)
+        }
+        RegistryType::CratesIo => format!("https://crates.io/crates/{}", name),
+        RegistryType::GoProxy => format!("https://pkg.go.dev/{}", name),
+        RegistryType::Jsr => format!("https://jsr.io/{}", name),
+        RegistryType::PyPI => format!("https://pypi.org/project/{}/", name),
+        RegistryType::Docker => match name.strip_prefix("library/") {
+            Some(official) => format!("https://hub.docker.com/_/{}", official),
+            None if name.starts_with("ghcr.io/") => format!("https://{}", name),
+            None => format!("https://hub.docker.com/r/{}", name),
+        },
+        RegistryType::Conda => format!("https://anaconda.org/conda-forge/{}", name),
+        RegistryType::NodeRelease => "https://nodejs.org/en/about/previous-releases".to_string(),
+        RegistryType::GoToolchain => "https://go.dev/doc/devel/release".to_string(),
+        RegistryType::RustToolchain => "https://releases.rs".to_string(),
+        RegistryType::RubyGems => format!("https://rubygems.org/gems/{}", name),
+        RegistryType::Pub => format!("https://pub.dev/packages/{}", name),
+    }
+}
+
+/// Number of leading lines scanned for a top-level `jobs:` key
+const WORKFLOW_SCAN_LINES: usize = 50;
+
+/// Detect the parser type from document content, for files whose path is
+/// not recognized by [`detect_parser_type`].
+///
+/// - `.yml`/`.yaml` with a top-level `jobs:` key in the first 50 lines: GitHub Actions
+/// - `.json` with a root `dependencies` or `devDependencies` object: npm
+pub fn detect_parser_type_from_content(uri: &str, content: &str) -> Option<RegistryType> {
+    if uri.ends_with(".yml") || uri.ends_with(".yaml") {
+        content
+            .lines()
+            .take(WORKFLOW_SCAN_LINES)
+            .any(|line| line.trim_end() == "jobs:" || line.starts_with("jobs: "))
+            .then_some(RegistryType::GitHubActions)
+    } else if uri.ends_with(".json") {
+        let value: serde_json::Value = serde_json::from_str(content).ok()?;
+        let root = value.as_object()?;
+        ["dependencies", "devDependencies"]
+            .iter()
+            .any(|key| root.get(*key).is_some_and(serde_json::Value::is_object))
+            .then_some(RegistryType::Npm)
     } else {
         None
     }
 }
 
+/// Lock file names. Lock files are handled by dedicated parsers and their
+/// diagnostics use a separate severity, since they are updated by tooling
+/// (e.g. `poetry update`) rather than edited by hand.
+pub const LOCK_FILE_NAMES: &[&str] = &[
+    "poetry.lock",
+    "package-lock.json",
+    "bun.lock",
+    "Cargo.lock",
+    "conda-lock.yml",
+];
+
+/// Check whether the URI points to a lock file
+pub fn is_lock_file(uri: &str) -> bool {
+    LOCK_FILE_NAMES
+        .iter()
+        .any(|name| uri.ends_with(&format!("/{}", name)))
+}
+
+/// Check whether the URI points to a binary lock file (`bun.lockb`), which
+/// cannot be parsed
+pub fn is_binary_lock_file(uri: &str) -> bool {
+    uri.ends_with("/bun.lockb")
+}
+
+/// Check whether the URI points to a pip requirements file:
+/// `requirements.txt`, `requirements-dev.txt`, `dev-requirements.txt` or
+/// any `.txt` file in a `requirements/` directory
+pub fn is_requirements_file(uri: &str) -> bool {
+    let Some((dir, file_name)) = uri.rsplit_once('/') else {
+        return false;
+    };
+    let Some(stem) = file_name.strip_suffix(".txt") else {
+        return false;
+    };
+    stem.starts_with("requirements")
+        || stem.ends_with("requirements")
+        || dir.ends_with("/requirements")
+}
+
+/// Check whether the URI points to a Go workspace file (`go.work`)
+pub fn is_go_work_file(uri: &str) -> bool {
+    uri.ends_with("/go.work")
+}
+
+/// Check whether the URI points to a Dockerfile: `Dockerfile`,
+/// `Dockerfile.*` (`Dockerfile.dev`) or `*.dockerfile`
+pub fn is_dockerfile(uri: &str) -> bool {
+    let file_name = uri.rsplit_once('/').map_or(uri, |(_, file_name)| file_name);
+    file_name == "Dockerfile"
+        || file_name.starts_with("Dockerfile.")
+        || file_name.ends_with(".dockerfile")
+}
+
 fn is_compose_file(uri: &str) -> bool {
     uri.ends_with("/compose.yaml")
         || uri.ends_with("/compose.yml")
@@ -106,6 +300,45 @@ pub enum ExtraInfo {
         /// End offset of the comment
         comment_end_offset: usize,
     },
+    /// npm lock file specific: resolved tarball information
+    NpmLock {
+        /// Exact tarball URL the version was resolved to
+        resolved: String,
+    },
+    /// package.json specific: a `catalog:` version resolved from the pnpm
+    /// workspace catalogs instead of written in place
+    PnpmCatalogRef {
+        /// Referenced catalog; `"default"` for a bare `catalog:`
+        catalog_name: String,
+    },
+    /// package.json specific: the package's own metadata, attached to the
+    /// synthetic entry for the package itself (not one of its dependencies)
+    PackageJsonMeta {
+        /// Whether the package is marked `"private": true` and never published
+        private: bool,
+        /// The package's own `name`
+        name: String,
+        /// The package's own `version`
+        own_version: String,
+    },
+    /// Cargo.toml specific: a dependency fetched from a git repository
+    /// rather than crates.io
+    CargoGitDep {
+        /// Repository URL from the `git` key
+        url: String,
+        /// Pinned `rev`, `tag` or `branch`; None tracks the default branch
+        rev_or_tag: Option<String>,
+    },
+    /// go.mod specific: a pseudo-version pinning an untagged commit
+    GoPseudoVersion {
+        /// `YYYYMMDDHHMMSS` UTC commit timestamp
+        commit_timestamp: String,
+        /// 12-character abbreviated commit hash
+        commit_hash: String,
+    },
+    /// conda-lock.yml specific: a package pinned by `conda-lock`, which is
+    /// updated with `conda-lock update` rather than edited by hand
+    CondaLock,
 }
 
 /// Information about a package dependency found in a file
@@ -120,6 +353,8 @@ pub struct PackageInfo {
     pub commit_hash: Option<String>,
     /// Type of registry this package belongs to
     pub registry_type: RegistryType,
+    /// Registry page URL for the package (e.g., "https://www.npmjs.com/package/lodash")
+    pub registry_url: Option<String>,
     /// Byte offset of the version string in the source (start)
     pub start_offset: usize,
     /// Byte offset of the version string in the source (end)
@@ -128,15 +363,108 @@ pub struct PackageInfo {
     pub line: usize,
     /// Column number (0-indexed)
     pub column: usize,
+    /// Section the dependency is declared in (e.g. `dev-dependencies`, a
+    /// `require` block), for parsers with clear section boundaries
+    pub section_name: Option<String>,
+    /// Line (0-indexed) of the header opening the section (`[dependencies]`,
+    /// `require (`), set along with `section_name`
+    pub section_line: Option<usize>,
     /// Registry-specific additional information
     pub extra_info: Option<ExtraInfo>,
 }
 
+impl PackageInfo {
+    /// Whether the dependency comes from a git repository, so it has no
+    /// registry versions to check, fetch or upgrade to
+    pub fn is_git_dependency(&self) -> bool {
+        matches!(self.extra_info, Some(ExtraInfo::CargoGitDep { .. }))
+    }
+
+    /// Whether the version token can be replaced as a whole, by code actions
+    /// or rename
+    ///
+    /// Excludes commit-pinned actions (the version comes from a comment),
+    /// specifiers without an explicit version (e.g. `jsr:@std/path`), whose
+    /// range does not hold the version string, pnpm `catalog:` references and
+    /// git dependencies.
+    pub fn has_replaceable_version(&self) -> bool {
+        !self.version.is_empty()
+            && self.commit_hash.is_none()
+            && !self.is_git_dependency()
+            && !matches!(self.extra_info, Some(ExtraInfo::PnpmCatalogRef { .. }))
+            && self.end_offset.saturating_sub(self.start_offset) == self.version.len()
+    }
+}
+
+/// Document-level information that isn't tied to a single dependency
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentMetadata {
+    /// package.json declares `"type": "module"`, so the project uses ES
+    /// modules and CommonJS-only packages may not work
+    pub is_esm: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    #[case(RegistryType::GitHubActions)]
+    #[case(RegistryType::Npm)]
+    #[case(RegistryType::CratesIo)]
+    #[case(RegistryType::GoProxy)]
+    #[case(RegistryType::PnpmCatalog)]
+    #[case(RegistryType::Jsr)]
+    #[case(RegistryType::PyPI)]
+    #[case(RegistryType::Docker)]
+    #[case(RegistryType::Conda)]
+    #[case(RegistryType::NodeRelease)]
+    #[case(RegistryType::GoToolchain)]
+    #[case(RegistryType::RustToolchain)]
+    #[case(RegistryType::RubyGems)]
+    #[case(RegistryType::Pub)]
+    fn registry_type_string_forms_match_as_str(#[case] registry_type: RegistryType) {
+        let json = serde_json::to_string(&registry_type).unwrap();
+
+        assert_eq!(json, format!("\"{}\"", registry_type.as_str()));
+        assert_eq!(
+            serde_json::from_str::<RegistryType>(&json).unwrap(),
+            registry_type
+        );
+        assert_eq!(
+            RegistryType::try_from(registry_type.as_str()),
+            Ok(registry_type)
+        );
+        assert_eq!(
+            RegistryType::try_from(registry_type.as_str().to_string()),
+            Ok(registry_type)
+        );
+    }
+
+    #[test]
+    fn registry_type_rejects_unknown_string() {
+        assert_eq!(RegistryType::try_from("maven"), Err(()));
+        assert!(serde_json::from_str::<RegistryType>("\"maven\"").is_err());
+    }
+
+    #[rstest]
+    #[case(RegistryType::GitHubActions, "GitHub Actions")]
+    #[case(RegistryType::CratesIo, "crates.io")]
+    #[case(RegistryType::GoProxy, "Go Proxy")]
+    #[case(RegistryType::PnpmCatalog, "pnpm Catalog")]
+    #[case(RegistryType::NodeRelease, "Node.js")]
+    #[case(RegistryType::GoToolchain, "Go")]
+    #[case(RegistryType::RustToolchain, "Rust")]
+    #[case(RegistryType::RubyGems, "RubyGems")]
+    #[case(RegistryType::Pub, "pub.dev")]
+    fn as_display_name_returns_human_readable_name(
+        #[case] registry_type: RegistryType,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(registry_type.as_display_name(), expected);
+    }
+
     #[test]
     fn extra_info_github_actions_holds_comment_data() {
         let extra = ExtraInfo::GitHubActions {
@@ -155,6 +483,7 @@ mod tests {
                 assert_eq!(comment_start_offset, 100);
                 assert_eq!(comment_end_offset, 108);
             }
+            other => panic!("unexpected extra info: {:?}", other),
         }
     }
 
@@ -165,10 +494,13 @@ mod tests {
             version: "v4.1.6".to_string(),
             commit_hash: Some("8e5e7e5ab8b370d6c329ec480221332ada57f0ab".to_string()),
             registry_type: RegistryType::GitHubActions,
+            registry_url: Some("https://github.com/actions/checkout".to_string()),
             start_offset: 50,
             end_offset: 90,
             line: 5,
             column: 10,
+            section_name: None,
+            section_line: None,
             extra_info: Some(ExtraInfo::GitHubActions {
                 comment_text: "v4.1.6".to_string(),
                 comment_start_offset: 92,
@@ -179,6 +511,56 @@ mod tests {
         assert!(info.extra_info.is_some());
     }
 
+    #[rstest]
+    #[case("^1.0.0", 6, None, None, true)]
+    #[case("", 0, None, None, false)]
+    #[case("1.0.0", 11, None, None, false)]
+    #[case(
+        "v4.1.6",
+        6,
+        Some("8e5e7e5ab8b370d6c329ec480221332ada57f0ab"),
+        None,
+        false
+    )]
+    #[case(
+        "1.0.0",
+        5,
+        None,
+        Some(ExtraInfo::PnpmCatalogRef { catalog_name: "default".to_string() }),
+        false
+    )]
+    #[case(
+        "1.0.0",
+        5,
+        None,
+        Some(ExtraInfo::CargoGitDep { url: "https://github.com/serde-rs/serde".to_string(), rev_or_tag: None }),
+        false
+    )]
+    fn has_replaceable_version_requires_version_token_spanning_its_range(
+        #[case] version: &str,
+        #[case] range_len: usize,
+        #[case] commit_hash: Option<&str>,
+        #[case] extra_info: Option<ExtraInfo>,
+        #[case] expected: bool,
+    ) {
+        let info = PackageInfo {
+            name: "pkg".to_string(),
+            version: version.to_string(),
+            commit_hash: commit_hash.map(str::to_string),
+            registry_type: RegistryType::Npm,
+            registry_url: None,
+            start_offset: 10,
+            end_offset: 10 + range_len,
+            line: 0,
+            column: 0,
+            section_name: None,
+            section_line: None,
+            extra_info,
+        };
+
+        assert_eq!(info.has_replaceable_version(), expected);
+    }
+
     #[rstest]
     #[case(".github/workflows/ci.yml", Some(RegistryType::GitHubActions))]
     #[case(".github/workflows/release.yaml", Some(RegistryType::GitHubActions))]
@@ -200,8 +582,13 @@ mod tests {
         Some(RegistryType::GitHubActions)
     )]
     #[case("/path/to/package.json", Some(RegistryType::Npm))]
+    #[case("/path/to/package-lock.json", Some(RegistryType::Npm))]
+    #[case("/path/to/bun.lock", Some(RegistryType::Npm))]
+    #[case("/path/to/bun.lockb", None)]
     #[case("/path/to/Cargo.toml", Some(RegistryType::CratesIo))]
     #[case("/path/to/go.mod", Some(RegistryType::GoProxy))]
+    #[case("/path/to/go.work", Some(RegistryType::GoProxy))]
+    #[case("/path/to/go.work.sum", None)]
     #[case("/path/to/pnpm-workspace.yaml", Some(RegistryType::PnpmCatalog))]
     #[case("/project/pnpm-workspace.yaml", Some(RegistryType::PnpmCatalog))]
     #[case(
@@ -217,11 +604,36 @@ mod tests {
     #[case("/path/to/pyproject.toml", Some(RegistryType::PyPI))]
     #[case("/project/pyproject.toml", Some(RegistryType::PyPI))]
     #[case("file:///home/user/pyproject.toml", Some(RegistryType::PyPI))]
+    #[case("/path/to/poetry.lock", Some(RegistryType::PyPI))]
+    #[case("/path/to/requirements.txt", Some(RegistryType::PyPI))]
+    #[case("/path/to/requirements-dev.txt", Some(RegistryType::PyPI))]
+    #[case("/path/to/dev-requirements.txt", Some(RegistryType::PyPI))]
+    #[case("file:///home/user/requirements/test.txt", Some(RegistryType::PyPI))]
+    #[case("/path/to/requirements.in", None)]
+    #[case("/path/to/notes.txt", None)]
+    #[case("/path/to/Cargo.lock", Some(RegistryType::CratesIo))]
     #[case("/path/to/compose.yaml", Some(RegistryType::Docker))]
     #[case("/path/to/compose.yml", Some(RegistryType::Docker))]
     #[case("/path/to/docker-compose.yaml", Some(RegistryType::Docker))]
     #[case("/path/to/docker-compose.yml", Some(RegistryType::Docker))]
     #[case("file:///home/user/compose.yaml", Some(RegistryType::Docker))]
+    #[case("file:///home/user/app/Dockerfile", Some(RegistryType::Docker))]
+    #[case("/path/to/Dockerfile.dev", Some(RegistryType::Docker))]
+    #[case("/path/to/api.dockerfile", Some(RegistryType::Docker))]
+    #[case("/path/to/Dockerfiles/README.md", None)]
+    #[case("/path/to/environment.yml", Some(RegistryType::Conda))]
+    #[case("/path/to/conda-lock.yml", Some(RegistryType::Conda))]
+    #[case("file:///home/user/environment.yaml", Some(RegistryType::Conda))]
+    #[case("file:///home/user/project/.nvmrc", Some(RegistryType::NodeRelease))]
+    #[case(
+        "file:///home/user/project/rust-toolchain.toml",
+        Some(RegistryType::RustToolchain)
+    )]
+    #[case("/path/to/.node-version", Some(RegistryType::NodeRelease))]
+    #[case("file:///home/user/project/Gemfile", Some(RegistryType::RubyGems))]
+    #[case("/path/to/Gemfile.lock", None)]
+    #[case("file:///home/user/app/pubspec.yaml", Some(RegistryType::Pub))]
+    #[case("/path/to/pubspec.lock", None)]
     #[case("workflow.yml", None)]
     #[case("random.txt", None)]
     fn detect_parser_type_returns_expected(
@@ -230,4 +642,119 @@ mod tests {
     ) {
         assert_eq!(detect_parser_type(uri), expected);
     }
+
+    #[rstest]
+    #[case(
+        "file:///ci/workspace.yml",
+        "name: CI\non: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n",
+        Some(RegistryType::GitHubActions)
+    )]
+    #[case(
+        "file:///ci/workspace.yaml",
+        "jobs: {}\n",
+        Some(RegistryType::GitHubActions)
+    )]
+    #[case("file:///app/config.yaml", "server:\n  jobs: 4\n", None)]
+    #[case(
+        "file:///app/deps.json",
+        r#"{"dependencies": {"lodash": "^4.17.0"}}"#,
+        Some(RegistryType::Npm)
+    )]
+    #[case(
+        "file:///app/deps.json",
+        r#"{"devDependencies": {"vitest": "^1.0.0"}}"#,
+        Some(RegistryType::Npm)
+    )]
+    #[case(
+        "file:///app/settings.json",
+        r#"{"editor": {"dependencies": {}}}"#,
+        None
+    )]
+    #[case("file:///app/broken.json", "{", None)]
+    #[case("file:///app/notes.txt", "jobs:\n", None)]
+    fn detect_parser_type_from_content_returns_expected(
+        #[case] uri: &str,
+        #[case] content: &str,
+        #[case] expected: Option<RegistryType>,
+    ) {
+        assert_eq!(detect_parser_type_from_content(uri, content), expected);
+    }
+
+    #[rstest]
+    #[case(
+        RegistryType::GitHubActions,
+        "actions/checkout",
+        "https://github.com/actions/checkout"
+    )]
+    #[case(
+        RegistryType::GitHubActions,
+        "github/codeql-action/init",
+        "https://github.com/github/codeql-action"
+    )]
+    #[case(
+        RegistryType::Npm,
+        "@types/node",
+        "https://www.npmjs.com/package/@types/node"
+    )]
+    #[case(
+        RegistryType::PnpmCatalog,
+        "react",
+        "https://www.npmjs.com/package/react"
+    )]
+    #[case(RegistryType::CratesIo, "serde", "https://crates.io/crates/serde")]
+    #[case(
+        RegistryType::GoProxy,
+        "golang.org/x/text",
+        "https://pkg.go.dev/golang.org/x/text"
+    )]
+    #[case(RegistryType::Jsr, "@std/path", "https://jsr.io/@std/path")]
+    #[case(RegistryType::PyPI, "requests", "https://pypi.org/project/requests/")]
+    #[case(
+        RegistryType::Docker,
+        "library/nginx",
+        "https://hub.docker.com/_/nginx"
+    )]
+    #[case(
+        RegistryType::Docker,
+        "bitnami/redis",
+        "https://hub.docker.com/r/bitnami/redis"
+    )]
+    #[case(RegistryType::Docker, "ghcr.io/owner/app", "https://ghcr.io/owner/app")]
+    #[case(RegistryType::Conda, "numpy", "https://anaconda.org/conda-forge/numpy")]
+    #[case(
+        RegistryType::NodeRelease,
+        "node",
+        "https://nodejs.org/en/about/previous-releases"
+    )]
+    #[case(RegistryType::GoToolchain, "go", "https://go.dev/doc/devel/release")]
+    #[case(RegistryType::RustToolchain, "rust", "https://releases.rs")]
+    #[case(RegistryType::RubyGems, "rails", "https://rubygems.org/gems/rails")]
+    #[case(RegistryType::Pub, "http", "https://pub.dev/packages/http")]
+    fn registry_type_to_url_returns_expected(
+        #[case] registry_type: RegistryType,
+        #[case] name: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(registry_type_to_url(registry_type, name), expected);
+    }
+
+    #[rstest]
+    #[case("file:///project/poetry.lock", true)]
+    #[case("file:///project/package-lock.json", true)]
+    #[case("file:///project/bun.lock", true)]
+    #[case("file:///project/Cargo.lock", true)]
+    #[case("file:///project/conda-lock.yml", true)]
+    #[case("file:///project/package.json", false)]
+    #[case("file:///project/pyproject.toml", false)]
+    #[case("file:///project/my-poetry.lock", false)]
+    fn is_lock_file_returns_expected(#[case] uri: &str, #[case] expected: bool) {
+        assert_eq!(is_lock_file(uri), expected);
+    }
+
+    #[rstest]
+    #[case("file:///project/bun.lockb", true)]
+    #[case("file:///project/bun.lock", false)]
+    fn is_binary_lock_file_returns_expected(#[case] uri: &str, #[case] expected: bool) {
+        assert_eq!(is_binary_lock_file(uri), expected);
+    }
 }
@@ -1,14 +1,16 @@
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::{Mutex, MutexGuard};
 
 use rusqlite::Connection;
 use tracing::{debug, info};
 
-use crate::config::FETCH_TIMEOUT_MS;
+use crate::config::{DEFAULT_NOT_FOUND_TTL_MS, FETCH_TIMEOUT_MS};
 use crate::parser::types::RegistryType;
 use crate::version::checker::VersionStorer;
 use crate::version::error::CacheError;
+use crate::version::types::{Advisory, CacheMetadata, PackageMetadata, PackageSummary};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PackageId {
@@ -16,6 +18,10 @@ pub struct PackageId {
     pub package_name: String,
 }
 
+/// Packages looked up per query by the batch lookups (two bound
+/// parameters each)
+const BATCH_LOOKUP_CHUNK_SIZE: usize = 500;
+
 /// Schema migrations
 /// Each version contains a list of SQL statements to execute
 const MIGRATIONS: &[&[&str]] = &[
@@ -23,11 +29,47 @@ const MIGRATIONS: &[&[&str]] = &[
     &["ALTER TABLE packages ADD COLUMN fetching_since INTEGER"],
     // v2: not_found column
     &["ALTER TABLE packages ADD COLUMN not_found INTEGER NOT NULL DEFAULT 0"],
+    // v3: metadata column (JSON-encoded PackageMetadata)
+    &["ALTER TABLE packages ADD COLUMN metadata TEXT"],
+    // v4: HTTP validators for conditional fetches
+    &[
+        "ALTER TABLE packages ADD COLUMN etag TEXT",
+        "ALTER TABLE packages ADD COLUMN last_modified TEXT",
+    ],
+    // v5: registry deprecation message
+    &["ALTER TABLE packages ADD COLUMN deprecated TEXT"],
+    // v6: security advisories (JSON-encoded Vec<Advisory>)
+    &["ALTER TABLE packages ADD COLUMN advisories TEXT"],
+    // v7: versions yanked by the registry
+    &["ALTER TABLE versions ADD COLUMN yanked INTEGER NOT NULL DEFAULT 0"],
 ];
 
+/// Size and age of the cached data, for `version-lsp cache stats`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Package counts keyed by `RegistryType::as_str()`
+    pub packages: HashMap<String, usize>,
+    pub total_versions: usize,
+    /// Oldest and newest `updated_at` (milliseconds since UNIX epoch)
+    pub oldest_updated_at: Option<i64>,
+    pub newest_updated_at: Option<i64>,
+}
+
+/// A cached package and when its versions were last fetched, for
+/// `version-lsp cache list`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedPackage {
+    pub registry_type: String,
+    pub package_name: String,
+    /// `updated_at` (milliseconds since UNIX epoch)
+    pub updated_at: i64,
+}
+
 pub struct Cache {
     conn: Mutex<Connection>,
     refresh_interval: i64,
+    /// Changed at runtime when the configuration is applied
+    not_found_ttl: AtomicI64,
     ignore_prerelease: bool,
 }
 
@@ -50,6 +92,7 @@ impl Cache {
         let cache = Self {
             conn: Mutex::new(conn),
             refresh_interval,
+            not_found_ttl: AtomicI64::new(DEFAULT_NOT_FOUND_TTL_MS),
             ignore_prerelease,
         };
 
@@ -59,6 +102,22 @@ impl Cache {
         Ok(cache)
     }
 
+    /// Set how long (in milliseconds) a "package not found" entry suppresses fetches
+    pub fn with_not_found_ttl(self, not_found_ttl: i64) -> Self {
+        self.set_not_found_ttl(not_found_ttl);
+        self
+    }
+
+    /// SQL condition on the versions table (aliased `v`) leaving out yanked
+    /// versions, unless they are included
+    fn yanked_condition(include_yanked: bool) -> &'static str {
+        if include_yanked {
+            ""
+        } else {
+            "AND v.yanked = 0"
+        }
+    }
+
     /// Acquire database connection lock with proper error handling
     fn lock_conn(&self) -> Result<MutexGuard<'_, Connection>, CacheError> {
         self.conn.lock().map_err(|_| CacheError::LockPoisoned)
@@ -178,16 +237,26 @@ impl Cache {
         &self,
         registry_type: RegistryType,
         package_name: &str,
+    ) -> Result<Vec<String>, CacheError> {
+        self.query_versions(registry_type, package_name, false)
+    }
+
+    fn query_versions(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        include_yanked: bool,
     ) -> Result<Vec<String>, CacheError> {
         let registry_type_str = registry_type.as_str();
         let conn = self.lock_conn()?;
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare(&format!(
             r#"
             SELECT v.version FROM versions v
             JOIN packages p ON v.package_id = p.id
-            WHERE p.registry_type = ?1 AND p.package_name = ?2
+            WHERE p.registry_type = ?1 AND p.package_name = ?2 {}
             "#,
-        )?;
+            Self::yanked_condition(include_yanked)
+        ))?;
 
         let versions = stmt
             .query_map((registry_type_str, package_name), |row| row.get(0))?
@@ -196,6 +265,81 @@ impl Cache {
         Ok(versions)
     }
 
+    /// Versions of several packages, keyed by `(registry_type, package_name)`,
+    /// looked up on an already locked connection
+    fn query_batch_versions(
+        &self,
+        conn: &Connection,
+        packages: &[(RegistryType, &str)],
+        include_yanked: bool,
+    ) -> Result<HashMap<(RegistryType, String), Vec<String>>, CacheError> {
+        let mut versions: HashMap<(RegistryType, String), Vec<String>> = HashMap::new();
+        // Chunked to stay below SQLite's limit on bound parameters
+        for chunk in packages.chunks(BATCH_LOOKUP_CHUNK_SIZE) {
+            let (placeholders, params) = Self::package_lookup(chunk);
+            let mut stmt = conn.prepare(&format!(
+                r#"
+                SELECT p.registry_type, p.package_name, v.version FROM versions v
+                JOIN packages p ON v.package_id = p.id
+                WHERE (p.registry_type, p.package_name) IN (VALUES {}) {}
+                "#,
+                placeholders,
+                Self::yanked_condition(include_yanked)
+            ))?;
+
+            let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?;
+            for row in rows {
+                let (registry_type, package_name, version) = row?;
+                // Only the registry types looked up are returned
+                let Ok(registry_type) = RegistryType::try_from(registry_type.as_str()) else {
+                    continue;
+                };
+                if self.ignore_prerelease && crate::version::semver::is_prerelease(&version) {
+                    continue;
+                }
+                versions
+                    .entry((registry_type, package_name))
+                    .or_default()
+                    .push(version);
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// `(?1, ?2), (?3, ?4), ...` placeholders matching
+    /// `(p.registry_type, p.package_name)` against `packages`, and their parameters
+    fn package_lookup<'a>(packages: &[(RegistryType, &'a str)]) -> (String, Vec<&'a str>) {
+        let placeholders: Vec<_> = (0..packages.len())
+            .map(|i| format!("(?{}, ?{})", 2 * i + 1, 2 * i + 2))
+            .collect();
+        let params = packages
+            .iter()
+            .flat_map(|(registry_type, package_name)| [registry_type.as_str(), *package_name])
+            .collect();
+        (placeholders.join(", "), params)
+    }
+
+    /// The semantically highest of `versions`, leaving out pre-releases if
+    /// they are ignored
+    fn highest_version(&self, versions: Vec<String>) -> Option<String> {
+        versions
+            .into_iter()
+            .filter(|v| !(self.ignore_prerelease && crate::version::semver::is_prerelease(v)))
+            .filter_map(|v| {
+                let parsed = crate::version::semver::parse_version(&v)?;
+                Some((v, parsed))
+            })
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(v, _)| v)
+    }
+
     /// Save dist tags for a package
     pub fn save_dist_tags(
         &self,
@@ -270,6 +414,76 @@ impl Cache {
             Err(e) => Err(e.into()),
         }
     }
+
+    /// Get all dist tags for a package
+    pub fn get_dist_tags(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<HashMap<String, String>, CacheError> {
+        let registry_type_str = registry_type.as_str();
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT dt.tag_name, dt.version FROM dist_tags dt
+            JOIN packages p ON dt.package_id = p.id
+            WHERE p.registry_type = ?1 AND p.package_name = ?2
+            "#,
+        )?;
+
+        let dist_tags = stmt
+            .query_map((registry_type_str, package_name), |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<Result<HashMap<String, String>, _>>()?;
+
+        Ok(dist_tags)
+    }
+
+    /// Collect package counts, the total version count and the range of
+    /// `updated_at` timestamps
+    pub fn stats(&self) -> Result<CacheStats, CacheError> {
+        let packages = self.count_packages()?;
+        let conn = self.lock_conn()?;
+        let total_versions: i64 =
+            conn.query_row("SELECT COUNT(*) FROM versions", [], |row| row.get(0))?;
+        let (oldest_updated_at, newest_updated_at) = conn.query_row(
+            "SELECT MIN(updated_at), MAX(updated_at) FROM packages",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        Ok(CacheStats {
+            packages,
+            total_versions: total_versions as usize,
+            oldest_updated_at,
+            newest_updated_at,
+        })
+    }
+
+    /// List the packages with cached versions, ordered by registry and name
+    pub fn list_packages(&self) -> Result<Vec<CachedPackage>, CacheError> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT p.registry_type, p.package_name, p.updated_at FROM packages p
+            WHERE EXISTS (SELECT 1 FROM versions v WHERE v.package_id = p.id)
+            ORDER BY p.registry_type, p.package_name
+            "#,
+        )?;
+
+        let packages = stmt
+            .query_map([], |row| {
+                Ok(CachedPackage {
+                    registry_type: row.get(0)?,
+                    package_name: row.get(1)?,
+                    updated_at: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(packages)
+    }
 }
 
 impl VersionStorer for Cache {
@@ -297,32 +511,19 @@ impl VersionStorer for Cache {
 
         // For registries without dist-tags (GitHub Actions, Go, etc.),
         // find the semantically highest version
-        drop(conn); // Release lock before calling get_versions
-        let versions = VersionStorer::get_versions(self, registry_type, package_name)?;
-
-        if versions.is_empty() {
-            return Ok(None);
-        }
-
-        // Find the semantically highest version
-        let latest = versions
-            .into_iter()
-            .filter_map(|v| {
-                let parsed = crate::version::semver::parse_version(&v)?;
-                Some((v, parsed))
-            })
-            .max_by(|(_, a), (_, b)| a.cmp(b))
-            .map(|(v, _)| v);
-
-        Ok(latest)
+        drop(conn); // Release lock before querying versions
+        // A yanked version is never the latest, even when yanked versions are shown
+        let versions = self.query_versions(registry_type, package_name, false)?;
+        Ok(self.highest_version(versions))
     }
 
     fn get_versions(
         &self,
         registry_type: RegistryType,
         package_name: &str,
+        include_yanked: bool,
     ) -> Result<Vec<String>, CacheError> {
-        let versions = Cache::get_versions(self, registry_type, package_name)?;
+        let versions = self.query_versions(registry_type, package_name, include_yanked)?;
         if self.ignore_prerelease {
             Ok(versions
                 .into_iter()
@@ -333,22 +534,117 @@ impl VersionStorer for Cache {
         }
     }
 
+    fn batch_get_versions(
+        &self,
+        packages: &[(RegistryType, &str)],
+        include_yanked: bool,
+    ) -> Result<HashMap<(RegistryType, String), Vec<String>>, CacheError> {
+        if packages.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let conn = self.lock_conn()?;
+        self.query_batch_versions(&conn, packages, include_yanked)
+    }
+
+    fn batch_get_package_summaries(
+        &self,
+        packages: &[(RegistryType, &str)],
+    ) -> Result<HashMap<(RegistryType, String), PackageSummary>, CacheError> {
+        let mut summaries: HashMap<(RegistryType, String), PackageSummary> = HashMap::new();
+        if packages.is_empty() {
+            return Ok(summaries);
+        }
+
+        let conn = self.lock_conn()?;
+        for chunk in packages.chunks(BATCH_LOOKUP_CHUNK_SIZE) {
+            let (placeholders, params) = Self::package_lookup(chunk);
+            // One row per dist tag, or a single row for packages without any
+            let mut stmt = conn.prepare(&format!(
+                r#"
+                SELECT p.registry_type, p.package_name, p.deprecated, p.advisories,
+                       dt.tag_name, dt.version
+                FROM packages p
+                LEFT JOIN dist_tags dt ON dt.package_id = p.id
+                WHERE (p.registry_type, p.package_name) IN (VALUES {})
+                "#,
+                placeholders
+            ))?;
+
+            let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                ))
+            })?;
+            for row in rows {
+                let (registry_type, package_name, deprecated, advisories, tag_name, tag_version) =
+                    row?;
+                // Only the registry types looked up are returned
+                let Ok(registry_type) = RegistryType::try_from(registry_type.as_str()) else {
+                    continue;
+                };
+                let summary = summaries
+                    .entry((registry_type, package_name))
+                    .or_insert_with(|| PackageSummary {
+                        deprecated,
+                        advisories: advisories
+                            .and_then(|json| serde_json::from_str(&json).ok())
+                            .unwrap_or_default(),
+                        ..Default::default()
+                    });
+                if let (Some(tag_name), Some(version)) = (tag_name, tag_version) {
+                    summary.dist_tags.insert(tag_name, version);
+                }
+            }
+        }
+
+        // Like get_latest_version, packages without a "latest" dist tag get
+        // their highest version, never a yanked one
+        let without_latest_tag: Vec<(RegistryType, &str)> = summaries
+            .iter()
+            .filter(|(_, summary)| !summary.dist_tags.contains_key("latest"))
+            .map(|((registry_type, package_name), _)| (*registry_type, package_name.as_str()))
+            .collect();
+        let mut versions = self.query_batch_versions(&conn, &without_latest_tag, false)?;
+        drop(conn);
+
+        Ok(summaries
+            .into_iter()
+            .filter_map(|(key, mut summary)| {
+                summary.latest_version = match summary.dist_tags.get("latest") {
+                    Some(latest) => latest.clone(),
+                    None => self.highest_version(versions.remove(&key)?)?,
+                };
+                Some((key, summary))
+            })
+            .collect())
+    }
+
     fn version_exists(
         &self,
         registry_type: RegistryType,
         package_name: &str,
         version: &str,
+        include_yanked: bool,
     ) -> Result<bool, CacheError> {
         let registry_type = registry_type.as_str();
         let conn = self.lock_conn()?;
         let exists: bool = conn.query_row(
-            r#"
-            SELECT EXISTS(
-                SELECT 1 FROM versions v
-                JOIN packages p ON v.package_id = p.id
-                WHERE p.registry_type = ?1 AND p.package_name = ?2 AND v.version = ?3
-            )
-            "#,
+            &format!(
+                r#"
+                SELECT EXISTS(
+                    SELECT 1 FROM versions v
+                    JOIN packages p ON v.package_id = p.id
+                    WHERE p.registry_type = ?1 AND p.package_name = ?2 AND v.version = ?3 {}
+                )
+                "#,
+                Self::yanked_condition(include_yanked)
+            ),
             (registry_type, package_name, version),
             |row| row.get(0),
         )?;
@@ -361,11 +657,13 @@ impl VersionStorer for Cache {
         registry_type: RegistryType,
         package_name: &str,
         versions: Vec<String>,
+        yanked: &[String],
     ) -> Result<(), CacheError> {
         let registry_type = registry_type.as_str();
         debug!(
-            "Saving {} versions for {}/{}",
+            "Saving {} versions ({} yanked) for {}/{}",
             versions.len(),
+            yanked.len(),
             registry_type,
             package_name
         );
@@ -380,7 +678,7 @@ impl VersionStorer for Cache {
             r#"
             INSERT INTO packages (registry_type, package_name, updated_at)
             VALUES (?1, ?2, ?3)
-            ON CONFLICT(registry_type, package_name) DO UPDATE SET updated_at = excluded.updated_at
+            ON CONFLICT(registry_type, package_name) DO UPDATE SET updated_at = excluded.updated_at, not_found = 0
             "#,
             (registry_type, package_name, now),
         )?;
@@ -392,16 +690,34 @@ impl VersionStorer for Cache {
             |row| row.get(0),
         )?;
 
-        // Insert only new versions (skip existing ones)
-        // Using INSERT OR IGNORE with UNIQUE constraint on (package_id, version)
+        // Insert only new versions (existing ones keep their order), and
+        // clear the yanked mark of versions the registry lists again
         {
-            let mut stmt =
-                tx.prepare("INSERT OR IGNORE INTO versions (package_id, version) VALUES (?1, ?2)")?;
+            let mut stmt = tx.prepare(
+                r#"
+                INSERT INTO versions (package_id, version) VALUES (?1, ?2)
+                ON CONFLICT(package_id, version) DO UPDATE SET yanked = 0
+                "#,
+            )?;
             for version in &versions {
                 stmt.execute((package_id, version))?;
             }
         }
 
+        // Mark yanked versions, including ones cached before they were
+        // yanked; others are added already marked
+        {
+            let mut stmt = tx.prepare(
+                r#"
+                INSERT INTO versions (package_id, version, yanked) VALUES (?1, ?2, 1)
+                ON CONFLICT(package_id, version) DO UPDATE SET yanked = 1
+                "#,
+            )?;
+            for version in yanked {
+                stmt.execute((package_id, version))?;
+            }
+        }
+
         tx.commit()?;
 
         debug!(
@@ -411,6 +727,23 @@ impl VersionStorer for Cache {
         Ok(())
     }
 
+    fn touch_package(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<(), CacheError> {
+        let registry_type = registry_type.as_str();
+        let now = Self::current_timestamp_ms();
+        let conn = self.lock_conn()?;
+
+        conn.execute(
+            "UPDATE packages SET updated_at = ?1 WHERE registry_type = ?2 AND package_name = ?3",
+            (now, registry_type, package_name),
+        )?;
+
+        Ok(())
+    }
+
     fn get_packages_needing_refresh(&self) -> Result<Vec<PackageId>, CacheError> {
         let now = Self::current_timestamp_ms();
         let threshold = now - self.refresh_interval;
@@ -451,20 +784,29 @@ impl VersionStorer for Cache {
         let registry_type = registry_type.as_str();
         let now = Self::current_timestamp_ms();
         let timeout_threshold = now - FETCH_TIMEOUT_MS;
+        let not_found_threshold = now - self.not_found_ttl.load(Ordering::Relaxed);
 
         let conn = self.lock_conn()?;
 
         // Try to set fetching_since if:
         // 1. fetching_since is NULL (not being fetched)
         // 2. fetching_since is older than timeout (previous fetch timed out)
+        // and the package is not marked as not found within the TTL
         let rows_affected = conn.execute(
             r#"
             UPDATE packages
             SET fetching_since = ?1
             WHERE registry_type = ?2 AND package_name = ?3
               AND (fetching_since IS NULL OR fetching_since < ?4)
+              AND (not_found = 0 OR updated_at < ?5)
             "#,
-            (now, registry_type, package_name, timeout_threshold),
+            (
+                now,
+                registry_type,
+                package_name,
+                timeout_threshold,
+                not_found_threshold,
+            ),
         )?;
 
         if rows_affected > 0 {
@@ -520,6 +862,14 @@ impl VersionStorer for Cache {
         Cache::save_dist_tags(self, registry_type, package_name, dist_tags)
     }
 
+    fn get_dist_tags(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<HashMap<String, String>, CacheError> {
+        Cache::get_dist_tags(self, registry_type, package_name)
+    }
+
     fn filter_packages_not_in_cache(
         &self,
         registry_type: RegistryType,
@@ -530,35 +880,41 @@ impl VersionStorer for Cache {
         }
 
         let registry_type = registry_type.as_str();
+        let not_found_threshold =
+            Self::current_timestamp_ms() - self.not_found_ttl.load(Ordering::Relaxed);
         let conn = self.lock_conn()?;
 
         // Build WHERE IN clause with placeholders
         let placeholders: Vec<_> = (0..package_names.len())
-            .map(|i| format!("?{}", i + 2))
+            .map(|i| format!("?{}", i + 3))
             .collect();
         let placeholders_str = placeholders.join(", ");
 
         // Consider packages as "cached" if:
         // 1. They have at least one version, OR
-        // 2. They are marked as not found (to skip repeated fetch attempts)
+        // 2. They were marked as not found within the TTL (to skip repeated fetch attempts)
         let query = format!(
             r#"
             SELECT p.package_name
             FROM packages p
             WHERE p.registry_type = ?1
               AND p.package_name IN ({})
-              AND (EXISTS (SELECT 1 FROM versions v WHERE v.package_id = p.id) OR p.not_found = 1)
+              AND (EXISTS (SELECT 1 FROM versions v WHERE v.package_id = p.id)
+                   OR (p.not_found = 1 AND p.updated_at >= ?2))
             "#,
             placeholders_str
         );
 
         let mut stmt = conn.prepare(&query)?;
 
-        // Build params: registry_type followed by all package names
-        let params: Vec<&dyn rusqlite::ToSql> =
-            std::iter::once(&registry_type as &dyn rusqlite::ToSql)
-                .chain(package_names.iter().map(|s| s as &dyn rusqlite::ToSql))
-                .collect();
+        // Build params: registry_type and threshold followed by all package names
+        let params: Vec<&dyn rusqlite::ToSql> = [
+            &registry_type as &dyn rusqlite::ToSql,
+            &not_found_threshold as &dyn rusqlite::ToSql,
+        ]
+        .into_iter()
+        .chain(package_names.iter().map(|s| s as &dyn rusqlite::ToSql))
+        .collect();
 
         let cached_packages: HashSet<String> = stmt
             .query_map(params.as_slice(), |row| row.get(0))?
@@ -575,78 +931,348 @@ impl VersionStorer for Cache {
         Ok(not_in_cache)
     }
 
+    fn filter_stale_packages(
+        &self,
+        registry_type: RegistryType,
+        package_names: &[String],
+    ) -> Result<Vec<String>, CacheError> {
+        if package_names.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let registry_type = registry_type.as_str();
+        let threshold = Self::current_timestamp_ms() - self.refresh_interval;
+        let conn = self.lock_conn()?;
+
+        let placeholders: Vec<_> = (0..package_names.len())
+            .map(|i| format!("?{}", i + 3))
+            .collect();
+        let placeholders_str = placeholders.join(", ");
+
+        // Stale packages still have versions to serve, but were last updated
+        // before the refresh threshold. Not-found packages are never refreshed.
+        let query = format!(
+            r#"
+            SELECT p.package_name
+            FROM packages p
+            WHERE p.registry_type = ?1
+              AND p.updated_at < ?2
+              AND p.not_found = 0
+              AND p.package_name IN ({})
+              AND EXISTS (SELECT 1 FROM versions v WHERE v.package_id = p.id)
+            "#,
+            placeholders_str
+        );
+
+        let mut stmt = conn.prepare(&query)?;
+
+        let params: Vec<&dyn rusqlite::ToSql> = [
+            &registry_type as &dyn rusqlite::ToSql,
+            &threshold as &dyn rusqlite::ToSql,
+        ]
+        .into_iter()
+        .chain(package_names.iter().map(|s| s as &dyn rusqlite::ToSql))
+        .collect();
+
+        let stale_packages: HashSet<String> = stmt
+            .query_map(params.as_slice(), |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        // Preserve original order
+        let stale = package_names
+            .iter()
+            .filter(|name| stale_packages.contains(*name))
+            .cloned()
+            .collect();
+
+        Ok(stale)
+    }
+
     fn mark_not_found(
         &self,
         registry_type: RegistryType,
         package_name: &str,
     ) -> Result<(), CacheError> {
         let registry_type = registry_type.as_str();
+        let now = Self::current_timestamp_ms();
         let conn = self.lock_conn()?;
 
+        // updated_at records when the package was marked, for the not-found TTL
         conn.execute(
-            "UPDATE packages SET not_found = 1 WHERE registry_type = ?1 AND package_name = ?2",
-            (registry_type, package_name),
+            "UPDATE packages SET not_found = 1, updated_at = ?1 WHERE registry_type = ?2 AND package_name = ?3",
+            (now, registry_type, package_name),
         )?;
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rstest::rstest;
-    use tempfile::TempDir;
+    fn set_not_found_ttl(&self, not_found_ttl: i64) {
+        self.not_found_ttl.store(not_found_ttl, Ordering::Relaxed);
+    }
 
-    #[test]
-    fn replace_versions_creates_new_package() {
-        let temp_dir = TempDir::new().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-        let cache = Cache::new(&db_path, 86400, false).unwrap();
+    fn save_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        metadata: &PackageMetadata,
+    ) -> Result<(), CacheError> {
+        let registry_type = registry_type.as_str();
+        let json = serde_json::to_string(metadata).expect("PackageMetadata serializes to JSON");
+        let conn = self.lock_conn()?;
 
-        let versions = vec![
-            "1.0.0".to_string(),
-            "1.1.0".to_string(),
-            "2.0.0".to_string(),
-        ];
-        cache
-            .replace_versions(RegistryType::Npm, "axios", versions.clone())
-            .unwrap();
+        conn.execute(
+            "UPDATE packages SET metadata = ?1 WHERE registry_type = ?2 AND package_name = ?3",
+            (json, registry_type, package_name),
+        )?;
 
-        let saved = cache.get_versions(RegistryType::Npm, "axios").unwrap();
-        assert_eq!(saved, versions);
+        Ok(())
     }
 
-    #[test]
-    fn replace_versions_updates_existing_package() {
-        let temp_dir = TempDir::new().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-        let cache = Cache::new(&db_path, 86400, false).unwrap();
+    fn get_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<PackageMetadata>, CacheError> {
+        let registry_type = registry_type.as_str();
+        let conn = self.lock_conn()?;
+        let result: Result<Option<String>, _> = conn.query_row(
+            "SELECT metadata FROM packages WHERE registry_type = ?1 AND package_name = ?2",
+            (registry_type, package_name),
+            |row| row.get(0),
+        );
 
-        let initial_versions = vec!["1.0.0".to_string()];
-        cache
-            .replace_versions(RegistryType::Npm, "axios", initial_versions)
-            .unwrap();
+        match result {
+            // Unreadable metadata is treated as absent rather than failing lookups
+            Ok(json) => Ok(json.and_then(|json| serde_json::from_str(&json).ok())),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
 
-        let new_versions = vec!["1.0.0".to_string(), "1.1.0".to_string()];
-        cache
-            .replace_versions(RegistryType::Npm, "axios", new_versions.clone())
-            .unwrap();
+    fn save_cache_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        cache_metadata: &CacheMetadata,
+    ) -> Result<(), CacheError> {
+        let registry_type = registry_type.as_str();
+        let conn = self.lock_conn()?;
 
-        let saved = cache.get_versions(RegistryType::Npm, "axios").unwrap();
-        assert_eq!(saved, new_versions);
-    }
+        conn.execute(
+            "UPDATE packages SET etag = ?1, last_modified = ?2 WHERE registry_type = ?3 AND package_name = ?4",
+            (
+                &cache_metadata.etag,
+                &cache_metadata.last_modified,
+                registry_type,
+                package_name,
+            ),
+        )?;
 
-    #[test]
-    fn replace_versions_adds_only_new_versions() {
-        let temp_dir = TempDir::new().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-        let cache = Cache::new(&db_path, 86400, false).unwrap();
+        Ok(())
+    }
+
+    fn get_cache_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<CacheMetadata>, CacheError> {
+        let registry_type = registry_type.as_str();
+        let conn = self.lock_conn()?;
+        let result = conn.query_row(
+            "SELECT etag, last_modified FROM packages WHERE registry_type = ?1 AND package_name = ?2",
+            (registry_type, package_name),
+            |row| {
+                Ok(CacheMetadata {
+                    etag: row.get(0)?,
+                    last_modified: row.get(1)?,
+                })
+            },
+        );
+
+        match result {
+            Ok(cache_metadata) if cache_metadata.is_empty() => Ok(None),
+            Ok(cache_metadata) => Ok(Some(cache_metadata)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn count_packages(&self) -> Result<HashMap<String, usize>, CacheError> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT registry_type, COUNT(*) as cnt FROM packages GROUP BY registry_type",
+        )?;
+
+        let counts = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+            })?
+            .collect::<Result<HashMap<String, usize>, _>>()?;
+
+        Ok(counts)
+    }
+
+    fn get_last_fetch_time(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<i64>, CacheError> {
+        let conn = self.lock_conn()?;
+
+        // A package row is created when its first fetch starts, so only
+        // packages with stored versions have actually been fetched
+        let result = conn.query_row(
+            r#"
+            SELECT p.updated_at FROM packages p
+            WHERE p.registry_type = ?1 AND p.package_name = ?2
+              AND EXISTS (SELECT 1 FROM versions v WHERE v.package_id = p.id)
+            "#,
+            (registry_type.as_str(), package_name),
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(updated_at) => Ok(Some(updated_at)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save_deprecated(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        deprecated: Option<String>,
+    ) -> Result<(), CacheError> {
+        let registry_type = registry_type.as_str();
+        let conn = self.lock_conn()?;
+
+        conn.execute(
+            "UPDATE packages SET deprecated = ?1 WHERE registry_type = ?2 AND package_name = ?3",
+            (deprecated, registry_type, package_name),
+        )?;
+
+        Ok(())
+    }
+
+    fn get_deprecated(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<String>, CacheError> {
+        let registry_type = registry_type.as_str();
+        let conn = self.lock_conn()?;
+        let result = conn.query_row(
+            "SELECT deprecated FROM packages WHERE registry_type = ?1 AND package_name = ?2",
+            (registry_type, package_name),
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(deprecated) => Ok(deprecated),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save_advisories(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        advisories: &[Advisory],
+    ) -> Result<(), CacheError> {
+        let registry_type = registry_type.as_str();
+        let json = (!advisories.is_empty())
+            .then(|| serde_json::to_string(advisories).expect("Advisory serializes to JSON"));
+        let conn = self.lock_conn()?;
+
+        conn.execute(
+            "UPDATE packages SET advisories = ?1 WHERE registry_type = ?2 AND package_name = ?3",
+            (json, registry_type, package_name),
+        )?;
+
+        Ok(())
+    }
+
+    fn get_advisories(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Vec<Advisory>, CacheError> {
+        let registry_type = registry_type.as_str();
+        let conn = self.lock_conn()?;
+        let result = conn.query_row(
+            "SELECT advisories FROM packages WHERE registry_type = ?1 AND package_name = ?2",
+            (registry_type, package_name),
+            |row| row.get::<_, Option<String>>(0),
+        );
+
+        match result {
+            Ok(json) => Ok(json
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default()),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    #[test]
+    fn replace_versions_creates_new_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        let versions = vec![
+            "1.0.0".to_string(),
+            "1.1.0".to_string(),
+            "2.0.0".to_string(),
+        ];
+        cache
+            .replace_versions(RegistryType::Npm, "axios", versions.clone(), &[])
+            .unwrap();
+
+        let saved = cache.get_versions(RegistryType::Npm, "axios").unwrap();
+        assert_eq!(saved, versions);
+    }
+
+    #[test]
+    fn replace_versions_updates_existing_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        let initial_versions = vec!["1.0.0".to_string()];
+        cache
+            .replace_versions(RegistryType::Npm, "axios", initial_versions, &[])
+            .unwrap();
+
+        let new_versions = vec!["1.0.0".to_string(), "1.1.0".to_string()];
+        cache
+            .replace_versions(RegistryType::Npm, "axios", new_versions.clone(), &[])
+            .unwrap();
+
+        let saved = cache.get_versions(RegistryType::Npm, "axios").unwrap();
+        assert_eq!(saved, new_versions);
+    }
+
+    #[test]
+    fn replace_versions_adds_only_new_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
 
         // Initial versions
         let initial_versions = vec!["1.0.0".to_string(), "1.1.0".to_string()];
         cache
-            .replace_versions(RegistryType::Npm, "axios", initial_versions)
+            .replace_versions(RegistryType::Npm, "axios", initial_versions, &[])
             .unwrap();
 
         // Add mix of existing and new versions
@@ -657,7 +1283,7 @@ mod tests {
             "2.0.0".to_string(), // new
         ];
         cache
-            .replace_versions(RegistryType::Npm, "axios", updated_versions)
+            .replace_versions(RegistryType::Npm, "axios", updated_versions, &[])
             .unwrap();
 
         // Verify all versions are present (no duplicates)
@@ -686,6 +1312,189 @@ mod tests {
         assert!(versions.is_empty());
     }
 
+    #[rstest]
+    #[case(false, vec!["4.17.20", "4.17.21", "5.0.0-beta.1"])]
+    #[case(true, vec!["4.17.20", "4.17.21"])]
+    fn batch_get_versions_looks_up_packages_of_several_registries(
+        #[case] ignore_prerelease: bool,
+        #[case] expected_lodash: Vec<&str>,
+    ) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, ignore_prerelease).unwrap();
+        cache
+            .replace_versions(
+                RegistryType::Npm,
+                "lodash",
+                vec![
+                    "4.17.20".to_string(),
+                    "4.17.21".to_string(),
+                    "5.0.0-beta.1".to_string(),
+                ],
+                &[],
+            )
+            .unwrap();
+        cache
+            .replace_versions(
+                RegistryType::CratesIo,
+                "serde",
+                vec!["1.0.0".to_string()],
+                &[],
+            )
+            .unwrap();
+        // Same name in a registry that is not requested
+        cache
+            .replace_versions(RegistryType::Jsr, "lodash", vec!["0.1.0".to_string()], &[])
+            .unwrap();
+
+        let mut versions = cache
+            .batch_get_versions(
+                &[
+                    (RegistryType::Npm, "lodash"),
+                    (RegistryType::CratesIo, "serde"),
+                    (RegistryType::Npm, "nonexistent"),
+                ],
+                false,
+            )
+            .unwrap();
+        versions.values_mut().for_each(|v| v.sort());
+
+        assert_eq!(
+            versions,
+            HashMap::from([
+                (
+                    (RegistryType::Npm, "lodash".to_string()),
+                    expected_lodash.into_iter().map(String::from).collect()
+                ),
+                (
+                    (RegistryType::CratesIo, "serde".to_string()),
+                    vec!["1.0.0".to_string()]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn batch_get_package_summaries_matches_single_package_lookups() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, true).unwrap();
+        cache
+            .replace_versions(
+                RegistryType::Npm,
+                "lodash",
+                vec!["4.17.20".to_string(), "4.17.21".to_string()],
+                &[],
+            )
+            .unwrap();
+        cache
+            .save_dist_tags(
+                RegistryType::Npm,
+                "lodash",
+                &HashMap::from([
+                    ("latest".to_string(), "4.17.20".to_string()),
+                    ("next".to_string(), "5.0.0-beta.1".to_string()),
+                ]),
+            )
+            .unwrap();
+        cache
+            .save_deprecated(
+                RegistryType::Npm,
+                "lodash",
+                Some("Use lodash-es".to_string()),
+            )
+            .unwrap();
+        // Without a "latest" dist tag, the highest version that is neither
+        // yanked nor a pre-release is the latest
+        cache
+            .replace_versions(
+                RegistryType::CratesIo,
+                "serde",
+                vec![
+                    "1.0.0".to_string(),
+                    "1.1.0".to_string(),
+                    "2.0.0-rc.1".to_string(),
+                ],
+                &["1.1.0".to_string()],
+            )
+            .unwrap();
+        let advisories = vec![Advisory {
+            id: "RUSTSEC-2021-0001".to_string(),
+            severity: "HIGH".to_string(),
+            summary: "Bug".to_string(),
+        }];
+        cache
+            .save_advisories(RegistryType::CratesIo, "serde", &advisories)
+            .unwrap();
+
+        let packages = [
+            (RegistryType::Npm, "lodash"),
+            (RegistryType::CratesIo, "serde"),
+            (RegistryType::Npm, "nonexistent"),
+        ];
+        let summaries = cache.batch_get_package_summaries(&packages).unwrap();
+
+        assert_eq!(
+            summaries,
+            HashMap::from([
+                (
+                    (RegistryType::Npm, "lodash".to_string()),
+                    PackageSummary {
+                        latest_version: "4.17.20".to_string(),
+                        dist_tags: HashMap::from([
+                            ("latest".to_string(), "4.17.20".to_string()),
+                            ("next".to_string(), "5.0.0-beta.1".to_string()),
+                        ]),
+                        deprecated: Some("Use lodash-es".to_string()),
+                        advisories: Vec::new(),
+                    }
+                ),
+                (
+                    (RegistryType::CratesIo, "serde".to_string()),
+                    PackageSummary {
+                        latest_version: "1.0.0".to_string(),
+                        dist_tags: HashMap::new(),
+                        deprecated: None,
+                        advisories,
+                    }
+                ),
+            ])
+        );
+        for (registry_type, package_name) in packages {
+            assert_eq!(
+                summaries
+                    .get(&(registry_type, package_name.to_string()))
+                    .map(|summary| summary.latest_version.clone()),
+                cache
+                    .get_latest_version(registry_type, package_name)
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn batch_get_versions_handles_more_packages_than_one_query_chunk() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+        let names: Vec<String> = (0..BATCH_LOOKUP_CHUNK_SIZE + 10)
+            .map(|i| format!("pkg-{}", i))
+            .collect();
+        for name in &names {
+            cache
+                .replace_versions(RegistryType::Npm, name, vec!["1.0.0".to_string()], &[])
+                .unwrap();
+        }
+        let packages: Vec<(RegistryType, &str)> = names
+            .iter()
+            .map(|name| (RegistryType::Npm, name.as_str()))
+            .collect();
+
+        let versions = cache.batch_get_versions(&packages, false).unwrap();
+
+        assert_eq!(versions.len(), names.len());
+    }
+
     #[test]
     fn get_versions_performance_with_1000_versions() {
         let temp_dir = TempDir::new().unwrap();
@@ -694,7 +1503,7 @@ mod tests {
 
         let versions: Vec<String> = (0..1000).map(|i| format!("{}.0.0", i)).collect();
         cache
-            .replace_versions(RegistryType::Npm, "large-package", versions.clone())
+            .replace_versions(RegistryType::Npm, "large-package", versions.clone(), &[])
             .unwrap();
 
         let start = std::time::Instant::now();
@@ -729,66 +1538,152 @@ mod tests {
 
         let versions = vec!["1.0.0".to_string(), "2.0.0".to_string()];
         cache
-            .replace_versions(RegistryType::Npm, "axios", versions)
+            .replace_versions(RegistryType::Npm, "axios", versions, &[])
             .unwrap();
 
         assert_eq!(
             cache
-                .version_exists(registry_type, package_name, version)
+                .version_exists(registry_type, package_name, version, false)
                 .unwrap(),
             expected
         );
     }
 
     #[rstest]
-    #[case(RegistryType::Npm, "axios", Some("3.0.0".to_string()))]
-    #[case(RegistryType::Npm, "nonexistent", None)]
-    #[case(RegistryType::CratesIo, "axios", None)]
-    fn get_latest_version_returns_last_inserted(
-        #[case] registry_type: RegistryType,
-        #[case] package_name: &str,
-        #[case] expected: Option<String>,
+    #[case(false, vec!["1.0.0"])]
+    #[case(true, vec!["1.0.0", "1.1.0", "2.0.0"])]
+    fn replace_versions_leaves_yanked_versions_out_of_lookups_unless_included(
+        #[case] include_yanked: bool,
+        #[case] expected: Vec<&str>,
     ) {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
         let cache = Cache::new(&db_path, 86400, false).unwrap();
 
-        let versions = vec![
-            "1.0.0".to_string(),
-            "2.0.0".to_string(),
-            "3.0.0".to_string(),
-        ];
         cache
-            .replace_versions(RegistryType::Npm, "axios", versions)
+            .replace_versions(
+                RegistryType::CratesIo,
+                "serde",
+                vec!["1.0.0".to_string(), "1.1.0".to_string()],
+                &[],
+            )
+            .unwrap();
+        // Yanked after it was cached, and yanked before it was ever cached
+        cache
+            .replace_versions(
+                RegistryType::CratesIo,
+                "serde",
+                vec!["1.0.0".to_string()],
+                &["1.1.0".to_string(), "2.0.0".to_string()],
+            )
             .unwrap();
 
         assert_eq!(
-            cache
-                .get_latest_version(registry_type, package_name)
+            VersionStorer::get_versions(&cache, RegistryType::CratesIo, "serde", include_yanked)
                 .unwrap(),
             expected
         );
+        assert_eq!(
+            cache
+                .batch_get_versions(&[(RegistryType::CratesIo, "serde")], include_yanked)
+                .unwrap()[&(RegistryType::CratesIo, "serde".to_string())],
+            expected
+        );
+        assert_eq!(
+            cache
+                .version_exists(RegistryType::CratesIo, "serde", "1.1.0", include_yanked)
+                .unwrap(),
+            include_yanked
+        );
+        // A yanked version is never the latest
+        assert_eq!(
+            cache
+                .get_latest_version(RegistryType::CratesIo, "serde")
+                .unwrap(),
+            Some("1.0.0".to_string())
+        );
     }
 
     #[test]
-    fn get_packages_needing_refresh_returns_packages_older_than_refresh_interval() {
+    fn replace_versions_clears_yanked_mark_of_listed_versions() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
-        // refresh_interval = 100ms
-        let cache = Cache::new(&db_path, 100, false).unwrap();
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
 
         cache
-            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()])
+            .replace_versions(
+                RegistryType::CratesIo,
+                "serde",
+                Vec::new(),
+                &["1.0.0".to_string()],
+            )
             .unwrap();
+        // The registry lists the version again once it is unyanked
         cache
-            .replace_versions(RegistryType::Npm, "lodash", vec!["4.0.0".to_string()])
+            .replace_versions(
+                RegistryType::CratesIo,
+                "serde",
+                vec!["1.0.0".to_string()],
+                &[],
+            )
             .unwrap();
 
-        // Wait for packages to become stale
-        std::thread::sleep(std::time::Duration::from_millis(150));
-
-        let stale = cache.get_packages_needing_refresh().unwrap();
-        assert_eq!(stale.len(), 2);
+        assert!(
+            cache
+                .version_exists(RegistryType::CratesIo, "serde", "1.0.0", false)
+                .unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(RegistryType::Npm, "axios", Some("3.0.0".to_string()))]
+    #[case(RegistryType::Npm, "nonexistent", None)]
+    #[case(RegistryType::CratesIo, "axios", None)]
+    fn get_latest_version_returns_last_inserted(
+        #[case] registry_type: RegistryType,
+        #[case] package_name: &str,
+        #[case] expected: Option<String>,
+    ) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        let versions = vec![
+            "1.0.0".to_string(),
+            "2.0.0".to_string(),
+            "3.0.0".to_string(),
+        ];
+        cache
+            .replace_versions(RegistryType::Npm, "axios", versions, &[])
+            .unwrap();
+
+        assert_eq!(
+            cache
+                .get_latest_version(registry_type, package_name)
+                .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn get_packages_needing_refresh_returns_packages_older_than_refresh_interval() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        // refresh_interval = 100ms
+        let cache = Cache::new(&db_path, 100, false).unwrap();
+
+        cache
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+        cache
+            .replace_versions(RegistryType::Npm, "lodash", vec!["4.0.0".to_string()], &[])
+            .unwrap();
+
+        // Wait for packages to become stale
+        std::thread::sleep(std::time::Duration::from_millis(150));
+
+        let stale = cache.get_packages_needing_refresh().unwrap();
+        assert_eq!(stale.len(), 2);
         assert!(stale.contains(&PackageId {
             registry_type: RegistryType::Npm,
             package_name: "axios".to_string()
@@ -799,6 +1694,105 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn filter_stale_packages_returns_only_cached_packages_older_than_refresh_interval() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        // refresh_interval = 100ms
+        let cache = Cache::new(&db_path, 100, false).unwrap();
+
+        cache
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+        cache
+            .replace_versions(RegistryType::Npm, "gone", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+        cache.mark_not_found(RegistryType::Npm, "gone").unwrap();
+
+        // Wait for packages to become stale
+        std::thread::sleep(std::time::Duration::from_millis(150));
+
+        cache
+            .replace_versions(RegistryType::Npm, "lodash", vec!["4.0.0".to_string()], &[])
+            .unwrap();
+
+        let names = vec![
+            "uncached".to_string(),
+            "lodash".to_string(),
+            "gone".to_string(),
+            "axios".to_string(),
+        ];
+        let stale = cache
+            .filter_stale_packages(RegistryType::Npm, &names)
+            .unwrap();
+
+        assert_eq!(stale, vec!["axios".to_string()]);
+    }
+
+    #[test]
+    fn touch_package_makes_stale_package_fresh() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        // refresh_interval = 100ms
+        let cache = Cache::new(&db_path, 100, false).unwrap();
+
+        cache
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+        let fetched_at = cache
+            .get_last_fetch_time(RegistryType::Npm, "axios")
+            .unwrap()
+            .unwrap();
+
+        // Wait for the package to become stale
+        std::thread::sleep(std::time::Duration::from_millis(150));
+        let names = vec!["axios".to_string()];
+        assert_eq!(
+            cache
+                .filter_stale_packages(RegistryType::Npm, &names)
+                .unwrap(),
+            names
+        );
+
+        cache.touch_package(RegistryType::Npm, "axios").unwrap();
+
+        assert!(
+            cache
+                .filter_stale_packages(RegistryType::Npm, &names)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(cache.get_packages_needing_refresh().unwrap().is_empty());
+        assert!(
+            cache
+                .get_last_fetch_time(RegistryType::Npm, "axios")
+                .unwrap()
+                .is_some_and(|touched_at| touched_at > fetched_at)
+        );
+        assert_eq!(
+            cache.get_versions(RegistryType::Npm, "axios").unwrap(),
+            vec!["1.0.0"]
+        );
+    }
+
+    #[test]
+    fn filter_stale_packages_returns_empty_for_fresh_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        // refresh_interval = 1 hour (in ms)
+        let cache = Cache::new(&db_path, 3600000, false).unwrap();
+
+        cache
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+
+        let stale = cache
+            .filter_stale_packages(RegistryType::Npm, &["axios".to_string()])
+            .unwrap();
+
+        assert!(stale.is_empty());
+    }
+
     #[test]
     fn get_packages_needing_refresh_excludes_fresh_packages() {
         let temp_dir = TempDir::new().unwrap();
@@ -807,7 +1801,7 @@ mod tests {
         let cache = Cache::new(&db_path, 3600000, false).unwrap();
 
         cache
-            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()])
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
             .unwrap();
 
         let stale = cache.get_packages_needing_refresh().unwrap();
@@ -835,7 +1829,7 @@ mod tests {
 
         // Pre-populate cache (fetching_since is NULL after replace_versions)
         cache
-            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()])
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
             .unwrap();
 
         // Package exists but not being fetched should allow fetch
@@ -851,7 +1845,7 @@ mod tests {
 
         // Pre-populate cache
         cache
-            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()])
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
             .unwrap();
 
         // First fetch should succeed
@@ -871,7 +1865,7 @@ mod tests {
 
         // Pre-populate cache
         cache
-            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()])
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
             .unwrap();
 
         // Start fetch
@@ -911,6 +1905,12 @@ mod tests {
             .unwrap();
         assert_eq!(beta, Some("5.0.0-beta.1".to_string()));
 
+        // All dist-tags
+        assert_eq!(
+            cache.get_dist_tags(RegistryType::Npm, "lodash").unwrap(),
+            dist_tags
+        );
+
         // Non-existent tag
         let unknown = cache
             .get_dist_tag(RegistryType::Npm, "lodash", "unknown")
@@ -924,6 +1924,38 @@ mod tests {
         assert_eq!(no_pkg, None);
     }
 
+    #[test]
+    fn replace_versions_keeps_dist_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        let mut dist_tags = std::collections::HashMap::new();
+        dist_tags.insert("latest".to_string(), "15.0.3".to_string());
+        dist_tags.insert("canary".to_string(), "15.1.0-canary.5".to_string());
+        cache
+            .replace_versions(RegistryType::Npm, "next", vec!["15.0.3".to_string()], &[])
+            .unwrap();
+        cache
+            .save_dist_tags(RegistryType::Npm, "next", &dist_tags)
+            .unwrap();
+
+        // A refresh that lists the versions again leaves the dist-tags alone
+        cache
+            .replace_versions(
+                RegistryType::Npm,
+                "next",
+                vec!["15.0.3".to_string(), "15.1.0-canary.5".to_string()],
+                &[],
+            )
+            .unwrap();
+
+        assert_eq!(
+            cache.get_dist_tags(RegistryType::Npm, "next").unwrap(),
+            dist_tags
+        );
+    }
+
     #[test]
     fn get_latest_version_prefers_dist_tag_latest_over_last_inserted() {
         let temp_dir = TempDir::new().unwrap();
@@ -938,7 +1970,7 @@ mod tests {
             "0.0.0-insiders.abc123".to_string(), // Pre-release published after stable
         ];
         cache
-            .replace_versions(RegistryType::Npm, "tailwindcss", versions)
+            .replace_versions(RegistryType::Npm, "tailwindcss", versions, &[])
             .unwrap();
 
         // Set dist-tags with latest pointing to stable version
@@ -965,7 +1997,12 @@ mod tests {
         // Insert versions without dist-tags (like GitHub Actions)
         let versions = vec!["v3.0.0".to_string(), "v4.0.0".to_string()];
         cache
-            .replace_versions(RegistryType::GitHubActions, "actions/checkout", versions)
+            .replace_versions(
+                RegistryType::GitHubActions,
+                "actions/checkout",
+                versions,
+                &[],
+            )
             .unwrap();
 
         // No dist-tags set, should return last inserted version
@@ -983,10 +2020,10 @@ mod tests {
 
         // Add some packages to cache
         cache
-            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()])
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
             .unwrap();
         cache
-            .replace_versions(RegistryType::Npm, "lodash", vec!["4.0.0".to_string()])
+            .replace_versions(RegistryType::Npm, "lodash", vec!["4.0.0".to_string()], &[])
             .unwrap();
 
         // Query for a mix of cached and uncached packages
@@ -1001,294 +2038,787 @@ mod tests {
             .filter_packages_not_in_cache(RegistryType::Npm, &package_names)
             .unwrap();
 
-        // Should only return packages NOT in cache
-        assert_eq!(
-            not_in_cache,
-            vec!["express".to_string(), "react".to_string()]
-        );
+        // Should only return packages NOT in cache
+        assert_eq!(
+            not_in_cache,
+            vec!["express".to_string(), "react".to_string()]
+        );
+    }
+
+    #[test]
+    fn filter_packages_not_in_cache_returns_empty_when_all_cached() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        cache
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+
+        let package_names = vec!["axios".to_string()];
+        let not_in_cache = cache
+            .filter_packages_not_in_cache(RegistryType::Npm, &package_names)
+            .unwrap();
+
+        assert!(not_in_cache.is_empty());
+    }
+
+    #[test]
+    fn filter_packages_not_in_cache_returns_all_when_none_cached() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        let package_names = vec!["express".to_string(), "react".to_string()];
+        let not_in_cache = cache
+            .filter_packages_not_in_cache(RegistryType::Npm, &package_names)
+            .unwrap();
+
+        assert_eq!(
+            not_in_cache,
+            vec!["express".to_string(), "react".to_string()]
+        );
+    }
+
+    #[test]
+    fn filter_packages_not_in_cache_respects_registry_type() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        // Add package to npm registry
+        cache
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+
+        // Query same package name but different registry
+        let package_names = vec!["axios".to_string()];
+        let not_in_cache = cache
+            .filter_packages_not_in_cache(RegistryType::CratesIo, &package_names)
+            .unwrap();
+
+        // Should return axios because it's not in CratesIo registry
+        assert_eq!(not_in_cache, vec!["axios".to_string()]);
+    }
+
+    #[test]
+    fn filter_packages_not_in_cache_treats_zero_versions_as_not_cached() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        // Simulate a failed fetch: package record exists but no versions
+        // This happens when try_start_fetch creates a record but fetch_all_versions fails
+        cache
+            .try_start_fetch(RegistryType::Npm, "failed-package")
+            .unwrap();
+        cache
+            .finish_fetch(RegistryType::Npm, "failed-package")
+            .unwrap();
+
+        // Add a package with versions for comparison
+        cache
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+
+        let package_names = vec!["failed-package".to_string(), "axios".to_string()];
+        let not_in_cache = cache
+            .filter_packages_not_in_cache(RegistryType::Npm, &package_names)
+            .unwrap();
+
+        // Should return failed-package because it has 0 versions
+        assert_eq!(not_in_cache, vec!["failed-package".to_string()]);
+    }
+
+    #[test]
+    fn get_latest_version_filters_prerelease_when_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, true).unwrap(); // ignore_prerelease = true
+
+        let versions = vec![
+            "1.0.0".to_string(),
+            "2.0.0".to_string(),
+            "3.0.0-alpha".to_string(), // prerelease (highest but filtered)
+        ];
+        cache
+            .replace_versions(
+                RegistryType::GitHubActions,
+                "actions/checkout",
+                versions,
+                &[],
+            )
+            .unwrap();
+
+        let latest = cache
+            .get_latest_version(RegistryType::GitHubActions, "actions/checkout")
+            .unwrap();
+        assert_eq!(latest, Some("2.0.0".to_string())); // highest stable version
+    }
+
+    #[test]
+    fn get_latest_version_includes_prerelease_when_disabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap(); // ignore_prerelease = false
+
+        let versions = vec![
+            "1.0.0".to_string(),
+            "2.0.0".to_string(),
+            "3.0.0-alpha".to_string(), // prerelease
+        ];
+        cache
+            .replace_versions(
+                RegistryType::GitHubActions,
+                "actions/checkout",
+                versions,
+                &[],
+            )
+            .unwrap();
+
+        let latest = cache
+            .get_latest_version(RegistryType::GitHubActions, "actions/checkout")
+            .unwrap();
+        assert_eq!(latest, Some("3.0.0-alpha".to_string())); // includes prerelease
+    }
+
+    #[test]
+    fn get_latest_version_returns_none_when_all_versions_are_prerelease_and_filtering_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, true).unwrap(); // ignore_prerelease = true
+
+        let versions = vec!["1.0.0-alpha".to_string(), "1.0.0-beta".to_string()];
+        cache
+            .replace_versions(
+                RegistryType::GitHubActions,
+                "actions/checkout",
+                versions,
+                &[],
+            )
+            .unwrap();
+
+        let latest = cache
+            .get_latest_version(RegistryType::GitHubActions, "actions/checkout")
+            .unwrap();
+        assert_eq!(latest, None); // all prerelease, so None
+    }
+
+    #[test]
+    fn get_latest_version_filters_go_pseudo_version_when_prerelease_filtering_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, true).unwrap(); // ignore_prerelease = true
+
+        let versions = vec![
+            "v1.0.0".to_string(),
+            "v0.0.0-20210201000000-abc123".to_string(), // pseudo-version (prerelease)
+        ];
+        cache
+            .replace_versions(
+                RegistryType::GoProxy,
+                "github.com/example/module",
+                versions,
+                &[],
+            )
+            .unwrap();
+
+        let latest = cache
+            .get_latest_version(RegistryType::GoProxy, "github.com/example/module")
+            .unwrap();
+        // pseudo-version is also filtered as prerelease
+        assert_eq!(latest, Some("v1.0.0".to_string()));
+    }
+
+    #[test]
+    fn get_latest_version_filters_go_regular_prerelease_when_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, true).unwrap(); // ignore_prerelease = true
+
+        let versions = vec![
+            "v1.0.0".to_string(),
+            "v2.0.0-alpha".to_string(), // regular prerelease
+        ];
+        cache
+            .replace_versions(
+                RegistryType::GoProxy,
+                "github.com/example/module",
+                versions,
+                &[],
+            )
+            .unwrap();
+
+        let latest = cache
+            .get_latest_version(RegistryType::GoProxy, "github.com/example/module")
+            .unwrap();
+        assert_eq!(latest, Some("v1.0.0".to_string())); // alpha is filtered
+    }
+
+    #[test]
+    fn mark_not_found_sets_not_found_flag() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        // Create a package entry via try_start_fetch (simulating a fetch attempt)
+        cache
+            .try_start_fetch(RegistryType::Npm, "nonexistent")
+            .unwrap();
+
+        // Mark as not found
+        cache
+            .mark_not_found(RegistryType::Npm, "nonexistent")
+            .unwrap();
+
+        // Verify the flag is set by checking it's excluded from needing refresh
+        cache
+            .finish_fetch(RegistryType::Npm, "nonexistent")
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(50));
+
+        let stale = cache.get_packages_needing_refresh().unwrap();
+        assert!(
+            stale.is_empty(),
+            "not_found packages should be excluded from refresh"
+        );
+    }
+
+    #[test]
+    fn get_packages_needing_refresh_skips_unknown_registry_types() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 100, false).unwrap();
+
+        cache
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+        // A registry type written by another version of version-lsp
+        cache
+            .lock_conn()
+            .unwrap()
+            .execute(
+                "INSERT INTO packages (registry_type, package_name, updated_at) VALUES ('bitbucket', 'repo', 0)",
+                [],
+            )
+            .unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(150));
+
+        let stale = cache.get_packages_needing_refresh().unwrap();
+        assert_eq!(
+            stale,
+            vec![PackageId {
+                registry_type: RegistryType::Npm,
+                package_name: "axios".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn get_packages_needing_refresh_excludes_not_found_packages() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        // refresh_interval = 100ms
+        let cache = Cache::new(&db_path, 100, false).unwrap();
+
+        // Add a normal package
+        cache
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+
+        // Add a package and mark as not found
+        cache
+            .try_start_fetch(RegistryType::Npm, "nonexistent")
+            .unwrap();
+        cache
+            .mark_not_found(RegistryType::Npm, "nonexistent")
+            .unwrap();
+        cache
+            .finish_fetch(RegistryType::Npm, "nonexistent")
+            .unwrap();
+
+        // Wait for packages to become stale
+        std::thread::sleep(std::time::Duration::from_millis(150));
+
+        let stale = cache.get_packages_needing_refresh().unwrap();
+
+        // Only axios should be returned, not the not_found package
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].package_name, "axios");
+    }
+
+    #[test]
+    fn filter_packages_not_in_cache_treats_not_found_as_cached() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        // Add a package and mark as not found
+        cache
+            .try_start_fetch(RegistryType::Npm, "nonexistent")
+            .unwrap();
+        cache
+            .mark_not_found(RegistryType::Npm, "nonexistent")
+            .unwrap();
+        cache
+            .finish_fetch(RegistryType::Npm, "nonexistent")
+            .unwrap();
+
+        // Add a normal package
+        cache
+            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+
+        let package_names = vec![
+            "nonexistent".to_string(), // marked as not found
+            "axios".to_string(),       // has versions
+            "express".to_string(),     // truly not in cache
+        ];
+
+        let not_in_cache = cache
+            .filter_packages_not_in_cache(RegistryType::Npm, &package_names)
+            .unwrap();
+
+        // Only express should be returned (nonexistent is not_found, axios has versions)
+        assert_eq!(not_in_cache, vec!["express".to_string()]);
+    }
+
+    #[test]
+    fn try_start_fetch_returns_false_for_not_found_package_within_ttl() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        cache.try_start_fetch(RegistryType::Npm, "loadsh").unwrap();
+        cache.mark_not_found(RegistryType::Npm, "loadsh").unwrap();
+        cache.finish_fetch(RegistryType::Npm, "loadsh").unwrap();
+
+        let result = cache.try_start_fetch(RegistryType::Npm, "loadsh").unwrap();
+
+        assert!(!result);
+    }
+
+    #[test]
+    fn try_start_fetch_returns_true_for_not_found_package_after_ttl() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        // not_found_ttl = 100ms
+        let cache = Cache::new(&db_path, 86400, false)
+            .unwrap()
+            .with_not_found_ttl(100);
+
+        cache.try_start_fetch(RegistryType::Npm, "loadsh").unwrap();
+        cache.mark_not_found(RegistryType::Npm, "loadsh").unwrap();
+        cache.finish_fetch(RegistryType::Npm, "loadsh").unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(150));
+
+        let result = cache.try_start_fetch(RegistryType::Npm, "loadsh").unwrap();
+
+        assert!(result);
+    }
+
+    #[test]
+    fn set_not_found_ttl_changes_when_not_found_package_is_retried() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
+
+        cache.try_start_fetch(RegistryType::Npm, "loadsh").unwrap();
+        cache.mark_not_found(RegistryType::Npm, "loadsh").unwrap();
+        cache.finish_fetch(RegistryType::Npm, "loadsh").unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(150));
+        assert!(!cache.try_start_fetch(RegistryType::Npm, "loadsh").unwrap());
+
+        cache.set_not_found_ttl(100);
+
+        assert!(cache.try_start_fetch(RegistryType::Npm, "loadsh").unwrap());
     }
 
     #[test]
-    fn filter_packages_not_in_cache_returns_empty_when_all_cached() {
+    fn filter_packages_not_in_cache_returns_not_found_package_after_ttl() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
-        let cache = Cache::new(&db_path, 86400, false).unwrap();
+        // not_found_ttl = 100ms
+        let cache = Cache::new(&db_path, 86400, false)
+            .unwrap()
+            .with_not_found_ttl(100);
 
-        cache
-            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()])
-            .unwrap();
+        cache.try_start_fetch(RegistryType::Npm, "loadsh").unwrap();
+        cache.mark_not_found(RegistryType::Npm, "loadsh").unwrap();
+        cache.finish_fetch(RegistryType::Npm, "loadsh").unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(150));
 
-        let package_names = vec!["axios".to_string()];
         let not_in_cache = cache
-            .filter_packages_not_in_cache(RegistryType::Npm, &package_names)
+            .filter_packages_not_in_cache(RegistryType::Npm, &["loadsh".to_string()])
             .unwrap();
 
-        assert!(not_in_cache.is_empty());
+        assert_eq!(not_in_cache, vec!["loadsh".to_string()]);
     }
 
     #[test]
-    fn filter_packages_not_in_cache_returns_all_when_none_cached() {
+    fn replace_versions_clears_not_found_flag() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
-        let cache = Cache::new(&db_path, 86400, false).unwrap();
+        // refresh_interval = 100ms
+        let cache = Cache::new(&db_path, 100, false).unwrap();
 
-        let package_names = vec!["express".to_string(), "react".to_string()];
-        let not_in_cache = cache
-            .filter_packages_not_in_cache(RegistryType::Npm, &package_names)
+        cache
+            .try_start_fetch(RegistryType::Npm, "left-pad")
+            .unwrap();
+        cache.mark_not_found(RegistryType::Npm, "left-pad").unwrap();
+        cache.finish_fetch(RegistryType::Npm, "left-pad").unwrap();
+        cache
+            .replace_versions(
+                RegistryType::Npm,
+                "left-pad",
+                vec!["1.3.0".to_string()],
+                &[],
+            )
             .unwrap();
 
+        std::thread::sleep(std::time::Duration::from_millis(150));
+
+        let stale = cache.get_packages_needing_refresh().unwrap();
         assert_eq!(
-            not_in_cache,
-            vec!["express".to_string(), "react".to_string()]
+            stale,
+            vec![PackageId {
+                registry_type: RegistryType::Npm,
+                package_name: "left-pad".to_string(),
+            }]
         );
     }
 
     #[test]
-    fn filter_packages_not_in_cache_respects_registry_type() {
+    fn save_and_get_metadata() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
         let cache = Cache::new(&db_path, 86400, false).unwrap();
+        let metadata = PackageMetadata {
+            homepage: Some("https://lodash.com/".to_string()),
+            repository: Some("https://github.com/lodash/lodash".to_string()),
+        };
 
-        // Add package to npm registry
         cache
-            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()])
+            .replace_versions(
+                RegistryType::Npm,
+                "lodash",
+                vec!["4.17.21".to_string()],
+                &[],
+            )
             .unwrap();
-
-        // Query same package name but different registry
-        let package_names = vec!["axios".to_string()];
-        let not_in_cache = cache
-            .filter_packages_not_in_cache(RegistryType::CratesIo, &package_names)
+        cache
+            .save_metadata(RegistryType::Npm, "lodash", &metadata)
             .unwrap();
 
-        // Should return axios because it's not in CratesIo registry
-        assert_eq!(not_in_cache, vec!["axios".to_string()]);
+        assert_eq!(
+            cache.get_metadata(RegistryType::Npm, "lodash").unwrap(),
+            Some(metadata)
+        );
     }
 
     #[test]
-    fn filter_packages_not_in_cache_treats_zero_versions_as_not_cached() {
+    fn stats_counts_packages_per_registry_and_versions() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
         let cache = Cache::new(&db_path, 86400, false).unwrap();
 
-        // Simulate a failed fetch: package record exists but no versions
-        // This happens when try_start_fetch creates a record but fetch_all_versions fails
+        let empty = cache.stats().unwrap();
+        assert_eq!(
+            empty,
+            CacheStats {
+                packages: HashMap::new(),
+                total_versions: 0,
+                oldest_updated_at: None,
+                newest_updated_at: None,
+            }
+        );
+
         cache
-            .try_start_fetch(RegistryType::Npm, "failed-package")
+            .replace_versions(
+                RegistryType::Npm,
+                "lodash",
+                vec!["4.17.20".to_string(), "4.17.21".to_string()],
+                &[],
+            )
             .unwrap();
         cache
-            .finish_fetch(RegistryType::Npm, "failed-package")
+            .replace_versions(RegistryType::Npm, "react", vec!["18.2.0".to_string()], &[])
             .unwrap();
-
-        // Add a package with versions for comparison
         cache
-            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()])
+            .replace_versions(
+                RegistryType::CratesIo,
+                "serde",
+                vec!["1.0.0".to_string()],
+                &[],
+            )
             .unwrap();
 
-        let package_names = vec!["failed-package".to_string(), "axios".to_string()];
-        let not_in_cache = cache
-            .filter_packages_not_in_cache(RegistryType::Npm, &package_names)
-            .unwrap();
+        let stats = cache.stats().unwrap();
 
-        // Should return failed-package because it has 0 versions
-        assert_eq!(not_in_cache, vec!["failed-package".to_string()]);
+        assert_eq!(
+            stats.packages,
+            HashMap::from([("npm".to_string(), 2), ("crates_io".to_string(), 1)])
+        );
+        assert_eq!(stats.total_versions, 4);
+        assert!(stats.oldest_updated_at.is_some());
+        assert!(stats.oldest_updated_at <= stats.newest_updated_at);
     }
 
     #[test]
-    fn get_latest_version_filters_prerelease_when_enabled() {
+    fn get_last_fetch_time_returns_updated_at_of_fetched_package() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
-        let cache = Cache::new(&db_path, 86400, true).unwrap(); // ignore_prerelease = true
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
 
-        let versions = vec![
-            "1.0.0".to_string(),
-            "2.0.0".to_string(),
-            "3.0.0-alpha".to_string(), // prerelease (highest but filtered)
-        ];
+        let before = Cache::current_timestamp_ms();
         cache
-            .replace_versions(RegistryType::GitHubActions, "actions/checkout", versions)
+            .replace_versions(
+                RegistryType::Npm,
+                "lodash",
+                vec!["4.17.21".to_string()],
+                &[],
+            )
             .unwrap();
+        // Being fetched for the first time: no versions yet
+        assert!(cache.try_start_fetch(RegistryType::Npm, "react").unwrap());
 
-        let latest = cache
-            .get_latest_version(RegistryType::GitHubActions, "actions/checkout")
+        let fetched = cache
+            .get_last_fetch_time(RegistryType::Npm, "lodash")
+            .unwrap()
             .unwrap();
-        assert_eq!(latest, Some("2.0.0".to_string())); // highest stable version
+        assert!(fetched >= before && fetched <= Cache::current_timestamp_ms());
+        assert_eq!(
+            cache
+                .get_last_fetch_time(RegistryType::Npm, "react")
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            cache
+                .get_last_fetch_time(RegistryType::Npm, "missing")
+                .unwrap(),
+            None
+        );
     }
 
     #[test]
-    fn get_latest_version_includes_prerelease_when_disabled() {
+    fn list_packages_returns_fetched_packages_in_order() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
-        let cache = Cache::new(&db_path, 86400, false).unwrap(); // ignore_prerelease = false
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
 
-        let versions = vec![
-            "1.0.0".to_string(),
-            "2.0.0".to_string(),
-            "3.0.0-alpha".to_string(), // prerelease
-        ];
         cache
-            .replace_versions(RegistryType::GitHubActions, "actions/checkout", versions)
-            .unwrap();
-
-        let latest = cache
-            .get_latest_version(RegistryType::GitHubActions, "actions/checkout")
+            .replace_versions(RegistryType::Npm, "react", vec!["18.2.0".to_string()], &[])
             .unwrap();
-        assert_eq!(latest, Some("3.0.0-alpha".to_string())); // includes prerelease
-    }
-
-    #[test]
-    fn get_latest_version_returns_none_when_all_versions_are_prerelease_and_filtering_enabled() {
-        let temp_dir = TempDir::new().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-        let cache = Cache::new(&db_path, 86400, true).unwrap(); // ignore_prerelease = true
-
-        let versions = vec!["1.0.0-alpha".to_string(), "1.0.0-beta".to_string()];
         cache
-            .replace_versions(RegistryType::GitHubActions, "actions/checkout", versions)
-            .unwrap();
-
-        let latest = cache
-            .get_latest_version(RegistryType::GitHubActions, "actions/checkout")
+            .replace_versions(
+                RegistryType::CratesIo,
+                "serde",
+                vec!["1.0.0".to_string()],
+                &[],
+            )
             .unwrap();
-        assert_eq!(latest, None); // all prerelease, so None
-    }
-
-    #[test]
-    fn get_latest_version_filters_go_pseudo_version_when_prerelease_filtering_enabled() {
-        let temp_dir = TempDir::new().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-        let cache = Cache::new(&db_path, 86400, true).unwrap(); // ignore_prerelease = true
-
-        let versions = vec![
-            "v1.0.0".to_string(),
-            "v0.0.0-20210201000000-abc123".to_string(), // pseudo-version (prerelease)
-        ];
         cache
-            .replace_versions(RegistryType::GoProxy, "github.com/example/module", versions)
+            .replace_versions(
+                RegistryType::Npm,
+                "lodash",
+                vec!["4.17.21".to_string()],
+                &[],
+            )
             .unwrap();
+        assert!(cache.try_start_fetch(RegistryType::Npm, "vue").unwrap());
 
-        let latest = cache
-            .get_latest_version(RegistryType::GoProxy, "github.com/example/module")
-            .unwrap();
-        // pseudo-version is also filtered as prerelease
-        assert_eq!(latest, Some("v1.0.0".to_string()));
+        let listed: Vec<(String, String)> = cache
+            .list_packages()
+            .unwrap()
+            .into_iter()
+            .map(|package| (package.registry_type, package.package_name))
+            .collect();
+
+        assert_eq!(
+            listed,
+            vec![
+                ("crates_io".to_string(), "serde".to_string()),
+                ("npm".to_string(), "lodash".to_string()),
+                ("npm".to_string(), "react".to_string()),
+            ]
+        );
     }
 
     #[test]
-    fn get_latest_version_filters_go_regular_prerelease_when_enabled() {
+    fn get_metadata_returns_none_when_not_saved() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
-        let cache = Cache::new(&db_path, 86400, true).unwrap(); // ignore_prerelease = true
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
 
-        let versions = vec![
-            "v1.0.0".to_string(),
-            "v2.0.0-alpha".to_string(), // regular prerelease
-        ];
         cache
-            .replace_versions(RegistryType::GoProxy, "github.com/example/module", versions)
+            .replace_versions(
+                RegistryType::Npm,
+                "lodash",
+                vec!["4.17.21".to_string()],
+                &[],
+            )
             .unwrap();
 
-        let latest = cache
-            .get_latest_version(RegistryType::GoProxy, "github.com/example/module")
-            .unwrap();
-        assert_eq!(latest, Some("v1.0.0".to_string())); // alpha is filtered
+        assert_eq!(
+            cache.get_metadata(RegistryType::Npm, "lodash").unwrap(),
+            None
+        );
+        assert_eq!(
+            cache.get_metadata(RegistryType::Npm, "missing").unwrap(),
+            None
+        );
     }
 
     #[test]
-    fn mark_not_found_sets_not_found_flag() {
+    fn save_and_get_cache_metadata() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
         let cache = Cache::new(&db_path, 86400, false).unwrap();
+        let cache_metadata = CacheMetadata {
+            etag: Some("W/\"abc123\"".to_string()),
+            last_modified: Some("Wed, 01 Jan 2025 00:00:00 GMT".to_string()),
+        };
 
-        // Create a package entry via try_start_fetch (simulating a fetch attempt)
         cache
-            .try_start_fetch(RegistryType::Npm, "nonexistent")
+            .replace_versions(
+                RegistryType::Npm,
+                "lodash",
+                vec!["4.17.21".to_string()],
+                &[],
+            )
             .unwrap();
 
-        // Mark as not found
-        cache
-            .mark_not_found(RegistryType::Npm, "nonexistent")
-            .unwrap();
+        assert_eq!(
+            cache
+                .get_cache_metadata(RegistryType::Npm, "lodash")
+                .unwrap(),
+            None
+        );
 
-        // Verify the flag is set by checking it's excluded from needing refresh
         cache
-            .finish_fetch(RegistryType::Npm, "nonexistent")
+            .save_cache_metadata(RegistryType::Npm, "lodash", &cache_metadata)
             .unwrap();
-        std::thread::sleep(std::time::Duration::from_millis(50));
 
-        let stale = cache.get_packages_needing_refresh().unwrap();
-        assert!(
-            stale.is_empty(),
-            "not_found packages should be excluded from refresh"
+        assert_eq!(
+            cache
+                .get_cache_metadata(RegistryType::Npm, "lodash")
+                .unwrap(),
+            Some(cache_metadata)
+        );
+        assert_eq!(
+            cache
+                .get_cache_metadata(RegistryType::Npm, "missing")
+                .unwrap(),
+            None
         );
     }
 
     #[test]
-    fn get_packages_needing_refresh_excludes_not_found_packages() {
+    fn save_and_get_deprecated() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
-        // refresh_interval = 100ms
-        let cache = Cache::new(&db_path, 100, false).unwrap();
+        let cache = Cache::new(&db_path, 86400, false).unwrap();
 
-        // Add a normal package
         cache
-            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()])
+            .replace_versions(
+                RegistryType::Npm,
+                "request",
+                vec!["2.88.2".to_string()],
+                &[],
+            )
             .unwrap();
 
-        // Add a package and mark as not found
-        cache
-            .try_start_fetch(RegistryType::Npm, "nonexistent")
-            .unwrap();
-        cache
-            .mark_not_found(RegistryType::Npm, "nonexistent")
-            .unwrap();
+        assert_eq!(
+            cache.get_deprecated(RegistryType::Npm, "request").unwrap(),
+            None
+        );
+
         cache
-            .finish_fetch(RegistryType::Npm, "nonexistent")
+            .save_deprecated(
+                RegistryType::Npm,
+                "request",
+                Some("request has been deprecated".to_string()),
+            )
             .unwrap();
 
-        // Wait for packages to become stale
-        std::thread::sleep(std::time::Duration::from_millis(150));
+        assert_eq!(
+            cache.get_deprecated(RegistryType::Npm, "request").unwrap(),
+            Some("request has been deprecated".to_string())
+        );
+        assert_eq!(
+            cache.get_deprecated(RegistryType::Npm, "missing").unwrap(),
+            None
+        );
 
-        let stale = cache.get_packages_needing_refresh().unwrap();
+        cache
+            .save_deprecated(RegistryType::Npm, "request", None)
+            .unwrap();
 
-        // Only axios should be returned, not the not_found package
-        assert_eq!(stale.len(), 1);
-        assert_eq!(stale[0].package_name, "axios");
+        assert_eq!(
+            cache.get_deprecated(RegistryType::Npm, "request").unwrap(),
+            None
+        );
     }
 
     #[test]
-    fn filter_packages_not_in_cache_treats_not_found_as_cached() {
+    fn save_and_get_advisories() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("test.db");
         let cache = Cache::new(&db_path, 86400, false).unwrap();
+        let advisories = vec![Advisory {
+            id: "RUSTSEC-2020-0071".to_string(),
+            severity: "MODERATE".to_string(),
+            summary: "Potential segfault in the time crate".to_string(),
+        }];
 
-        // Add a package and mark as not found
-        cache
-            .try_start_fetch(RegistryType::Npm, "nonexistent")
-            .unwrap();
-        cache
-            .mark_not_found(RegistryType::Npm, "nonexistent")
-            .unwrap();
         cache
-            .finish_fetch(RegistryType::Npm, "nonexistent")
+            .replace_versions(
+                RegistryType::CratesIo,
+                "time",
+                vec!["0.1.45".to_string()],
+                &[],
+            )
             .unwrap();
 
-        // Add a normal package
+        assert!(
+            cache
+                .get_advisories(RegistryType::CratesIo, "time")
+                .unwrap()
+                .is_empty()
+        );
+
         cache
-            .replace_versions(RegistryType::Npm, "axios", vec!["1.0.0".to_string()])
+            .save_advisories(RegistryType::CratesIo, "time", &advisories)
             .unwrap();
 
-        let package_names = vec![
-            "nonexistent".to_string(), // marked as not found
-            "axios".to_string(),       // has versions
-            "express".to_string(),     // truly not in cache
-        ];
+        assert_eq!(
+            cache
+                .get_advisories(RegistryType::CratesIo, "time")
+                .unwrap(),
+            advisories
+        );
+        assert!(
+            cache
+                .get_advisories(RegistryType::CratesIo, "missing")
+                .unwrap()
+                .is_empty()
+        );
 
-        let not_in_cache = cache
-            .filter_packages_not_in_cache(RegistryType::Npm, &package_names)
+        cache
+            .save_advisories(RegistryType::CratesIo, "time", &[])
             .unwrap();
 
-        // Only express should be returned (nonexistent is not_found, axios has versions)
-        assert_eq!(not_in_cache, vec!["express".to_string()]);
+        assert!(
+            cache
+                .get_advisories(RegistryType::CratesIo, "time")
+                .unwrap()
+                .is_empty()
+        );
     }
 
     mod migrations {
@@ -1354,13 +2884,23 @@ mod tests {
 
         #[rstest]
         // New DB: both columns added
-        #[case(false, false, 0, 2)]
+        #[case(false, false, 0, 7)]
         // Existing DB with fetching_since only: not_found added
-        #[case(true, false, 0, 2)]
+        #[case(true, false, 0, 7)]
         // Existing DB with both columns: skip (duplicate detection)
-        #[case(true, true, 0, 2)]
+        #[case(true, true, 0, 7)]
+        // Existing DB at v2: metadata, validators, deprecated, advisories and yanked added
+        #[case(true, true, 2, 7)]
+        // Existing DB at v3: validators, deprecated, advisories and yanked added
+        #[case(true, true, 3, 7)]
+        // Existing DB at v4: deprecated, advisories and yanked added
+        #[case(true, true, 4, 7)]
+        // Existing DB at v5: advisories and yanked added
+        #[case(true, true, 5, 7)]
+        // Existing DB at v6: only yanked added
+        #[case(true, true, 6, 7)]
         // Existing DB with user_version already set: skip migrations
-        #[case(true, true, 2, 2)]
+        #[case(true, true, 7, 7)]
         fn migration_applies_correctly(
             #[case] has_fetching_since: bool,
             #[case] has_not_found: bool,
@@ -1389,6 +2929,37 @@ mod tests {
                 column_exists(&conn, "packages", "not_found"),
                 "not_found should exist"
             );
+            if initial_version < 3 {
+                assert!(
+                    column_exists(&conn, "packages", "metadata"),
+                    "metadata should exist"
+                );
+            }
+            if initial_version < 4 {
+                assert!(
+                    column_exists(&conn, "packages", "etag")
+                        && column_exists(&conn, "packages", "last_modified"),
+                    "etag and last_modified should exist"
+                );
+            }
+            if initial_version < 5 {
+                assert!(
+                    column_exists(&conn, "packages", "deprecated"),
+                    "deprecated should exist"
+                );
+            }
+            if initial_version < 6 {
+                assert!(
+                    column_exists(&conn, "packages", "advisories"),
+                    "advisories should exist"
+                );
+            }
+            if initial_version < 7 {
+                assert!(
+                    column_exists(&conn, "versions", "yanked"),
+                    "yanked should exist"
+                );
+            }
             assert_eq!(get_user_version(&conn), expected_version);
         }
 
@@ -7,6 +7,9 @@ use crate::parser::types::RegistryType;
 use crate::version::error::CacheError;
 use crate::version::matcher::VersionMatcher;
 use crate::version::semver::CompareResult;
+use crate::version::types::{
+    Advisory, CacheMetadata, PackageMetadata, PackageSummary, PackageVersions,
+};
 
 use crate::version::cache::PackageId;
 
@@ -21,26 +24,105 @@ pub trait VersionStorer: Send + Sync + 'static {
     ) -> Result<Option<String>, CacheError>;
 
     /// Get all versions for a package
+    /// Versions come back in the order they were stored, which need not be
+    /// semver order; use [`Self::get_versions_ordered_by_semver`] when it matters.
+    /// Yanked versions are left out unless `include_yanked` (`showYanked`).
     fn get_versions(
         &self,
         registry_type: RegistryType,
         package_name: &str,
+        include_yanked: bool,
     ) -> Result<Vec<String>, CacheError>;
 
+    /// Get all versions for a package, oldest to newest by semver
+    /// Versions that cannot be parsed keep their stored order after the semver ones.
+    fn get_versions_ordered_by_semver(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        include_yanked: bool,
+    ) -> Result<Vec<String>, CacheError> {
+        let versions = self.get_versions(registry_type, package_name, include_yanked)?;
+        Ok(PackageVersions::sorted_by_semver(versions).versions)
+    }
+
+    /// Get all versions of several packages at once, keyed by
+    /// `(registry_type, package_name)`
+    /// Packages without cached versions are left out. The default looks up
+    /// each package with [`Self::get_versions`].
+    // The lifetime is named because automock can't mock an elided one here
+    #[allow(clippy::needless_lifetimes)]
+    fn batch_get_versions<'a>(
+        &self,
+        packages: &[(RegistryType, &'a str)],
+        include_yanked: bool,
+    ) -> Result<std::collections::HashMap<(RegistryType, String), Vec<String>>, CacheError> {
+        let mut versions = std::collections::HashMap::new();
+        for (registry_type, package_name) in packages {
+            let package_versions =
+                self.get_versions(*registry_type, package_name, include_yanked)?;
+            if !package_versions.is_empty() {
+                versions.insert((*registry_type, package_name.to_string()), package_versions);
+            }
+        }
+        Ok(versions)
+    }
+
+    /// Get the latest version, dist tags, deprecation and advisories of
+    /// several packages at once, keyed by `(registry_type, package_name)`
+    /// Packages without a latest version are left out. The default looks up
+    /// each package with the single-package methods.
+    // The lifetime is named because automock can't mock an elided one here
+    #[allow(clippy::needless_lifetimes)]
+    fn batch_get_package_summaries<'a>(
+        &self,
+        packages: &[(RegistryType, &'a str)],
+    ) -> Result<std::collections::HashMap<(RegistryType, String), PackageSummary>, CacheError> {
+        let mut summaries = std::collections::HashMap::new();
+        for (registry_type, package_name) in packages {
+            let Some(latest_version) = self.get_latest_version(*registry_type, package_name)?
+            else {
+                continue;
+            };
+            let summary = PackageSummary {
+                latest_version,
+                dist_tags: self.get_dist_tags(*registry_type, package_name)?,
+                deprecated: self.get_deprecated(*registry_type, package_name)?,
+                advisories: self.get_advisories(*registry_type, package_name)?,
+            };
+            summaries.insert((*registry_type, package_name.to_string()), summary);
+        }
+        Ok(summaries)
+    }
+
     /// Check if a specific version exists for a package
+    /// A yanked version only exists if `include_yanked`.
     fn version_exists(
         &self,
         registry_type: RegistryType,
         package_name: &str,
         version: &str,
+        include_yanked: bool,
     ) -> Result<bool, CacheError>;
 
     /// Replace all versions for a package
+    /// `yanked` lists the versions the registry yanked, including ones
+    /// cached before they were yanked; they are kept but marked, so version
+    /// lookups leave them out unless asked to include them.
     fn replace_versions(
         &self,
         registry_type: RegistryType,
         package_name: &str,
         versions: Vec<String>,
+        yanked: &[String],
+    ) -> Result<(), CacheError>;
+
+    /// Record that the registry confirmed the cached versions of a package
+    /// are current (e.g. answered 304 Not Modified), so it is fresh again
+    fn touch_package(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
     ) -> Result<(), CacheError>;
 
     /// Get packages that need to be refreshed
@@ -70,12 +152,25 @@ pub trait VersionStorer: Send + Sync + 'static {
     ) -> Result<Option<String>, CacheError>;
 
     /// Save dist tags for a package
+    /// Storers that do not keep dist tags discard them.
     fn save_dist_tags(
         &self,
-        registry_type: RegistryType,
-        package_name: &str,
-        dist_tags: &std::collections::HashMap<String, String>,
-    ) -> Result<(), CacheError>;
+        _registry_type: RegistryType,
+        _package_name: &str,
+        _dist_tags: &std::collections::HashMap<String, String>,
+    ) -> Result<(), CacheError> {
+        Ok(())
+    }
+
+    /// Get all dist tags for a package (tag name -> version)
+    /// Empty when the package has no dist tags or the storer keeps none.
+    fn get_dist_tags(
+        &self,
+        _registry_type: RegistryType,
+        _package_name: &str,
+    ) -> Result<std::collections::HashMap<String, String>, CacheError> {
+        Ok(std::collections::HashMap::new())
+    }
 
     /// Filter packages that are not in the cache
     /// Returns package names that have no entries in the cache
@@ -86,12 +181,365 @@ pub trait VersionStorer: Send + Sync + 'static {
     ) -> Result<Vec<String>, CacheError>;
 
     /// Mark a package as not found (does not exist in registry)
-    /// This prevents repeated fetch attempts for non-existent packages
+    /// This prevents repeated fetch attempts for non-existent packages until
+    /// the not-found TTL elapses
     fn mark_not_found(
         &self,
         registry_type: RegistryType,
         package_name: &str,
     ) -> Result<(), CacheError>;
+
+    /// Set how long (in milliseconds) a not-found mark suppresses fetches
+    ///
+    /// Called whenever the configuration is applied. Storers whose not-found
+    /// marks don't expire ignore it.
+    fn set_not_found_ttl(&self, _not_found_ttl: i64) {}
+
+    /// Filter packages whose cached versions are older than the refresh interval
+    /// Stale versions are still served; callers refresh them in the background.
+    fn filter_stale_packages(
+        &self,
+        _registry_type: RegistryType,
+        _package_names: &[String],
+    ) -> Result<Vec<String>, CacheError> {
+        Ok(Vec::new())
+    }
+
+    /// Save homepage/repository links for a package
+    fn save_metadata(
+        &self,
+        _registry_type: RegistryType,
+        _package_name: &str,
+        _metadata: &PackageMetadata,
+    ) -> Result<(), CacheError> {
+        Ok(())
+    }
+
+    /// Get homepage/repository links for a package
+    fn get_metadata(
+        &self,
+        _registry_type: RegistryType,
+        _package_name: &str,
+    ) -> Result<Option<PackageMetadata>, CacheError> {
+        Ok(None)
+    }
+
+    /// Save the ETag/Last-Modified validators of the last registry response
+    fn save_cache_metadata(
+        &self,
+        _registry_type: RegistryType,
+        _package_name: &str,
+        _cache_metadata: &CacheMetadata,
+    ) -> Result<(), CacheError> {
+        Ok(())
+    }
+
+    /// Get the validators to make the next fetch of a package conditional
+    fn get_cache_metadata(
+        &self,
+        _registry_type: RegistryType,
+        _package_name: &str,
+    ) -> Result<Option<CacheMetadata>, CacheError> {
+        Ok(None)
+    }
+
+    /// Count cached packages per registry, keyed by `RegistryType::as_str()`
+    fn count_packages(&self) -> Result<std::collections::HashMap<String, usize>, CacheError> {
+        Ok(std::collections::HashMap::new())
+    }
+
+    /// When the versions of a package were last fetched, in milliseconds
+    /// since the UNIX epoch
+    /// Returns None if no versions are stored.
+    fn get_last_fetch_time(
+        &self,
+        _registry_type: RegistryType,
+        _package_name: &str,
+    ) -> Result<Option<i64>, CacheError> {
+        Ok(None)
+    }
+
+    /// Save the registry's deprecation message for a package
+    /// `None` clears a previous deprecation.
+    fn save_deprecated(
+        &self,
+        _registry_type: RegistryType,
+        _package_name: &str,
+        _deprecated: Option<String>,
+    ) -> Result<(), CacheError> {
+        Ok(())
+    }
+
+    /// Get the deprecation message of a package, if the registry deprecated it
+    fn get_deprecated(
+        &self,
+        _registry_type: RegistryType,
+        _package_name: &str,
+    ) -> Result<Option<String>, CacheError> {
+        Ok(None)
+    }
+
+    /// Save the security advisories affecting the latest version of a package
+    /// An empty list clears previous advisories.
+    fn save_advisories(
+        &self,
+        _registry_type: RegistryType,
+        _package_name: &str,
+        _advisories: &[Advisory],
+    ) -> Result<(), CacheError> {
+        Ok(())
+    }
+
+    /// Get the security advisories affecting the latest version of a package
+    fn get_advisories(
+        &self,
+        _registry_type: RegistryType,
+        _package_name: &str,
+    ) -> Result<Vec<Advisory>, CacheError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Delegating impl so that a shared `Arc<T>` can be passed wherever a
+/// `VersionStorer` is expected, without dereferencing at every call site.
+impl<T: VersionStorer> VersionStorer for std::sync::Arc<T> {
+    fn get_latest_version(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<String>, CacheError> {
+        self.as_ref()
+            .get_latest_version(registry_type, package_name)
+    }
+
+    fn get_versions(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        include_yanked: bool,
+    ) -> Result<Vec<String>, CacheError> {
+        self.as_ref()
+            .get_versions(registry_type, package_name, include_yanked)
+    }
+
+    fn get_versions_ordered_by_semver(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        include_yanked: bool,
+    ) -> Result<Vec<String>, CacheError> {
+        self.as_ref()
+            .get_versions_ordered_by_semver(registry_type, package_name, include_yanked)
+    }
+
+    fn batch_get_versions(
+        &self,
+        packages: &[(RegistryType, &str)],
+        include_yanked: bool,
+    ) -> Result<std::collections::HashMap<(RegistryType, String), Vec<String>>, CacheError> {
+        self.as_ref().batch_get_versions(packages, include_yanked)
+    }
+
+    fn batch_get_package_summaries(
+        &self,
+        packages: &[(RegistryType, &str)],
+    ) -> Result<std::collections::HashMap<(RegistryType, String), PackageSummary>, CacheError> {
+        self.as_ref().batch_get_package_summaries(packages)
+    }
+
+    fn version_exists(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        version: &str,
+        include_yanked: bool,
+    ) -> Result<bool, CacheError> {
+        self.as_ref()
+            .version_exists(registry_type, package_name, version, include_yanked)
+    }
+
+    fn replace_versions(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        versions: Vec<String>,
+        yanked: &[String],
+    ) -> Result<(), CacheError> {
+        self.as_ref()
+            .replace_versions(registry_type, package_name, versions, yanked)
+    }
+
+    fn touch_package(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<(), CacheError> {
+        self.as_ref().touch_package(registry_type, package_name)
+    }
+
+    fn get_packages_needing_refresh(&self) -> Result<Vec<PackageId>, CacheError> {
+        self.as_ref().get_packages_needing_refresh()
+    }
+
+    fn try_start_fetch(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<bool, CacheError> {
+        self.as_ref().try_start_fetch(registry_type, package_name)
+    }
+
+    fn finish_fetch(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<(), CacheError> {
+        self.as_ref().finish_fetch(registry_type, package_name)
+    }
+
+    fn get_dist_tag(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        tag_name: &str,
+    ) -> Result<Option<String>, CacheError> {
+        self.as_ref()
+            .get_dist_tag(registry_type, package_name, tag_name)
+    }
+
+    fn save_dist_tags(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        dist_tags: &std::collections::HashMap<String, String>,
+    ) -> Result<(), CacheError> {
+        self.as_ref()
+            .save_dist_tags(registry_type, package_name, dist_tags)
+    }
+
+    fn get_dist_tags(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<std::collections::HashMap<String, String>, CacheError> {
+        self.as_ref().get_dist_tags(registry_type, package_name)
+    }
+
+    fn filter_packages_not_in_cache(
+        &self,
+        registry_type: RegistryType,
+        package_names: &[String],
+    ) -> Result<Vec<String>, CacheError> {
+        self.as_ref()
+            .filter_packages_not_in_cache(registry_type, package_names)
+    }
+
+    fn mark_not_found(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<(), CacheError> {
+        self.as_ref().mark_not_found(registry_type, package_name)
+    }
+
+    fn set_not_found_ttl(&self, not_found_ttl: i64) {
+        self.as_ref().set_not_found_ttl(not_found_ttl)
+    }
+
+    fn filter_stale_packages(
+        &self,
+        registry_type: RegistryType,
+        package_names: &[String],
+    ) -> Result<Vec<String>, CacheError> {
+        self.as_ref()
+            .filter_stale_packages(registry_type, package_names)
+    }
+
+    fn save_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        metadata: &PackageMetadata,
+    ) -> Result<(), CacheError> {
+        self.as_ref()
+            .save_metadata(registry_type, package_name, metadata)
+    }
+
+    fn get_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<PackageMetadata>, CacheError> {
+        self.as_ref().get_metadata(registry_type, package_name)
+    }
+
+    fn save_cache_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        cache_metadata: &CacheMetadata,
+    ) -> Result<(), CacheError> {
+        self.as_ref()
+            .save_cache_metadata(registry_type, package_name, cache_metadata)
+    }
+
+    fn get_cache_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<CacheMetadata>, CacheError> {
+        self.as_ref()
+            .get_cache_metadata(registry_type, package_name)
+    }
+
+    fn count_packages(&self) -> Result<std::collections::HashMap<String, usize>, CacheError> {
+        self.as_ref().count_packages()
+    }
+
+    fn get_last_fetch_time(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<i64>, CacheError> {
+        self.as_ref()
+            .get_last_fetch_time(registry_type, package_name)
+    }
+
+    fn save_deprecated(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        deprecated: Option<String>,
+    ) -> Result<(), CacheError> {
+        self.as_ref()
+            .save_deprecated(registry_type, package_name, deprecated)
+    }
+
+    fn get_deprecated(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<String>, CacheError> {
+        self.as_ref().get_deprecated(registry_type, package_name)
+    }
+
+    fn save_advisories(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        advisories: &[Advisory],
+    ) -> Result<(), CacheError> {
+        self.as_ref()
+            .save_advisories(registry_type, package_name, advisories)
+    }
+
+    fn get_advisories(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Vec<Advisory>, CacheError> {
+        self.as_ref().get_advisories(registry_type, package_name)
+    }
 }
 
 /// Result of version comparison
@@ -122,44 +570,19 @@ pub enum VersionStatus {
     NotFound,
 }
 
-/// Common npm dist-tag names that we should not treat as invalid versions
-/// These are well-known dist-tags used in the npm ecosystem
-const KNOWN_DIST_TAGS: &[&str] = &[
-    "latest",
-    "next",
-    "beta",
-    "alpha",
-    "canary",
-    "rc",
-    "stable",
-    "dev",
-    "experimental",
-    "nightly",
-    "preview",
-    "insiders",
-    "edge",
-];
-
-/// Check if a version string is a known dist-tag pattern
-/// Returns true only for well-known dist-tag names
-fn is_potential_dist_tag(version: &str) -> bool {
-    KNOWN_DIST_TAGS.contains(&version.to_lowercase().as_str())
-}
-
 /// Compare the version status for a package
+/// A version the registry yanked is only found if `include_yanked`.
 pub fn compare_version<S: VersionStorer>(
     storer: &S,
     matcher: &dyn VersionMatcher,
     package_name: &str,
     current_version: &str,
+    include_yanked: bool,
 ) -> Result<VersionCompareResult, CacheError> {
     let registry_type = matcher.registry_type();
 
-    // Get latest version from storer
-    let latest_version = storer.get_latest_version(registry_type, package_name)?;
-
     // If no versions in cache, return NotInCache
-    let Some(latest) = latest_version else {
+    let Some(latest) = storer.get_latest_version(registry_type, package_name)? else {
         return Ok(VersionCompareResult {
             current_version: current_version.to_string(),
             latest_version: None,
@@ -167,32 +590,55 @@ pub fn compare_version<S: VersionStorer>(
         });
     };
 
+    let all_versions = storer.get_versions(registry_type, package_name, include_yanked)?;
     // Try to resolve dist-tag to actual version (e.g., "latest" -> "4.17.21")
-    let dist_tag_resolution = storer.get_dist_tag(registry_type, package_name, current_version)?;
+    let dist_tag_version = storer.get_dist_tag(registry_type, package_name, current_version)?;
+
+    Ok(compare_version_with_versions(
+        matcher,
+        current_version,
+        &all_versions,
+        &latest,
+        dist_tag_version.as_deref(),
+    ))
+}
 
-    // If version looks like a dist-tag but we couldn't resolve it, return NotInCache
-    // This avoids showing "Invalid version format" for unresolved dist-tags like "latest"
-    let resolved_version = match dist_tag_resolution {
+/// Compare the version status for a package whose cached versions, latest
+/// version and dist tags were already looked up (e.g. with
+/// [`VersionStorer::batch_get_versions`] and
+/// [`VersionStorer::batch_get_package_summaries`])
+///
+/// `dist_tag_version` is the version `current_version` resolves to when it
+/// is a dist tag.
+pub fn compare_version_with_versions(
+    matcher: &dyn VersionMatcher,
+    current_version: &str,
+    all_versions: &[String],
+    latest: &str,
+    dist_tag_version: Option<&str>,
+) -> VersionCompareResult {
+    // If version is a dist-tag but we couldn't resolve it, return NotInCache
+    // This avoids reporting an unresolved dist-tag like "latest" as up to date
+    let resolved_version = match dist_tag_version {
         Some(version) => version,
-        None if is_potential_dist_tag(current_version) => {
-            return Ok(VersionCompareResult {
+        None if matcher.is_dist_tag(current_version) => {
+            return VersionCompareResult {
                 current_version: current_version.to_string(),
-                latest_version: Some(latest),
+                latest_version: Some(latest.to_string()),
                 status: VersionStatus::NotInCache,
-            });
+            };
         }
-        None => current_version.to_string(),
+        None => current_version,
     };
 
     // Check if current version exists in registry
-    let all_versions = storer.get_versions(registry_type, package_name)?;
-    let version_exists = matcher.version_exists(&resolved_version, &all_versions);
+    let version_exists = matcher.version_exists(resolved_version, all_versions);
 
     // Let matcher resolve the effective latest version (e.g., Docker suffix matching)
-    let effective_latest = matcher.resolve_latest(&resolved_version, &latest, &all_versions);
+    let effective_latest = matcher.resolve_latest(resolved_version, latest, all_versions);
 
     // Compare versions
-    let status = match matcher.compare_to_latest(&resolved_version, &effective_latest) {
+    let status = match matcher.compare_to_latest(resolved_version, &effective_latest) {
         CompareResult::Invalid => VersionStatus::Invalid,
         _ if !version_exists => VersionStatus::NotFound,
         CompareResult::Latest => VersionStatus::Latest,
@@ -200,11 +646,11 @@ pub fn compare_version<S: VersionStorer>(
         CompareResult::Newer => VersionStatus::Newer,
     };
 
-    Ok(VersionCompareResult {
+    VersionCompareResult {
         current_version: current_version.to_string(),
         latest_version: Some(effective_latest),
         status,
-    })
+    }
 }
 
 #[cfg(test)]
@@ -255,6 +701,7 @@ mod tests {
             &self,
             _registry_type: RegistryType,
             _package_name: &str,
+            _include_yanked: bool,
         ) -> Result<Vec<String>, CacheError> {
             Ok(self.existing_versions.clone())
         }
@@ -264,6 +711,7 @@ mod tests {
             _registry_type: RegistryType,
             _package_name: &str,
             version: &str,
+            _include_yanked: bool,
         ) -> Result<bool, CacheError> {
             Ok(self.existing_versions.contains(&version.to_string()))
         }
@@ -273,6 +721,15 @@ mod tests {
             _registry_type: RegistryType,
             _package_name: &str,
             _versions: Vec<String>,
+            _yanked: &[String],
+        ) -> Result<(), CacheError> {
+            Ok(())
+        }
+
+        fn touch_package(
+            &self,
+            _registry_type: RegistryType,
+            _package_name: &str,
         ) -> Result<(), CacheError> {
             Ok(())
         }
@@ -348,7 +805,8 @@ mod tests {
         let storer = MockStorer::new(Some(latest), existing);
         let matcher = GitHubActionsMatcher;
 
-        let result = compare_version(&storer, &matcher, "actions/checkout", current).unwrap();
+        let result =
+            compare_version(&storer, &matcher, "actions/checkout", current, false).unwrap();
 
         assert_eq!(result.current_version, current);
         assert_eq!(result.latest_version, Some(latest.to_string()));
@@ -360,7 +818,8 @@ mod tests {
         let storer = MockStorer::new(None, vec![]);
         let matcher = GitHubActionsMatcher;
 
-        let result = compare_version(&storer, &matcher, "nonexistent/repo", "1.0.0").unwrap();
+        let result =
+            compare_version(&storer, &matcher, "nonexistent/repo", "1.0.0", false).unwrap();
 
         assert_eq!(
             result,
@@ -372,6 +831,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compare_version_accepts_arc_wrapped_storer() {
+        let storer = std::sync::Arc::new(MockStorer::new(Some("4.0.0"), vec!["4.0.0", "3.0.0"]));
+        let matcher = GitHubActionsMatcher;
+
+        let result =
+            compare_version(&storer, &matcher, "actions/checkout", "3.0.0", false).unwrap();
+
+        assert_eq!(
+            result,
+            VersionCompareResult {
+                current_version: "3.0.0".to_string(),
+                latest_version: Some("4.0.0".to_string()),
+                status: VersionStatus::Outdated,
+            }
+        );
+    }
+
+    #[test]
+    fn get_versions_ordered_by_semver_sorts_stored_versions() {
+        let storer = std::sync::Arc::new(MockStorer::new(
+            None,
+            vec!["1.10.0", "nightly", "1.2.0", "2.0.0", "1.9.0"],
+        ));
+
+        let versions = storer
+            .get_versions_ordered_by_semver(RegistryType::Npm, "lodash", false)
+            .unwrap();
+
+        assert_eq!(
+            versions,
+            vec!["1.2.0", "1.9.0", "1.10.0", "2.0.0", "nightly"]
+        );
+    }
+
     #[rstest]
     // Major only: v6 matches v6.0.0, v6.1.0, etc.
     #[case("v6", "v6.0.0", vec!["v6.0.0", "v5.0.0"], VersionStatus::Latest)]
@@ -392,7 +886,8 @@ mod tests {
         let storer = MockStorer::new(Some(latest), existing);
         let matcher = GitHubActionsMatcher;
 
-        let result = compare_version(&storer, &matcher, "actions/checkout", current).unwrap();
+        let result =
+            compare_version(&storer, &matcher, "actions/checkout", current, false).unwrap();
 
         assert_eq!(result.status, expected);
     }
@@ -411,7 +906,7 @@ mod tests {
             let matcher = NpmVersionMatcher;
 
             // "latest" should resolve to "4.17.21" which is the latest
-            let result = compare_version(&storer, &matcher, "lodash", "latest").unwrap();
+            let result = compare_version(&storer, &matcher, "lodash", "latest", false).unwrap();
 
             assert_eq!(result.status, VersionStatus::Latest);
             assert_eq!(result.current_version, "latest");
@@ -430,7 +925,7 @@ mod tests {
             let matcher = NpmVersionMatcher;
 
             // "beta" should resolve to "5.0.0-beta.1" which is newer than latest stable
-            let result = compare_version(&storer, &matcher, "lodash", "beta").unwrap();
+            let result = compare_version(&storer, &matcher, "lodash", "beta", false).unwrap();
 
             assert_eq!(result.status, VersionStatus::Newer);
             assert_eq!(result.current_version, "beta");
@@ -447,7 +942,7 @@ mod tests {
 
             // "latest" is a potential dist-tag, but we don't have dist-tag info
             // Return NotInCache to avoid confusing "Invalid version format" error
-            let result = compare_version(&storer, &matcher, "lodash", "latest").unwrap();
+            let result = compare_version(&storer, &matcher, "lodash", "latest", false).unwrap();
 
             assert_eq!(result.status, VersionStatus::NotInCache);
         }
@@ -462,7 +957,7 @@ mod tests {
             let matcher = NpmVersionMatcher;
 
             // "beta" is a potential dist-tag that we can't resolve
-            let result = compare_version(&storer, &matcher, "lodash", "beta").unwrap();
+            let result = compare_version(&storer, &matcher, "lodash", "beta", false).unwrap();
 
             assert_eq!(result.status, VersionStatus::NotInCache);
         }
@@ -477,9 +972,24 @@ mod tests {
             let matcher = NpmVersionMatcher;
 
             // "invalid@#$" is not a valid semver and not a potential dist-tag
-            let result = compare_version(&storer, &matcher, "lodash", "invalid@#$").unwrap();
+            let result = compare_version(&storer, &matcher, "lodash", "invalid@#$", false).unwrap();
 
             assert_eq!(result.status, VersionStatus::Invalid);
         }
+
+        #[test]
+        fn compare_version_returns_not_in_cache_for_unresolved_custom_dist_tag() {
+            let storer = MockStorer::with_dist_tags(
+                Some("5.90.0"),
+                vec!["5.89.0", "5.90.0"],
+                std::collections::HashMap::new(),
+            );
+            let matcher = NpmVersionMatcher;
+
+            // Any bare word is a dist-tag, not only well-known ones like "beta"
+            let result = compare_version(&storer, &matcher, "webpack", "webpack-6", false).unwrap();
+
+            assert_eq!(result.status, VersionStatus::NotInCache);
+        }
     }
 }
@@ -0,0 +1,273 @@
+//! Per-registry circuit breaker
+//!
+//! When a registry is down, every document change would otherwise trigger a
+//! new round of failing fetches. After `failure_threshold` consecutive
+//! failures the circuit for that registry opens and fetches are skipped for
+//! the cooldown period. After the cooldown a single probe request is let
+//! through: success closes the circuit, failure opens it again.
+//!
+//! Each allowed request holds a [`RequestPermit`]. A permit dropped without
+//! an outcome (e.g. the fetch was cancelled by a timeout) counts as a
+//! failure, so a cancelled probe cannot leave the circuit half-open forever.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use crate::config::{DEFAULT_CIRCUIT_BREAKER_COOLDOWN_MS, DEFAULT_CIRCUIT_BREAKER_THRESHOLD};
+use crate::parser::types::RegistryType;
+
+/// State of the circuit for a single registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are allowed; counts failures since the last success
+    Closed { consecutive_failures: u32 },
+    /// Requests are blocked until the cooldown since `opened_at` has elapsed
+    Open { opened_at: Instant },
+    /// A single probe request is in flight; other requests are blocked
+    HalfOpen,
+}
+
+impl Default for CircuitState {
+    fn default() -> Self {
+        Self::Closed {
+            consecutive_failures: 0,
+        }
+    }
+}
+
+/// Tracks registry health and blocks requests to failing registries
+///
+/// Clones share the same state.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    states: Arc<Mutex<HashMap<RegistryType, CircuitState>>>,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            states: Arc::new(Mutex::new(HashMap::new())),
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+        }
+    }
+
+    /// Current state of the circuit for `registry_type`
+    pub fn state(&self, registry_type: RegistryType) -> CircuitState {
+        let states = self.states.lock().expect("circuit breaker lock poisoned");
+        states.get(&registry_type).copied().unwrap_or_default()
+    }
+
+    /// Permit to send a request to `registry_type` now, if the circuit allows it
+    ///
+    /// Once the cooldown has elapsed, the first caller is granted the probe
+    /// request. The outcome is reported through the returned permit.
+    pub fn allow_request(&self, registry_type: RegistryType) -> Option<RequestPermit> {
+        let mut states = self.states.lock().expect("circuit breaker lock poisoned");
+        let state = states.entry(registry_type).or_default();
+
+        let allowed = match *state {
+            CircuitState::Closed { .. } => true,
+            CircuitState::Open { opened_at } if opened_at.elapsed() >= self.cooldown => {
+                info!(
+                    "Circuit for {} is half-open, sending probe request",
+                    registry_type.as_str()
+                );
+                *state = CircuitState::HalfOpen;
+                true
+            }
+            CircuitState::Open { .. } | CircuitState::HalfOpen => false,
+        };
+
+        allowed.then(|| RequestPermit {
+            breaker: self.clone(),
+            registry_type,
+            resolved: false,
+        })
+    }
+
+    /// Record a successful response from `registry_type`, closing the circuit
+    fn record_success(&self, registry_type: RegistryType) {
+        let mut states = self.states.lock().expect("circuit breaker lock poisoned");
+        let state = states.entry(registry_type).or_default();
+
+        if *state == CircuitState::HalfOpen {
+            info!("Circuit for {} closed", registry_type.as_str());
+        }
+        *state = CircuitState::default();
+    }
+
+    /// Record a failed request to `registry_type`
+    ///
+    /// Opens the circuit once the failure threshold is reached, or
+    /// immediately when the probe request of a half-open circuit fails.
+    fn record_failure(&self, registry_type: RegistryType) {
+        let mut states = self.states.lock().expect("circuit breaker lock poisoned");
+        let state = states.entry(registry_type).or_default();
+
+        let should_open = match *state {
+            CircuitState::Closed {
+                consecutive_failures,
+            } => {
+                let consecutive_failures = consecutive_failures + 1;
+                *state = CircuitState::Closed {
+                    consecutive_failures,
+                };
+                consecutive_failures >= self.failure_threshold
+            }
+            CircuitState::HalfOpen => true,
+            CircuitState::Open { .. } => false,
+        };
+
+        if should_open {
+            warn!(
+                "Circuit for {} opened, skipping requests for {:?}",
+                registry_type.as_str(),
+                self.cooldown
+            );
+            *state = CircuitState::Open {
+                opened_at: Instant::now(),
+            };
+        }
+    }
+}
+
+/// An allowed request to a registry, reporting its outcome to the breaker
+///
+/// Dropping the permit without calling [`Self::record_success`] or
+/// [`Self::record_failure`] records a failure.
+#[derive(Debug)]
+#[must_use = "dropping the permit records a failure"]
+pub struct RequestPermit {
+    breaker: CircuitBreaker,
+    registry_type: RegistryType,
+    resolved: bool,
+}
+
+impl RequestPermit {
+    /// The registry answered
+    pub fn record_success(mut self) {
+        self.resolved = true;
+        self.breaker.record_success(self.registry_type);
+    }
+
+    /// The request to the registry failed
+    pub fn record_failure(mut self) {
+        self.resolved = true;
+        self.breaker.record_failure(self.registry_type);
+    }
+}
+
+impl Drop for RequestPermit {
+    fn drop(&mut self) {
+        if !self.resolved {
+            self.breaker.record_failure(self.registry_type);
+        }
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_CIRCUIT_BREAKER_THRESHOLD,
+            Duration::from_millis(DEFAULT_CIRCUIT_BREAKER_COOLDOWN_MS),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_after_consecutive_failures_and_blocks_only_that_registry() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+
+        for _ in 0..2 {
+            breaker.record_failure(RegistryType::Npm);
+        }
+        assert_eq!(
+            breaker.state(RegistryType::Npm),
+            CircuitState::Closed {
+                consecutive_failures: 2
+            }
+        );
+        breaker
+            .allow_request(RegistryType::Npm)
+            .unwrap()
+            .record_failure();
+
+        assert!(matches!(
+            breaker.state(RegistryType::Npm),
+            CircuitState::Open { .. }
+        ));
+        assert!(breaker.allow_request(RegistryType::Npm).is_none());
+        assert!(breaker.allow_request(RegistryType::CratesIo).is_some());
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+
+        breaker.record_failure(RegistryType::Npm);
+        breaker
+            .allow_request(RegistryType::Npm)
+            .unwrap()
+            .record_success();
+        breaker.record_failure(RegistryType::Npm);
+
+        assert!(breaker.allow_request(RegistryType::Npm).is_some());
+    }
+
+    #[test]
+    fn half_open_allows_single_probe_and_closes_on_success() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        breaker.record_failure(RegistryType::Npm);
+
+        let probe = breaker.allow_request(RegistryType::Npm).unwrap();
+        assert_eq!(breaker.state(RegistryType::Npm), CircuitState::HalfOpen);
+        assert!(breaker.allow_request(RegistryType::Npm).is_none());
+
+        probe.record_success();
+
+        assert_eq!(breaker.state(RegistryType::Npm), CircuitState::default());
+        assert!(breaker.allow_request(RegistryType::Npm).is_some());
+    }
+
+    #[test]
+    fn half_open_reopens_when_probe_fails() {
+        let breaker = CircuitBreaker::new(5, Duration::ZERO);
+        for _ in 0..5 {
+            breaker.record_failure(RegistryType::Npm);
+        }
+        let probe = breaker.allow_request(RegistryType::Npm).unwrap();
+
+        probe.record_failure();
+
+        assert!(matches!(
+            breaker.state(RegistryType::Npm),
+            CircuitState::Open { .. }
+        ));
+    }
+
+    #[test]
+    fn dropping_probe_without_outcome_reopens_circuit() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        breaker.record_failure(RegistryType::Npm);
+
+        let probe = breaker.allow_request(RegistryType::Npm).unwrap();
+        drop(probe);
+
+        assert!(matches!(
+            breaker.state(RegistryType::Npm),
+            CircuitState::Open { .. }
+        ));
+        // The next probe is granted once the cooldown has elapsed
+        assert!(breaker.allow_request(RegistryType::Npm).is_some());
+    }
+}
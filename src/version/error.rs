@@ -23,3 +23,14 @@ pub enum RegistryError {
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
 }
+
+impl RegistryError {
+    /// Rate limit error for a 429 response, honoring its `Retry-After` header
+    pub(crate) fn rate_limited(headers: &reqwest::header::HeaderMap) -> Self {
+        let retry_after_secs = headers
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok());
+        Self::RateLimited { retry_after_secs }
+    }
+}
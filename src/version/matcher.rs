@@ -3,7 +3,7 @@
 use crate::parser::types::RegistryType;
 use crate::version::semver::{
     CompareResult, calculate_latest_major, calculate_latest_minor, calculate_latest_patch,
-    calculate_next_major, calculate_next_minor,
+    calculate_next_major, calculate_next_minor, extract_version_prefix, find_semantic_max,
 };
 
 /// Bump target versions: latest patch, next/latest minor, next/latest major.
@@ -35,6 +35,26 @@ pub trait VersionMatcher: Send + Sync {
     /// For npm: ^1.0.0 matches 1.0.0, 1.1.0, 1.9.9, but not 2.0.0
     fn version_exists(&self, version_spec: &str, available_versions: &[String]) -> bool;
 
+    /// Find the highest available version that satisfies a version specification
+    ///
+    /// For npm: ^17.0.0 with 17.0.2 and 18.0.0 available gives 17.0.2.
+    /// Returns None if no available version satisfies the specification.
+    ///
+    /// Default: the semver maximum of the versions accepted one by one by
+    /// [`Self::version_exists`].
+    fn version_in_range(
+        &self,
+        version_spec: &str,
+        available_versions: &[String],
+    ) -> Option<String> {
+        find_semantic_max(
+            available_versions
+                .iter()
+                .filter(|v| self.version_exists(version_spec, std::slice::from_ref(*v))),
+        )
+        .cloned()
+    }
+
     /// Compare the current version specification to the latest version
     ///
     /// Returns whether the current version is latest, outdated, newer, or invalid
@@ -53,21 +73,126 @@ pub trait VersionMatcher: Send + Sync {
         latest_version.to_string()
     }
 
+    /// Canonical form of a version specification, for diagnostic messages
+    ///
+    /// Default: returns the specification unchanged. Matchers that pad
+    /// partial versions override this so messages show the range actually
+    /// checked (`^1.0` is shown as `^1.0.0`).
+    fn normalize_spec(&self, version_spec: &str) -> String {
+        version_spec.to_string()
+    }
+
+    /// Whether a version specification names a dist-tag (`next`, `beta`)
+    /// that only the registry's dist-tags resolve to a version
+    ///
+    /// Default: false, for registries whose specifications are all versions
+    /// or ranges.
+    fn is_dist_tag(&self, _version_spec: &str) -> bool {
+        false
+    }
+
     /// Calculate bump targets (patch, next/latest minor, next/latest major) for code actions.
     ///
-    /// Default implementation uses semver-based calculation.
+    /// Default implementation uses semver-based calculation, offering
+    /// pre-releases only when `include_pre_release` is set or the current
+    /// version is a pre-release.
     /// Docker overrides this to handle suffix-aware tag comparison.
     fn calculate_bump_targets(
         &self,
         current_version: &str,
         available_versions: &[String],
+        include_pre_release: bool,
     ) -> BumpTargets {
-        BumpTargets {
-            patch: calculate_latest_patch(current_version, available_versions),
-            next_minor: calculate_next_minor(current_version, available_versions),
-            minor: calculate_latest_minor(current_version, available_versions),
-            next_major: calculate_next_major(current_version, available_versions),
-            major: calculate_latest_major(current_version, available_versions),
-        }
+        semver_bump_targets(current_version, available_versions, include_pre_release)
+    }
+
+    /// Build the replacement for `current_spec` when upgrading to `target_version`.
+    ///
+    /// Default: keeps the prefix of the current specification (`^1.0` + `2.0.0`
+    /// becomes `^2.0.0`, `=1.0.0` + `1.1.0` becomes `=1.1.0`). Targets that
+    /// already carry the prefix, like GitHub Actions tags (`v4.0.0`), are used as is.
+    fn suggest_upgrade(&self, current_spec: &str, target_version: &str) -> String {
+        upgrade_keeping_prefix(current_spec, target_version)
+    }
+
+    /// Specification matching exactly `version`, for pinning a range to it
+    ///
+    /// Default: the bare version, which is an exact match for npm ranges and
+    /// tags (`4.17.21`, `v4.2.1`). Registries where a bare version is itself
+    /// a range override this (Cargo: `=1.2.3`).
+    fn exact_spec(&self, version: &str) -> String {
+        version.to_string()
+    }
+}
+
+/// Semver-based bump targets, the default of
+/// [`VersionMatcher::calculate_bump_targets`]
+pub fn semver_bump_targets(
+    current_version: &str,
+    available_versions: &[String],
+    include_pre_release: bool,
+) -> BumpTargets {
+    BumpTargets {
+        patch: calculate_latest_patch(current_version, available_versions, include_pre_release),
+        next_minor: calculate_next_minor(current_version, available_versions, include_pre_release),
+        minor: calculate_latest_minor(current_version, available_versions, include_pre_release),
+        next_major: calculate_next_major(current_version, available_versions, include_pre_release),
+        major: calculate_latest_major(current_version, available_versions, include_pre_release),
+    }
+}
+
+/// `target_version` with the prefix of `current_spec`, the default of
+/// [`VersionMatcher::suggest_upgrade`]
+pub fn upgrade_keeping_prefix(current_spec: &str, target_version: &str) -> String {
+    let prefix = extract_version_prefix(current_spec);
+    if target_version.starts_with(prefix) {
+        target_version.to_string()
+    } else {
+        format!("{prefix}{target_version}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::matchers::{CratesVersionMatcher, GitHubActionsMatcher, NpmVersionMatcher};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(&NpmVersionMatcher as &dyn VersionMatcher, "^1.0", "2.0.0", "^2.0.0")]
+    #[case(&NpmVersionMatcher as &dyn VersionMatcher, "~4.17.19", "4.17.21", "~4.17.21")]
+    #[case(&NpmVersionMatcher as &dyn VersionMatcher, "4.17.19", "4.18.0", "4.18.0")]
+    #[case(&CratesVersionMatcher as &dyn VersionMatcher, "=1.0.0", "1.1.0", "=1.1.0")]
+    #[case(&CratesVersionMatcher as &dyn VersionMatcher, ">=1.0.0", "2.0.0", ">=2.0.0")]
+    #[case(&GitHubActionsMatcher as &dyn VersionMatcher, "v3.0.0", "v4.0.0", "v4.0.0")]
+    #[case(&GitHubActionsMatcher as &dyn VersionMatcher, "v3", "4.0.0", "v4.0.0")]
+    fn suggest_upgrade_keeps_current_prefix(
+        #[case] matcher: &dyn VersionMatcher,
+        #[case] current_spec: &str,
+        #[case] target_version: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            matcher.suggest_upgrade(current_spec, target_version),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("v4", vec!["v3.9.0", "v4.0.0", "v4.2.1", "v5.0.0"], Some("v4.2.1"))]
+    #[case("v4.1", vec!["v4.0.0", "v4.1.0", "v4.1.3", "v4.2.0"], Some("v4.1.3"))]
+    #[case("v6", vec!["v4.0.0", "v5.0.0"], None)]
+    fn version_in_range_defaults_to_highest_existing_version(
+        #[case] version_spec: &str,
+        #[case] available: Vec<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let available: Vec<String> = available.into_iter().map(String::from).collect();
+        assert_eq!(
+            GitHubActionsMatcher
+                .version_in_range(version_spec, &available)
+                .as_deref(),
+            expected
+        );
     }
 }
@@ -0,0 +1,269 @@
+//! Conda version matcher
+//!
+//! Supports conda match spec version constraints:
+//! - `1.21.0` / `==1.21.0` - exact match
+//! - `=1.21` - fuzzy match (1.21, 1.21.x)
+//! - `1.21.*` - wildcard (same as `=1.21`)
+//! - `>=1.7`, `>1.7`, `<=1.7`, `<1.7`, `!=1.7` - comparison operators
+//! - `>=1.7,<2` - AND, `1.7|1.8` - OR
+//!
+//! Versions are compared component-wise like conda's `VersionOrder`:
+//! numeric components compare numerically, missing components count as 0,
+//! and alphabetic components sort before numbers (`1.0a1` < `1.0`).
+
+use std::cmp::Ordering;
+
+use crate::parser::types::RegistryType;
+use crate::version::matcher::VersionMatcher;
+use crate::version::semver::CompareResult;
+
+pub struct CondaVersionMatcher;
+
+/// A single version constraint
+#[derive(Debug)]
+enum Constraint {
+    Exact(String),
+    /// Matches the version and any version it is a prefix of (`=1.21`, `1.21.*`)
+    Fuzzy(String),
+    Gte(String),
+    Gt(String),
+    Lte(String),
+    Lt(String),
+    Ne(String),
+}
+
+impl Constraint {
+    fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+        let constraint = if let Some(rest) = spec.strip_prefix(">=") {
+            Constraint::Gte(rest.to_string())
+        } else if let Some(rest) = spec.strip_prefix("<=") {
+            Constraint::Lte(rest.to_string())
+        } else if let Some(rest) = spec.strip_prefix("==") {
+            Constraint::Exact(rest.to_string())
+        } else if let Some(rest) = spec.strip_prefix("!=") {
+            Constraint::Ne(rest.to_string())
+        } else if let Some(rest) = spec.strip_prefix('>') {
+            Constraint::Gt(rest.to_string())
+        } else if let Some(rest) = spec.strip_prefix('<') {
+            Constraint::Lt(rest.to_string())
+        } else if let Some(rest) = spec.strip_prefix('=') {
+            Constraint::Fuzzy(
+                rest.trim_end_matches(".*")
+                    .trim_end_matches('*')
+                    .to_string(),
+            )
+        } else if let Some(rest) = spec.strip_suffix('*') {
+            Constraint::Fuzzy(rest.trim_end_matches('.').to_string())
+        } else {
+            Constraint::Exact(spec.to_string())
+        };
+
+        (!constraint.version().is_empty()).then_some(constraint)
+    }
+
+    fn version(&self) -> &str {
+        match self {
+            Constraint::Exact(v)
+            | Constraint::Fuzzy(v)
+            | Constraint::Gte(v)
+            | Constraint::Gt(v)
+            | Constraint::Lte(v)
+            | Constraint::Lt(v)
+            | Constraint::Ne(v) => v,
+        }
+    }
+
+    fn satisfies(&self, version: &str) -> bool {
+        let ordering = compare_versions(version, self.version());
+        match self {
+            Constraint::Exact(_) => ordering == Ordering::Equal,
+            Constraint::Fuzzy(prefix) => is_fuzzy_match(version, prefix),
+            Constraint::Gte(_) => ordering != Ordering::Less,
+            Constraint::Gt(_) => ordering == Ordering::Greater,
+            Constraint::Lte(_) => ordering != Ordering::Greater,
+            Constraint::Lt(_) => ordering == Ordering::Less,
+            Constraint::Ne(_) => ordering != Ordering::Equal,
+        }
+    }
+}
+
+/// A version spec: OR (`|`) of AND (`,`) groups of constraints
+#[derive(Debug)]
+struct VersionSpec(Vec<Vec<Constraint>>);
+
+impl VersionSpec {
+    fn parse(spec: &str) -> Option<Self> {
+        spec.split('|')
+            .map(|group| group.split(',').map(Constraint::parse).collect())
+            .collect::<Option<Vec<_>>>()
+            .map(VersionSpec)
+    }
+
+    fn satisfies(&self, version: &str) -> bool {
+        self.0
+            .iter()
+            .any(|group| group.iter().all(|c| c.satisfies(version)))
+    }
+
+    /// Version of the first constraint, used to decide outdated vs newer
+    fn base_version(&self) -> Option<&str> {
+        self.0.first()?.first().map(Constraint::version)
+    }
+}
+
+/// A version component: numbers sort after strings, like conda
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Component {
+    Str(String),
+    Num(u64),
+}
+
+/// Split a version into comparable components (`1.0a1` -> [1, 0, "a", 1])
+fn components(version: &str) -> Vec<Component> {
+    let version = version.trim().to_lowercase();
+    let mut result = Vec::new();
+
+    for segment in version.split(['.', '_', '-', '+']) {
+        let mut rest = segment;
+        while let Some(first) = rest.chars().next() {
+            let is_digit = first.is_ascii_digit();
+            let len = rest
+                .find(|c: char| c.is_ascii_digit() != is_digit)
+                .unwrap_or(rest.len());
+            let (run, tail) = rest.split_at(len);
+            result.push(match run.parse() {
+                Ok(n) if is_digit => Component::Num(n),
+                _ => Component::Str(run.to_string()),
+            });
+            rest = tail;
+        }
+    }
+
+    result
+}
+
+/// Compare two conda versions, padding missing components with 0
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    compare_components(&components(a), &components(b))
+}
+
+fn compare_components(a: &[Component], b: &[Component]) -> Ordering {
+    let zero = Component::Num(0);
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&zero).cmp(b.get(i).unwrap_or(&zero)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Check whether `version` equals `prefix` or extends it (`1.21.3` matches `1.21`)
+fn is_fuzzy_match(version: &str, prefix: &str) -> bool {
+    let (version, prefix) = (components(version), components(prefix));
+    version.starts_with(&prefix) || compare_components(&version, &prefix) == Ordering::Equal
+}
+
+impl VersionMatcher for CondaVersionMatcher {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::Conda
+    }
+
+    fn version_exists(&self, version_spec: &str, available_versions: &[String]) -> bool {
+        let Some(spec) = VersionSpec::parse(version_spec) else {
+            return false;
+        };
+
+        available_versions.iter().any(|v| spec.satisfies(v))
+    }
+
+    fn compare_to_latest(&self, current_version: &str, latest_version: &str) -> CompareResult {
+        let Some(spec) = VersionSpec::parse(current_version) else {
+            return CompareResult::Invalid;
+        };
+
+        if latest_version.trim().is_empty() {
+            return CompareResult::Invalid;
+        }
+
+        if spec.satisfies(latest_version) {
+            return CompareResult::Latest;
+        }
+
+        let Some(base) = spec.base_version() else {
+            return CompareResult::Latest;
+        };
+
+        match compare_versions(base, latest_version) {
+            Ordering::Less => CompareResult::Outdated,
+            _ => CompareResult::Newer,
+        }
+    }
+
+    /// Specs follow the package name directly (`numpy>=1.21`), so pins
+    /// need `==`
+    fn exact_spec(&self, version: &str) -> String {
+        format!("=={version}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1.21.0", vec!["1.21.0", "1.22.0"], true)]
+    #[case("==1.21.0", vec!["1.21.1"], false)]
+    #[case("=1.21", vec!["1.21.5"], true)]
+    #[case("=1.21", vec!["1.2.1", "1.22.0"], false)]
+    #[case("1.21.*", vec!["1.21.5"], true)]
+    #[case(">=1.7", vec!["1.6.0", "1.7.0"], true)]
+    #[case(">=1.7,<2", vec!["2.0.0"], false)]
+    #[case("1.7|1.8", vec!["1.8"], true)]
+    #[case("!=1.7", vec!["1.7.0"], false)]
+    #[case("3.11", vec!["3.11.0"], true)]
+    #[case("", vec!["1.0.0"], false)]
+    fn version_exists_returns_expected(
+        #[case] version_spec: &str,
+        #[case] available: Vec<&str>,
+        #[case] expected: bool,
+    ) {
+        let available: Vec<String> = available.into_iter().map(String::from).collect();
+        assert_eq!(
+            CondaVersionMatcher.version_exists(version_spec, &available),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("=1.21.0", "1.21.0", CompareResult::Latest)]
+    #[case("=1.21", "1.21.6", CompareResult::Latest)]
+    #[case("=1.21.0", "2.1.0", CompareResult::Outdated)]
+    #[case(">=1.7", "1.11.4", CompareResult::Latest)]
+    #[case("<1.7", "1.11.4", CompareResult::Outdated)]
+    #[case("==3.0.0", "2.1.0", CompareResult::Newer)]
+    #[case("1.0a1", "1.0", CompareResult::Outdated)]
+    #[case("=1.21.0", "", CompareResult::Invalid)]
+    fn compare_to_latest_returns_expected(
+        #[case] current: &str,
+        #[case] latest: &str,
+        #[case] expected: CompareResult,
+    ) {
+        assert_eq!(
+            CondaVersionMatcher.compare_to_latest(current, latest),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("1.10.0", "1.9.0", Ordering::Greater)]
+    #[case("1.21", "1.21.0", Ordering::Equal)]
+    #[case("1.0a1", "1.0", Ordering::Less)]
+    #[case("2024.1", "2023.12", Ordering::Greater)]
+    fn compare_versions_returns_expected(
+        #[case] a: &str,
+        #[case] b: &str,
+        #[case] expected: Ordering,
+    ) {
+        assert_eq!(compare_versions(a, b), expected);
+    }
+}
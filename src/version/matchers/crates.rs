@@ -6,12 +6,17 @@
 //! - `~1.2.3` - tilde: >=1.2.3 <1.3.0
 //! - `>=1.2.3`, `>1.2.3`, `<=1.2.3`, `<1.2.3`, `=1.2.3` - comparison operators
 //! - `1.2.*`, `1.*`, `*` - wildcards
+//! - `>=1.2, <2.0` - comma-separated requirements, all of which must match
 
 use semver::Version;
 
 use crate::parser::types::RegistryType;
-use crate::version::matcher::VersionMatcher;
-use crate::version::semver::{CompareResult, parse_version};
+use crate::version::matcher::{
+    BumpTargets, VersionMatcher, semver_bump_targets, upgrade_keeping_prefix,
+};
+use crate::version::semver::{
+    BarePartial, CompareResult, extract_version_prefix, normalize_partial_spec, parse_version,
+};
 
 pub struct CratesVersionMatcher;
 
@@ -201,6 +206,21 @@ impl VersionMatcher for CratesVersionMatcher {
         })
     }
 
+    fn version_in_range(
+        &self,
+        version_spec: &str,
+        available_versions: &[String],
+    ) -> Option<String> {
+        let spec = VersionSpec::parse(version_spec)?;
+
+        available_versions
+            .iter()
+            .filter_map(|v| Some((v, Version::parse(v).ok()?)))
+            .filter(|(_, ver)| spec.satisfies(ver))
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(v, _)| v.clone())
+    }
+
     fn compare_to_latest(&self, current_version: &str, latest_version: &str) -> CompareResult {
         let Some(spec) = VersionSpec::parse(current_version) else {
             return CompareResult::Invalid;
@@ -226,6 +246,93 @@ impl VersionMatcher for CratesVersionMatcher {
             CompareResult::Newer
         }
     }
+
+    /// A bare partial version is a caret requirement (`1` is shown as `^1.0.0`)
+    fn normalize_spec(&self, version_spec: &str) -> String {
+        normalize_partial_spec(version_spec, BarePartial::Caret)
+    }
+
+    /// A bare version is a caret requirement, so pins need `=`
+    fn exact_spec(&self, version: &str) -> String {
+        format!("={version}")
+    }
+
+    /// Comma-separated requirements are bumped from their lower bound
+    fn calculate_bump_targets(
+        &self,
+        current_version: &str,
+        available_versions: &[String],
+        include_pre_release: bool,
+    ) -> BumpTargets {
+        semver_bump_targets(
+            lower_bound(current_version),
+            available_versions,
+            include_pre_release,
+        )
+    }
+
+    /// Comma-separated requirements are rewritten as a whole: the lower
+    /// bound moves to the target and upper bounds that exclude the target
+    /// are widened (`>=1.0, <2.0` + `2.1.0` becomes `>=2.1.0, <3.0`)
+    fn suggest_upgrade(&self, current_spec: &str, target_version: &str) -> String {
+        if !current_spec.contains(',') {
+            return upgrade_keeping_prefix(current_spec, target_version);
+        }
+        let Ok(target) = Version::parse(target_version) else {
+            return current_spec.to_string();
+        };
+
+        current_spec
+            .split(',')
+            .map(str::trim)
+            .map(|requirement| {
+                let prefix = extract_version_prefix(requirement);
+                let bound = requirement[prefix.len()..].trim();
+                let excludes_target = parse_version(bound).map(|bound| match prefix {
+                    "<" => bound <= target,
+                    "<=" => bound < target,
+                    _ => false,
+                });
+                match (prefix, excludes_target) {
+                    ("<", Some(true)) => format!("<{}", next_breaking_version(&target, bound)),
+                    ("<=", Some(true)) => format!("<={target_version}"),
+                    ("<" | "<=", _) => requirement.to_string(),
+                    _ => upgrade_keeping_prefix(requirement, target_version),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Lower bound of a comma-separated requirement (`>=1.2` of `>=1.2, <2.0`),
+/// or the whole spec when it is a single requirement
+fn lower_bound(spec: &str) -> &str {
+    if !spec.contains(',') {
+        return spec;
+    }
+    spec.split(',')
+        .map(str::trim)
+        .find(|requirement| !requirement.starts_with('<'))
+        .unwrap_or(spec)
+}
+
+/// First version that is a breaking change from `version` (next major, or
+/// next minor/patch for 0.x), written with as many components as `like`
+fn next_breaking_version(version: &Version, like: &str) -> String {
+    let (parts, needed) = if version.major > 0 {
+        ([version.major + 1, 0, 0], 1)
+    } else if version.minor > 0 {
+        ([0, version.minor + 1, 0], 2)
+    } else {
+        ([0, 0, version.patch + 1], 3)
+    };
+    let components = like.split('.').count().clamp(needed, 3);
+    parts[..components]
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(".")
 }
 
 #[cfg(test)]
@@ -234,6 +341,27 @@ mod tests {
     use rstest::rstest;
 
     // version_exists tests - default (caret-like) requirements
+    #[rstest]
+    #[case("1.2.3", vec!["1.2.3", "1.10.0", "1.9.0", "2.0.0"], Some("1.10.0"))]
+    #[case("0.2.3", vec!["0.2.3", "0.2.9", "0.3.0"], Some("0.2.9"))]
+    #[case("=1.0.0", vec!["1.0.0", "1.0.1"], Some("1.0.0"))]
+    #[case(">=1.0, <1.5", vec!["1.4.9", "1.5.0"], Some("1.4.9"))]
+    #[case("3", vec!["1.0.0", "2.0.0"], None)]
+    #[case("not-a-version", vec!["1.0.0"], None)]
+    fn version_in_range_returns_highest_satisfying_version(
+        #[case] version_spec: &str,
+        #[case] available: Vec<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let available: Vec<String> = available.into_iter().map(String::from).collect();
+        assert_eq!(
+            CratesVersionMatcher
+                .version_in_range(version_spec, &available)
+                .as_deref(),
+            expected
+        );
+    }
+
     #[rstest]
     // 1.2.3 means >=1.2.3, <2.0.0
     #[case("1.2.3", vec!["1.2.3", "1.3.0", "2.0.0"], true)]
@@ -383,6 +511,46 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case(">=1.0, <2.0", "2.1.0", ">=2.1.0, <3.0")]
+    #[case(">=1.0.0, <2.0.0", "1.5.0", ">=1.5.0, <2.0.0")]
+    #[case(">=1.0, <=2.0", "2.1.0", ">=2.1.0, <=2.1.0")]
+    #[case(">= 0.5, < 0.6", "0.7.2", ">=0.7.2, <0.8")]
+    #[case(">=0.0.1, <0.0.2", "0.0.3", ">=0.0.3, <0.0.4")]
+    #[case("^1.2", "2.0.0", "^2.0.0")]
+    #[case("1.2.3", "2.0.0", "2.0.0")]
+    fn suggest_upgrade_rewrites_whole_multi_constraint_spec(
+        #[case] current_spec: &str,
+        #[case] target_version: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            CratesVersionMatcher.suggest_upgrade(current_spec, target_version),
+            expected
+        );
+    }
+
+    #[test]
+    fn calculate_bump_targets_uses_lower_bound_of_multi_constraint_spec() {
+        let available: Vec<String> = vec!["1.0.0", "1.0.5", "1.4.0", "2.0.0", "2.3.0"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let targets = CratesVersionMatcher.calculate_bump_targets(">=1.0, <2.0", &available, false);
+
+        assert_eq!(
+            targets,
+            BumpTargets {
+                patch: Some("1.0.5".to_string()),
+                next_minor: Some("1.4.0".to_string()),
+                minor: Some("1.4.0".to_string()),
+                next_major: Some("2.3.0".to_string()),
+                major: Some("2.3.0".to_string()),
+            }
+        );
+    }
+
     // compare_to_latest tests
     #[rstest]
     // Partial version comparison
@@ -416,4 +584,52 @@ mod tests {
             expected
         );
     }
+
+    // Property-based tests: version_exists and compare_to_latest must share
+    // the same range interpretation, so they are compared on random range
+    // specs and versions.
+    mod properties {
+        use super::*;
+        use crate::version::matchers::properties::*;
+        use proptest::prelude::*;
+
+        /// Every kind of Cargo version requirement (single, wildcard, comma-separated)
+        fn spec_strategy() -> impl Strategy<Value = String> {
+            prop_oneof![
+                single_spec_strategy(),
+                prop::string::string_regex(
+                    "\\*|(0|[1-9][0-9]?)\\.\\*|(0|[1-9][0-9]?)\\.(0|[1-9][0-9]?)\\.\\*"
+                )
+                .unwrap(),
+                (single_spec_strategy(), single_spec_strategy())
+                    .prop_map(|(a, b)| format!("{a}, {b}")),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn compare_latest_implies_version_exists(
+                spec in spec_strategy(),
+                latest in version_strategy(),
+            ) {
+                check_latest_implies_version_exists(&CratesVersionMatcher, &spec, &latest)?;
+            }
+
+            #[test]
+            fn version_not_satisfied_implies_outdated_or_newer(
+                spec in spec_strategy(),
+                latest in version_strategy(),
+            ) {
+                check_unsatisfied_implies_outdated_or_newer(&CratesVersionMatcher, &spec, &latest)?;
+            }
+
+            #[test]
+            fn v_prefix_does_not_change_result(
+                version in version_strategy(),
+                latest in version_strategy(),
+            ) {
+                check_v_prefix_does_not_change_result(&CratesVersionMatcher, &version, &latest)?;
+            }
+        }
+    }
 }
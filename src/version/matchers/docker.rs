@@ -58,10 +58,13 @@ impl VersionMatcher for DockerVersionMatcher {
         }
     }
 
+    /// Tag suffixes (`-alpine`) are variants rather than pre-releases, so
+    /// `include_pre_release` does not apply
     fn calculate_bump_targets(
         &self,
         current_version: &str,
         available_versions: &[String],
+        _include_pre_release: bool,
     ) -> BumpTargets {
         let Some(current_parsed) = parse_docker_tag(current_version) else {
             return BumpTargets::default();
@@ -408,7 +411,7 @@ mod tests {
             "2.0-alpine".to_string(),
             "3.0-alpine".to_string(),
         ];
-        let targets = matcher.calculate_bump_targets("1.25-alpine", &versions);
+        let targets = matcher.calculate_bump_targets("1.25-alpine", &versions, false);
         assert_eq!(
             targets,
             BumpTargets {
@@ -431,7 +434,7 @@ mod tests {
             "2.0".to_string(),
             "3.0".to_string(),
         ];
-        let targets = matcher.calculate_bump_targets("1.25", &versions);
+        let targets = matcher.calculate_bump_targets("1.25", &versions, false);
         assert_eq!(
             targets,
             BumpTargets {
@@ -448,7 +451,7 @@ mod tests {
     fn calculate_bump_targets_returns_default_when_invalid_tag() {
         let matcher = DockerVersionMatcher;
         let versions = vec!["1.25".to_string(), "1.27".to_string()];
-        let targets = matcher.calculate_bump_targets("latest", &versions);
+        let targets = matcher.calculate_bump_targets("latest", &versions, false);
         assert_eq!(targets, BumpTargets::default());
     }
 }
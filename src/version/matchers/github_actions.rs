@@ -101,7 +101,7 @@ fn count_version_parts(version: &str) -> usize {
 /// - `v6` matches any version with major version 6 (e.g., `v6.0.0`, `v6.1.0`)
 /// - `v6.1` matches any version with major.minor 6.1 (e.g., `v6.1.0`, `v6.1.5`)
 /// - `v6.1.0` requires exact match
-fn version_matches_any(current: &str, available_versions: &[String]) -> bool {
+pub(crate) fn version_matches_any(current: &str, available_versions: &[String]) -> bool {
     let Some(current_normalized) = normalize_version(current) else {
         return false;
     };
@@ -137,7 +137,11 @@ fn version_matches_any(current: &str, available_versions: &[String]) -> bool {
     false
 }
 
-fn compare_versions(current: &str, latest: &str) -> CompareResult {
+/// Compare the current version against the latest version.
+///
+/// Floating tags are compared only as deep as they are specified, so `v4`
+/// is `Latest` for any `v4.x.y` release and `v4.1` for any `v4.1.y` release.
+pub(crate) fn compare_versions(current: &str, latest: &str) -> CompareResult {
     let Some(current_normalized) = normalize_version(current) else {
         warn!("Invalid current version format: '{}'", current);
         return CompareResult::Invalid;
@@ -240,6 +244,8 @@ mod tests {
     #[case("v4", "v4.0.0", CompareResult::Latest)]
     #[case("v4", "v4.1.0", CompareResult::Latest)]
     #[case("v4", "v4.5.3", CompareResult::Latest)]
+    #[case("v4", "v4.2.0", CompareResult::Latest)]
+    #[case("v3", "v4.2.0", CompareResult::Outdated)]
     #[case("v4", "v5.0.0", CompareResult::Outdated)]
     #[case("v5", "v4.0.0", CompareResult::Newer)]
     #[case("5", "v5.2.1", CompareResult::Latest)]
@@ -248,6 +254,7 @@ mod tests {
     #[case("v4.1", "v4.1.5", CompareResult::Latest)]
     #[case("v4.1", "v4.2.0", CompareResult::Outdated)]
     #[case("v4.2", "v4.1.0", CompareResult::Newer)]
+    #[case("v3.9", "v4.2.0", CompareResult::Outdated)]
     // Full version - exact comparison
     #[case("v4.1.0", "v4.1.0", CompareResult::Latest)]
     #[case("v4.1.0", "v4.1.5", CompareResult::Outdated)]
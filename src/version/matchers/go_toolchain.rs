@@ -0,0 +1,85 @@
+//! Go toolchain version matcher for the go.mod `go` directive
+//!
+//! The directive is the minimum Go version required to build the module and
+//! is compared as that exact version, with missing parts as zero:
+//! - 1.21 is 1.21.0, outdated once 1.21.1 is released
+//! - 1.20 must be a release (Go 1.20 was released as `go1.20`)
+
+use crate::parser::types::RegistryType;
+use crate::version::matcher::VersionMatcher;
+use crate::version::semver::{CompareResult, parse_version};
+
+pub struct GoToolchainMatcher;
+
+impl VersionMatcher for GoToolchainMatcher {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::GoToolchain
+    }
+
+    fn version_exists(&self, version_spec: &str, available_versions: &[String]) -> bool {
+        let Some(version) = parse_version(version_spec) else {
+            return false;
+        };
+
+        available_versions
+            .iter()
+            .any(|v| parse_version(v).is_some_and(|v| v == version))
+    }
+
+    fn compare_to_latest(&self, current_version: &str, latest_version: &str) -> CompareResult {
+        let (Some(current), Some(latest)) = (
+            parse_version(current_version),
+            parse_version(latest_version),
+        ) else {
+            return CompareResult::Invalid;
+        };
+
+        match current.cmp(&latest) {
+            std::cmp::Ordering::Less => CompareResult::Outdated,
+            std::cmp::Ordering::Equal => CompareResult::Latest,
+            std::cmp::Ordering::Greater => CompareResult::Newer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1.21", vec!["1.20", "1.21.0", "1.21.1"], true)]
+    #[case("1.21.1", vec!["1.21.0", "1.21.1"], true)]
+    #[case("1.20", vec!["1.20", "1.20.1"], true)]
+    #[case("1.21.5", vec!["1.21.0", "1.21.1"], false)]
+    #[case("1.19", vec!["1.20", "1.21.0"], false)]
+    fn version_exists_returns_expected(
+        #[case] version_spec: &str,
+        #[case] available: Vec<&str>,
+        #[case] expected: bool,
+    ) {
+        let available: Vec<String> = available.into_iter().map(String::from).collect();
+        assert_eq!(
+            GoToolchainMatcher.version_exists(version_spec, &available),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("1.22.1", "1.22.1", CompareResult::Latest)]
+    #[case("1.22", "1.22.0", CompareResult::Latest)]
+    #[case("1.22", "1.22.1", CompareResult::Outdated)]
+    #[case("1.21.0", "1.22.1", CompareResult::Outdated)]
+    #[case("1.23", "1.22.1", CompareResult::Newer)]
+    #[case("latest", "1.22.1", CompareResult::Invalid)]
+    fn compare_to_latest_returns_expected(
+        #[case] current_version: &str,
+        #[case] latest_version: &str,
+        #[case] expected: CompareResult,
+    ) {
+        assert_eq!(
+            GoToolchainMatcher.compare_to_latest(current_version, latest_version),
+            expected
+        );
+    }
+}
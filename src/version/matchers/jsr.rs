@@ -5,8 +5,8 @@
 
 use crate::parser::types::RegistryType;
 use crate::version::matcher::VersionMatcher;
-use crate::version::matchers::npm::{npm_compare_to_latest, npm_version_exists};
-use crate::version::semver::CompareResult;
+use crate::version::matchers::npm::{npm_compare_to_latest, npm_is_dist_tag, npm_version_exists};
+use crate::version::semver::{BarePartial, CompareResult, normalize_partial_spec};
 
 pub struct JsrVersionMatcher;
 
@@ -22,4 +22,12 @@ impl VersionMatcher for JsrVersionMatcher {
     fn compare_to_latest(&self, current_version: &str, latest_version: &str) -> CompareResult {
         npm_compare_to_latest(current_version, latest_version)
     }
+
+    fn normalize_spec(&self, version_spec: &str) -> String {
+        normalize_partial_spec(version_spec, BarePartial::XRange)
+    }
+
+    fn is_dist_tag(&self, version_spec: &str) -> bool {
+        npm_is_dist_tag(version_spec)
+    }
 }
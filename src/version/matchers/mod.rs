@@ -1,19 +1,33 @@
 //! Registry-specific version matchers
 
+pub mod conda;
 pub mod crates;
 pub mod docker;
 pub mod github_actions;
 pub mod go;
+pub mod go_toolchain;
 pub mod jsr;
+pub mod node;
 pub mod npm;
 pub mod pnpm;
+#[cfg(test)]
+mod properties;
+pub mod pub_dev;
 pub mod pypi;
+pub mod rubygems;
+pub mod rust_toolchain;
 
+pub use conda::CondaVersionMatcher;
 pub use crates::CratesVersionMatcher;
 pub use docker::DockerVersionMatcher;
 pub use github_actions::GitHubActionsMatcher;
 pub use go::GoVersionMatcher;
+pub use go_toolchain::GoToolchainMatcher;
 pub use jsr::JsrVersionMatcher;
+pub use node::NodeVersionMatcher;
 pub use npm::NpmVersionMatcher;
 pub use pnpm::PnpmCatalogMatcher;
+pub use pub_dev::PubVersionMatcher;
 pub use pypi::PypiVersionMatcher;
+pub use rubygems::RubyGemsVersionMatcher;
+pub use rust_toolchain::RustToolchainMatcher;
@@ -0,0 +1,66 @@
+//! Node.js version matcher for .nvmrc / .node-version
+//!
+//! Uses the same partial matching as GitHub Actions, like nvm does:
+//! - 20 matches any 20.x.y release
+//! - 20.11 matches any 20.11.y release
+//! - 20.11.0 requires exact match
+
+use crate::parser::types::RegistryType;
+use crate::version::matcher::VersionMatcher;
+use crate::version::matchers::github_actions::{compare_versions, version_matches_any};
+use crate::version::semver::CompareResult;
+
+pub struct NodeVersionMatcher;
+
+impl VersionMatcher for NodeVersionMatcher {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::NodeRelease
+    }
+
+    fn version_exists(&self, version_spec: &str, available_versions: &[String]) -> bool {
+        version_matches_any(version_spec, available_versions)
+    }
+
+    fn compare_to_latest(&self, current_version: &str, latest_version: &str) -> CompareResult {
+        compare_versions(current_version, latest_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("20", vec!["18.20.4", "20.11.0"], true)]
+    #[case("v20.11", vec!["20.11.1"], true)]
+    #[case("20.11.0", vec!["20.11.1"], false)]
+    #[case("19", vec!["18.20.4", "20.11.0"], false)]
+    fn version_exists_returns_expected(
+        #[case] version_spec: &str,
+        #[case] available: Vec<&str>,
+        #[case] expected: bool,
+    ) {
+        let available: Vec<String> = available.into_iter().map(String::from).collect();
+        assert_eq!(
+            NodeVersionMatcher.version_exists(version_spec, &available),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("20", "20.11.1", CompareResult::Latest)]
+    #[case("v18.17.0", "20.11.1", CompareResult::Outdated)]
+    #[case("22", "20.11.1", CompareResult::Newer)]
+    #[case("lts/iron", "20.11.1", CompareResult::Invalid)]
+    fn compare_to_latest_returns_expected(
+        #[case] current_version: &str,
+        #[case] latest_version: &str,
+        #[case] expected: CompareResult,
+    ) {
+        assert_eq!(
+            NodeVersionMatcher.compare_to_latest(current_version, latest_version),
+            expected
+        );
+    }
+}
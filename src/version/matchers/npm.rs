@@ -5,16 +5,22 @@
 //! - `^1.2.3` - compatible with version (>=1.2.3 <2.0.0)
 //! - `~1.2.3` - approximately equivalent (>=1.2.3 <1.3.0)
 //! - `>=1.2.3`, `>1.2.3`, `<=1.2.3`, `<1.2.3` - comparison operators
-//! - `1.2.x`, `1.x`, `*` - wildcards
+//! - `1.2.x`, `1.x`, `*`, `x` - wildcards
+//! - `next`, `beta` - dist-tags, always satisfied
 
 use semver::Version;
 
 use crate::parser::types::RegistryType;
 use crate::version::matcher::VersionMatcher;
-use crate::version::semver::{CompareResult, parse_version};
+use crate::version::semver::{BarePartial, CompareResult, normalize_partial_spec, parse_version};
 
 pub struct NpmVersionMatcher;
 
+/// Whether `spec` is a wildcard matching any version (`*`, `x` or `X`)
+fn is_any_wildcard(spec: &str) -> bool {
+    spec == "*" || spec.eq_ignore_ascii_case("x")
+}
+
 /// Top-level version specification parser
 /// Handles compound ranges (AND, OR) as well as simple ranges
 #[derive(Debug)]
@@ -25,6 +31,9 @@ enum VersionSpec {
     And(Vec<VersionSpec>),
     /// OR of specs (^1.0.0 || ^2.0.0) - any must satisfy
     Or(Vec<VersionSpec>),
+    /// Dist-tag (next, beta, ...) - resolves to whatever version the tag
+    /// currently points to, so it is always satisfied
+    DistTag,
 }
 
 impl VersionSpec {
@@ -35,6 +44,11 @@ impl VersionSpec {
             return None;
         }
 
+        // `x` is the wildcard, not a dist-tag of that name
+        if !is_any_wildcard(spec) && Self::is_dist_tag(spec) {
+            return Some(VersionSpec::DistTag);
+        }
+
         // First, check for OR (||) - lowest precedence
         if spec.contains("||") {
             let or_parts: Vec<&str> = spec.split("||").map(|s| s.trim()).collect();
@@ -51,6 +65,14 @@ impl VersionSpec {
         Self::parse_and_or_single(spec)
     }
 
+    /// Check if a spec is a bare dist-tag name (`^[a-z][a-z0-9-]*$`)
+    fn is_dist_tag(spec: &str) -> bool {
+        spec.starts_with(|c: char| c.is_ascii_lowercase())
+            && spec
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    }
+
     /// Parse a spec that may be AND (space-separated) or a single range
     fn parse_and_or_single(spec: &str) -> Option<Self> {
         let spec = spec.trim();
@@ -123,6 +145,7 @@ impl VersionSpec {
             VersionSpec::Single(range) => range.satisfies(version),
             VersionSpec::And(specs) => specs.iter().all(|s| s.satisfies(version)),
             VersionSpec::Or(specs) => specs.iter().any(|s| s.satisfies(version)),
+            VersionSpec::DistTag => true,
         }
     }
 
@@ -138,6 +161,7 @@ impl VersionSpec {
                 // For OR ranges, use the first spec's base version
                 specs.first().and_then(|s| s.base_version())
             }
+            VersionSpec::DistTag => None,
         }
     }
 }
@@ -159,7 +183,7 @@ enum VersionRange {
     Lte(Version),
     /// Less than
     Lt(Version),
-    /// Any version: * matches all versions
+    /// Any version: * (or x) matches all versions
     Any,
     /// Wildcard major: 1.x means >=1.0.0 <2.0.0
     WildcardMajor(u64),
@@ -191,7 +215,7 @@ impl VersionRange {
             parse_version(rest.trim()).map(VersionRange::Caret)
         } else if let Some(rest) = spec.strip_prefix('~') {
             parse_version(rest.trim()).map(VersionRange::Tilde)
-        } else if spec == "*" {
+        } else if is_any_wildcard(spec) {
             Some(VersionRange::Any)
         } else if let Some(range) = Self::parse_wildcard(spec) {
             Some(range)
@@ -302,9 +326,43 @@ impl VersionMatcher for NpmVersionMatcher {
         npm_version_exists(version_spec, available_versions)
     }
 
+    fn version_in_range(
+        &self,
+        version_spec: &str,
+        available_versions: &[String],
+    ) -> Option<String> {
+        // A dist-tag names a version only the registry's dist-tags resolve
+        let spec = VersionSpec::parse(version_spec)?;
+        if matches!(spec, VersionSpec::DistTag) {
+            return None;
+        }
+
+        available_versions
+            .iter()
+            .filter_map(|v| Some((v, Version::parse(v).ok()?)))
+            .filter(|(_, ver)| spec.satisfies(ver))
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(v, _)| v.clone())
+    }
+
     fn compare_to_latest(&self, current_version: &str, latest_version: &str) -> CompareResult {
         npm_compare_to_latest(current_version, latest_version)
     }
+
+    /// A bare partial version is an x-range (`1` is shown as `^1.0.0` and
+    /// `1.2` as `~1.2.0`)
+    fn normalize_spec(&self, version_spec: &str) -> String {
+        normalize_partial_spec(version_spec, BarePartial::XRange)
+    }
+
+    fn is_dist_tag(&self, version_spec: &str) -> bool {
+        npm_is_dist_tag(version_spec)
+    }
+}
+
+/// Common implementation for npm dist-tag detection
+pub(crate) fn npm_is_dist_tag(version_spec: &str) -> bool {
+    matches!(VersionSpec::parse(version_spec), Some(VersionSpec::DistTag))
 }
 
 /// Common implementation for npm version existence check
@@ -312,6 +370,9 @@ pub(crate) fn npm_version_exists(version_spec: &str, available_versions: &[Strin
     let Some(spec) = VersionSpec::parse(version_spec) else {
         return false;
     };
+    if matches!(spec, VersionSpec::DistTag) {
+        return true;
+    }
 
     available_versions.iter().any(|v| {
         Version::parse(v)
@@ -320,11 +381,24 @@ pub(crate) fn npm_version_exists(version_spec: &str, available_versions: &[Strin
     })
 }
 
+/// Check whether the lowest version allowed by `version_spec` satisfies the
+/// npm range `range_spec`
+///
+/// Returns None when either spec cannot be parsed or has no lowest version.
+pub(crate) fn npm_spec_satisfies_range(version_spec: &str, range_spec: &str) -> Option<bool> {
+    let base = VersionSpec::parse(version_spec)?.base_version()?;
+    let range = VersionSpec::parse(range_spec)?;
+    Some(range.satisfies(&base))
+}
+
 /// Common implementation for npm version comparison
 pub(crate) fn npm_compare_to_latest(current_version: &str, latest_version: &str) -> CompareResult {
     let Some(spec) = VersionSpec::parse(current_version) else {
         return CompareResult::Invalid;
     };
+    if matches!(spec, VersionSpec::DistTag) {
+        return CompareResult::Latest;
+    }
 
     let Ok(latest) = Version::parse(latest_version) else {
         return CompareResult::Invalid;
@@ -438,6 +512,8 @@ mod tests {
     // * matches any version
     #[case("*", vec!["1.0.0", "2.0.0"], true)]
     #[case("*", vec!["0.0.1"], true)]
+    #[case("x", vec!["1.0.0", "2.0.0"], true)]
+    #[case("X", vec!["0.0.1"], true)]
     // 1.x matches >=1.0.0 <2.0.0
     #[case("1.x", vec!["1.0.0", "1.9.9"], true)]
     #[case("1.x", vec!["0.9.9", "2.0.0"], false)]
@@ -558,6 +634,49 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("^17.0.0", vec!["16.14.0", "17.0.0", "17.0.2", "18.2.0"], Some("17.0.2"))]
+    #[case("~4.17.0", vec!["4.17.21", "4.17.3", "4.18.0"], Some("4.17.21"))]
+    #[case(">=1.0.0 <2.0.0", vec!["0.9.0", "1.5.0", "2.0.0"], Some("1.5.0"))]
+    #[case("*", vec!["1.0.0", "2.0.0"], Some("2.0.0"))]
+    #[case("x", vec!["1.0.0", "2.0.0"], Some("2.0.0"))]
+    #[case("^3.0.0", vec!["1.0.0", "2.0.0"], None)]
+    #[case("next", vec!["1.0.0"], None)]
+    #[case("1.0.0.0", vec!["1.0.0"], None)]
+    fn version_in_range_returns_highest_satisfying_version(
+        #[case] version_spec: &str,
+        #[case] available: Vec<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let available: Vec<String> = available.into_iter().map(String::from).collect();
+        assert_eq!(
+            NpmVersionMatcher
+                .version_in_range(version_spec, &available)
+                .as_deref(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("next", vec!["1.0.0"], true)]
+    #[case("beta", vec![], true)]
+    #[case("canary-2024", vec!["1.0.0"], true)]
+    #[case("x", vec![], false)] // wildcard, needs a published version
+    #[case("Beta", vec!["1.0.0"], false)]
+    #[case("2beta", vec!["1.0.0"], false)]
+    #[case("next_1", vec!["1.0.0"], false)]
+    fn version_exists_dist_tags(
+        #[case] version_spec: &str,
+        #[case] available: Vec<&str>,
+        #[case] expected: bool,
+    ) {
+        let available: Vec<String> = available.into_iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            NpmVersionMatcher.version_exists(version_spec, &available),
+            expected
+        );
+    }
+
     // compare_to_latest tests
     #[rstest]
     // Partial version comparison
@@ -593,7 +712,11 @@ mod tests {
     #[case("^1.0.0 || ^2.0.0", "2.5.0", CompareResult::Latest)]
     #[case("^1.0.0 || ^2.0.0", "3.0.0", CompareResult::Outdated)]
     // Invalid versions
-    #[case("invalid", "1.0.0", CompareResult::Invalid)]
+    #[case("1.0.0.0", "1.0.0", CompareResult::Invalid)]
+    #[case("Next", "1.0.0", CompareResult::Invalid)]
+    // Dist-tags resolve to whatever the tag currently points to
+    #[case("next", "1.0.0", CompareResult::Latest)]
+    #[case("beta-2", "1.0.0", CompareResult::Latest)]
     #[case("1.0.0", "invalid", CompareResult::Invalid)]
     fn compare_to_latest_returns_expected(
         #[case] current: &str,
@@ -605,4 +728,69 @@ mod tests {
             expected
         );
     }
+
+    #[rstest]
+    #[case("18.3.0", ">=17.0.0", Some(true))]
+    #[case("^18.2.0", "^18.0.0 || ^19.0.0", Some(true))]
+    #[case("16.14.0", ">=17.0.0", Some(false))]
+    #[case("~17.0.2", "^18.0.0", Some(false))]
+    #[case("latest", ">=17.0.0", None)]
+    #[case("18.3.0", "workspace:*", None)]
+    fn npm_spec_satisfies_range_returns_expected(
+        #[case] version_spec: &str,
+        #[case] range_spec: &str,
+        #[case] expected: Option<bool>,
+    ) {
+        assert_eq!(npm_spec_satisfies_range(version_spec, range_spec), expected);
+    }
+
+    // Property-based tests: version_exists and compare_to_latest must share
+    // the same range interpretation, so they are compared on random range
+    // specs and versions.
+    mod properties {
+        use super::*;
+        use crate::version::matchers::properties::*;
+        use proptest::prelude::*;
+
+        /// Every kind of npm range spec (single range, wildcard, hyphen, AND, OR)
+        fn spec_strategy() -> impl Strategy<Value = String> {
+            prop_oneof![
+                single_spec_strategy(),
+                prop::string::string_regex(
+                    "\\*|(0|[1-9][0-9]?)\\.x|(0|[1-9][0-9]?)\\.(0|[1-9][0-9]?)\\.x"
+                )
+                .unwrap(),
+                (version_strategy(), version_strategy()).prop_map(|(a, b)| format!("{a} - {b}")),
+                (version_strategy(), version_strategy()).prop_map(|(a, b)| format!(">={a} <{b}")),
+                (single_spec_strategy(), single_spec_strategy())
+                    .prop_map(|(a, b)| format!("{a} || {b}")),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn compare_latest_implies_version_exists(
+                spec in spec_strategy(),
+                latest in version_strategy(),
+            ) {
+                check_latest_implies_version_exists(&NpmVersionMatcher, &spec, &latest)?;
+            }
+
+            #[test]
+            fn version_not_satisfied_implies_outdated_or_newer(
+                spec in spec_strategy(),
+                latest in version_strategy(),
+            ) {
+                check_unsatisfied_implies_outdated_or_newer(&NpmVersionMatcher, &spec, &latest)?;
+            }
+
+            #[test]
+            fn v_prefix_does_not_change_result(
+                version in version_strategy(),
+                latest in version_strategy(),
+            ) {
+                check_v_prefix_does_not_change_result(&NpmVersionMatcher, &version, &latest)?;
+            }
+        }
+    }
 }
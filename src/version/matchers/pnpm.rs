@@ -1,11 +1,14 @@
 //! pnpm catalog version matcher
 //!
 //! Uses the same version matching logic as npm since pnpm catalogs use npm registry.
+//! `catalog:` references in package.json are resolved from pnpm-workspace.yaml,
+//! where the catalog entry itself is checked, so they are always up to date.
 
+use crate::parser::pnpm_workspace::catalog_ref_name;
 use crate::parser::types::RegistryType;
 use crate::version::matcher::VersionMatcher;
-use crate::version::matchers::npm::{npm_compare_to_latest, npm_version_exists};
-use crate::version::semver::CompareResult;
+use crate::version::matchers::npm::{npm_compare_to_latest, npm_is_dist_tag, npm_version_exists};
+use crate::version::semver::{BarePartial, CompareResult, normalize_partial_spec};
 
 /// pnpm catalog version matcher
 /// Uses the same logic as npm since pnpm catalogs use npm registry
@@ -17,10 +20,52 @@ impl VersionMatcher for PnpmCatalogMatcher {
     }
 
     fn version_exists(&self, version_spec: &str, available_versions: &[String]) -> bool {
-        npm_version_exists(version_spec, available_versions)
+        catalog_ref_name(version_spec).is_some()
+            || npm_version_exists(version_spec, available_versions)
     }
 
     fn compare_to_latest(&self, current_version: &str, latest_version: &str) -> CompareResult {
+        if catalog_ref_name(current_version).is_some() {
+            return CompareResult::Latest;
+        }
         npm_compare_to_latest(current_version, latest_version)
     }
+
+    fn normalize_spec(&self, version_spec: &str) -> String {
+        normalize_partial_spec(version_spec, BarePartial::XRange)
+    }
+
+    fn is_dist_tag(&self, version_spec: &str) -> bool {
+        npm_is_dist_tag(version_spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("catalog:", "1.0.0", CompareResult::Latest)]
+    #[case("catalog:react17", "18.2.0", CompareResult::Latest)]
+    #[case("^17.0.2", "18.2.0", CompareResult::Outdated)]
+    fn compare_to_latest_treats_catalog_refs_as_latest(
+        #[case] current_version: &str,
+        #[case] latest_version: &str,
+        #[case] expected: CompareResult,
+    ) {
+        assert_eq!(
+            PnpmCatalogMatcher.compare_to_latest(current_version, latest_version),
+            expected
+        );
+    }
+
+    #[test]
+    fn version_exists_accepts_catalog_refs() {
+        let versions = vec!["18.2.0".to_string()];
+
+        assert!(PnpmCatalogMatcher.version_exists("catalog:", &versions));
+        assert!(PnpmCatalogMatcher.version_exists("catalog:react18", &versions));
+        assert!(!PnpmCatalogMatcher.version_exists("^17.0.2", &versions));
+    }
 }
@@ -0,0 +1,73 @@
+//! Property-based checks shared by the range matchers
+//!
+//! `version_exists` and `compare_to_latest` must read a range spec the same
+//! way; each matcher runs these checks on random specs of its own syntax.
+
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+use crate::version::matcher::VersionMatcher;
+use crate::version::semver::{CompareResult, find_semantic_max, parse_version};
+
+/// A `major.minor.patch` version
+pub fn version_strategy() -> impl Strategy<Value = String> {
+    prop::string::string_regex("(0|[1-9][0-9]?)\\.(0|[1-9][0-9]?)\\.(0|[1-9][0-9]?)").unwrap()
+}
+
+/// A single range, possibly partial, with or without an operator
+pub fn single_spec_strategy() -> impl Strategy<Value = String> {
+    prop::string::string_regex(
+        "(\\^|~|>=|<=|>|<|=)?(0|[1-9][0-9]?)(\\.(0|[1-9][0-9]?)(\\.(0|[1-9][0-9]?))?)?",
+    )
+    .unwrap()
+}
+
+/// A spec reported as Latest is satisfied by the latest version itself
+pub fn check_latest_implies_version_exists(
+    matcher: &dyn VersionMatcher,
+    spec: &str,
+    latest: &str,
+) -> Result<(), TestCaseError> {
+    if matcher.compare_to_latest(spec, latest) == CompareResult::Latest {
+        prop_assert!(matcher.version_exists(spec, &[latest.to_string()]));
+    }
+    Ok(())
+}
+
+/// A spec the latest version does not satisfy is Outdated or Newer
+pub fn check_unsatisfied_implies_outdated_or_newer(
+    matcher: &dyn VersionMatcher,
+    spec: &str,
+    latest: &str,
+) -> Result<(), TestCaseError> {
+    if !matcher.version_exists(spec, &[latest.to_string()]) {
+        prop_assert!(matches!(
+            matcher.compare_to_latest(spec, latest),
+            CompareResult::Outdated | CompareResult::Newer
+        ));
+    }
+    Ok(())
+}
+
+/// `v1.2.3` and `1.2.3` are the same version
+pub fn check_v_prefix_does_not_change_result(
+    matcher: &dyn VersionMatcher,
+    version: &str,
+    latest: &str,
+) -> Result<(), TestCaseError> {
+    let prefixed = format!("v{version}");
+    prop_assert_eq!(
+        matcher.compare_to_latest(&prefixed, latest),
+        matcher.compare_to_latest(version, latest)
+    );
+    prop_assert_eq!(
+        matcher.version_exists(&prefixed, &[latest.to_string()]),
+        matcher.version_exists(version, &[latest.to_string()])
+    );
+    prop_assert_eq!(
+        find_semantic_max(&[prefixed, latest.to_string()]).and_then(|v| parse_version(v)),
+        find_semantic_max(&[version.to_string(), latest.to_string()])
+            .and_then(|v| parse_version(v))
+    );
+    Ok(())
+}
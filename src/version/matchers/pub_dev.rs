@@ -0,0 +1,89 @@
+//! pub.dev version matcher
+//!
+//! Dart version constraints are the subset of npm ranges below, so matching
+//! is delegated to the npm logic:
+//! - `1.2.3` - exact match
+//! - `^1.2.3` - compatible with version (`>=1.2.3 <2.0.0`, `^0.1.2` is `>=0.1.2 <0.2.0`)
+//! - `>=1.0.0 <2.0.0` - space-separated comparison operators
+//! - `any` - any version
+
+use crate::parser::types::RegistryType;
+use crate::version::matcher::VersionMatcher;
+use crate::version::matchers::npm::{npm_compare_to_latest, npm_version_exists};
+use crate::version::semver::CompareResult;
+
+pub struct PubVersionMatcher;
+
+/// The npm range of a Dart constraint, or None for constraints Dart does
+/// not support (npm dist-tags, `~`, `||`)
+fn to_npm_spec(version_spec: &str) -> Option<&str> {
+    let spec = version_spec.trim();
+    if spec == "any" {
+        return Some("*");
+    }
+    let unsupported = spec.starts_with(|c: char| c.is_ascii_alphabetic())
+        || spec.starts_with('~')
+        || spec.contains("||");
+    (!unsupported).then_some(spec)
+}
+
+impl VersionMatcher for PubVersionMatcher {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::Pub
+    }
+
+    fn version_exists(&self, version_spec: &str, available_versions: &[String]) -> bool {
+        to_npm_spec(version_spec).is_some_and(|spec| npm_version_exists(spec, available_versions))
+    }
+
+    fn compare_to_latest(&self, current_version: &str, latest_version: &str) -> CompareResult {
+        match to_npm_spec(current_version) {
+            Some(spec) => npm_compare_to_latest(spec, latest_version),
+            None => CompareResult::Invalid,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("^1.2.3", vec!["1.9.0"], true)]
+    #[case("^1.2.3", vec!["2.0.0"], false)]
+    #[case("^0.1.2", vec!["0.2.0"], false)]
+    #[case(">=1.0.0 <2.0.0", vec!["1.5.0"], true)]
+    #[case("1.2.3", vec!["1.2.4"], false)]
+    #[case("any", vec!["0.0.1"], true)]
+    #[case("latest", vec!["1.0.0"], false)]
+    fn version_exists_returns_expected(
+        #[case] version_spec: &str,
+        #[case] available: Vec<&str>,
+        #[case] expected: bool,
+    ) {
+        let available: Vec<String> = available.into_iter().map(String::from).collect();
+        assert_eq!(
+            PubVersionMatcher.version_exists(version_spec, &available),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("^1.2.3", "1.9.0", CompareResult::Latest)]
+    #[case("^1.2.3", "2.0.0", CompareResult::Outdated)]
+    #[case(">=0.18.0 <0.20.0", "0.20.1", CompareResult::Outdated)]
+    #[case("6.1.2", "6.1.2", CompareResult::Latest)]
+    #[case("any", "3.0.0", CompareResult::Latest)]
+    #[case("~1.2.3", "1.2.4", CompareResult::Invalid)]
+    fn compare_to_latest_returns_expected(
+        #[case] current: &str,
+        #[case] latest: &str,
+        #[case] expected: CompareResult,
+    ) {
+        assert_eq!(
+            PubVersionMatcher.compare_to_latest(current, latest),
+            expected
+        );
+    }
+}
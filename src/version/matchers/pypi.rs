@@ -2,7 +2,7 @@
 
 use std::str::FromStr;
 
-use pep508_rs::pep440_rs::{Version, VersionSpecifiers};
+use pep508_rs::pep440_rs::{Version, VersionSpecifiers, VersionSpecifiersParseError};
 use tracing::warn;
 
 use crate::parser::types::RegistryType;
@@ -24,7 +24,7 @@ impl VersionMatcher for PypiVersionMatcher {
         }
 
         // Parse the version specifiers
-        let Ok(specifiers) = VersionSpecifiers::from_str(version_spec).inspect_err(|e| {
+        let Ok(specifiers) = parse_specifiers(version_spec).inspect_err(|e| {
             warn!(
                 "Failed to parse version specifiers '{}': {}",
                 version_spec, e
@@ -55,7 +55,7 @@ impl VersionMatcher for PypiVersionMatcher {
         };
 
         // Parse the version specifiers
-        let Ok(specifiers) = VersionSpecifiers::from_str(current_version).inspect_err(|e| {
+        let Ok(specifiers) = parse_specifiers(current_version).inspect_err(|e| {
             warn!(
                 "Failed to parse version specifiers '{}': {}",
                 current_version, e
@@ -87,14 +87,85 @@ impl VersionMatcher for PypiVersionMatcher {
             CompareResult::Newer
         }
     }
+
+    /// Specifiers follow the package name directly (`requests>=2.28`), so
+    /// pins need `==`
+    fn exact_spec(&self, version: &str) -> String {
+        format!("=={version}")
+    }
+}
+
+/// Parse PEP 440 version specifiers.
+///
+/// A bare version (e.g., `2.31.0` as locked in `poetry.lock`) is treated as
+/// an exact `==` pin. Poetry's `^`, `~` and `*` constraints are translated
+/// to their PEP 440 ranges.
+fn parse_specifiers(spec: &str) -> Result<VersionSpecifiers, VersionSpecifiersParseError> {
+    if Version::from_str(spec.trim()).is_ok() {
+        return VersionSpecifiers::from_str(&format!("=={}", spec.trim()));
+    }
+    let translated: Vec<String> = spec.split(',').map(poetry_to_pep440).collect();
+    VersionSpecifiers::from_str(&translated.join(","))
+}
+
+/// Translate one Poetry constraint to PEP 440, leaving others unchanged
+///
+/// `^1.2.3` allows changes that keep the leftmost non-zero component
+/// (`>=1.2.3,<2.0.0`), `~1.2.3` allows patch changes (`>=1.2.3,<1.3.0`)
+/// and `*` allows any version.
+fn poetry_to_pep440(constraint: &str) -> String {
+    let constraint = constraint.trim();
+    if constraint == "*" {
+        return String::new();
+    }
+
+    let (base, bump_index) = if let Some(base) = constraint.strip_prefix('^') {
+        let parts: Vec<&str> = base.split('.').collect();
+        // Bump the first non-zero component, or the last given one
+        let index = parts
+            .iter()
+            .position(|part| *part != "0")
+            .unwrap_or(parts.len() - 1);
+        (base, index)
+    } else if let Some(base) = constraint
+        .strip_prefix('~')
+        .filter(|base| !base.starts_with('='))
+    {
+        // ~1 allows minor changes, ~1.2 and ~1.2.3 only patch changes
+        let index = if base.contains('.') { 1 } else { 0 };
+        (base, index)
+    } else {
+        return constraint.to_string();
+    };
+
+    let base = base.trim();
+    let parts: Vec<&str> = base.split('.').collect();
+    let Some(bumped) = parts
+        .get(bump_index)
+        .and_then(|part| part.parse::<u64>().ok())
+        .map(|part| part + 1)
+    else {
+        return constraint.to_string();
+    };
+
+    let upper: Vec<String> = parts[..bump_index]
+        .iter()
+        .map(|part| part.to_string())
+        .chain(std::iter::once(bumped.to_string()))
+        .chain(std::iter::repeat_n(
+            "0".to_string(),
+            parts.len() - bump_index - 1,
+        ))
+        .collect();
+    format!(">={},<{}", base, upper.join("."))
 }
 
 /// Extract the base version from a PEP 440 version specifier
 fn extract_base_version(spec: &str) -> Option<&str> {
     let spec = spec.trim();
 
-    // Handle operators: >=, <=, ==, !=, ~=, >, <
-    let operators = [">=", "<=", "==", "!=", "~=", ">", "<"];
+    // Handle operators: >=, <=, ==, !=, ~=, >, < and Poetry's ^, ~
+    let operators = [">=", "<=", "==", "!=", "~=", ">", "<", "^", "~"];
 
     for op in operators {
         if let Some(rest) = spec.strip_prefix(op) {
@@ -127,6 +198,8 @@ mod tests {
     #[case("==2.0.0", vec!["2.0.1", "1.9.0"], false)]
     #[case("!=2.0.0", vec!["2.0.1", "1.9.0"], true)]
     #[case("!=2.0.0", vec!["2.0.0"], false)]
+    #[case("2.0.0", vec!["2.0.0", "2.1.0"], true)]
+    #[case("2.0.0", vec!["2.1.0"], false)]
     fn version_exists_basic_operators(
         #[case] version_spec: &str,
         #[case] available: Vec<&str>,
@@ -213,6 +286,12 @@ mod tests {
     #[case("<2.0", "2.0.0", CompareResult::Outdated)]
     #[case("<=2.0", "2.0.0", CompareResult::Latest)]
     #[case("<=2.0", "2.0.1", CompareResult::Outdated)]
+    #[case("2.31.0", "2.31.0", CompareResult::Latest)]
+    #[case("2.31.0", "2.32.0", CompareResult::Outdated)]
+    #[case("^2.28", "2.32.0", CompareResult::Latest)]
+    #[case("^2.28", "3.0.0", CompareResult::Outdated)]
+    #[case("~1.4", "1.5.0", CompareResult::Outdated)]
+    #[case("*", "1.0.0", CompareResult::Latest)]
     fn compare_to_latest_returns_expected(
         #[case] current: &str,
         #[case] latest: &str,
@@ -224,6 +303,34 @@ mod tests {
         );
     }
 
+    // compare_to_latest tests - compatible release (~=): the second-to-last
+    // component is the upper bound, so `~=1.4.2` means `>=1.4.2, <1.5`
+    // and `~=1.4` means `>=1.4, <2`
+    #[rstest]
+    #[case("~=1.4.2", "1.4.2", CompareResult::Latest)]
+    #[case("~=1.4.2", "1.4.1", CompareResult::Newer)]
+    #[case("~=1.4", "1.9.0", CompareResult::Latest)]
+    #[case("~=1.4", "2.0.0", CompareResult::Outdated)]
+    #[case("~=1.0", "1.99.0", CompareResult::Latest)]
+    #[case("~=1.0", "2.0.0", CompareResult::Outdated)]
+    #[case("~=1.0.0", "1.0.9", CompareResult::Latest)]
+    #[case("~=1.0.0", "1.1.0", CompareResult::Outdated)]
+    #[case(">=1.4.2, ~=1.4", "1.9.0", CompareResult::Latest)]
+    #[case(">=1.4.2, ~=1.4", "2.0.0", CompareResult::Outdated)]
+    #[case(">=1.4.2, ~=1.4", "1.4.1", CompareResult::Newer)]
+    // PEP 440 requires at least two components after ~=
+    #[case("~=1", "1.0.0", CompareResult::Invalid)]
+    fn compare_to_latest_compatible_release(
+        #[case] current: &str,
+        #[case] latest: &str,
+        #[case] expected: CompareResult,
+    ) {
+        assert_eq!(
+            PypiVersionMatcher.compare_to_latest(current, latest),
+            expected
+        );
+    }
+
     #[test]
     fn compare_to_latest_with_empty_spec_returns_latest() {
         assert_eq!(
@@ -248,6 +355,22 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("^1.2.3", ">=1.2.3,<2.0.0")]
+    #[case("^1.2", ">=1.2,<2.0")]
+    #[case("^0.2.3", ">=0.2.3,<0.3.0")]
+    #[case("^0.0.3", ">=0.0.3,<0.0.4")]
+    #[case("^0", ">=0,<1")]
+    #[case("~1.2.3", ">=1.2.3,<1.3.0")]
+    #[case("~1.2", ">=1.2,<1.3")]
+    #[case("~1", ">=1,<2")]
+    #[case("*", "")]
+    #[case("~=1.4", "~=1.4")]
+    #[case(">=1.0", ">=1.0")]
+    fn poetry_to_pep440_returns_expected(#[case] constraint: &str, #[case] expected: &str) {
+        assert_eq!(poetry_to_pep440(constraint), expected);
+    }
+
     #[test]
     fn registry_type_returns_pypi() {
         assert_eq!(PypiVersionMatcher.registry_type(), RegistryType::PyPI);
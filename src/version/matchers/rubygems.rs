@@ -0,0 +1,308 @@
+//! RubyGems version matcher
+//!
+//! Supports RubyGems requirement operators:
+//! - `7.1.0` / `= 7.1.0` - exact match
+//! - `~> 7.1` - pessimistic: `>= 7.1, < 8.0` (`~> 7.1.2` is `>= 7.1.2, < 7.2`)
+//! - `>= 4.9`, `> 4.9`, `<= 4.9`, `< 4.9`, `!= 4.9` - comparison operators
+//! - `>= 0.18, < 2.0` - AND of several requirements
+//!
+//! Versions are compared segment-wise like `Gem::Version`: numeric segments
+//! compare numerically, missing segments count as 0, and alphabetic
+//! segments sort before numbers (`7.1.0.rc1` < `7.1.0`).
+
+use std::cmp::Ordering;
+
+use crate::parser::types::RegistryType;
+use crate::version::matcher::{BumpTargets, VersionMatcher, semver_bump_targets};
+use crate::version::semver::CompareResult;
+
+pub struct RubyGemsVersionMatcher;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Pessimistic,
+}
+
+/// A single requirement, like `~> 7.1`
+#[derive(Debug)]
+struct Requirement<'a> {
+    operator: Operator,
+    /// Operator with its trailing whitespace as written (`"~> "`)
+    prefix: &'a str,
+    version: &'a str,
+}
+
+impl<'a> Requirement<'a> {
+    fn parse(spec: &'a str) -> Option<Self> {
+        let spec = spec.trim();
+        let (operator, rest) = [
+            ("~>", Operator::Pessimistic),
+            (">=", Operator::Gte),
+            ("<=", Operator::Lte),
+            ("!=", Operator::Ne),
+            (">", Operator::Gt),
+            ("<", Operator::Lt),
+            ("=", Operator::Eq),
+        ]
+        .into_iter()
+        .find_map(|(token, operator)| Some((operator, spec.strip_prefix(token)?)))
+        .unwrap_or((Operator::Eq, spec));
+
+        let version = rest.trim_start();
+        let is_valid = version.starts_with(|c: char| c.is_ascii_digit())
+            && version
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+        is_valid.then(|| Requirement {
+            operator,
+            prefix: &spec[..spec.len() - version.len()],
+            version,
+        })
+    }
+
+    fn satisfies(&self, version: &str) -> bool {
+        let ordering = compare_versions(version, self.version);
+        match self.operator {
+            Operator::Eq => ordering == Ordering::Equal,
+            Operator::Ne => ordering != Ordering::Equal,
+            Operator::Gt => ordering == Ordering::Greater,
+            Operator::Gte => ordering != Ordering::Less,
+            Operator::Lt => ordering == Ordering::Less,
+            Operator::Lte => ordering != Ordering::Greater,
+            Operator::Pessimistic => {
+                ordering != Ordering::Less
+                    && compare_segments(&segments(version), &bump(self.version)) == Ordering::Less
+            }
+        }
+    }
+}
+
+/// Comma-separated requirements, all of which must be satisfied
+fn parse_requirements(spec: &str) -> Option<Vec<Requirement<'_>>> {
+    spec.split(',').map(Requirement::parse).collect()
+}
+
+/// A version segment: numbers sort after strings, like `Gem::Version`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Segment {
+    Str(String),
+    Num(u64),
+}
+
+/// Split a version into its segments (`7.1.0.rc1` -> [7, 1, 0, "rc", 1])
+fn segments(version: &str) -> Vec<Segment> {
+    let mut result = Vec::new();
+
+    for part in version.trim().split(['.', '-']) {
+        let mut rest = part;
+        while let Some(first) = rest.chars().next() {
+            let is_digit = first.is_ascii_digit();
+            let len = rest
+                .find(|c: char| c.is_ascii_digit() != is_digit)
+                .unwrap_or(rest.len());
+            let (run, tail) = rest.split_at(len);
+            result.push(match run.parse() {
+                Ok(n) if is_digit => Segment::Num(n),
+                _ => Segment::Str(run.to_lowercase()),
+            });
+            rest = tail;
+        }
+    }
+
+    result
+}
+
+/// Compare two RubyGems versions, padding missing segments with 0
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    compare_segments(&segments(a), &segments(b))
+}
+
+fn compare_segments(a: &[Segment], b: &[Segment]) -> Ordering {
+    let zero = Segment::Num(0);
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&zero).cmp(b.get(i).unwrap_or(&zero)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Upper bound of `~> version`, like `Gem::Version#bump`: drop pre-release
+/// segments and the last segment, then increment the new last one
+/// (`7.1` -> `8`, `7.1.2` -> `7.2`, `7` -> `8`)
+fn bump(version: &str) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = segments(version)
+        .into_iter()
+        .take_while(|segment| matches!(segment, Segment::Num(_)))
+        .collect();
+    if segments.len() > 1 {
+        segments.pop();
+    }
+    if let Some(Segment::Num(last)) = segments.last_mut() {
+        *last += 1;
+    }
+    segments
+}
+
+impl VersionMatcher for RubyGemsVersionMatcher {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::RubyGems
+    }
+
+    fn version_exists(&self, version_spec: &str, available_versions: &[String]) -> bool {
+        let Some(requirements) = parse_requirements(version_spec) else {
+            return false;
+        };
+
+        available_versions
+            .iter()
+            .any(|v| requirements.iter().all(|r| r.satisfies(v)))
+    }
+
+    fn compare_to_latest(&self, current_version: &str, latest_version: &str) -> CompareResult {
+        let Some(requirements) = parse_requirements(current_version) else {
+            return CompareResult::Invalid;
+        };
+
+        if latest_version.trim().is_empty() {
+            return CompareResult::Invalid;
+        }
+
+        if requirements.iter().all(|r| r.satisfies(latest_version)) {
+            return CompareResult::Latest;
+        }
+
+        match compare_versions(requirements[0].version, latest_version) {
+            Ordering::Less => CompareResult::Outdated,
+            _ => CompareResult::Newer,
+        }
+    }
+
+    fn calculate_bump_targets(
+        &self,
+        current_version: &str,
+        available_versions: &[String],
+        include_pre_release: bool,
+    ) -> BumpTargets {
+        match parse_requirements(current_version) {
+            Some(requirements) => semver_bump_targets(
+                requirements[0].version,
+                available_versions,
+                include_pre_release,
+            ),
+            None => BumpTargets::default(),
+        }
+    }
+
+    /// The upgrade keeps the operator of the first requirement, as written,
+    /// and replaces every requirement. A pessimistic requirement keeps its
+    /// precision (`~> 7.1` + `8.0.3` becomes `~> 8.0`).
+    fn suggest_upgrade(&self, current_spec: &str, target_version: &str) -> String {
+        let Some(first) = parse_requirements(current_spec)
+            .and_then(|requirements| requirements.into_iter().next())
+        else {
+            return target_version.to_string();
+        };
+
+        let target = if first.operator == Operator::Pessimistic {
+            let precision = first.version.split('.').count();
+            let parts: Vec<&str> = target_version.split('.').collect();
+            parts[..precision.min(parts.len())].join(".")
+        } else {
+            target_version.to_string()
+        };
+        format!("{}{}", first.prefix, target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("~> 7.1", vec!["7.1.0", "8.0.0"], true)]
+    #[case("~> 7.1", vec!["7.0.9", "8.0.0"], false)]
+    #[case("~> 7.1", vec!["7.9.3"], true)]
+    #[case("~> 7.1.2", vec!["7.2.0"], false)]
+    #[case("~> 7.1.2", vec!["7.1.9"], true)]
+    #[case("~>2.0", vec!["2.4"], true)]
+    #[case("~> 7", vec!["7.5.0"], true)]
+    #[case("~> 7", vec!["8.0.0"], false)]
+    #[case(">= 4.9", vec!["4.8.0", "4.9.3"], true)]
+    #[case("> 4.9", vec!["4.9"], false)]
+    #[case("<= 4.9", vec!["4.9.0"], true)]
+    #[case("< 4.9", vec!["4.9.0"], false)]
+    #[case("!= 4.9", vec!["4.9.0"], false)]
+    #[case("= 6.4.2", vec!["6.4.2"], true)]
+    #[case("6.4.2", vec!["6.4.3"], false)]
+    #[case(">= 0.18, < 2.0", vec!["1.5.6"], true)]
+    #[case(">= 0.18, < 2.0", vec!["2.1.0"], false)]
+    #[case("latest", vec!["1.0.0"], false)]
+    fn version_exists_returns_expected(
+        #[case] version_spec: &str,
+        #[case] available: Vec<&str>,
+        #[case] expected: bool,
+    ) {
+        let available: Vec<String> = available.into_iter().map(String::from).collect();
+        assert_eq!(
+            RubyGemsVersionMatcher.version_exists(version_spec, &available),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("~> 7.1", "7.2.1", CompareResult::Latest)]
+    #[case("~> 7.1", "8.0.0", CompareResult::Outdated)]
+    #[case(">= 4.9", "4.9.3", CompareResult::Latest)]
+    #[case(">= 0.18, < 2.0", "2.1.0", CompareResult::Outdated)]
+    #[case("= 9.0.0", "8.0.0", CompareResult::Newer)]
+    #[case("7.1.0.rc1", "7.1.0", CompareResult::Outdated)]
+    #[case("~> 7.1", "", CompareResult::Invalid)]
+    #[case("~> latest", "7.1.0", CompareResult::Invalid)]
+    fn compare_to_latest_returns_expected(
+        #[case] current: &str,
+        #[case] latest: &str,
+        #[case] expected: CompareResult,
+    ) {
+        assert_eq!(
+            RubyGemsVersionMatcher.compare_to_latest(current, latest),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("~> 7.1", "8.0.3", "~> 8.0")]
+    #[case("~> 7.1.2", "8.0.3", "~> 8.0.3")]
+    #[case("~>2.0", "3.1", "~>3.1")]
+    #[case(">= 4.9", "5.0.1", ">= 5.0.1")]
+    #[case(">= 0.18, < 2.0", "2.1.0", ">= 2.1.0")]
+    #[case("6.4.2", "6.5.0", "6.5.0")]
+    fn suggest_upgrade_keeps_operator_of_first_requirement(
+        #[case] current_spec: &str,
+        #[case] target_version: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            RubyGemsVersionMatcher.suggest_upgrade(current_spec, target_version),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("7.1.10", "7.1.9", Ordering::Greater)]
+    #[case("7.1", "7.1.0", Ordering::Equal)]
+    #[case("7.1.0.rc1", "7.1.0", Ordering::Less)]
+    #[case("7.1.0.beta1", "7.1.0.rc1", Ordering::Less)]
+    fn compare_versions_returns_expected(
+        #[case] a: &str,
+        #[case] b: &str,
+        #[case] expected: Ordering,
+    ) {
+        assert_eq!(compare_versions(a, b), expected);
+    }
+}
@@ -0,0 +1,163 @@
+//! Rust toolchain version matcher for the rust-toolchain.toml `channel`
+//!
+//! - Releases are compared to the current stable release. `1.75` is any
+//!   1.75.x release, as rustup installs the newest patch release.
+//! - Dated nightlies (`nightly-2024-01-15`) are compared to the current
+//!   nightly, and only reported once they are more than
+//!   [`NIGHTLY_STALE_DAYS`] days older, since pinned nightlies are usually
+//!   bumped on purpose rather than daily.
+
+use chrono::NaiveDate;
+
+use crate::parser::types::RegistryType;
+use crate::version::matcher::VersionMatcher;
+use crate::version::semver::{CompareResult, parse_version};
+
+/// Days a pinned nightly may trail the current nightly before it is outdated
+pub const NIGHTLY_STALE_DAYS: i64 = 7;
+
+pub struct RustToolchainMatcher;
+
+/// Date of a `nightly-YYYY-MM-DD` channel
+fn nightly_date(channel: &str) -> Option<NaiveDate> {
+    let date = channel.strip_prefix("nightly-")?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+impl VersionMatcher for RustToolchainMatcher {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::RustToolchain
+    }
+
+    /// Only the current release of each channel is known, so a release
+    /// exists if it is not newer than the current stable release, and any
+    /// nightly date exists up to the current nightly
+    fn version_exists(&self, version_spec: &str, available_versions: &[String]) -> bool {
+        if let Some(date) = nightly_date(version_spec) {
+            return available_versions
+                .iter()
+                .filter_map(|v| nightly_date(v))
+                .any(|latest| date <= latest);
+        }
+
+        let Some(version) = parse_version(version_spec) else {
+            return false;
+        };
+        available_versions
+            .iter()
+            .filter_map(|v| parse_version(v))
+            .any(|latest| version <= latest)
+    }
+
+    fn compare_to_latest(&self, current_version: &str, latest_version: &str) -> CompareResult {
+        if let Some(current) = nightly_date(current_version) {
+            let Some(latest) = nightly_date(latest_version) else {
+                return CompareResult::Invalid;
+            };
+            return match (latest - current).num_days() {
+                days if days < 0 => CompareResult::Newer,
+                days if days > NIGHTLY_STALE_DAYS => CompareResult::Outdated,
+                _ => CompareResult::Latest,
+            };
+        }
+
+        let (Some(current), Some(latest)) = (
+            parse_version(current_version),
+            parse_version(latest_version),
+        ) else {
+            return CompareResult::Invalid;
+        };
+
+        // `1.75` tracks the newest 1.75.x release
+        let ordering = if current_version.split('.').count() == 2 {
+            (current.major, current.minor).cmp(&(latest.major, latest.minor))
+        } else {
+            current.cmp(&latest)
+        };
+        match ordering {
+            std::cmp::Ordering::Less => CompareResult::Outdated,
+            std::cmp::Ordering::Equal => CompareResult::Latest,
+            std::cmp::Ordering::Greater => CompareResult::Newer,
+        }
+    }
+
+    /// Dated nightlies are compared to the current nightly instead of the
+    /// stable release
+    fn resolve_latest(
+        &self,
+        current_version: &str,
+        latest_version: &str,
+        available_versions: &[String],
+    ) -> String {
+        if nightly_date(current_version).is_none() {
+            return latest_version.to_string();
+        }
+        available_versions
+            .iter()
+            .filter(|v| nightly_date(v).is_some())
+            .max_by_key(|v| nightly_date(v))
+            .cloned()
+            .unwrap_or_else(|| latest_version.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn available() -> Vec<String> {
+        vec!["nightly-2024-09-20".to_string(), "1.81.0".to_string()]
+    }
+
+    #[rstest]
+    #[case("1.81.0", true)]
+    #[case("1.75", true)]
+    #[case("1.82.0", false)]
+    #[case("nightly-2024-01-15", true)]
+    #[case("nightly-2024-09-20", true)]
+    #[case("nightly-2024-09-21", false)]
+    #[case("stable", false)]
+    fn version_exists_returns_expected(#[case] version_spec: &str, #[case] expected: bool) {
+        assert_eq!(
+            RustToolchainMatcher.version_exists(version_spec, &available()),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("1.81.0", "1.81.0", CompareResult::Latest)]
+    #[case("1.81", "1.81.1", CompareResult::Latest)]
+    #[case("1.81.0", "1.81.1", CompareResult::Outdated)]
+    #[case("1.75", "1.81.0", CompareResult::Outdated)]
+    #[case("1.82", "1.81.0", CompareResult::Newer)]
+    #[case("nightly-2024-09-20", "nightly-2024-09-20", CompareResult::Latest)]
+    #[case("nightly-2024-09-13", "nightly-2024-09-20", CompareResult::Latest)]
+    #[case("nightly-2024-09-12", "nightly-2024-09-20", CompareResult::Outdated)]
+    #[case("nightly-2024-09-21", "nightly-2024-09-20", CompareResult::Newer)]
+    #[case("nightly-2024-09-20", "1.81.0", CompareResult::Invalid)]
+    #[case("stable", "1.81.0", CompareResult::Invalid)]
+    fn compare_to_latest_returns_expected(
+        #[case] current_version: &str,
+        #[case] latest_version: &str,
+        #[case] expected: CompareResult,
+    ) {
+        assert_eq!(
+            RustToolchainMatcher.compare_to_latest(current_version, latest_version),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("nightly-2024-01-15", "nightly-2024-09-20")]
+    #[case("1.75.0", "1.81.0")]
+    fn resolve_latest_uses_channel_of_current_version(
+        #[case] current_version: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            RustToolchainMatcher.resolve_latest(current_version, "1.81.0", &available()),
+            expected
+        );
+    }
+}
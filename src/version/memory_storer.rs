@@ -0,0 +1,733 @@
+//! In-memory VersionStorer implementation
+//!
+//! Keeps everything in a `HashMap` behind a mutex, so tests can use a real
+//! storer without creating a SQLite database in a temporary directory.
+//! Like the SQLite cache, versions become stale after the refresh interval;
+//! unlike it, not-found marks never expire.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::config::DEFAULT_REFRESH_INTERVAL_MS;
+use crate::parser::types::RegistryType;
+use crate::version::cache::PackageId;
+use crate::version::checker::VersionStorer;
+use crate::version::error::CacheError;
+use crate::version::types::{Advisory, CacheMetadata, PackageMetadata};
+
+#[derive(Debug, Default)]
+struct PackageEntry {
+    versions: Vec<String>,
+    /// When the versions were last saved (milliseconds since UNIX epoch)
+    updated_at: i64,
+    cache_metadata: Option<CacheMetadata>,
+    dist_tags: HashMap<String, String>,
+    metadata: Option<PackageMetadata>,
+    deprecated: Option<String>,
+    yanked: HashSet<String>,
+    advisories: Vec<Advisory>,
+    not_found: bool,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    packages: HashMap<(RegistryType, String), PackageEntry>,
+    /// Keys (`registry_type/package_name`) of fetches in progress
+    fetching: HashSet<String>,
+}
+
+/// VersionStorer backed by in-memory maps
+///
+/// Clones share the same storage.
+#[derive(Debug, Clone)]
+pub struct MemoryVersionStorer {
+    state: Arc<Mutex<State>>,
+    refresh_interval: i64,
+}
+
+impl Default for MemoryVersionStorer {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl MemoryVersionStorer {
+    /// Creates a storer pre-populated with versions for each package
+    ///
+    /// Seeded versions count as just fetched.
+    pub fn new(seed: Vec<(RegistryType, &str, Vec<&str>)>) -> Self {
+        let now = Self::current_timestamp_ms();
+        let packages = seed
+            .into_iter()
+            .map(|(registry_type, package_name, versions)| {
+                let entry = PackageEntry {
+                    versions: versions.into_iter().map(String::from).collect(),
+                    updated_at: now,
+                    ..Default::default()
+                };
+                ((registry_type, package_name.to_string()), entry)
+            })
+            .collect();
+
+        Self {
+            state: Arc::new(Mutex::new(State {
+                packages,
+                fetching: HashSet::new(),
+            })),
+            refresh_interval: DEFAULT_REFRESH_INTERVAL_MS,
+        }
+    }
+
+    /// Set how long (in milliseconds) saved versions stay fresh
+    pub fn with_refresh_interval(mut self, refresh_interval: i64) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    fn current_timestamp_ms() -> i64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time before UNIX epoch")
+            .as_millis() as i64
+    }
+
+    /// Entries last updated before this time are stale
+    fn stale_threshold(&self) -> i64 {
+        Self::current_timestamp_ms() - self.refresh_interval
+    }
+
+    fn lock_state(&self) -> Result<MutexGuard<'_, State>, CacheError> {
+        self.state.lock().map_err(|_| CacheError::LockPoisoned)
+    }
+
+    fn fetch_key(registry_type: RegistryType, package_name: &str) -> String {
+        format!("{}/{}", registry_type.as_str(), package_name)
+    }
+}
+
+impl PackageEntry {
+    /// Versions that are not yanked, unless yanked ones are included
+    fn listed_versions(&self, include_yanked: bool) -> impl Iterator<Item = &String> {
+        self.versions
+            .iter()
+            .filter(move |version| include_yanked || !self.yanked.contains(*version))
+    }
+}
+
+impl State {
+    fn entry(&self, registry_type: RegistryType, package_name: &str) -> Option<&PackageEntry> {
+        self.packages
+            .get(&(registry_type, package_name.to_string()))
+    }
+
+    fn entry_mut(&mut self, registry_type: RegistryType, package_name: &str) -> &mut PackageEntry {
+        self.packages
+            .entry((registry_type, package_name.to_string()))
+            .or_default()
+    }
+}
+
+impl VersionStorer for MemoryVersionStorer {
+    fn get_latest_version(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<String>, CacheError> {
+        let state = self.lock_state()?;
+        let Some(entry) = state.entry(registry_type, package_name) else {
+            return Ok(None);
+        };
+
+        // Prefer the "latest" dist tag, then the semantically highest version
+        if let Some(latest) = entry.dist_tags.get("latest") {
+            return Ok(Some(latest.clone()));
+        }
+
+        Ok(entry
+            .listed_versions(false)
+            .filter_map(|v| Some((v, crate::version::semver::parse_version(v)?)))
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(v, _)| v.clone()))
+    }
+
+    fn get_versions(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        include_yanked: bool,
+    ) -> Result<Vec<String>, CacheError> {
+        let state = self.lock_state()?;
+        Ok(state
+            .entry(registry_type, package_name)
+            .map(|entry| entry.listed_versions(include_yanked).cloned().collect())
+            .unwrap_or_default())
+    }
+
+    fn version_exists(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        version: &str,
+        include_yanked: bool,
+    ) -> Result<bool, CacheError> {
+        let state = self.lock_state()?;
+        Ok(state
+            .entry(registry_type, package_name)
+            .is_some_and(|entry| entry.listed_versions(include_yanked).any(|v| v == version)))
+    }
+
+    fn replace_versions(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        versions: Vec<String>,
+        yanked: &[String],
+    ) -> Result<(), CacheError> {
+        let mut state = self.lock_state()?;
+        let entry = state.entry_mut(registry_type, package_name);
+
+        // Like the SQLite cache, keep known versions and append new ones
+        for version in versions {
+            entry.yanked.remove(&version);
+            if !entry.versions.contains(&version) {
+                entry.versions.push(version);
+            }
+        }
+        for version in yanked {
+            if !entry.versions.contains(version) {
+                entry.versions.push(version.clone());
+            }
+            entry.yanked.insert(version.clone());
+        }
+        entry.updated_at = Self::current_timestamp_ms();
+        entry.not_found = false;
+
+        Ok(())
+    }
+
+    fn touch_package(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<(), CacheError> {
+        let mut state = self.lock_state()?;
+        if let Some(entry) = state
+            .packages
+            .get_mut(&(registry_type, package_name.to_string()))
+        {
+            entry.updated_at = Self::current_timestamp_ms();
+        }
+        Ok(())
+    }
+
+    fn get_packages_needing_refresh(&self) -> Result<Vec<PackageId>, CacheError> {
+        let threshold = self.stale_threshold();
+        let state = self.lock_state()?;
+        Ok(state
+            .packages
+            .iter()
+            .filter(|(_, entry)| entry.updated_at < threshold && !entry.not_found)
+            .map(|((registry_type, package_name), _)| PackageId {
+                registry_type: *registry_type,
+                package_name: package_name.clone(),
+            })
+            .collect())
+    }
+
+    fn try_start_fetch(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<bool, CacheError> {
+        let mut state = self.lock_state()?;
+        if state
+            .entry(registry_type, package_name)
+            .is_some_and(|entry| entry.not_found)
+        {
+            return Ok(false);
+        }
+
+        Ok(state
+            .fetching
+            .insert(Self::fetch_key(registry_type, package_name)))
+    }
+
+    fn finish_fetch(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<(), CacheError> {
+        let mut state = self.lock_state()?;
+        state
+            .fetching
+            .remove(&Self::fetch_key(registry_type, package_name));
+        Ok(())
+    }
+
+    fn get_dist_tag(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        tag_name: &str,
+    ) -> Result<Option<String>, CacheError> {
+        let state = self.lock_state()?;
+        Ok(state
+            .entry(registry_type, package_name)
+            .and_then(|entry| entry.dist_tags.get(tag_name).cloned()))
+    }
+
+    fn save_dist_tags(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        dist_tags: &HashMap<String, String>,
+    ) -> Result<(), CacheError> {
+        if dist_tags.is_empty() {
+            return Ok(());
+        }
+
+        let mut state = self.lock_state()?;
+        state.entry_mut(registry_type, package_name).dist_tags = dist_tags.clone();
+        Ok(())
+    }
+
+    fn get_dist_tags(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<HashMap<String, String>, CacheError> {
+        let state = self.lock_state()?;
+        Ok(state
+            .entry(registry_type, package_name)
+            .map(|entry| entry.dist_tags.clone())
+            .unwrap_or_default())
+    }
+
+    fn filter_packages_not_in_cache(
+        &self,
+        registry_type: RegistryType,
+        package_names: &[String],
+    ) -> Result<Vec<String>, CacheError> {
+        let state = self.lock_state()?;
+        Ok(package_names
+            .iter()
+            .filter(|name| {
+                !state
+                    .entry(registry_type, name)
+                    .is_some_and(|entry| entry.not_found || !entry.versions.is_empty())
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn mark_not_found(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<(), CacheError> {
+        let mut state = self.lock_state()?;
+        state.entry_mut(registry_type, package_name).not_found = true;
+        Ok(())
+    }
+
+    fn filter_stale_packages(
+        &self,
+        registry_type: RegistryType,
+        package_names: &[String],
+    ) -> Result<Vec<String>, CacheError> {
+        let threshold = self.stale_threshold();
+        let state = self.lock_state()?;
+        Ok(package_names
+            .iter()
+            .filter(|name| {
+                state.entry(registry_type, name).is_some_and(|entry| {
+                    entry.updated_at < threshold && !entry.not_found && !entry.versions.is_empty()
+                })
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn save_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        metadata: &PackageMetadata,
+    ) -> Result<(), CacheError> {
+        let mut state = self.lock_state()?;
+        state.entry_mut(registry_type, package_name).metadata = Some(metadata.clone());
+        Ok(())
+    }
+
+    fn get_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<PackageMetadata>, CacheError> {
+        let state = self.lock_state()?;
+        Ok(state
+            .entry(registry_type, package_name)
+            .and_then(|entry| entry.metadata.clone()))
+    }
+
+    fn save_cache_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        cache_metadata: &CacheMetadata,
+    ) -> Result<(), CacheError> {
+        let mut state = self.lock_state()?;
+        state.entry_mut(registry_type, package_name).cache_metadata = Some(cache_metadata.clone());
+        Ok(())
+    }
+
+    fn get_cache_metadata(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<CacheMetadata>, CacheError> {
+        let state = self.lock_state()?;
+        Ok(state
+            .entry(registry_type, package_name)
+            .and_then(|entry| entry.cache_metadata.clone()))
+    }
+
+    fn count_packages(&self) -> Result<HashMap<String, usize>, CacheError> {
+        let state = self.lock_state()?;
+        let mut counts = HashMap::new();
+        for (registry_type, _) in state.packages.keys() {
+            *counts
+                .entry(registry_type.as_str().to_string())
+                .or_default() += 1;
+        }
+        Ok(counts)
+    }
+
+    fn get_last_fetch_time(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<i64>, CacheError> {
+        let state = self.lock_state()?;
+        Ok(state
+            .entry(registry_type, package_name)
+            .filter(|entry| !entry.versions.is_empty())
+            .map(|entry| entry.updated_at))
+    }
+
+    fn save_deprecated(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        deprecated: Option<String>,
+    ) -> Result<(), CacheError> {
+        let mut state = self.lock_state()?;
+        state.entry_mut(registry_type, package_name).deprecated = deprecated;
+        Ok(())
+    }
+
+    fn get_deprecated(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Option<String>, CacheError> {
+        let state = self.lock_state()?;
+        Ok(state
+            .entry(registry_type, package_name)
+            .and_then(|entry| entry.deprecated.clone()))
+    }
+
+    fn save_advisories(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+        advisories: &[Advisory],
+    ) -> Result<(), CacheError> {
+        let mut state = self.lock_state()?;
+        state.entry_mut(registry_type, package_name).advisories = advisories.to_vec();
+        Ok(())
+    }
+
+    fn get_advisories(
+        &self,
+        registry_type: RegistryType,
+        package_name: &str,
+    ) -> Result<Vec<Advisory>, CacheError> {
+        let state = self.lock_state()?;
+        Ok(state
+            .entry(registry_type, package_name)
+            .map(|entry| entry.advisories.clone())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn new_seeds_versions() {
+        let storer = MemoryVersionStorer::new(vec![(
+            RegistryType::Npm,
+            "lodash",
+            vec!["4.17.20", "4.17.21"],
+        )]);
+
+        assert_eq!(
+            storer
+                .get_versions(RegistryType::Npm, "lodash", false)
+                .unwrap(),
+            vec!["4.17.20", "4.17.21"]
+        );
+        assert!(
+            storer
+                .version_exists(RegistryType::Npm, "lodash", "4.17.20", false)
+                .unwrap()
+        );
+        assert!(
+            storer
+                .get_versions(RegistryType::CratesIo, "lodash", false)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn batch_get_versions_leaves_out_packages_without_versions() {
+        let storer = MemoryVersionStorer::new(vec![
+            (RegistryType::Npm, "lodash", vec!["4.17.21"]),
+            (RegistryType::CratesIo, "lodash", vec!["0.1.0"]),
+        ]);
+
+        let versions = storer
+            .batch_get_versions(
+                &[
+                    (RegistryType::Npm, "lodash"),
+                    (RegistryType::Npm, "missing"),
+                ],
+                false,
+            )
+            .unwrap();
+
+        assert_eq!(
+            versions,
+            HashMap::from([(
+                (RegistryType::Npm, "lodash".to_string()),
+                vec!["4.17.21".to_string()]
+            )])
+        );
+    }
+
+    #[rstest]
+    #[case(vec![], Some("2.0.0"))]
+    #[case(vec![("latest", "1.5.0")], Some("1.5.0"))]
+    fn get_latest_version_prefers_latest_dist_tag(
+        #[case] dist_tags: Vec<(&str, &str)>,
+        #[case] expected: Option<&str>,
+    ) {
+        let storer = MemoryVersionStorer::new(vec![(
+            RegistryType::Npm,
+            "pkg",
+            vec!["1.5.0", "2.0.0", "1.0.0"],
+        )]);
+        let dist_tags = dist_tags
+            .into_iter()
+            .map(|(tag, version)| (tag.to_string(), version.to_string()))
+            .collect();
+        storer
+            .save_dist_tags(RegistryType::Npm, "pkg", &dist_tags)
+            .unwrap();
+        assert_eq!(
+            storer.get_dist_tags(RegistryType::Npm, "pkg").unwrap(),
+            dist_tags
+        );
+
+        assert_eq!(
+            storer
+                .get_latest_version(RegistryType::Npm, "pkg")
+                .unwrap()
+                .as_deref(),
+            expected
+        );
+    }
+
+    #[test]
+    fn try_start_fetch_rejects_package_being_fetched_until_finished() {
+        let storer = MemoryVersionStorer::default();
+
+        assert!(storer.try_start_fetch(RegistryType::Npm, "pkg").unwrap());
+        assert!(!storer.try_start_fetch(RegistryType::Npm, "pkg").unwrap());
+        assert!(storer.try_start_fetch(RegistryType::Jsr, "pkg").unwrap());
+
+        storer.finish_fetch(RegistryType::Npm, "pkg").unwrap();
+
+        assert!(storer.try_start_fetch(RegistryType::Npm, "pkg").unwrap());
+    }
+
+    #[rstest]
+    #[case(false, vec!["1.0.0"])]
+    #[case(true, vec!["1.0.0", "1.1.0", "2.0.0"])]
+    fn replace_versions_leaves_yanked_versions_out_of_lookups_unless_included(
+        #[case] include_yanked: bool,
+        #[case] expected: Vec<&str>,
+    ) {
+        let storer = MemoryVersionStorer::new(vec![(
+            RegistryType::CratesIo,
+            "serde",
+            vec!["1.0.0", "1.1.0"],
+        )]);
+
+        // Yanked after it was cached, and yanked before it was ever cached
+        storer
+            .replace_versions(
+                RegistryType::CratesIo,
+                "serde",
+                vec!["1.0.0".to_string()],
+                &["1.1.0".to_string(), "2.0.0".to_string()],
+            )
+            .unwrap();
+
+        assert_eq!(
+            storer
+                .get_versions(RegistryType::CratesIo, "serde", include_yanked)
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            storer
+                .version_exists(RegistryType::CratesIo, "serde", "1.1.0", include_yanked)
+                .unwrap(),
+            include_yanked
+        );
+        assert_eq!(
+            storer
+                .get_latest_version(RegistryType::CratesIo, "serde")
+                .unwrap(),
+            Some("1.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn filter_stale_packages_returns_cached_packages_older_than_refresh_interval() {
+        let storer = MemoryVersionStorer::new(vec![(RegistryType::Npm, "seeded", vec!["1.0.0"])])
+            .with_refresh_interval(0);
+        storer.mark_not_found(RegistryType::Npm, "gone").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let names = vec![
+            "seeded".to_string(),
+            "gone".to_string(),
+            "missing".to_string(),
+        ];
+
+        assert_eq!(
+            storer
+                .filter_stale_packages(RegistryType::Npm, &names)
+                .unwrap(),
+            vec!["seeded"]
+        );
+        assert_eq!(
+            storer.get_packages_needing_refresh().unwrap(),
+            vec![PackageId {
+                registry_type: RegistryType::Npm,
+                package_name: "seeded".to_string(),
+            }]
+        );
+
+        let fresh = storer.clone().with_refresh_interval(60_000);
+        assert!(
+            fresh
+                .filter_stale_packages(RegistryType::Npm, &names)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(fresh.get_packages_needing_refresh().unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_last_fetch_time_is_set_once_versions_are_saved() {
+        let storer = MemoryVersionStorer::default();
+        storer.mark_not_found(RegistryType::Npm, "pkg").unwrap();
+
+        assert_eq!(
+            storer
+                .get_last_fetch_time(RegistryType::Npm, "pkg")
+                .unwrap(),
+            None
+        );
+
+        let before = MemoryVersionStorer::current_timestamp_ms();
+        storer
+            .replace_versions(RegistryType::Npm, "pkg", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+
+        assert!(
+            storer
+                .get_last_fetch_time(RegistryType::Npm, "pkg")
+                .unwrap()
+                .is_some_and(|fetched_at| fetched_at >= before)
+        );
+    }
+
+    #[test]
+    fn save_cache_metadata_round_trips() {
+        let storer = MemoryVersionStorer::default();
+        let cache_metadata = CacheMetadata {
+            etag: Some("\"abc\"".to_string()),
+            last_modified: None,
+        };
+
+        storer
+            .save_cache_metadata(RegistryType::Npm, "pkg", &cache_metadata)
+            .unwrap();
+
+        assert_eq!(
+            storer.get_cache_metadata(RegistryType::Npm, "pkg").unwrap(),
+            Some(cache_metadata)
+        );
+        assert_eq!(
+            storer
+                .get_cache_metadata(RegistryType::Npm, "other")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn mark_not_found_is_treated_as_cached_until_versions_are_saved() {
+        let storer = MemoryVersionStorer::default();
+        let names = vec!["gone".to_string(), "missing".to_string()];
+
+        storer.mark_not_found(RegistryType::Npm, "gone").unwrap();
+
+        assert!(!storer.try_start_fetch(RegistryType::Npm, "gone").unwrap());
+        assert_eq!(
+            storer
+                .filter_packages_not_in_cache(RegistryType::Npm, &names)
+                .unwrap(),
+            vec!["missing"]
+        );
+
+        storer
+            .replace_versions(RegistryType::Npm, "gone", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+
+        assert!(storer.try_start_fetch(RegistryType::Npm, "gone").unwrap());
+    }
+
+    #[test]
+    fn clones_share_storage() {
+        let storer = MemoryVersionStorer::default();
+        let clone = storer.clone();
+
+        clone
+            .replace_versions(RegistryType::Npm, "pkg", vec!["1.0.0".to_string()], &[])
+            .unwrap();
+
+        assert!(
+            storer
+                .version_exists(RegistryType::Npm, "pkg", "1.0.0", false)
+                .unwrap()
+        );
+    }
+}
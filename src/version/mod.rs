@@ -22,18 +22,23 @@
 //!
 //! - [`cache`]: SQLite-based version cache with refresh logic
 //! - [`checker`]: Version comparison and status determination
+//! - [`circuit_breaker`]: Per-registry circuit breaker for failing registries
 //! - [`matcher`]: Version matching trait and registry-specific implementations
 //! - [`registry`]: Registry trait for fetching versions from remote sources
 //! - [`registries`]: Concrete registry implementations (npm, crates.io, etc.)
+//! - [`osv`]: Security advisory lookup in the OSV database
 //! - [`error`]: Error types for cache and registry operations
 //! - [`semver`]: Shared semver utilities
 //! - [`types`]: Common types like `PackageVersions`
 
 pub mod cache;
 pub mod checker;
+pub mod circuit_breaker;
 pub mod error;
 pub mod matcher;
 pub mod matchers;
+pub mod memory_storer;
+pub mod osv;
 pub mod registries;
 pub mod registry;
 pub mod semver;
@@ -0,0 +1,236 @@
+//! OSV (Open Source Vulnerabilities) advisory lookup
+//!
+//! Queries the OSV API for the security advisories affecting one version of
+//! a package, the same database `cargo audit` advisories are published to.
+
+use serde::Deserialize;
+use serde_json::json;
+use tracing::warn;
+
+use crate::version::error::RegistryError;
+use crate::version::types::Advisory;
+
+/// Default base URL for the OSV API
+const DEFAULT_BASE_URL: &str = "https://api.osv.dev";
+
+/// OSV ecosystem name of crates.io packages
+pub const CRATES_IO_ECOSYSTEM: &str = "crates.io";
+
+/// Response of `POST /v1/query`; OSV omits `vulns` when there are none
+#[derive(Debug, Deserialize)]
+struct QueryResponse {
+    #[serde(default)]
+    vulns: Vec<Vulnerability>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Vulnerability {
+    id: String,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    details: Option<String>,
+    #[serde(default)]
+    severity: Vec<SeverityScore>,
+    #[serde(default)]
+    database_specific: Option<DatabaseSpecific>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SeverityScore {
+    score: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseSpecific {
+    #[serde(default)]
+    severity: Option<String>,
+}
+
+impl From<Vulnerability> for Advisory {
+    fn from(vuln: Vulnerability) -> Self {
+        // GitHub advisories carry a rating (e.g. "HIGH"); RustSec ones only
+        // a CVSS vector, if anything
+        let severity = vuln
+            .database_specific
+            .and_then(|specific| specific.severity)
+            .or_else(|| vuln.severity.into_iter().next().map(|s| s.score))
+            .unwrap_or_else(|| "UNKNOWN".to_string());
+        let summary = vuln
+            .summary
+            .or_else(|| {
+                vuln.details
+                    .and_then(|details| details.lines().next().map(str::to_string))
+            })
+            .unwrap_or_default();
+
+        Advisory {
+            id: vuln.id,
+            severity,
+            summary,
+        }
+    }
+}
+
+/// Client for the OSV query API
+pub struct OsvAdvisoryChecker {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl OsvAdvisoryChecker {
+    /// Creates a new OsvAdvisoryChecker with a custom base URL
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .user_agent("version-lsp")
+                .build()
+                .expect("Failed to create HTTP client"),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Advisories affecting `version` of `package_name` in the OSV
+    /// `ecosystem` (e.g. [`CRATES_IO_ECOSYSTEM`])
+    pub async fn query(
+        &self,
+        ecosystem: &str,
+        package_name: &str,
+        version: &str,
+    ) -> Result<Vec<Advisory>, RegistryError> {
+        let url = format!("{}/v1/query", self.base_url);
+        let body = json!({
+            "package": { "name": package_name, "ecosystem": ecosystem },
+            "version": version,
+        });
+
+        let response = self.client.post(&url).json(&body).send().await?;
+
+        let status = response.status();
+
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(RegistryError::rate_limited(response.headers()));
+        }
+
+        if !status.is_success() {
+            warn!("OSV returned status {}: {}", status, url);
+            return Err(RegistryError::InvalidResponse(format!(
+                "Unexpected status: {}",
+                status
+            )));
+        }
+
+        let response: QueryResponse = response.json().await.map_err(|e| {
+            warn!("Failed to parse OSV response: {}", e);
+            RegistryError::InvalidResponse(e.to_string())
+        })?;
+
+        Ok(response.vulns.into_iter().map(Advisory::from).collect())
+    }
+}
+
+impl Default for OsvAdvisoryChecker {
+    fn default() -> Self {
+        Self::new(DEFAULT_BASE_URL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    #[tokio::test]
+    async fn query_returns_advisories_of_version() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/query")
+            .match_body(Matcher::Json(json!({
+                "package": { "name": "time", "ecosystem": "crates.io" },
+                "version": "0.1.45",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"vulns":[
+                    {
+                        "id": "RUSTSEC-2020-0071",
+                        "summary": "Potential segfault in the time crate",
+                        "severity": [{"type": "CVSS_V3", "score": "CVSS:3.1/AV:L/AC:H/PR:N/UI:N/S:U/C:N/I:N/A:H"}]
+                    },
+                    {
+                        "id": "GHSA-wcg3-cvx6-7396",
+                        "details": "Segmentation fault in time\nMore details",
+                        "database_specific": {"severity": "MODERATE"}
+                    },
+                    {"id": "RUSTSEC-2099-0001"}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+
+        let advisories = OsvAdvisoryChecker::new(&server.url())
+            .query(CRATES_IO_ECOSYSTEM, "time", "0.1.45")
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(
+            advisories,
+            vec![
+                Advisory {
+                    id: "RUSTSEC-2020-0071".to_string(),
+                    severity: "CVSS:3.1/AV:L/AC:H/PR:N/UI:N/S:U/C:N/I:N/A:H".to_string(),
+                    summary: "Potential segfault in the time crate".to_string(),
+                },
+                Advisory {
+                    id: "GHSA-wcg3-cvx6-7396".to_string(),
+                    severity: "MODERATE".to_string(),
+                    summary: "Segmentation fault in time".to_string(),
+                },
+                Advisory {
+                    id: "RUSTSEC-2099-0001".to_string(),
+                    severity: "UNKNOWN".to_string(),
+                    summary: String::new(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn query_returns_empty_for_version_without_advisories() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/query")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("{}")
+            .create_async()
+            .await;
+
+        let advisories = OsvAdvisoryChecker::new(&server.url())
+            .query(CRATES_IO_ECOSYSTEM, "serde", "1.0.200")
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert!(advisories.is_empty());
+    }
+
+    #[tokio::test]
+    async fn query_returns_invalid_response_for_server_error() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/query")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let result = OsvAdvisoryChecker::new(&server.url())
+            .query(CRATES_IO_ECOSYSTEM, "serde", "1.0.200")
+            .await;
+
+        mock.assert_async().await;
+        assert!(matches!(result, Err(RegistryError::InvalidResponse(_))));
+    }
+}
@@ -0,0 +1,141 @@
+//! Conda registry client using the anaconda.org package API
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::parser::types::RegistryType;
+use crate::version::error::RegistryError;
+use crate::version::registry::Registry;
+use crate::version::types::PackageVersions;
+
+/// Package API of the conda-forge channel. Override the URL to use another
+/// channel (e.g. `https://api.anaconda.org/package/bioconda`).
+const DEFAULT_CONDA_REGISTRY: &str = "https://api.anaconda.org/package/conda-forge";
+
+/// Conda registry client
+pub struct CondaRegistry {
+    client: Client,
+    base_url: String,
+}
+
+impl Default for CondaRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_CONDA_REGISTRY.to_string())
+    }
+}
+
+impl CondaRegistry {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url,
+        }
+    }
+}
+
+/// anaconda.org package API response structure
+#[derive(Debug, Deserialize)]
+struct CondaPackageResponse {
+    versions: Vec<String>,
+    latest_version: Option<String>,
+}
+
+#[async_trait]
+impl Registry for CondaRegistry {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::Conda
+    }
+
+    async fn fetch_all_versions(
+        &self,
+        package_name: &str,
+    ) -> Result<PackageVersions, RegistryError> {
+        let url = format!("{}/{}", self.base_url, package_name);
+        debug!("Fetching conda package: {}", url);
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(RegistryError::NotFound(package_name.to_string()));
+        }
+
+        if !response.status().is_success() {
+            return Err(RegistryError::InvalidResponse(format!(
+                "anaconda.org API returned status {}",
+                response.status()
+            )));
+        }
+
+        let package: CondaPackageResponse = response
+            .json()
+            .await
+            .map_err(|e| RegistryError::InvalidResponse(e.to_string()))?;
+
+        debug!(
+            "Found {} versions for package {}",
+            package.versions.len(),
+            package_name
+        );
+
+        let dist_tags: HashMap<String, String> = package
+            .latest_version
+            .map(|latest| ("latest".to_string(), latest))
+            .into_iter()
+            .collect();
+
+        Ok(PackageVersions::with_dist_tags(package.versions, dist_tags))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_versions_and_latest() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/numpy")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "name": "numpy",
+                    "latest_version": "2.1.0",
+                    "versions": ["1.21.0", "1.26.4", "2.1.0"]
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = CondaRegistry::new(server.url());
+        let result = registry.fetch_all_versions("numpy").await.unwrap();
+
+        mock.assert_async().await;
+
+        assert_eq!(result.versions, vec!["1.21.0", "1.26.4", "2.1.0"]);
+        assert_eq!(result.dist_tags.get("latest"), Some(&"2.1.0".to_string()));
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_not_found_for_missing_package() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/nonexistent")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let registry = CondaRegistry::new(server.url());
+        let result = registry.fetch_all_versions("nonexistent").await;
+
+        mock.assert_async().await;
+
+        assert!(matches!(result, Err(RegistryError::NotFound(_))));
+    }
+}
@@ -1,9 +1,18 @@
 //! crates.io registry API implementation
+//!
+//! Versions come from the crates.io web API, or from the sparse index
+//! (RFC 2789) which serves one small NDJSON file per crate and is not subject
+//! to the API rate limits. Builds with the `sparse-index` feature use the
+//! sparse index by default.
+
+use std::sync::Arc;
 
 use crate::parser::types::RegistryType;
 use crate::version::error::RegistryError;
+use crate::version::osv::{CRATES_IO_ECOSYSTEM, OsvAdvisoryChecker};
 use crate::version::registry::Registry;
-use crate::version::types::PackageVersions;
+use crate::version::semver::{is_prerelease, parse_version};
+use crate::version::types::{Advisory, CacheMetadata, FetchResult, PackageVersions};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use tracing::warn;
@@ -11,6 +20,9 @@ use tracing::warn;
 /// Default base URL for crates.io registry
 const DEFAULT_BASE_URL: &str = "https://crates.io/api/v1/crates";
 
+/// Default base URL for the crates.io sparse index
+const DEFAULT_SPARSE_INDEX_URL: &str = "https://index.crates.io";
+
 /// Response from crates.io registry API
 #[derive(Debug, Deserialize)]
 struct CratesIoResponse {
@@ -25,31 +37,155 @@ struct CrateVersion {
     created_at: String,
 }
 
+/// One line of a sparse index file
+#[derive(Debug, Deserialize)]
+struct IndexEntry {
+    vers: String,
+    #[serde(default)]
+    yanked: bool,
+}
+
+/// Where versions are fetched from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Api {
+    /// `{base_url}/{name}` JSON API
+    Web,
+    /// `{base_url}/{index path}` NDJSON sparse index
+    SparseIndex,
+}
+
 /// Registry implementation for crates.io API
 pub struct CratesIoRegistry {
     client: reqwest::Client,
     base_url: String,
+    api: Api,
+    /// Bearer token for private or staging registries. Never sent to the
+    /// public crates.io endpoints.
+    auth_token: Option<String>,
+    /// Looks up security advisories of the latest version, when enabled
+    advisory_checker: Option<Arc<OsvAdvisoryChecker>>,
 }
 
 impl CratesIoRegistry {
     /// Creates a new CratesIoRegistry with a custom base URL
     pub fn new(base_url: &str) -> Self {
+        Self::with_api(base_url, Api::Web)
+    }
+
+    /// Creates a CratesIoRegistry reading a sparse index at `base_url`
+    pub fn sparse_index(base_url: &str) -> Self {
+        Self::with_api(base_url, Api::SparseIndex)
+    }
+
+    /// Creates a CratesIoRegistry for `base_url` using the API selected at
+    /// build time: the sparse index with the `sparse-index` feature, the web
+    /// API otherwise
+    pub fn with_default_api(base_url: &str) -> Self {
+        if cfg!(feature = "sparse-index") {
+            Self::sparse_index(base_url)
+        } else {
+            Self::new(base_url)
+        }
+    }
+
+    fn with_api(base_url: &str, api: Api) -> Self {
         Self {
             client: reqwest::Client::builder()
                 .user_agent("version-lsp")
                 .build()
                 .expect("Failed to create HTTP client"),
-            base_url: base_url.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api,
+            auth_token: None,
+            advisory_checker: None,
+        }
+    }
+
+    /// Sets the bearer token sent with every request to a registry other
+    /// than the public crates.io
+    pub fn with_auth_token(mut self, auth_token: Option<String>) -> Self {
+        self.auth_token = auth_token;
+        self
+    }
+
+    /// Sets the checker used to look up the security advisories of the latest
+    /// version
+    pub fn with_advisory_checker(
+        mut self,
+        advisory_checker: Option<Arc<OsvAdvisoryChecker>>,
+    ) -> Self {
+        self.advisory_checker = advisory_checker;
+        self
+    }
+
+    /// The auth token, unless the registry is the public crates.io
+    fn request_auth_token(&self) -> Option<&str> {
+        let is_public =
+            [DEFAULT_BASE_URL, DEFAULT_SPARSE_INDEX_URL].contains(&self.base_url.as_str());
+        self.auth_token.as_deref().filter(|_| !is_public)
+    }
+
+    fn package_url(&self, package_name: &str) -> String {
+        match self.api {
+            Api::Web => format!("{}/{}", self.base_url, package_name),
+            Api::SparseIndex => format!("{}/{}", self.base_url, index_path(package_name)),
         }
     }
 }
 
 impl Default for CratesIoRegistry {
     fn default() -> Self {
-        Self::new(DEFAULT_BASE_URL)
+        if cfg!(feature = "sparse-index") {
+            Self::sparse_index(DEFAULT_SPARSE_INDEX_URL)
+        } else {
+            Self::new(DEFAULT_BASE_URL)
+        }
+    }
+}
+
+/// Path of a crate's file in the sparse index
+///
+/// Names are lowercased; 1-3 character names live in `1/`, `2/` and
+/// `3/{first char}/`, longer ones in `{chars 1-2}/{chars 3-4}/`.
+fn index_path(package_name: &str) -> String {
+    let name = package_name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
     }
 }
 
+/// The highest stable version, or the newest version when all are
+/// pre-releases
+fn latest_stable_version(versions: &[String]) -> Option<&str> {
+    versions
+        .iter()
+        .filter(|v| !is_prerelease(v))
+        .filter_map(|v| Some((v, parse_version(v)?)))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(v, _)| v.as_str())
+        .or_else(|| versions.last().map(String::as_str))
+}
+
+/// Parse a sparse index file into non-yanked versions in publish order,
+/// with the yanked versions alongside
+fn parse_index_file(body: &str) -> PackageVersions {
+    let (yanked, published): (Vec<IndexEntry>, Vec<IndexEntry>) = body
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            serde_json::from_str::<IndexEntry>(line)
+                .inspect_err(|e| warn!("Failed to parse crates.io index entry: {}", e))
+                .ok()
+        })
+        .partition(|entry| entry.yanked);
+
+    PackageVersions::new(published.into_iter().map(|entry| entry.vers).collect())
+        .with_yanked(yanked.into_iter().map(|entry| entry.vers).collect())
+}
+
 #[async_trait::async_trait]
 impl Registry for CratesIoRegistry {
     fn registry_type(&self) -> RegistryType {
@@ -60,16 +196,38 @@ impl Registry for CratesIoRegistry {
         &self,
         package_name: &str,
     ) -> Result<PackageVersions, RegistryError> {
-        let url = format!("{}/{}", self.base_url, package_name);
+        self.fetch_versions_if_modified(package_name, &CacheMetadata::default())
+            .await?
+            .into_versions()
+    }
 
-        let response = self.client.get(&url).send().await?;
+    async fn fetch_versions_if_modified(
+        &self,
+        package_name: &str,
+        cache_metadata: &CacheMetadata,
+    ) -> Result<FetchResult, RegistryError> {
+        let url = self.package_url(package_name);
+
+        let mut request = self.client.get(&url);
+        if let Some(token) = self.request_auth_token() {
+            request = request.bearer_auth(token);
+        }
+        let response = cache_metadata.apply(request).send().await?;
 
         let status = response.status();
 
+        if status == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(FetchResult::NotModified);
+        }
+
         if status == reqwest::StatusCode::NOT_FOUND {
             return Err(RegistryError::NotFound(package_name.to_string()));
         }
 
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(RegistryError::rate_limited(response.headers()));
+        }
+
         if !status.is_success() {
             warn!("crates.io registry returned status {}: {}", status, url);
             return Err(RegistryError::InvalidResponse(format!(
@@ -78,16 +236,28 @@ impl Registry for CratesIoRegistry {
             )));
         }
 
+        let cache_metadata = CacheMetadata::from_headers(response.headers());
+
+        // Index files list versions in publish order (oldest first, newest last)
+        if self.api == Api::SparseIndex {
+            let body = response.text().await?;
+            return Ok(FetchResult::Modified(
+                parse_index_file(&body).with_cache_metadata(cache_metadata),
+            ));
+        }
+
         let crate_info: CratesIoResponse = response.json().await.map_err(|e| {
             warn!("Failed to parse crates.io registry response: {}", e);
             RegistryError::InvalidResponse(e.to_string())
         })?;
 
-        // Filter out yanked versions and sort by created_at (oldest first, newest last)
-        let mut versions: Vec<(String, Option<DateTime<Utc>>)> = crate_info
-            .versions
+        // Set aside yanked versions and sort by created_at (oldest first, newest last)
+        let (yanked, published): (Vec<CrateVersion>, Vec<CrateVersion>) =
+            crate_info.versions.into_iter().partition(|v| v.yanked);
+        let yanked = yanked.into_iter().map(|v| v.num).collect();
+
+        let mut versions: Vec<(String, Option<DateTime<Utc>>)> = published
             .into_iter()
-            .filter(|v| !v.yanked)
             .map(|v| {
                 let timestamp = DateTime::parse_from_rfc3339(&v.created_at)
                     .ok()
@@ -100,7 +270,36 @@ impl Registry for CratesIoRegistry {
 
         let versions: Vec<String> = versions.into_iter().map(|(v, _)| v).collect();
 
-        Ok(PackageVersions::new(versions))
+        Ok(FetchResult::Modified(
+            PackageVersions::new(versions)
+                .with_yanked(yanked)
+                .with_cache_metadata(cache_metadata),
+        ))
+    }
+
+    /// Queries OSV for the latest stable version, if advisory checking is
+    /// enabled
+    ///
+    /// A failed lookup is logged and returns `None`, so OSV being unavailable
+    /// neither fails the refresh nor clears the cached advisories.
+    async fn fetch_advisories(
+        &self,
+        package_name: &str,
+        versions: &[String],
+    ) -> Option<Vec<Advisory>> {
+        let checker = self.advisory_checker.as_ref()?;
+        let latest = latest_stable_version(versions)?;
+
+        checker
+            .query(CRATES_IO_ECOSYSTEM, package_name, latest)
+            .await
+            .inspect_err(|e| {
+                warn!(
+                    "Failed to check advisories of {}@{}: {}",
+                    package_name, latest, e
+                )
+            })
+            .ok()
     }
 }
 
@@ -108,6 +307,61 @@ impl Registry for CratesIoRegistry {
 mod tests {
     use super::*;
     use mockito::Server;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("a", "1/a")]
+    #[case("cc", "2/cc")]
+    #[case("syn", "3/s/syn")]
+    #[case("serde", "se/rd/serde")]
+    #[case("Inflector", "in/fl/inflector")]
+    fn index_path_follows_sparse_index_layout(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(index_path(name), expected);
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_reads_sparse_index_and_excludes_yanked_versions() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/se/rd/serde")
+            .with_status(200)
+            .with_body(
+                r#"{"name":"serde","vers":"1.0.0","deps":[],"cksum":"a","features":{},"yanked":false}
+{"name":"serde","vers":"1.0.1","deps":[],"cksum":"b","features":{},"yanked":true}
+{"name":"serde","vers":"1.0.2","deps":[],"cksum":"c","features":{},"yanked":false}
+"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = CratesIoRegistry::sparse_index(&format!("{}/", server.url()));
+        let result = registry.fetch_all_versions("serde").await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(
+            result.versions,
+            vec!["1.0.0".to_string(), "1.0.2".to_string()]
+        );
+        assert_eq!(result.yanked, vec!["1.0.1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_not_found_for_crate_missing_from_sparse_index() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/no/ne/nonexistent")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let registry = CratesIoRegistry::sparse_index(&server.url());
+        let result = registry.fetch_all_versions("nonexistent").await;
+
+        mock.assert_async().await;
+        assert!(matches!(result, Err(RegistryError::NotFound(_))));
+    }
 
     #[tokio::test]
     async fn fetch_all_versions_returns_versions_sorted_by_created_at() {
@@ -200,6 +454,7 @@ mod tests {
             result.versions,
             vec!["1.0.0".to_string(), "1.0.2".to_string()]
         );
+        assert_eq!(result.yanked, vec!["1.0.1".to_string()]);
     }
 
     #[tokio::test]
@@ -228,4 +483,121 @@ mod tests {
         mock.assert_async().await;
         assert!(result.is_empty());
     }
+
+    #[tokio::test]
+    async fn fetch_all_versions_sends_auth_token_to_custom_registry() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/internal-crate")
+            .match_header("authorization", "Bearer secret-token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"versions": [{"num": "0.1.0", "yanked": false, "created_at": "2020-01-01T00:00:00.000Z"}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let registry =
+            CratesIoRegistry::new(&server.url()).with_auth_token(Some("secret-token".to_string()));
+        let result = registry.fetch_all_versions("internal-crate").await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(result.versions, vec!["0.1.0".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_omits_authorization_header_without_token() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/se/rd/serde")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_body(r#"{"name":"serde","vers":"1.0.0","deps":[],"cksum":"a","features":{},"yanked":false}"#)
+            .create_async()
+            .await;
+
+        let registry = CratesIoRegistry::sparse_index(&server.url());
+        let result = registry.fetch_all_versions("serde").await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(result.versions, vec!["1.0.0".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn fetch_advisories_queries_latest_stable_version() {
+        let mut server = Server::new_async().await;
+
+        let osv_mock = server
+            .mock("POST", "/v1/query")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({ "version": "0.1.45" }),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"vulns":[{"id":"RUSTSEC-2020-0071","summary":"Potential segfault in the time crate"}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = CratesIoRegistry::sparse_index(&server.url())
+            .with_advisory_checker(Some(Arc::new(OsvAdvisoryChecker::new(&server.url()))));
+        let versions = ["0.1.44", "0.1.45", "0.2.0-alpha.1"].map(String::from);
+        let advisories = registry.fetch_advisories("time", &versions).await.unwrap();
+
+        osv_mock.assert_async().await;
+        let ids: Vec<&str> = advisories.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["RUSTSEC-2020-0071"]);
+    }
+
+    #[tokio::test]
+    async fn fetch_advisories_returns_none_for_failed_lookup() {
+        let mut server = Server::new_async().await;
+
+        let osv_mock = server
+            .mock("POST", "/v1/query")
+            .with_status(503)
+            .create_async()
+            .await;
+
+        let registry = CratesIoRegistry::sparse_index(&server.url())
+            .with_advisory_checker(Some(Arc::new(OsvAdvisoryChecker::new(&server.url()))));
+        let advisories = registry
+            .fetch_advisories("serde", &["1.0.0".to_string()])
+            .await;
+
+        osv_mock.assert_async().await;
+        assert_eq!(advisories, None);
+    }
+
+    #[tokio::test]
+    async fn fetch_advisories_returns_none_without_advisory_checker() {
+        let registry = CratesIoRegistry::sparse_index("http://127.0.0.1:1");
+
+        let advisories = registry
+            .fetch_advisories("serde", &["1.0.0".to_string()])
+            .await;
+
+        assert_eq!(advisories, None);
+    }
+
+    #[rstest]
+    #[case(DEFAULT_BASE_URL, None)]
+    #[case(DEFAULT_SPARSE_INDEX_URL, None)]
+    #[case(
+        "https://crates.internal.example.com/api/v1/crates",
+        Some("secret-token")
+    )]
+    fn request_auth_token_is_never_sent_to_public_crates_io(
+        #[case] base_url: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let registry =
+            CratesIoRegistry::new(base_url).with_auth_token(Some("secret-token".to_string()));
+
+        assert_eq!(registry.request_auth_token(), expected);
+    }
 }
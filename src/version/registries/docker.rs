@@ -138,14 +138,7 @@ impl DockerRegistry {
         }
 
         if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            let retry_after = response
-                .headers()
-                .get("retry-after")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| v.parse().ok());
-            return Err(RegistryError::RateLimited {
-                retry_after_secs: retry_after,
-            });
+            return Err(RegistryError::rate_limited(response.headers()));
         }
 
         if !status.is_success() {
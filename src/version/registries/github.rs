@@ -2,8 +2,9 @@
 
 use crate::parser::types::RegistryType;
 use crate::version::error::RegistryError;
+use crate::version::registries::pagination::next_page_url;
 use crate::version::registry::Registry;
-use crate::version::types::PackageVersions;
+use crate::version::types::{CacheMetadata, FetchResult, PackageVersions};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use tracing::warn;
@@ -11,6 +12,12 @@ use tracing::warn;
 /// Default base URL for GitHub API
 const DEFAULT_BASE_URL: &str = "https://api.github.com";
 
+/// Largest page size the GitHub API accepts
+const TAGS_PER_PAGE: usize = 100;
+
+/// Default number of tags looked through before giving up on a tag
+const DEFAULT_MAX_TAGS: usize = 200;
+
 /// Response from GitHub Releases API
 #[derive(Debug, Deserialize)]
 struct Release {
@@ -46,19 +53,34 @@ pub trait TagShaFetcher: Send + Sync {
 pub struct GitHubRegistry {
     client: reqwest::Client,
     base_url: String,
+    /// Tags looked through by [`TagShaFetcher::fetch_tag_sha`] across pages
+    max_tags: usize,
 }
 
 impl GitHubRegistry {
     /// Creates a new GitHubRegistry with a custom base URL
+    ///
+    /// GitHub Enterprise Server exposes the API under a path prefix
+    /// (`https://github.example.com/api/v3`); a trailing slash is tolerated.
     pub fn new(base_url: &str) -> Self {
         Self {
             client: reqwest::Client::builder()
                 .user_agent("version-lsp")
                 .build()
                 .expect("Failed to create HTTP client"),
-            base_url: base_url.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            max_tags: DEFAULT_MAX_TAGS,
         }
     }
+
+    /// Sets how many tags are looked through when fetching the SHA of a tag
+    ///
+    /// Tags are listed newest first, 100 per page, so older tags of
+    /// repositories with many tags are only found with a larger limit.
+    pub fn with_max_tags(mut self, max_tags: usize) -> Self {
+        self.max_tags = max_tags;
+        self
+    }
 }
 
 impl Default for GitHubRegistry {
@@ -80,30 +102,36 @@ impl Registry for GitHubRegistry {
         &self,
         package_name: &str,
     ) -> Result<PackageVersions, RegistryError> {
+        self.fetch_versions_if_modified(package_name, &CacheMetadata::default())
+            .await?
+            .into_versions()
+    }
+
+    async fn fetch_versions_if_modified(
+        &self,
+        package_name: &str,
+        cache_metadata: &CacheMetadata,
+    ) -> Result<FetchResult, RegistryError> {
         let url = format!("{}/repos/{}/releases", self.base_url, package_name);
 
-        let response = self
+        let request = self
             .client
             .get(&url)
-            .header("Accept", "application/vnd.github+json")
-            .send()
-            .await?;
+            .header("Accept", "application/vnd.github+json");
+        let response = cache_metadata.apply(request).send().await?;
 
         let status = response.status();
 
+        if status == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(FetchResult::NotModified);
+        }
+
         if status == reqwest::StatusCode::NOT_FOUND {
             return Err(RegistryError::NotFound(package_name.to_string()));
         }
 
         if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            let retry_after = response
-                .headers()
-                .get("retry-after")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| v.parse().ok());
-            return Err(RegistryError::RateLimited {
-                retry_after_secs: retry_after,
-            });
+            return Err(RegistryError::rate_limited(response.headers()));
         }
 
         if !status.is_success() {
@@ -114,6 +142,7 @@ impl Registry for GitHubRegistry {
             )));
         }
 
+        let cache_metadata = CacheMetadata::from_headers(response.headers());
         let releases: Vec<Release> = response.json().await.map_err(|e| {
             warn!("Failed to parse GitHub releases response: {}", e);
             RegistryError::InvalidResponse(e.to_string())
@@ -139,7 +168,9 @@ impl Registry for GitHubRegistry {
             .map(|(tag, _)| tag)
             .collect();
 
-        Ok(PackageVersions::new(versions))
+        Ok(FetchResult::Modified(
+            PackageVersions::new(versions).with_cache_metadata(cache_metadata),
+        ))
     }
 }
 
@@ -150,57 +181,68 @@ impl TagShaFetcher for GitHubRegistry {
         package_name: &str,
         tag_name: &str,
     ) -> Result<String, RegistryError> {
-        let url = format!("{}/repos/{}/tags", self.base_url, package_name);
-
-        let response = self
-            .client
-            .get(&url)
-            .header("Accept", "application/vnd.github+json")
-            .send()
-            .await?;
-
-        let status = response.status();
-
-        if status == reqwest::StatusCode::NOT_FOUND {
-            return Err(RegistryError::NotFound(package_name.to_string()));
-        }
-
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            let retry_after = response
-                .headers()
-                .get("retry-after")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| v.parse().ok());
-            return Err(RegistryError::RateLimited {
-                retry_after_secs: retry_after,
-            });
-        }
-
-        if !status.is_success() {
-            warn!("GitHub API returned status {}: {}", status, url);
-            return Err(RegistryError::InvalidResponse(format!(
-                "Unexpected status: {}",
-                status
-            )));
+        let mut url = format!(
+            "{}/repos/{}/tags?per_page={}",
+            self.base_url, package_name, TAGS_PER_PAGE
+        );
+        let mut seen = 0;
+
+        // Tags are paginated; follow the Link header until the tag is found
+        loop {
+            let response = self
+                .client
+                .get(&url)
+                .header("Accept", "application/vnd.github+json")
+                .send()
+                .await?;
+
+            let status = response.status();
+
+            if status == reqwest::StatusCode::NOT_FOUND {
+                return Err(RegistryError::NotFound(package_name.to_string()));
+            }
+
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Err(RegistryError::rate_limited(response.headers()));
+            }
+
+            if !status.is_success() {
+                warn!("GitHub API returned status {}: {}", status, url);
+                return Err(RegistryError::InvalidResponse(format!(
+                    "Unexpected status: {}",
+                    status
+                )));
+            }
+
+            let next_url = next_page_url(response.headers());
+            let tags: Vec<Tag> = response.json().await.map_err(|e| {
+                warn!("Failed to parse GitHub tags response: {}", e);
+                RegistryError::InvalidResponse(e.to_string())
+            })?;
+            seen += tags.len();
+
+            // Find the tag with matching name
+            if let Some(tag) = tags.into_iter().find(|t| t.name == tag_name) {
+                return Ok(tag.commit.sha);
+            }
+
+            match next_url {
+                Some(next_url) if seen < self.max_tags => url = next_url,
+                _ => {
+                    return Err(RegistryError::NotFound(format!(
+                        "Tag {} not found",
+                        tag_name
+                    )));
+                }
+            }
         }
-
-        let tags: Vec<Tag> = response.json().await.map_err(|e| {
-            warn!("Failed to parse GitHub tags response: {}", e);
-            RegistryError::InvalidResponse(e.to_string())
-        })?;
-
-        // Find the tag with matching name
-        tags.into_iter()
-            .find(|t| t.name == tag_name)
-            .map(|t| t.commit.sha)
-            .ok_or_else(|| RegistryError::NotFound(format!("Tag {} not found", tag_name)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mockito::Server;
+    use mockito::{Matcher, Server};
 
     #[tokio::test]
     async fn fetch_all_versions_returns_releases_sorted_by_published_at() {
@@ -309,6 +351,10 @@ mod tests {
 
         let mock = server
             .mock("GET", "/repos/actions/checkout/tags")
+            .match_query(Matcher::UrlEncoded(
+                "per_page".to_string(),
+                "100".to_string(),
+            ))
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(
@@ -336,6 +382,7 @@ mod tests {
 
         let mock = server
             .mock("GET", "/repos/actions/checkout/tags")
+            .match_query(Matcher::UrlEncoded("per_page".to_string(), "100".to_string()))
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(
@@ -353,12 +400,107 @@ mod tests {
         assert!(matches!(result, Err(RegistryError::NotFound(_))));
     }
 
+    #[tokio::test]
+    async fn fetch_tag_sha_follows_next_page_link() {
+        let mut server = Server::new_async().await;
+
+        let first_page = server
+            .mock("GET", "/repos/actions/checkout/tags")
+            .match_query(Matcher::UrlEncoded(
+                "per_page".to_string(),
+                "100".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header(
+                "link",
+                &format!(
+                    r#"<{}/repos/actions/checkout/tags?per_page=100&page=2>; rel="next""#,
+                    server.url()
+                ),
+            )
+            .with_body(
+                r#"[
+                    {"name": "v4.1.6", "commit": {"sha": "8e5e7e5ab8b370d6c329ec480221332ada57f0ab"}}
+                ]"#,
+            )
+            .create_async()
+            .await;
+        let second_page = server
+            .mock("GET", "/repos/actions/checkout/tags")
+            .match_query(Matcher::UrlEncoded("page".to_string(), "2".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {"name": "v2.0.0", "commit": {"sha": "abcdef1234567890abcdef1234567890abcdef12"}}
+                ]"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = GitHubRegistry::new(&server.url());
+        let result = registry
+            .fetch_tag_sha("actions/checkout", "v2.0.0")
+            .await
+            .unwrap();
+
+        first_page.assert_async().await;
+        second_page.assert_async().await;
+        assert_eq!(result, "abcdef1234567890abcdef1234567890abcdef12");
+    }
+
+    #[tokio::test]
+    async fn fetch_tag_sha_stops_paginating_at_max_tags() {
+        let mut server = Server::new_async().await;
+
+        let first_page = server
+            .mock("GET", "/repos/actions/checkout/tags")
+            .match_query(Matcher::UrlEncoded(
+                "per_page".to_string(),
+                "100".to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header(
+                "link",
+                &format!(
+                    r#"<{}/repos/actions/checkout/tags?per_page=100&page=2>; rel="next""#,
+                    server.url()
+                ),
+            )
+            .with_body(
+                r#"[
+                    {"name": "v4.1.6", "commit": {"sha": "8e5e7e5ab8b370d6c329ec480221332ada57f0ab"}}
+                ]"#,
+            )
+            .create_async()
+            .await;
+        let second_page = server
+            .mock("GET", "/repos/actions/checkout/tags")
+            .match_query(Matcher::UrlEncoded("page".to_string(), "2".to_string()))
+            .expect(0)
+            .create_async()
+            .await;
+
+        let registry = GitHubRegistry::new(&server.url()).with_max_tags(1);
+        let result = registry.fetch_tag_sha("actions/checkout", "v2.0.0").await;
+
+        first_page.assert_async().await;
+        second_page.assert_async().await;
+        assert!(matches!(result, Err(RegistryError::NotFound(_))));
+    }
+
     #[tokio::test]
     async fn fetch_tag_sha_returns_rate_limited_for_429() {
         let mut server = Server::new_async().await;
 
         let mock = server
             .mock("GET", "/repos/actions/checkout/tags")
+            .match_query(Matcher::UrlEncoded(
+                "per_page".to_string(),
+                "100".to_string(),
+            ))
             .with_status(429)
             .with_header("content-type", "application/json")
             .with_header("retry-after", "120")
@@ -4,16 +4,22 @@ use crate::parser::types::RegistryType;
 use crate::version::error::RegistryError;
 use crate::version::registry::Registry;
 use crate::version::types::PackageVersions;
-use semver::Version;
+use glob::{MatchOptions, Pattern};
 use tracing::warn;
 
 /// Default base URL for Go proxy
 const DEFAULT_BASE_URL: &str = "https://proxy.golang.org";
 
+/// Environment variables listing private module patterns
+/// (comma-separated globs, as understood by the go command)
+const PRIVATE_MODULE_ENV_VARS: [&str; 2] = ["GOPRIVATE", "GONOSUMCHECK"];
+
 /// Registry implementation for Go proxy API
 pub struct GoProxyRegistry {
     client: reqwest::Client,
     base_url: String,
+    /// Module path prefixes of private modules
+    private_patterns: Vec<Pattern>,
 }
 
 impl GoProxyRegistry {
@@ -25,16 +31,82 @@ impl GoProxyRegistry {
                 .build()
                 .expect("Failed to create HTTP client"),
             base_url: base_url.to_string(),
+            private_patterns: Vec::new(),
         }
     }
+
+    /// Treat modules matching the comma-separated glob patterns as private
+    ///
+    /// Like `GOPRIVATE`, a pattern matches a prefix of the module path:
+    /// `*.corp.example.com` matches `git.corp.example.com/team/lib`.
+    /// Versions of private modules are still fetched from the proxy, but a
+    /// failing response is reported as a network error rather than "not
+    /// found", since private proxies answer 404 when credentials are missing.
+    /// No checksum database is consulted for any module.
+    pub fn with_private_patterns(mut self, patterns: &str) -> Self {
+        self.private_patterns.extend(
+            patterns
+                .split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .filter_map(|pattern| {
+                    Pattern::new(pattern)
+                        .inspect_err(|e| {
+                            warn!("Invalid private module pattern '{}': {}", pattern, e)
+                        })
+                        .ok()
+                }),
+        );
+        self
+    }
+
+    /// Add the private module patterns of `GOPRIVATE` and `GONOSUMCHECK`
+    pub fn with_private_patterns_from_env(self) -> Self {
+        PRIVATE_MODULE_ENV_VARS
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .fold(self, |registry, patterns| {
+                registry.with_private_patterns(&patterns)
+            })
+    }
+
+    /// Whether `module_path` matches one of the private module patterns
+    fn is_private(&self, module_path: &str) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        self.private_patterns.iter().any(|pattern| {
+            let elements = pattern.as_str().split('/').count();
+            let prefix: Vec<&str> = module_path.split('/').take(elements).collect();
+            prefix.len() == elements && pattern.matches_with(&prefix.join("/"), options)
+        })
+    }
 }
 
 impl Default for GoProxyRegistry {
     fn default() -> Self {
-        Self::new(DEFAULT_BASE_URL)
+        // Honor the proxy configured for the go command
+        let base_url = std::env::var("GOPROXY")
+            .ok()
+            .as_deref()
+            .and_then(first_proxy_url)
+            .map(String::from)
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        Self::new(&base_url)
     }
 }
 
+/// First proxy URL in a `GOPROXY` list, skipping `direct` and `off`
+///
+/// Entries are separated by `,` or `|`.
+fn first_proxy_url(goproxy: &str) -> Option<&str> {
+    goproxy
+        .split([',', '|'])
+        .map(|entry| entry.trim().trim_end_matches('/'))
+        .find(|entry| entry.starts_with("https://") || entry.starts_with("http://"))
+}
+
 #[async_trait::async_trait]
 impl Registry for GoProxyRegistry {
     fn registry_type(&self) -> RegistryType {
@@ -52,6 +124,20 @@ impl Registry for GoProxyRegistry {
 
         let response = self.client.get(&url).send().await?;
 
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(RegistryError::rate_limited(response.headers()));
+        }
+
+        // A private module missing from its proxy usually means the request
+        // lacked credentials, so it must not be cached as nonexistent
+        let response = if self.is_private(package_name) {
+            response.error_for_status().inspect_err(|e| {
+                warn!("Go proxy failed for private module {}: {}", package_name, e)
+            })?
+        } else {
+            response
+        };
+
         let status = response.status();
 
         // Go proxy returns 404 or 410 for modules that don't exist
@@ -72,22 +158,14 @@ impl Registry for GoProxyRegistry {
             RegistryError::InvalidResponse(e.to_string())
         })?;
 
-        // Go proxy returns versions one per line
-        // Sort by semver (oldest first, newest last)
-        let mut versions: Vec<(String, Option<Version>)> = body
+        // Go proxy returns versions one per line in arbitrary order
+        let versions: Vec<String> = body
             .lines()
             .filter(|line| !line.is_empty())
-            .map(|line| {
-                let parsed = line.strip_prefix('v').and_then(|v| Version::parse(v).ok());
-                (line.to_string(), parsed)
-            })
+            .map(String::from)
             .collect();
 
-        versions.sort_by(|(_, a), (_, b)| a.cmp(b));
-
-        let versions: Vec<String> = versions.into_iter().map(|(v, _)| v).collect();
-
-        Ok(PackageVersions::new(versions))
+        Ok(PackageVersions::sorted_by_semver(versions))
     }
 }
 
@@ -110,6 +188,7 @@ fn encode_module_path(path: &str) -> String {
 mod tests {
     use super::*;
     use mockito::Server;
+    use rstest::rstest;
 
     #[tokio::test]
     async fn fetch_all_versions_returns_versions_sorted_by_semver() {
@@ -160,6 +239,87 @@ mod tests {
         assert!(matches!(result, Err(RegistryError::NotFound(_))));
     }
 
+    #[rstest]
+    #[case(404)]
+    #[case(410)]
+    #[case(500)]
+    #[tokio::test]
+    async fn fetch_all_versions_returns_network_error_for_private_module(#[case] status: usize) {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/git.corp.example.com/team/lib/@v/list")
+            .with_status(status)
+            .with_body("not found")
+            .create_async()
+            .await;
+
+        let registry = GoProxyRegistry::new(&server.url())
+            .with_private_patterns("rsc.io/private, *.corp.example.com");
+        let result = registry
+            .fetch_all_versions("git.corp.example.com/team/lib")
+            .await;
+
+        mock.assert_async().await;
+        assert!(matches!(result, Err(RegistryError::Network(_))));
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_fetches_private_module_from_proxy() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/git.corp.example.com/team/lib/@v/list")
+            .with_status(200)
+            .with_body("v1.1.0\nv1.0.0\n")
+            .create_async()
+            .await;
+
+        let registry =
+            GoProxyRegistry::new(&server.url()).with_private_patterns("*.corp.example.com");
+        let result = registry
+            .fetch_all_versions("git.corp.example.com/team/lib")
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(result.versions, vec!["v1.0.0", "v1.1.0"]);
+    }
+
+    #[rstest]
+    #[case("*.corp.example.com", "git.corp.example.com/team/lib", true)]
+    #[case("*.corp.example.com", "corp.example.com/lib", false)]
+    #[case("rsc.io/private", "rsc.io/private/quux", true)]
+    #[case("rsc.io/private", "rsc.io/public", false)]
+    #[case("github.com/acme/*", "github.com/acme/tool/v2", true)]
+    #[case("github.com/acme/*", "github.com/other/tool", false)]
+    #[case("", "golang.org/x/text", false)]
+    fn is_private_matches_module_path_prefix(
+        #[case] patterns: &str,
+        #[case] module_path: &str,
+        #[case] expected: bool,
+    ) {
+        let registry = GoProxyRegistry::new(DEFAULT_BASE_URL).with_private_patterns(patterns);
+
+        assert_eq!(registry.is_private(module_path), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "https://goproxy.corp.example.com,direct",
+        Some("https://goproxy.corp.example.com")
+    )]
+    #[case("direct", None)]
+    #[case("off", None)]
+    #[case(
+        "https://a.example.com/|https://b.example.com",
+        Some("https://a.example.com")
+    )]
+    #[case("direct,http://localhost:3000", Some("http://localhost:3000"))]
+    fn first_proxy_url_skips_direct_and_off(#[case] goproxy: &str, #[case] expected: Option<&str>) {
+        assert_eq!(first_proxy_url(goproxy), expected);
+    }
+
     #[tokio::test]
     async fn fetch_all_versions_returns_not_found_for_gone_status() {
         let mut server = Server::new_async().await;
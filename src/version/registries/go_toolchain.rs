@@ -0,0 +1,138 @@
+//! Go toolchain release registry using the go.dev download index
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::parser::go_mod::GO_TOOLCHAIN_PACKAGE_NAME;
+use crate::parser::types::RegistryType;
+use crate::version::error::RegistryError;
+use crate::version::registry::Registry;
+use crate::version::types::PackageVersions;
+
+/// Base URL of the Go downloads page; the index is `{base}/?mode=json`
+const DEFAULT_GO_DL_URL: &str = "https://go.dev/dl";
+
+/// Go toolchain release registry client
+pub struct GoToolchainRegistry {
+    client: Client,
+    base_url: String,
+}
+
+impl Default for GoToolchainRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_GO_DL_URL.to_string())
+    }
+}
+
+impl GoToolchainRegistry {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+/// One release in the download index
+#[derive(Debug, Deserialize)]
+struct GoRelease {
+    /// Release version with a `go` prefix (`go1.22.0`, `go1.20`)
+    version: String,
+    /// False for betas and release candidates
+    #[serde(default)]
+    stable: bool,
+}
+
+#[async_trait]
+impl Registry for GoToolchainRegistry {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::GoToolchain
+    }
+
+    async fn fetch_all_versions(
+        &self,
+        package_name: &str,
+    ) -> Result<PackageVersions, RegistryError> {
+        // The index only lists the Go toolchain itself
+        if package_name != GO_TOOLCHAIN_PACKAGE_NAME {
+            return Err(RegistryError::NotFound(package_name.to_string()));
+        }
+
+        // Without include=all only the two supported release lines are
+        // listed, and older go directives would be reported as not found
+        let url = format!("{}/?mode=json&include=all", self.base_url);
+        debug!("Fetching Go releases: {}", url);
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(RegistryError::InvalidResponse(format!(
+                "go.dev returned status {}",
+                response.status()
+            )));
+        }
+
+        let releases: Vec<GoRelease> = response
+            .json()
+            .await
+            .map_err(|e| RegistryError::InvalidResponse(e.to_string()))?;
+
+        debug!("Found {} Go releases", releases.len());
+
+        let versions = releases
+            .into_iter()
+            .filter(|release| release.stable)
+            .map(|release| release.version.trim_start_matches("go").to_string())
+            .collect();
+
+        // The index lists the newest release first
+        Ok(PackageVersions::sorted_by_semver(versions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_stable_releases() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("mode".to_string(), "json".to_string()),
+                Matcher::UrlEncoded("include".to_string(), "all".to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {"version": "go1.23rc1", "stable": false, "files": []},
+                    {"version": "go1.22.1", "stable": true, "files": []},
+                    {"version": "go1.22.0", "stable": true, "files": []},
+                    {"version": "go1.21.13", "stable": true, "files": []},
+                    {"version": "go1.20", "stable": true, "files": []}
+                ]"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = GoToolchainRegistry::new(format!("{}/", server.url()));
+        let result = registry.fetch_all_versions("go").await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(result.versions, vec!["1.20", "1.21.13", "1.22.0", "1.22.1"]);
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_not_found_for_other_packages() {
+        let registry = GoToolchainRegistry::new("http://127.0.0.1:1".to_string());
+
+        let result = registry.fetch_all_versions("golang.org/x/text").await;
+
+        assert!(matches!(result, Err(RegistryError::NotFound(_))));
+    }
+}
@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use crate::parser::types::RegistryType;
 use crate::version::error::RegistryError;
 use crate::version::registry::Registry;
-use crate::version::types::PackageVersions;
+use crate::version::types::{CacheMetadata, FetchResult, PackageVersions};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use tracing::warn;
@@ -16,7 +16,6 @@ const DEFAULT_BASE_URL: &str = "https://jsr.io";
 /// Response from JSR registry API
 #[derive(Debug, Deserialize)]
 struct JsrMetaResponse {
-    #[allow(dead_code)]
     latest: Option<String>,
     versions: HashMap<String, JsrVersionMeta>,
 }
@@ -66,22 +65,36 @@ impl Registry for JsrRegistry {
         &self,
         package_name: &str,
     ) -> Result<PackageVersions, RegistryError> {
+        self.fetch_versions_if_modified(package_name, &CacheMetadata::default())
+            .await?
+            .into_versions()
+    }
+
+    async fn fetch_versions_if_modified(
+        &self,
+        package_name: &str,
+        cache_metadata: &CacheMetadata,
+    ) -> Result<FetchResult, RegistryError> {
         // JSR API URL: https://jsr.io/@scope/package/meta.json
         let url = format!("{}/{}/meta.json", self.base_url, package_name);
 
-        let response = self
-            .client
-            .get(&url)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+        let request = self.client.get(&url).header("Accept", "application/json");
+        let response = cache_metadata.apply(request).send().await?;
 
         let status = response.status();
 
+        if status == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(FetchResult::NotModified);
+        }
+
         if status == reqwest::StatusCode::NOT_FOUND {
             return Err(RegistryError::NotFound(package_name.to_string()));
         }
 
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(RegistryError::rate_limited(response.headers()));
+        }
+
         if !status.is_success() {
             warn!("JSR registry returned status {}: {}", status, url);
             return Err(RegistryError::InvalidResponse(format!(
@@ -90,16 +103,20 @@ impl Registry for JsrRegistry {
             )));
         }
 
+        let cache_metadata = CacheMetadata::from_headers(response.headers());
         let meta: JsrMetaResponse = response.json().await.map_err(|e| {
             warn!("Failed to parse JSR registry response: {}", e);
             RegistryError::InvalidResponse(e.to_string())
         })?;
 
-        // Filter out yanked versions and sort by createdAt (oldest first)
-        let mut versions: Vec<(String, Option<DateTime<Utc>>)> = meta
-            .versions
+        // Set aside yanked versions and sort by createdAt (oldest first)
+        let (yanked, published): (Vec<_>, Vec<_>) =
+            meta.versions.into_iter().partition(|(_, meta)| meta.yanked);
+        let mut yanked: Vec<String> = yanked.into_iter().map(|(v, _)| v).collect();
+        yanked.sort();
+
+        let mut versions: Vec<(String, Option<DateTime<Utc>>)> = published
             .into_iter()
-            .filter(|(_, meta)| !meta.yanked)
             .map(|(v, meta)| {
                 let timestamp = meta
                     .created_at
@@ -113,7 +130,18 @@ impl Registry for JsrRegistry {
 
         let versions: Vec<String> = versions.into_iter().map(|(v, _)| v).collect();
 
-        Ok(PackageVersions::new(versions))
+        // JSR reports the latest stable version explicitly; expose it as the
+        // "latest" dist tag so it takes precedence over semver ordering
+        let dist_tags = meta
+            .latest
+            .map(|latest| HashMap::from([("latest".to_string(), latest)]))
+            .unwrap_or_default();
+
+        Ok(FetchResult::Modified(
+            PackageVersions::with_dist_tags(versions, dist_tags)
+                .with_yanked(yanked)
+                .with_cache_metadata(cache_metadata),
+        ))
     }
 }
 
@@ -148,6 +176,7 @@ mod tests {
         let result = registry.fetch_all_versions("@luca/flag").await.unwrap();
 
         mock.assert_async().await;
+        assert_eq!(result.dist_tags.get("latest"), Some(&"1.0.1".to_string()));
         assert_eq!(
             result.versions,
             vec!["1.0.0".to_string(), "1.0.1".to_string()]
@@ -185,6 +214,7 @@ mod tests {
             result.versions,
             vec!["1.0.0".to_string(), "1.0.2".to_string()]
         );
+        assert_eq!(result.yanked, vec!["1.0.1".to_string()]);
     }
 
     #[tokio::test]
@@ -206,6 +236,29 @@ mod tests {
         assert!(matches!(result, Err(RegistryError::NotFound(_))));
     }
 
+    #[tokio::test]
+    async fn fetch_all_versions_returns_rate_limited_on_429() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/@std/path/meta.json")
+            .with_status(429)
+            .with_header("retry-after", "30")
+            .create_async()
+            .await;
+
+        let registry = JsrRegistry::new(&server.url());
+        let result = registry.fetch_all_versions("@std/path").await;
+
+        mock.assert_async().await;
+        assert!(matches!(
+            result,
+            Err(RegistryError::RateLimited {
+                retry_after_secs: Some(30)
+            })
+        ));
+    }
+
     #[tokio::test]
     async fn fetch_all_versions_returns_empty_for_package_without_versions() {
         let mut server = Server::new_async().await;
@@ -1,17 +1,32 @@
 //! Registry implementations for fetching package versions
 
+pub mod conda;
 pub mod crates_io;
 pub mod docker;
 pub mod github;
 pub mod go_proxy;
+pub mod go_toolchain;
 pub mod jsr;
+pub mod multi;
+pub mod node;
 pub mod npm;
+pub mod pagination;
+pub mod pub_dev;
 pub mod pypi;
+pub mod rubygems;
+pub mod rust_toolchain;
 
+pub use conda::CondaRegistry;
 pub use crates_io::CratesIoRegistry;
 pub use docker::DockerRegistry;
 pub use github::GitHubRegistry;
 pub use go_proxy::GoProxyRegistry;
+pub use go_toolchain::GoToolchainRegistry;
 pub use jsr::JsrRegistry;
+pub use multi::MultiRegistry;
+pub use node::NodeReleaseRegistry;
 pub use npm::NpmRegistry;
+pub use pub_dev::PubRegistry;
 pub use pypi::PypiRegistry;
+pub use rubygems::RubyGemsRegistry;
+pub use rust_toolchain::RustToolchainRegistry;
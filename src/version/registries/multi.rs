@@ -0,0 +1,209 @@
+//! Registry that falls back through several registries in order
+//!
+//! Used when packages may be served by more than one registry, e.g. a
+//! private crates registry proxying some crates, with crates.io as the
+//! fallback for the rest.
+
+use std::sync::Arc;
+
+use tracing::debug;
+
+use crate::parser::types::RegistryType;
+use crate::version::error::RegistryError;
+use crate::version::registry::Registry;
+use crate::version::types::{Advisory, CacheMetadata, FetchResult, PackageVersions};
+
+/// Tries each inner registry in order and returns the first successful result
+///
+/// Only `RegistryError::NotFound` moves on to the next registry. Any other
+/// error (network failure, rate limit, invalid response) is returned right
+/// away, so an unreachable primary registry doesn't silently serve versions
+/// from a fallback.
+pub struct MultiRegistry {
+    registries: Vec<Arc<dyn Registry>>,
+}
+
+impl MultiRegistry {
+    /// Creates a MultiRegistry trying `primary` first, then each of `fallbacks`
+    pub fn new(primary: Arc<dyn Registry>, fallbacks: Vec<Arc<dyn Registry>>) -> Self {
+        let mut registries = vec![primary];
+        registries.extend(fallbacks);
+        Self { registries }
+    }
+}
+
+#[async_trait::async_trait]
+impl Registry for MultiRegistry {
+    fn registry_type(&self) -> RegistryType {
+        self.registries[0].registry_type()
+    }
+
+    async fn fetch_all_versions(
+        &self,
+        package_name: &str,
+    ) -> Result<PackageVersions, RegistryError> {
+        let mut last_error = None;
+        for (index, registry) in self.registries.iter().enumerate() {
+            match registry.fetch_all_versions(package_name).await {
+                Err(RegistryError::NotFound(name)) => {
+                    debug!("{} not found in registry #{}, trying next", name, index);
+                    last_error = Some(RegistryError::NotFound(name));
+                }
+                result => return result,
+            }
+        }
+        Err(last_error.unwrap_or_else(|| RegistryError::NotFound(package_name.to_string())))
+    }
+
+    async fn fetch_versions_if_modified(
+        &self,
+        package_name: &str,
+        cache_metadata: &CacheMetadata,
+    ) -> Result<FetchResult, RegistryError> {
+        let mut last_error = None;
+        for (index, registry) in self.registries.iter().enumerate() {
+            match registry
+                .fetch_versions_if_modified(package_name, cache_metadata)
+                .await
+            {
+                Err(RegistryError::NotFound(name)) => {
+                    debug!("{} not found in registry #{}, trying next", name, index);
+                    last_error = Some(RegistryError::NotFound(name));
+                }
+                result => return result,
+            }
+        }
+        Err(last_error.unwrap_or_else(|| RegistryError::NotFound(package_name.to_string())))
+    }
+
+    /// Advisories come from a database shared by all inner registries, so
+    /// the primary one answers
+    async fn fetch_advisories(
+        &self,
+        package_name: &str,
+        versions: &[String],
+    ) -> Option<Vec<Advisory>> {
+        self.registries[0]
+            .fetch_advisories(package_name, versions)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::registry::MockRegistry;
+
+    fn registry_returning(
+        result: fn() -> Result<PackageVersions, RegistryError>,
+        times: usize,
+    ) -> Arc<dyn Registry> {
+        let mut registry = MockRegistry::new();
+        registry
+            .expect_registry_type()
+            .returning(|| RegistryType::CratesIo);
+        registry
+            .expect_fetch_all_versions()
+            .times(times)
+            .returning(move |_| result());
+        Arc::new(registry)
+    }
+
+    fn not_found() -> Result<PackageVersions, RegistryError> {
+        Err(RegistryError::NotFound("serde".to_string()))
+    }
+
+    fn found_private() -> Result<PackageVersions, RegistryError> {
+        Ok(PackageVersions::new(vec!["1.0.0-private".to_string()]))
+    }
+
+    fn found_public() -> Result<PackageVersions, RegistryError> {
+        Ok(PackageVersions::new(vec!["1.0.0".to_string()]))
+    }
+
+    fn rate_limited() -> Result<PackageVersions, RegistryError> {
+        Err(RegistryError::RateLimited {
+            retry_after_secs: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn returns_result_of_primary_without_trying_fallbacks() {
+        let registry = MultiRegistry::new(
+            registry_returning(found_private, 1),
+            vec![registry_returning(found_public, 0)],
+        );
+
+        let result = registry.fetch_all_versions("serde").await.unwrap();
+
+        assert_eq!(result.versions, vec!["1.0.0-private"]);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_next_registry_when_not_found() {
+        let registry = MultiRegistry::new(
+            registry_returning(not_found, 1),
+            vec![
+                registry_returning(not_found, 1),
+                registry_returning(found_public, 1),
+            ],
+        );
+
+        let result = registry.fetch_all_versions("serde").await.unwrap();
+
+        assert_eq!(result.versions, vec!["1.0.0"]);
+    }
+
+    #[tokio::test]
+    async fn returns_not_found_when_no_registry_has_the_package() {
+        let registry = MultiRegistry::new(
+            registry_returning(not_found, 1),
+            vec![registry_returning(not_found, 1)],
+        );
+
+        let result = registry.fetch_all_versions("serde").await;
+
+        assert!(matches!(result, Err(RegistryError::NotFound(name)) if name == "serde"));
+    }
+
+    #[tokio::test]
+    async fn other_errors_short_circuit() {
+        let registry = MultiRegistry::new(
+            registry_returning(rate_limited, 1),
+            vec![registry_returning(found_public, 0)],
+        );
+
+        let result = registry.fetch_all_versions("serde").await;
+
+        assert!(matches!(result, Err(RegistryError::RateLimited { .. })));
+    }
+
+    #[tokio::test]
+    async fn fetch_versions_if_modified_falls_back_when_not_found() {
+        let mut primary = MockRegistry::new();
+        primary
+            .expect_fetch_versions_if_modified()
+            .times(1)
+            .returning(|name, _| Err(RegistryError::NotFound(name.to_string())));
+        let mut fallback = MockRegistry::new();
+        fallback
+            .expect_fetch_versions_if_modified()
+            .times(1)
+            .returning(|_, _| Ok(FetchResult::NotModified));
+        let registry = MultiRegistry::new(Arc::new(primary), vec![Arc::new(fallback)]);
+
+        let result = registry
+            .fetch_versions_if_modified("serde", &CacheMetadata::default())
+            .await
+            .unwrap();
+
+        assert!(matches!(result, FetchResult::NotModified));
+    }
+
+    #[test]
+    fn registry_type_is_that_of_primary() {
+        let registry = MultiRegistry::new(registry_returning(found_public, 0), vec![]);
+
+        assert_eq!(registry.registry_type(), RegistryType::CratesIo);
+    }
+}
@@ -0,0 +1,187 @@
+//! Node.js release registry using the nodejs.org distribution index
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::parser::nvmrc::NODE_PACKAGE_NAME;
+use crate::parser::types::RegistryType;
+use crate::version::error::RegistryError;
+use crate::version::registry::Registry;
+use crate::version::semver::parse_version;
+use crate::version::types::PackageVersions;
+
+/// Base URL of the Node.js distribution; the index is `{base}/index.json`
+const DEFAULT_NODE_DIST_URL: &str = "https://nodejs.org/dist";
+
+/// Node.js release registry client
+pub struct NodeReleaseRegistry {
+    client: Client,
+    base_url: String,
+}
+
+impl Default for NodeReleaseRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_NODE_DIST_URL.to_string())
+    }
+}
+
+impl NodeReleaseRegistry {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+/// One release in `index.json`
+#[derive(Debug, Deserialize)]
+struct NodeRelease {
+    /// Release version with a `v` prefix (`v20.11.0`)
+    version: String,
+    /// LTS codename (`"Iron"`), or `false` for non-LTS releases
+    #[serde(default)]
+    lts: serde_json::Value,
+}
+
+impl NodeRelease {
+    fn lts_codename(&self) -> Option<&str> {
+        self.lts.as_str()
+    }
+}
+
+/// Build dist tags from the releases
+///
+/// - `latest`: the newest LTS release, which pinned versions are compared to
+/// - `current`: the newest release of any line
+/// - `lts/*` and `lts/<codename>` (lowercase): the newest release of all LTS
+///   lines or of one LTS line, as resolved by nvm
+fn dist_tags(releases: &[NodeRelease]) -> HashMap<String, String> {
+    let mut newest: HashMap<String, (semver::Version, String)> = HashMap::new();
+    let mut record = |tag: String, version: &str| {
+        let Some(parsed) = parse_version(version) else {
+            return;
+        };
+        let is_newer = newest
+            .get(&tag)
+            .is_none_or(|(existing, _)| parsed > *existing);
+        if is_newer {
+            newest.insert(tag, (parsed, version.to_string()));
+        }
+    };
+
+    for release in releases {
+        let version = release.version.trim_start_matches('v');
+        record("current".to_string(), version);
+        if let Some(codename) = release.lts_codename() {
+            record("latest".to_string(), version);
+            record("lts/*".to_string(), version);
+            record(format!("lts/{}", codename.to_lowercase()), version);
+        }
+    }
+
+    newest
+        .into_iter()
+        .map(|(tag, (_, version))| (tag, version))
+        .collect()
+}
+
+#[async_trait]
+impl Registry for NodeReleaseRegistry {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::NodeRelease
+    }
+
+    async fn fetch_all_versions(
+        &self,
+        package_name: &str,
+    ) -> Result<PackageVersions, RegistryError> {
+        // The index only lists Node.js itself
+        if package_name != NODE_PACKAGE_NAME {
+            return Err(RegistryError::NotFound(package_name.to_string()));
+        }
+
+        let url = format!("{}/index.json", self.base_url);
+        debug!("Fetching Node.js releases: {}", url);
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(RegistryError::InvalidResponse(format!(
+                "nodejs.org returned status {}",
+                response.status()
+            )));
+        }
+
+        let releases: Vec<NodeRelease> = response
+            .json()
+            .await
+            .map_err(|e| RegistryError::InvalidResponse(e.to_string()))?;
+
+        debug!("Found {} Node.js releases", releases.len());
+
+        let dist_tags = dist_tags(&releases);
+        let versions = releases
+            .into_iter()
+            .map(|release| release.version.trim_start_matches('v').to_string())
+            .collect();
+
+        // index.json lists the newest release first
+        let versions = PackageVersions::sorted_by_semver(versions).versions;
+        Ok(PackageVersions::with_dist_tags(versions, dist_tags))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_releases_and_lts_tags() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/index.json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {"version": "v21.6.0", "date": "2024-01-14", "lts": false},
+                    {"version": "v20.11.0", "date": "2024-01-09", "lts": "Iron"},
+                    {"version": "v20.10.0", "date": "2023-11-22", "lts": "Iron"},
+                    {"version": "v18.19.0", "date": "2023-11-29", "lts": "Hydrogen"},
+                    {"version": "v18.17.0", "date": "2023-07-18", "lts": "Hydrogen"}
+                ]"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = NodeReleaseRegistry::new(format!("{}/", server.url()));
+        let result = registry.fetch_all_versions("node").await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(
+            result.versions,
+            vec!["18.17.0", "18.19.0", "20.10.0", "20.11.0", "21.6.0"]
+        );
+        let tag = |name: &str| result.dist_tags.get(name).map(String::as_str);
+        assert_eq!(tag("latest"), Some("20.11.0"));
+        assert_eq!(tag("current"), Some("21.6.0"));
+        assert_eq!(tag("lts/*"), Some("20.11.0"));
+        assert_eq!(tag("lts/iron"), Some("20.11.0"));
+        assert_eq!(tag("lts/hydrogen"), Some("18.19.0"));
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_not_found_for_other_packages() {
+        let registry = NodeReleaseRegistry::new("http://127.0.0.1:1".to_string());
+
+        let result = registry.fetch_all_versions("deno").await;
+
+        assert!(matches!(result, Err(RegistryError::NotFound(_))));
+    }
+}
@@ -5,15 +5,28 @@ use std::collections::HashMap;
 use crate::parser::types::RegistryType;
 use crate::version::error::RegistryError;
 use crate::version::registry::Registry;
-use crate::version::types::PackageVersions;
+use crate::version::semver::parse_version;
+use crate::version::types::{CacheMetadata, FetchResult, PackageMetadata, PackageVersions};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
-use tracing::warn;
+use tracing::{debug, warn};
 
 /// Default base URL for npm registry
 const DEFAULT_BASE_URL: &str = "https://registry.npmjs.org";
 
+/// Media type of the abbreviated ("corgi") package document used by npm v7+
+///
+/// Only carries versions, dist-tags and install-time fields, so it is a
+/// fraction of the size of the full document for packages with many releases.
+/// The homepage and repository links are looked up once from the full
+/// document instead (see [`NpmRegistry::fetch_metadata`]).
+const ABBREVIATED_METADATA_ACCEPT: &str =
+    "application/vnd.npm.install-v1+json; q=1.0, application/json; q=0.8";
+
 /// Response from npm registry API
+///
+/// Abbreviated documents have no `time`, `homepage` or `repository`; those
+/// fields are only filled when a registry answers with the full document.
 #[derive(Debug, Deserialize)]
 struct NpmPackageResponse {
     versions: HashMap<String, serde_json::Value>,
@@ -22,6 +35,72 @@ struct NpmPackageResponse {
     /// Version publish timestamps (version -> ISO 8601 timestamp)
     #[serde(default)]
     time: HashMap<String, String>,
+    #[serde(default)]
+    homepage: Option<String>,
+    #[serde(default)]
+    repository: Option<NpmRepository>,
+}
+
+/// Links of the full package document, which the abbreviated one leaves out
+#[derive(Debug, Deserialize)]
+struct NpmPackageLinks {
+    #[serde(default)]
+    homepage: Option<String>,
+    #[serde(default)]
+    repository: Option<NpmRepository>,
+}
+
+/// `repository` field: either a shorthand string or `{ "type", "url" }`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum NpmRepository {
+    Shorthand(String),
+    Object { url: Option<String> },
+}
+
+impl NpmRepository {
+    fn url(&self) -> Option<&str> {
+        match self {
+            NpmRepository::Shorthand(url) => Some(url),
+            NpmRepository::Object { url } => url.as_deref(),
+        }
+    }
+}
+
+/// Normalize a repository URL to a browsable https URL
+///
+/// Handles `git+https://github.com/o/r.git`, `git://github.com/o/r.git`,
+/// `git@github.com:o/r.git`, `github:o/r` and the bare `o/r` shorthand.
+fn normalize_repository_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    let url = url.strip_prefix("git+").unwrap_or(url);
+    let url = url.strip_suffix(".git").unwrap_or(url);
+
+    let normalized = if let Some(rest) = url.strip_prefix("git://") {
+        format!("https://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("ssh://git@") {
+        format!("https://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("git@") {
+        format!("https://{}", rest.replacen(':', "/", 1))
+    } else if let Some(rest) = url.strip_prefix("github:") {
+        format!("https://github.com/{}", rest)
+    } else if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else if !url.contains(':') && url.split('/').count() == 2 {
+        format!("https://github.com/{}", url)
+    } else {
+        return None;
+    };
+
+    Some(normalized)
+}
+
+/// Registry serving the packages of one scope (`@scope:registry` in `.npmrc`)
+#[derive(Clone)]
+pub struct ScopeRegistry {
+    pub base_url: String,
+    /// Sent as a bearer token, like `_authToken` in `.npmrc`
+    pub auth_token: Option<String>,
 }
 
 /// Registry implementation for npm registry API
@@ -29,6 +108,8 @@ struct NpmPackageResponse {
 pub struct NpmRegistry {
     client: reqwest::Client,
     base_url: String,
+    /// Registries for scoped packages, keyed by scope (`@mycompany`)
+    scope_registries: HashMap<String, ScopeRegistry>,
 }
 
 impl NpmRegistry {
@@ -40,7 +121,55 @@ impl NpmRegistry {
                 .build()
                 .expect("Failed to create HTTP client"),
             base_url: base_url.to_string(),
+            scope_registries: HashMap::new(),
+        }
+    }
+
+    /// Fetch scoped packages from their own registries instead of the base URL
+    ///
+    /// Scopes may be given with or without the leading `@`.
+    pub fn with_scope_registries(
+        mut self,
+        scope_registries: HashMap<String, ScopeRegistry>,
+    ) -> Self {
+        self.scope_registries = scope_registries
+            .into_iter()
+            .map(|(scope, registry)| {
+                let scope = format!("@{}", scope.trim_start_matches('@'));
+                let registry = ScopeRegistry {
+                    base_url: registry.base_url.trim_end_matches('/').to_string(),
+                    ..registry
+                };
+                (scope, registry)
+            })
+            .collect();
+        self
+    }
+
+    /// Base URL and auth token for a package, from its scope registry if one is configured
+    fn registry_for(&self, package_name: &str) -> (&str, Option<&str>) {
+        package_name
+            .split_once('/')
+            .and_then(|(scope, _)| self.scope_registries.get(scope))
+            .map_or((self.base_url.as_str(), None), |registry| {
+                (registry.base_url.as_str(), registry.auth_token.as_deref())
+            })
+    }
+
+    /// Send a GET request for a package document with the given `Accept` header
+    async fn send_request(
+        &self,
+        url: &str,
+        auth_token: Option<&str>,
+        cache_metadata: &CacheMetadata,
+        accept: &str,
+    ) -> Result<reqwest::Response, RegistryError> {
+        let mut request = self.client.get(url).header(reqwest::header::ACCEPT, accept);
+        if let Some(token) = auth_token {
+            request = request.bearer_auth(token);
         }
+        request = cache_metadata.apply(request);
+        Ok(request.send().await?)
     }
 
     /// Encode package name for URL (handles scoped packages)
@@ -70,17 +199,55 @@ impl Registry for NpmRegistry {
         &self,
         package_name: &str,
     ) -> Result<PackageVersions, RegistryError> {
-        let encoded_name = Self::encode_package_name(package_name);
-        let url = format!("{}/{}", self.base_url, encoded_name);
+        self.fetch_versions_if_modified(package_name, &CacheMetadata::default())
+            .await?
+            .into_versions()
+    }
 
-        let response = self.client.get(&url).send().await?;
+    async fn fetch_versions_if_modified(
+        &self,
+        package_name: &str,
+        cache_metadata: &CacheMetadata,
+    ) -> Result<FetchResult, RegistryError> {
+        let encoded_name = Self::encode_package_name(package_name);
+        let (base_url, auth_token) = self.registry_for(package_name);
+        let url = format!("{}/{}", base_url, encoded_name);
+
+        let mut response = self
+            .send_request(
+                &url,
+                auth_token,
+                cache_metadata,
+                ABBREVIATED_METADATA_ACCEPT,
+            )
+            .await?;
+
+        // Registries that cannot serve the abbreviated document reject the
+        // Accept header; fall back to the full document
+        if response.status() == reqwest::StatusCode::NOT_ACCEPTABLE {
+            debug!(
+                "npm registry does not serve abbreviated metadata, fetching full document: {}",
+                url
+            );
+            response = self
+                .send_request(&url, auth_token, cache_metadata, "application/json")
+                .await?;
+        }
 
         let status = response.status();
 
+        if status == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(FetchResult::NotModified);
+        }
+
         if status == reqwest::StatusCode::NOT_FOUND {
             return Err(RegistryError::NotFound(package_name.to_string()));
         }
 
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(RegistryError::rate_limited(response.headers()));
+        }
+
         if !status.is_success() {
             warn!("npm registry returned status {}: {}", status, url);
             return Err(RegistryError::InvalidResponse(format!(
@@ -89,16 +256,49 @@ impl Registry for NpmRegistry {
             )));
         }
 
+        let cache_metadata = CacheMetadata::from_headers(response.headers());
         let package_info: NpmPackageResponse = response.json().await.map_err(|e| {
             warn!("Failed to parse npm registry response: {}", e);
             RegistryError::InvalidResponse(e.to_string())
         })?;
 
+        // npm deprecates a whole package by deprecating its versions, so the
+        // package counts as deprecated when its latest version is
+        let deprecated = package_info
+            .dist_tags
+            .get("latest")
+            .and_then(|latest| package_info.versions.get(latest))
+            .and_then(|manifest| manifest.get("deprecated"))
+            .and_then(serde_json::Value::as_str)
+            .filter(|message| !message.is_empty())
+            .map(String::from);
+
+        // Deprecating single versions soft-yanks them. Once the package itself
+        // is deprecated, its versions stay and the deprecation is reported
+        let mut yanked: Vec<String> = if deprecated.is_some() {
+            Vec::new()
+        } else {
+            package_info
+                .versions
+                .iter()
+                .filter(|(_, manifest)| {
+                    manifest
+                        .get("deprecated")
+                        .and_then(serde_json::Value::as_str)
+                        .is_some_and(|message| !message.is_empty())
+                })
+                .map(|(version, _)| version.clone())
+                .collect()
+        };
+        yanked.sort_by_key(|version| parse_version(version));
+
         // Sort versions by publish date (oldest first, newest last)
-        // Versions without timestamps are placed at the beginning
+        // Versions without timestamps are placed at the beginning, in semver
+        // order, which covers abbreviated documents that carry no timestamps
         let mut versions: Vec<(String, Option<DateTime<Utc>>)> = package_info
             .versions
             .into_keys()
+            .filter(|v| !yanked.contains(v))
             .map(|v| {
                 let timestamp = package_info
                     .time
@@ -109,21 +309,78 @@ impl Registry for NpmRegistry {
             })
             .collect();
 
-        versions.sort_by_key(|(_, a)| *a);
+        versions.sort_by(|(a, a_time), (b, b_time)| {
+            a_time
+                .cmp(b_time)
+                .then_with(|| parse_version(a).cmp(&parse_version(b)))
+        });
 
         let versions: Vec<String> = versions.into_iter().map(|(v, _)| v).collect();
 
-        Ok(PackageVersions::with_dist_tags(
-            versions,
-            package_info.dist_tags,
+        let repository = package_info
+            .repository
+            .as_ref()
+            .and_then(NpmRepository::url)
+            .and_then(normalize_repository_url);
+
+        Ok(FetchResult::Modified(
+            PackageVersions::with_dist_tags(versions, package_info.dist_tags)
+                .with_metadata(package_info.homepage, repository)
+                .with_deprecated(deprecated)
+                .with_yanked(yanked)
+                .with_cache_metadata(cache_metadata),
         ))
     }
+
+    /// Fetch the full document for the homepage and repository links the
+    /// abbreviated document leaves out
+    async fn fetch_metadata(&self, package_name: &str) -> Option<PackageMetadata> {
+        let encoded_name = Self::encode_package_name(package_name);
+        let (base_url, auth_token) = self.registry_for(package_name);
+        let url = format!("{}/{}", base_url, encoded_name);
+
+        let response = self
+            .send_request(
+                &url,
+                auth_token,
+                &CacheMetadata::default(),
+                "application/json",
+            )
+            .await
+            .inspect_err(|e| warn!("Failed to fetch npm package links: {}", e))
+            .ok()?;
+
+        if !response.status().is_success() {
+            warn!(
+                "npm registry returned status {}: {}",
+                response.status(),
+                url
+            );
+            return None;
+        }
+
+        let links: NpmPackageLinks = response
+            .json()
+            .await
+            .inspect_err(|e| warn!("Failed to parse npm registry response: {}", e))
+            .ok()?;
+
+        Some(PackageMetadata {
+            homepage: links.homepage,
+            repository: links
+                .repository
+                .as_ref()
+                .and_then(NpmRepository::url)
+                .and_then(normalize_repository_url),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use mockito::Server;
+    use rstest::rstest;
 
     #[tokio::test]
     async fn fetch_all_versions_returns_versions_sorted_by_time() {
@@ -166,6 +423,267 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn fetch_all_versions_requests_abbreviated_metadata() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/lodash")
+            .match_header(
+                "accept",
+                mockito::Matcher::Regex("^application/vnd\\.npm\\.install-v1\\+json".to_string()),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/vnd.npm.install-v1+json")
+            .with_body(
+                r#"{
+                    "name": "lodash",
+                    "modified": "2021-02-20T15:42:16.891Z",
+                    "dist-tags": { "latest": "4.17.21" },
+                    "versions": {
+                        "4.17.21": { "name": "lodash", "version": "4.17.21" },
+                        "4.17.9": { "name": "lodash", "version": "4.17.9" },
+                        "4.17.20": { "name": "lodash", "version": "4.17.20" }
+                    }
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = NpmRegistry::new(&server.url());
+        let result = registry.fetch_all_versions("lodash").await.unwrap();
+
+        mock.assert_async().await;
+        // Abbreviated documents have no publish times, so versions are in semver order
+        assert_eq!(result.versions, vec!["4.17.9", "4.17.20", "4.17.21"]);
+        assert_eq!(result.dist_tags.get("latest"), Some(&"4.17.21".to_string()));
+        assert_eq!(result.homepage, None);
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_falls_back_to_full_metadata_on_406() {
+        let mut server = Server::new_async().await;
+
+        let abbreviated_mock = server
+            .mock("GET", "/lodash")
+            .match_header(
+                "accept",
+                mockito::Matcher::Regex("application/vnd\\.npm\\.install-v1\\+json".to_string()),
+            )
+            .with_status(406)
+            .create_async()
+            .await;
+        let full_mock = server
+            .mock("GET", "/lodash")
+            .match_header("accept", "application/json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "versions": { "4.17.20": {}, "4.17.21": {} },
+                    "dist-tags": { "latest": "4.17.21" },
+                    "homepage": "https://lodash.com/"
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = NpmRegistry::new(&server.url());
+        let result = registry.fetch_all_versions("lodash").await.unwrap();
+
+        abbreviated_mock.assert_async().await;
+        full_mock.assert_async().await;
+        assert_eq!(result.versions, vec!["4.17.20", "4.17.21"]);
+        assert_eq!(result.homepage, Some("https://lodash.com/".to_string()));
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_sends_exact_accept_header_and_json_on_retry() {
+        let mut server = Server::new_async().await;
+
+        let abbreviated_mock = server
+            .mock("GET", "/lodash")
+            .match_header("accept", ABBREVIATED_METADATA_ACCEPT)
+            .with_status(406)
+            .expect(1)
+            .create_async()
+            .await;
+        let full_mock = server
+            .mock("GET", "/lodash")
+            .match_header("accept", "application/json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "versions": { "4.17.21": {} } }"#)
+            .expect(1)
+            .create_async()
+            .await;
+        // Requests without the expected Accept header fail the fetch
+        let unexpected_mock = server
+            .mock("GET", "/lodash")
+            .with_status(500)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let registry = NpmRegistry::new(&server.url());
+        let result = registry.fetch_all_versions("lodash").await.unwrap();
+
+        abbreviated_mock.assert_async().await;
+        full_mock.assert_async().await;
+        unexpected_mock.assert_async().await;
+        assert_eq!(result.versions, vec!["4.17.21"]);
+    }
+
+    #[tokio::test]
+    async fn fetch_metadata_reads_links_from_full_document() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/@types%2Fnode")
+            .match_header("accept", "application/json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "name": "@types/node",
+                    "versions": { "20.0.0": {} },
+                    "homepage": "https://github.com/DefinitelyTyped/DefinitelyTyped",
+                    "repository": "github:DefinitelyTyped/DefinitelyTyped"
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = NpmRegistry::new(&server.url());
+        let metadata = registry.fetch_metadata("@types/node").await;
+
+        mock.assert_async().await;
+        assert_eq!(
+            metadata,
+            Some(PackageMetadata {
+                homepage: Some("https://github.com/DefinitelyTyped/DefinitelyTyped".to_string()),
+                repository: Some("https://github.com/DefinitelyTyped/DefinitelyTyped".to_string()),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_metadata_returns_none_on_error_status() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/lodash")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let registry = NpmRegistry::new(&server.url());
+
+        assert_eq!(registry.fetch_metadata("lodash").await, None);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn fetch_versions_if_modified_returns_validators_of_response() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/lodash")
+            .match_header("if-none-match", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header("etag", "W/\"abc123\"")
+            .with_header("last-modified", "Wed, 01 Jan 2025 00:00:00 GMT")
+            .with_body(r#"{"versions": {"4.17.21": {}}}"#)
+            .create_async()
+            .await;
+
+        let registry = NpmRegistry::new(&server.url());
+        let result = registry
+            .fetch_versions_if_modified("lodash", &CacheMetadata::default())
+            .await
+            .unwrap()
+            .into_versions()
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(
+            result.cache_metadata,
+            CacheMetadata {
+                etag: Some("W/\"abc123\"".to_string()),
+                last_modified: Some("Wed, 01 Jan 2025 00:00:00 GMT".to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_versions_if_modified_returns_not_modified_for_304() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/lodash")
+            .match_header("if-none-match", "W/\"abc123\"")
+            .match_header("if-modified-since", "Wed, 01 Jan 2025 00:00:00 GMT")
+            .with_status(304)
+            .create_async()
+            .await;
+
+        let registry = NpmRegistry::new(&server.url());
+        let cache_metadata = CacheMetadata {
+            etag: Some("W/\"abc123\"".to_string()),
+            last_modified: Some("Wed, 01 Jan 2025 00:00:00 GMT".to_string()),
+        };
+        let result = registry
+            .fetch_versions_if_modified("lodash", &cache_metadata)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(result, FetchResult::NotModified);
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_uses_scope_registry_with_auth_token() {
+        let mut default_server = Server::new_async().await;
+        let mut scope_server = Server::new_async().await;
+
+        let scoped_mock = scope_server
+            .mock("GET", "/@mycompany%2Fcore")
+            .match_header("authorization", "Bearer secret-token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"versions": {"1.0.0": {}}}"#)
+            .create_async()
+            .await;
+        let default_mock = default_server
+            .mock("GET", "/@types%2Fnode")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"versions": {"20.0.0": {}}}"#)
+            .create_async()
+            .await;
+
+        let registry =
+            NpmRegistry::new(&default_server.url()).with_scope_registries(HashMap::from([(
+                "mycompany".to_string(),
+                ScopeRegistry {
+                    base_url: format!("{}/", scope_server.url()),
+                    auth_token: Some("secret-token".to_string()),
+                },
+            )]));
+        let scoped = registry
+            .fetch_all_versions("@mycompany/core")
+            .await
+            .unwrap();
+        let unscoped = registry.fetch_all_versions("@types/node").await.unwrap();
+
+        scoped_mock.assert_async().await;
+        default_mock.assert_async().await;
+        assert_eq!(scoped.versions, vec!["1.0.0"]);
+        assert_eq!(unscoped.versions, vec!["20.0.0"]);
+    }
+
     #[tokio::test]
     async fn fetch_all_versions_returns_not_found_for_nonexistent_package() {
         let mut server = Server::new_async().await;
@@ -331,4 +849,133 @@ mod tests {
             Some(&"5.0.0-beta.1".to_string())
         );
     }
+
+    #[rstest]
+    #[case(
+        r#""request has been deprecated""#,
+        Some("request has been deprecated")
+    )]
+    #[case(r#""""#, None)]
+    #[case("false", None)]
+    #[tokio::test]
+    async fn fetch_all_versions_returns_deprecation_of_latest_version(
+        #[case] deprecated: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/request")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{
+                    "versions": {{
+                        "2.88.1": {{}},
+                        "2.88.2": {{ "deprecated": {} }}
+                    }},
+                    "dist-tags": {{ "latest": "2.88.2" }}
+                }}"#,
+                deprecated
+            ))
+            .create_async()
+            .await;
+
+        let registry = NpmRegistry::new(&server.url());
+        let result = registry.fetch_all_versions("request").await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(result.deprecated.as_deref(), expected);
+        // A deprecated package keeps its deprecated versions
+        assert_eq!(result.versions, vec!["2.88.1", "2.88.2"]);
+        assert!(result.yanked.is_empty());
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_soft_yanks_deprecated_versions() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/left-pad")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "versions": {
+                        "1.0.0": {},
+                        "1.1.0": { "deprecated": "critical bug, use 1.1.1" },
+                        "1.1.1": {}
+                    },
+                    "dist-tags": { "latest": "1.1.1" }
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = NpmRegistry::new(&server.url());
+        let result = registry.fetch_all_versions("left-pad").await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(result.versions, vec!["1.0.0", "1.1.1"]);
+        assert_eq!(result.yanked, vec!["1.1.0"]);
+        assert_eq!(result.deprecated, None);
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_homepage_and_repository() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/lodash")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "name": "lodash",
+                    "versions": { "4.17.21": {} },
+                    "homepage": "https://lodash.com/",
+                    "repository": {
+                        "type": "git",
+                        "url": "git+https://github.com/lodash/lodash.git"
+                    }
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = NpmRegistry::new(&server.url());
+        let result = registry.fetch_all_versions("lodash").await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(result.homepage, Some("https://lodash.com/".to_string()));
+        assert_eq!(
+            result.repository,
+            Some("https://github.com/lodash/lodash".to_string())
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "git+https://github.com/lodash/lodash.git",
+        Some("https://github.com/lodash/lodash")
+    )]
+    #[case(
+        "git://github.com/owner/repo.git",
+        Some("https://github.com/owner/repo")
+    )]
+    #[case(
+        "git+ssh://git@github.com/owner/repo.git",
+        Some("https://github.com/owner/repo")
+    )]
+    #[case("git@github.com:owner/repo.git", Some("https://github.com/owner/repo"))]
+    #[case("github:owner/repo", Some("https://github.com/owner/repo"))]
+    #[case("owner/repo", Some("https://github.com/owner/repo"))]
+    #[case("https://gitlab.com/owner/repo", Some("https://gitlab.com/owner/repo"))]
+    #[case("gitlab:owner/repo", None)]
+    fn normalize_repository_url_returns_expected(
+        #[case] url: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(normalize_repository_url(url).as_deref(), expected);
+    }
 }
@@ -0,0 +1,66 @@
+//! Helpers for paginated registry APIs
+
+use reqwest::header::{HeaderMap, LINK};
+
+/// Parse an RFC 8288 `Link` header and return the URL with the given `rel`
+///
+/// `<https://api.github.com/repos/o/r/tags?page=2>; rel="next"` yields the
+/// URL for `rel = "next"`. Returns None if no link has that relation.
+pub fn parse_link_header(value: &str, rel: &str) -> Option<String> {
+    value.split(',').find_map(|link| {
+        let mut parts = link.split(';');
+        let url = parts.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+        parts
+            .filter_map(|param| param.trim().strip_prefix("rel="))
+            .any(|rels| rels.trim_matches('"').split_whitespace().any(|r| r == rel))
+            .then(|| url.to_string())
+    })
+}
+
+/// URL of the next page from the `Link` header of a response, if any
+pub fn next_page_url(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(LINK)?.to_str().ok()?;
+    parse_link_header(value, "next")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        r#"<https://api.github.com/repos/o/r/tags?page=2>; rel="next", <https://api.github.com/repos/o/r/tags?page=5>; rel="last""#,
+        "next",
+        Some("https://api.github.com/repos/o/r/tags?page=2")
+    )]
+    #[case(
+        r#"<https://api.github.com/repos/o/r/tags?page=2>; rel="next", <https://api.github.com/repos/o/r/tags?page=5>; rel="last""#,
+        "last",
+        Some("https://api.github.com/repos/o/r/tags?page=5")
+    )]
+    #[case(
+        r#"<https://api.github.com/repos/o/r/tags?page=1>; rel="prev first""#,
+        "first",
+        Some("https://api.github.com/repos/o/r/tags?page=1")
+    )]
+    #[case(
+        r#"<https://api.github.com/repos/o/r/tags?page=1>; rel="prev""#,
+        "next",
+        None
+    )]
+    #[case("", "next", None)]
+    #[case("not a link", "next", None)]
+    fn parse_link_header_returns_url_of_relation(
+        #[case] value: &str,
+        #[case] rel: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(parse_link_header(value, rel).as_deref(), expected);
+    }
+
+    #[test]
+    fn next_page_url_returns_none_without_link_header() {
+        assert_eq!(next_page_url(&HeaderMap::new()), None);
+    }
+}
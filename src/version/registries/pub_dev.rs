@@ -0,0 +1,165 @@
+//! pub.dev registry client for fetching Dart package versions
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::parser::types::RegistryType;
+use crate::version::error::RegistryError;
+use crate::version::registry::Registry;
+use crate::version::types::PackageVersions;
+
+const DEFAULT_PUB_REGISTRY: &str = "https://pub.dev";
+
+/// pub.dev registry client
+pub struct PubRegistry {
+    client: Client,
+    base_url: String,
+}
+
+impl Default for PubRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_PUB_REGISTRY.to_string())
+    }
+}
+
+impl PubRegistry {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url,
+        }
+    }
+}
+
+/// pub.dev package API response structure
+#[derive(Debug, Deserialize)]
+struct PubPackageResponse {
+    latest: PubVersion,
+    versions: Vec<PubVersion>,
+}
+
+/// Version entry of a package
+#[derive(Debug, Deserialize)]
+struct PubVersion {
+    version: String,
+    #[serde(default)]
+    retracted: bool,
+}
+
+#[async_trait]
+impl Registry for PubRegistry {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::Pub
+    }
+
+    async fn fetch_all_versions(
+        &self,
+        package_name: &str,
+    ) -> Result<PackageVersions, RegistryError> {
+        let url = format!("{}/api/packages/{}", self.base_url, package_name);
+        debug!("Fetching pub.dev package: {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Accept", "application/vnd.pub.v2+json")
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(RegistryError::NotFound(package_name.to_string()));
+        }
+
+        if !response.status().is_success() {
+            return Err(RegistryError::InvalidResponse(format!(
+                "pub.dev API returned status {}",
+                response.status()
+            )));
+        }
+
+        let package: PubPackageResponse = response
+            .json()
+            .await
+            .map_err(|e| RegistryError::InvalidResponse(e.to_string()))?;
+
+        // Versions are listed oldest first; retracted versions are set aside
+        // like yanked ones
+        let (retracted, versions): (Vec<_>, Vec<_>) = package
+            .versions
+            .into_iter()
+            .partition(|version| version.retracted);
+        let versions: Vec<String> = versions.into_iter().map(|v| v.version).collect();
+        let retracted: Vec<String> = retracted.into_iter().map(|v| v.version).collect();
+
+        let mut dist_tags = HashMap::new();
+        dist_tags.insert("latest".to_string(), package.latest.version);
+
+        debug!(
+            "Found {} versions for package {}",
+            versions.len(),
+            package_name
+        );
+
+        Ok(PackageVersions::with_dist_tags(versions, dist_tags).with_yanked(retracted))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_versions_and_latest() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/packages/http")
+            .match_header("accept", "application/vnd.pub.v2+json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "name": "http",
+                    "latest": {"version": "1.2.2"},
+                    "versions": [
+                        {"version": "1.1.0"},
+                        {"version": "1.2.1", "retracted": true},
+                        {"version": "1.2.2"},
+                        {"version": "1.3.0-beta.1"}
+                    ]
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = PubRegistry::new(server.url());
+        let result = registry.fetch_all_versions("http").await.unwrap();
+
+        mock.assert_async().await;
+
+        assert_eq!(result.versions, vec!["1.1.0", "1.2.2", "1.3.0-beta.1"]);
+        assert_eq!(result.yanked, vec!["1.2.1"]);
+        assert_eq!(result.dist_tags.get("latest"), Some(&"1.2.2".to_string()));
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_not_found_for_missing_package() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/packages/nonexistent")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let registry = PubRegistry::new(server.url());
+        let result = registry.fetch_all_versions("nonexistent").await;
+
+        mock.assert_async().await;
+
+        assert!(matches!(result, Err(RegistryError::NotFound(_))));
+    }
+}
@@ -10,7 +10,7 @@ use tracing::debug;
 use crate::parser::types::RegistryType;
 use crate::version::error::RegistryError;
 use crate::version::registry::Registry;
-use crate::version::types::PackageVersions;
+use crate::version::types::{CacheMetadata, FetchResult, PackageVersions};
 
 const DEFAULT_PYPI_REGISTRY: &str = "https://pypi.org";
 
@@ -49,11 +49,11 @@ struct PypiInfo {
     version: String,
 }
 
-/// File information (not used currently but needed for deserialization)
+/// File of a release
 #[derive(Debug, Deserialize)]
 struct PypiFile {
-    // We don't need any fields, just need the structure to exist
-    // The releases keys are what we care about
+    #[serde(default)]
+    yanked: bool,
 }
 
 #[async_trait]
@@ -66,10 +66,25 @@ impl Registry for PypiRegistry {
         &self,
         package_name: &str,
     ) -> Result<PackageVersions, RegistryError> {
+        self.fetch_versions_if_modified(package_name, &CacheMetadata::default())
+            .await?
+            .into_versions()
+    }
+
+    async fn fetch_versions_if_modified(
+        &self,
+        package_name: &str,
+        cache_metadata: &CacheMetadata,
+    ) -> Result<FetchResult, RegistryError> {
         let url = format!("{}/pypi/{}/json", self.base_url, package_name);
         debug!("Fetching PyPI package: {}", url);
 
-        let response = self.client.get(&url).send().await?;
+        let request = self.client.get(&url);
+        let response = cache_metadata.apply(request).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(FetchResult::NotModified);
+        }
 
         if response.status() == reqwest::StatusCode::NOT_FOUND {
             return Err(RegistryError::NotFound(package_name.to_string()));
@@ -82,13 +97,21 @@ impl Registry for PypiRegistry {
             )));
         }
 
+        let cache_metadata = CacheMetadata::from_headers(response.headers());
         let pypi_response: PypiResponse = response
             .json()
             .await
             .map_err(|e| RegistryError::InvalidResponse(e.to_string()))?;
 
-        // Extract versions from releases keys
-        let versions: Vec<String> = pypi_response.releases.keys().cloned().collect();
+        // Extract versions from releases keys, setting aside releases whose
+        // files are all yanked
+        let (yanked, versions): (Vec<_>, Vec<_>) = pypi_response
+            .releases
+            .into_iter()
+            .partition(|(_, files)| !files.is_empty() && files.iter().all(|file| file.yanked));
+        let versions: Vec<String> = versions.into_iter().map(|(version, _)| version).collect();
+        let mut yanked: Vec<String> = yanked.into_iter().map(|(version, _)| version).collect();
+        yanked.sort();
 
         // Create dist-tags with "latest" pointing to info.version
         let mut dist_tags = HashMap::new();
@@ -100,7 +123,11 @@ impl Registry for PypiRegistry {
             package_name
         );
 
-        Ok(PackageVersions::with_dist_tags(versions, dist_tags))
+        Ok(FetchResult::Modified(
+            PackageVersions::with_dist_tags(versions, dist_tags)
+                .with_yanked(yanked)
+                .with_cache_metadata(cache_metadata),
+        ))
     }
 }
 
@@ -140,6 +167,37 @@ mod tests {
         assert_eq!(result.dist_tags.get("latest"), Some(&"2.32.5".to_string()));
     }
 
+    #[tokio::test]
+    async fn fetch_all_versions_sets_aside_releases_with_only_yanked_files() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/pypi/requests/json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "info": {"version": "2.32.5"},
+                    "releases": {
+                        "2.32.0": [{"yanked": true}, {"yanked": false}],
+                        "2.32.1": [{"yanked": true}],
+                        "2.32.5": [{"yanked": false}]
+                    }
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = PypiRegistry::new(server.url());
+        let result = registry.fetch_all_versions("requests").await.unwrap();
+
+        mock.assert_async().await;
+
+        let mut versions = result.versions;
+        versions.sort();
+        assert_eq!(versions, vec!["2.32.0", "2.32.5"]);
+        assert_eq!(result.yanked, vec!["2.32.1"]);
+    }
+
     #[tokio::test]
     async fn fetch_all_versions_returns_not_found_for_missing_package() {
         let mut server = Server::new_async().await;
@@ -187,6 +245,8 @@ mod tests {
         let mut versions = result.versions;
         versions.sort();
         assert_eq!(versions, vec!["4.1.0", "4.2.0", "5.0a1", "5.0b1", "5.0rc1"]);
+        // Pre-releases are stored, but the latest stays the stable info.version
+        assert_eq!(result.dist_tags.get("latest"), Some(&"4.2.0".to_string()));
     }
 
     #[tokio::test]
@@ -0,0 +1,173 @@
+//! RubyGems registry client using the rubygems.org versions API
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::parser::types::RegistryType;
+use crate::version::error::RegistryError;
+use crate::version::registry::Registry;
+use crate::version::types::PackageVersions;
+
+const DEFAULT_RUBYGEMS_REGISTRY: &str = "https://rubygems.org";
+
+/// RubyGems registry client
+pub struct RubyGemsRegistry {
+    client: Client,
+    base_url: String,
+}
+
+impl Default for RubyGemsRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_RUBYGEMS_REGISTRY.to_string())
+    }
+}
+
+impl RubyGemsRegistry {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url,
+        }
+    }
+}
+
+/// Version entry of the `/api/v1/versions/{gem}.json` response
+#[derive(Debug, Deserialize)]
+struct RubyGemsVersion {
+    number: String,
+    #[serde(default)]
+    prerelease: bool,
+}
+
+#[async_trait]
+impl Registry for RubyGemsRegistry {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::RubyGems
+    }
+
+    async fn fetch_all_versions(
+        &self,
+        package_name: &str,
+    ) -> Result<PackageVersions, RegistryError> {
+        let url = format!("{}/api/v1/versions/{}.json", self.base_url, package_name);
+        debug!("Fetching RubyGems gem: {}", url);
+
+        let response = self.client.get(&url).send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(RegistryError::NotFound(package_name.to_string()));
+        }
+
+        if !response.status().is_success() {
+            return Err(RegistryError::InvalidResponse(format!(
+                "RubyGems API returned status {}",
+                response.status()
+            )));
+        }
+
+        let entries: Vec<RubyGemsVersion> = response
+            .json()
+            .await
+            .map_err(|e| RegistryError::InvalidResponse(e.to_string()))?;
+
+        // The API lists versions newest first, so the first stable entry is
+        // the latest release
+        let dist_tags: HashMap<String, String> = entries
+            .iter()
+            .find(|entry| !entry.prerelease)
+            .map(|entry| ("latest".to_string(), entry.number.clone()))
+            .into_iter()
+            .collect();
+
+        // Gems with native extensions list one entry per platform, all
+        // sharing the version number. Oldest first, like other registries.
+        let mut versions: Vec<String> = Vec::new();
+        for entry in entries.into_iter().rev() {
+            if !versions.contains(&entry.number) {
+                versions.push(entry.number);
+            }
+        }
+
+        debug!("Found {} versions for gem {}", versions.len(), package_name);
+
+        Ok(PackageVersions::with_dist_tags(versions, dist_tags))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_versions_and_stable_latest() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/versions/rails.json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {"number": "8.0.0.rc1", "prerelease": true, "platform": "ruby"},
+                    {"number": "7.2.1", "prerelease": false, "platform": "ruby"},
+                    {"number": "7.1.4", "prerelease": false, "platform": "ruby"}
+                ]"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = RubyGemsRegistry::new(server.url());
+        let result = registry.fetch_all_versions("rails").await.unwrap();
+
+        mock.assert_async().await;
+
+        assert_eq!(result.versions, vec!["7.1.4", "7.2.1", "8.0.0.rc1"]);
+        assert_eq!(result.dist_tags.get("latest"), Some(&"7.2.1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_deduplicates_platform_builds() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/versions/nokogiri.json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {"number": "1.16.7", "prerelease": false, "platform": "x86_64-linux"},
+                    {"number": "1.16.7", "prerelease": false, "platform": "ruby"},
+                    {"number": "1.16.6", "prerelease": false, "platform": "ruby"}
+                ]"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = RubyGemsRegistry::new(server.url());
+        let result = registry.fetch_all_versions("nokogiri").await.unwrap();
+
+        mock.assert_async().await;
+
+        assert_eq!(result.versions, vec!["1.16.6", "1.16.7"]);
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_not_found_for_missing_gem() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/versions/nonexistent.json")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let registry = RubyGemsRegistry::new(server.url());
+        let result = registry.fetch_all_versions("nonexistent").await;
+
+        mock.assert_async().await;
+
+        assert!(matches!(result, Err(RegistryError::NotFound(_))));
+    }
+}
@@ -0,0 +1,197 @@
+//! Rust toolchain release registry using the rustup channel manifests
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::parser::rust_toolchain::RUST_TOOLCHAIN_PACKAGE_NAME;
+use crate::parser::types::RegistryType;
+use crate::version::error::RegistryError;
+use crate::version::registry::Registry;
+use crate::version::types::PackageVersions;
+
+/// Base URL of the Rust distribution; manifests are `{base}/channel-rust-{channel}.toml`
+const DEFAULT_RUST_DIST_URL: &str = "https://static.rust-lang.org/dist";
+
+/// Rust toolchain release registry client
+pub struct RustToolchainRegistry {
+    client: Client,
+    base_url: String,
+}
+
+impl Default for RustToolchainRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_RUST_DIST_URL.to_string())
+    }
+}
+
+impl RustToolchainRegistry {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Fetch the manifest of the current release of a channel
+    async fn fetch_manifest(&self, channel: &str) -> Result<ChannelManifest, RegistryError> {
+        let url = format!("{}/channel-rust-{}.toml", self.base_url, channel);
+        debug!("Fetching Rust channel manifest: {}", url);
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(RegistryError::InvalidResponse(format!(
+                "static.rust-lang.org returned status {}",
+                response.status()
+            )));
+        }
+
+        let body = response.text().await?;
+        toml::from_str(&body).map_err(|e| RegistryError::InvalidResponse(e.to_string()))
+    }
+}
+
+/// The parts of a channel manifest used here
+#[derive(Debug, Deserialize)]
+struct ChannelManifest {
+    /// Release date of the channel (`2024-01-15`)
+    date: String,
+    pkg: ManifestPackages,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestPackages {
+    rust: ManifestPackage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestPackage {
+    /// Version with commit and date (`1.75.0 (82e1608df 2023-12-21)`)
+    version: String,
+}
+
+#[async_trait]
+impl Registry for RustToolchainRegistry {
+    fn registry_type(&self) -> RegistryType {
+        RegistryType::RustToolchain
+    }
+
+    /// Returns the current stable release and nightly
+    ///
+    /// The manifests only describe the current release of each channel, so
+    /// the versions are the stable version (also the `latest` dist tag) and
+    /// `nightly-<date>` (the `nightly` dist tag).
+    async fn fetch_all_versions(
+        &self,
+        package_name: &str,
+    ) -> Result<PackageVersions, RegistryError> {
+        if package_name != RUST_TOOLCHAIN_PACKAGE_NAME {
+            return Err(RegistryError::NotFound(package_name.to_string()));
+        }
+
+        let stable = self.fetch_manifest("stable").await?;
+        let nightly = self.fetch_manifest("nightly").await?;
+
+        let stable_version = stable
+            .pkg
+            .rust
+            .version
+            .split_whitespace()
+            .next()
+            .ok_or_else(|| {
+                RegistryError::InvalidResponse("stable manifest has no rust version".to_string())
+            })?
+            .to_string();
+        let nightly_version = format!("nightly-{}", nightly.date);
+
+        let dist_tags = HashMap::from([
+            ("latest".to_string(), stable_version.clone()),
+            ("nightly".to_string(), nightly_version.clone()),
+        ]);
+
+        Ok(PackageVersions::with_dist_tags(
+            vec![nightly_version, stable_version],
+            dist_tags,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_stable_and_nightly() {
+        let mut server = Server::new_async().await;
+        let stable_mock = server
+            .mock("GET", "/channel-rust-stable.toml")
+            .with_status(200)
+            .with_body(
+                r#"manifest-version = "2"
+date = "2024-09-05"
+
+[pkg.rust]
+version = "1.81.0 (eeb90cda1 2024-09-04)"
+
+[pkg.rust.target.x86_64-unknown-linux-gnu]
+available = true
+"#,
+            )
+            .create_async()
+            .await;
+        let nightly_mock = server
+            .mock("GET", "/channel-rust-nightly.toml")
+            .with_status(200)
+            .with_body(
+                r#"manifest-version = "2"
+date = "2024-09-20"
+
+[pkg.rust]
+version = "1.83.0-nightly (f79a912d9 2024-09-18)"
+"#,
+            )
+            .create_async()
+            .await;
+
+        let registry = RustToolchainRegistry::new(format!("{}/", server.url()));
+        let result = registry.fetch_all_versions("rust").await.unwrap();
+
+        stable_mock.assert_async().await;
+        nightly_mock.assert_async().await;
+        assert_eq!(result.versions, vec!["nightly-2024-09-20", "1.81.0"]);
+        assert_eq!(result.resolve_dist_tag("latest"), Some("1.81.0"));
+        assert_eq!(
+            result.resolve_dist_tag("nightly"),
+            Some("nightly-2024-09-20")
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_error_for_unsuccessful_response() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/channel-rust-stable.toml")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let registry = RustToolchainRegistry::new(server.url());
+        let result = registry.fetch_all_versions("rust").await;
+
+        assert!(matches!(result, Err(RegistryError::InvalidResponse(_))));
+    }
+
+    #[tokio::test]
+    async fn fetch_all_versions_returns_not_found_for_other_packages() {
+        let registry = RustToolchainRegistry::new("http://127.0.0.1:1".to_string());
+
+        let result = registry.fetch_all_versions("cargo").await;
+
+        assert!(matches!(result, Err(RegistryError::NotFound(_))));
+    }
+}
@@ -5,7 +5,9 @@ use mockall::automock;
 
 use crate::parser::types::RegistryType;
 use crate::version::error::RegistryError;
-use crate::version::types::PackageVersions;
+use crate::version::types::{
+    Advisory, CacheMetadata, FetchResult, PackageMetadata, PackageVersions,
+};
 
 /// Trait for fetching package versions from a registry
 #[cfg_attr(test, automock)]
@@ -20,10 +22,50 @@ pub trait Registry: Send + Sync {
     /// * `package_name` - The name of the package (e.g., "actions/checkout" for GitHub Actions)
     ///
     /// # Returns
-    /// * `Ok(PackageVersions)` - List of versions, ordered from newest to oldest
+    /// * `Ok(PackageVersions)` - List of versions, ordered from oldest to newest
     /// * `Err(RegistryError)` - If the fetch fails
     async fn fetch_all_versions(
         &self,
         package_name: &str,
     ) -> Result<PackageVersions, RegistryError>;
+
+    /// Fetches all versions if they changed since the cached response
+    ///
+    /// `cache_metadata` holds the validators of the cached response (empty when
+    /// nothing is cached); registries supporting conditional requests send them
+    /// and return `FetchResult::NotModified` on 304. The default ignores them
+    /// and always fetches.
+    async fn fetch_versions_if_modified(
+        &self,
+        package_name: &str,
+        _cache_metadata: &CacheMetadata,
+    ) -> Result<FetchResult, RegistryError> {
+        self.fetch_all_versions(package_name)
+            .await
+            .map(FetchResult::Modified)
+    }
+
+    /// Looks up the security advisories affecting the latest of `versions`
+    ///
+    /// Runs on every refresh, including when the versions were not modified,
+    /// so newly published advisories are picked up. Returns `None` when the
+    /// registry does not check advisories or the lookup failed, in which case
+    /// the cached advisories are kept. The default checks none.
+    async fn fetch_advisories(
+        &self,
+        _package_name: &str,
+        _versions: &[String],
+    ) -> Option<Vec<Advisory>> {
+        None
+    }
+
+    /// Looks up the homepage and repository links of a package
+    ///
+    /// Called when a fetched document carried no links and none are cached,
+    /// for registries whose version listing leaves them out (npm's
+    /// abbreviated document). Returns `None` when the registry has no other
+    /// source for them or the lookup failed. The default looks up none.
+    async fn fetch_metadata(&self, _package_name: &str) -> Option<PackageMetadata> {
+        None
+    }
 }
@@ -45,72 +45,160 @@ pub fn parse_version(version: &str) -> Option<Version> {
     Version::parse(&normalized).ok()
 }
 
+/// Extract version prefix (^, ~, ~=, ==, !=, >=, <=, >, <, =, v) from a version string
+pub fn extract_version_prefix(version: &str) -> &str {
+    if version.starts_with("~=") {
+        "~="
+    } else if version.starts_with(">=") {
+        ">="
+    } else if version.starts_with("<=") {
+        "<="
+    } else if version.starts_with("==") {
+        "=="
+    } else if version.starts_with("!=") {
+        "!="
+    } else if version.starts_with('>') {
+        ">"
+    } else if version.starts_with('<') {
+        "<"
+    } else if version.starts_with('=') {
+        "="
+    } else if version.starts_with('^') {
+        "^"
+    } else if version.starts_with('~') {
+        "~"
+    } else if version.starts_with('v') {
+        "v"
+    } else {
+        ""
+    }
+}
+
+/// How a partial version without an operator is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarePartial {
+    /// Cargo: `1` and `1.2` are caret requirements (`^1.0.0`, `^1.2.0`)
+    Caret,
+    /// npm: `1` is `1.x` (`^1.0.0`) and `1.2` is `1.2.x` (`~1.2.0`)
+    XRange,
+}
+
+/// Pad a partial version spec to `major.minor.patch`, the way the npm and
+/// Cargo matchers interpret it
+///
+/// `^1.0` becomes `^1.0.0` and `~1` becomes `~1.0.0`. A spec without an
+/// operator gets the operator `bare` reads it as. Partials whose range would
+/// change when padded (`>1`, `<=1`, `=1`), full versions, wildcards,
+/// compound ranges and other operators are returned unchanged.
+pub fn normalize_partial_spec(spec: &str, bare: BarePartial) -> String {
+    let prefix = extract_version_prefix(spec);
+    let version = &spec[prefix.len()..];
+    let parts: Vec<&str> = version.split('.').collect();
+    let is_partial = parts.len() < 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+    if !is_partial || !["", "^", "~", ">=", "<"].contains(&prefix) {
+        return spec.to_string();
+    }
+
+    let prefix = match (prefix, bare) {
+        ("", BarePartial::Caret) => "^",
+        ("", BarePartial::XRange) if parts.len() == 1 => "^",
+        ("", BarePartial::XRange) => "~",
+        (prefix, _) => prefix,
+    };
+    let padding = ".0".repeat(3 - parts.len());
+    format!("{prefix}{version}{padding}")
+}
+
+/// Parse the available versions that can be offered as bump targets
+///
+/// Pre-releases are only candidates when `include_pre_release` is set
+/// (`includePreRelease`) or the current version is itself a pre-release;
+/// otherwise a stable version is never bumped to an `-rc` or `-beta`.
+/// Which pre-releases are available at all is governed by `ignorePrerelease`
+/// in the storer.
+fn bump_candidates<'a>(
+    current: &Version,
+    available_versions: &'a [String],
+    include_pre_release: bool,
+) -> impl Iterator<Item = (&'a String, Version)> {
+    let include_prerelease = include_pre_release || !current.pre.is_empty();
+    available_versions
+        .iter()
+        .filter_map(|v| Some((v, parse_version(v)?)))
+        .filter(move |(_, parsed)| include_prerelease || parsed.pre.is_empty())
+}
+
+/// Returns the original string of the highest version, if it is newer than `current`
+fn newest_above<'a>(
+    current: &Version,
+    candidates: impl Iterator<Item = (&'a String, Version)>,
+) -> Option<String> {
+    candidates
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .filter(|(_, latest)| latest > current)
+        .map(|(v, _)| v.clone())
+}
+
 /// Calculate the latest patch version within the same major.minor
 ///
 /// Returns the latest patch version if a newer patch exists,
 /// or None if the current version is already the latest patch.
+/// Prefixes (`v`, `^`, ...) are ignored for comparison; the available
+/// version is returned as written (`v4.1.7`).
 pub fn calculate_latest_patch(
     current_version: &str,
     available_versions: &[String],
+    include_pre_release: bool,
 ) -> Option<String> {
     let current = parse_version(current_version)?;
 
-    let latest_patch = available_versions
-        .iter()
-        .filter_map(|v| parse_version(v))
-        .filter(|v| v.major == current.major && v.minor == current.minor)
-        .max()?;
-
-    if latest_patch > current {
-        Some(latest_patch.to_string())
-    } else {
-        None
-    }
+    newest_above(
+        &current,
+        bump_candidates(&current, available_versions, include_pre_release)
+            .filter(|(_, v)| v.major == current.major && v.minor == current.minor),
+    )
 }
 
 /// Calculate the latest minor version within the same major
 ///
 /// Returns the latest minor.patch version if a newer minor exists,
 /// or None if the current version is already the latest minor.
+/// Prefixes (`v`, `^`, ...) are ignored for comparison; the available
+/// version is returned as written.
 pub fn calculate_latest_minor(
     current_version: &str,
     available_versions: &[String],
+    include_pre_release: bool,
 ) -> Option<String> {
     let current = parse_version(current_version)?;
 
-    let latest_minor = available_versions
-        .iter()
-        .filter_map(|v| parse_version(v))
-        .filter(|v| v.major == current.major)
-        .max()?;
-
-    if latest_minor > current {
-        Some(latest_minor.to_string())
-    } else {
-        None
-    }
+    newest_above(
+        &current,
+        bump_candidates(&current, available_versions, include_pre_release)
+            .filter(|(_, v)| v.major == current.major),
+    )
 }
 
 /// Calculate the latest major version
 ///
 /// Returns the latest version if a newer major version exists,
 /// or None if the current version is already the latest.
+/// Prefixes (`v`, `^`, ...) are ignored for comparison; the available
+/// version is returned as written.
 pub fn calculate_latest_major(
     current_version: &str,
     available_versions: &[String],
+    include_pre_release: bool,
 ) -> Option<String> {
     let current = parse_version(current_version)?;
 
-    let latest = available_versions
-        .iter()
-        .filter_map(|v| parse_version(v))
-        .max()?;
-
-    if latest > current {
-        Some(latest.to_string())
-    } else {
-        None
-    }
+    newest_above(
+        &current,
+        bump_candidates(&current, available_versions, include_pre_release),
+    )
 }
 
 /// Calculate the next minor version (current.minor + 1 series)
@@ -120,22 +208,20 @@ pub fn calculate_latest_major(
 pub fn calculate_next_minor(
     current_version: &str,
     available_versions: &[String],
+    include_pre_release: bool,
 ) -> Option<String> {
     let current = parse_version(current_version)?;
 
-    let next_minor_num = available_versions
-        .iter()
-        .filter_map(|v| parse_version(v))
-        .filter(|v| v.major == current.major && v.minor > current.minor)
-        .map(|v| v.minor)
+    let next_minor_num = bump_candidates(&current, available_versions, include_pre_release)
+        .filter(|(_, v)| v.major == current.major && v.minor > current.minor)
+        .map(|(_, v)| v.minor)
         .min()?;
 
-    available_versions
-        .iter()
-        .filter_map(|v| parse_version(v))
-        .filter(|v| v.major == current.major && v.minor == next_minor_num)
-        .max()
-        .map(|v| v.to_string())
+    newest_above(
+        &current,
+        bump_candidates(&current, available_versions, include_pre_release)
+            .filter(|(_, v)| v.major == current.major && v.minor == next_minor_num),
+    )
 }
 
 /// Calculate the next major version (current.major + 1 series)
@@ -145,22 +231,31 @@ pub fn calculate_next_minor(
 pub fn calculate_next_major(
     current_version: &str,
     available_versions: &[String],
+    include_pre_release: bool,
 ) -> Option<String> {
     let current = parse_version(current_version)?;
 
-    let next_major_num = available_versions
-        .iter()
-        .filter_map(|v| parse_version(v))
-        .filter(|v| v.major > current.major)
-        .map(|v| v.major)
+    let next_major_num = bump_candidates(&current, available_versions, include_pre_release)
+        .filter(|(_, v)| v.major > current.major)
+        .map(|(_, v)| v.major)
         .min()?;
 
-    available_versions
-        .iter()
-        .filter_map(|v| parse_version(v))
-        .filter(|v| v.major == next_major_num)
-        .max()
-        .map(|v| v.to_string())
+    newest_above(
+        &current,
+        bump_candidates(&current, available_versions, include_pre_release)
+            .filter(|(_, v)| v.major == next_major_num),
+    )
+}
+
+/// Find the semantically highest of `versions`, returned as written
+///
+/// Versions that cannot be parsed are ignored.
+pub fn find_semantic_max<'a>(versions: impl IntoIterator<Item = &'a String>) -> Option<&'a String> {
+    versions
+        .into_iter()
+        .filter_map(|v| Some((v, parse_version(v)?)))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(v, _)| v)
 }
 
 /// Check if a version string is a prerelease version.
@@ -176,6 +271,62 @@ mod tests {
     use super::*;
     use rstest::rstest;
 
+    #[rstest]
+    #[case(vec!["1.2.0", "1.10.0", "1.9.0"], Some("1.10.0"))]
+    #[case(vec!["v4.0.0", "nightly", "v4.1.0"], Some("v4.1.0"))]
+    #[case(vec!["2.0.0-rc.1", "1.9.0"], Some("2.0.0-rc.1"))]
+    #[case(vec!["nightly"], None)]
+    #[case(vec![], None)]
+    fn find_semantic_max_returns_highest_version(
+        #[case] versions: Vec<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let versions: Vec<String> = versions.into_iter().map(String::from).collect();
+        assert_eq!(find_semantic_max(&versions).map(String::as_str), expected);
+    }
+
+    #[rstest]
+    #[case("^4.17.19", "^")]
+    #[case("~4.17.19", "~")]
+    #[case("4.17.19", "")]
+    #[case(">=2.0.0", ">=")]
+    #[case("==2.0.0", "==")]
+    #[case("~=1.4.2", "~=")]
+    #[case("!=2.0.0", "!=")]
+    #[case("v1.0.0", "v")]
+    fn test_extract_version_prefix(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(extract_version_prefix(input), expected);
+    }
+
+    #[rstest]
+    #[case("^1.0", BarePartial::XRange, "^1.0.0")]
+    #[case("~1", BarePartial::XRange, "~1.0.0")]
+    #[case(">=1.2", BarePartial::XRange, ">=1.2.0")]
+    #[case("<2", BarePartial::Caret, "<2.0.0")]
+    #[case("1", BarePartial::Caret, "^1.0.0")]
+    #[case("0.14", BarePartial::Caret, "^0.14.0")]
+    #[case("1", BarePartial::XRange, "^1.0.0")]
+    #[case("1.2", BarePartial::XRange, "~1.2.0")]
+    #[case(">1", BarePartial::XRange, ">1")]
+    #[case("<=1.2", BarePartial::Caret, "<=1.2")]
+    #[case("=1", BarePartial::Caret, "=1")]
+    #[case("^1.2.3", BarePartial::Caret, "^1.2.3")]
+    #[case("1.2.3", BarePartial::Caret, "1.2.3")]
+    #[case("1.x", BarePartial::XRange, "1.x")]
+    #[case("1.*", BarePartial::Caret, "1.*")]
+    #[case("*", BarePartial::Caret, "*")]
+    #[case(">=1.0, <2.0", BarePartial::Caret, ">=1.0, <2.0")]
+    #[case("^1 || ^2", BarePartial::XRange, "^1 || ^2")]
+    #[case("~=1.4", BarePartial::XRange, "~=1.4")]
+    #[case("latest", BarePartial::XRange, "latest")]
+    fn normalize_partial_spec_pads_partial_versions(
+        #[case] spec: &str,
+        #[case] bare: BarePartial,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(normalize_partial_spec(spec, bare), expected);
+    }
+
     #[rstest]
     #[case("1.2.3", Some(Version::new(1, 2, 3)))]
     #[case("^1.2.3", Some(Version::new(1, 2, 3)))] // caret prefix
@@ -201,6 +352,10 @@ mod tests {
     #[case("^1.2.3", &["1.2.3", "1.2.5", "1.3.0", "2.0.0"], Some("1.2.5".to_string()))] // caret prefix
     #[case("~1.2.3", &["1.2.3", "1.2.5", "1.3.0", "2.0.0"], Some("1.2.5".to_string()))] // tilde prefix
     #[case("1.2.5", &["1.2.3", "1.2.5", "1.3.0", "2.0.0"], None)] // already latest patch
+    #[case("v4.1.6", &["v4.1.6", "v4.1.7", "v4.2.0"], Some("v4.1.7".to_string()))] // v prefix (returned as written)
+    #[case("4.1.6", &["v4.1.7"], Some("v4.1.7".to_string()))] // v prefix only on available versions
+    #[case("v3.0.0", &["v3.0.0", "v3.0.1-rc.1"], None)] // pre-release excluded
+    #[case("v3.0.1-rc.1", &["v3.0.1-rc.1", "v3.0.1-rc.2"], Some("v3.0.1-rc.2".to_string()))] // pre-release current
     #[case("invalid", &["1.2.3", "1.2.5"], None)] // unparseable current version
     #[case("1.2.3", &["invalid", "not-a-version"], None)] // no valid available versions
     #[case("1.2.3", &[], None)] // empty available versions
@@ -211,7 +366,7 @@ mod tests {
     ) {
         let available_strings: Vec<String> = available.iter().map(|s| s.to_string()).collect();
         assert_eq!(
-            calculate_latest_patch(current, &available_strings),
+            calculate_latest_patch(current, &available_strings, false),
             expected
         );
     }
@@ -219,6 +374,9 @@ mod tests {
     #[rstest]
     #[case("1.2.3", &["1.2.3", "1.3.0", "1.5.0", "2.0.0"], Some("1.5.0".to_string()))]
     #[case("1.5.0", &["1.2.3", "1.3.0", "1.5.0", "2.0.0"], None)] // already latest minor
+    #[case("v4.1.6", &["v4.1.7", "v4.2.0", "v5.0.0"], Some("v4.2.0".to_string()))] // v prefix (returned as written)
+    #[case("v3.0.0", &["v3.0.0", "v3.1.0", "v4.0.0"], Some("v3.1.0".to_string()))]
+    #[case("v3.0.0", &["v3.1.0", "v3.2.0-beta.1"], Some("v3.1.0".to_string()))] // pre-release excluded
     #[case("invalid", &["1.2.3", "1.5.0"], None)] // unparseable current version
     #[case("1.2.3", &["invalid", "not-a-version"], None)] // no valid available versions
     #[case("1.2.3", &[], None)] // empty available versions
@@ -229,7 +387,7 @@ mod tests {
     ) {
         let available_strings: Vec<String> = available.iter().map(|s| s.to_string()).collect();
         assert_eq!(
-            calculate_latest_minor(current, &available_strings),
+            calculate_latest_minor(current, &available_strings, false),
             expected
         );
     }
@@ -237,6 +395,11 @@ mod tests {
     #[rstest]
     #[case("1.2.3", &["1.2.3", "2.0.0", "3.0.0"], Some("3.0.0".to_string()))]
     #[case("3.0.0", &["1.2.3", "2.0.0", "3.0.0"], None)] // already latest major
+    #[case("v4.1.6", &["v4.1.7", "v5.0.0"], Some("v5.0.0".to_string()))] // v prefix (returned as written)
+    #[case("v3.0.0", &["v3.0.0", "v3.1.0", "v4.0.0"], Some("v4.0.0".to_string()))]
+    #[case("v3.0.0", &["v3.1.0", "v4.0.0", "v4.0.0-rc.1", "v5.0.0-rc.1"], Some("v4.0.0".to_string()))] // pre-release excluded
+    #[case("v3.1.0", &["v3.1.0", "v4.0.0-rc.1"], None)] // only pre-release newer
+    #[case("4.0.0-rc.1", &["4.0.0-rc.1", "4.0.0-rc.2"], Some("4.0.0-rc.2".to_string()))] // pre-release current
     #[case("invalid", &["1.2.3", "2.0.0"], None)] // unparseable current version
     #[case("1.2.3", &["invalid", "not-a-version"], None)] // no valid available versions
     #[case("1.2.3", &[], None)] // empty available versions
@@ -247,7 +410,26 @@ mod tests {
     ) {
         let available_strings: Vec<String> = available.iter().map(|s| s.to_string()).collect();
         assert_eq!(
-            calculate_latest_major(current, &available_strings),
+            calculate_latest_major(current, &available_strings, false),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("v3.1.0", &["v3.1.0", "v4.0.0-rc.1"], false, None)] // pre-release excluded by default
+    #[case("v3.1.0", &["v3.1.0", "v4.0.0-rc.1"], true, Some("v4.0.0-rc.1".to_string()))]
+    #[case("v3.1.0", &["v3.1.0", "v4.0.0", "v4.0.0-rc.1"], true, Some("v4.0.0".to_string()))] // release wins over its rc
+    #[case("v3.1.0", &["v3.1.0", "v4.0.0", "v4.1.0-rc.1"], false, Some("v4.0.0".to_string()))]
+    #[case("v3.1.0", &["v3.1.0", "v4.0.0", "v4.1.0-rc.1"], true, Some("v4.1.0-rc.1".to_string()))]
+    fn test_calculate_latest_major_include_pre_release(
+        #[case] current: &str,
+        #[case] available: &[&str],
+        #[case] include_pre_release: bool,
+        #[case] expected: Option<String>,
+    ) {
+        let available_strings: Vec<String> = available.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            calculate_latest_major(current, &available_strings, include_pre_release),
             expected
         );
     }
@@ -258,8 +440,10 @@ mod tests {
     #[case("1.2.3", &["1.2.3", "1.3.0"], Some("1.3.0".to_string()))] // single next minor
     #[case("1.5.0", &["1.2.3", "1.3.0", "1.5.0"], None)] // already at latest minor
     #[case("1.2.3", &["1.2.3", "2.0.0"], None)] // no higher minor in same major
-    #[case("1.2.3", &["1.2.3", "1.3.0-beta.1", "1.4.0"], Some("1.3.0-beta.1".to_string()))] // prerelease is next minor
-    #[case("1.2.3", &["1.2.3", "1.3.0-beta.1"], Some("1.3.0-beta.1".to_string()))] // prerelease minor included
+    #[case("1.2.3", &["1.2.3", "1.3.0-beta.1", "1.4.0"], Some("1.4.0".to_string()))] // prerelease skipped
+    #[case("1.2.3", &["1.2.3", "1.3.0-beta.1"], None)] // prerelease minor excluded
+    #[case("1.2.3-beta.1", &["1.2.3-beta.1", "1.3.0-beta.1"], Some("1.3.0-beta.1".to_string()))] // prerelease current
+    #[case("v1.2.3", &["v1.2.3", "v1.3.0", "v1.3.1", "v1.4.0"], Some("v1.3.1".to_string()))] // v prefix
     #[case("invalid", &["1.3.0"], None)]
     #[case("1.2.3", &[], None)]
     fn test_calculate_next_minor(
@@ -268,7 +452,10 @@ mod tests {
         #[case] expected: Option<String>,
     ) {
         let available_strings: Vec<String> = available.iter().map(|s| s.to_string()).collect();
-        assert_eq!(calculate_next_minor(current, &available_strings), expected);
+        assert_eq!(
+            calculate_next_minor(current, &available_strings, false),
+            expected
+        );
     }
 
     #[rstest]
@@ -276,8 +463,10 @@ mod tests {
     #[case("1.2.3", &["1.2.3", "2.0.0", "2.3.0"], Some("2.3.0".to_string()))] // latest within next major
     #[case("1.2.3", &["1.2.3", "2.0.0"], Some("2.0.0".to_string()))] // single next major
     #[case("3.0.0", &["1.2.3", "2.0.0", "3.0.0"], None)] // already at latest major
-    #[case("1.2.3", &["1.2.3", "2.0.0-canary.123", "3.0.0"], Some("2.0.0-canary.123".to_string()))] // prerelease is next major
-    #[case("1.2.3", &["1.2.3", "2.0.0-alpha.1"], Some("2.0.0-alpha.1".to_string()))] // prerelease major included
+    #[case("1.2.3", &["1.2.3", "2.0.0-canary.123", "3.0.0"], Some("3.0.0".to_string()))] // prerelease skipped
+    #[case("1.2.3", &["1.2.3", "2.0.0-alpha.1"], None)] // prerelease major excluded
+    #[case("1.2.3-rc.1", &["1.2.3-rc.1", "2.0.0-alpha.1"], Some("2.0.0-alpha.1".to_string()))] // prerelease current
+    #[case("v1.2.3", &["v1.2.3", "v2.0.0", "v2.1.0", "v3.0.0"], Some("v2.1.0".to_string()))] // v prefix
     #[case("invalid", &["2.0.0"], None)]
     #[case("1.2.3", &[], None)]
     fn test_calculate_next_major(
@@ -286,7 +475,10 @@ mod tests {
         #[case] expected: Option<String>,
     ) {
         let available_strings: Vec<String> = available.iter().map(|s| s.to_string()).collect();
-        assert_eq!(calculate_next_major(current, &available_strings), expected);
+        assert_eq!(
+            calculate_next_major(current, &available_strings, false),
+            expected
+        );
     }
 
     // npm / crates.io / JSR / pnpm format
@@ -1,14 +1,30 @@
 //! Common types for version management
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
+
+use crate::version::error::RegistryError;
+use crate::version::semver::parse_version;
+
 /// Collection of versions for a package
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PackageVersions {
-    /// List of versions, ordered from newest to oldest
+    /// List of versions, ordered from oldest to newest
     pub versions: Vec<String>,
     /// Dist tags mapping tag names to versions (e.g., "latest" -> "4.17.21")
     pub dist_tags: HashMap<String, String>,
+    /// Package homepage URL, if the registry provides one
+    pub homepage: Option<String>,
+    /// Source repository URL (normalized to https), if the registry provides one
+    pub repository: Option<String>,
+    /// Deprecation message, if the registry marks the package as deprecated
+    pub deprecated: Option<String>,
+    /// Versions the registry yanked, left out of `versions`
+    pub yanked: Vec<String>,
+    /// Validators from the response, sent back on the next conditional fetch
+    pub cache_metadata: CacheMetadata,
 }
 
 impl PackageVersions {
@@ -17,20 +33,92 @@ impl PackageVersions {
         Self {
             versions,
             dist_tags: HashMap::new(),
+            homepage: None,
+            repository: None,
+            deprecated: None,
+            yanked: Vec::new(),
+            cache_metadata: CacheMetadata::default(),
         }
     }
 
+    /// Creates a new PackageVersions from versions in arbitrary order,
+    /// sorted oldest to newest by semver
+    ///
+    /// Versions that cannot be parsed keep their relative order after the
+    /// semver ones, so the last semver version is always the latest.
+    pub fn sorted_by_semver(versions: Vec<String>) -> Self {
+        let mut versions: Vec<_> = versions
+            .into_iter()
+            .map(|v| {
+                let parsed = parse_version(&v);
+                (v, parsed)
+            })
+            .collect();
+
+        // Stable sort: non-semver versions (None) go last
+        versions.sort_by(|(_, a), (_, b)| match (a, b) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+
+        Self::new(versions.into_iter().map(|(v, _)| v).collect())
+    }
+
     /// Creates a new PackageVersions with versions and dist tags
     pub fn with_dist_tags(versions: Vec<String>, dist_tags: HashMap<String, String>) -> Self {
         Self {
             versions,
             dist_tags,
+            homepage: None,
+            repository: None,
+            deprecated: None,
+            yanked: Vec::new(),
+            cache_metadata: CacheMetadata::default(),
         }
     }
 
-    /// Returns the latest (first) version, if any
+    /// Attach homepage and repository URLs
+    pub fn with_metadata(mut self, homepage: Option<String>, repository: Option<String>) -> Self {
+        self.homepage = homepage;
+        self.repository = repository;
+        self
+    }
+
+    /// Attach the registry's deprecation message
+    pub fn with_deprecated(mut self, deprecated: Option<String>) -> Self {
+        self.deprecated = deprecated;
+        self
+    }
+
+    /// Attach the versions the registry yanked
+    pub fn with_yanked(mut self, yanked: Vec<String>) -> Self {
+        self.yanked = yanked;
+        self
+    }
+
+    /// Attach the response's ETag/Last-Modified validators
+    pub fn with_cache_metadata(mut self, cache_metadata: CacheMetadata) -> Self {
+        self.cache_metadata = cache_metadata;
+        self
+    }
+
+    /// Returns the homepage and repository URLs as cacheable metadata
+    pub fn metadata(&self) -> PackageMetadata {
+        PackageMetadata {
+            homepage: self.homepage.clone(),
+            repository: self.repository.clone(),
+        }
+    }
+
+    /// Returns the latest (semantically highest) version, if any
     pub fn latest(&self) -> Option<&str> {
-        self.versions.first().map(|s| s.as_str())
+        self.versions
+            .iter()
+            .filter_map(|v| Some((v, parse_version(v)?)))
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(v, _)| v.as_str())
     }
 
     /// Returns true if the collection is empty
@@ -43,3 +131,191 @@ impl PackageVersions {
         self.dist_tags.get(tag).map(|s| s.as_str())
     }
 }
+
+/// A security advisory affecting a package version (e.g. from OSV)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Advisory {
+    /// Advisory identifier (e.g. "RUSTSEC-2021-0078" or "GHSA-...")
+    pub id: String,
+    /// Severity as reported by the advisory database (e.g. "HIGH"),
+    /// "UNKNOWN" when it has none
+    pub severity: String,
+    /// One-line description of the vulnerability
+    pub summary: String,
+}
+
+/// What version checks need to know about a cached package besides its versions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageSummary {
+    /// The `latest` dist tag, or else the highest version that is not yanked
+    pub latest_version: String,
+    /// Dist tags of the package (tag name -> version)
+    pub dist_tags: HashMap<String, String>,
+    /// Deprecation message, if the registry deprecated the package
+    pub deprecated: Option<String>,
+    /// Security advisories affecting the latest version
+    pub advisories: Vec<Advisory>,
+}
+
+/// HTTP validators of a registry response (`ETag` / `Last-Modified`)
+///
+/// Stored with the cached versions and sent back as `If-None-Match` /
+/// `If-Modified-Since`, so unchanged packages are answered with 304.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheMetadata {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheMetadata {
+    /// Read the validators from response headers
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+        Self {
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+        }
+    }
+
+    /// Returns true if the response carried no validator
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    /// Make `request` conditional on the stored validators
+    pub fn apply(&self, mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if let Some(etag) = &self.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    }
+}
+
+/// Outcome of a conditional registry fetch
+#[derive(Debug, Clone, PartialEq, Eq)]
+// Returned once per fetch and unwrapped right away, so boxing buys nothing
+#[allow(clippy::large_enum_variant)]
+pub enum FetchResult {
+    /// The package changed (or no validators were sent)
+    Modified(PackageVersions),
+    /// 304 Not Modified: the cached versions are still current
+    NotModified,
+}
+
+impl FetchResult {
+    /// Versions of a full response
+    ///
+    /// Registries only answer 304 to conditional requests, so for an
+    /// unconditional fetch NotModified is an invalid response.
+    pub fn into_versions(self) -> Result<PackageVersions, RegistryError> {
+        match self {
+            FetchResult::Modified(versions) => Ok(versions),
+            FetchResult::NotModified => Err(RegistryError::InvalidResponse(
+                "Unexpected 304 Not Modified".to_string(),
+            )),
+        }
+    }
+}
+
+/// Package links stored alongside cached versions (JSON-encoded in the cache)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageMetadata {
+    pub homepage: Option<String>,
+    pub repository: Option<String>,
+}
+
+impl PackageMetadata {
+    /// Returns true if no link is known
+    pub fn is_empty(&self) -> bool {
+        self.homepage.is_none() && self.repository.is_none()
+    }
+
+    /// URL to the package's changelog: the GitHub releases page when the
+    /// repository is on GitHub, otherwise the repository or homepage
+    pub fn changelog_url(&self) -> Option<String> {
+        match self.repository.as_deref() {
+            Some(repo) if repo.starts_with("https://github.com/") => {
+                Some(format!("{}/releases", repo.trim_end_matches('/')))
+            }
+            Some(repo) => Some(repo.to_string()),
+            None => self.homepage.clone(),
+        }
+    }
+
+    /// Markdown `[Changelog](url)` link for hover content
+    pub fn changelog_link(&self) -> Option<String> {
+        self.changelog_url()
+            .map(|url| format!("[Changelog]({})", url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(vec!["1.10.0", "1.2.0", "1.9.0"], vec!["1.2.0", "1.9.0", "1.10.0"])]
+    #[case(vec!["v2.0.0", "nightly", "v1.0.0", "edge"], vec!["v1.0.0", "v2.0.0", "nightly", "edge"])]
+    #[case(vec![], vec![])]
+    fn sorted_by_semver_orders_oldest_to_newest(
+        #[case] versions: Vec<&str>,
+        #[case] expected: Vec<&str>,
+    ) {
+        let versions = versions.into_iter().map(String::from).collect();
+        assert_eq!(
+            PackageVersions::sorted_by_semver(versions).versions,
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(vec!["1.0.0", "2.0.0", "1.5.0"], Some("2.0.0"))]
+    #[case(vec!["1.0.0", "nightly"], Some("1.0.0"))]
+    #[case(vec![], None)]
+    fn latest_returns_highest_semver_version(
+        #[case] versions: Vec<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let versions = PackageVersions::new(versions.into_iter().map(String::from).collect());
+        assert_eq!(versions.latest(), expected);
+        assert_eq!(versions.is_empty(), expected.is_none());
+    }
+
+    #[rstest]
+    #[case(
+        Some("https://github.com/lodash/lodash"),
+        Some("https://lodash.com/"),
+        Some("[Changelog](https://github.com/lodash/lodash/releases)")
+    )]
+    #[case(
+        Some("https://gitlab.com/foo/bar"),
+        None,
+        Some("[Changelog](https://gitlab.com/foo/bar)")
+    )]
+    #[case(
+        None,
+        Some("https://example.com"),
+        Some("[Changelog](https://example.com)")
+    )]
+    #[case(None, None, None)]
+    fn changelog_link_returns_expected(
+        #[case] repository: Option<&str>,
+        #[case] homepage: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let metadata = PackageMetadata {
+            homepage: homepage.map(String::from),
+            repository: repository.map(String::from),
+        };
+        assert_eq!(metadata.changelog_link().as_deref(), expected);
+    }
+}
@@ -0,0 +1,292 @@
+//! Conda (environment.yml, conda-lock.yml) E2E tests
+
+mod helper;
+
+use std::collections::HashMap;
+
+use tower::Service;
+use tower_lsp::LspService;
+use tower_lsp::lsp_types::*;
+
+use helper::{
+    MockRegistry, create_did_open_notification, create_initialize_request,
+    create_initialized_notification, create_test_cache, create_test_resolver,
+    spawn_notification_collector, wait_for_notification,
+};
+use version_lsp::lsp::backend::Backend;
+use version_lsp::lsp::resolver::PackageResolver;
+use version_lsp::parser::types::RegistryType;
+use version_lsp::version::checker::VersionStorer;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_outdated_version_warning() {
+    // 1. Setup real Cache with test data (oldest first, newest last)
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Conda,
+        &[("numpy", vec!["1.21.0", "1.26.4", "2.1.0"])],
+    );
+
+    // 2. Setup mock Registry and resolver
+    let registry = MockRegistry::new(RegistryType::Conda)
+        .with_versions("numpy", vec!["1.21.0", "1.26.4", "2.1.0"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Conda,
+        create_test_resolver(RegistryType::Conda, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen with a fuzzy pin that doesn't include latest
+    // =1.21.0 matches 1.21.0 only, so 2.1.0 is outside the range -> outdated
+    let environment_yml = r#"name: my-env
+dependencies:
+  - numpy=1.21.0
+"#;
+
+    service
+        .call(create_did_open_notification(
+            "file:///test/environment.yml",
+            environment_yml,
+        ))
+        .await
+        .unwrap();
+
+    // 6. Receive publishDiagnostics notification
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].severity,
+        Some(DiagnosticSeverity::WARNING)
+    );
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on Conda: =1.21.0 -> 2.1.0"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_lock_drift_information_for_conda_lock() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Conda,
+        &[("numpy", vec!["1.21.0", "1.26.4", "2.1.0"])],
+    );
+
+    let registry = MockRegistry::new(RegistryType::Conda)
+        .with_versions("numpy", vec!["1.21.0", "1.26.4", "2.1.0"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Conda,
+        create_test_resolver(RegistryType::Conda, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // numpy is locked once per platform; only the first entry is reported
+    let conda_lock_yml = r#"version: 1
+package:
+- name: numpy
+  version: 1.26.4
+  manager: conda
+  platform: linux-64
+- name: numpy
+  version: 1.26.4
+  manager: conda
+  platform: osx-arm64
+"#;
+
+    service
+        .call(create_did_open_notification(
+            "file:///test/conda-lock.yml",
+            conda_lock_yml,
+        ))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].severity,
+        Some(DiagnosticSeverity::INFORMATION)
+    );
+    assert_eq!(params.diagnostics[0].range.start, Position::new(3, 11));
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on Conda: 1.26.4 -> 2.1.0; run `conda-lock update` to update the lock file"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn no_diagnostics_for_latest_version_or_pip_requirements() {
+    // 1. Setup real Cache with test data
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Conda,
+        &[("scipy", vec!["1.7.0", "1.11.4", "1.14.1"])],
+    );
+
+    // 2. Setup mock Registry and resolver
+    let registry = MockRegistry::new(RegistryType::Conda)
+        .with_versions("scipy", vec!["1.7.0", "1.11.4", "1.14.1"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Conda,
+        create_test_resolver(RegistryType::Conda, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen with a conda spec that includes latest
+    // >=1.7 includes 1.14.1, so it's latest. pip requirements are checked
+    // against PyPI, which has no resolver here.
+    let environment_yml = r#"name: my-env
+dependencies:
+  - scipy>=1.7
+  - pip:
+    - flask==0.1.0
+"#;
+
+    service
+        .call(create_did_open_notification(
+            "file:///test/environment.yml",
+            environment_yml,
+        ))
+        .await
+        .unwrap();
+
+    // 6. Receive publishDiagnostics notification - should be empty
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert!(params.diagnostics.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_diagnostics_for_conda_and_pip_packages() {
+    // 1. Setup real Cache with conda and PyPI test data
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Conda,
+        &[("numpy", vec!["1.21.0", "1.26.4", "2.1.0"])],
+    );
+    cache
+        .replace_versions(
+            RegistryType::PyPI,
+            "flask",
+            vec!["2.0.0".to_string(), "3.0.0".to_string()],
+            &[],
+        )
+        .unwrap();
+
+    // 2. Setup mock Registries and resolvers for both registries
+    let conda_registry = MockRegistry::new(RegistryType::Conda)
+        .with_versions("numpy", vec!["1.21.0", "1.26.4", "2.1.0"]);
+    let pypi_registry =
+        MockRegistry::new(RegistryType::PyPI).with_versions("flask", vec!["2.0.0", "3.0.0"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([
+        (
+            RegistryType::Conda,
+            create_test_resolver(RegistryType::Conda, conda_registry),
+        ),
+        (
+            RegistryType::PyPI,
+            create_test_resolver(RegistryType::PyPI, pypi_registry),
+        ),
+    ]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen with an outdated conda package and an outdated pip requirement;
+    // each is checked against its own registry
+    let environment_yml = r#"name: my-env
+dependencies:
+  - numpy=1.21.0
+  - pip:
+    - flask==2.0.0
+"#;
+
+    service
+        .call(create_did_open_notification(
+            "file:///test/environment.yml",
+            environment_yml,
+        ))
+        .await
+        .unwrap();
+
+    // 6. Receive publishDiagnostics notification
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    let messages: Vec<&str> = params
+        .diagnostics
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "Update available on Conda: =1.21.0 -> 2.1.0",
+            "Update available on PyPI: ==2.0.0 -> 3.0.0",
+        ]
+    );
+}
@@ -3,19 +3,23 @@
 mod helper;
 
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 use tower::Service;
 use tower_lsp::LspService;
 use tower_lsp::lsp_types::*;
 
 use helper::{
-    MockRegistry, create_did_open_notification, create_initialize_request,
-    create_initialized_notification, create_test_cache, create_test_resolver,
-    spawn_notification_collector, wait_for_notification,
+    MockRegistry, create_did_change_configuration_notification, create_did_open_notification,
+    create_folding_range_request, create_initialize_request, create_initialize_request_with_root,
+    create_initialized_notification, create_prepare_rename_request, create_rename_request,
+    create_test_cache, create_test_resolver, spawn_notification_collector,
+    spawn_notification_collector_with_settings, wait_for_notification,
 };
 use version_lsp::lsp::backend::Backend;
 use version_lsp::lsp::resolver::PackageResolver;
 use version_lsp::parser::types::RegistryType;
+use version_lsp::version::checker::VersionStorer;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn publishes_outdated_version_warning() {
@@ -81,7 +85,83 @@ serde = "~1.0.100"
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: ~1.0.100 -> 1.1.0"
+        "Update available on crates.io: ~1.0.100 -> 1.1.0"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_information_for_locked_version_behind_latest() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::CratesIo,
+        &[
+            ("serde", vec!["1.0.100", "1.0.200"]),
+            ("itoa", vec!["1.0.10", "1.0.11"]),
+        ],
+    );
+
+    let registry = MockRegistry::new(RegistryType::CratesIo)
+        .with_versions("serde", vec!["1.0.100", "1.0.200"])
+        .with_versions("itoa", vec!["1.0.10", "1.0.11"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::CratesIo,
+        create_test_resolver(RegistryType::CratesIo, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // serde 1.0.100 is locked although 1.0.200 satisfies the same caret
+    // requirement; the local test-project has no source and is skipped
+    let cargo_lock = r#"version = 4
+
+[[package]]
+name = "itoa"
+version = "1.0.11"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde"
+version = "1.0.100"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "test-project"
+version = "0.1.0"
+"#;
+
+    service
+        .call(create_did_open_notification(
+            "file:///test/Cargo.lock",
+            cargo_lock,
+        ))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].severity,
+        Some(DiagnosticSeverity::INFORMATION)
+    );
+    assert_eq!(params.diagnostics[0].range.start, Position::new(9, 11));
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on crates.io: =1.0.100 -> 1.0.200"
     );
 }
 
@@ -328,6 +408,308 @@ prost = "0.13"
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: 0.13 -> 0.14.1"
+        "Update available on crates.io: ^0.13.0 -> 0.14.1"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn prefetches_dependencies_of_workspace_manifests_on_startup() {
+    // 1. Setup empty Cache
+    let (_temp_dir, cache) = create_test_cache(RegistryType::CratesIo, &[]);
+
+    // 2. Setup mock Registries and resolvers
+    let crates_registry =
+        MockRegistry::new(RegistryType::CratesIo).with_versions("serde", vec!["1.0.0", "1.0.1"]);
+    let npm_registry =
+        MockRegistry::new(RegistryType::Npm).with_versions("lodash", vec!["4.17.21"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([
+        (
+            RegistryType::CratesIo,
+            create_test_resolver(RegistryType::CratesIo, crates_registry),
+        ),
+        (
+            RegistryType::Npm,
+            create_test_resolver(RegistryType::Npm, npm_registry),
+        ),
+    ]);
+
+    // 3. Write a workspace with a nested crate and a package.json
+    let workspace = tempfile::TempDir::new().unwrap();
+    let crate_dir = workspace.path().join("crates").join("core");
+    std::fs::create_dir_all(&crate_dir).unwrap();
+    std::fs::write(
+        crate_dir.join("Cargo.toml"),
+        "[dependencies]\nserde = \"1.0\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        workspace.path().join("package.json"),
+        r#"{ "dependencies": { "lodash": "^4.0.0" } }"#,
+    )
+    .unwrap();
+    let root_uri = Url::from_directory_path(workspace.path()).unwrap();
+
+    // 4. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let _notification_rx = spawn_notification_collector(socket);
+
+    // 5. Initialize with the workspace root, without opening any document
+    service
+        .call(create_initialize_request_with_root(1, root_uri))
+        .await
+        .unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 6. Dependencies of every manifest are fetched in the background
+    let cached = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            let serde = cache
+                .get_versions(RegistryType::CratesIo, "serde", false)
+                .unwrap();
+            let lodash = cache
+                .get_versions(RegistryType::Npm, "lodash", false)
+                .unwrap();
+            if !serde.is_empty() && !lodash.is_empty() {
+                return (serde, lodash);
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("Expected workspace manifest dependencies to be cached");
+
+    assert_eq!(
+        cached,
+        (
+            vec!["1.0.0".to_string(), "1.0.1".to_string()],
+            vec!["4.17.21".to_string()]
+        )
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rename_updates_version_of_dependency_in_every_section() {
+    // 1. Setup real Cache with test data
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::CratesIo,
+        &[("tokio", vec!["1.35.0", "1.40.0"])],
+    );
+
+    // 2. Setup mock Registry and resolver
+    let registry =
+        MockRegistry::new(RegistryType::CratesIo).with_versions("tokio", vec!["1.35.0", "1.40.0"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::CratesIo,
+        create_test_resolver(RegistryType::CratesIo, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen with tokio in both dependency sections
+    let uri = "file:///test/Cargo.toml";
+    let cargo_toml = r#"[dependencies]
+tokio = "1.35"
+
+[dev-dependencies]
+tokio = "1.35"
+"#;
+
+    service
+        .call(create_did_open_notification(uri, cargo_toml))
+        .await
+        .unwrap();
+
+    wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+        .await
+        .expect("Expected publishDiagnostics notification");
+
+    let version_range = |line| Range {
+        start: Position { line, character: 9 },
+        end: Position {
+            line,
+            character: 13,
+        },
+    };
+
+    // 6. prepareRename covers the version token, and rejects other positions
+    let response = service
+        .call(create_prepare_rename_request(2, uri, 1, 10))
+        .await
+        .unwrap()
+        .expect("Expected prepareRename response");
+    let prepared: Option<PrepareRenameResponse> =
+        serde_json::from_value(response.result().unwrap().clone()).unwrap();
+    assert_eq!(
+        prepared,
+        Some(PrepareRenameResponse::Range(version_range(1)))
     );
+
+    let response = service
+        .call(create_prepare_rename_request(3, uri, 1, 2))
+        .await
+        .unwrap()
+        .expect("Expected prepareRename response");
+    assert_eq!(response.result().unwrap(), &serde_json::Value::Null);
+
+    // 7. rename edits every declaration of tokio
+    let response = service
+        .call(create_rename_request(4, uri, 1, 10, "1.40"))
+        .await
+        .unwrap()
+        .expect("Expected rename response");
+    let edit: Option<WorkspaceEdit> =
+        serde_json::from_value(response.result().unwrap().clone()).unwrap();
+
+    let text_edit = |line| TextEdit {
+        range: version_range(line),
+        new_text: "1.40".to_string(),
+    };
+    assert_eq!(
+        edit.expect("Expected workspace edit").changes,
+        Some(HashMap::from([(
+            uri.parse().unwrap(),
+            vec![text_edit(1), text_edit(4)]
+        )]))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn clears_diagnostics_when_registry_is_disabled_by_configuration_change() {
+    let (_temp_dir, cache) =
+        create_test_cache(RegistryType::CratesIo, &[("serde", vec!["1.0.0", "1.1.0"])]);
+    let registry =
+        MockRegistry::new(RegistryType::CratesIo).with_versions("serde", vec!["1.0.0", "1.1.0"]);
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::CratesIo,
+        create_test_resolver(RegistryType::CratesIo, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let settings = Arc::new(Mutex::new(serde_json::json!({})));
+    let mut notification_rx = spawn_notification_collector_with_settings(socket, settings.clone());
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    service
+        .call(create_did_open_notification(
+            "file:///test/Cargo.toml",
+            "[dependencies]\nserde = \"=1.0.0\"\n",
+        ))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+
+    // The user disables crates.io in their editor settings
+    *settings.lock().unwrap() = serde_json::json!({
+        "registries": { "crates": { "enabled": false } }
+    });
+    service
+        .call(create_did_change_configuration_notification())
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected diagnostics to be republished");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.uri.as_str(), "file:///test/Cargo.toml");
+    assert_eq!(params.version, Some(1));
+    assert!(params.diagnostics.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn folds_dependency_tables() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::CratesIo,
+        &[
+            ("serde", vec!["1.0.100", "1.0.200"]),
+            ("rstest", vec!["0.23.0"]),
+        ],
+    );
+    let registry = MockRegistry::new(RegistryType::CratesIo)
+        .with_versions("serde", vec!["1.0.100", "1.0.200"])
+        .with_versions("rstest", vec!["0.23.0"]);
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::CratesIo,
+        create_test_resolver(RegistryType::CratesIo, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    let cargo_toml = r#"[package]
+name = "test-project"
+
+[dependencies]
+serde = "1.0.200"
+anyhow = { path = "../anyhow" }
+
+[dev-dependencies]
+rstest = "0.23.0"
+"#;
+    service
+        .call(create_did_open_notification(
+            "file:///test/Cargo.toml",
+            cargo_toml,
+        ))
+        .await
+        .unwrap();
+    wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+        .await
+        .expect("Expected publishDiagnostics notification");
+
+    let response = service
+        .call(create_folding_range_request(2, "file:///test/Cargo.toml"))
+        .await
+        .unwrap()
+        .expect("Expected foldingRange response");
+    let ranges: Vec<FoldingRange> =
+        serde_json::from_value(response.result().unwrap().clone()).unwrap();
+
+    let folded: Vec<(u32, u32)> = ranges
+        .iter()
+        .map(|range| (range.start_line, range.end_line))
+        .collect();
+    assert_eq!(folded, vec![(3, 4), (7, 8)]);
 }
@@ -1,4 +1,4 @@
-//! Docker (compose.yaml) E2E tests
+//! Docker (compose.yaml, Dockerfile) E2E tests
 
 mod helper;
 
@@ -75,7 +75,7 @@ async fn publishes_outdated_version_warning() {
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: 1.25 -> 1.27"
+        "Update available on Docker: 1.25 -> 1.27"
     );
 }
 
@@ -242,6 +242,70 @@ async fn handles_suffixed_tag_comparison() {
     // Should suggest 1.27-alpine (same suffix available)
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: 1.25-alpine -> 1.27-alpine"
+        "Update available on Docker: 1.25-alpine -> 1.27-alpine"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dockerfile_publishes_outdated_version_warning() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Docker,
+        &[(
+            "library/python",
+            vec!["3.11-slim", "3.11", "3.13-slim", "3.13"],
+        )],
+    );
+
+    let registry = MockRegistry::new(RegistryType::Docker).with_versions(
+        "library/python",
+        vec!["3.11-slim", "3.11", "3.13-slim", "3.13"],
+    );
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Docker,
+        create_test_resolver(RegistryType::Docker, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // The final stage refers to the builder stage, which has no tag
+    let dockerfile = r#"FROM python:3.11-slim AS builder
+RUN pip install --prefix=/install flask
+
+FROM builder
+"#;
+
+    service
+        .call(create_did_open_notification(
+            "file:///test/Dockerfile",
+            dockerfile,
+        ))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on Docker: 3.11-slim -> 3.13-slim"
+    );
+    assert_eq!(
+        params.diagnostics[0].range,
+        Range::new(Position::new(0, 12), Position::new(0, 21))
     );
 }
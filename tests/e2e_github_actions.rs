@@ -89,7 +89,7 @@ jobs:
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: 3.0.0 -> 4.0.0"
+        "Update available on GitHub Actions: 3.0.0 -> 4.0.0"
     );
 }
 
@@ -310,7 +310,7 @@ jobs:
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: 3.0.0 -> 4.0.0"
+        "Update available on GitHub Actions: 3.0.0 -> 4.0.0"
     );
 }
 
@@ -408,6 +408,10 @@ async fn code_action_returns_bump_actions_for_hash_with_comment() {
     // Mock GitHub Tags API (called twice: once for patch, once for minor)
     let mock = server
         .mock("GET", "/repos/actions/checkout/tags")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "per_page".to_string(),
+            "100".to_string(),
+        ))
         .with_status(200)
         .with_header("content-type", "application/json")
         .with_body(
@@ -542,6 +546,10 @@ async fn code_action_returns_bump_actions_for_hash_only() {
     // Mock GitHub Tags API
     let mock = server
         .mock("GET", "/repos/actions/checkout/tags")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "per_page".to_string(),
+            "100".to_string(),
+        ))
         .with_status(200)
         .with_header("content-type", "application/json")
         .with_body(
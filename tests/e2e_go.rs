@@ -1,4 +1,4 @@
-//! Go (go.mod) E2E tests
+//! Go (go.mod, go.work) E2E tests
 
 mod helper;
 
@@ -9,13 +9,14 @@ use tower_lsp::LspService;
 use tower_lsp::lsp_types::*;
 
 use helper::{
-    MockRegistry, create_did_open_notification, create_initialize_request,
+    MockRegistry, create_did_open_notification, create_hover_request, create_initialize_request,
     create_initialized_notification, create_test_cache, create_test_resolver,
     spawn_notification_collector, wait_for_notification,
 };
 use version_lsp::lsp::backend::Backend;
 use version_lsp::lsp::resolver::PackageResolver;
 use version_lsp::parser::types::RegistryType;
+use version_lsp::version::checker::VersionStorer;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn publishes_outdated_version_warning() {
@@ -75,7 +76,83 @@ require golang.org/x/text v0.12.0
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: v0.12.0 -> v0.14.0"
+        "Update available on Go Proxy: v0.12.0 -> v0.14.0"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_outdated_go_directive_warning() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::GoProxy,
+        &[("golang.org/x/text", vec!["v0.14.0"])],
+    );
+    cache
+        .replace_versions(
+            RegistryType::GoToolchain,
+            "go",
+            vec![
+                "1.21.0".to_string(),
+                "1.22.0".to_string(),
+                "1.22.1".to_string(),
+            ],
+            &[],
+        )
+        .unwrap();
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([
+        (
+            RegistryType::GoProxy,
+            create_test_resolver(
+                RegistryType::GoProxy,
+                MockRegistry::new(RegistryType::GoProxy)
+                    .with_versions("golang.org/x/text", vec!["v0.14.0"]),
+            ),
+        ),
+        (
+            RegistryType::GoToolchain,
+            create_test_resolver(
+                RegistryType::GoToolchain,
+                MockRegistry::new(RegistryType::GoToolchain)
+                    .with_versions("go", vec!["1.21.0", "1.22.0", "1.22.1"]),
+            ),
+        ),
+    ]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    let go_mod = r#"module example.com/myapp
+
+go 1.21
+
+require golang.org/x/text v0.14.0
+"#;
+
+    service
+        .call(create_did_open_notification("file:///test/go.mod", go_mod))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(params.diagnostics[0].range.start, Position::new(2, 3));
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on Go: 1.21 -> 1.22.1"
     );
 }
 
@@ -256,6 +333,220 @@ require (
     // Both diagnostics should be warnings about outdated versions
     for diag in &params.diagnostics {
         assert_eq!(diag.severity, Some(DiagnosticSeverity::WARNING));
-        assert!(diag.message.starts_with("Update available:"));
+        assert!(diag.message.starts_with("Update available on Go Proxy:"));
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn go_work_publishes_outdated_version_warning() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::GoProxy,
+        &[
+            ("golang.org/x/text", vec!["v0.12.0", "v0.14.0"]),
+            ("golang.org/x/net", vec!["v0.19.0", "v0.20.0"]),
+        ],
+    );
+
+    let registry = MockRegistry::new(RegistryType::GoProxy)
+        .with_versions("golang.org/x/text", vec!["v0.12.0", "v0.14.0"])
+        .with_versions("golang.org/x/net", vec!["v0.19.0", "v0.20.0"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::GoProxy,
+        create_test_resolver(RegistryType::GoProxy, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // Only the require entry is checked; use and replace are skipped
+    let go_work = r#"go 1.22
+
+use (
+	./api
+	./web
+)
+
+require golang.org/x/text v0.12.0
+
+replace golang.org/x/net v0.19.0 => ../net
+"#;
+
+    service
+        .call(create_did_open_notification(
+            "file:///test/go.work",
+            go_work,
+        ))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on Go Proxy: v0.12.0 -> v0.14.0"
+    );
+    assert_eq!(params.diagnostics[0].range.start.line, 7);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_information_for_module_missing_from_go_sum() {
+    // 1. Setup real Cache with test data (oldest first, newest last)
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::GoProxy,
+        &[
+            ("golang.org/x/text", vec!["v0.13.0", "v0.14.0"]),
+            ("golang.org/x/net", vec!["v0.19.0", "v0.20.0"]),
+        ],
+    );
+
+    // 2. Setup mock Registry and resolver
+    let registry = MockRegistry::new(RegistryType::GoProxy)
+        .with_versions("golang.org/x/text", vec!["v0.13.0", "v0.14.0"])
+        .with_versions("golang.org/x/net", vec!["v0.19.0", "v0.20.0"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::GoProxy,
+        create_test_resolver(RegistryType::GoProxy, registry),
+    )]);
+
+    // 3. Write go.mod and a go.sum that only covers golang.org/x/text
+    let module_dir = tempfile::TempDir::new().unwrap();
+    let go_mod = r#"module example.com/myapp
+
+go 1.21
+
+require (
+	golang.org/x/text v0.14.0
+	golang.org/x/net v0.20.0
+)
+"#;
+    std::fs::write(module_dir.path().join("go.mod"), go_mod).unwrap();
+    std::fs::write(
+        module_dir.path().join("go.sum"),
+        "golang.org/x/text v0.14.0 h1:ScX5w1eTa3QqT8oi6+ziP7dTV1S2+ALU0bI+0zXKWiQ=\n",
+    )
+    .unwrap();
+    let go_mod_uri = Url::from_file_path(module_dir.path().join("go.mod")).unwrap();
+
+    // 4. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 5. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 6. didOpen go.mod
+    service
+        .call(create_did_open_notification(go_mod_uri.as_str(), go_mod))
+        .await
+        .unwrap();
+
+    // 7. Receive publishDiagnostics notification
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].severity,
+        Some(DiagnosticSeverity::INFORMATION)
+    );
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Missing go.sum entry for golang.org/x/net v0.20.0"
+    );
+    assert_eq!(params.diagnostics[0].range.start.line, 6);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn describes_commit_of_outdated_pseudo_version() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::GoProxy,
+        &[("github.com/some/repo", vec!["v1.0.0", "v1.5.0"])],
+    );
+    let registry = MockRegistry::new(RegistryType::GoProxy)
+        .with_versions("github.com/some/repo", vec!["v1.0.0", "v1.5.0"]);
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::GoProxy,
+        create_test_resolver(RegistryType::GoProxy, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    let go_mod = r#"module example.com/myapp
+
+require github.com/some/repo v0.0.0-20210101000000-abcdef123456
+"#;
+    service
+        .call(create_did_open_notification("file:///test/go.mod", go_mod))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Pinned to unreleased commit from 2021-01-01; latest release is v1.5.0"
+    );
+
+    let response = service
+        .call(create_hover_request(2, "file:///test/go.mod", 2, 35))
+        .await
+        .unwrap()
+        .expect("Expected hover response");
+    let hover: Option<Hover> = serde_json::from_value(response.result().unwrap().clone()).unwrap();
+
+    let HoverContents::Markup(contents) = hover.expect("Expected hover").contents else {
+        panic!("Expected markup hover contents");
+    };
+    assert_eq!(contents.kind, MarkupKind::Markdown);
+    // The versions were cached when the test started
+    assert!(
+        contents.value.starts_with(
+            "**github.com/some/repo** v0.0.0-20210101000000-abcdef123456 → latest: v1.5.0\n\n\
+             Update available · [View on Go Proxy](https://pkg.go.dev/github.com/some/repo)\n\n\
+             Pinned to commit `abcdef12` from 2021-01-01\n\nLast updated: "
+        ),
+        "unexpected hover: {}",
+        contents.value
+    );
+    assert!(contents.value.ends_with(" UTC (just now)"));
+}
@@ -77,7 +77,7 @@ async fn publishes_outdated_version_warning() {
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: 1.0.1 -> 1.0.2"
+        "Update available on JSR: 1.0.1 -> 1.0.2"
     );
 }
 
@@ -0,0 +1,85 @@
+//! Node.js (.nvmrc, .node-version) E2E tests
+
+mod helper;
+
+use std::collections::HashMap;
+
+use tower::Service;
+use tower_lsp::LspService;
+use tower_lsp::lsp_types::*;
+
+use helper::{
+    MockRegistry, create_did_open_notification, create_initialize_request,
+    create_initialized_notification, create_test_cache, create_test_resolver,
+    spawn_notification_collector, wait_for_notification,
+};
+use version_lsp::lsp::backend::Backend;
+use version_lsp::lsp::resolver::PackageResolver;
+use version_lsp::parser::types::RegistryType;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_outdated_node_version_warning() {
+    // 1. Setup real Cache with test data (oldest first, newest last)
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::NodeRelease,
+        &[("node", vec!["18.17.0", "18.19.0", "20.11.0"])],
+    );
+
+    // 2. Setup mock Registry and resolver
+    let registry = MockRegistry::new(RegistryType::NodeRelease)
+        .with_versions("node", vec!["18.17.0", "18.19.0", "20.11.0"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::NodeRelease,
+        create_test_resolver(RegistryType::NodeRelease, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen a .nvmrc pinned to an older major
+    service
+        .call(create_did_open_notification(
+            "file:///test/.nvmrc",
+            "v18.17.0\n",
+        ))
+        .await
+        .unwrap();
+
+    // 6. Receive publishDiagnostics notification
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on Node.js: v18.17.0 -> 20.11.0"
+    );
+    assert_eq!(
+        params.diagnostics[0].range,
+        Range {
+            start: Position {
+                line: 0,
+                character: 0
+            },
+            end: Position {
+                line: 0,
+                character: 8
+            },
+        }
+    );
+}
@@ -3,19 +3,29 @@
 mod helper;
 
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use tower::Service;
 use tower_lsp::LspService;
 use tower_lsp::lsp_types::*;
 
 use helper::{
-    MockRegistry, create_did_open_notification, create_initialize_request,
-    create_initialized_notification, create_test_cache, create_test_resolver,
-    spawn_notification_collector, wait_for_notification,
+    MockRegistry, create_code_action_range_request, create_completion_request,
+    create_did_change_configuration_notification, create_did_change_notification,
+    create_did_open_notification, create_did_save_notification, create_execute_command_request,
+    create_execute_command_request_with_arguments, create_hover_request, create_initialize_request,
+    create_initialized_notification, create_inlay_hint_request,
+    create_linked_editing_range_request, create_source_code_action_request, create_test_cache,
+    create_test_resolver, create_workspace_symbol_request, spawn_notification_collector,
+    spawn_notification_collector_with_settings, wait_for_notification,
+    wait_for_notification_within,
 };
-use version_lsp::lsp::backend::Backend;
+use version_lsp::lsp::backend::{Backend, CACHE_STATS_COMMAND, DIAGNOSE_DOCUMENT_COMMAND};
 use version_lsp::lsp::resolver::PackageResolver;
 use version_lsp::parser::types::RegistryType;
+use version_lsp::version::checker::VersionStorer;
+use version_lsp::version::types::PackageMetadata;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn publishes_outdated_version_warning() {
@@ -78,7 +88,7 @@ async fn publishes_outdated_version_warning() {
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: 4.17.20 -> 4.17.21"
+        "Update available on npm: 4.17.20 -> 4.17.21"
     );
 }
 
@@ -254,3 +264,1065 @@ async fn caret_range_is_latest_when_satisfied() {
         serde_json::from_value(notification.params().unwrap().clone()).unwrap();
     assert!(params.diagnostics.is_empty());
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn code_action_for_selection_offers_update_all() {
+    // 1. Setup real Cache with test data (oldest first, newest last)
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Npm,
+        &[
+            ("lodash", vec!["4.17.20", "4.17.21"]),
+            ("react", vec!["17.0.2", "18.2.0"]),
+        ],
+    );
+
+    // 2. Setup mock Registry and resolver
+    let registry = MockRegistry::new(RegistryType::Npm)
+        .with_versions("lodash", vec!["4.17.20", "4.17.21"])
+        .with_versions("react", vec!["17.0.2", "18.2.0"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen with two outdated packages
+    let uri = "file:///test/package.json";
+    let package_json = r#"{
+  "dependencies": {
+    "lodash": "4.17.20",
+    "react": "^17.0.2"
+  }
+}"#;
+
+    service
+        .call(create_did_open_notification(uri, package_json))
+        .await
+        .unwrap();
+
+    wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+        .await
+        .expect("Expected publishDiagnostics notification");
+
+    // 6. Request code actions for a selection covering both dependencies
+    let range = Range {
+        start: Position {
+            line: 1,
+            character: 0,
+        },
+        end: Position {
+            line: 4,
+            character: 0,
+        },
+    };
+    let response = service
+        .call(create_code_action_range_request(2, uri, range))
+        .await
+        .unwrap()
+        .expect("Expected code action response");
+    let actions: Option<Vec<CodeActionOrCommand>> =
+        serde_json::from_value(response.result().unwrap().clone()).unwrap();
+
+    let titles: Vec<String> = actions
+        .expect("Expected code actions")
+        .into_iter()
+        .filter_map(|action| match action {
+            CodeActionOrCommand::CodeAction(action) => Some(action.title),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            "lodash: Upgrade to latest patch: 4.17.21",
+            "react: Upgrade to latest major: ^18.2.0",
+            "Update all in selection (2 packages)",
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn code_action_for_whole_document_offers_update_all_outdated() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Npm,
+        &[
+            ("lodash", vec!["4.17.20", "4.17.21"]),
+            ("react", vec!["17.0.2", "18.2.0"]),
+        ],
+    );
+    let registry = MockRegistry::new(RegistryType::Npm)
+        .with_versions("lodash", vec!["4.17.20", "4.17.21"])
+        .with_versions("react", vec!["17.0.2", "18.2.0"]);
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    let uri = "file:///test/package.json";
+    let package_json = r#"{
+  "dependencies": {
+    "lodash": "4.17.20",
+    "react": "^17.0.2"
+  }
+}"#;
+    service
+        .call(create_did_open_notification(uri, package_json))
+        .await
+        .unwrap();
+    wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+        .await
+        .expect("Expected publishDiagnostics notification");
+
+    let code_actions = |response: tower_lsp::jsonrpc::Response| -> Vec<CodeAction> {
+        let actions: Option<Vec<CodeActionOrCommand>> =
+            serde_json::from_value(response.result().unwrap().clone()).unwrap();
+        actions
+            .expect("Expected code actions")
+            .into_iter()
+            .filter_map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => Some(action),
+                _ => None,
+            })
+            .collect()
+    };
+
+    // A selection of the whole document
+    let range = Range::new(Position::new(0, 0), Position::new(5, 1));
+    let response = service
+        .call(create_code_action_range_request(2, uri, range))
+        .await
+        .unwrap()
+        .expect("Expected code action response");
+    let titles: Vec<String> = code_actions(response)
+        .into_iter()
+        .map(|action| action.title)
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            "lodash: Upgrade to latest patch: 4.17.21",
+            "react: Upgrade to latest major: ^18.2.0",
+            "Update all outdated dependencies",
+        ]
+    );
+
+    // A request for source actions only
+    let response = service
+        .call(create_source_code_action_request(3, uri))
+        .await
+        .unwrap()
+        .expect("Expected code action response");
+    let actions = code_actions(response);
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].title, "Update all outdated dependencies");
+    assert_eq!(actions[0].kind, Some(CodeActionKind::SOURCE));
+    let changes = actions[0].edit.clone().unwrap().changes.unwrap();
+    let new_texts: Vec<&str> = changes[&uri.parse::<Url>().unwrap()]
+        .iter()
+        .map(|edit| edit.new_text.as_str())
+        .collect();
+    assert_eq!(new_texts, vec!["4.17.21", "^18.2.0"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn linked_editing_range_links_same_dependency_in_several_sections() {
+    // 1. Setup real Cache with test data
+    let (_temp_dir, cache) =
+        create_test_cache(RegistryType::Npm, &[("lodash", vec!["4.17.20", "4.17.21"])]);
+
+    // 2. Setup mock Registry and resolver
+    let registry =
+        MockRegistry::new(RegistryType::Npm).with_versions("lodash", vec!["4.17.20", "4.17.21"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen with lodash in both dependency sections
+    let uri = "file:///test/package.json";
+    let package_json = r#"{
+  "dependencies": {
+    "lodash": "4.17.20"
+  },
+  "devDependencies": {
+    "lodash": "4.17.20"
+  }
+}"#;
+
+    service
+        .call(create_did_open_notification(uri, package_json))
+        .await
+        .unwrap();
+
+    wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+        .await
+        .expect("Expected publishDiagnostics notification");
+
+    // 6. Request linked editing ranges at the first version
+    let response = service
+        .call(create_linked_editing_range_request(2, uri, 2, 17))
+        .await
+        .unwrap()
+        .expect("Expected linkedEditingRange response");
+    let ranges: Option<LinkedEditingRanges> =
+        serde_json::from_value(response.result().unwrap().clone()).unwrap();
+
+    let version_range = |line| Range {
+        start: Position {
+            line,
+            character: 15,
+        },
+        end: Position {
+            line,
+            character: 22,
+        },
+    };
+    assert_eq!(
+        ranges.expect("Expected linked ranges").ranges,
+        vec![version_range(2), version_range(5)]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn completion_offers_cached_versions_keeping_operator() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Npm,
+        &[("lodash", vec!["4.17.19", "4.17.20", "4.17.21"])],
+    );
+
+    let registry = MockRegistry::new(RegistryType::Npm)
+        .with_versions("lodash", vec!["4.17.19", "4.17.20", "4.17.21"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    let uri = "file:///test/package.json";
+    let package_json = r#"{
+  "dependencies": {
+    "lodash": "^4.17.19"
+  }
+}"#;
+
+    service
+        .call(create_did_open_notification(uri, package_json))
+        .await
+        .unwrap();
+
+    wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+        .await
+        .expect("Expected publishDiagnostics notification");
+
+    let response = service
+        .call(create_completion_request(2, uri, 2, 17))
+        .await
+        .unwrap()
+        .expect("Expected completion response");
+    let completions: Option<CompletionResponse> =
+        serde_json::from_value(response.result().unwrap().clone()).unwrap();
+
+    let Some(CompletionResponse::Array(items)) = completions else {
+        panic!("Expected completion items");
+    };
+    let items: Vec<(String, String)> = items
+        .into_iter()
+        .map(|item| {
+            let Some(CompletionTextEdit::Edit(edit)) = item.text_edit else {
+                panic!("Expected a text edit");
+            };
+            (item.label, edit.new_text)
+        })
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            ("4.17.21 (latest)".to_string(), "^4.17.21".to_string()),
+            (
+                "4.17.20 (1 release behind)".to_string(),
+                "^4.17.20".to_string()
+            ),
+            (
+                "4.17.19 (2 releases behind)".to_string(),
+                "^4.17.19".to_string()
+            ),
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn inlay_hints_show_latest_version_when_enabled() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Npm,
+        &[
+            ("lodash", vec!["4.17.20", "4.17.21"]),
+            ("react", vec!["18.2.0"]),
+        ],
+    );
+    let registry = MockRegistry::new(RegistryType::Npm)
+        .with_versions("lodash", vec!["4.17.20", "4.17.21"])
+        .with_versions("react", vec!["18.2.0"]);
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let settings = Arc::new(Mutex::new(serde_json::json!({})));
+    let mut notification_rx = spawn_notification_collector_with_settings(socket, settings.clone());
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    let uri = "file:///test/package.json";
+    let package_json = r#"{
+  "dependencies": {
+    "lodash": "4.17.20",
+    "react": "^18.0.0"
+  }
+}"#;
+    service
+        .call(create_did_open_notification(uri, package_json))
+        .await
+        .unwrap();
+    wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+        .await
+        .expect("Expected publishDiagnostics notification");
+
+    let range = Range::new(Position::new(0, 0), Position::new(5, 1));
+    let inlay_hints = |response: tower_lsp::jsonrpc::Response| -> Option<Vec<(Position, String)>> {
+        let hints: Option<Vec<InlayHint>> =
+            serde_json::from_value(response.result().unwrap().clone()).unwrap();
+        hints.map(|hints| {
+            hints
+                .into_iter()
+                .map(|hint| {
+                    let InlayHintLabel::String(label) = hint.label else {
+                        panic!("Expected a string label");
+                    };
+                    (hint.position, label)
+                })
+                .collect()
+        })
+    };
+
+    // Off by default
+    let response = service
+        .call(create_inlay_hint_request(2, uri, range))
+        .await
+        .unwrap()
+        .expect("Expected inlayHint response");
+    assert_eq!(inlay_hints(response), None);
+
+    *settings.lock().unwrap() = serde_json::json!({ "inlayHints": true });
+    service
+        .call(create_did_change_configuration_notification())
+        .await
+        .unwrap();
+    wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+        .await
+        .expect("Expected diagnostics to be republished");
+
+    let response = service
+        .call(create_inlay_hint_request(3, uri, range))
+        .await
+        .unwrap()
+        .expect("Expected inlayHint response");
+    assert_eq!(
+        inlay_hints(response),
+        Some(vec![
+            (Position::new(2, 23), "→ 4.17.21".to_string()),
+            (Position::new(3, 22), "✓ up-to-date".to_string()),
+        ])
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn configured_not_found_ttl_is_applied_to_cache() {
+    let (_temp_dir, cache) = create_test_cache(RegistryType::Npm, &[]);
+    cache.try_start_fetch(RegistryType::Npm, "loadsh").unwrap();
+    cache.mark_not_found(RegistryType::Npm, "loadsh").unwrap();
+    cache.finish_fetch(RegistryType::Npm, "loadsh").unwrap();
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, MockRegistry::new(RegistryType::Npm)),
+    )]);
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+    let settings = Arc::new(Mutex::new(serde_json::json!({})));
+    let _notification_rx = spawn_notification_collector_with_settings(socket, settings.clone());
+
+    service.call(create_initialize_request(1)).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    // The default TTL of an hour still suppresses the fetch
+    assert!(!cache.try_start_fetch(RegistryType::Npm, "loadsh").unwrap());
+
+    *settings.lock().unwrap() = serde_json::json!({ "cache": { "notFoundTtl": 100 } });
+    service
+        .call(create_did_change_configuration_notification())
+        .await
+        .unwrap();
+
+    assert!(cache.try_start_fetch(RegistryType::Npm, "loadsh").unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cache_stats_command_returns_package_counts_per_registry() {
+    // 1. Setup real Cache with test data
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Npm,
+        &[
+            ("lodash", vec!["4.17.20", "4.17.21"]),
+            ("react", vec!["18.2.0"]),
+        ],
+    );
+
+    // 2. Setup mock Registry and resolver
+    let registry = MockRegistry::new(RegistryType::Npm);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, _socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+
+    // 5. Execute the cache stats command
+    let response = service
+        .call(create_execute_command_request(2, CACHE_STATS_COMMAND))
+        .await
+        .unwrap()
+        .expect("Expected executeCommand response");
+
+    assert_eq!(response.result().unwrap(), &serde_json::json!({ "npm": 2 }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn diagnose_document_command_refetches_cached_packages() {
+    // 1. Setup real Cache whose versions are behind the registry
+    let (_temp_dir, cache) = create_test_cache(RegistryType::Npm, &[("lodash", vec!["4.17.20"])]);
+
+    // 2. Setup mock Registry and resolver
+    let registry =
+        MockRegistry::new(RegistryType::Npm).with_versions("lodash", vec!["4.17.20", "4.17.21"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen: the cached versions say 4.17.20 is the latest
+    let package_json = r#"{
+  "dependencies": {
+    "lodash": "4.17.20"
+  }
+}"#;
+    service
+        .call(create_did_open_notification(
+            "file:///test/package.json",
+            package_json,
+        ))
+        .await
+        .unwrap();
+    wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+        .await
+        .expect("Expected publishDiagnostics notification");
+
+    // 6. Re-check the document on demand
+    let response = service
+        .call(create_execute_command_request_with_arguments(
+            2,
+            DIAGNOSE_DOCUMENT_COMMAND,
+            vec![serde_json::json!({ "uri": "file:///test/package.json" })],
+        ))
+        .await
+        .unwrap()
+        .expect("Expected executeCommand response");
+
+    assert_eq!(
+        response.result().unwrap(),
+        &serde_json::json!({ "fetched": 1, "diagnostics": 1 })
+    );
+    assert_eq!(
+        cache
+            .get_versions(RegistryType::Npm, "lodash", false)
+            .unwrap(),
+        vec!["4.17.20", "4.17.21"]
+    );
+
+    // 7. Documents that are not open are rejected
+    let response = service
+        .call(create_execute_command_request_with_arguments(
+            3,
+            DIAGNOSE_DOCUMENT_COMMAND,
+            vec![serde_json::json!({ "uri": "file:///test/other/package.json" })],
+        ))
+        .await
+        .unwrap()
+        .expect("Expected executeCommand response");
+
+    assert!(response.error().is_some());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn drops_diagnostics_of_background_fetch_for_outdated_document_version() {
+    // 1. Setup empty Cache, so opening the document spawns a fetch
+    let (_temp_dir, cache) = create_test_cache(RegistryType::Npm, &[]);
+
+    // 2. Setup a slow mock Registry and resolver
+    let registry = MockRegistry::new(RegistryType::Npm)
+        .with_versions("lodash", vec!["4.17.20", "4.17.21"])
+        .with_delay(Duration::from_millis(500));
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen (version 1) with an outdated dependency that is not cached yet
+    let uri = "file:///test/package.json";
+    let package_json = r#"{
+  "dependencies": {
+    "lodash": "4.17.20"
+  }
+}"#;
+    service
+        .call(create_did_open_notification(uri, package_json))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.version, Some(1));
+    assert!(params.diagnostics.is_empty());
+
+    // 6. didChange (version 2) removes the dependency while lodash is fetched
+    service
+        .call(create_did_change_notification(
+            uri,
+            r#"{ "dependencies": {} }"#,
+            2,
+        ))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.version, Some(2));
+    assert!(params.diagnostics.is_empty());
+
+    // 7. The fetch spawned for version 1 finishes but must not republish
+    let notification = wait_for_notification_within(
+        &mut notification_rx,
+        "textDocument/publishDiagnostics",
+        Duration::from_millis(1500),
+    )
+    .await;
+    assert!(
+        notification.is_none(),
+        "Unexpected publishDiagnostics: {:?}",
+        notification
+    );
+    assert_eq!(
+        cache
+            .get_versions(RegistryType::Npm, "lodash", false)
+            .unwrap(),
+        vec!["4.17.20", "4.17.21"]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reports_dev_dependency_outside_peer_range_when_enabled() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Npm,
+        &[("react", vec!["16.14.0", "17.0.2", "18.3.1"])],
+    );
+    let registry = MockRegistry::new(RegistryType::Npm)
+        .with_versions("react", vec!["16.14.0", "17.0.2", "18.3.1"]);
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let settings = Arc::new(Mutex::new(serde_json::json!({})));
+    let mut notification_rx = spawn_notification_collector_with_settings(socket, settings.clone());
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    let package_json = r#"{
+  "name": "my-react-lib",
+  "devDependencies": {
+    "react": "^16.14.0"
+  },
+  "peerDependencies": {
+    "react": ">=17.0.0"
+  }
+}"#;
+    service
+        .call(create_did_open_notification(
+            "file:///test/package.json",
+            package_json,
+        ))
+        .await
+        .unwrap();
+
+    // Off by default: only the outdated devDependency is reported
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    let messages: Vec<&str> = params
+        .diagnostics
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        vec!["Update available on npm: ^16.14.0 -> 18.3.1"]
+    );
+
+    *settings.lock().unwrap() = serde_json::json!({ "checkPeerDependencies": true });
+    service
+        .call(create_did_change_configuration_notification())
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected diagnostics to be republished");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    let peer_diagnostic = params
+        .diagnostics
+        .iter()
+        .find(|d| d.severity == Some(DiagnosticSeverity::INFORMATION))
+        .expect("Expected peer dependency diagnostic");
+    assert_eq!(
+        peer_diagnostic.message,
+        "react ^16.14.0 does not satisfy peerDependencies range >=17.0.0"
+    );
+    assert_eq!(peer_diagnostic.range.start, Position::new(3, 14));
+    assert_eq!(peer_diagnostic.code, Some(NumberOrString::Number(1008)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reports_unpublished_own_version_when_enabled() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Npm,
+        &[
+            ("my-lib", vec!["1.2.0", "1.2.2"]),
+            ("lodash", vec!["4.17.21"]),
+        ],
+    );
+    let registry = MockRegistry::new(RegistryType::Npm)
+        .with_versions("my-lib", vec!["1.2.0", "1.2.2"])
+        .with_versions("lodash", vec!["4.17.21"]);
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let settings = Arc::new(Mutex::new(serde_json::json!({})));
+    let mut notification_rx = spawn_notification_collector_with_settings(socket, settings.clone());
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    let package_json = r#"{
+  "name": "my-lib",
+  "version": "1.2.3",
+  "dependencies": {
+    "lodash": "4.17.21"
+  }
+}"#;
+    service
+        .call(create_did_open_notification(
+            "file:///test/package.json",
+            package_json,
+        ))
+        .await
+        .unwrap();
+
+    // Off by default: the own version is not checked
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert!(params.diagnostics.is_empty());
+
+    *settings.lock().unwrap() = serde_json::json!({ "checkOwnVersion": true });
+    service
+        .call(create_did_change_configuration_notification())
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected diagnostics to be republished");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Version 1.2.3 not yet published to npm"
+    );
+    assert_eq!(
+        params.diagnostics[0].severity,
+        Some(DiagnosticSeverity::INFORMATION)
+    );
+    assert_eq!(params.diagnostics[0].range.start, Position::new(2, 14));
+    assert_eq!(
+        params.diagnostics[0].code,
+        Some(NumberOrString::Number(1009))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn workspace_symbol_finds_package_in_every_open_document() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Npm,
+        &[("lodash", vec!["4.17.21"]), ("react", vec!["18.2.0"])],
+    );
+    let registry = MockRegistry::new(RegistryType::Npm)
+        .with_versions("lodash", vec!["4.17.21"])
+        .with_versions("react", vec!["18.2.0"]);
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    for (uri, package_json) in [
+        (
+            "file:///test/packages/web/package.json",
+            r#"{
+  "dependencies": {
+    "lodash": "^4.17.20",
+    "react": "18.2.0"
+  }
+}"#,
+        ),
+        (
+            "file:///test/packages/api/package.json",
+            r#"{
+  "dependencies": {
+    "lodash": "4.17.21"
+  }
+}"#,
+        ),
+    ] {
+        service
+            .call(create_did_open_notification(uri, package_json))
+            .await
+            .unwrap();
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+    }
+
+    let response = service
+        .call(create_workspace_symbol_request(2, "Lodash"))
+        .await
+        .unwrap()
+        .expect("Expected workspace/symbol response");
+    let symbols: Vec<SymbolInformation> =
+        serde_json::from_value(response.result().unwrap().clone()).unwrap();
+
+    let found: Vec<(&str, &str, Position)> = symbols
+        .iter()
+        .map(|symbol| {
+            assert_eq!(symbol.kind, SymbolKind::PACKAGE);
+            (
+                symbol.name.as_str(),
+                symbol.location.uri.as_str(),
+                symbol.location.range.start,
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (
+                "lodash 4.17.21",
+                "file:///test/packages/api/package.json",
+                Position::new(2, 15)
+            ),
+            (
+                "lodash ^4.17.20",
+                "file:///test/packages/web/package.json",
+                Position::new(2, 15)
+            ),
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn save_refetches_fresh_packages_and_republishes_diagnostics() {
+    // 1. Setup Cache with fresh data that predates the latest release
+    let (_temp_dir, cache) = create_test_cache(RegistryType::Npm, &[("lodash", vec!["4.17.20"])]);
+
+    // 2. Setup mock Registry that already has the new release
+    let registry =
+        MockRegistry::new(RegistryType::Npm).with_versions("lodash", vec!["4.17.20", "4.17.21"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen: the cache is fresh, so nothing is fetched
+    let package_json = r#"{
+  "dependencies": {
+    "lodash": "4.17.20"
+  }
+}"#;
+    service
+        .call(create_did_open_notification(
+            "file:///test/package.json",
+            package_json,
+        ))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert!(params.diagnostics.is_empty());
+
+    // 6. didSave: diagnostics are published from the cache, then again once
+    // the forced refresh has fetched the new release
+    service
+        .call(create_did_save_notification("file:///test/package.json"))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification on save");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert!(params.diagnostics.is_empty());
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected diagnostics to be republished after refresh");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.version, Some(1));
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on npm: 4.17.20 -> 4.17.21"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn hover_links_changelog_of_package_repository() {
+    let (_temp_dir, cache) =
+        create_test_cache(RegistryType::Npm, &[("lodash", vec!["4.17.20", "4.17.21"])]);
+    cache
+        .save_metadata(
+            RegistryType::Npm,
+            "lodash",
+            &PackageMetadata {
+                homepage: Some("https://lodash.com/".to_string()),
+                repository: Some("https://github.com/lodash/lodash".to_string()),
+            },
+        )
+        .unwrap();
+
+    let registry =
+        MockRegistry::new(RegistryType::Npm).with_versions("lodash", vec!["4.17.20", "4.17.21"]);
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    let package_json = r#"{
+  "dependencies": {
+    "lodash": "4.17.21"
+  }
+}"#;
+    service
+        .call(create_did_open_notification(
+            "file:///test/package.json",
+            package_json,
+        ))
+        .await
+        .unwrap();
+    wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+        .await
+        .expect("Expected publishDiagnostics notification");
+
+    let response = service
+        .call(create_hover_request(2, "file:///test/package.json", 2, 16))
+        .await
+        .unwrap()
+        .expect("Expected hover response");
+    let hover: Option<Hover> = serde_json::from_value(response.result().unwrap().clone()).unwrap();
+
+    let HoverContents::Markup(contents) = hover.expect("Expected hover").contents else {
+        panic!("Expected markup hover contents");
+    };
+    assert!(
+        contents.value.starts_with(
+            "**lodash** 4.17.21 → latest: 4.17.21\n\n\
+             Up to date · [View on npm](https://www.npmjs.com/package/lodash) · \
+             [Changelog](https://github.com/lodash/lodash/releases)"
+        ),
+        "unexpected hover: {}",
+        contents.value
+    );
+}
@@ -9,13 +9,15 @@ use tower_lsp::LspService;
 use tower_lsp::lsp_types::*;
 
 use helper::{
-    MockRegistry, create_did_open_notification, create_initialize_request,
-    create_initialized_notification, create_test_cache, create_test_resolver,
-    spawn_notification_collector, wait_for_notification,
+    MockRegistry, create_definition_request, create_did_open_notification,
+    create_initialize_request, create_initialize_request_with_root,
+    create_initialized_notification, create_references_request, create_test_cache,
+    create_test_resolver, spawn_notification_collector, wait_for_notification,
 };
 use version_lsp::lsp::backend::Backend;
 use version_lsp::lsp::resolver::PackageResolver;
 use version_lsp::parser::types::RegistryType;
+use version_lsp::version::checker::VersionStorer;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn publishes_outdated_version_warning_for_single_catalog() {
@@ -75,7 +77,7 @@ async fn publishes_outdated_version_warning_for_single_catalog() {
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: 4.17.20 -> 4.17.21"
+        "Update available on pnpm Catalog: 4.17.20 -> 4.17.21"
     );
 }
 
@@ -138,7 +140,7 @@ async fn publishes_outdated_version_warning_for_named_catalogs() {
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: 18.2.0 -> 18.3.1"
+        "Update available on pnpm Catalog: 18.2.0 -> 18.3.1"
     );
 }
 
@@ -195,6 +197,58 @@ async fn no_diagnostics_for_latest_version() {
     assert!(params.diagnostics.is_empty());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn no_diagnostics_for_npm_alias_with_latest_dist_tag() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::PnpmCatalog,
+        &[("@voidzero-dev/vite-plus-core", vec!["0.1.0", "0.2.0"])],
+    );
+    cache
+        .save_dist_tags(
+            RegistryType::PnpmCatalog,
+            "@voidzero-dev/vite-plus-core",
+            &HashMap::from([("latest".to_string(), "0.2.0".to_string())]),
+        )
+        .unwrap();
+
+    let registry = MockRegistry::new(RegistryType::PnpmCatalog)
+        .with_versions("@voidzero-dev/vite-plus-core", vec!["0.1.0", "0.2.0"]);
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::PnpmCatalog,
+        create_test_resolver(RegistryType::PnpmCatalog, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // The entry aliases the real package, and `latest` is its dist tag
+    let pnpm_workspace = r#"catalog:
+  vite: npm:@voidzero-dev/vite-plus-core@latest
+"#;
+    service
+        .call(create_did_open_notification(
+            "file:///test/pnpm-workspace.yaml",
+            pnpm_workspace,
+        ))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert!(params.diagnostics.is_empty());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn publishes_error_for_nonexistent_version() {
     // 1. Setup real Cache with test data (oldest first, newest last)
@@ -255,3 +309,251 @@ async fn publishes_error_for_nonexistent_version() {
         "Version 999.0.0 not found in registry"
     );
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn prefetches_dependencies_of_workspace_members_on_startup() {
+    // 1. Setup empty Cache
+    let (_temp_dir, cache) = create_test_cache(RegistryType::Npm, &[]);
+
+    // 2. Setup mock Registry and resolver
+    let registry = MockRegistry::new(RegistryType::Npm)
+        .with_versions("lodash", vec!["4.17.20", "4.17.21"])
+        .with_versions("react", vec!["18.2.0"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Npm,
+        create_test_resolver(RegistryType::Npm, registry),
+    )]);
+
+    // 3. Write a pnpm workspace with two members
+    let workspace = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        workspace.path().join("pnpm-workspace.yaml"),
+        "packages:\n  - 'packages/*'\n",
+    )
+    .unwrap();
+    for (member, dependency) in [("core", "lodash"), ("web", "react")] {
+        let member_dir = workspace.path().join("packages").join(member);
+        std::fs::create_dir_all(&member_dir).unwrap();
+        std::fs::write(
+            member_dir.join("package.json"),
+            format!(r#"{{ "dependencies": {{ "{dependency}": "^1.0.0" }} }}"#),
+        )
+        .unwrap();
+    }
+    let root_uri = Url::from_directory_path(workspace.path()).unwrap();
+
+    // 4. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let _notification_rx = spawn_notification_collector(socket);
+
+    // 5. Initialize with the workspace root, without opening any document
+    service
+        .call(create_initialize_request_with_root(1, root_uri))
+        .await
+        .unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 6. Member dependencies are fetched in the background
+    let cached = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            let lodash = cache
+                .get_versions(RegistryType::Npm, "lodash", false)
+                .unwrap();
+            let react = cache
+                .get_versions(RegistryType::Npm, "react", false)
+                .unwrap();
+            if !lodash.is_empty() && !react.is_empty() {
+                return (lodash, react);
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("Expected workspace member dependencies to be cached");
+
+    assert_eq!(
+        cached,
+        (
+            vec!["4.17.20".to_string(), "4.17.21".to_string()],
+            vec!["18.2.0".to_string()]
+        )
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn catalog_references_in_package_json_are_not_reported_as_invalid() {
+    // 1. Setup real Cache with test data (oldest first, newest last)
+    let (_temp_dir, cache) =
+        create_test_cache(RegistryType::Npm, &[("lodash", vec!["4.17.20", "4.17.21"])]);
+    cache
+        .replace_versions(
+            RegistryType::PnpmCatalog,
+            "ag-grid-community",
+            vec!["31.0.0".to_string(), "32.0.0".to_string()],
+            &[],
+        )
+        .unwrap();
+
+    // 2. Setup mock Registries and resolvers
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([
+        (
+            RegistryType::Npm,
+            create_test_resolver(
+                RegistryType::Npm,
+                MockRegistry::new(RegistryType::Npm)
+                    .with_versions("lodash", vec!["4.17.20", "4.17.21"]),
+            ),
+        ),
+        (
+            RegistryType::PnpmCatalog,
+            create_test_resolver(
+                RegistryType::PnpmCatalog,
+                MockRegistry::new(RegistryType::PnpmCatalog)
+                    .with_versions("ag-grid-community", vec!["31.0.0", "32.0.0"]),
+            ),
+        ),
+    ]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen with catalog references next to an outdated npm version
+    let package_json = r#"{
+  "dependencies": {
+    "ag-grid-community": "catalog:ag-grid",
+    "react": "catalog:",
+    "lodash": "4.17.20"
+  }
+}
+"#;
+
+    service
+        .call(create_did_open_notification(
+            "file:///test/package.json",
+            package_json,
+        ))
+        .await
+        .unwrap();
+
+    // 6. Only the npm version is reported; catalog references take their
+    // version from pnpm-workspace.yaml, where the catalog entry is checked
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on npm: 4.17.20 -> 4.17.21"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn navigates_between_catalog_references_and_catalog_entries() {
+    // 1. Setup empty Cache; navigation doesn't need versions
+    let (_temp_dir, cache) = create_test_cache(RegistryType::Npm, &[]);
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([
+        (
+            RegistryType::Npm,
+            create_test_resolver(RegistryType::Npm, MockRegistry::new(RegistryType::Npm)),
+        ),
+        (
+            RegistryType::PnpmCatalog,
+            create_test_resolver(
+                RegistryType::PnpmCatalog,
+                MockRegistry::new(RegistryType::PnpmCatalog),
+            ),
+        ),
+    ]);
+
+    // 2. Write a pnpm workspace whose member uses a named catalog
+    let workspace = tempfile::TempDir::new().unwrap();
+    let workspace_yaml =
+        "packages:\n  - 'apps/*'\ncatalogs:\n  ag-grid:\n    ag-grid-community: ^31.0.0\n";
+    let workspace_file = workspace.path().join("pnpm-workspace.yaml");
+    std::fs::write(&workspace_file, workspace_yaml).unwrap();
+    let member_dir = workspace.path().join("apps").join("web");
+    std::fs::create_dir_all(&member_dir).unwrap();
+    let package_json =
+        "{\n  \"dependencies\": {\n    \"ag-grid-community\": \"catalog:ag-grid\"\n  }\n}\n";
+    let manifest = member_dir.join("package.json");
+    std::fs::write(&manifest, package_json).unwrap();
+    let workspace_uri = Url::from_file_path(&workspace_file).unwrap();
+    let manifest_uri = Url::from_file_path(&manifest).unwrap();
+
+    // 3. Create LspService and open both documents
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+    let _notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+    for (uri, content) in [
+        (&manifest_uri, package_json),
+        (&workspace_uri, workspace_yaml),
+    ] {
+        service
+            .call(create_did_open_notification(uri.as_str(), content))
+            .await
+            .unwrap();
+    }
+
+    let entry_location = Location {
+        uri: workspace_uri.clone(),
+        range: Range::new(Position::new(4, 23), Position::new(4, 30)),
+    };
+    let reference_location = Location {
+        uri: manifest_uri.clone(),
+        range: Range::new(Position::new(2, 26), Position::new(2, 41)),
+    };
+
+    // 4. Definition jumps from the catalog: reference to the catalog entry
+    let response = service
+        .call(create_definition_request(2, manifest_uri.as_str(), 2, 30))
+        .await
+        .unwrap()
+        .expect("Expected definition response");
+    let definition: Option<GotoDefinitionResponse> =
+        serde_json::from_value(response.result().unwrap().clone()).unwrap();
+    assert_eq!(
+        definition,
+        Some(GotoDefinitionResponse::Scalar(entry_location.clone()))
+    );
+
+    // 5. References lists the package.json using the catalog entry
+    let response = service
+        .call(create_references_request(
+            3,
+            workspace_uri.as_str(),
+            4,
+            25,
+            true,
+        ))
+        .await
+        .unwrap()
+        .expect("Expected references response");
+    let references: Option<Vec<Location>> =
+        serde_json::from_value(response.result().unwrap().clone()).unwrap();
+    assert_eq!(references, Some(vec![entry_location, reference_location]));
+}
@@ -0,0 +1,93 @@
+//! pub.dev (pubspec.yaml) E2E tests
+
+mod helper;
+
+use std::collections::HashMap;
+
+use tower::Service;
+use tower_lsp::LspService;
+use tower_lsp::lsp_types::*;
+
+use helper::{
+    MockRegistry, create_did_open_notification, create_initialize_request,
+    create_initialized_notification, create_test_cache, create_test_resolver,
+    spawn_notification_collector, wait_for_notification,
+};
+use version_lsp::lsp::backend::Backend;
+use version_lsp::lsp::resolver::PackageResolver;
+use version_lsp::parser::types::RegistryType;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_outdated_version_warning() {
+    // 1. Setup real Cache with test data (oldest first, newest last)
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::Pub,
+        &[
+            ("http", vec!["0.13.6", "1.1.0", "1.2.2"]),
+            ("provider", vec!["6.0.5", "6.1.2"]),
+        ],
+    );
+
+    // 2. Setup mock Registry and resolver
+    let registry = MockRegistry::new(RegistryType::Pub)
+        .with_versions("http", vec!["0.13.6", "1.1.0", "1.2.2"])
+        .with_versions("provider", vec!["6.0.5", "6.1.2"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::Pub,
+        create_test_resolver(RegistryType::Pub, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen: ^0.13.0 means >=0.13.0 <0.14.0, so 1.2.2 is outside the
+    // range; the flutter SDK dependency is not checked
+    let pubspec_yaml = r#"name: app
+dependencies:
+  flutter:
+    sdk: flutter
+  http: ^0.13.0
+  provider: ^6.0.0
+"#;
+
+    service
+        .call(create_did_open_notification(
+            "file:///test/pubspec.yaml",
+            pubspec_yaml,
+        ))
+        .await
+        .unwrap();
+
+    // 6. Receive publishDiagnostics notification
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].severity,
+        Some(DiagnosticSeverity::WARNING)
+    );
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on pub.dev: ^0.13.0 -> 1.2.2"
+    );
+    assert_eq!(
+        params.diagnostics[0].range,
+        Range::new(Position::new(4, 8), Position::new(4, 15))
+    );
+}
@@ -1,4 +1,4 @@
-//! PyPI (pyproject.toml) E2E tests
+//! PyPI (pyproject.toml, requirements.txt) E2E tests
 
 mod helper;
 
@@ -82,7 +82,7 @@ dependencies = [
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: ~=2.28.0 -> 2.32.0"
+        "Update available on PyPI: ~=2.28.0 -> 2.32.0"
     );
 }
 
@@ -271,6 +271,66 @@ build-backend = "setuptools.build_meta"
     );
     assert_eq!(
         params.diagnostics[0].message,
-        "Update available: >=61.0, <62.0 -> 70.0.0"
+        "Update available on PyPI: >=61.0, <62.0 -> 70.0.0"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn requirements_txt_publishes_outdated_version_warning() {
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::PyPI,
+        &[
+            ("requests", vec!["2.27.0", "2.28.0", "2.32.0"]),
+            ("flask", vec!["2.0.0", "3.0.0"]),
+        ],
+    );
+
+    let registry = MockRegistry::new(RegistryType::PyPI)
+        .with_versions("requests", vec!["2.27.0", "2.28.0", "2.32.0"])
+        .with_versions("flask", vec!["2.0.0", "3.0.0"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::PyPI,
+        create_test_resolver(RegistryType::PyPI, registry),
+    )]);
+
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // flask>=2.0 includes 3.0.0; the editable install and -r line are skipped
+    let requirements_txt =
+        "-r base.txt\n-e .\nrequests~=2.28.0\nFlask[async]>=2.0 ; python_version >= '3.8'\n";
+
+    service
+        .call(create_did_open_notification(
+            "file:///test/requirements-dev.txt",
+            requirements_txt,
+        ))
+        .await
+        .unwrap();
+
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].range,
+        Range::new(Position::new(2, 8), Position::new(2, 16))
+    );
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on PyPI: ~=2.28.0 -> 2.32.0"
     );
 }
@@ -0,0 +1,93 @@
+//! Rust toolchain (rust-toolchain.toml) E2E tests
+
+mod helper;
+
+use std::collections::HashMap;
+
+use tower::Service;
+use tower_lsp::LspService;
+use tower_lsp::lsp_types::*;
+
+use helper::{
+    MockRegistry, create_did_open_notification, create_initialize_request,
+    create_initialized_notification, create_test_cache, create_test_resolver,
+    spawn_notification_collector, wait_for_notification,
+};
+use version_lsp::lsp::backend::Backend;
+use version_lsp::lsp::resolver::PackageResolver;
+use version_lsp::parser::types::RegistryType;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publishes_stale_nightly_information() {
+    // 1. Setup real Cache with the current nightly and stable release
+    let (_temp_dir, cache) = create_test_cache(
+        RegistryType::RustToolchain,
+        &[("rust", vec!["nightly-2024-09-20", "1.81.0"])],
+    );
+
+    // 2. Setup mock Registry and resolver
+    let registry = MockRegistry::new(RegistryType::RustToolchain)
+        .with_versions("rust", vec!["nightly-2024-09-20", "1.81.0"]);
+
+    let resolvers: HashMap<RegistryType, PackageResolver> = HashMap::from([(
+        RegistryType::RustToolchain,
+        create_test_resolver(RegistryType::RustToolchain, registry),
+    )]);
+
+    // 3. Create LspService
+    let (mut service, socket) =
+        LspService::build(|client| Backend::build(client, cache.clone(), resolvers)).finish();
+
+    let mut notification_rx = spawn_notification_collector(socket);
+
+    // 4. Initialize
+    service.call(create_initialize_request(1)).await.unwrap();
+    service
+        .call(create_initialized_notification())
+        .await
+        .unwrap();
+
+    // 5. didOpen a rust-toolchain.toml pinned to a nightly older than a week
+    service
+        .call(create_did_open_notification(
+            "file:///test/rust-toolchain.toml",
+            r#"[toolchain]
+channel = "nightly-2024-01-15"
+components = ["rust-src", "clippy"]
+targets = ["wasm32-unknown-unknown"]
+"#,
+        ))
+        .await
+        .unwrap();
+
+    // 6. Receive publishDiagnostics notification
+    let notification =
+        wait_for_notification(&mut notification_rx, "textDocument/publishDiagnostics")
+            .await
+            .expect("Expected publishDiagnostics notification");
+
+    let params: PublishDiagnosticsParams =
+        serde_json::from_value(notification.params().unwrap().clone()).unwrap();
+    assert_eq!(params.diagnostics.len(), 1);
+    assert_eq!(
+        params.diagnostics[0].message,
+        "Update available on Rust: nightly-2024-01-15 -> nightly-2024-09-20"
+    );
+    assert_eq!(
+        params.diagnostics[0].severity,
+        Some(DiagnosticSeverity::INFORMATION)
+    );
+    assert_eq!(
+        params.diagnostics[0].range,
+        Range {
+            start: Position {
+                line: 1,
+                character: 11
+            },
+            end: Position {
+                line: 1,
+                character: 29
+            },
+        }
+    );
+}
@@ -1,12 +1,13 @@
 //! LSP request/notification test utilities
 
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use futures::StreamExt;
+use futures::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
 use tokio::time::timeout;
 use tower_lsp::ClientSocket;
-use tower_lsp::jsonrpc::Request;
+use tower_lsp::jsonrpc::{Request, Response};
 use tower_lsp::lsp_types::*;
 
 /// Create an LSP initialize request
@@ -17,6 +18,24 @@ pub fn create_initialize_request(id: i64) -> Request {
         .finish()
 }
 
+/// Create an LSP initialize request for a workspace folder
+#[allow(dead_code)]
+pub fn create_initialize_request_with_root(id: i64, root_uri: Url) -> Request {
+    Request::build("initialize")
+        .id(id)
+        .params(
+            serde_json::to_value(InitializeParams {
+                workspace_folders: Some(vec![WorkspaceFolder {
+                    uri: root_uri,
+                    name: "workspace".to_string(),
+                }]),
+                ..Default::default()
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
 /// Create an LSP initialized notification
 pub fn create_initialized_notification() -> Request {
     Request::build("initialized")
@@ -62,6 +81,22 @@ pub fn create_did_change_notification(uri: &str, content: &str, version: i32) ->
         .finish()
 }
 
+/// Create an LSP didSave notification
+#[allow(dead_code)]
+pub fn create_did_save_notification(uri: &str) -> Request {
+    Request::build("textDocument/didSave")
+        .params(
+            serde_json::to_value(DidSaveTextDocumentParams {
+                text_document: TextDocumentIdentifier {
+                    uri: uri.parse().unwrap(),
+                },
+                text: None,
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
 /// Collect notifications in background and return a receiver
 pub fn spawn_notification_collector(mut socket: ClientSocket) -> mpsc::Receiver<Request> {
     let (tx, rx) = mpsc::channel(100);
@@ -77,13 +112,64 @@ pub fn spawn_notification_collector(mut socket: ClientSocket) -> mpsc::Receiver<
     rx
 }
 
+/// Collect notifications in background like [`spawn_notification_collector`],
+/// answering `workspace/configuration` requests with the current `settings`
+#[allow(dead_code)]
+pub fn spawn_notification_collector_with_settings(
+    socket: ClientSocket,
+    settings: Arc<Mutex<serde_json::Value>>,
+) -> mpsc::Receiver<Request> {
+    let (tx, rx) = mpsc::channel(100);
+    let (mut stream, mut sink) = socket.split();
+
+    tokio::spawn(async move {
+        while let Some(request) = stream.next().await {
+            if request.method() == "workspace/configuration" {
+                let id = request.id().cloned().unwrap();
+                let value = settings.lock().unwrap().clone();
+                let response = Response::from_ok(id, serde_json::json!([value]));
+                if sink.send(response).await.is_err() {
+                    break;
+                }
+                continue;
+            }
+            if tx.send(request).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    rx
+}
+
+/// Create an LSP workspace/didChangeConfiguration notification
+#[allow(dead_code)]
+pub fn create_did_change_configuration_notification() -> Request {
+    Request::build("workspace/didChangeConfiguration")
+        .params(
+            serde_json::to_value(DidChangeConfigurationParams {
+                settings: serde_json::Value::Null,
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
 /// Wait for a notification with the specified method name from the receiver
 pub async fn wait_for_notification(
     rx: &mut mpsc::Receiver<Request>,
     method: &str,
 ) -> Option<Request> {
-    let timeout_duration = Duration::from_secs(5);
+    wait_for_notification_within(rx, method, Duration::from_secs(5)).await
+}
 
+/// Wait for a notification with the specified method name, giving up when
+/// none arrives within `timeout_duration`
+pub async fn wait_for_notification_within(
+    rx: &mut mpsc::Receiver<Request>,
+    method: &str,
+    timeout_duration: Duration,
+) -> Option<Request> {
     loop {
         match timeout(timeout_duration, rx.recv()).await {
             Ok(Some(notification)) => {
@@ -100,6 +186,259 @@ pub async fn wait_for_notification(
 /// Create an LSP codeAction request
 #[allow(dead_code)]
 pub fn create_code_action_request(id: i64, uri: &str, line: u32, character: u32) -> Request {
+    create_code_action_range_request(
+        id,
+        uri,
+        Range {
+            start: Position { line, character },
+            end: Position { line, character },
+        },
+    )
+}
+
+/// Create an LSP linkedEditingRange request
+#[allow(dead_code)]
+pub fn create_linked_editing_range_request(
+    id: i64,
+    uri: &str,
+    line: u32,
+    character: u32,
+) -> Request {
+    Request::build("textDocument/linkedEditingRange")
+        .id(id)
+        .params(
+            serde_json::to_value(LinkedEditingRangeParams {
+                text_document_position_params: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: uri.parse().unwrap(),
+                    },
+                    position: Position { line, character },
+                },
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
+/// Create an LSP textDocument/hover request
+#[allow(dead_code)]
+pub fn create_hover_request(id: i64, uri: &str, line: u32, character: u32) -> Request {
+    Request::build("textDocument/hover")
+        .id(id)
+        .params(
+            serde_json::to_value(HoverParams {
+                text_document_position_params: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: uri.parse().unwrap(),
+                    },
+                    position: Position { line, character },
+                },
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
+/// Create an LSP textDocument/completion request
+#[allow(dead_code)]
+pub fn create_completion_request(id: i64, uri: &str, line: u32, character: u32) -> Request {
+    Request::build("textDocument/completion")
+        .id(id)
+        .params(
+            serde_json::to_value(CompletionParams {
+                text_document_position: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: uri.parse().unwrap(),
+                    },
+                    position: Position { line, character },
+                },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+                context: None,
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
+/// Create an LSP textDocument/inlayHint request for `range`
+#[allow(dead_code)]
+pub fn create_inlay_hint_request(id: i64, uri: &str, range: Range) -> Request {
+    Request::build("textDocument/inlayHint")
+        .id(id)
+        .params(
+            serde_json::to_value(InlayHintParams {
+                text_document: TextDocumentIdentifier {
+                    uri: uri.parse().unwrap(),
+                },
+                range,
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
+/// Create an LSP workspace/symbol request
+#[allow(dead_code)]
+pub fn create_workspace_symbol_request(id: i64, query: &str) -> Request {
+    Request::build("workspace/symbol")
+        .id(id)
+        .params(
+            serde_json::to_value(WorkspaceSymbolParams {
+                query: query.to_string(),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
+/// Create an LSP textDocument/foldingRange request
+#[allow(dead_code)]
+pub fn create_folding_range_request(id: i64, uri: &str) -> Request {
+    Request::build("textDocument/foldingRange")
+        .id(id)
+        .params(
+            serde_json::to_value(FoldingRangeParams {
+                text_document: TextDocumentIdentifier {
+                    uri: uri.parse().unwrap(),
+                },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
+/// Create an LSP textDocument/definition request
+#[allow(dead_code)]
+pub fn create_definition_request(id: i64, uri: &str, line: u32, character: u32) -> Request {
+    Request::build("textDocument/definition")
+        .id(id)
+        .params(
+            serde_json::to_value(GotoDefinitionParams {
+                text_document_position_params: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: uri.parse().unwrap(),
+                    },
+                    position: Position { line, character },
+                },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
+/// Create an LSP textDocument/references request
+#[allow(dead_code)]
+pub fn create_references_request(
+    id: i64,
+    uri: &str,
+    line: u32,
+    character: u32,
+    include_declaration: bool,
+) -> Request {
+    Request::build("textDocument/references")
+        .id(id)
+        .params(
+            serde_json::to_value(ReferenceParams {
+                text_document_position: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: uri.parse().unwrap(),
+                    },
+                    position: Position { line, character },
+                },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+                context: ReferenceContext {
+                    include_declaration,
+                },
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
+/// Create an LSP textDocument/prepareRename request
+#[allow(dead_code)]
+pub fn create_prepare_rename_request(id: i64, uri: &str, line: u32, character: u32) -> Request {
+    Request::build("textDocument/prepareRename")
+        .id(id)
+        .params(
+            serde_json::to_value(TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: uri.parse().unwrap(),
+                },
+                position: Position { line, character },
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
+/// Create an LSP textDocument/rename request
+#[allow(dead_code)]
+pub fn create_rename_request(
+    id: i64,
+    uri: &str,
+    line: u32,
+    character: u32,
+    new_name: &str,
+) -> Request {
+    Request::build("textDocument/rename")
+        .id(id)
+        .params(
+            serde_json::to_value(RenameParams {
+                text_document_position: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: uri.parse().unwrap(),
+                    },
+                    position: Position { line, character },
+                },
+                new_name: new_name.to_string(),
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
+/// Create an LSP workspace/executeCommand request
+#[allow(dead_code)]
+pub fn create_execute_command_request(id: i64, command: &str) -> Request {
+    create_execute_command_request_with_arguments(id, command, Vec::new())
+}
+
+/// Create an LSP workspace/executeCommand request with arguments
+#[allow(dead_code)]
+pub fn create_execute_command_request_with_arguments(
+    id: i64,
+    command: &str,
+    arguments: Vec<serde_json::Value>,
+) -> Request {
+    Request::build("workspace/executeCommand")
+        .id(id)
+        .params(
+            serde_json::to_value(ExecuteCommandParams {
+                command: command.to_string(),
+                arguments,
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap(),
+        )
+        .finish()
+}
+
+/// Create an LSP codeAction request for a selection
+#[allow(dead_code)]
+pub fn create_code_action_range_request(id: i64, uri: &str, range: Range) -> Request {
     Request::build("textDocument/codeAction")
         .id(id)
         .params(
@@ -107,10 +446,7 @@ pub fn create_code_action_request(id: i64, uri: &str, line: u32, character: u32)
                 text_document: TextDocumentIdentifier {
                     uri: uri.parse().unwrap(),
                 },
-                range: Range {
-                    start: Position { line, character },
-                    end: Position { line, character },
-                },
+                range,
                 context: CodeActionContext {
                     diagnostics: vec![],
                     only: None,
@@ -123,3 +459,27 @@ pub fn create_code_action_request(id: i64, uri: &str, line: u32, character: u32)
         )
         .finish()
 }
+
+/// Create an LSP textDocument/codeAction request for `source` actions only
+#[allow(dead_code)]
+pub fn create_source_code_action_request(id: i64, uri: &str) -> Request {
+    Request::build("textDocument/codeAction")
+        .id(id)
+        .params(
+            serde_json::to_value(CodeActionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: uri.parse().unwrap(),
+                },
+                range: Range::default(),
+                context: CodeActionContext {
+                    diagnostics: vec![],
+                    only: Some(vec![CodeActionKind::SOURCE]),
+                    trigger_kind: None,
+                },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap(),
+        )
+        .finish()
+}
@@ -2,26 +2,41 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use tempfile::TempDir;
 
 use version_lsp::lsp::resolver::PackageResolver;
+use version_lsp::parser::cargo_lock::CargoLockParser;
 use version_lsp::parser::cargo_toml::CargoTomlParser;
 use version_lsp::parser::compose::ComposeParser;
+use version_lsp::parser::conda_env::CondaEnvParser;
+use version_lsp::parser::conda_lock::CondaLockParser;
 use version_lsp::parser::deno_json::DenoJsonParser;
+use version_lsp::parser::dockerfile::DockerfileParser;
+use version_lsp::parser::gemfile::GemfileParser;
 use version_lsp::parser::github_actions::GitHubActionsParser;
 use version_lsp::parser::go_mod::GoModParser;
+use version_lsp::parser::go_work::GoWorkParser;
+use version_lsp::parser::nvmrc::NvmrcParser;
 use version_lsp::parser::package_json::PackageJsonParser;
 use version_lsp::parser::pnpm_workspace::PnpmWorkspaceParser;
+use version_lsp::parser::pubspec_yaml::PubspecYamlParser;
 use version_lsp::parser::pyproject_toml::PyprojectTomlParser;
-use version_lsp::parser::types::RegistryType;
+use version_lsp::parser::requirements_txt::RequirementsTxtParser;
+use version_lsp::parser::rust_toolchain::RustToolchainParser;
+use version_lsp::parser::types::{
+    RegistryType, is_dockerfile, is_go_work_file, is_requirements_file,
+};
 use version_lsp::version::cache::Cache;
 use version_lsp::version::checker::VersionStorer;
 use version_lsp::version::error::RegistryError;
 use version_lsp::version::matchers::{
-    CratesVersionMatcher, DockerVersionMatcher, GitHubActionsMatcher, GoVersionMatcher,
-    JsrVersionMatcher, NpmVersionMatcher, PnpmCatalogMatcher, PypiVersionMatcher,
+    CondaVersionMatcher, CratesVersionMatcher, DockerVersionMatcher, GitHubActionsMatcher,
+    GoToolchainMatcher, GoVersionMatcher, JsrVersionMatcher, NodeVersionMatcher, NpmVersionMatcher,
+    PnpmCatalogMatcher, PubVersionMatcher, PypiVersionMatcher, RubyGemsVersionMatcher,
+    RustToolchainMatcher,
 };
 use version_lsp::version::registries::github::GitHubRegistry;
 use version_lsp::version::registry::Registry;
@@ -31,6 +46,7 @@ use version_lsp::version::types::PackageVersions;
 pub struct MockRegistry {
     registry_type: RegistryType,
     versions: HashMap<String, Vec<String>>,
+    delay: Duration,
 }
 
 impl MockRegistry {
@@ -38,9 +54,17 @@ impl MockRegistry {
         Self {
             registry_type,
             versions: HashMap::new(),
+            delay: Duration::ZERO,
         }
     }
 
+    /// Delay every fetch, to simulate a slow registry
+    #[allow(dead_code)]
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
     pub fn with_versions(mut self, package: &str, versions: Vec<&str>) -> Self {
         self.versions.insert(
             package.to_string(),
@@ -60,8 +84,9 @@ impl Registry for MockRegistry {
         &self,
         package_name: &str,
     ) -> Result<PackageVersions, RegistryError> {
+        tokio::time::sleep(self.delay).await;
         match self.versions.get(package_name) {
-            Some(versions) => Ok(PackageVersions::new(versions.clone())),
+            Some(versions) => Ok(PackageVersions::sorted_by_semver(versions.clone())),
             None => Err(RegistryError::NotFound(package_name.to_string())),
         }
     }
@@ -90,12 +115,14 @@ pub fn create_test_resolver(
             Arc::new(CargoTomlParser::new()),
             Arc::new(CratesVersionMatcher),
             Arc::new(mock_registry),
-        ),
+        )
+        .with_lock_parser("Cargo.lock", Arc::new(CargoLockParser::new())),
         RegistryType::GoProxy => PackageResolver::new(
             Arc::new(GoModParser::new()),
             Arc::new(GoVersionMatcher),
             Arc::new(mock_registry),
-        ),
+        )
+        .with_file_parser(is_go_work_file, Arc::new(GoWorkParser::new())),
         RegistryType::PnpmCatalog => PackageResolver::new(
             Arc::new(PnpmWorkspaceParser),
             Arc::new(PnpmCatalogMatcher),
@@ -110,11 +137,44 @@ pub fn create_test_resolver(
             Arc::new(PyprojectTomlParser::new()),
             Arc::new(PypiVersionMatcher),
             Arc::new(mock_registry),
-        ),
+        )
+        .with_file_parser(is_requirements_file, Arc::new(RequirementsTxtParser::new())),
         RegistryType::Docker => PackageResolver::new(
             Arc::new(ComposeParser::new()),
             Arc::new(DockerVersionMatcher),
             Arc::new(mock_registry),
+        )
+        .with_file_parser(is_dockerfile, Arc::new(DockerfileParser::new())),
+        RegistryType::Conda => PackageResolver::new(
+            Arc::new(CondaEnvParser::new()),
+            Arc::new(CondaVersionMatcher),
+            Arc::new(mock_registry),
+        )
+        .with_lock_parser("conda-lock.yml", Arc::new(CondaLockParser::new())),
+        RegistryType::NodeRelease => PackageResolver::new(
+            Arc::new(NvmrcParser::new()),
+            Arc::new(NodeVersionMatcher),
+            Arc::new(mock_registry),
+        ),
+        RegistryType::GoToolchain => PackageResolver::new(
+            Arc::new(GoModParser::new()),
+            Arc::new(GoToolchainMatcher),
+            Arc::new(mock_registry),
+        ),
+        RegistryType::RustToolchain => PackageResolver::new(
+            Arc::new(RustToolchainParser::new()),
+            Arc::new(RustToolchainMatcher),
+            Arc::new(mock_registry),
+        ),
+        RegistryType::RubyGems => PackageResolver::new(
+            Arc::new(GemfileParser::new()),
+            Arc::new(RubyGemsVersionMatcher),
+            Arc::new(mock_registry),
+        ),
+        RegistryType::Pub => PackageResolver::new(
+            Arc::new(PubspecYamlParser::new()),
+            Arc::new(PubVersionMatcher),
+            Arc::new(mock_registry),
         ),
     }
 }
@@ -134,6 +194,7 @@ pub fn create_test_cache(
                 registry_type,
                 package_name,
                 package_versions.iter().map(|v| v.to_string()).collect(),
+                &[],
             )
             .unwrap();
     }
@@ -0,0 +1,277 @@
+//! HTTP-level registry tests
+//!
+//! Each registry is pointed at a `mockito` server serving responses captured
+//! from the real APIs (`tests/fixtures/registry`), so response parsing and
+//! error handling are tested without network access.
+
+use rstest::rstest;
+
+use version_lsp::version::error::RegistryError;
+use version_lsp::version::registries::crates_io::CratesIoRegistry;
+use version_lsp::version::registries::github::{GitHubRegistry, TagShaFetcher};
+use version_lsp::version::registries::go_proxy::GoProxyRegistry;
+use version_lsp::version::registries::npm::NpmRegistry;
+use version_lsp::version::registry::Registry;
+
+const NPM_PACKAGE: &str = include_str!("fixtures/registry/npm_is_odd.json");
+const CRATES_IO_CRATE: &str = include_str!("fixtures/registry/crates_io_itoa.json");
+const GO_PROXY_LIST: &str = include_str!("fixtures/registry/go_proxy_x_text.txt");
+const GITHUB_RELEASES: &str = include_str!("fixtures/registry/github_releases_checkout.json");
+const GITHUB_TAGS: &str = include_str!("fixtures/registry/github_tags_checkout.json");
+
+/// Builds a registry talking to the mock server at the given URL
+type RegistryFactory = fn(&str) -> Box<dyn Registry>;
+
+fn npm(url: &str) -> Box<dyn Registry> {
+    Box::new(NpmRegistry::new(url))
+}
+
+fn crates_io(url: &str) -> Box<dyn Registry> {
+    Box::new(CratesIoRegistry::new(url))
+}
+
+fn go_proxy(url: &str) -> Box<dyn Registry> {
+    Box::new(GoProxyRegistry::new(url))
+}
+
+fn github(url: &str) -> Box<dyn Registry> {
+    Box::new(GitHubRegistry::new(url))
+}
+
+#[tokio::test]
+async fn npm_registry_parses_package_document() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/is-odd")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(NPM_PACKAGE)
+        .create_async()
+        .await;
+
+    let result = NpmRegistry::new(&server.url())
+        .fetch_all_versions("is-odd")
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(result.versions, vec!["2.0.0", "3.0.0", "3.0.1"]);
+    assert_eq!(
+        result.dist_tags.get("latest").map(String::as_str),
+        Some("3.0.1")
+    );
+    assert_eq!(
+        result.repository.as_deref(),
+        Some("https://github.com/jonschlinkert/is-odd")
+    );
+}
+
+#[tokio::test]
+async fn crates_io_registry_parses_crate_document() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/itoa")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(CRATES_IO_CRATE)
+        .create_async()
+        .await;
+
+    let result = CratesIoRegistry::new(&server.url())
+        .fetch_all_versions("itoa")
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    // Yanked 1.0.8 is excluded; the rest are ordered by publish date
+    assert_eq!(result.versions, vec!["1.0.6", "1.0.9"]);
+}
+
+#[tokio::test]
+async fn go_proxy_registry_parses_version_list() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/golang.org/x/text/@v/list")
+        .with_status(200)
+        .with_header("content-type", "text/plain; charset=UTF-8")
+        .with_body(GO_PROXY_LIST)
+        .create_async()
+        .await;
+
+    let result = GoProxyRegistry::new(&server.url())
+        .fetch_all_versions("golang.org/x/text")
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(
+        result.versions,
+        vec!["v0.3.0", "v0.3.8", "v0.13.0", "v0.14.0", "v0.15.0-rc.1"]
+    );
+}
+
+#[tokio::test]
+async fn github_registry_parses_releases() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/repos/actions/checkout/releases")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(GITHUB_RELEASES)
+        .create_async()
+        .await;
+
+    let result = GitHubRegistry::new(&server.url())
+        .fetch_all_versions("actions/checkout")
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(result.versions, vec!["v3.5.3", "v4.0.0", "v4.1.7"]);
+}
+
+#[tokio::test]
+async fn github_registry_finds_tag_sha() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/repos/actions/checkout/tags")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "per_page".to_string(),
+            "100".to_string(),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(GITHUB_TAGS)
+        .create_async()
+        .await;
+
+    let sha = GitHubRegistry::new(&server.url())
+        .fetch_tag_sha("actions/checkout", "v4.1.7")
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(sha, "692973e3d937129bcbf40652eb9f2f61becf3332");
+}
+
+#[rstest]
+#[case::npm(npm as RegistryFactory, "is-odd", "/is-odd")]
+#[case::crates_io(crates_io as RegistryFactory, "itoa", "/itoa")]
+#[case::go_proxy(go_proxy as RegistryFactory, "golang.org/x/text", "/golang.org/x/text/@v/list")]
+#[case::github(github as RegistryFactory, "actions/checkout", "/repos/actions/checkout/releases")]
+#[tokio::test]
+async fn registry_returns_not_found_for_404(
+    #[case] registry: RegistryFactory,
+    #[case] package_name: &str,
+    #[case] path: &str,
+) {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", path)
+        .with_status(404)
+        .with_body("Not Found")
+        .create_async()
+        .await;
+
+    let result = registry(&server.url())
+        .fetch_all_versions(package_name)
+        .await;
+
+    mock.assert_async().await;
+    assert!(
+        matches!(result, Err(RegistryError::NotFound(ref name)) if name == package_name),
+        "unexpected result: {:?}",
+        result
+    );
+}
+
+#[rstest]
+#[case::npm(npm as RegistryFactory, "is-odd", "/is-odd")]
+#[case::crates_io(crates_io as RegistryFactory, "itoa", "/itoa")]
+#[case::github(github as RegistryFactory, "actions/checkout", "/repos/actions/checkout/releases")]
+#[tokio::test]
+async fn registry_returns_invalid_response_for_malformed_json(
+    #[case] registry: RegistryFactory,
+    #[case] package_name: &str,
+    #[case] path: &str,
+) {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", path)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"versions": ["#)
+        .create_async()
+        .await;
+
+    let result = registry(&server.url())
+        .fetch_all_versions(package_name)
+        .await;
+
+    mock.assert_async().await;
+    assert!(
+        matches!(result, Err(RegistryError::InvalidResponse(_))),
+        "unexpected result: {:?}",
+        result
+    );
+}
+
+#[tokio::test]
+async fn go_proxy_registry_returns_invalid_response_for_server_error() {
+    // The Go proxy list is plain text, so a broken proxy shows up as an
+    // error status rather than an unparsable body
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/golang.org/x/text/@v/list")
+        .with_status(502)
+        .with_body("<html>Bad Gateway</html>")
+        .create_async()
+        .await;
+
+    let result = GoProxyRegistry::new(&server.url())
+        .fetch_all_versions("golang.org/x/text")
+        .await;
+
+    mock.assert_async().await;
+    assert!(
+        matches!(result, Err(RegistryError::InvalidResponse(_))),
+        "unexpected result: {:?}",
+        result
+    );
+}
+
+#[rstest]
+#[case::npm(npm as RegistryFactory, "is-odd", "/is-odd")]
+#[case::crates_io(crates_io as RegistryFactory, "itoa", "/itoa")]
+#[case::go_proxy(go_proxy as RegistryFactory, "golang.org/x/text", "/golang.org/x/text/@v/list")]
+#[case::github(github as RegistryFactory, "actions/checkout", "/repos/actions/checkout/releases")]
+#[tokio::test]
+async fn registry_returns_rate_limited_for_429(
+    #[case] registry: RegistryFactory,
+    #[case] package_name: &str,
+    #[case] path: &str,
+) {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", path)
+        .with_status(429)
+        .with_header("retry-after", "42")
+        .create_async()
+        .await;
+
+    let result = registry(&server.url())
+        .fetch_all_versions(package_name)
+        .await;
+
+    mock.assert_async().await;
+    assert!(
+        matches!(
+            result,
+            Err(RegistryError::RateLimited {
+                retry_after_secs: Some(42)
+            })
+        ),
+        "unexpected result: {:?}",
+        result
+    );
+}